@@ -3,24 +3,32 @@ use std::fs;
 use std::path::Path;
 use std::time::Instant;
 
-use log::{debug, trace};
-use plonky2::field::types::PrimeField64;
+use anyhow::{anyhow, Context, Result};
+use log::{debug, error, trace};
+use plonky2::field::types::{Field, PrimeField64};
 use plonky2::iop::witness::PartialWitness;
 use plonky2::plonk::circuit_data::CircuitData;
 use plonky2::plonk::config::{AlgebraicHasher, GenericConfig, GenericHashOut};
 use plonky2::plonk::proof::ProofWithPublicInputs;
 use plonky2::plonk::prover::prove_with_partition_witness;
-use plonky2::util::serialization::{Buffer, GateSerializer, IoResult, Read, Write};
+use plonky2::util::serialization::{Buffer, GateSerializer, IoError, IoResult, Read, Write};
 use plonky2::util::timing::TimingTree;
 
 use super::config::PlonkParameters;
 use super::input::PublicInput;
 use super::output::PublicOutput;
+use super::report::WitnessReport;
 use super::serialization::hints::HintSerializer;
-use super::serialization::{GateRegistry, HintRegistry};
-use super::witness::{generate_witness, generate_witness_async};
+use super::serialization::{GateRegistry, HintRegistry, CIRCUIT_FORMAT_VERSION};
+use super::witness::{
+    generate_partial_witness_with_tape, generate_witness_async_with_tape,
+    generate_witness_with_tape,
+};
 use crate::frontend::builder::CircuitIO;
 use crate::frontend::hint::asynchronous::generator::AsyncHintDataRef;
+use crate::frontend::hint::asynchronous::tape::HintTape;
+use crate::frontend::vars::{CircuitVariable, Variable};
+use crate::utils::hash::sha256;
 use crate::utils::hex;
 use crate::utils::serde::{BufferRead, BufferWrite};
 
@@ -32,6 +40,7 @@ pub struct CircuitBuild<L: PlonkParameters<D>, const D: usize> {
     pub data: CircuitData<L::Field, L::Config, D>,
     pub io: CircuitIO<D>,
     pub async_hints: BTreeMap<usize, AsyncHintDataRef<L, D>>,
+    pub named_variables: Vec<(String, Vec<Variable>)>,
 }
 
 impl<L: PlonkParameters<D>, const D: usize> CircuitBuild<L, D> {
@@ -40,6 +49,22 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuild<L, D> {
         PublicInput::new(&self.io)
     }
 
+    /// Returns the public inputs of a circuit that reads its whole input with
+    /// `CircuitBuilder::read_input`, from the value of the variable it reads.
+    pub fn typed_input<V: CircuitVariable>(
+        &self,
+        value: V::ValueType<L::Field>,
+    ) -> PublicInput<L, D> {
+        assert_eq!(
+            V::nb_elements(),
+            self.io.input().len(),
+            "the typed input does not match the input of the circuit"
+        );
+        let mut input = self.input();
+        input.write::<V>(value);
+        input
+    }
+
     /// Generates a proof for the circuit using a plonky2 partial witness. The proof can be verified
     /// using `verify`.
     pub fn prove_with_partial_witness(
@@ -49,22 +74,43 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuild<L, D> {
         ProofWithPublicInputs<L::Field, L::Config, D>,
         PublicOutput<L, D>,
     )
+    where
+        <<L as PlonkParameters<D>>::Config as GenericConfig<D>>::Hasher:
+            AlgebraicHasher<<L as PlonkParameters<D>>::Field>,
+    {
+        self.prove_with_partial_witness_and_tape(pw, None)
+    }
+
+    fn prove_with_partial_witness_and_tape(
+        &self,
+        pw: PartialWitness<L::Field>,
+        tape: Option<HintTape>,
+    ) -> (
+        ProofWithPublicInputs<L::Field, L::Config, D>,
+        PublicOutput<L, D>,
+    )
     where
         <<L as PlonkParameters<D>>::Config as GenericConfig<D>>::Hasher:
             AlgebraicHasher<<L as PlonkParameters<D>>::Field>,
     {
         let start_time = Instant::now();
         trace!("generating witness...");
-        let partition_witness = generate_witness(
+        let partition_witness = generate_witness_with_tape(
             pw,
             &self.data.prover_only,
             &self.data.common,
             &self.async_hints,
+            tape,
         )
         .unwrap();
         let elapsed_time = start_time.elapsed();
         debug!("Witness generation took {:?}", elapsed_time);
         trace!("finished generating witness");
+        // With hashed io, only the digests are public so the output is read from the witness.
+        let hashed_output = self
+            .io
+            .is_hashed()
+            .then(|| PublicOutput::from_witness(&self.io, &partition_witness));
         trace!("generating proof...");
         let proof_with_pis = prove_with_partition_witness::<L::Field, L::Config, D>(
             &self.data.prover_only,
@@ -74,7 +120,8 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuild<L, D> {
         )
         .unwrap();
         trace!("finished generating proof");
-        let output = PublicOutput::from_proof_with_pis(&self.io, &proof_with_pis);
+        let output = hashed_output
+            .unwrap_or_else(|| PublicOutput::from_proof_with_pis(&self.io, &proof_with_pis));
         let elapsed_time = start_time.elapsed();
         debug!("proving took: {:?}", elapsed_time);
         (proof_with_pis, output)
@@ -97,6 +144,100 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuild<L, D> {
         self.prove_with_partial_witness(pw)
     }
 
+    /// Generates a proof for the circuit, recording the asynchronous hints to `tape` or replaying
+    /// them from it. The proof can be verified using `verify`.
+    pub fn prove_with_tape(
+        &self,
+        input: &PublicInput<L, D>,
+        tape: &HintTape,
+    ) -> (
+        ProofWithPublicInputs<L::Field, L::Config, D>,
+        PublicOutput<L, D>,
+    )
+    where
+        <<L as PlonkParameters<D>>::Config as GenericConfig<D>>::Hasher:
+            AlgebraicHasher<<L as PlonkParameters<D>>::Field>,
+    {
+        let mut pw = PartialWitness::new();
+        self.io.set_witness(&mut pw, input);
+        self.prove_with_partial_witness_and_tape(pw, Some(tape.clone()))
+    }
+
+    /// Generates the witness of the circuit for `input`, and reports the values of the variables
+    /// named with `CircuitBuilder::name_variable` and the first constraint which is not satisfied
+    /// by the witness.
+    ///
+    /// If witness generation fails, the report is built from the values generated before the
+    /// failure and records the error.
+    pub fn check_witness(&self, input: &PublicInput<L, D>) -> Result<WitnessReport> {
+        self.check_witness_with_tape(input, None)
+    }
+
+    fn check_witness_with_tape(
+        &self,
+        input: &PublicInput<L, D>,
+        tape: Option<HintTape>,
+    ) -> Result<WitnessReport> {
+        let mut pw = PartialWitness::new();
+        self.io.set_witness(&mut pw, input);
+        let (witness, result) = generate_partial_witness_with_tape(
+            pw,
+            &self.data.prover_only,
+            &self.data.common,
+            &self.async_hints,
+            tape,
+        );
+        Ok(WitnessReport::new::<L, D>(
+            &self.data.prover_only,
+            &self.data.common,
+            &witness,
+            &self.named_variables,
+            result.err(),
+        ))
+    }
+
+    /// Generates a proof for the circuit like `prove`, after checking the witness with
+    /// `check_witness`. If witness generation fails or the witness does not satisfy the
+    /// constraints, the report is saved as JSON to `report_path` before panicking.
+    ///
+    /// The asynchronous hints are only run once, as they are replayed when proving.
+    pub fn prove_with_report(
+        &self,
+        input: &PublicInput<L, D>,
+        report_path: &Path,
+    ) -> (
+        ProofWithPublicInputs<L::Field, L::Config, D>,
+        PublicOutput<L, D>,
+    )
+    where
+        <<L as PlonkParameters<D>>::Config as GenericConfig<D>>::Hasher:
+            AlgebraicHasher<<L as PlonkParameters<D>>::Field>,
+    {
+        let tape = HintTape::record();
+        let report = self
+            .check_witness_with_tape(input, Some(tape.clone()))
+            .unwrap();
+        if let Some(error) = &report.error {
+            report.save(report_path).unwrap();
+            panic!(
+                "witness generation failed: {}, see the report {}",
+                error,
+                report_path.display()
+            );
+        }
+        if let Some(constraint) = &report.unsatisfied_constraint {
+            report.save(report_path).unwrap();
+            panic!(
+                "constraint {} of gate {} at row {} is not satisfied, see the report {}",
+                constraint.constraint,
+                constraint.gate,
+                constraint.row,
+                report_path.display()
+            );
+        }
+        self.prove_with_tape(input, &HintTape::replay(tape.records()))
+    }
+
     /// Generates a proof for the circuit using a plonky2 partial witness. The proof can be verified
     /// using `verify`.
     pub async fn prove_with_partial_witness_async(
@@ -106,23 +247,44 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuild<L, D> {
         ProofWithPublicInputs<L::Field, L::Config, D>,
         PublicOutput<L, D>,
     )
+    where
+        <<L as PlonkParameters<D>>::Config as GenericConfig<D>>::Hasher:
+            AlgebraicHasher<<L as PlonkParameters<D>>::Field>,
+    {
+        self.prove_with_partial_witness_async_and_tape(pw, None)
+            .await
+    }
+
+    async fn prove_with_partial_witness_async_and_tape(
+        &self,
+        pw: PartialWitness<L::Field>,
+        tape: Option<HintTape>,
+    ) -> (
+        ProofWithPublicInputs<L::Field, L::Config, D>,
+        PublicOutput<L, D>,
+    )
     where
         <<L as PlonkParameters<D>>::Config as GenericConfig<D>>::Hasher:
             AlgebraicHasher<<L as PlonkParameters<D>>::Field>,
     {
         let start_time = tokio::time::Instant::now();
         trace!("generating witness...");
-        let partition_witness = generate_witness_async(
+        let partition_witness = generate_witness_async_with_tape(
             pw,
             &self.data.prover_only,
             &self.data.common,
             &self.async_hints,
+            tape,
         )
         .await
         .unwrap();
         let elapsed_time = start_time.elapsed();
         debug!("Witness generation took {:?}", elapsed_time);
         trace!("finished generating witness");
+        let hashed_output = self
+            .io
+            .is_hashed()
+            .then(|| PublicOutput::from_witness(&self.io, &partition_witness));
         trace!("generating proof...");
         tokio::task::block_in_place(|| {
             let proof_with_pis = prove_with_partition_witness::<L::Field, L::Config, D>(
@@ -132,7 +294,8 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuild<L, D> {
                 &mut TimingTree::default(),
             )
             .unwrap();
-            let output = PublicOutput::from_proof_with_pis(&self.io, &proof_with_pis);
+            let output = hashed_output
+                .unwrap_or_else(|| PublicOutput::from_proof_with_pis(&self.io, &proof_with_pis));
             let elapsed_time = start_time.elapsed();
             debug!("proving took: {:?}", elapsed_time);
             (proof_with_pis, output)
@@ -156,6 +319,25 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuild<L, D> {
         self.prove_with_partial_witness_async(pw).await
     }
 
+    /// An asynchronous version of `prove_with_tape`.
+    pub async fn prove_async_with_tape(
+        &self,
+        input: &PublicInput<L, D>,
+        tape: &HintTape,
+    ) -> (
+        ProofWithPublicInputs<L::Field, L::Config, D>,
+        PublicOutput<L, D>,
+    )
+    where
+        <<L as PlonkParameters<D>>::Config as GenericConfig<D>>::Hasher:
+            AlgebraicHasher<<L as PlonkParameters<D>>::Field>,
+    {
+        let mut pw = PartialWitness::new();
+        self.io.set_witness(&mut pw, input);
+        self.prove_with_partial_witness_async_and_tape(pw, Some(tape.clone()))
+            .await
+    }
+
     /// Verifies a proof for the circuit.
     pub fn verify(
         &self,
@@ -163,6 +345,19 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuild<L, D> {
         input: &PublicInput<L, D>,
         output: &PublicOutput<L, D>,
     ) {
+        if self.io.is_hashed() {
+            let (PublicInput::Bytes(input), PublicOutput::Bytes(output)) = (input, output) else {
+                panic!("hashed io expects bytes input and output");
+            };
+            let expected_pis = sha256(input)
+                .into_iter()
+                .chain(sha256(output))
+                .map(L::Field::from_canonical_u8)
+                .collect::<Vec<_>>();
+            assert_eq!(proof.public_inputs, expected_pis);
+            self.data.verify(proof.clone()).unwrap();
+            return;
+        }
         let expected_input = PublicInput::<L, D>::from_proof_with_pis(&self.io, proof);
         let expected_output = PublicOutput::<L, D>::from_proof_with_pis(&self.io, proof);
         assert_eq!(input, &expected_input);
@@ -190,6 +385,7 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuild<L, D> {
         hint_serializer: &impl HintSerializer<L, D>,
     ) -> IoResult<Vec<u8>> {
         let mut buffer = Vec::new();
+        buffer.write_u8(CIRCUIT_FORMAT_VERSION)?;
 
         let data = self.data.to_bytes(gate_serializer, hint_serializer)?;
         buffer.write_bytes(&data)?;
@@ -205,6 +401,9 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuild<L, D> {
             hint_serializer.write_async_hint(&mut buffer, hint_data, &self.data.common)?;
         }
 
+        let named_variables = bincode::serialize(&self.named_variables).unwrap();
+        buffer.write_bytes(&named_variables)?;
+
         Ok(buffer)
     }
 
@@ -216,6 +415,15 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuild<L, D> {
     ) -> IoResult<Self> {
         let mut buffer = Buffer::new(buffer);
 
+        let version = buffer.read_u8()?;
+        if version != CIRCUIT_FORMAT_VERSION {
+            error!(
+                "the circuit has format version {}, expected {}",
+                version, CIRCUIT_FORMAT_VERSION
+            );
+            return Err(IoError);
+        }
+
         let data = buffer.read_bytes()?;
         let data = CircuitData::<L::Field, L::Config, D>::from_bytes(
             &data,
@@ -224,7 +432,10 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuild<L, D> {
         )?;
 
         let io = buffer.read_bytes()?;
-        let io: CircuitIO<D> = bincode::deserialize(&io).unwrap();
+        let io: CircuitIO<D> = bincode::deserialize(&io).map_err(|e| {
+            error!("failed to deserialize the circuit io: {}", e);
+            IoError
+        })?;
 
         let mut async_hints = BTreeMap::new();
         let map_size = buffer.read_usize()?;
@@ -234,38 +445,53 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuild<L, D> {
             async_hints.insert(key, hint_data);
         }
 
+        // The named variables are part of the format since `CIRCUIT_FORMAT_VERSION` 1, which is
+        // checked above.
+        let named_variables = buffer.read_bytes()?;
+        let named_variables = bincode::deserialize(&named_variables).map_err(|e| {
+            error!("failed to deserialize the named variables: {}", e);
+            IoError
+        })?;
+
         Ok(CircuitBuild {
             data,
             io,
             async_hints,
+            named_variables,
         })
     }
 
     /// Saves the circuit to a file.
-    pub fn save(
+    pub fn save<P: AsRef<Path>>(
         &self,
-        path: &String,
+        path: P,
         gate_serializer: &impl GateSerializer<L::Field, D>,
         hint_serializer: &impl HintSerializer<L, D>,
-    ) {
-        let path = Path::new(path);
+    ) -> Result<()> {
+        let path = path.as_ref();
         if let Some(parent_dir) = path.parent() {
             if !parent_dir.exists() {
-                fs::create_dir_all(parent_dir).unwrap();
+                fs::create_dir_all(parent_dir)?;
             }
         }
-        let bytes = self.serialize(gate_serializer, hint_serializer).unwrap();
-        fs::write(path, bytes).unwrap();
+        let bytes = self
+            .serialize(gate_serializer, hint_serializer)
+            .map_err(|_| anyhow!("failed to serialize circuit {}", path.display()))?;
+        fs::write(path, bytes)
+            .with_context(|| format!("failed to write circuit {}", path.display()))
     }
 
     /// Loads the circuit from a file.
-    pub fn load(
-        path: &str,
+    pub fn load<P: AsRef<Path>>(
+        path: P,
         gate_serializer: &impl GateSerializer<L::Field, D>,
         hint_serializer: &impl HintSerializer<L, D>,
-    ) -> IoResult<Self> {
-        let bytes = fs::read(path).unwrap();
+    ) -> Result<Self> {
+        let path = path.as_ref();
+        let bytes =
+            fs::read(path).with_context(|| format!("failed to read circuit {}", path.display()))?;
         Self::deserialize(bytes.as_slice(), gate_serializer, hint_serializer)
+            .map_err(|_| anyhow!("failed to deserialize circuit {}", path.display()))
     }
 
     /// Tests that the circuit can be serialized/deserialzie given the default serializers.
@@ -298,7 +524,7 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuild<L, D> {
 #[cfg(test)]
 pub(crate) mod tests {
 
-    use crate::backend::circuit::CircuitBuild;
+    use crate::backend::circuit::{CircuitBuild, CIRCUIT_FORMAT_VERSION};
     use crate::prelude::*;
 
     type L = DefaultParameters;
@@ -358,6 +584,26 @@ pub(crate) mod tests {
         }
     }
 
+    #[test]
+    fn test_deserialize_rejects_other_format_version() {
+        let mut builder = DefaultBuilder::new();
+        let a = builder.read::<Variable>();
+        builder.write(a);
+        let circuit = builder.build();
+
+        let gate_serializer = GateRegistry::<L, D>::new();
+        let hint_serializer = HintRegistry::<L, D>::new();
+        let mut bytes = circuit
+            .serialize(&gate_serializer, &hint_serializer)
+            .unwrap();
+        assert_eq!(bytes[0], CIRCUIT_FORMAT_VERSION);
+
+        bytes[0] = CIRCUIT_FORMAT_VERSION + 1;
+        assert!(
+            CircuitBuild::<L, D>::deserialize(&bytes, &gate_serializer, &hint_serializer).is_err()
+        );
+    }
+
     #[test]
     fn test_serialize_with_evm_io() {
         // Define your circuit.
@@ -1,6 +1,8 @@
+use anyhow::{bail, ensure, Context, Result};
 use itertools::Itertools;
 use plonky2::plonk::circuit_data::VerifierCircuitData;
 use plonky2::plonk::proof::ProofWithPublicInputs;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 
 use super::PlonkParameters;
@@ -41,12 +43,54 @@ impl<L: PlonkParameters<D>, const D: usize> PublicInput<L, D> {
         }
     }
 
+    /// Creates a public input instance from the JSON of a value of `V`, which usually is a struct
+    /// with all the inputs of the circuit. This method only works if the circuit is using field
+    /// element-based IO.
+    ///
+    /// The value is in the serde format of its value type, so that for instance the values of
+    /// `Bytes32Variable` and `U256Variable` are hex strings and the values of `U64Variable` are
+    /// decimal numbers.
+    pub fn from_json<V: CircuitVariable>(io: &CircuitIO<D>, json: &str) -> Result<Self>
+    where
+        V::ValueType<L::Field>: DeserializeOwned,
+    {
+        let mut input = Self::new(io);
+        input.write_json::<V>(json)?;
+        Ok(input)
+    }
+
+    /// Returns the JSON of the public input as a value of `V`, which is the inverse of `from_json`.
+    /// This method only works if the circuit is using field element-based IO, and `V` must hold
+    /// all the field elements of the input.
+    pub fn to_json<V: CircuitVariable>(&self) -> Result<String>
+    where
+        V::ValueType<L::Field>: Serialize,
+    {
+        let elements = match self {
+            PublicInput::Elements(input) => input,
+            PublicInput::RecursiveProofs(_, input) => input,
+            PublicInput::CyclicProof(input, _, _) => input,
+            _ => bail!("field io is not enabled"),
+        };
+        ensure!(
+            elements.len() == V::nb_elements(),
+            "the input has {} field elements, but the value has {}",
+            elements.len(),
+            V::nb_elements()
+        );
+        let value = V::from_elements::<L::Field>(elements);
+        serde_json::to_string(&value).context("failed to serialize the input value")
+    }
+
     /// Create a public input instance with data from the proof with public inputs.
     pub fn from_proof_with_pis(
         io: &CircuitIO<D>,
         proof_with_pis: &ProofWithPublicInputs<L::Field, L::Config, D>,
     ) -> Self {
         match io {
+            CircuitIO::Bytes(io) if io.hashed => {
+                panic!("hashed io only exposes the sha256 digest of the input")
+            }
             CircuitIO::Bytes(io) => {
                 let offset = ByteVariable::nb_elements() * io.input.len();
                 let elements = proof_with_pis.public_inputs[..offset].to_vec();
@@ -87,6 +131,17 @@ impl<L: PlonkParameters<D>, const D: usize> PublicInput<L, D> {
         };
     }
 
+    /// Writes a value of `V` parsed from JSON to the public circuit input using field-based
+    /// serialization, as in `from_json`.
+    pub fn write_json<V: CircuitVariable>(&mut self, json: &str) -> Result<()>
+    where
+        V::ValueType<L::Field>: DeserializeOwned,
+    {
+        let value = serde_json::from_str(json).context("failed to parse the input value")?;
+        self.write::<V>(value);
+        Ok(())
+    }
+
     /// Writes a slice of field elements to the public circuit input.
     pub fn write_all(&mut self, value: &[L::Field]) {
         match self {
@@ -112,6 +167,17 @@ impl<L: PlonkParameters<D>, const D: usize> PublicInput<L, D> {
         };
     }
 
+    /// Writes a value of `V` parsed from JSON to the public circuit input using byte-based
+    /// serialization, as in `from_json`.
+    pub fn evm_write_json<V: EvmVariable>(&mut self, json: &str) -> Result<()>
+    where
+        V::ValueType<L::Field>: DeserializeOwned,
+    {
+        let value = serde_json::from_str(json).context("failed to parse the input value")?;
+        self.evm_write::<V>(value);
+        Ok(())
+    }
+
     /// Writes a stream of bytes to the public circuit input. Assumes that the bytes can be
     /// properly deserialized.
     pub fn evm_write_all(&mut self, bytes: &[u8]) {
@@ -163,3 +229,41 @@ impl<L: PlonkParameters<D>, const D: usize> PublicInput<L, D> {
         todo!()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use ethers::types::H256;
+
+    use super::*;
+    use crate::prelude::{Bytes32Variable, DefaultBuilder, DefaultParameters, U64Variable};
+
+    #[test]
+    fn test_public_input_from_json() {
+        let mut builder = DefaultBuilder::new();
+        let (a, b) = builder.read::<(U64Variable, Bytes32Variable)>();
+        builder.write(a);
+        builder.write(b);
+        let circuit = builder.mock_build();
+
+        let json = r#"[7, "0x0101010101010101010101010101010101010101010101010101010101010101"]"#;
+        let mut input = PublicInput::<DefaultParameters, 2>::from_json::<(
+            U64Variable,
+            Bytes32Variable,
+        )>(&circuit.io, json)
+        .unwrap();
+        let (_witness, mut output) = circuit.mock_prove(&input);
+        assert_eq!(output.read::<U64Variable>(), 7);
+        assert_eq!(
+            output.read_json::<Bytes32Variable>().unwrap(),
+            format!("\"{:?}\"", H256::repeat_byte(1))
+        );
+
+        assert_eq!(
+            input.to_json::<(U64Variable, Bytes32Variable)>().unwrap(),
+            format!("[7,\"{:?}\"]", H256::repeat_byte(1))
+        );
+        assert!(input.to_json::<U64Variable>().is_err());
+
+        assert!(input.write_json::<U64Variable>("\"seven\"").is_err());
+    }
+}
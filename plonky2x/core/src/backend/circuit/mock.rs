@@ -7,10 +7,11 @@ use plonky2::plonk::config::{AlgebraicHasher, GenericConfig};
 
 use super::input::PublicInput;
 use super::output::PublicOutput;
-use super::witness::generate_witness;
+use super::witness::generate_witness_with_tape;
 use super::PlonkParameters;
 use crate::frontend::builder::CircuitIO;
 use crate::frontend::hint::asynchronous::generator::AsyncHintDataRef;
+use crate::frontend::hint::asynchronous::tape::HintTape;
 
 /// A mock circuit that can be used for testing.
 ///
@@ -39,6 +40,28 @@ impl<L: PlonkParameters<D>, const D: usize> MockCircuitBuild<L, D> {
         <<L as PlonkParameters<D>>::Config as GenericConfig<D>>::Hasher:
             AlgebraicHasher<<L as PlonkParameters<D>>::Field>,
     {
+        self.mock_prove_inner(input, None)
+    }
+
+    /// Generates a mock proof, recording the asynchronous hints to `tape` or replaying them from
+    /// it.
+    pub fn mock_prove_with_tape(
+        &self,
+        input: &PublicInput<L, D>,
+        tape: &HintTape,
+    ) -> (PartitionWitness<L::Field>, PublicOutput<L, D>)
+    where
+        <<L as PlonkParameters<D>>::Config as GenericConfig<D>>::Hasher:
+            AlgebraicHasher<<L as PlonkParameters<D>>::Field>,
+    {
+        self.mock_prove_inner(input, Some(tape.clone()))
+    }
+
+    fn mock_prove_inner(
+        &self,
+        input: &PublicInput<L, D>,
+        tape: Option<HintTape>,
+    ) -> (PartitionWitness<L::Field>, PublicOutput<L, D>) {
         // Initialize the witness.
         let mut pw = PartialWitness::new();
 
@@ -46,11 +69,12 @@ impl<L: PlonkParameters<D>, const D: usize> MockCircuitBuild<L, D> {
         self.io.set_witness(&mut pw, input);
 
         // Generate the rest of witness.
-        let witness = generate_witness(
+        let witness = generate_witness_with_tape(
             pw,
             &self.data.prover_only,
             &self.data.common,
             &self.async_hints,
+            tape,
         )
         .unwrap();
 
@@ -3,11 +3,16 @@ pub mod config;
 mod input;
 mod mock;
 mod output;
+mod proof;
+mod report;
 mod serialization;
 mod witness;
 
 use core::fmt::Debug;
+use std::path::Path;
 
+use anyhow::{ensure, Result};
+use log::warn;
 use plonky2::plonk::config::{AlgebraicHasher, GenericConfig};
 
 pub use self::build::CircuitBuild;
@@ -15,10 +20,16 @@ pub use self::config::{DefaultParameters, Groth16WrapperParameters, PlonkParamet
 pub use self::input::PublicInput;
 pub use self::mock::MockCircuitBuild;
 pub use self::output::PublicOutput;
+pub use self::proof::{Proof, PROOF_FORMAT_VERSION};
+pub use self::report::{NamedValue, UnsatisfiedConstraint, WitnessReport};
 pub use self::serialization::{
     CircuitSerializer, DefaultSerializer, GateRegistry, HintRegistry, Serializer,
+    CIRCUIT_FORMAT_VERSION,
+};
+pub use self::witness::{
+    generate_witness, generate_witness_async, generate_witness_async_with_tape,
+    generate_witness_with_tape,
 };
-pub use self::witness::{generate_witness, generate_witness_async};
 use crate::prelude::CircuitBuilder;
 
 pub trait Circuit: Debug + Clone + Send + Sync + 'static {
@@ -44,6 +55,90 @@ pub trait Circuit: Debug + Clone + Send + Sync + 'static {
     {
     }
 
+    /// Saves a build of the circuit to `path`, serialized with the gates and generators of the
+    /// crate and the ones registered by the circuit.
+    fn save<L: PlonkParameters<D>, const D: usize, P: AsRef<Path>>(
+        circuit: &CircuitBuild<L, D>,
+        path: P,
+    ) -> Result<()>
+    where
+        <<L as PlonkParameters<D>>::Config as GenericConfig<D>>::Hasher: AlgebraicHasher<L::Field>,
+    {
+        let mut generator_registry = HintRegistry::<L, D>::new();
+        Self::register_generators(&mut generator_registry);
+        let mut gate_registry = GateRegistry::<L, D>::new();
+        Self::register_gates(&mut gate_registry);
+        circuit.save(path, &gate_registry, &generator_registry)
+    }
+
+    /// Loads a build of the circuit saved with `Circuit::save`.
+    fn load<L: PlonkParameters<D>, const D: usize, P: AsRef<Path>>(
+        path: P,
+    ) -> Result<CircuitBuild<L, D>>
+    where
+        <<L as PlonkParameters<D>>::Config as GenericConfig<D>>::Hasher: AlgebraicHasher<L::Field>,
+    {
+        let mut generator_registry = HintRegistry::<L, D>::new();
+        Self::register_generators(&mut generator_registry);
+        let mut gate_registry = GateRegistry::<L, D>::new();
+        Self::register_gates(&mut gate_registry);
+        CircuitBuild::load(path, &gate_registry, &generator_registry)
+    }
+
+    /// Loads a build of the circuit saved with `Circuit::save`, and checks that its id, which is
+    /// derived from the circuit digest, is `circuit_id`.
+    fn load_with_id<L: PlonkParameters<D>, const D: usize, P: AsRef<Path>>(
+        path: P,
+        circuit_id: &str,
+    ) -> Result<CircuitBuild<L, D>>
+    where
+        <<L as PlonkParameters<D>>::Config as GenericConfig<D>>::Hasher: AlgebraicHasher<L::Field>,
+    {
+        let path = path.as_ref();
+        let circuit = Self::load(path)?;
+        ensure!(
+            circuit.id() == circuit_id,
+            "circuit {} has id {}, expected {}",
+            path.display(),
+            circuit.id(),
+            circuit_id
+        );
+        Ok(circuit)
+    }
+
+    /// Loads the circuit with id `circuit_id` from `build_dir/{circuit_id}.circuit` if it was saved
+    /// there, and otherwise builds it and saves it there.
+    ///
+    /// A saved circuit is only returned if its id is `circuit_id`. If the circuit built from
+    /// `define` does not have this id, the definition has changed since `circuit_id` was computed,
+    /// and an error is returned.
+    fn build_or_load<L: PlonkParameters<D>, const D: usize, P: AsRef<Path>>(
+        build_dir: P,
+        circuit_id: &str,
+    ) -> Result<CircuitBuild<L, D>>
+    where
+        <<L as PlonkParameters<D>>::Config as GenericConfig<D>>::Hasher: AlgebraicHasher<L::Field>,
+    {
+        let path = build_dir.as_ref().join(format!("{}.circuit", circuit_id));
+        if path.exists() {
+            match Self::load_with_id(&path, circuit_id) {
+                Ok(circuit) => return Ok(circuit),
+                Err(e) => warn!("rebuilding circuit {}: {:#}", circuit_id, e),
+            }
+        }
+        let mut builder = CircuitBuilder::<L, D>::new();
+        Self::define(&mut builder);
+        let circuit = builder.build();
+        ensure!(
+            circuit.id() == circuit_id,
+            "the circuit was built with id {}, expected {}",
+            circuit.id(),
+            circuit_id
+        );
+        Self::save(&circuit, &path)?;
+        Ok(circuit)
+    }
+
     // Tests that the circuit can be serialized and deserialized.
     fn test_serialization<L: PlonkParameters<D>, const D: usize>()
     where
@@ -62,3 +157,64 @@ pub trait Circuit: Debug + Clone + Send + Sync + 'static {
         circuit.test_serializers(&gate_registry, &generator_registry);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs};
+
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::prelude::*;
+
+    #[derive(Debug, Clone)]
+    struct AddCircuit;
+
+    impl Circuit for AddCircuit {
+        fn define<L: PlonkParameters<D>, const D: usize>(builder: &mut CircuitBuilder<L, D>)
+        where
+            <<L as PlonkParameters<D>>::Config as GenericConfig<D>>::Hasher:
+                AlgebraicHasher<<L as PlonkParameters<D>>::Field>,
+        {
+            let a = builder.read::<U64Variable>();
+            let b = builder.read::<U64Variable>();
+            let c = builder.add(a, b);
+            builder.write(c);
+        }
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_circuit_save_and_load() {
+        let build_dir = env::temp_dir().join(format!("plonky2x_test_{}", Uuid::new_v4()));
+        let mut builder = DefaultBuilder::new();
+        AddCircuit::define(&mut builder);
+        let built = builder.build();
+        let circuit_id = built.id();
+
+        // The first call builds the circuit and saves it, the second one loads it.
+        let circuit =
+            AddCircuit::build_or_load::<DefaultParameters, 2, _>(&build_dir, &circuit_id).unwrap();
+        assert_eq!(circuit.id(), circuit_id);
+        let path = build_dir.join(format!("{}.circuit", circuit_id));
+        assert!(path.exists());
+        let circuit =
+            AddCircuit::build_or_load::<DefaultParameters, 2, _>(&build_dir, &circuit_id).unwrap();
+        assert_eq!(circuit.id(), circuit_id);
+
+        let mut input = circuit.input();
+        input.write::<U64Variable>(3);
+        input.write::<U64Variable>(4);
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+        assert_eq!(output.read::<U64Variable>(), 7);
+
+        // A saved circuit with another id is not returned.
+        assert!(AddCircuit::load_with_id::<DefaultParameters, 2, _>(&path, "another id").is_err());
+        assert!(
+            AddCircuit::build_or_load::<DefaultParameters, 2, _>(&build_dir, "another id").is_err()
+        );
+
+        fs::remove_dir_all(build_dir).unwrap();
+    }
+}
@@ -1,3 +1,4 @@
+use anyhow::{Context, Result};
 use itertools::Itertools;
 use plonky2::iop::witness::PartitionWitness;
 use plonky2::plonk::proof::ProofWithPublicInputs;
@@ -24,6 +25,9 @@ impl<L: PlonkParameters<D>, const D: usize> PublicOutput<L, D> {
         proof_with_pis: &ProofWithPublicInputs<L::Field, L::Config, D>,
     ) -> Self {
         match io {
+            CircuitIO::Bytes(io) if io.hashed => {
+                panic!("hashed io only exposes the sha256 digest of the output")
+            }
             CircuitIO::Bytes(io) => {
                 let offset = ByteVariable::nb_elements() * io.input.len();
                 let elements = proof_with_pis.public_inputs[offset..].to_vec();
@@ -79,6 +83,27 @@ impl<L: PlonkParameters<D>, const D: usize> PublicOutput<L, D> {
         }
     }
 
+    /// Reads the whole output of a circuit that writes it with `CircuitBuilder::write_output`, as
+    /// the value of the variable it writes.
+    pub fn read_output<V: CircuitVariable>(&mut self) -> V::ValueType<L::Field> {
+        assert_eq!(
+            V::nb_elements(),
+            self.read_all().len(),
+            "the typed output does not match the output of the circuit"
+        );
+        self.read::<V>()
+    }
+
+    /// Reads a value of `V` from the public circuit output using field-based serialization, and
+    /// returns it as JSON in the serde format of its value type.
+    pub fn read_json<V: CircuitVariable>(&mut self) -> Result<String>
+    where
+        V::ValueType<L::Field>: Serialize,
+    {
+        let value = self.read::<V>();
+        serde_json::to_string(&value).context("failed to serialize the output value")
+    }
+
     /// Reads the entire stream of field elements from the public circuit output.
     pub fn read_all(&self) -> Vec<L::Field> {
         match self {
@@ -116,6 +141,16 @@ impl<L: PlonkParameters<D>, const D: usize> PublicOutput<L, D> {
         }
     }
 
+    /// Reads a value of `V` from the public circuit output using byte-based serialization, and
+    /// returns it as JSON in the serde format of its value type.
+    pub fn evm_read_json<V: EvmVariable>(&mut self) -> Result<String>
+    where
+        V::ValueType<L::Field>: Serialize,
+    {
+        let value = self.evm_read::<V>();
+        serde_json::to_string(&value).context("failed to serialize the output value")
+    }
+
     /// Reads the entire stream of bytes from the public circuit output.
     pub fn evm_read_all(&self) -> Vec<u8> {
         match self {
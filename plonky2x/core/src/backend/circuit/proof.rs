@@ -0,0 +1,294 @@
+use anyhow::{anyhow, ensure, Context, Result};
+use plonky2::field::types::{Field, Field64, PrimeField64};
+use plonky2::plonk::proof::ProofWithPublicInputs;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use super::PlonkParameters;
+use crate::utils::serde::{deserialize_hex, serialize_hex};
+
+/// The version of the serialization format of proofs.
+pub const PROOF_FORMAT_VERSION: u8 = 1;
+
+/// A proof with its public inputs, in a versioned format that is used to transport proofs between
+/// processes, such as in the requests and results of functions.
+///
+/// The bytes of a proof are laid out as:
+/// - the format version, as one byte,
+/// - the number of public inputs, as a little-endian `u64`,
+/// - the public inputs, each as a canonical little-endian `u64`,
+/// - the bincode serialization of the plonky2 proof.
+///
+/// The version and the public inputs can be read without plonky2, but the plonky2 proof is only
+/// meant to be deserialized by this crate, as its bincode layout follows the plonky2 types.
+/// Verifying proofs in other languages is out of scope of this format.
+///
+/// The hex representation is the bytes prefixed with `0x`, and the JSON representation has the
+/// fields `version`, `publicInputs` (decimal strings), and `proof` (the hex of the plonky2 proof).
+/// Proofs written before the format was versioned, as the `0x`-prefixed hex of the bincode
+/// serialization of the plonky2 proof with its public inputs, are still accepted when
+/// deserializing from JSON.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Proof<L: PlonkParameters<D>, const D: usize> {
+    pub proof_with_pis: ProofWithPublicInputs<L::Field, L::Config, D>,
+}
+
+/// The JSON representation of a proof.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ProofJson {
+    version: u8,
+    public_inputs: Vec<String>,
+    #[serde(serialize_with = "serialize_hex")]
+    #[serde(deserialize_with = "deserialize_hex")]
+    proof: Vec<u8>,
+}
+
+/// The JSON representations of a proof accepted when deserializing, which are the versioned one
+/// and the hex string of the proofs written before the format was versioned.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+enum AnyProofJson {
+    Versioned(ProofJson),
+    Legacy(String),
+}
+
+impl<L: PlonkParameters<D>, const D: usize> Proof<L, D> {
+    pub fn new(proof_with_pis: ProofWithPublicInputs<L::Field, L::Config, D>) -> Self {
+        Self { proof_with_pis }
+    }
+
+    pub fn public_inputs(&self) -> &[L::Field] {
+        &self.proof_with_pis.public_inputs
+    }
+
+    /// Serializes the proof to bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let public_inputs = self.public_inputs();
+        let mut bytes = vec![PROOF_FORMAT_VERSION];
+        bytes.extend((public_inputs.len() as u64).to_le_bytes());
+        for input in public_inputs {
+            bytes.extend(input.to_canonical_u64().to_le_bytes());
+        }
+        bytes.extend(bincode::serialize(&self.proof_with_pis.proof).unwrap());
+        bytes
+    }
+
+    /// Deserializes a proof serialized with `Proof::to_bytes`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let (&version, bytes) = bytes.split_first().ok_or_else(|| anyhow!("empty proof"))?;
+        check_version(version)?;
+
+        let (num_public_inputs, mut bytes) = read_u64(bytes)?;
+        let mut public_inputs = Vec::new();
+        for _ in 0..num_public_inputs {
+            let (input, rest) = read_u64(bytes)?;
+            public_inputs.push(to_field::<L, D>(input)?);
+            bytes = rest;
+        }
+        let proof = bincode::deserialize(bytes).context("failed to deserialize proof")?;
+
+        Ok(Self::new(ProofWithPublicInputs {
+            proof,
+            public_inputs,
+        }))
+    }
+
+    /// Serializes the proof to a `0x`-prefixed hex string.
+    pub fn to_hex(&self) -> String {
+        format!("0x{}", hex::encode(self.to_bytes()))
+    }
+
+    /// Deserializes a proof serialized with `Proof::to_hex`.
+    pub fn from_hex(s: &str) -> Result<Self> {
+        let bytes = hex::decode(s.strip_prefix("0x").unwrap_or(s)).context("invalid proof hex")?;
+        Self::from_bytes(&bytes)
+    }
+
+    /// Serializes the proof to JSON.
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Deserializes a proof serialized with `Proof::to_json`.
+    pub fn from_json(s: &str) -> Result<Self> {
+        Ok(serde_json::from_str(s)?)
+    }
+
+    /// Deserializes a proof written before the format was versioned, which is the bincode
+    /// serialization of the plonky2 proof with its public inputs.
+    pub fn from_legacy_bytes(bytes: &[u8]) -> Result<Self> {
+        let proof_with_pis =
+            bincode::deserialize(bytes).context("failed to deserialize legacy proof")?;
+        Ok(Self::new(proof_with_pis))
+    }
+
+    fn to_json_repr(&self) -> ProofJson {
+        ProofJson {
+            version: PROOF_FORMAT_VERSION,
+            public_inputs: self
+                .public_inputs()
+                .iter()
+                .map(|input| input.to_canonical_u64().to_string())
+                .collect(),
+            proof: bincode::serialize(&self.proof_with_pis.proof).unwrap(),
+        }
+    }
+
+    fn from_json_repr(json: ProofJson) -> Result<Self> {
+        check_version(json.version)?;
+        let public_inputs = json
+            .public_inputs
+            .iter()
+            .map(|input| {
+                let input = input
+                    .parse::<u64>()
+                    .with_context(|| format!("invalid public input {}", input))?;
+                to_field::<L, D>(input)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let proof = bincode::deserialize(&json.proof).context("failed to deserialize proof")?;
+
+        Ok(Self::new(ProofWithPublicInputs {
+            proof,
+            public_inputs,
+        }))
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize> From<ProofWithPublicInputs<L::Field, L::Config, D>>
+    for Proof<L, D>
+{
+    fn from(proof_with_pis: ProofWithPublicInputs<L::Field, L::Config, D>) -> Self {
+        Self::new(proof_with_pis)
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize> Serialize for Proof<L, D> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_json_repr().serialize(serializer)
+    }
+}
+
+impl<'de, L: PlonkParameters<D>, const D: usize> Deserialize<'de> for Proof<L, D> {
+    fn deserialize<De: Deserializer<'de>>(deserializer: De) -> Result<Self, De::Error> {
+        let proof = match AnyProofJson::deserialize(deserializer)? {
+            AnyProofJson::Versioned(json) => Self::from_json_repr(json),
+            AnyProofJson::Legacy(s) => hex::decode(s.strip_prefix("0x").unwrap_or(&s))
+                .context("invalid proof hex")
+                .and_then(|bytes| Self::from_legacy_bytes(&bytes)),
+        };
+        proof.map_err(|e| serde::de::Error::custom(format!("{:#}", e)))
+    }
+}
+
+fn check_version(version: u8) -> Result<()> {
+    ensure!(
+        version == PROOF_FORMAT_VERSION,
+        "unsupported proof format version {}, expected {}",
+        version,
+        PROOF_FORMAT_VERSION
+    );
+    Ok(())
+}
+
+fn read_u64(bytes: &[u8]) -> Result<(u64, &[u8])> {
+    ensure!(bytes.len() >= 8, "proof is truncated");
+    let (value, rest) = bytes.split_at(8);
+    Ok((u64::from_le_bytes(value.try_into().unwrap()), rest))
+}
+
+fn to_field<L: PlonkParameters<D>, const D: usize>(value: u64) -> Result<L::Field> {
+    ensure!(
+        value < L::Field::ORDER,
+        "public input {} is not a canonical field element",
+        value
+    );
+    Ok(L::Field::from_canonical_u64(value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::circuit::PublicOutput;
+    use crate::backend::function::ProofResult;
+    use crate::prelude::*;
+
+    #[test]
+    fn test_proof_serialization_formats() {
+        let mut builder = DefaultBuilder::new();
+        let a = builder.read::<Variable>();
+        let b = builder.read::<Variable>();
+        let c = builder.add(a, b);
+        builder.write(c);
+        let circuit = builder.build();
+
+        let mut input = circuit.input();
+        input.write::<Variable>(GoldilocksField::TWO);
+        input.write::<Variable>(GoldilocksField::ONE);
+        let (proof, output) = circuit.prove(&input);
+        let proof = Proof::<DefaultParameters, 2>::new(proof);
+
+        let from_bytes = Proof::<DefaultParameters, 2>::from_bytes(&proof.to_bytes()).unwrap();
+        assert_eq!(from_bytes, proof);
+
+        let hex = proof.to_hex();
+        assert!(hex.starts_with("0x01"));
+        let from_hex = Proof::<DefaultParameters, 2>::from_hex(&hex).unwrap();
+        assert_eq!(from_hex, proof);
+
+        let json = proof.to_json().unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["version"], PROOF_FORMAT_VERSION);
+        assert_eq!(value["publicInputs"], serde_json::json!(["2", "1", "3"]));
+        let from_json = Proof::<DefaultParameters, 2>::from_json(&json).unwrap();
+        assert_eq!(from_json, proof);
+
+        circuit.verify(&from_json.proof_with_pis, &input, &output);
+
+        // Proofs in other versions of the format are rejected.
+        let mut bytes = proof.to_bytes();
+        bytes[0] = PROOF_FORMAT_VERSION + 1;
+        let error = Proof::<DefaultParameters, 2>::from_bytes(&bytes).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("unsupported proof format version"));
+    }
+
+    #[test]
+    fn test_proof_legacy_formats() {
+        let mut builder = DefaultBuilder::new();
+        let a = builder.read::<Variable>();
+        let b = builder.read::<Variable>();
+        let c = builder.add(a, b);
+        builder.write(c);
+        let circuit = builder.build();
+
+        let mut input = circuit.input();
+        input.write::<Variable>(GoldilocksField::TWO);
+        input.write::<Variable>(GoldilocksField::ONE);
+        let (proof_with_pis, _) = circuit.prove(&input);
+        let proof = Proof::<DefaultParameters, 2>::new(proof_with_pis.clone());
+
+        // Before the format was versioned, proofs were the hex of their bincode serialization.
+        let legacy = format!(
+            "0x{}",
+            hex::encode(bincode::serialize(&proof_with_pis).unwrap())
+        );
+        let from_legacy: Proof<DefaultParameters, 2> =
+            serde_json::from_value(serde_json::json!(legacy)).unwrap();
+        assert_eq!(from_legacy, proof);
+
+        // Function results written in the legacy format can still be read.
+        let result = serde_json::json!({
+            "type": "res_recursiveProofs",
+            "data": { "output": ["3"], "proof": legacy },
+        });
+        let result: ProofResult<DefaultParameters, 2> = serde_json::from_value(result).unwrap();
+        let (result_proof, result_output) = result.as_proof_and_output();
+        assert_eq!(result_proof, proof_with_pis);
+        assert_eq!(
+            result_output,
+            PublicOutput::Proofs(vec![GoldilocksField::from_canonical_u64(3)])
+        );
+    }
+}
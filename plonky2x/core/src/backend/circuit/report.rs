@@ -0,0 +1,287 @@
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+
+use anyhow::{Context, Error, Result};
+use plonky2::field::extension::{Extendable, FieldExtension};
+use plonky2::field::types::{Field, PrimeField64};
+use plonky2::iop::target::Target;
+use plonky2::iop::witness::{PartitionWitness, Witness};
+use plonky2::plonk::circuit_data::{CommonCircuitData, ProverOnlyCircuitData};
+use plonky2::plonk::config::{GenericConfig, Hasher};
+use plonky2::plonk::vars::EvaluationVars;
+use serde::{Deserialize, Serialize};
+
+use super::PlonkParameters;
+use crate::frontend::vars::Variable;
+
+/// The value of a variable named with `CircuitBuilder::name_variable`. The elements which are not
+/// set in the witness are `None`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NamedValue {
+    pub name: String,
+    pub elements: Vec<Option<u64>>,
+}
+
+/// A constraint of a gate which is not satisfied by the witness.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnsatisfiedConstraint {
+    /// The row of the gate.
+    pub row: usize,
+    /// The id of the gate.
+    pub gate: String,
+    /// The index of the constraint among the constraints of the gate.
+    pub constraint: usize,
+}
+
+/// A report on a witness of a circuit, with the values of the named variables, the first
+/// constraint which is not satisfied by the witness, and the error of witness generation if it
+/// failed, in which case the witness only has the values generated before the failure.
+///
+/// The wires which are not set in the witness are zero, as in the prover. The lookups and the copy
+/// constraints are not checked, since the copy constraints are enforced by the witness itself.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WitnessReport {
+    pub named_values: Vec<NamedValue>,
+    pub unsatisfied_constraint: Option<UnsatisfiedConstraint>,
+    pub error: Option<String>,
+}
+
+impl WitnessReport {
+    /// Creates the report of a witness generated for a circuit.
+    pub fn new<L: PlonkParameters<D>, const D: usize>(
+        prover_data: &ProverOnlyCircuitData<L::Field, L::Config, D>,
+        common_data: &CommonCircuitData<L::Field, D>,
+        witness: &PartitionWitness<L::Field>,
+        named_variables: &[(String, Vec<Variable>)],
+        error: Option<Error>,
+    ) -> Self {
+        let named_values = named_variables
+            .iter()
+            .map(|(name, variables)| NamedValue {
+                name: name.clone(),
+                elements: variables
+                    .iter()
+                    .map(|v| witness.try_get_target(v.0).map(|x| x.to_canonical_u64()))
+                    .collect(),
+            })
+            .collect();
+        let unsatisfied_constraint =
+            first_unsatisfied_constraint::<L, D>(prover_data, common_data, witness);
+        Self {
+            named_values,
+            unsatisfied_constraint,
+            error: error.map(|e| format!("{:#}", e)),
+        }
+    }
+
+    /// Returns whether witness generation succeeded and all the constraints are satisfied by the
+    /// witness.
+    pub fn is_satisfied(&self) -> bool {
+        self.error.is_none() && self.unsatisfied_constraint.is_none()
+    }
+
+    /// Saves the report as JSON.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        let file = File::create(path)
+            .with_context(|| format!("failed to create witness report {}", path.display()))?;
+        serde_json::to_writer_pretty(BufWriter::new(file), self)?;
+        Ok(())
+    }
+}
+
+/// Evaluates the constraints of the gates on the witness, row by row, and returns the first one
+/// which is not satisfied.
+fn first_unsatisfied_constraint<L: PlonkParameters<D>, const D: usize>(
+    prover_data: &ProverOnlyCircuitData<L::Field, L::Config, D>,
+    common_data: &CommonCircuitData<L::Field, D>,
+    witness: &PartitionWitness<L::Field>,
+) -> Option<UnsatisfiedConstraint> {
+    let to_extension =
+        <<L::Field as Extendable<D>>::Extension as FieldExtension<D>>::from_basefield;
+
+    // The constants, including the selectors, on the rows of the circuit.
+    let constants = prover_data.constants_sigmas_commitment.polynomials
+        [..common_data.num_constants]
+        .iter()
+        .map(|poly| poly.clone().fft().values)
+        .collect::<Vec<_>>();
+    let num_selectors = common_data.selectors_info.num_selectors();
+    let num_prefix = num_selectors + common_data.num_lookup_selectors;
+
+    let public_inputs = prover_data
+        .public_inputs
+        .iter()
+        .map(|t| witness.try_get_target(*t).unwrap_or(L::Field::ZERO))
+        .collect::<Vec<_>>();
+    let public_inputs_hash =
+        <<L::Config as GenericConfig<D>>::InnerHasher as Hasher<L::Field>>::hash_no_pad(
+            &public_inputs,
+        );
+
+    for row in 0..common_data.degree() {
+        let local_constants = constants
+            .iter()
+            .map(|values| values[row])
+            .collect::<Vec<_>>();
+        let local_wires = (0..common_data.config.num_wires)
+            .map(|column| {
+                let value = witness
+                    .try_get_target(Target::wire(row, column))
+                    .unwrap_or(L::Field::ZERO);
+                to_extension(value)
+            })
+            .collect::<Vec<_>>();
+        let local_constants_ext = local_constants[num_prefix..]
+            .iter()
+            .map(|c| to_extension(*c))
+            .collect::<Vec<_>>();
+
+        // The gate of the row is the one whose index is the value of its selector.
+        for (gate_index, gate) in common_data.gates.iter().enumerate() {
+            let selector_index = common_data.selectors_info.selector_indices[gate_index];
+            if local_constants[selector_index] != L::Field::from_canonical_usize(gate_index) {
+                continue;
+            }
+            let vars = EvaluationVars {
+                local_constants: &local_constants_ext,
+                local_wires: &local_wires,
+                public_inputs_hash: &public_inputs_hash,
+            };
+            let constraint = gate
+                .0
+                .eval_unfiltered(vars)
+                .iter()
+                .position(|c| !c.is_zero());
+            if let Some(constraint) = constraint {
+                return Some(UnsatisfiedConstraint {
+                    row,
+                    gate: gate.0.id(),
+                    constraint,
+                });
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use plonky2::gates::arithmetic_base::ArithmeticGate;
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+    use crate::frontend::hint::simple::hint::Hint;
+    use crate::prelude::*;
+
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    struct WrongProduct;
+
+    impl<L: PlonkParameters<D>, const D: usize> Hint<L, D> for WrongProduct {
+        fn hint(
+            &self,
+            input_stream: &mut ValueStream<L, D>,
+            output_stream: &mut ValueStream<L, D>,
+        ) {
+            let a = input_stream.read_value::<Variable>();
+            let b = input_stream.read_value::<Variable>();
+            output_stream.write_value::<Variable>(a * b + L::Field::ONE);
+        }
+    }
+
+    #[test]
+    fn test_witness_report_unsatisfied_constraint() {
+        let mut builder = DefaultBuilder::new();
+        let a = builder.read::<Variable>();
+        let b = builder.read::<Variable>();
+        let mut input_stream = VariableStream::new();
+        input_stream.write(&a);
+        input_stream.write(&b);
+        let c = builder
+            .hint(input_stream, WrongProduct)
+            .read::<Variable>(&mut builder);
+        builder.name_variable("a", &a);
+        builder.name_variable("c", &c);
+
+        // Constrain `c` to be the product of `a` and `b`, which the hint does not compute.
+        let gate = ArithmeticGate::new_from_config(&builder.api.config);
+        let row = builder
+            .api
+            .add_gate(gate, vec![GoldilocksField::ONE, GoldilocksField::ZERO]);
+        builder.api.connect(
+            a.0,
+            Target::wire(row, ArithmeticGate::wire_ith_multiplicand_0(0)),
+        );
+        builder.api.connect(
+            b.0,
+            Target::wire(row, ArithmeticGate::wire_ith_multiplicand_1(0)),
+        );
+        builder
+            .api
+            .connect(c.0, Target::wire(row, ArithmeticGate::wire_ith_output(0)));
+        builder.write(c);
+        let circuit = builder.build();
+
+        let mut input = circuit.input();
+        input.write::<Variable>(GoldilocksField::from_canonical_u64(3));
+        input.write::<Variable>(GoldilocksField::from_canonical_u64(4));
+        let report = circuit.check_witness(&input).unwrap();
+
+        assert!(!report.is_satisfied());
+        let constraint = report.unsatisfied_constraint.unwrap();
+        assert_eq!(constraint.row, row);
+        assert!(constraint.gate.starts_with("ArithmeticGate"));
+        assert_eq!(
+            report.named_values,
+            vec![
+                NamedValue {
+                    name: "a".to_string(),
+                    elements: vec![Some(3)],
+                },
+                NamedValue {
+                    name: "c".to_string(),
+                    elements: vec![Some(13)],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_witness_report_failed_generation() {
+        let mut builder = DefaultBuilder::new();
+        let a = builder.read::<Variable>();
+        // `b` is never set, so the hint which reads it cannot run.
+        let b = builder.init::<Variable>();
+        let mut input_stream = VariableStream::new();
+        input_stream.write(&a);
+        input_stream.write(&b);
+        let c = builder
+            .hint(input_stream, WrongProduct)
+            .read::<Variable>(&mut builder);
+        builder.name_variable("a", &a);
+        builder.name_variable("c", &c);
+        builder.write(c);
+        let circuit = builder.build();
+
+        let mut input = circuit.input();
+        input.write::<Variable>(GoldilocksField::from_canonical_u64(3));
+        let report = circuit.check_witness(&input).unwrap();
+
+        assert!(!report.is_satisfied());
+        assert!(report.error.unwrap().contains("Witness generation failed"));
+        assert_eq!(
+            report.named_values,
+            vec![
+                NamedValue {
+                    name: "a".to_string(),
+                    elements: vec![Some(3)],
+                },
+                NamedValue {
+                    name: "c".to_string(),
+                    elements: vec![None],
+                },
+            ]
+        );
+    }
+}
@@ -1,6 +1,8 @@
 use core::fmt::Debug;
 use core::marker::PhantomData;
+use std::collections::HashMap;
 
+use log::error;
 use plonky2::field::extension::Extendable;
 use plonky2::gadgets::arithmetic::EqualityGenerator;
 use plonky2::gadgets::arithmetic_extension::QuotientGeneratorExtension;
@@ -28,7 +30,7 @@ use plonky2::iop::generator::{
 use plonky2::plonk::circuit_data::CommonCircuitData;
 use plonky2::plonk::config::{AlgebraicHasher, GenericConfig};
 use plonky2::recursion::dummy_circuit::DummyProofGenerator;
-use plonky2::util::serialization::{Buffer, IoResult, Read, WitnessGeneratorSerializer, Write};
+use plonky2::util::serialization::{Buffer, IoError, IoResult, WitnessGeneratorSerializer};
 use starkyx::machine::hash::blake::blake2b::BLAKE2B;
 use starkyx::machine::hash::sha::sha256::SHA256;
 use starkyx::machine::hash::sha::sha512::SHA512;
@@ -38,22 +40,46 @@ use starkyx::plonky2::cubic::mul_gate::MulCubicGenerator;
 use super::registry::{SerializationRegistry, Serializer};
 use super::PlonkParameters;
 use crate as plonky2x;
+use crate::frontend::builder::lookup::ByteDecompositionHint;
+use crate::frontend::builder::sort::SortHint;
+use crate::frontend::ecc::bls12_381::fp12::Bls12381Fp12InverseHint;
+use crate::frontend::ecc::bls12_381::fp2::{Bls12381Fp2DivHint, Bls12381Fp2SqrtHint};
 use crate::frontend::ecc::curve25519::curta::proof_hint::EcOpProofHint;
 use crate::frontend::ecc::curve25519::curta::result_hint::EcOpResultHint;
+use crate::frontend::ecc::ecgfp5::field::GFp5DivHint;
+use crate::frontend::ecc::nonnative::NonNativeDivHint;
+use crate::frontend::ecc::weierstrass::WeierstrassLiftXHint;
 use crate::frontend::eth::beacon::generators::{
-    BeaconAllWithdrawalsHint, BeaconBalanceBatchWitnessHint, BeaconBalanceGenerator,
-    BeaconBalanceWitnessHint, BeaconBalancesGenerator, BeaconBlockRootsHint, BeaconGraffitiHint,
-    BeaconHeaderHint, BeaconHeadersFromOffsetRangeHint, BeaconHistoricalBlockHint,
-    BeaconPartialBalancesHint, BeaconPartialValidatorsHint, BeaconValidatorBatchHint,
-    BeaconValidatorGenerator, BeaconValidatorsGenerator, BeaconValidatorsHint,
-    BeaconWithdrawalGenerator, BeaconWithdrawalsGenerator, CompressedBeaconValidatorBatchHint,
+    BeaconAllWithdrawalsHint, BeaconBalanceBatchWitnessHint, BeaconBalanceByIndexHint,
+    BeaconBalanceByPubkeyHint, BeaconBalanceWitnessHint, BeaconBalancesHint, BeaconBlockRootsHint,
+    BeaconGraffitiHint, BeaconHeaderHint, BeaconHeadersFromOffsetRangeHint,
+    BeaconHistoricalBlockHint, BeaconPartialBalancesHint, BeaconPartialValidatorsHint,
+    BeaconValidatorBatchHint, BeaconValidatorByIndexHint, BeaconValidatorByPubkeyHint,
+    BeaconValidatorHint, BeaconValidatorsHint, BeaconWithdrawalHint, BeaconWithdrawalsHint,
+    CompressedBeaconValidatorBatchHint,
+};
+#[allow(deprecated)]
+use crate::frontend::eth::beacon::generators::{
+    BeaconBalanceGenerator, BeaconBalancesGenerator, BeaconValidatorGenerator,
+    BeaconValidatorsGenerator, BeaconWithdrawalGenerator, BeaconWithdrawalsGenerator,
+};
+use crate::frontend::eth::beacon::rpc::{
+    BeaconRpcBlockRootHint, BeaconRpcHeaderHint, BeaconRpcLightClientBootstrapHint,
+    BeaconRpcLightClientUpdateHint, BeaconRpcValidatorHint,
 };
 use crate::frontend::eth::beacon::vars::{
     BeaconBalancesVariable, BeaconHeaderVariable, BeaconValidatorVariable,
     BeaconValidatorsVariable, BeaconWithdrawalVariable, BeaconWithdrawalsVariable,
 };
+use crate::frontend::eth::header::builder::MAX_HEADER_FIELDS;
+use crate::frontend::eth::rlp::builder::RLPListHint;
+use crate::frontend::eth::rpc::{
+    EthBlockByNumberHint, EthCallHint, EthGetProofHint, EthTransactionReceiptHint,
+};
+#[allow(deprecated)]
+use crate::frontend::eth::storage::generators::{EthBlockGenerator, EthLogGenerator};
 use crate::frontend::eth::storage::generators::{
-    EthBlockGenerator, EthLogGenerator, EthStorageKeyGenerator, EthStorageProofHint,
+    EthBlockHint, EthLogHint, EthStorageKeyGenerator, EthStorageProofHint,
 };
 use crate::frontend::hash::curta::digest_hint::HashDigestHint;
 use crate::frontend::hash::curta::proof_hint::HashProofHint;
@@ -66,6 +92,7 @@ use crate::frontend::hint::simple::hint::Hint;
 use crate::frontend::hint::simple::serializer::SimpleHintSerializer;
 use crate::frontend::hint::synchronous::Async;
 use crate::frontend::uint::num::biguint::BigUintDivRemGenerator;
+use crate::frontend::uint::num::u32::gadgets::arithmetic_u32::SplitToU32Generator;
 use crate::frontend::uint::num::u32::gates::add_many_u32::U32AddManyGenerator;
 use crate::frontend::uint::num::u32::gates::arithmetic_u32::U32ArithmeticGenerator;
 use crate::frontend::uint::num::u32::gates::comparison::ComparisonGenerator;
@@ -73,7 +100,11 @@ use crate::frontend::uint::num::u32::gates::range_check_u32::U32RangeCheckGenera
 use crate::frontend::uint::num::u32::gates::subtraction_u32::U32SubtractionGenerator;
 use crate::frontend::uint::uint64::U64Variable;
 use crate::frontend::vars::{Bytes32Variable, SubArrayExtractorHint, U256Variable};
-use crate::prelude::{ArrayVariable, BoolVariable, U32Variable, Variable};
+use crate::prelude::{
+    ArrayVariable, BoolVariable, ByteVariable, I64Variable, U128Variable, U32Variable, U8Variable,
+    Variable,
+};
+use crate::utils::serde::{BufferRead, BufferWrite};
 
 pub trait HintSerializer<L: PlonkParameters<D>, const D: usize>:
     WitnessGeneratorSerializer<L::Field, D>
@@ -95,11 +126,19 @@ pub trait HintSerializer<L: PlonkParameters<D>, const D: usize>:
 /// A registry to store serializers for witness generators.
 ///
 /// New witness generators can be added to the registry by calling the `register` method,
-/// specifying the type and the generator's id.
+/// specifying the type and the generator's id. Generators are serialized with their id, so that a
+/// circuit can be deserialized by any registry in which its generators are registered, regardless
+/// of the order of registration.
+///
+/// The id of a hint defaults to its type name, which depends on the path of its module and may
+/// vary between compilation units. Hints registered with `register_hint_with_id` are serialized
+/// with the given id instead, which is how the hints of this crate are registered.
 #[derive(Debug)]
 pub struct HintRegistry<L: PlonkParameters<D>, const D: usize> {
     generators: SerializationRegistry<String, L::Field, WitnessGeneratorRef<L::Field, D>, D>,
     async_hints: SerializationRegistry<String, L::Field, AsyncHintDataRef<L, D>, D>,
+    /// The ids with which generators are serialized, by the id returned by the generator.
+    ids: HashMap<String, String>,
 }
 
 /// A serializer for a plonky2 witness generator.
@@ -144,20 +183,39 @@ impl<L: PlonkParameters<D>, const D: usize> HintRegistry<L, D> {
 
     /// Registers a hint into the registry.
     pub fn register_hint<H: Hint<L, D>>(&mut self) {
+        self.register_hint_with_id::<H>(&H::id())
+    }
+
+    /// Registers a hint into the registry, which is serialized with the id `id`.
+    pub fn register_hint_with_id<H: Hint<L, D>>(&mut self, id: &str) {
         let serializer = SimpleHintSerializer::<L, H>::new();
-        let id = H::id();
-        self.generators.register(id, serializer).unwrap();
+        self.ids.insert(H::id(), id.to_string());
+        self.generators
+            .register(id.to_string(), serializer)
+            .unwrap();
     }
 
     /// Registers an asynchronous hint into the registry.
     pub fn register_async_hint<H: AsyncHint<L, D>>(&mut self) {
+        self.register_async_hint_with_id::<H>(&H::id())
+    }
+
+    /// Registers an asynchronous hint into the registry, which is serialized with the id `id`.
+    pub fn register_async_hint_with_id<H: AsyncHint<L, D>>(&mut self, id: &str) {
         let serializer = AsyncHintSerializer::<L, H>::new();
-        let id = AsyncHintRef::<L, D>::id(H::id());
+        let id = AsyncHintRef::<L, D>::id(id.to_string());
+        self.ids
+            .insert(AsyncHintRef::<L, D>::id(H::id()), id.clone());
         self.generators
             .register(id.clone(), serializer.clone())
             .unwrap();
         self.async_hints.register(id, serializer).unwrap();
     }
+
+    /// Returns the id with which a generator returning `id` is serialized.
+    fn serialized_id(&self, id: String) -> String {
+        self.ids.get(&id).cloned().unwrap_or(id)
+    }
 }
 
 #[macro_export]
@@ -172,53 +230,160 @@ macro_rules! register_watch_generator {
 
 macro_rules! register_powers_of_two {
     ($r:ident, $hint:ident) => {
-        $r.register_hint::<$hint<2>>();
-        $r.register_hint::<$hint<4>>();
-        $r.register_hint::<$hint<8>>();
-        $r.register_hint::<$hint<16>>();
-        $r.register_hint::<$hint<32>>();
-        $r.register_hint::<$hint<64>>();
-        $r.register_hint::<$hint<128>>();
-        $r.register_hint::<$hint<256>>();
-        $r.register_hint::<$hint<512>>();
-        $r.register_hint::<$hint<1024>>();
-        $r.register_hint::<$hint<2048>>();
-        $r.register_hint::<$hint<4096>>();
-        $r.register_hint::<$hint<8192>>();
-        $r.register_hint::<$hint<16384>>();
-        $r.register_hint::<$hint<32768>>();
-        $r.register_hint::<$hint<65536>>();
-        $r.register_hint::<$hint<131072>>();
-        $r.register_hint::<$hint<262144>>();
-        $r.register_hint::<$hint<524288>>();
-        $r.register_hint::<$hint<1048576>>();
-        $r.register_hint::<$hint<2097152>>();
+        $r.register_hint_with_id::<$hint<2>>(concat!(stringify!($hint), "<2>"));
+        $r.register_hint_with_id::<$hint<4>>(concat!(stringify!($hint), "<4>"));
+        $r.register_hint_with_id::<$hint<8>>(concat!(stringify!($hint), "<8>"));
+        $r.register_hint_with_id::<$hint<16>>(concat!(stringify!($hint), "<16>"));
+        $r.register_hint_with_id::<$hint<32>>(concat!(stringify!($hint), "<32>"));
+        $r.register_hint_with_id::<$hint<64>>(concat!(stringify!($hint), "<64>"));
+        $r.register_hint_with_id::<$hint<128>>(concat!(stringify!($hint), "<128>"));
+        $r.register_hint_with_id::<$hint<256>>(concat!(stringify!($hint), "<256>"));
+        $r.register_hint_with_id::<$hint<512>>(concat!(stringify!($hint), "<512>"));
+        $r.register_hint_with_id::<$hint<1024>>(concat!(stringify!($hint), "<1024>"));
+        $r.register_hint_with_id::<$hint<2048>>(concat!(stringify!($hint), "<2048>"));
+        $r.register_hint_with_id::<$hint<4096>>(concat!(stringify!($hint), "<4096>"));
+        $r.register_hint_with_id::<$hint<8192>>(concat!(stringify!($hint), "<8192>"));
+        $r.register_hint_with_id::<$hint<16384>>(concat!(stringify!($hint), "<16384>"));
+        $r.register_hint_with_id::<$hint<32768>>(concat!(stringify!($hint), "<32768>"));
+        $r.register_hint_with_id::<$hint<65536>>(concat!(stringify!($hint), "<65536>"));
+        $r.register_hint_with_id::<$hint<131072>>(concat!(stringify!($hint), "<131072>"));
+        $r.register_hint_with_id::<$hint<262144>>(concat!(stringify!($hint), "<262144>"));
+        $r.register_hint_with_id::<$hint<524288>>(concat!(stringify!($hint), "<524288>"));
+        $r.register_hint_with_id::<$hint<1048576>>(concat!(stringify!($hint), "<1048576>"));
+        $r.register_hint_with_id::<$hint<2097152>>(concat!(stringify!($hint), "<2097152>"));
     };
 }
 
 macro_rules! register_powers_of_two_async {
     ($r:ident, $hint:ident) => {
-        $r.register_async_hint::<$hint<2>>();
-        $r.register_async_hint::<$hint<4>>();
-        $r.register_async_hint::<$hint<8>>();
-        $r.register_async_hint::<$hint<16>>();
-        $r.register_async_hint::<$hint<32>>();
-        $r.register_async_hint::<$hint<64>>();
-        $r.register_async_hint::<$hint<128>>();
-        $r.register_async_hint::<$hint<256>>();
-        $r.register_async_hint::<$hint<512>>();
-        $r.register_async_hint::<$hint<1024>>();
-        $r.register_async_hint::<$hint<2048>>();
-        $r.register_async_hint::<$hint<4096>>();
-        $r.register_async_hint::<$hint<8192>>();
-        $r.register_async_hint::<$hint<16384>>();
-        $r.register_async_hint::<$hint<32768>>();
-        $r.register_async_hint::<$hint<65536>>();
-        $r.register_async_hint::<$hint<131072>>();
-        $r.register_async_hint::<$hint<262144>>();
-        $r.register_async_hint::<$hint<524288>>();
-        $r.register_async_hint::<$hint<1048576>>();
-        $r.register_async_hint::<$hint<2097152>>();
+        $r.register_async_hint_with_id::<$hint<2>>(concat!(stringify!($hint), "<2>"));
+        $r.register_async_hint_with_id::<$hint<4>>(concat!(stringify!($hint), "<4>"));
+        $r.register_async_hint_with_id::<$hint<8>>(concat!(stringify!($hint), "<8>"));
+        $r.register_async_hint_with_id::<$hint<16>>(concat!(stringify!($hint), "<16>"));
+        $r.register_async_hint_with_id::<$hint<32>>(concat!(stringify!($hint), "<32>"));
+        $r.register_async_hint_with_id::<$hint<64>>(concat!(stringify!($hint), "<64>"));
+        $r.register_async_hint_with_id::<$hint<128>>(concat!(stringify!($hint), "<128>"));
+        $r.register_async_hint_with_id::<$hint<256>>(concat!(stringify!($hint), "<256>"));
+        $r.register_async_hint_with_id::<$hint<512>>(concat!(stringify!($hint), "<512>"));
+        $r.register_async_hint_with_id::<$hint<1024>>(concat!(stringify!($hint), "<1024>"));
+        $r.register_async_hint_with_id::<$hint<2048>>(concat!(stringify!($hint), "<2048>"));
+        $r.register_async_hint_with_id::<$hint<4096>>(concat!(stringify!($hint), "<4096>"));
+        $r.register_async_hint_with_id::<$hint<8192>>(concat!(stringify!($hint), "<8192>"));
+        $r.register_async_hint_with_id::<$hint<16384>>(concat!(stringify!($hint), "<16384>"));
+        $r.register_async_hint_with_id::<$hint<32768>>(concat!(stringify!($hint), "<32768>"));
+        $r.register_async_hint_with_id::<$hint<65536>>(concat!(stringify!($hint), "<65536>"));
+        $r.register_async_hint_with_id::<$hint<131072>>(concat!(stringify!($hint), "<131072>"));
+        $r.register_async_hint_with_id::<$hint<262144>>(concat!(stringify!($hint), "<262144>"));
+        $r.register_async_hint_with_id::<$hint<524288>>(concat!(stringify!($hint), "<524288>"));
+        $r.register_async_hint_with_id::<$hint<1048576>>(concat!(stringify!($hint), "<1048576>"));
+        $r.register_async_hint_with_id::<$hint<2097152>>(concat!(stringify!($hint), "<2097152>"));
+    };
+    ($r:ident, Async<$hint:ident>) => {
+        $r.register_async_hint_with_id::<Async<$hint<2>>>(concat!(
+            "Async<",
+            stringify!($hint),
+            "<2>>"
+        ));
+        $r.register_async_hint_with_id::<Async<$hint<4>>>(concat!(
+            "Async<",
+            stringify!($hint),
+            "<4>>"
+        ));
+        $r.register_async_hint_with_id::<Async<$hint<8>>>(concat!(
+            "Async<",
+            stringify!($hint),
+            "<8>>"
+        ));
+        $r.register_async_hint_with_id::<Async<$hint<16>>>(concat!(
+            "Async<",
+            stringify!($hint),
+            "<16>>"
+        ));
+        $r.register_async_hint_with_id::<Async<$hint<32>>>(concat!(
+            "Async<",
+            stringify!($hint),
+            "<32>>"
+        ));
+        $r.register_async_hint_with_id::<Async<$hint<64>>>(concat!(
+            "Async<",
+            stringify!($hint),
+            "<64>>"
+        ));
+        $r.register_async_hint_with_id::<Async<$hint<128>>>(concat!(
+            "Async<",
+            stringify!($hint),
+            "<128>>"
+        ));
+        $r.register_async_hint_with_id::<Async<$hint<256>>>(concat!(
+            "Async<",
+            stringify!($hint),
+            "<256>>"
+        ));
+        $r.register_async_hint_with_id::<Async<$hint<512>>>(concat!(
+            "Async<",
+            stringify!($hint),
+            "<512>>"
+        ));
+        $r.register_async_hint_with_id::<Async<$hint<1024>>>(concat!(
+            "Async<",
+            stringify!($hint),
+            "<1024>>"
+        ));
+        $r.register_async_hint_with_id::<Async<$hint<2048>>>(concat!(
+            "Async<",
+            stringify!($hint),
+            "<2048>>"
+        ));
+        $r.register_async_hint_with_id::<Async<$hint<4096>>>(concat!(
+            "Async<",
+            stringify!($hint),
+            "<4096>>"
+        ));
+        $r.register_async_hint_with_id::<Async<$hint<8192>>>(concat!(
+            "Async<",
+            stringify!($hint),
+            "<8192>>"
+        ));
+        $r.register_async_hint_with_id::<Async<$hint<16384>>>(concat!(
+            "Async<",
+            stringify!($hint),
+            "<16384>>"
+        ));
+        $r.register_async_hint_with_id::<Async<$hint<32768>>>(concat!(
+            "Async<",
+            stringify!($hint),
+            "<32768>>"
+        ));
+        $r.register_async_hint_with_id::<Async<$hint<65536>>>(concat!(
+            "Async<",
+            stringify!($hint),
+            "<65536>>"
+        ));
+        $r.register_async_hint_with_id::<Async<$hint<131072>>>(concat!(
+            "Async<",
+            stringify!($hint),
+            "<131072>>"
+        ));
+        $r.register_async_hint_with_id::<Async<$hint<262144>>>(concat!(
+            "Async<",
+            stringify!($hint),
+            "<262144>>"
+        ));
+        $r.register_async_hint_with_id::<Async<$hint<524288>>>(concat!(
+            "Async<",
+            stringify!($hint),
+            "<524288>>"
+        ));
+        $r.register_async_hint_with_id::<Async<$hint<1048576>>>(concat!(
+            "Async<",
+            stringify!($hint),
+            "<1048576>>"
+        ));
+        $r.register_async_hint_with_id::<Async<$hint<2097152>>>(concat!(
+            "Async<",
+            stringify!($hint),
+            "<2097152>>"
+        ));
     };
 }
 
@@ -228,10 +393,12 @@ where
 {
     /// Creates a new registry with all the default generators that are used in a Plonky2x circuit.
     #[allow(clippy::new_without_default)]
+    #[allow(deprecated)]
     pub fn new() -> Self {
         let mut r = Self {
             generators: SerializationRegistry::new(),
             async_hints: SerializationRegistry::new(),
+            ids: HashMap::new(),
         };
 
         let arithmetic_generator_id = ArithmeticBaseGenerator::<L::Field, D>::default().id();
@@ -368,17 +535,57 @@ where
         let comparison_generator_id = ComparisonGenerator::<L::Field, D>::id();
         r.register_simple::<ComparisonGenerator<L::Field, D>>(comparison_generator_id);
 
-        r.register_hint::<BeaconBalanceWitnessHint>();
+        let split_to_u32_generator_id = SplitToU32Generator::<L::Field, D>::id();
+        r.register_simple::<SplitToU32Generator<L::Field, D>>(split_to_u32_generator_id);
+
+        r.register_hint_with_id::<BeaconBalanceWitnessHint>("BeaconBalanceWitnessHint");
+        r.register_hint_with_id::<BeaconValidatorHint>("BeaconValidatorHint");
 
-        r.register_async_hint::<BeaconAllWithdrawalsHint>();
-        r.register_async_hint::<BeaconHeaderHint>();
-        r.register_async_hint::<BeaconHistoricalBlockHint>();
-        r.register_async_hint::<EthStorageProofHint<L, D>>();
-        r.register_async_hint::<BeaconValidatorsHint>();
+        // The synchronous beacon hints fetch over the network, so the builder runs them through
+        // `Async` to have their results recorded by the hint tape.
+        r.register_async_hint_with_id::<Async<BeaconBalanceWitnessHint>>(
+            "Async<BeaconBalanceWitnessHint>",
+        );
+        r.register_async_hint_with_id::<Async<BeaconValidatorHint>>("Async<BeaconValidatorHint>");
+        r.register_async_hint_with_id::<Async<BeaconBalancesHint>>("Async<BeaconBalancesHint>");
+        r.register_async_hint_with_id::<Async<BeaconWithdrawalsHint>>(
+            "Async<BeaconWithdrawalsHint>",
+        );
+        r.register_async_hint_with_id::<Async<BeaconWithdrawalHint>>("Async<BeaconWithdrawalHint>");
+        r.register_async_hint_with_id::<Async<BeaconBlockRootsHint>>("Async<BeaconBlockRootsHint>");
+        r.register_async_hint_with_id::<Async<BeaconGraffitiHint>>("Async<BeaconGraffitiHint>");
+
+        r.register_async_hint_with_id::<BeaconAllWithdrawalsHint>("BeaconAllWithdrawalsHint");
+        r.register_async_hint_with_id::<BeaconHeaderHint>("BeaconHeaderHint");
+        r.register_async_hint_with_id::<BeaconHistoricalBlockHint>("BeaconHistoricalBlockHint");
+        r.register_async_hint_with_id::<BeaconValidatorByIndexHint>("BeaconValidatorByIndexHint");
+        r.register_async_hint_with_id::<BeaconValidatorByPubkeyHint>("BeaconValidatorByPubkeyHint");
+        r.register_async_hint_with_id::<BeaconBalanceByIndexHint>("BeaconBalanceByIndexHint");
+        r.register_async_hint_with_id::<BeaconBalanceByPubkeyHint>("BeaconBalanceByPubkeyHint");
+        r.register_async_hint_with_id::<EthStorageProofHint<L, D>>("EthStorageProofHint");
+        r.register_async_hint_with_id::<EthBlockHint<L, D>>("EthBlockHint");
+        r.register_async_hint_with_id::<EthLogHint<L, D>>("EthLogHint");
+        r.register_async_hint_with_id::<EthBlockByNumberHint>("EthBlockByNumberHint");
+        r.register_async_hint_with_id::<EthGetProofHint>("EthGetProofHint");
+        r.register_async_hint_with_id::<EthTransactionReceiptHint>("EthTransactionReceiptHint");
+        r.register_async_hint_with_id::<EthCallHint>("EthCallHint");
+        r.register_async_hint_with_id::<BeaconRpcBlockRootHint>("BeaconRpcBlockRootHint");
+        r.register_async_hint_with_id::<BeaconRpcHeaderHint>("BeaconRpcHeaderHint");
+        r.register_async_hint_with_id::<BeaconRpcValidatorHint>("BeaconRpcValidatorHint");
+        r.register_async_hint_with_id::<BeaconRpcLightClientBootstrapHint>(
+            "BeaconRpcLightClientBootstrapHint",
+        );
+        r.register_async_hint_with_id::<BeaconRpcLightClientUpdateHint>(
+            "BeaconRpcLightClientUpdateHint",
+        );
+        r.register_async_hint_with_id::<BeaconValidatorsHint>("BeaconValidatorsHint");
 
         register_powers_of_two!(r, BeaconBalanceBatchWitnessHint);
         register_powers_of_two!(r, BeaconValidatorBatchHint);
         register_powers_of_two!(r, CompressedBeaconValidatorBatchHint);
+        register_powers_of_two_async!(r, Async<BeaconBalanceBatchWitnessHint>);
+        register_powers_of_two_async!(r, Async<BeaconValidatorBatchHint>);
+        register_powers_of_two_async!(r, Async<CompressedBeaconValidatorBatchHint>);
 
         register_powers_of_two_async!(r, BeaconPartialBalancesHint);
         register_powers_of_two_async!(r, BeaconPartialValidatorsHint);
@@ -392,41 +599,90 @@ where
         let id = MulCubicGenerator::<L::Field, D>::id();
         r.register_simple::<MulCubicGenerator<L::Field, D>>(id);
 
-        r.register_hint::<SubArrayExtractorHint>();
+        r.register_hint_with_id::<SubArrayExtractorHint>("SubArrayExtractorHint");
+
+        // The list lengths decoded by the account, receipt, transaction, trie and header circuits.
+        r.register_hint_with_id::<RLPListHint<2>>("RLPListHint<2>");
+        r.register_hint_with_id::<RLPListHint<3>>("RLPListHint<3>");
+        r.register_hint_with_id::<RLPListHint<4>>("RLPListHint<4>");
+        r.register_hint_with_id::<RLPListHint<12>>("RLPListHint<12>");
+        r.register_hint_with_id::<RLPListHint<17>>("RLPListHint<17>");
+        r.register_hint_with_id::<RLPListHint<MAX_HEADER_FIELDS>>(&format!(
+            "RLPListHint<{}>",
+            MAX_HEADER_FIELDS
+        ));
+
+        r.register_hint_with_id::<ByteDecompositionHint>("ByteDecompositionHint");
 
-        r.register_hint::<BeaconBlockRootsHint>();
+        r.register_hint_with_id::<SortHint<U32Variable>>("SortHint<U32Variable>");
+        r.register_hint_with_id::<SortHint<U64Variable>>("SortHint<U64Variable>");
+        r.register_hint_with_id::<SortHint<U256Variable>>("SortHint<U256Variable>");
 
-        r.register_hint::<BeaconGraffitiHint>();
+        r.register_hint_with_id::<BeaconBlockRootsHint>("BeaconBlockRootsHint");
+
+        r.register_hint_with_id::<BeaconGraffitiHint>("BeaconGraffitiHint");
+
+        r.register_hint_with_id::<HashDigestHint<SHA256, 64, false, 8>>(
+            "HashDigestHint<SHA256, 64, false, 8>",
+        );
+        r.register_async_hint_with_id::<Async<HashDigestHint<SHA256, 64, false, 8>>>(
+            "Async<HashDigestHint<SHA256, 64, false, 8>>",
+        );
 
-        r.register_hint::<HashDigestHint<SHA256, 64, false, 8>>();
-        r.register_async_hint::<Async<HashDigestHint<SHA256, 64, false, 8>>>();
+        r.register_hint_with_id::<HashProofHint<SHA256, 64, false, 8>>(
+            "HashProofHint<SHA256, 64, false, 8>",
+        );
+        r.register_async_hint_with_id::<Async<HashProofHint<SHA256, 64, false, 8>>>(
+            "Async<HashProofHint<SHA256, 64, false, 8>>",
+        );
 
-        r.register_hint::<HashProofHint<SHA256, 64, false, 8>>();
-        r.register_async_hint::<Async<HashProofHint<SHA256, 64, false, 8>>>();
+        r.register_hint_with_id::<HashDigestHint<SHA512, 80, false, 8>>(
+            "HashDigestHint<SHA512, 80, false, 8>",
+        );
+        r.register_async_hint_with_id::<Async<HashDigestHint<SHA512, 80, false, 8>>>(
+            "Async<HashDigestHint<SHA512, 80, false, 8>>",
+        );
 
-        r.register_hint::<HashDigestHint<SHA512, 80, false, 8>>();
-        r.register_async_hint::<Async<HashDigestHint<SHA512, 80, false, 8>>>();
+        r.register_hint_with_id::<HashProofHint<SHA512, 80, false, 8>>(
+            "HashProofHint<SHA512, 80, false, 8>",
+        );
+        r.register_async_hint_with_id::<Async<HashProofHint<SHA512, 80, false, 8>>>(
+            "Async<HashProofHint<SHA512, 80, false, 8>>",
+        );
 
-        r.register_hint::<HashProofHint<SHA512, 80, false, 8>>();
-        r.register_async_hint::<Async<HashProofHint<SHA512, 80, false, 8>>>();
+        r.register_hint_with_id::<HashDigestHint<BLAKE2B, 96, true, 4>>(
+            "HashDigestHint<BLAKE2B, 96, true, 4>",
+        );
+        r.register_async_hint_with_id::<Async<HashDigestHint<BLAKE2B, 96, true, 4>>>(
+            "Async<HashDigestHint<BLAKE2B, 96, true, 4>>",
+        );
 
-        r.register_hint::<HashDigestHint<BLAKE2B, 96, true, 4>>();
-        r.register_async_hint::<Async<HashDigestHint<BLAKE2B, 96, true, 4>>>();
+        r.register_hint_with_id::<HashProofHint<BLAKE2B, 96, true, 4>>(
+            "HashProofHint<BLAKE2B, 96, true, 4>",
+        );
+        r.register_async_hint_with_id::<Async<HashProofHint<BLAKE2B, 96, true, 4>>>(
+            "Async<HashProofHint<BLAKE2B, 96, true, 4>>",
+        );
 
-        r.register_hint::<HashProofHint<BLAKE2B, 96, true, 4>>();
-        r.register_async_hint::<Async<HashProofHint<BLAKE2B, 96, true, 4>>>();
+        r.register_hint_with_id::<EcOpProofHint>("EcOpProofHint");
+        r.register_async_hint_with_id::<Async<EcOpProofHint>>("Async<EcOpProofHint>");
 
-        r.register_hint::<EcOpProofHint>();
-        r.register_async_hint::<Async<EcOpProofHint>>();
+        r.register_hint_with_id::<EcOpResultHint>("EcOpResultHint");
+        r.register_async_hint_with_id::<Async<EcOpResultHint>>("Async<EcOpResultHint>");
 
-        r.register_hint::<EcOpResultHint>();
-        r.register_async_hint::<Async<EcOpResultHint>>();
+        r.register_hint_with_id::<NonNativeDivHint>("NonNativeDivHint");
+        r.register_hint_with_id::<WeierstrassLiftXHint>("WeierstrassLiftXHint");
+        r.register_hint_with_id::<GFp5DivHint>("GFp5DivHint");
+        r.register_hint_with_id::<Bls12381Fp2DivHint>("Bls12381Fp2DivHint");
+        r.register_hint_with_id::<Bls12381Fp2SqrtHint>("Bls12381Fp2SqrtHint");
+        r.register_hint_with_id::<Bls12381Fp12InverseHint>("Bls12381Fp12InverseHint");
 
         let dummy_proof_generator_id =
             DummyProofGenerator::<L::Field, L::Config, D>::default().id();
         r.register_simple::<DummyProofGenerator<L::Field, L::Config, D>>(dummy_proof_generator_id);
 
         register_powers_of_two!(r, BeaconHeadersFromOffsetRangeHint);
+        register_powers_of_two_async!(r, Async<BeaconHeadersFromOffsetRangeHint>);
 
         register_watch_generator!(
             r,
@@ -434,9 +690,13 @@ where
             D,
             Variable,
             BoolVariable,
+            ByteVariable,
+            U8Variable,
             U32Variable,
             U64Variable,
+            U128Variable,
             U256Variable,
+            I64Variable,
             Bytes32Variable,
             BeaconValidatorsVariable,
             BeaconBalancesVariable,
@@ -452,6 +712,17 @@ where
     }
 }
 
+/// Reads the id of a generator written by `write_id`.
+fn read_id(buf: &mut Buffer) -> IoResult<String> {
+    let bytes = buf.read_bytes()?;
+    String::from_utf8(bytes).map_err(|_| IoError)
+}
+
+/// Writes the id of a generator.
+fn write_id(buf: &mut Vec<u8>, id: &str) -> IoResult<()> {
+    buf.write_bytes(id.as_bytes())
+}
+
 impl<L: PlonkParameters<D>, const D: usize> WitnessGeneratorSerializer<L::Field, D>
     for HintRegistry<L, D>
 {
@@ -460,13 +731,15 @@ impl<L: PlonkParameters<D>, const D: usize> WitnessGeneratorSerializer<L::Field,
         buf: &mut Buffer,
         common_data: &CommonCircuitData<L::Field, D>,
     ) -> IoResult<WitnessGeneratorRef<L::Field, D>> {
-        let idx = buf.read_usize()?;
-        let id = &self.generators.identifiers[idx];
+        let id = read_id(buf)?;
 
         self.generators
             .registry
-            .get(id)
-            .unwrap_or_else(|| panic!("Generator type not registered {}", id))
+            .get(&id)
+            .ok_or_else(|| {
+                error!("Generator type not registered {}", id);
+                IoError
+            })?
             .read(buf, common_data)
     }
 
@@ -476,20 +749,13 @@ impl<L: PlonkParameters<D>, const D: usize> WitnessGeneratorSerializer<L::Field,
         generator: &WitnessGeneratorRef<L::Field, D>,
         common_data: &CommonCircuitData<L::Field, D>,
     ) -> IoResult<()> {
-        let id = generator.0.id();
-        let idx = self
-            .generators
-            .index
-            .get(&id)
-            .unwrap_or_else(|| panic!("Generator type not registered {}", id));
-        buf.write_usize(*idx)?;
-
-        self.generators
-            .registry
-            .get(&id)
-            .unwrap_or_else(|| panic!("Generator type not registered {}", id))
-            .write(buf, generator, common_data)?;
-        Ok(())
+        let id = self.serialized_id(generator.0.id());
+        let serializer = self.generators.registry.get(&id).ok_or_else(|| {
+            error!("Generator type not registered {}", id);
+            IoError
+        })?;
+        write_id(buf, &id)?;
+        serializer.write(buf, generator, common_data)
     }
 }
 
@@ -499,13 +765,15 @@ impl<L: PlonkParameters<D>, const D: usize> HintSerializer<L, D> for HintRegistr
         buf: &mut Buffer,
         common_data: &CommonCircuitData<L::Field, D>,
     ) -> IoResult<AsyncHintDataRef<L, D>> {
-        let idx = buf.read_usize()?;
-        let id = &self.async_hints.identifiers[idx];
+        let id = read_id(buf)?;
 
         self.async_hints
             .registry
-            .get(id)
-            .unwrap_or_else(|| panic!("Hint type not registered {}", id))
+            .get(&id)
+            .ok_or_else(|| {
+                error!("Hint type not registered {}", id);
+                IoError
+            })?
             .read(buf, common_data)
     }
 
@@ -515,20 +783,13 @@ impl<L: PlonkParameters<D>, const D: usize> HintSerializer<L, D> for HintRegistr
         hint: &AsyncHintDataRef<L, D>,
         common_data: &CommonCircuitData<L::Field, D>,
     ) -> IoResult<()> {
-        let id = hint.0.id();
-        let idx = self
-            .async_hints
-            .index
-            .get(&id)
-            .unwrap_or_else(|| panic!("Generator type not registered {}", id));
-        buf.write_usize(*idx)?;
-
-        self.async_hints
-            .registry
-            .get(&id)
-            .unwrap_or_else(|| panic!("Generator type not registered {}", id))
-            .write(buf, hint, common_data)?;
-        Ok(())
+        let id = self.serialized_id(hint.0.id());
+        let serializer = self.async_hints.registry.get(&id).ok_or_else(|| {
+            error!("Hint type not registered {}", id);
+            IoError
+        })?;
+        write_id(buf, &id)?;
+        serializer.write(buf, hint, common_data)
     }
 }
 
@@ -537,10 +798,15 @@ mod tests {
     use plonky2::field::goldilocks_field::GoldilocksField;
     use plonky2::iop::generator::{ConstantGenerator, SimpleGenerator, WitnessGeneratorRef};
     use plonky2::util::serialization::{Buffer, WitnessGeneratorSerializer};
+    use serde::{Deserialize, Serialize};
 
     use crate::backend::circuit::serialization::hints::HintRegistry;
     use crate::backend::circuit::DefaultParameters;
-    use crate::prelude::CircuitBuilder;
+    use crate::frontend::hint::simple::generator::HintSimpleGenerator;
+    use crate::frontend::hint::simple::hint::Hint;
+    use crate::prelude::{
+        ByteVariable, CircuitBuilder, PlonkParameters, ValueStream, VariableStream,
+    };
 
     type L = DefaultParameters;
     type F = GoldilocksField;
@@ -564,4 +830,139 @@ mod tests {
         let read_generator = registry.read_generator(&mut buffer, &common_data).unwrap();
         assert_eq!(raw_generator, read_generator);
     }
+
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    struct AddOne;
+
+    impl<L: PlonkParameters<D>, const D: usize> Hint<L, D> for AddOne {
+        fn hint(
+            &self,
+            input_stream: &mut ValueStream<L, D>,
+            output_stream: &mut ValueStream<L, D>,
+        ) {
+            let a = input_stream.read_value::<ByteVariable>();
+            output_stream.write_value::<ByteVariable>(a + 1)
+        }
+    }
+
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    struct AddTwo;
+
+    impl<L: PlonkParameters<D>, const D: usize> Hint<L, D> for AddTwo {
+        fn hint(
+            &self,
+            input_stream: &mut ValueStream<L, D>,
+            output_stream: &mut ValueStream<L, D>,
+        ) {
+            let a = input_stream.read_value::<ByteVariable>();
+            output_stream.write_value::<ByteVariable>(a + 2)
+        }
+    }
+
+    #[test]
+    fn test_custom_hint_serialization_by_id() {
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let mut input_stream = VariableStream::new();
+        input_stream.write(&builder.init::<ByteVariable>());
+        let mut output_stream = VariableStream::new();
+        output_stream.write(&builder.init::<ByteVariable>());
+        let common_data = builder.build().data.common;
+
+        let raw_generator = WitnessGeneratorRef::new(HintSimpleGenerator::<L, AddTwo>::new(
+            input_stream,
+            output_stream,
+            AddTwo,
+        ));
+
+        let mut registry = HintRegistry::<L, D>::new();
+        registry.register_hint::<AddTwo>();
+        let mut bytes = Vec::<u8>::new();
+        registry
+            .write_generator(&mut bytes, &raw_generator, &common_data)
+            .unwrap();
+
+        // A registry in which the hints are registered in a different order reads the generator.
+        let mut other_registry = HintRegistry::<L, D>::new();
+        other_registry.register_hint::<AddOne>();
+        other_registry.register_hint::<AddTwo>();
+        let mut buffer = Buffer::new(&bytes);
+        let read_generator = other_registry
+            .read_generator(&mut buffer, &common_data)
+            .unwrap();
+        assert_eq!(raw_generator, read_generator);
+
+        // A registry in which the hint is not registered fails to read the generator.
+        let default_registry = HintRegistry::<L, D>::new();
+        let mut buffer = Buffer::new(&bytes);
+        assert!(default_registry
+            .read_generator(&mut buffer, &common_data)
+            .is_err());
+    }
+
+    #[test]
+    fn test_custom_hint_serialization_with_explicit_id() {
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let mut input_stream = VariableStream::new();
+        input_stream.write(&builder.init::<ByteVariable>());
+        let mut output_stream = VariableStream::new();
+        output_stream.write(&builder.init::<ByteVariable>());
+        let common_data = builder.build().data.common;
+
+        let raw_generator = WitnessGeneratorRef::new(HintSimpleGenerator::<L, AddTwo>::new(
+            input_stream,
+            output_stream,
+            AddTwo,
+        ));
+
+        let mut registry = HintRegistry::<L, D>::new();
+        registry.register_hint_with_id::<AddTwo>("AddTwo");
+        let mut bytes = Vec::<u8>::new();
+        registry
+            .write_generator(&mut bytes, &raw_generator, &common_data)
+            .unwrap();
+
+        // The generator is serialized with the explicit id instead of the type name.
+        let type_name = <AddTwo as Hint<L, D>>::id();
+        assert!(!bytes
+            .windows(type_name.len())
+            .any(|w| w == type_name.as_bytes()));
+
+        let mut other_registry = HintRegistry::<L, D>::new();
+        other_registry.register_hint_with_id::<AddTwo>("AddTwo");
+        let mut buffer = Buffer::new(&bytes);
+        let read_generator = other_registry
+            .read_generator(&mut buffer, &common_data)
+            .unwrap();
+        assert_eq!(raw_generator, read_generator);
+
+        // A registry in which the hint is registered by its type name fails to read the generator.
+        let mut type_name_registry = HintRegistry::<L, D>::new();
+        type_name_registry.register_hint::<AddTwo>();
+        let mut buffer = Buffer::new(&bytes);
+        assert!(type_name_registry
+            .read_generator(&mut buffer, &common_data)
+            .is_err());
+    }
+
+    #[test]
+    fn test_write_unregistered_generator_fails() {
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let mut input_stream = VariableStream::new();
+        input_stream.write(&builder.init::<ByteVariable>());
+        let mut output_stream = VariableStream::new();
+        output_stream.write(&builder.init::<ByteVariable>());
+        let common_data = builder.build().data.common;
+
+        let raw_generator = WitnessGeneratorRef::new(HintSimpleGenerator::<L, AddOne>::new(
+            input_stream,
+            output_stream,
+            AddOne,
+        ));
+
+        let registry = HintRegistry::<L, D>::new();
+        let mut bytes = Vec::<u8>::new();
+        assert!(registry
+            .write_generator(&mut bytes, &raw_generator, &common_data)
+            .is_err());
+    }
 }
@@ -11,6 +11,12 @@ pub use registry::Serializer;
 
 use super::{Circuit, PlonkParameters};
 
+/// The version of the format of serialized circuits, which is written before the circuit data.
+///
+/// It is incremented when the layout of the serialized circuit or the ids of the hints of this
+/// crate change, so that a circuit saved by another version is rejected instead of misread.
+pub const CIRCUIT_FORMAT_VERSION: u8 = 1;
+
 /// A trait that allows a type to define a custom generator and gate registry.
 ///
 /// It is used when builder methods need access to custom serializers for recursive proofs.
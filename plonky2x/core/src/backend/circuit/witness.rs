@@ -2,7 +2,8 @@
 //!
 //! These methods are based on the [`generate_partial_witness`][1] method in `plonky2`, with the
 //! added functionality to have non-blocking witness generation for asynchronous hints and
-//! computationally expensive generators.
+//! computationally expensive generators. The generators that are ready to run at the same time
+//! are independent, so they are run in parallel.
 //!
 //! [1] : https://github.com/mir-protocol/plonky2/blob/main/plonky2/src/iop/generator.rs#L19
 
@@ -14,13 +15,14 @@ use log::trace;
 use plonky2::iop::generator::{GeneratedValues, WitnessGeneratorRef};
 use plonky2::iop::witness::{PartialWitness, PartitionWitness, WitnessWrite};
 use plonky2::plonk::circuit_data::{CommonCircuitData, ProverOnlyCircuitData};
-use starkyx::maybe_rayon::rayon;
+use starkyx::maybe_rayon::*;
 use tokio::sync::mpsc::unbounded_channel;
 use tokio::sync::oneshot;
 
 use super::PlonkParameters;
 use crate::frontend::hint::asynchronous::generator::{AsyncHintDataRef, AsyncHintRef, HintPoll};
 use crate::frontend::hint::asynchronous::handler::HintHandler;
+use crate::frontend::hint::asynchronous::tape::HintTape;
 
 /// Given a `PartialWitness` that has only inputs set, populates the rest of the witness using the
 /// given set of generators.
@@ -30,6 +32,37 @@ pub fn generate_witness<'a, L: PlonkParameters<D>, const D: usize>(
     common_data: &'a CommonCircuitData<L::Field, D>,
     async_generator_refs: &'a BTreeMap<usize, AsyncHintDataRef<L, D>>,
 ) -> Result<PartitionWitness<'a, L::Field>> {
+    generate_witness_with_tape(inputs, prover_data, common_data, async_generator_refs, None)
+}
+
+/// Given a `PartialWitness` that has only inputs set, populates the rest of the witness using the
+/// given set of generators, recording the asynchronous hints to `tape` or replaying them from it.
+pub fn generate_witness_with_tape<'a, L: PlonkParameters<D>, const D: usize>(
+    inputs: PartialWitness<L::Field>,
+    prover_data: &'a ProverOnlyCircuitData<L::Field, L::Config, D>,
+    common_data: &'a CommonCircuitData<L::Field, D>,
+    async_generator_refs: &'a BTreeMap<usize, AsyncHintDataRef<L, D>>,
+    tape: Option<HintTape>,
+) -> Result<PartitionWitness<'a, L::Field>> {
+    let (witness, result) = generate_partial_witness_with_tape(
+        inputs,
+        prover_data,
+        common_data,
+        async_generator_refs,
+        tape,
+    );
+    result.map(|()| witness)
+}
+
+/// A version of `generate_witness_with_tape` which returns the witness even if witness generation
+/// fails, with the values generated before the failure, along with the result of the generation.
+pub(crate) fn generate_partial_witness_with_tape<'a, L: PlonkParameters<D>, const D: usize>(
+    inputs: PartialWitness<L::Field>,
+    prover_data: &'a ProverOnlyCircuitData<L::Field, L::Config, D>,
+    common_data: &'a CommonCircuitData<L::Field, D>,
+    async_generator_refs: &'a BTreeMap<usize, AsyncHintDataRef<L, D>>,
+    tape: Option<HintTape>,
+) -> (PartitionWitness<'a, L::Field>, Result<()>) {
     // If async hints are present, set up the a handler and initialize the generators with the
     // handler's communication channel.
     let (tx_handler_error, rx_handler_error) = oneshot::channel();
@@ -38,14 +71,15 @@ pub fn generate_witness<'a, L: PlonkParameters<D>, const D: usize>(
         false => {
             let (tx, rx) = unbounded_channel();
             // Initialize the hint handler.
-            let mut hint_handler = HintHandler::<L, D>::new(rx);
+            let mut hint_handler = HintHandler::<L, D>::new(rx, tape);
 
             // Spawn a runtime and run the hint handler.
             let rt = tokio::runtime::Runtime::new().expect("Failed to create runtime");
             rayon::spawn(move || {
                 let result = rt.block_on(hint_handler.run());
                 if let Err(e) = result {
-                    tx_handler_error.send(e).unwrap();
+                    // The witness generation may have finished or failed already.
+                    tx_handler_error.send(e).ok();
                 }
             });
 
@@ -71,6 +105,19 @@ pub async fn generate_witness_async<'a, L: PlonkParameters<D>, const D: usize>(
     prover_data: &'a ProverOnlyCircuitData<L::Field, L::Config, D>,
     common_data: &'a CommonCircuitData<L::Field, D>,
     async_generator_refs: &'a BTreeMap<usize, AsyncHintDataRef<L, D>>,
+) -> Result<PartitionWitness<'a, L::Field>> {
+    generate_witness_async_with_tape(inputs, prover_data, common_data, async_generator_refs, None)
+        .await
+}
+
+/// An asynchronous version of `generate_witness_with_tape`, which runs the hint handler on the
+/// current runtime.
+pub async fn generate_witness_async_with_tape<'a, L: PlonkParameters<D>, const D: usize>(
+    inputs: PartialWitness<L::Field>,
+    prover_data: &'a ProverOnlyCircuitData<L::Field, L::Config, D>,
+    common_data: &'a CommonCircuitData<L::Field, D>,
+    async_generator_refs: &'a BTreeMap<usize, AsyncHintDataRef<L, D>>,
+    tape: Option<HintTape>,
 ) -> Result<PartitionWitness<'a, L::Field>> {
     // If async hints are present, set up the a handler and initialize the generators with the
     // handler's communication channel.
@@ -80,13 +127,14 @@ pub async fn generate_witness_async<'a, L: PlonkParameters<D>, const D: usize>(
         false => {
             let (tx, rx) = unbounded_channel();
             // Initialize the hint handler.
-            let mut hint_handler = HintHandler::<L, D>::new(rx);
+            let mut hint_handler = HintHandler::<L, D>::new(rx, tape);
 
             // Spawn a runtime and run the hint handler.
             tokio::spawn(async move {
                 let result = hint_handler.run().await;
                 if let Err(e) = result {
-                    tx_handler_error.send(e).unwrap();
+                    // The witness generation may have finished or failed already.
+                    tx_handler_error.send(e).ok();
                 }
             });
 
@@ -98,7 +146,7 @@ pub async fn generate_witness_async<'a, L: PlonkParameters<D>, const D: usize>(
         }
     };
 
-    tokio::task::block_in_place(move || {
+    let (witness, result) = tokio::task::block_in_place(move || {
         fill_witness_values::<L, D>(
             inputs,
             prover_data,
@@ -106,18 +154,45 @@ pub async fn generate_witness_async<'a, L: PlonkParameters<D>, const D: usize>(
             async_generators,
             rx_handler_error,
         )
-    })
+    });
+    result.map(|()| witness)
 }
 
-/// Fill in the witness after intiializing async generators.
+/// Fill in the witness after intiializing async generators, returning the witness along with the
+/// result of the generation, so that the witness is returned even if the generation fails.
 fn fill_witness_values<'a, L: PlonkParameters<D>, const D: usize>(
     inputs: PartialWitness<L::Field>,
     prover_data: &'a ProverOnlyCircuitData<L::Field, L::Config, D>,
     common_data: &'a CommonCircuitData<L::Field, D>,
+    async_generators: BTreeMap<usize, AsyncHintRef<L, D>>,
+    rx_handler_error: oneshot::Receiver<Error>,
+) -> (PartitionWitness<'a, L::Field>, Result<()>) {
+    let mut witness = PartitionWitness::new(
+        common_data.config.num_wires,
+        common_data.degree(),
+        &prover_data.representative_map,
+    );
+
+    for (t, v) in inputs.target_values.into_iter() {
+        witness.set_target(t, v);
+    }
+
+    let result = run_generators::<L, D>(
+        &mut witness,
+        prover_data,
+        async_generators,
+        rx_handler_error,
+    );
+    (witness, result)
+}
+
+/// Runs the generators until all of them have finished, setting their values in the witness.
+fn run_generators<L: PlonkParameters<D>, const D: usize>(
+    witness: &mut PartitionWitness<L::Field>,
+    prover_data: &ProverOnlyCircuitData<L::Field, L::Config, D>,
     mut async_generators: BTreeMap<usize, AsyncHintRef<L, D>>,
     mut rx_handler_error: oneshot::Receiver<Error>,
-) -> Result<PartitionWitness<'a, L::Field>> {
-    let config = &common_data.config;
+) -> Result<()> {
     let generators = &prover_data.generators;
     let generator_indices_by_watches = &prover_data.generator_indices_by_watches;
 
@@ -129,55 +204,65 @@ fn fill_witness_values<'a, L: PlonkParameters<D>, const D: usize>(
     let mut generator_is_expired = vec![false; generators.len()];
     let mut remaining_generators = generators.len();
 
-    let mut buffer = GeneratedValues::empty();
-    let mut witness = PartitionWitness::new(
-        config.num_wires,
-        common_data.degree(),
-        &prover_data.representative_map,
-    );
-
-    for (t, v) in inputs.target_values.into_iter() {
-        witness.set_target(t, v);
-    }
-
     // Keep running generators until we fail to make progress.
     while !pending_generator_indices.is_empty() {
         let mut next_pending_generator_indices = HashSet::new();
-        for &generator_idx in &pending_generator_indices {
-            if generator_is_expired[generator_idx] {
-                continue;
+
+        let (async_generator_indices, generator_indices): (Vec<usize>, Vec<usize>) =
+            pending_generator_indices
+                .iter()
+                .copied()
+                .filter(|&generator_idx| !generator_is_expired[generator_idx])
+                .partition(|generator_idx| async_generators.contains_key(generator_idx));
+
+        // The pending generators only read the witness, so they are run in parallel and their
+        // generated values are merged into the witness once they have all run.
+        let results = generator_indices
+            .par_iter()
+            .map(|&generator_idx| {
+                let mut buffer = GeneratedValues::empty();
+                let finished = generators[generator_idx].0.run(witness, &mut buffer);
+                (generator_idx, finished, buffer)
+            })
+            .collect::<Vec<_>>();
+
+        let mut generated_values = Vec::with_capacity(pending_generator_indices.len());
+        for (generator_idx, finished, buffer) in results {
+            if finished {
+                generator_is_expired[generator_idx] = true;
+                remaining_generators -= 1;
             }
+            generated_values.push(buffer);
+        }
 
-            // Run the generator, depending on whether it is an asyncronous or not.
-            if let Some(async_gen) = async_generators.get_mut(&generator_idx) {
-                // Check if the hint handler has returned an error, and if so, return it.
-                if let Ok(e) = rx_handler_error.try_recv() {
-                    return Err(e);
-                }
-                let pol = async_gen.0.run(&witness, &mut buffer);
-                match pol {
-                    HintPoll::InputPending => {}
-                    HintPoll::Pending => {
-                        next_pending_generator_indices.insert(generator_idx);
-                    }
-                    HintPoll::Ready => {
-                        generator_is_expired[generator_idx] = true;
-                        remaining_generators -= 1;
-                    }
+        // Poll the asynchronous generators, whose hints are run concurrently by the hint handler.
+        for generator_idx in async_generator_indices {
+            // Check if the hint handler has returned an error, and if so, return it.
+            if let Ok(e) = rx_handler_error.try_recv() {
+                return Err(e);
+            }
+            let async_gen = async_generators.get_mut(&generator_idx).unwrap();
+            let mut buffer = GeneratedValues::empty();
+            let pol = async_gen.0.run(witness, &mut buffer)?;
+            match pol {
+                HintPoll::InputPending => {}
+                HintPoll::Pending => {
+                    next_pending_generator_indices.insert(generator_idx);
                 }
-            } else {
-                let finished = generators[generator_idx].0.run(&witness, &mut buffer);
-                if finished {
+                HintPoll::Ready => {
                     generator_is_expired[generator_idx] = true;
                     remaining_generators -= 1;
                 }
             }
+            generated_values.push(buffer);
+        }
 
-            // Merge any generated values into our witness, and get a list of newly-populated
+        for buffer in generated_values {
+            // Merge the generated values into our witness, and get a list of newly-populated
             // targets' representatives.
             let new_target_reps = buffer
                 .target_values
-                .drain(..)
+                .into_iter()
                 .flat_map(|(t, v)| witness.set_target_returning_rep(t, v));
 
             // Enqueue unfinished generators that were watching one of the newly populated targets.
@@ -209,7 +294,7 @@ fn fill_witness_values<'a, L: PlonkParameters<D>, const D: usize>(
         "finished filling in witness: nb_public_inputs={}",
         prover_data.public_inputs.len()
     );
-    Ok(witness)
+    Ok(())
 }
 
 #[inline]
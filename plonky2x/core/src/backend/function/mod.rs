@@ -74,11 +74,7 @@ impl<C: Circuit> Plonky2xFunction for C {
 
         // Serialize the circuit to disk.
         let path = format!("{}/main.circuit", args.build_dir);
-        let mut generator_registry = HintRegistry::new();
-        let mut gate_registry = GateRegistry::new();
-        C::register_generators::<L, D>(&mut generator_registry);
-        C::register_gates::<L, D>(&mut gate_registry);
-        circuit.save(&path, &gate_registry, &generator_registry);
+        C::save(&circuit, &path).unwrap();
         info!("Successfully saved circuit to disk at {}.", path);
 
         // Serialize the verifier contract to disk.
@@ -163,11 +159,6 @@ impl<C: Circuit> Plonky2xFunction for C {
             None
         };
 
-        let mut generator_registry = HintRegistry::new();
-        let mut gate_registry = GateRegistry::new();
-        C::register_generators::<InnerParameters, D>(&mut generator_registry);
-        C::register_gates::<InnerParameters, D>(&mut gate_registry);
-
         let mut path = match request {
             ProofRequest::Bytes(_) => {
                 format!("{}/main.circuit", args.build_dir)
@@ -185,9 +176,7 @@ impl<C: Circuit> Plonky2xFunction for C {
         }
 
         info!("Loading circuit from {}...", path);
-        let circuit =
-            CircuitBuild::<InnerParameters, D>::load(&path, &gate_registry, &generator_registry)
-                .unwrap();
+        let circuit = C::load::<InnerParameters, D, _>(&path).unwrap();
         info!("Successfully loaded circuit.");
 
         let input = request.input();
@@ -256,7 +245,7 @@ impl<C: Circuit> Plonky2xFunction for C {
             file.write_all(json.as_bytes()).unwrap();
             info!("Successfully saved full result to disk at output.json.");
         } else {
-            let result = ProofResult::from_proof_output(proof, output);
+            let result = ProofResult::from_proof_output(Proof::new(proof), output);
             let json = serde_json::to_string_pretty(&result).unwrap();
             let mut file = File::create("output.json").unwrap();
             file.write_all(json.as_bytes()).unwrap();
@@ -1,14 +1,12 @@
 use core::fmt::Debug;
 use std::env;
 
-use plonky2::plonk::proof::ProofWithPublicInputs;
 use serde::{Deserialize, Serialize};
 
-use crate::backend::circuit::{PlonkParameters, PublicInput};
+use crate::backend::circuit::{PlonkParameters, Proof, PublicInput};
 use crate::backend::prover::ProofId;
 use crate::utils::serde::{
-    deserialize_elements, deserialize_hex, deserialize_proof_with_pis_vec, serialize_elements,
-    serialize_hex, serialize_proof_with_pis_vec,
+    deserialize_elements, deserialize_hex, serialize_elements, serialize_hex,
 };
 
 /// Fields for a function request that uses bytes io.
@@ -32,11 +30,10 @@ pub struct ElementsRequestData<L: PlonkParameters<D>, const D: usize> {
 /// Fields for a function request that uses recursive proofs io.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[serde(bound = "")]
 pub struct RecursiveProofsRequestData<L: PlonkParameters<D>, const D: usize> {
     pub circuit_id: String,
-    #[serde(serialize_with = "serialize_proof_with_pis_vec")]
-    #[serde(deserialize_with = "deserialize_proof_with_pis_vec")]
-    pub proofs: Vec<ProofWithPublicInputs<L::Field, L::Config, D>>,
+    pub proofs: Vec<Proof<L, D>>,
 }
 
 /// Fields for a function request that uses recursive proofs io but with remote proofs.
@@ -105,7 +102,7 @@ impl<L: PlonkParameters<D>, const D: usize> ProofRequest<L, D> {
                     files: Some(vec![format!("{}.circuit", circuit_id)]),
                     data: RecursiveProofsRequestData {
                         circuit_id: circuit_id.to_string(),
-                        proofs: input.clone(),
+                        proofs: input.iter().cloned().map(Proof::new).collect(),
                     },
                 })
             }
@@ -142,7 +139,12 @@ impl<L: PlonkParameters<D>, const D: usize> ProofRequest<L, D> {
                 PublicInput::Elements(data.input.clone())
             }
             ProofRequest::RecursiveProofs(ProofRequestBase { data, .. }) => {
-                PublicInput::RecursiveProofs(data.proofs.clone(), vec![])
+                let proofs = data
+                    .proofs
+                    .iter()
+                    .map(|proof| proof.proof_with_pis.clone())
+                    .collect();
+                PublicInput::RecursiveProofs(proofs, vec![])
             }
             _ => panic!("invalid proof request type"),
         }
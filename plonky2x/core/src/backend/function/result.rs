@@ -4,10 +4,9 @@ use core::panic;
 use plonky2::plonk::proof::ProofWithPublicInputs;
 use serde::{Deserialize, Serialize};
 
-use crate::backend::circuit::{PlonkParameters, PublicOutput};
+use crate::backend::circuit::{PlonkParameters, Proof, PublicOutput};
 use crate::utils::serde::{
-    deserialize_elements, deserialize_hex, deserialize_proof_with_pis, serialize_elements,
-    serialize_hex, serialize_proof_with_pis,
+    deserialize_elements, deserialize_hex, serialize_elements, serialize_hex,
 };
 
 /// Fields for a function result that uses bytes io.
@@ -23,22 +22,20 @@ pub struct BytesResultData {
 
 /// Fields for a function result that uses field elements io.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(bound = "")]
 pub struct ElementsResultData<L: PlonkParameters<D>, const D: usize> {
     pub output: Vec<L::Field>,
-    #[serde(serialize_with = "serialize_proof_with_pis")]
-    #[serde(deserialize_with = "deserialize_proof_with_pis")]
-    pub proof: ProofWithPublicInputs<L::Field, L::Config, D>,
+    pub proof: Proof<L, D>,
 }
 
 /// Fields for a function result that uses recursive proofs io.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(bound = "")]
 pub struct RecursiveProofsResultData<L: PlonkParameters<D>, const D: usize> {
     #[serde(serialize_with = "serialize_elements")]
     #[serde(deserialize_with = "deserialize_elements")]
     pub output: Vec<L::Field>,
-    #[serde(serialize_with = "serialize_proof_with_pis")]
-    #[serde(deserialize_with = "deserialize_proof_with_pis")]
-    pub proof: ProofWithPublicInputs<L::Field, L::Config, D>,
+    pub proof: Proof<L, D>,
 }
 
 /// Common fields for all function results.
@@ -65,15 +62,14 @@ pub enum ProofResult<L: PlonkParameters<D>, const D: usize> {
 
 impl<L: PlonkParameters<D>, const D: usize> ProofResult<L, D> {
     /// Creates a new function result from a proof and output.
-    pub fn from_proof_output(
-        proof: ProofWithPublicInputs<L::Field, L::Config, D>,
-        output: PublicOutput<L, D>,
-    ) -> Self {
+    pub fn from_proof_output(proof: Proof<L, D>, output: PublicOutput<L, D>) -> Self {
         match output {
             PublicOutput::Bytes(output) => {
+                // Consumers of bytes results read the bincode serialization of the proof, which is
+                // kept unversioned.
                 let data = BytesResultData {
                     output,
-                    proof: bincode::serialize(&proof).unwrap(),
+                    proof: bincode::serialize(&proof.proof_with_pis).unwrap(),
                 };
                 ProofResult::Bytes(ProofResultBase { data })
             }
@@ -104,12 +100,12 @@ impl<L: PlonkParameters<D>, const D: usize> ProofResult<L, D> {
             ProofResult::Elements(result) => {
                 let proof = &result.data.proof;
                 let output = PublicOutput::Elements(result.data.output.clone());
-                (proof.clone(), output)
+                (proof.proof_with_pis.clone(), output)
             }
             ProofResult::RecursiveProofs(result) => {
                 let proof = &result.data.proof;
                 let output = PublicOutput::Proofs(result.data.output.clone());
-                (proof.clone(), output)
+                (proof.proof_with_pis.clone(), output)
             }
             _ => panic!("cannot convert to proof and output"),
         }
@@ -0,0 +1,149 @@
+use itertools::Itertools;
+use plonky2::iop::target::BoolTarget;
+
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::builder::CircuitBuilder;
+use crate::frontend::vars::{BoolVariable, CircuitVariable, Variable};
+
+/// The order of bits in a bit decomposition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Endianness {
+    /// The least significant bit comes first.
+    Little,
+    /// The most significant bit comes first.
+    Big,
+}
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Decomposes `value` into `width` bits in the given `endianness`.
+    ///
+    /// The underlying variables of `value` are treated as little-endian limbs of
+    /// `width / V::nb_elements()` bits each, which is the layout of `Variable`, `BoolVariable`,
+    /// `U8Variable` and the u32-limbed uint types. Each limb is constrained to fit in its width.
+    pub fn to_bits<V: CircuitVariable>(
+        &mut self,
+        value: &V,
+        width: usize,
+        endianness: Endianness,
+    ) -> Vec<BoolVariable> {
+        let variables = value.variables();
+        assert_eq!(
+            width % variables.len(),
+            0,
+            "width {} is not a multiple of the number of limbs {}",
+            width,
+            variables.len()
+        );
+        let limb_bits = width / variables.len();
+        // A 64-bit decomposition of a field element is not unique, since `x` and `x + p` both fit.
+        assert!(limb_bits < 64, "limbs must be narrower than 64 bits");
+
+        let mut bits = variables
+            .iter()
+            .flat_map(|variable| self.api.split_le(variable.0, limb_bits))
+            .map(BoolVariable::from)
+            .collect_vec();
+        if endianness == Endianness::Big {
+            bits.reverse();
+        }
+        bits
+    }
+
+    /// Recomposes a value from its bits in the given `endianness`. This is the inverse of
+    /// `to_bits` with `width = bits.len()`.
+    pub fn from_bits<V: CircuitVariable>(
+        &mut self,
+        bits: &[BoolVariable],
+        endianness: Endianness,
+    ) -> V {
+        let nb_limbs = V::nb_elements();
+        assert_eq!(
+            bits.len() % nb_limbs,
+            0,
+            "number of bits {} is not a multiple of the number of limbs {}",
+            bits.len(),
+            nb_limbs
+        );
+        let limb_bits = bits.len() / nb_limbs;
+        assert!(limb_bits < 64, "limbs must be narrower than 64 bits");
+
+        let mut le_bits = bits.to_vec();
+        if endianness == Endianness::Big {
+            le_bits.reverse();
+        }
+        let variables = le_bits
+            .chunks(limb_bits)
+            .map(|chunk| {
+                let targets = chunk.iter().map(|bit| -> BoolTarget { (*bit).into() });
+                Variable(self.api.le_sum(targets))
+            })
+            .collect_vec();
+        V::from_variables(self, &variables)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Endianness;
+    use crate::prelude::*;
+
+    #[test]
+    fn test_to_bits_and_from_bits() {
+        let mut builder = DefaultBuilder::new();
+
+        let x = builder.read::<U64Variable>();
+        let y = builder.read::<Variable>();
+
+        let x_le = builder.to_bits(&x, 64, Endianness::Little);
+        let x_be = builder.to_bits(&x, 64, Endianness::Big);
+        let y_be = builder.to_bits(&y, 40, Endianness::Big);
+        for bit in x_le.iter().chain(x_be.iter()).chain(y_be.iter()) {
+            builder.write(*bit);
+        }
+
+        let x_from_le = builder.from_bits::<U64Variable>(&x_le, Endianness::Little);
+        let x_from_be = builder.from_bits::<U64Variable>(&x_be, Endianness::Big);
+        let y_from_be = builder.from_bits::<Variable>(&y_be, Endianness::Big);
+        builder.assert_is_equal(x_from_le, x);
+        builder.assert_is_equal(x_from_be, x);
+        builder.assert_is_equal(y_from_be, y);
+
+        let circuit = builder.build();
+        let x_value = 0xfedc_ba98_7654_3210u64;
+        let y_value = 0x12_3456_789au64;
+        let mut input = circuit.input();
+        input.write::<U64Variable>(x_value);
+        input.write::<Variable>(GoldilocksField::from_canonical_u64(y_value));
+
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        for i in 0..64 {
+            assert_eq!(output.read::<BoolVariable>(), (x_value >> i) & 1 == 1);
+        }
+        for i in (0..64).rev() {
+            assert_eq!(output.read::<BoolVariable>(), (x_value >> i) & 1 == 1);
+        }
+        for i in (0..40).rev() {
+            assert_eq!(output.read::<BoolVariable>(), (y_value >> i) & 1 == 1);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "was set twice with different values")]
+    fn test_to_bits_value_too_wide() {
+        let mut builder = DefaultBuilder::new();
+        let y = builder.read::<Variable>();
+        let bits = builder.to_bits(&y, 8, Endianness::Little);
+        for bit in bits {
+            builder.write(bit);
+        }
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        input.write::<Variable>(GoldilocksField::from_canonical_u64(256));
+
+        let (proof, output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+    }
+}
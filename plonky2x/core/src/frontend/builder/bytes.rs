@@ -0,0 +1,104 @@
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::builder::CircuitBuilder;
+use crate::frontend::vars::{ByteVariable, EvmVariable};
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Serializes the variable to bytes, most significant byte first.
+    pub fn to_be_bytes<V: EvmVariable>(&mut self, variable: &V) -> Vec<ByteVariable> {
+        variable.encode(self)
+    }
+
+    /// Serializes the variable to bytes, least significant byte first.
+    pub fn to_le_bytes<V: EvmVariable>(&mut self, variable: &V) -> Vec<ByteVariable> {
+        let mut bytes = variable.encode(self);
+        bytes.reverse();
+        bytes
+    }
+
+    /// Deserializes a variable from bytes, most significant byte first.
+    pub fn from_be_bytes<V: EvmVariable>(&mut self, bytes: &[ByteVariable]) -> V {
+        V::decode(self, bytes)
+    }
+
+    /// Deserializes a variable from bytes, least significant byte first.
+    pub fn from_le_bytes<V: EvmVariable>(&mut self, bytes: &[ByteVariable]) -> V {
+        let mut bytes = bytes.to_vec();
+        bytes.reverse();
+        V::decode(self, &bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ethers::types::U256;
+
+    use crate::prelude::*;
+
+    type L = DefaultParameters;
+    const D: usize = 2;
+
+    #[test]
+    fn test_uint_bytes_endianness() {
+        let mut builder = CircuitBuilder::<L, D>::new();
+
+        let a_value = 0x12345678u32;
+        let b_value = 0x0102030405060708u64;
+        let c_value = U256::from_dec_str(
+            "90954689457896187098756465498798756465498798756465498798756465498798",
+        )
+        .unwrap();
+        let mut c_be = [0u8; 32];
+        c_value.to_big_endian(&mut c_be);
+        let mut c_le = [0u8; 32];
+        c_value.to_little_endian(&mut c_le);
+
+        let a = builder.constant::<U32Variable>(a_value);
+        let b = builder.constant::<U64Variable>(b_value);
+        let c = builder.constant::<U256Variable>(c_value);
+
+        let a_be = builder.to_be_bytes(&a);
+        let a_le = builder.to_le_bytes(&a);
+        let b_be = builder.to_be_bytes(&b);
+        let b_le = builder.to_le_bytes(&b);
+        let c_be_var = builder.to_be_bytes(&c);
+        let c_le_var = builder.to_le_bytes(&c);
+
+        let expected_a_be = builder.constant_vec::<ByteVariable>(&a_value.to_be_bytes());
+        let expected_a_le = builder.constant_vec::<ByteVariable>(&a_value.to_le_bytes());
+        let expected_b_be = builder.constant_vec::<ByteVariable>(&b_value.to_be_bytes());
+        let expected_b_le = builder.constant_vec::<ByteVariable>(&b_value.to_le_bytes());
+        let expected_c_be = builder.constant_vec::<ByteVariable>(&c_be);
+        let expected_c_le = builder.constant_vec::<ByteVariable>(&c_le);
+        for (x, y) in [
+            (a_be.clone(), expected_a_be),
+            (a_le.clone(), expected_a_le),
+            (b_be.clone(), expected_b_be),
+            (b_le.clone(), expected_b_le),
+            (c_be_var.clone(), expected_c_be),
+            (c_le_var.clone(), expected_c_le),
+        ] {
+            assert_eq!(x.len(), y.len());
+            for (x, y) in x.into_iter().zip(y) {
+                builder.assert_is_equal(x, y);
+            }
+        }
+
+        let a_from_be = builder.from_be_bytes::<U32Variable>(&a_be);
+        let a_from_le = builder.from_le_bytes::<U32Variable>(&a_le);
+        let b_from_be = builder.from_be_bytes::<U64Variable>(&b_be);
+        let b_from_le = builder.from_le_bytes::<U64Variable>(&b_le);
+        let c_from_be = builder.from_be_bytes::<U256Variable>(&c_be_var);
+        let c_from_le = builder.from_le_bytes::<U256Variable>(&c_le_var);
+        builder.assert_is_equal(a_from_be, a);
+        builder.assert_is_equal(a_from_le, a);
+        builder.assert_is_equal(b_from_be, b);
+        builder.assert_is_equal(b_from_le, b);
+        builder.assert_is_equal(c_from_be, c);
+        builder.assert_is_equal(c_from_le, c);
+
+        let circuit = builder.build();
+        let pw = PartialWitness::new();
+        let proof = circuit.data.prove(pw).unwrap();
+        circuit.data.verify(proof).unwrap();
+    }
+}
@@ -0,0 +1,132 @@
+use super::CircuitBuilder;
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::vars::{ArrayVariable, CircuitVariable, Variable};
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Applies `f` to every element of `array`, returning the results in the same order.
+    pub fn map_array<V, W, F, const N: usize>(
+        &mut self,
+        array: &ArrayVariable<V, N>,
+        mut f: F,
+    ) -> ArrayVariable<W, N>
+    where
+        V: CircuitVariable,
+        W: CircuitVariable,
+        F: FnMut(&mut Self, &V) -> W,
+    {
+        let results = array
+            .as_slice()
+            .iter()
+            .map(|element| f(self, element))
+            .collect::<Vec<_>>();
+        ArrayVariable::new(results)
+    }
+
+    /// Folds `f` over every element of `array`, starting from `init`.
+    pub fn fold_array<V, A, F, const N: usize>(
+        &mut self,
+        array: &ArrayVariable<V, N>,
+        init: A,
+        mut f: F,
+    ) -> A
+    where
+        V: CircuitVariable,
+        A: CircuitVariable,
+        F: FnMut(&mut Self, A, &V) -> A,
+    {
+        let mut accumulator = init;
+        for element in array.as_slice() {
+            accumulator = f(self, accumulator, element);
+        }
+        accumulator
+    }
+
+    /// Folds `f` over the first `length` elements of `array`, starting from `init`, and asserts
+    /// that `length <= N`.
+    ///
+    /// The fold is still unrolled over all `N` elements, and the accumulator after `length`
+    /// elements is selected at the end, so `f` must be safe to call on the padding elements.
+    pub fn fold_array_with_length<V, A, F, const N: usize>(
+        &mut self,
+        array: &ArrayVariable<V, N>,
+        length: Variable,
+        init: A,
+        mut f: F,
+    ) -> A
+    where
+        V: CircuitVariable,
+        A: CircuitVariable,
+        F: FnMut(&mut Self, A, &V) -> A,
+    {
+        let mut accumulators = Vec::with_capacity(N + 1);
+        accumulators.push(init);
+        for element in array.as_slice() {
+            let accumulator = f(self, accumulators.last().unwrap().clone(), element);
+            accumulators.push(accumulator);
+        }
+        self.select_index(&accumulators, length)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn test_fold_and_map_array() {
+        let mut builder = DefaultBuilder::new();
+
+        let array = builder.read::<ArrayVariable<U32Variable, 5>>();
+        let length = builder.read::<Variable>();
+
+        let doubled = builder.map_array(&array, |builder, x| builder.add(*x, *x));
+        let zero = builder.zero::<U32Variable>();
+        let sum = builder.fold_array(&doubled, zero, |builder, acc, x| builder.add(acc, *x));
+        let partial_sum =
+            builder.fold_array_with_length(&array, length, zero, |builder, acc, x| {
+                builder.add(acc, *x)
+            });
+
+        builder.write(doubled);
+        builder.write(sum);
+        builder.write(partial_sum);
+
+        let circuit = builder.build();
+        for (length, expected_partial_sum) in [(0, 0), (3, 6), (5, 15)] {
+            let mut input = circuit.input();
+            input.write::<ArrayVariable<U32Variable, 5>>(vec![1, 2, 3, 4, 5]);
+            input.write::<Variable>(GoldilocksField::from_canonical_u64(length));
+
+            let (proof, mut output) = circuit.prove(&input);
+            circuit.verify(&proof, &input, &output);
+
+            assert_eq!(
+                output.read::<ArrayVariable<U32Variable, 5>>(),
+                vec![2, 4, 6, 8, 10]
+            );
+            assert_eq!(output.read::<U32Variable>(), 30);
+            assert_eq!(output.read::<U32Variable>(), expected_partial_sum);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "is larger than the vector size")]
+    fn test_fold_array_length_too_large() {
+        let mut builder = DefaultBuilder::new();
+        let array = builder.read::<ArrayVariable<U32Variable, 3>>();
+        let length = builder.read::<Variable>();
+        let zero = builder.zero::<U32Variable>();
+        let sum = builder
+            .fold_array_with_length(&array, length, zero, |builder, acc, x| builder.add(acc, *x));
+        builder.write(sum);
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        input.write::<ArrayVariable<U32Variable, 3>>(vec![1, 2, 3]);
+        // There are only 4 accumulators, so the random access gate rejects the index 4.
+        input.write::<Variable>(GoldilocksField::from_canonical_u64(4));
+
+        let (proof, output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+    }
+}
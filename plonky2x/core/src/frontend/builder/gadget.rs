@@ -0,0 +1,376 @@
+//! Reusable sub-circuits.
+//!
+//! A gadget is a sub-circuit with a typed input and output that is instantiated through
+//! `builder.call_gadget::<_, Serializer>(&gadget, input)`. Calls with the same gadget id and the
+//! same input variables are deduplicated: the constraints are only emitted once and later calls
+//! reuse the output of the first one.
+//!
+//! By default, calls on different inputs each emit all the constraints of the gadget again. A
+//! gadget whose `recursive` method returns true is instead defined once, in a circuit of its own
+//! whose constants are shared by every call. Each call then verifies a proof of that circuit on
+//! its input, so the size of the outer circuit does not depend on the size of the gadget. This is
+//! worth it for large gadgets, such as a sha256 compression, that are called on many inputs.
+
+use core::marker::PhantomData;
+
+use log::debug;
+use plonky2::iop::generator::{GeneratedValues, SimpleGenerator};
+use plonky2::iop::target::Target;
+use plonky2::iop::witness::{PartitionWitness, Witness, WitnessWrite};
+use plonky2::plonk::circuit_data::CommonCircuitData;
+use plonky2::plonk::config::{AlgebraicHasher, GenericConfig};
+use plonky2::plonk::proof::ProofWithPublicInputsTarget;
+use plonky2::util::serialization::{Buffer, IoResult, Read, Write};
+
+use crate::backend::circuit::{CircuitBuild, CircuitSerializer, PlonkParameters, PublicInput};
+use crate::backend::prover::EnvProver;
+use crate::frontend::builder::CircuitBuilder;
+use crate::frontend::vars::{CircuitVariable, Variable};
+use crate::utils::proof::ProofWithPublicInputsTargetUtils;
+
+/// A sub-circuit mapping an input variable to an output variable.
+pub trait Gadget<L: PlonkParameters<D>, const D: usize> {
+    type Input: CircuitVariable;
+    type Output: CircuitVariable;
+
+    /// Adds the constraints of the gadget to the builder and returns its output.
+    fn define(&self, input: Self::Input, builder: &mut CircuitBuilder<L, D>) -> Self::Output;
+
+    /// An identifier for the gadget. Two gadgets with the same id must emit the same constraints
+    /// for the same input, so gadgets that are parameterized should include their parameters.
+    fn id(&self) -> String {
+        std::any::type_name::<Self>().to_string()
+    }
+
+    /// Whether the gadget is defined once in a circuit of its own and every call verifies a proof
+    /// of that circuit, instead of emitting the constraints of the gadget at every call.
+    fn recursive(&self) -> bool {
+        false
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Instantiates `gadget` on `input`, either by emitting its constraints or, if the gadget is
+    /// recursive, by verifying a proof of its circuit. Calls with the same gadget id and the same
+    /// input variables reuse the output of the first one.
+    pub fn call_gadget<G: Gadget<L, D>, Serializer: CircuitSerializer>(
+        &mut self,
+        gadget: &G,
+        input: G::Input,
+    ) -> G::Output
+    where
+        <<L as PlonkParameters<D>>::Config as GenericConfig<D>>::Hasher: AlgebraicHasher<L::Field>,
+    {
+        if gadget.recursive() {
+            self.call_gadget_recursive::<G, Serializer>(gadget, input)
+        } else {
+            self.call_gadget_deduplicated(gadget, input)
+        }
+    }
+
+    /// Instantiates `gadget` on `input`, reusing the output of a previous call with the same gadget
+    /// id and the same input variables if there is one. Otherwise, all the constraints of the
+    /// gadget are emitted.
+    pub fn call_gadget_deduplicated<G: Gadget<L, D>>(
+        &mut self,
+        gadget: &G,
+        input: G::Input,
+    ) -> G::Output {
+        let key = (gadget.id(), input.variables());
+        if let Some(output) = self.gadget_cache.get(&key) {
+            // The cached variables were produced by the same gadget, so they are already valid.
+            return G::Output::from_variables_unsafe(output);
+        }
+
+        let output = gadget.define(input, self);
+        self.gadget_cache.insert(key, output.variables());
+        output
+    }
+
+    /// Instantiates `gadget` on `input` by verifying a proof of the circuit of the gadget, which is
+    /// built the first time the gadget is called and saved to `./build`. Calls with the same gadget
+    /// id and the same input variables reuse the output of the first one.
+    pub fn call_gadget_recursive<G: Gadget<L, D>, Serializer: CircuitSerializer>(
+        &mut self,
+        gadget: &G,
+        input: G::Input,
+    ) -> G::Output
+    where
+        <<L as PlonkParameters<D>>::Config as GenericConfig<D>>::Hasher: AlgebraicHasher<L::Field>,
+    {
+        let key = (gadget.id(), input.variables());
+        if let Some(output) = self.gadget_cache.get(&key) {
+            // The cached variables were produced by the same gadget, so they are already valid.
+            return G::Output::from_variables_unsafe(output);
+        }
+
+        let circuit = match self.gadget_circuits.remove(&gadget.id()) {
+            Some(circuit) => circuit,
+            None => self.build_gadget::<G, Serializer>(gadget),
+        };
+        let circuit_id = circuit.id();
+
+        // Verify a proof of the gadget circuit, whose public inputs are the input of the gadget
+        // followed by its output.
+        let verifier_data = self.constant_verifier_data::<L>(&circuit.data);
+        let proof = self.add_virtual_proof_with_pis(&circuit.data.common);
+        self.verify_proof::<L>(&proof, &verifier_data, &circuit.data.common);
+        self.gadget_circuits.insert(gadget.id(), circuit);
+
+        let proof_input = proof.read_start_from_pis::<G::Input>();
+        self.assert_is_equal(proof_input, input.clone());
+        let output = proof.read_end_from_pis::<G::Output>();
+
+        self.add_simple_generator(GadgetProofGenerator::<L, Serializer, D> {
+            circuit_id,
+            input: input.variables(),
+            proof,
+            _phantom: PhantomData,
+        });
+
+        self.gadget_cache.insert(key, output.variables());
+        output
+    }
+
+    /// Builds the circuit of `gadget`, which reads the input of the gadget and writes its output,
+    /// and saves it to `./build`.
+    fn build_gadget<G: Gadget<L, D>, Serializer: CircuitSerializer>(
+        &self,
+        gadget: &G,
+    ) -> CircuitBuild<L, D> {
+        let mut builder = CircuitBuilder::<L, D>::new();
+        builder.beacon_client = self.beacon_client.clone();
+        builder.execution_client = self.execution_client.clone();
+        builder.chain_id = self.chain_id;
+
+        let input = builder.read::<G::Input>();
+        let output = gadget.define(input, &mut builder);
+        builder.write(output);
+        let circuit = builder.build();
+
+        let gate_serializer = Serializer::gate_registry::<L, D>();
+        let generator_serializer = Serializer::generator_registry::<L, D>();
+        let circuit_path = format!("./build/{}.circuit", circuit.id());
+        circuit
+            .save(&circuit_path, &gate_serializer, &generator_serializer)
+            .unwrap();
+        debug!(
+            "successfully built gadget circuit: gadget={}, id={}",
+            gadget.id(),
+            circuit.id()
+        );
+        circuit
+    }
+}
+
+/// Proves the circuit of a recursive gadget on the values of its input.
+#[derive(Debug, Clone)]
+pub struct GadgetProofGenerator<L, Serializer, const D: usize>
+where
+    L: PlonkParameters<D>,
+    Serializer: CircuitSerializer,
+{
+    /// The identifier for the compiled gadget circuit.
+    pub circuit_id: String,
+
+    /// The input variables of the gadget.
+    pub input: Vec<Variable>,
+
+    /// The proof target for the gadget circuit proof.
+    pub proof: ProofWithPublicInputsTarget<D>,
+
+    /// Phantom data.
+    pub _phantom: PhantomData<(L, Serializer)>,
+}
+
+impl<L, Serializer, const D: usize> GadgetProofGenerator<L, Serializer, D>
+where
+    L: PlonkParameters<D>,
+    Serializer: CircuitSerializer,
+{
+    pub fn id() -> String {
+        "GadgetProofGenerator".to_string()
+    }
+}
+
+impl<L, Serializer, const D: usize> SimpleGenerator<L::Field, D>
+    for GadgetProofGenerator<L, Serializer, D>
+where
+    L: PlonkParameters<D>,
+    <<L as PlonkParameters<D>>::Config as GenericConfig<D>>::Hasher: AlgebraicHasher<L::Field>,
+    Serializer: CircuitSerializer,
+{
+    fn id(&self) -> String {
+        Self::id()
+    }
+
+    fn dependencies(&self) -> Vec<Target> {
+        self.input.iter().map(|v| v.0).collect()
+    }
+
+    fn run_once(
+        &self,
+        witness: &PartitionWitness<L::Field>,
+        out_buffer: &mut GeneratedValues<L::Field>,
+    ) {
+        let values = self
+            .input
+            .iter()
+            .map(|v| witness.get_target(v.0))
+            .collect::<Vec<_>>();
+        let input = PublicInput::<L, D>::Elements(values);
+
+        let (proof, _) = EnvProver::new()
+            .prove::<L, Serializer, D>(&self.circuit_id, &input)
+            .unwrap()
+            .materialize()
+            .unwrap();
+        out_buffer.set_proof_with_pis_target(&self.proof, &proof);
+    }
+
+    fn serialize(&self, dst: &mut Vec<u8>, _: &CommonCircuitData<L::Field, D>) -> IoResult<()> {
+        // Write circuit id.
+        dst.write_usize(self.circuit_id.len())?;
+        dst.write_all(self.circuit_id.as_bytes())?;
+
+        // Write input targets.
+        dst.write_target_vec(&self.input.iter().map(|v| v.0).collect::<Vec<_>>())?;
+
+        // Write proof target.
+        dst.write_target_proof_with_public_inputs(&self.proof)
+    }
+
+    fn deserialize(src: &mut Buffer, _: &CommonCircuitData<L::Field, D>) -> IoResult<Self> {
+        // Read circuit id.
+        let circuit_id_length = src.read_usize()?;
+        let mut circuit_id = vec![0u8; circuit_id_length];
+        src.read_exact(&mut circuit_id)?;
+
+        // Read input targets.
+        let input = src.read_target_vec()?.into_iter().map(Variable).collect();
+
+        // Read proof target.
+        let proof = src.read_target_proof_with_public_inputs()?;
+
+        Ok(Self {
+            circuit_id: String::from_utf8(circuit_id).unwrap(),
+            input,
+            proof,
+            _phantom: PhantomData,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Gadget;
+    use crate::backend::circuit::DefaultSerializer;
+    use crate::prelude::*;
+
+    type L = DefaultParameters;
+    const D: usize = 2;
+
+    #[derive(Debug, Clone)]
+    struct SumOfSquares;
+
+    impl Gadget<L, D> for SumOfSquares {
+        type Input = (U32Variable, U32Variable);
+        type Output = U32Variable;
+
+        fn define(&self, input: Self::Input, builder: &mut CircuitBuilder<L, D>) -> Self::Output {
+            let (a, b) = input;
+            let a_squared = builder.mul(a, a);
+            let b_squared = builder.mul(b, b);
+            builder.add(a_squared, b_squared)
+        }
+    }
+
+    #[derive(Debug, Clone)]
+    struct RecursiveSumOfSquares;
+
+    impl Gadget<L, D> for RecursiveSumOfSquares {
+        type Input = (U32Variable, U32Variable);
+        type Output = U32Variable;
+
+        fn define(&self, input: Self::Input, builder: &mut CircuitBuilder<L, D>) -> Self::Output {
+            SumOfSquares.define(input, builder)
+        }
+
+        fn recursive(&self) -> bool {
+            true
+        }
+    }
+
+    #[test]
+    fn test_gadget_deduplication() {
+        let mut builder = CircuitBuilder::<L, D>::new();
+
+        let a = builder.read::<U32Variable>();
+        let b = builder.read::<U32Variable>();
+        let c = builder.read::<U32Variable>();
+
+        let first = builder.call_gadget_deduplicated(&SumOfSquares, (a, b));
+        let nb_gates = builder.api.num_gates();
+        let second = builder.call_gadget_deduplicated(&SumOfSquares, (a, b));
+        assert_eq!(builder.api.num_gates(), nb_gates);
+        assert_eq!(builder.gadget_cache.len(), 1);
+        builder.assert_is_equal(first, second);
+
+        let third = builder.call_gadget_deduplicated(&SumOfSquares, (a, c));
+        assert_eq!(builder.gadget_cache.len(), 2);
+
+        builder.write(second);
+        builder.write(third);
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        input.write::<U32Variable>(3);
+        input.write::<U32Variable>(4);
+        input.write::<U32Variable>(5);
+
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        assert_eq!(output.read::<U32Variable>(), 25);
+        assert_eq!(output.read::<U32Variable>(), 34);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_gadget_recursive() {
+        env_logger::try_init().unwrap_or_default();
+
+        let mut builder = CircuitBuilder::<L, D>::new();
+
+        let a = builder.read::<U32Variable>();
+        let b = builder.read::<U32Variable>();
+        let c = builder.read::<U32Variable>();
+
+        // The gadget circuit is built once, and each call on a new input verifies a proof of it.
+        let first = builder.call_gadget::<_, DefaultSerializer>(&RecursiveSumOfSquares, (a, b));
+        let second = builder.call_gadget::<_, DefaultSerializer>(&RecursiveSumOfSquares, (a, c));
+        let third = builder.call_gadget::<_, DefaultSerializer>(&RecursiveSumOfSquares, (b, c));
+        assert_eq!(builder.gadget_circuits.len(), 1);
+        assert_eq!(builder.gadget_cache.len(), 3);
+
+        let cached = builder.call_gadget::<_, DefaultSerializer>(&RecursiveSumOfSquares, (a, b));
+        builder.assert_is_equal(first, cached);
+
+        builder.write(first);
+        builder.write(second);
+        builder.write(third);
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        input.write::<U32Variable>(3);
+        input.write::<U32Variable>(4);
+        input.write::<U32Variable>(5);
+
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        assert_eq!(output.read::<U32Variable>(), 25);
+        assert_eq!(output.read::<U32Variable>(), 34);
+        assert_eq!(output.read::<U32Variable>(), 41);
+    }
+}
@@ -15,10 +15,15 @@ use crate::utils::serde::{
 };
 
 /// A schema for a circuit that uses bytes for input and output.
+///
+/// If `hashed` is set, the bytes themselves are not public inputs. Instead, the circuit exposes
+/// `sha256(input)` followed by `sha256(output)`, each as 32 field elements holding one byte.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BytesIO {
     pub input: Vec<ByteVariable>,
     pub output: Vec<ByteVariable>,
+    #[serde(default)]
+    pub hashed: bool,
 }
 
 /// A schema for a circuit that uses field elements for input and output.
@@ -90,6 +95,12 @@ impl<const D: usize> CircuitIO<D> {
         }
     }
 
+    /// Whether the circuit uses bytes io where only the sha256 digests of the input and output
+    /// are public.
+    pub fn is_hashed(&self) -> bool {
+        matches!(self, Self::Bytes(io) if io.hashed)
+    }
+
     pub fn set_witness<L: PlonkParameters<D>>(
         &self,
         pw: &mut PartialWitness<L::Field>,
@@ -102,6 +113,7 @@ impl<const D: usize> CircuitIO<D> {
             CircuitIO::Bytes(io) => {
                 let variables = &io.input;
                 if let PublicInput::Bytes(input) = input {
+                    assert_input_len(variables.len(), input.len(), "bytes");
                     for i in 0..variables.len() {
                         variables[i].set(pw, input[i]);
                     }
@@ -112,6 +124,7 @@ impl<const D: usize> CircuitIO<D> {
             CircuitIO::Elements(io) => {
                 let variables = &io.input;
                 if let PublicInput::Elements(input) = input {
+                    assert_input_len(variables.len(), input.len(), "field elements");
                     for i in 0..variables.len() {
                         variables[i].set(pw, input[i]);
                     }
@@ -123,6 +136,8 @@ impl<const D: usize> CircuitIO<D> {
                 let proof_with_pis_targets = &io.proof_input;
                 let variables = &io.input;
                 if let PublicInput::RecursiveProofs(proof_input, input) = input {
+                    assert_input_len(proof_with_pis_targets.len(), proof_input.len(), "proofs");
+                    assert_input_len(variables.len(), input.len(), "field elements");
                     for i in 0..proof_with_pis_targets.len() {
                         pw.set_proof_with_pis_target(&proof_with_pis_targets[i], &proof_input[i]);
                     }
@@ -136,6 +151,7 @@ impl<const D: usize> CircuitIO<D> {
             CircuitIO::CyclicProof(io) => {
                 let variables = &io.input;
                 if let PublicInput::CyclicProof(input, proof, verifier_data) = input {
+                    assert_input_len(variables.len(), input.len(), "field elements");
                     for i in 0..variables.len() {
                         variables[i].set(pw, input[i]);
                     }
@@ -154,6 +170,16 @@ impl<const D: usize> CircuitIO<D> {
     }
 }
 
+/// Panics with a descriptive message if the number of values written to the circuit input does
+/// not match the number of values read by the circuit.
+fn assert_input_len(expected: usize, actual: usize, kind: &str) {
+    assert_eq!(
+        expected, actual,
+        "circuit reads {} {} but the input contains {}",
+        expected, kind, actual
+    );
+}
+
 impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
     fn try_init_field_io(&mut self) {
         match self.io {
@@ -176,6 +202,7 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
                 self.io = CircuitIO::Bytes(BytesIO {
                     input: Vec::new(),
                     output: Vec::new(),
+                    hashed: false,
                 })
             }
             CircuitIO::Bytes(_) => {}
@@ -198,6 +225,22 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         };
     }
 
+    /// Uses bytes io where only `sha256(input)` and `sha256(output)` are registered as public
+    /// inputs. Must be called before any `evm_read` or `evm_write`.
+    pub fn use_hashed_evm_io(&mut self) {
+        match self.io {
+            CircuitIO::None() => {
+                self.io = CircuitIO::Bytes(BytesIO {
+                    input: Vec::new(),
+                    output: Vec::new(),
+                    hashed: true,
+                })
+            }
+            CircuitIO::Bytes(ref io) if io.hashed => {}
+            _ => panic!("other io used already"),
+        };
+    }
+
     pub fn use_cyclic_recursion(&mut self) {
         match self.io {
             CircuitIO::None() => {
@@ -262,6 +305,18 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         variable
     }
 
+    /// Reads the whole input of the circuit as a single variable, usually a struct deriving
+    /// `CircuitVariable`. The value type generated for the struct is then the typed input of the
+    /// circuit, passed to `CircuitBuild::typed_input`.
+    pub fn read_input<V: CircuitVariable>(&mut self) -> V {
+        self.try_init_field_io();
+        assert!(
+            self.io.input().is_empty(),
+            "read_input must be the only read of the circuit"
+        );
+        self.read::<V>()
+    }
+
     // @audit
     pub fn evm_read<V: EvmVariable>(&mut self) -> V {
         self.try_init_evm_io();
@@ -318,6 +373,18 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         }
     }
 
+    /// Writes the whole output of the circuit as a single variable, usually a struct deriving
+    /// `CircuitVariable`. The value type generated for the struct is then the typed output of the
+    /// circuit, returned by `PublicOutput::read_output`.
+    pub fn write_output<V: CircuitVariable>(&mut self, variable: V) {
+        self.try_init_field_io();
+        assert!(
+            self.io.output().is_empty(),
+            "write_output must be the only write of the circuit"
+        );
+        self.write(variable);
+    }
+
     // @audit
     pub fn evm_write<V: EvmVariable>(&mut self, variable: V) {
         self.try_init_evm_io();
@@ -337,3 +404,119 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use plonky2::field::types::PrimeField64;
+
+    use crate::prelude::*;
+
+    #[test]
+    #[should_panic(expected = "circuit reads 2 field elements but the input contains 3")]
+    fn test_input_too_long() {
+        let mut builder = DefaultBuilder::new();
+        let a = builder.read::<Variable>();
+        let b = builder.read::<Variable>();
+        let c = builder.add(a, b);
+        builder.write(c);
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        input.write::<Variable>(GoldilocksField::ONE);
+        input.write::<Variable>(GoldilocksField::ONE);
+        input.write::<Variable>(GoldilocksField::ONE);
+        circuit.prove(&input);
+    }
+
+    #[test]
+    #[should_panic(expected = "circuit reads 8 bytes but the input contains 4")]
+    fn test_evm_input_too_short() {
+        let mut builder = DefaultBuilder::new();
+        let a = builder.evm_read::<U64Variable>();
+        builder.evm_write(a);
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        input.evm_write::<U32Variable>(1);
+        circuit.prove(&input);
+    }
+
+    #[test]
+    fn test_hashed_evm_io() {
+        let mut builder = DefaultBuilder::new();
+        builder.use_hashed_evm_io();
+        let a = builder.evm_read::<U32Variable>();
+        let b = builder.evm_read::<U32Variable>();
+        let c = builder.add(a, b);
+        builder.evm_write(c);
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        input.evm_write::<U32Variable>(7);
+        input.evm_write::<U32Variable>(35);
+
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        // Only the two sha256 digests are public.
+        assert_eq!(proof.public_inputs.len(), 64);
+        let expected_input_hash = crate::utils::hash::sha256(&[0, 0, 0, 7, 0, 0, 0, 35]);
+        let input_hash = proof.public_inputs[..32]
+            .iter()
+            .map(|e| e.to_canonical_u64() as u8)
+            .collect::<Vec<_>>();
+        assert_eq!(input_hash, expected_input_hash.to_vec());
+
+        assert_eq!(output.evm_read::<U32Variable>(), 42);
+    }
+
+    #[derive(Debug, Clone, CircuitVariable)]
+    #[value_name(SwapInput)]
+    struct SwapInputVariable {
+        a: U64Variable,
+        b: U64Variable,
+    }
+
+    #[derive(Debug, Clone, CircuitVariable)]
+    #[value_name(SwapOutput)]
+    #[value_derive(PartialEq, Eq)]
+    struct SwapOutputVariable {
+        a: U64Variable,
+        b: U64Variable,
+        sum: U64Variable,
+    }
+
+    #[test]
+    fn test_typed_io() {
+        let mut builder = DefaultBuilder::new();
+        let input = builder.read_input::<SwapInputVariable>();
+        let sum = builder.add(input.a, input.b);
+        builder.write_output(SwapOutputVariable {
+            a: input.b,
+            b: input.a,
+            sum,
+        });
+
+        let circuit = builder.build();
+        let input = circuit.typed_input::<SwapInputVariable>(SwapInput { a: 3, b: 4 });
+
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        assert_eq!(
+            output.read_output::<SwapOutputVariable>(),
+            SwapOutput { a: 4, b: 3, sum: 7 }
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "the typed input does not match the input of the circuit")]
+    fn test_typed_input_mismatch() {
+        let mut builder = DefaultBuilder::new();
+        let input = builder.read_input::<SwapInputVariable>();
+        builder.write(input.a);
+
+        let circuit = builder.build();
+        circuit.typed_input::<U64Variable>(3);
+    }
+}
@@ -0,0 +1,267 @@
+use alloc::sync::Arc;
+
+use plonky2::field::types::{Field, PrimeField64};
+use serde::{Deserialize, Serialize};
+
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::builder::CircuitBuilder;
+use crate::frontend::hint::simple::hint::Hint;
+use crate::frontend::uint::uint8::U8Variable;
+use crate::frontend::vars::{CircuitVariable, ValueStream, Variable, VariableStream};
+
+/// Decomposes each input value into little-endian bytes, `nb_limbs[i]` bytes for the i-th value.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ByteDecompositionHint {
+    nb_limbs: Vec<usize>,
+}
+
+impl<L: PlonkParameters<D>, const D: usize> Hint<L, D> for ByteDecompositionHint {
+    fn hint(&self, input_stream: &mut ValueStream<L, D>, output_stream: &mut ValueStream<L, D>) {
+        for nb_limbs in self.nb_limbs.iter() {
+            let value = input_stream.read_value::<Variable>().to_canonical_u64();
+            for i in 0..*nb_limbs {
+                output_stream.write_value::<U8Variable>((value >> (8 * i)) as u8);
+            }
+        }
+    }
+}
+
+/// A handle to a lookup table that has been added to the circuit.
+///
+/// Lookup tables map 16-bit inputs to 16-bit outputs, and can be used to replace expensive
+/// computations such as S-boxes or bitwise operations with a single lookup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LookupTable {
+    index: usize,
+    max_input: u16,
+    max_output: u16,
+}
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Adds a lookup table with the given `(input, output)` pairs to the circuit.
+    pub fn add_lookup_table(&mut self, pairs: Vec<(u16, u16)>) -> LookupTable {
+        assert!(!pairs.is_empty(), "lookup tables cannot be empty");
+        let max_input = pairs.iter().map(|(input, _)| *input).max().unwrap();
+        let max_output = pairs.iter().map(|(_, output)| *output).max().unwrap();
+        let index = self.api.add_lookup_table_from_pairs(Arc::new(pairs));
+        LookupTable {
+            index,
+            max_input,
+            max_output,
+        }
+    }
+
+    /// Adds a lookup table mapping each of the `inputs` to `f(input)` to the circuit.
+    pub fn add_lookup_table_from_fn(&mut self, inputs: &[u16], f: fn(u16) -> u16) -> LookupTable {
+        let pairs = inputs.iter().map(|input| (*input, f(*input))).collect();
+        self.add_lookup_table(pairs)
+    }
+
+    /// Returns the output of `table` for `input`. The proof fails if `input` is not in the table.
+    pub fn lookup(&mut self, table: LookupTable, input: Variable) -> Variable {
+        Variable(self.api.add_lookup_from_index(input.0, table.index))
+    }
+
+    /// Returns the output of `table` for a byte `input`. The table must only map bytes to bytes.
+    pub fn lookup_u8(&mut self, table: LookupTable, input: U8Variable) -> U8Variable {
+        assert!(
+            table.max_input <= u8::MAX as u16 && table.max_output <= u8::MAX as u16,
+            "table does not map bytes to bytes"
+        );
+        let output = self.lookup(table, input.variable);
+
+        // Every output of the table is a byte.
+        U8Variable::from_variables_unsafe(&[output])
+    }
+
+    /// Returns the index of the lookup table containing every byte value, adding the table to the
+    /// circuit the first time it is requested.
+    fn byte_lookup_table(&mut self) -> usize {
+        if let Some(index) = self.byte_lookup_table {
+            return index;
+        }
+        let table = Arc::new((0..256u16).map(|i| (i, i)).collect::<Vec<_>>());
+        let index = self.api.add_lookup_table_from_pairs(table);
+        self.byte_lookup_table = Some(index);
+        index
+    }
+
+    /// Constrains `variable` to be in the range `[0, 256)` using a single lookup into the byte
+    /// table, which is much cheaper than decomposing the value into bits.
+    pub fn range_check_u8(&mut self, variable: Variable) {
+        let index = self.byte_lookup_table();
+        let output = self.api.add_lookup_from_index(variable.0, index);
+        self.api.connect(output, variable.0);
+    }
+
+    /// Constrains `variable` to be in the range `[0, 2^nb_bits)`.
+    ///
+    /// The check is deferred until the circuit is built, at which point all pending range checks
+    /// are decomposed into bytes by a single hint and checked against the shared byte table.
+    pub fn range_check(&mut self, variable: Variable, nb_bits: usize) {
+        assert!(
+            nb_bits > 0 && nb_bits < 64,
+            "range checks must be between 1 and 63 bits"
+        );
+        self.range_checks.push((variable, nb_bits));
+    }
+
+    /// Adds the constraints for every range check registered with `range_check`.
+    pub(crate) fn constrain_range_checks(&mut self) {
+        if self.range_checks.is_empty() {
+            return;
+        }
+        let range_checks = core::mem::take(&mut self.range_checks);
+
+        let mut input_stream = VariableStream::new();
+        let mut nb_limbs = Vec::with_capacity(range_checks.len());
+        for (variable, nb_bits) in range_checks.iter() {
+            input_stream.write(variable);
+            nb_limbs.push((nb_bits + 7) / 8);
+        }
+        let output_stream = self.hint(
+            input_stream,
+            ByteDecompositionHint {
+                nb_limbs: nb_limbs.clone(),
+            },
+        );
+
+        for ((variable, nb_bits), nb_limbs) in range_checks.into_iter().zip(nb_limbs) {
+            // Reading a U8Variable range checks it to a byte through the lookup table.
+            let limbs = (0..nb_limbs)
+                .map(|_| output_stream.read::<U8Variable>(self))
+                .collect::<Vec<_>>();
+
+            // The most significant limb only holds `top_bits` bits. Since it is already a byte,
+            // scaling it by 2^(8 - top_bits) stays below the field order, and the scaled value is a
+            // byte exactly when the limb fits in `top_bits` bits.
+            let top_bits = nb_bits - 8 * (nb_limbs - 1);
+            if top_bits < 8 {
+                let scale =
+                    self.constant::<Variable>(L::Field::from_canonical_u64(1 << (8 - top_bits)));
+                let scaled = self.mul(limbs[nb_limbs - 1].variable, scale);
+                self.range_check_u8(scaled);
+            }
+
+            // Since nb_bits < 64, the weighted sum of the limbs cannot wrap around the field.
+            let mut sum = self.zero::<Variable>();
+            for (i, limb) in limbs.iter().enumerate() {
+                let weight = self.constant::<Variable>(L::Field::from_canonical_u64(1 << (8 * i)));
+                let term = self.mul(limb.variable, weight);
+                sum = self.add(sum, term);
+            }
+            self.assert_is_equal(sum, variable);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use plonky2::field::types::Field;
+
+    use crate::prelude::*;
+
+    type L = DefaultParameters;
+    type F = GoldilocksField;
+    const D: usize = 2;
+
+    #[test]
+    fn test_range_check_u8() {
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let a = builder.init::<Variable>();
+        let b = builder.init::<Variable>();
+        builder.range_check_u8(a);
+        builder.range_check_u8(b);
+
+        let circuit = builder.build();
+        let mut pw = PartialWitness::new();
+        a.set(&mut pw, F::from_canonical_u8(0));
+        b.set(&mut pw, F::from_canonical_u8(255));
+        let proof = circuit.data.prove(pw).unwrap();
+        circuit.data.verify(proof).unwrap();
+    }
+
+    #[test]
+    fn test_lookup_tables() {
+        let mut builder = CircuitBuilder::<L, D>::new();
+
+        // A nibble XOR table, keyed by `(a << 4) | b`.
+        let xor_table = builder
+            .add_lookup_table_from_fn(&(0..256).collect::<Vec<_>>(), |x| (x >> 4) ^ (x & 0xf));
+        let sbox_table =
+            builder.add_lookup_table_from_fn(&(0..256).collect::<Vec<_>>(), |x| (x * 7 + 3) % 256);
+        let times_ten_table = builder.add_lookup_table(vec![(3, 30), (300, 3000), (4000, 40000)]);
+
+        let a = builder.read::<U8Variable>();
+        let xor = builder.lookup_u8(xor_table, a);
+        let sbox = builder.lookup_u8(sbox_table, a);
+        let b = builder.read::<Variable>();
+        let times_ten = builder.lookup(times_ten_table, b);
+        builder.write(xor);
+        builder.write(sbox);
+        builder.write(times_ten);
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        input.write::<U8Variable>(0xa3);
+        input.write::<Variable>(F::from_canonical_u16(300));
+
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        assert_eq!(output.read::<U8Variable>(), 0xa ^ 0x3);
+        assert_eq!(output.read::<U8Variable>(), ((0xa3 * 7 + 3) % 256) as u8);
+        assert_eq!(output.read::<Variable>(), F::from_canonical_u16(3000));
+    }
+
+    #[test]
+    fn test_range_check() {
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let a = builder.init::<Variable>();
+        let b = builder.init::<Variable>();
+        let c = builder.init::<Variable>();
+        builder.range_check(a, 1);
+        builder.range_check(b, 12);
+        builder.range_check(c, 63);
+
+        let circuit = builder.build();
+        let mut pw = PartialWitness::new();
+        a.set(&mut pw, F::ONE);
+        b.set(&mut pw, F::from_canonical_u16((1 << 12) - 1));
+        c.set(&mut pw, F::from_canonical_u64((1 << 63) - 1));
+        let proof = circuit.data.prove(pw).unwrap();
+        circuit.data.verify(proof).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds")]
+    fn test_range_check_fails() {
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let a = builder.init::<Variable>();
+        builder.range_check(a, 12);
+
+        let circuit = builder.build();
+        let mut pw = PartialWitness::new();
+        // The scaled top limb is 256, which is past the end of the byte table that the lookup
+        // generator indexes.
+        a.set(&mut pw, F::from_canonical_u16(1 << 12));
+        let proof = circuit.data.prove(pw).unwrap();
+        circuit.data.verify(proof).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds")]
+    fn test_range_check_u8_fails() {
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let a = builder.init::<Variable>();
+        builder.range_check_u8(a);
+
+        let circuit = builder.build();
+        let mut pw = PartialWitness::new();
+        // The lookup generator indexes the byte table by the input, so a value past the end of the
+        // table fails the witness generation.
+        a.set(&mut pw, F::from_canonical_u16(256));
+        let proof = circuit.data.prove(pw).unwrap();
+        circuit.data.verify(proof).unwrap();
+    }
+}
@@ -1,7 +1,14 @@
+pub mod bits;
 mod boolean;
+mod bytes;
+mod combinators;
+pub mod gadget;
 pub mod io;
+pub mod lookup;
 pub mod permutation;
+pub mod profile;
 mod proof;
+pub mod sort;
 pub mod watch;
 
 use alloc::collections::BTreeMap;
@@ -12,6 +19,7 @@ use backtrace::Backtrace;
 use ethers::providers::{Http, Middleware, Provider};
 use ethers::types::U256;
 use itertools::Itertools;
+use log::{log_enabled, Level};
 use plonky2::iop::generator::{SimpleGenerator, WitnessGeneratorRef};
 use plonky2::iop::target::{BoolTarget, Target};
 use plonky2::plonk::circuit_builder::CircuitBuilder as CircuitAPI;
@@ -22,10 +30,13 @@ use starkyx::machine::hash::sha::sha512::SHA512;
 use tokio::runtime::Runtime;
 
 pub use self::io::CircuitIO;
+use self::profile::ContextStats;
 use super::ecc::curve25519::curta::accelerator::EcOpAccelerator;
 use super::hash::blake2::curta::BLAKE2BAccelerator;
+use super::hash::keccak::lookup::KeccakLookupTables;
 use super::hash::sha::sha256::curta::SHA256Accelerator;
 use super::hash::sha::sha512::curta::SHA512Accelerator;
+use super::hash::HashBackend;
 use super::hint::HintGenerator;
 use super::vars::EvmVariable;
 use crate::backend::circuit::{CircuitBuild, DefaultParameters, MockCircuitBuild, PlonkParameters};
@@ -43,14 +54,25 @@ pub struct CircuitBuilder<L: PlonkParameters<D>, const D: usize> {
     pub beacon_client: Option<BeaconClient>,
     pub debug: bool,
     pub debug_variables: HashMap<usize, String>,
+    pub(crate) named_variables: Vec<(String, Vec<Variable>)>,
     pub(crate) hints: Vec<Box<dyn HintGenerator<L, D>>>,
     pub(crate) async_hints: Vec<AsyncHintDataRef<L, D>>,
     pub(crate) async_hints_indices: Vec<usize>,
+    pub(crate) byte_lookup_table: Option<usize>,
+    pub(crate) range_checks: Vec<(Variable, usize)>,
+    pub(crate) gadget_cache: HashMap<(String, Vec<Variable>), Vec<Variable>>,
+    pub(crate) gadget_circuits: HashMap<String, CircuitBuild<L, D>>,
+    pub(crate) context_stack: Vec<(String, usize)>,
+    pub(crate) context_stats: Vec<ContextStats>,
 
     pub blake2b_accelerator: Option<BLAKE2BAccelerator>,
     pub sha256_accelerator: Option<SHA256Accelerator>,
     pub sha512_accelerator: Option<SHA512Accelerator>,
     pub ec_25519_ops_accelerator: Option<EcOpAccelerator>,
+
+    pub(crate) sha256_backend: HashBackend,
+    pub(crate) keccak256_backend: HashBackend,
+    pub(crate) keccak_lookup_tables: Option<KeccakLookupTables>,
 }
 
 /// The universal api for building circuits using `plonky2x` with default parameters.
@@ -76,13 +98,23 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
             chain_id: None,
             debug: false,
             debug_variables: HashMap::new(),
+            named_variables: Vec::new(),
             hints: Vec::new(),
             async_hints: Vec::new(),
             async_hints_indices: Vec::new(),
+            byte_lookup_table: None,
+            range_checks: Vec::new(),
+            gadget_cache: HashMap::new(),
+            gadget_circuits: HashMap::new(),
+            context_stack: Vec::new(),
+            context_stats: Vec::new(),
             blake2b_accelerator: None,
             sha256_accelerator: None,
             sha512_accelerator: None,
             ec_25519_ops_accelerator: None,
+            sha256_backend: HashBackend::default(),
+            keccak256_backend: HashBackend::default(),
+            keccak_lookup_tables: None,
         };
 
         if let Ok(rpc_url) = env::var("CONSENSUS_RPC_URL") {
@@ -110,6 +142,12 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         }
     }
 
+    /// Names `variable`, so that its value is in the `WitnessReport` of a witness of the circuit.
+    pub fn name_variable<V: CircuitVariable>(&mut self, name: &str, variable: &V) {
+        self.named_variables
+            .push((String::from(name), variable.variables()));
+    }
+
     pub fn set_execution_client(&mut self, client: Provider<Http>) {
         let rt = Runtime::new().expect("failed to create tokio runtime");
         let result: U256 = rt.block_on(async {
@@ -131,26 +169,79 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         self.beacon_client = Some(client);
     }
 
+    /// Selects the implementation used by `sha256`, `sha256_variable` and `sha256_pair`.
+    pub fn set_sha256_backend(&mut self, backend: HashBackend) {
+        assert!(
+            backend != HashBackend::Lookup,
+            "sha256 does not have a lookup backend"
+        );
+        self.sha256_backend = backend;
+    }
+
+    /// Selects the implementation used by `keccak256` and `keccak256_variable`.
+    pub fn set_keccak256_backend(&mut self, backend: HashBackend) {
+        assert!(
+            backend != HashBackend::Curta,
+            "keccak256 does not have a STARK backend"
+        );
+        self.keccak256_backend = backend;
+    }
+
     /// Adds all the constraints nedded before building the circuit and registering hints.
     fn pre_build(&mut self) {
+        assert!(
+            self.context_stack.is_empty(),
+            "push_context called without a matching pop_context"
+        );
+
+        // The digests are computed first so that their sha256 requests are constrained by the
+        // accelerator below.
+        let io_digests = match self.io {
+            CircuitIO::Bytes(ref io) if io.hashed => {
+                let (input, output) = (io.input.clone(), io.output.clone());
+                let input_hash = self.curta_sha256(&input);
+                let output_hash = self.curta_sha256(&output);
+                let digests = input_hash
+                    .as_bytes()
+                    .iter()
+                    .chain(output_hash.as_bytes().iter())
+                    .map(|byte| byte.to_variable(self))
+                    .collect::<Vec<_>>();
+                Some(digests)
+            }
+            _ => None,
+        };
+
+        if !self.range_checks.is_empty() {
+            self.with_context("range_checks", |builder| builder.constrain_range_checks());
+        }
+
         let blake2b_accelerator = self.blake2b_accelerator.clone();
         if let Some(accelerator) = blake2b_accelerator {
-            self.curta_constrain_hash::<BLAKE2B, 96, true, 4>(accelerator);
+            self.with_context("blake2b_accelerator", |builder| {
+                builder.curta_constrain_hash::<BLAKE2B, 96, true, 4>(accelerator)
+            });
         }
 
         let sha256_accelerator = self.sha256_accelerator.clone();
         if let Some(accelerator) = sha256_accelerator {
-            self.curta_constrain_hash::<SHA256, 64, false, 8>(accelerator);
+            self.with_context("sha256_accelerator", |builder| {
+                builder.curta_constrain_hash::<SHA256, 64, false, 8>(accelerator)
+            });
         }
 
         let sha512_accelerator = self.sha512_accelerator.clone();
         if let Some(accelerator) = sha512_accelerator {
-            self.curta_constrain_hash::<SHA512, 80, false, 8>(accelerator);
+            self.with_context("sha512_accelerator", |builder| {
+                builder.curta_constrain_hash::<SHA512, 80, false, 8>(accelerator)
+            });
         }
 
         let ec_ops_accelerator = self.ec_25519_ops_accelerator.clone();
         if let Some(accelerator) = ec_ops_accelerator {
-            self.curta_constrain_ec_op(accelerator);
+            self.with_context("ec_25519_ops_accelerator", |builder| {
+                builder.curta_constrain_ec_op(accelerator)
+            });
         }
 
         for (index, gen_ref) in self
@@ -171,6 +262,9 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         self.api.add_generators(generators);
 
         match self.io {
+            CircuitIO::Bytes(_) if io_digests.is_some() => {
+                self.register_public_inputs(io_digests.as_ref().unwrap());
+            }
             CircuitIO::Bytes(ref io) => {
                 let input = io
                     .input
@@ -244,11 +338,16 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         self.pre_build();
         let data = self.api.build();
         let async_hints = Self::async_hint_map(&data.prover_only.generators, self.async_hints);
-        CircuitBuild {
+        let circuit = CircuitBuild {
             data,
             io: self.io,
             async_hints,
+            named_variables: self.named_variables,
+        };
+        if log_enabled!(Level::Debug) {
+            circuit.log_context_profile(&self.context_stats);
         }
+        circuit
     }
 
     /// Try to build the circuit, returning data and success. If it fails due to unexpected cyclic
@@ -262,6 +361,7 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
                 data,
                 io: self.io,
                 async_hints,
+                named_variables: self.named_variables,
             },
             success,
         )
@@ -344,7 +444,8 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
     }
 
     // @audit
-    /// If selector is true, yields i1 else yields i2.
+    /// If selector is true, yields i1 else yields i2. Works for any `CircuitVariable` by muxing
+    /// each of its underlying targets.
     pub fn select<V: CircuitVariable>(&mut self, selector: BoolVariable, i1: V, i2: V) -> V {
         assert_eq!(i1.targets().len(), i2.targets().len());
         let mut targets = Vec::new();
@@ -357,12 +458,16 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         V::from_targets(&targets)
     }
 
-    /// Returns 1 if i1 is zero, 0 otherwise as a boolean.
+    /// Returns 1 if every underlying variable of i1 is zero, 0 otherwise as a boolean.
     #[must_use]
-    pub fn is_zero(&mut self, i1: Variable) -> BoolVariable {
+    pub fn is_zero<V: CircuitVariable>(&mut self, i1: V) -> BoolVariable {
         let zero = self.api.zero();
-
-        self.api.is_equal(i1.0, zero).into()
+        let mut result = self._true();
+        for t in i1.targets() {
+            let target_is_zero: BoolVariable = self.api.is_equal(t, zero).into();
+            result = self.and(target_is_zero, result);
+        }
+        result
     }
 
     /// Fails if i1 != i2.
@@ -372,6 +477,28 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         }
     }
 
+    /// Fails if `condition` is true and i1 != i2. Does nothing if `condition` is false.
+    pub fn assert_is_equal_if<V: CircuitVariable>(
+        &mut self,
+        condition: BoolVariable,
+        i1: V,
+        i2: V,
+    ) {
+        assert_eq!(i1.targets().len(), i2.targets().len());
+        for (t1, t2) in i1.targets().iter().zip(i2.targets().iter()) {
+            // condition * (t1 - t2) == 0
+            let diff = self.api.sub(*t1, *t2);
+            let masked_diff = self.api.mul(condition.variable.0, diff);
+            self.api.assert_zero(masked_diff);
+        }
+    }
+
+    /// Fails if `condition` is true and `value` is false.
+    pub fn assert_true_if(&mut self, condition: BoolVariable, value: BoolVariable) {
+        let true_v = self._true();
+        self.assert_is_equal_if(condition, value, true_v);
+    }
+
     /// Returns 1 if i1 == i2 and 0 otherwise as a BoolVariable.
     #[must_use]
     pub fn is_equal<V: CircuitVariable>(&mut self, i1: V, i2: V) -> BoolVariable {
@@ -411,6 +538,7 @@ impl<L: PlonkParameters<D>, const D: usize> Default for CircuitBuilder<L, D> {
 #[cfg(test)]
 pub(crate) mod tests {
 
+    use ethers::types::U256;
     use log::debug;
 
     use crate::prelude::*;
@@ -473,4 +601,119 @@ pub(crate) mod tests {
         let xor = output.evm_read::<ByteVariable>();
         debug!("{}", xor);
     }
+
+    #[test]
+    fn test_is_equal_and_is_zero() {
+        let mut builder = DefaultBuilder::new();
+
+        let a = builder.read::<U256Variable>();
+        let b = builder.read::<U256Variable>();
+        let c = builder.read::<U256Variable>();
+
+        let a_eq_b = builder.is_equal(a, b);
+        let a_eq_c = builder.is_equal(a, c);
+        let a_is_zero = builder.is_zero(a);
+        let c_is_zero = builder.is_zero(c);
+        builder.write(a_eq_b);
+        builder.write(a_eq_c);
+        builder.write(a_is_zero);
+        builder.write(c_is_zero);
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        input.write::<U256Variable>(U256::from(1) << 128);
+        input.write::<U256Variable>(U256::from(1) << 128);
+        input.write::<U256Variable>(U256::zero());
+
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        assert!(output.read::<BoolVariable>());
+        assert!(!output.read::<BoolVariable>());
+        assert!(!output.read::<BoolVariable>());
+        assert!(output.read::<BoolVariable>());
+    }
+
+    #[test]
+    fn test_assert_is_equal_if() {
+        let mut builder = DefaultBuilder::new();
+
+        let condition = builder.read::<BoolVariable>();
+        let a = builder.read::<U256Variable>();
+        let b = builder.read::<U256Variable>();
+        let a_eq_b = builder.is_equal(a, b);
+        builder.assert_is_equal_if(condition, a, b);
+        builder.assert_true_if(condition, a_eq_b);
+
+        let circuit = builder.build();
+
+        // When the condition is false, the values are allowed to differ.
+        let mut input = circuit.input();
+        input.write::<BoolVariable>(false);
+        input.write::<U256Variable>(U256::from(1));
+        input.write::<U256Variable>(U256::from(2));
+        let (proof, output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        let mut input = circuit.input();
+        input.write::<BoolVariable>(true);
+        input.write::<U256Variable>(U256::from(3));
+        input.write::<U256Variable>(U256::from(3));
+        let (proof, output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+    }
+
+    #[test]
+    #[should_panic(expected = "was set twice with different values")]
+    fn test_assert_is_equal_if_fails() {
+        let mut builder = DefaultBuilder::new();
+
+        let condition = builder.read::<BoolVariable>();
+        let a = builder.read::<U256Variable>();
+        let b = builder.read::<U256Variable>();
+        builder.assert_is_equal_if(condition, a, b);
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        input.write::<BoolVariable>(true);
+        input.write::<U256Variable>(U256::from(1));
+        input.write::<U256Variable>(U256::from(2));
+        let (proof, output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+    }
+
+    #[test]
+    fn test_select() {
+        let mut builder = DefaultBuilder::new();
+
+        let selector = builder.read::<BoolVariable>();
+        let a = builder.read::<(U256Variable, ArrayVariable<ByteVariable, 3>)>();
+        let b = builder.read::<(U256Variable, ArrayVariable<ByteVariable, 3>)>();
+
+        let selected = builder.select(selector, a.clone(), b.clone());
+        let not_selector = builder.not(selector);
+        let not_selected = builder.select(not_selector, a, b);
+        builder.write(selected);
+        builder.write(not_selected);
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        let a_value = (U256::from(12345), vec![1u8, 2, 3]);
+        let b_value = (U256::MAX, vec![4u8, 5, 6]);
+        input.write::<BoolVariable>(true);
+        input.write::<(U256Variable, ArrayVariable<ByteVariable, 3>)>(a_value.clone());
+        input.write::<(U256Variable, ArrayVariable<ByteVariable, 3>)>(b_value.clone());
+
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        assert_eq!(
+            output.read::<(U256Variable, ArrayVariable<ByteVariable, 3>)>(),
+            a_value
+        );
+        assert_eq!(
+            output.read::<(U256Variable, ArrayVariable<ByteVariable, 3>)>(),
+            b_value
+        );
+    }
 }
@@ -1,3 +1,5 @@
+use plonky2::hash::poseidon::PoseidonHash;
+use plonky2::iop::challenger::RecursiveChallenger;
 use plonky2::plonk::config::{AlgebraicHasher, GenericConfig};
 use serde::{Deserialize, Serialize};
 
@@ -5,7 +7,7 @@ use super::CircuitBuilder;
 use crate::frontend::extension::CubicExtensionVariable;
 use crate::frontend::hint::simple::hint::Hint;
 use crate::frontend::uint::uint32::U32Variable;
-use crate::frontend::vars::{EvmVariable, ValueStream, VariableStream};
+use crate::frontend::vars::{CircuitVariable, EvmVariable, ValueStream, VariableStream};
 use crate::prelude::{ArrayVariable, PlonkParameters};
 use crate::utils::hash::sha256;
 
@@ -40,6 +42,61 @@ impl<L: PlonkParameters<D>, const D: usize, const B: usize> Hint<L, D>
 }
 
 impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Asserts that `b` is a permutation of `a`, i.e. both contain the same elements with the same
+    /// multiplicities.
+    ///
+    /// Each element is compressed into the cubic extension as `sum_j alpha^j * v_j` and both sides
+    /// are compared as `prod_i (gamma - a_i) == prod_i (gamma - b_i)`, where `alpha` and `gamma`
+    /// are derived in-circuit from both arrays.
+    pub fn assert_permutation<V: CircuitVariable>(&mut self, a: &[V], b: &[V]) {
+        assert_eq!(a.len(), b.len(), "arrays have different lengths");
+
+        let mut challenger = RecursiveChallenger::<L::Field, PoseidonHash, D>::new(&mut self.api);
+        let targets = a
+            .iter()
+            .chain(b.iter())
+            .flat_map(|v| v.targets())
+            .collect::<Vec<_>>();
+        challenger.observe_elements(&targets);
+        let challenges = challenger.get_n_challenges(&mut self.api, 6);
+        let alpha = CubicExtensionVariable::new(
+            challenges[0].into(),
+            challenges[1].into(),
+            challenges[2].into(),
+        );
+        let gamma = CubicExtensionVariable::new(
+            challenges[3].into(),
+            challenges[4].into(),
+            challenges[5].into(),
+        );
+
+        let a_product = self.grand_product(a, alpha, gamma);
+        let b_product = self.grand_product(b, alpha, gamma);
+        self.assert_is_equal(a_product, b_product);
+    }
+
+    fn grand_product<V: CircuitVariable>(
+        &mut self,
+        values: &[V],
+        alpha: CubicExtensionVariable,
+        gamma: CubicExtensionVariable,
+    ) -> CubicExtensionVariable {
+        let mut product = self.one::<CubicExtensionVariable>();
+        for value in values {
+            let mut compressed = self.zero::<CubicExtensionVariable>();
+            let mut power = self.one::<CubicExtensionVariable>();
+            for variable in value.variables() {
+                let variable = variable.as_cubic_extension(self);
+                let term = self.mul(power, variable);
+                compressed = self.add(compressed, term);
+                power = self.mul(power, alpha);
+            }
+            let factor = self.sub(gamma, compressed);
+            product = self.mul(product, factor);
+        }
+        product
+    }
+
     // @no-audit-okay
     pub fn permute_with_dummy<const B: usize>(
         &mut self,
@@ -156,4 +213,37 @@ pub(crate) mod tests {
         let (proof, output) = circuit.prove(&input);
         circuit.verify(&proof, &input, &output);
     }
+
+    #[test]
+    fn test_assert_permutation() {
+        let mut builder = DefaultBuilder::new();
+        let a = builder.read::<ArrayVariable<U64Variable, 5>>();
+        let b = builder.read::<ArrayVariable<U64Variable, 5>>();
+        builder.assert_permutation(a.as_slice(), b.as_slice());
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        input.write::<ArrayVariable<U64Variable, 5>>(vec![1, 2, 2, 1 << 40, 5]);
+        input.write::<ArrayVariable<U64Variable, 5>>(vec![2, 1 << 40, 5, 2, 1]);
+
+        let (proof, output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+    }
+
+    #[test]
+    #[should_panic(expected = "was set twice with different values")]
+    fn test_assert_permutation_different_multiplicities() {
+        let mut builder = DefaultBuilder::new();
+        let a = builder.read::<ArrayVariable<U32Variable, 4>>();
+        let b = builder.read::<ArrayVariable<U32Variable, 4>>();
+        builder.assert_permutation(a.as_slice(), b.as_slice());
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        input.write::<ArrayVariable<U32Variable, 4>>(vec![1, 1, 2, 3]);
+        input.write::<ArrayVariable<U32Variable, 4>>(vec![1, 2, 2, 3]);
+
+        let (proof, output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+    }
 }
@@ -0,0 +1,208 @@
+use alloc::collections::BTreeMap;
+use core::ops::Range;
+
+use log::debug;
+use plonky2::field::types::PrimeField64;
+use plonky2::gates::gate::Gate;
+
+use crate::backend::circuit::{CircuitBuild, PlonkParameters};
+use crate::frontend::builder::CircuitBuilder;
+
+/// The rows of the circuit added within a labeled region.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContextStats {
+    /// The labels of the enclosing regions and of the region itself, joined with `/`.
+    pub path: String,
+    /// The number of enclosing regions.
+    pub depth: usize,
+    /// The rows of the trace added within the region, including nested regions. Every gate
+    /// occupies one row.
+    pub rows: Range<usize>,
+}
+
+impl ContextStats {
+    /// The number of gates (i.e. rows of the trace) added within the region.
+    pub fn nb_gates(&self) -> usize {
+        self.rows.len()
+    }
+}
+
+/// The rows and constraints of a labeled region in a built circuit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContextProfile {
+    /// The labels of the enclosing regions and of the region itself, joined with `/`.
+    pub path: String,
+    /// The number of enclosing regions.
+    pub depth: usize,
+    /// The number of rows of the trace used by the region.
+    pub nb_rows: usize,
+    /// The number of gate constraints enforced on the rows of the region. Copy constraints are
+    /// enforced by the permutation argument over the whole trace and are not counted.
+    pub nb_constraints: usize,
+    /// The number of rows used by each kind of gate, keyed by the gate id.
+    pub gates: BTreeMap<String, usize>,
+}
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Starts a labeled region. Every gate added until the matching `pop_context` is attributed to
+    /// this region.
+    pub fn push_context(&mut self, name: &str) {
+        let nb_gates = self.api.num_gates();
+        self.context_stack.push((name.to_string(), nb_gates));
+    }
+
+    /// Ends the most recently started region and records its statistics.
+    pub fn pop_context(&mut self) {
+        let (name, start) = self
+            .context_stack
+            .pop()
+            .expect("pop_context called without a matching push_context");
+        let path = self
+            .context_stack
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .chain(std::iter::once(name.as_str()))
+            .collect::<Vec<_>>()
+            .join("/");
+        self.context_stats.push(ContextStats {
+            path,
+            depth: self.context_stack.len(),
+            rows: start..self.api.num_gates(),
+        });
+    }
+
+    /// Runs `f` within a labeled region and returns its result.
+    pub fn with_context<T>(&mut self, name: &str, f: impl FnOnce(&mut Self) -> T) -> T {
+        self.push_context(name);
+        let result = f(self);
+        self.pop_context();
+        result
+    }
+
+    /// Returns the statistics of every completed region, in the order the regions ended.
+    pub fn context_report(&self) -> &[ContextStats] {
+        &self.context_stats
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuild<L, D> {
+    /// Returns the rows and constraints of each region of `stats`, which were recorded by the
+    /// builder of this circuit.
+    ///
+    /// The gate of every row is recovered from the selector polynomials of the circuit: the
+    /// selector of the group of a gate evaluates to the index of the gate on the rows it occupies.
+    pub fn context_profile(&self, stats: &[ContextStats]) -> Vec<ContextProfile> {
+        let gates = &self.data.common.gates;
+        let nb_selectors = self.data.common.selectors_info.num_selectors();
+        let selectors = self
+            .data
+            .prover_only
+            .constants_sigmas_commitment
+            .polynomials[..nb_selectors]
+            .iter()
+            .map(|polynomial| polynomial.clone().fft())
+            .collect::<Vec<_>>();
+        // Selectors of other groups hold a sentinel value that is larger than any gate index.
+        let row_gate = |row: usize| {
+            selectors
+                .iter()
+                .map(|selector| selector.values[row].to_canonical_u64() as usize)
+                .find(|&index| index < gates.len())
+                .expect("row is not used by any gate")
+        };
+
+        stats
+            .iter()
+            .map(|stats| {
+                let mut profile = ContextProfile {
+                    path: stats.path.clone(),
+                    depth: stats.depth,
+                    nb_rows: stats.nb_gates(),
+                    nb_constraints: 0,
+                    gates: BTreeMap::new(),
+                };
+                for row in stats.rows.clone() {
+                    let gate = &gates[row_gate(row)].0;
+                    profile.nb_constraints += gate.num_constraints();
+                    *profile.gates.entry(gate.id()).or_insert(0) += 1;
+                }
+                profile
+            })
+            .collect()
+    }
+
+    /// Logs the rows and constraints of each region of `stats` at the debug level.
+    pub fn log_context_profile(&self, stats: &[ContextStats]) {
+        if stats.is_empty() {
+            return;
+        }
+        debug!(
+            "rows and constraints per context (total rows: {}):",
+            self.data.common.degree()
+        );
+        for profile in self.context_profile(stats) {
+            debug!(
+                "{}{}: {} rows, {} constraints",
+                "  ".repeat(profile.depth),
+                profile.path,
+                profile.nb_rows,
+                profile.nb_constraints
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    type L = DefaultParameters;
+    const D: usize = 2;
+
+    #[test]
+    fn test_context_report() {
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let a = builder.read::<U256Variable>();
+        let b = builder.read::<U256Variable>();
+
+        builder.push_context("outer");
+        let sum = builder.add(a, b);
+        let product = builder.with_context("mul", |builder| builder.mul(sum, b));
+        builder.pop_context();
+        builder.write(product);
+
+        let report = builder.context_report().to_vec();
+        assert_eq!(report.len(), 2);
+        assert_eq!(report[0].path, "outer/mul");
+        assert_eq!(report[0].depth, 1);
+        assert_eq!(report[1].path, "outer");
+        assert_eq!(report[1].depth, 0);
+        assert!(report[0].nb_gates() > 0);
+        assert!(report[1].nb_gates() >= report[0].nb_gates());
+
+        let circuit = builder.build();
+
+        let profile = circuit.context_profile(&report);
+        assert_eq!(profile.len(), 2);
+        for (profile, stats) in profile.iter().zip(report.iter()) {
+            assert_eq!(profile.path, stats.path);
+            assert_eq!(profile.nb_rows, stats.nb_gates());
+            assert_eq!(profile.gates.values().sum::<usize>(), profile.nb_rows);
+            assert!(profile.nb_constraints > 0);
+        }
+        assert!(profile[1].nb_constraints >= profile[0].nb_constraints);
+        let mut input = circuit.input();
+        input.write::<U256Variable>(3.into());
+        input.write::<U256Variable>(4.into());
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+        assert_eq!(output.read::<U256Variable>(), 28.into());
+    }
+
+    #[test]
+    #[should_panic(expected = "pop_context called without a matching push_context")]
+    fn test_pop_context_without_push() {
+        let mut builder = CircuitBuilder::<L, D>::new();
+        builder.pop_context();
+    }
+}
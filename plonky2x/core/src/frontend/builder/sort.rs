@@ -0,0 +1,99 @@
+use core::marker::PhantomData;
+
+use plonky2::hash::hash_types::RichField;
+use serde::{Deserialize, Serialize};
+
+use super::CircuitBuilder;
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::hint::simple::hint::Hint;
+use crate::frontend::ops::LessThanOrEqual;
+use crate::frontend::vars::{ArrayVariable, CircuitVariable, ValueStream, VariableStream};
+
+/// Sorts `len` values of type `V` in ascending order.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct SortHint<V> {
+    len: usize,
+    _marker: PhantomData<V>,
+}
+
+impl<L: PlonkParameters<D>, const D: usize, V: CircuitVariable> Hint<L, D> for SortHint<V>
+where
+    V::ValueType<L::Field>: Ord,
+{
+    fn hint(&self, input_stream: &mut ValueStream<L, D>, output_stream: &mut ValueStream<L, D>) {
+        let mut values = (0..self.len)
+            .map(|_| input_stream.read_value::<V>())
+            .collect::<Vec<_>>();
+        values.sort();
+        for value in values {
+            output_stream.write_value::<V>(value);
+        }
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Returns `array` sorted in ascending order.
+    ///
+    /// The sorted array is computed by a hint and constrained to be a permutation of `array` using
+    /// a grand-product argument, and to be non-decreasing using `lte`.
+    pub fn sort<V, const N: usize>(&mut self, array: &ArrayVariable<V, N>) -> ArrayVariable<V, N>
+    where
+        V: CircuitVariable + LessThanOrEqual<L, D>,
+        SortHint<V>: Hint<L, D>,
+    {
+        let mut input_stream = VariableStream::new();
+        input_stream.write(array);
+        let hint = SortHint::<V> {
+            len: N,
+            _marker: PhantomData,
+        };
+        let output_stream = self.hint(input_stream, hint);
+        let sorted = output_stream.read::<ArrayVariable<V, N>>(self);
+
+        self.assert_permutation(array.as_slice(), sorted.as_slice());
+
+        let true_v = self._true();
+        for i in 1..N {
+            let is_ordered = self.lte(sorted[i - 1].clone(), sorted[i].clone());
+            self.assert_is_equal(is_ordered, true_v);
+        }
+
+        sorted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn test_sort() {
+        let mut builder = DefaultBuilder::new();
+
+        let array = builder.read::<ArrayVariable<U32Variable, 6>>();
+        let sorted = builder.sort(&array);
+        builder.write(sorted);
+
+        let wide = builder.read::<ArrayVariable<U64Variable, 4>>();
+        let wide_sorted = builder.sort(&wide);
+        builder.write(wide_sorted);
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        input.write::<ArrayVariable<U32Variable, 6>>(vec![5, 3, 9, 3, 0, u32::MAX]);
+        input.write::<ArrayVariable<U64Variable, 4>>(vec![1 << 40, 7, u64::MAX, 1 << 32]);
+
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        assert_eq!(
+            output.read::<ArrayVariable<U32Variable, 6>>(),
+            vec![0, 3, 3, 5, 9, u32::MAX]
+        );
+        assert_eq!(
+            output.read::<ArrayVariable<U64Variable, 4>>(),
+            vec![7, 1 << 32, 1 << 40, u64::MAX]
+        );
+    }
+}
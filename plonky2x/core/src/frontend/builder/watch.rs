@@ -20,6 +20,8 @@ pub struct WatchGenerator<L: PlonkParameters<D>, const D: usize, V: CircuitVaria
 }
 
 impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Logs the value of `variable` at the info level, prefixed with `log`, once it is known
+    /// during witness generation.
     pub fn watch<V: CircuitVariable>(&mut self, variable: &V, log: &str) {
         let variable = variable.clone();
         let log = String::from(log);
@@ -33,6 +35,7 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         self.add_simple_generator(generator);
     }
 
+    /// Like `watch`, but logs at the given level.
     pub fn watch_with_level<V: CircuitVariable>(
         &mut self,
         variable: &V,
@@ -51,6 +54,8 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         self.add_simple_generator(generator);
     }
 
+    /// Logs the values of `variables` at the info level, prefixed with `log`, once they are all
+    /// known during witness generation.
     pub fn watch_slice<V: CircuitVariable>(&mut self, variables: &[V], log: &str) {
         let variables = variables.to_vec();
         let log = String::from(log);
@@ -64,6 +69,7 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         self.add_simple_generator(generator);
     }
 
+    /// Like `watch_slice`, but logs at the given level.
     pub fn watch_slice_with_level<V: CircuitVariable>(
         &mut self,
         variables: &[V],
@@ -220,4 +226,20 @@ mod tests {
         let sum = output.read::<Variable>();
         debug!("{}", sum.0);
     }
+
+    #[test]
+    fn test_watcher_serialization() {
+        utils::setup_logger();
+
+        let mut builder = DefaultBuilder::new();
+        let a = builder.read::<ByteVariable>();
+        let b = builder.read::<U128Variable>();
+        builder.watch(&a, "a");
+        builder.watch_slice(&[b, b], "b");
+
+        let circuit = builder.build();
+        let gate_serializer = GateRegistry::<DefaultParameters, 2>::new();
+        let hint_serializer = HintRegistry::<DefaultParameters, 2>::new();
+        circuit.test_serializers(&gate_serializer, &hint_serializer);
+    }
 }
@@ -0,0 +1,31 @@
+use num::BigUint;
+
+use crate::frontend::ecc::nonnative::NonNativeField;
+
+/// The base field of BLS12-381, over which the coordinates of the points of G1 are defined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bls12381Base;
+
+impl NonNativeField for Bls12381Base {
+    fn modulus() -> BigUint {
+        BigUint::parse_bytes(
+            b"1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaaab",
+            16,
+        )
+        .unwrap()
+    }
+}
+
+/// The scalar field of BLS12-381, whose order is the order of the subgroup G1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bls12381Scalar;
+
+impl NonNativeField for Bls12381Scalar {
+    fn modulus() -> BigUint {
+        BigUint::parse_bytes(
+            b"73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001",
+            16,
+        )
+        .unwrap()
+    }
+}
@@ -0,0 +1,357 @@
+use core::array;
+
+use plonky2::hash::hash_types::RichField;
+use serde::{Deserialize, Serialize};
+
+use super::field::Bls12381Base;
+use super::fp2::{Bls12381Fp2, Bls12381Fp2Variable, Fp2ProductSum};
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::builder::CircuitBuilder;
+use crate::frontend::ecc::nonnative::NonNativeField;
+use crate::frontend::hint::simple::hint::Hint;
+use crate::frontend::vars::{BoolVariable, CircuitVariable, ValueStream, Variable, VariableStream};
+
+/// An element `c0 + c1 * w + ... + c5 * w^5` of the extension of degree twelve of the base field
+/// of BLS12-381, where the coefficients are in the quadratic extension and `w^6 = 1 + u`, in which
+/// the pairing takes its values.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bls12381Fp12 {
+    pub coeffs: [Bls12381Fp2; 6],
+}
+
+impl Bls12381Fp12 {
+    pub fn one() -> Self {
+        Self {
+            coeffs: array::from_fn(|i| {
+                if i == 0 {
+                    Bls12381Fp2::one()
+                } else {
+                    Bls12381Fp2::zero()
+                }
+            }),
+        }
+    }
+
+    pub fn mul(&self, other: &Self) -> Self {
+        let mut coeffs: [Bls12381Fp2; 6] = array::from_fn(|_| Bls12381Fp2::zero());
+        for (i, a) in self.coeffs.iter().enumerate() {
+            for (j, b) in other.coeffs.iter().enumerate() {
+                let product = a.mul(b);
+                if i + j < 6 {
+                    coeffs[i + j] = coeffs[i + j].add(&product);
+                } else {
+                    coeffs[i + j - 6] = coeffs[i + j - 6].add(&product.mul_by_nonresidue());
+                }
+            }
+        }
+        Self { coeffs }
+    }
+
+    /// Returns the image of the element by the sixth power of the Frobenius map, which negates
+    /// the odd coefficients.
+    pub fn conjugate(&self) -> Self {
+        Self {
+            coeffs: array::from_fn(|i| {
+                if i % 2 == 0 {
+                    self.coeffs[i].clone()
+                } else {
+                    self.coeffs[i].neg()
+                }
+            }),
+        }
+    }
+
+    /// Returns the inverse of the element, or zero if it is zero.
+    pub fn inverse(&self) -> Self {
+        // The norm `a * conj(a)` is in the extension of degree six, whose elements are
+        // `x0 + x1 * v + x2 * v^2` with `v = w^2` and `v^3 = 1 + u`, and is inverted there.
+        let conjugate = self.conjugate();
+        let norm = self.mul(&conjugate);
+        let [x0, _, x1, _, x2, _] = &norm.coeffs;
+        let t0 = x0.square().sub(&x1.mul(x2).mul_by_nonresidue());
+        let t1 = x2.square().mul_by_nonresidue().sub(&x0.mul(x1));
+        let t2 = x1.square().sub(&x0.mul(x2));
+        let denominator = x0
+            .mul(&t0)
+            .add(&x2.mul(&t1).add(&x1.mul(&t2)).mul_by_nonresidue());
+        let denominator_inverse = denominator.inverse();
+        let zero = Bls12381Fp2::zero();
+        let norm_inverse = Self {
+            coeffs: [
+                t0.mul(&denominator_inverse),
+                zero.clone(),
+                t1.mul(&denominator_inverse),
+                zero.clone(),
+                t2.mul(&denominator_inverse),
+                zero,
+            ],
+        };
+        conjugate.mul(&norm_inverse)
+    }
+}
+
+/// Returns the coefficient by which the Frobenius map raised to `power` multiplies the
+/// coefficient of `w^k`, which is `(1 + u)^(k * (p^power - 1) / 6)`.
+fn frobenius_coefficient(power: usize, k: usize) -> Bls12381Fp2 {
+    let p = Bls12381Base::modulus();
+    let exponent = (p.pow(power as u32) - 1u32) * k / 6u32;
+    Bls12381Fp2::nonresidue().pow(&exponent)
+}
+
+/// An element of the extension of degree twelve of the base field of BLS12-381.
+#[derive(Debug, Clone)]
+pub struct Bls12381Fp12Variable {
+    pub coeffs: [Bls12381Fp2Variable; 6],
+}
+
+impl CircuitVariable for Bls12381Fp12Variable {
+    type ValueType<F: RichField> = Bls12381Fp12;
+
+    fn init_unsafe<L: PlonkParameters<D>, const D: usize>(
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> Self {
+        Self {
+            coeffs: array::from_fn(|_| Bls12381Fp2Variable::init_unsafe(builder)),
+        }
+    }
+
+    fn variables(&self) -> Vec<Variable> {
+        self.coeffs.iter().flat_map(|c| c.variables()).collect()
+    }
+
+    fn from_variables_unsafe(variables: &[Variable]) -> Self {
+        assert_eq!(variables.len(), Self::nb_elements());
+        let mut chunks = variables.chunks(Bls12381Fp2Variable::nb_elements());
+        Self {
+            coeffs: array::from_fn(|_| {
+                Bls12381Fp2Variable::from_variables_unsafe(chunks.next().unwrap())
+            }),
+        }
+    }
+
+    fn assert_is_valid<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        builder: &mut CircuitBuilder<L, D>,
+    ) {
+        for c in self.coeffs.iter() {
+            c.assert_is_valid(builder);
+        }
+    }
+
+    fn nb_elements() -> usize {
+        6 * Bls12381Fp2Variable::nb_elements()
+    }
+
+    fn elements<F: RichField>(value: Self::ValueType<F>) -> Vec<F> {
+        value
+            .coeffs
+            .into_iter()
+            .flat_map(Bls12381Fp2Variable::elements::<F>)
+            .collect()
+    }
+
+    fn from_elements<F: RichField>(elements: &[F]) -> Self::ValueType<F> {
+        assert_eq!(elements.len(), Self::nb_elements());
+        let mut chunks = elements.chunks(Bls12381Fp2Variable::nb_elements());
+        Bls12381Fp12 {
+            coeffs: array::from_fn(|_| {
+                Bls12381Fp2Variable::from_elements::<F>(chunks.next().unwrap())
+            }),
+        }
+    }
+}
+
+/// Computes the inverse of an element of the extension of degree twelve, or zero if it is zero.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Bls12381Fp12InverseHint;
+
+impl<L: PlonkParameters<D>, const D: usize> Hint<L, D> for Bls12381Fp12InverseHint {
+    fn hint(&self, input_stream: &mut ValueStream<L, D>, output_stream: &mut ValueStream<L, D>) {
+        let a = input_stream.read_value::<Bls12381Fp12Variable>();
+        output_stream.write_value::<Bls12381Fp12Variable>(a.inverse());
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    pub fn bls12_381_fp12_mul(
+        &mut self,
+        a: &Bls12381Fp12Variable,
+        b: &Bls12381Fp12Variable,
+    ) -> Bls12381Fp12Variable {
+        let b = b.coeffs.iter().map(Some).collect::<Vec<_>>();
+        self.bls12_381_fp12_sparse_mul(a, &b)
+    }
+
+    pub fn bls12_381_fp12_square(&mut self, a: &Bls12381Fp12Variable) -> Bls12381Fp12Variable {
+        // Each product of two distinct coefficients appears twice in the square.
+        let mut sums: [Fp2ProductSum; 6] = Default::default();
+        for i in 0..6 {
+            for j in i..6 {
+                let multiplicity = if i == j { 1 } else { 2 };
+                self.bls12_381_fp2_add_product(
+                    &mut sums[(i + j) % 6],
+                    &a.coeffs[i],
+                    &a.coeffs[j],
+                    i + j >= 6,
+                    multiplicity,
+                );
+            }
+        }
+        Bls12381Fp12Variable {
+            coeffs: array::from_fn(|k| self.bls12_381_fp2_reduce_sum(&sums[k])),
+        }
+    }
+
+    /// Returns the product of `a` with `l0 + l2 * w^2 + l3 * w^3`, which is the form of the
+    /// evaluation of the lines of the Miller loop.
+    pub(crate) fn bls12_381_fp12_mul_by_line(
+        &mut self,
+        a: &Bls12381Fp12Variable,
+        l0: &Bls12381Fp2Variable,
+        l2: &Bls12381Fp2Variable,
+        l3: &Bls12381Fp2Variable,
+    ) -> Bls12381Fp12Variable {
+        self.bls12_381_fp12_sparse_mul(a, &[Some(l0), None, Some(l2), Some(l3), None, None])
+    }
+
+    /// Returns `conj(a)`, the image of `a` by the sixth power of the Frobenius map, which is the
+    /// inverse of `a` for elements of the cyclotomic subgroup.
+    pub fn bls12_381_fp12_conjugate(&mut self, a: &Bls12381Fp12Variable) -> Bls12381Fp12Variable {
+        Bls12381Fp12Variable {
+            coeffs: array::from_fn(|i| {
+                if i % 2 == 0 {
+                    a.coeffs[i].clone()
+                } else {
+                    self.bls12_381_fp2_neg(&a.coeffs[i])
+                }
+            }),
+        }
+    }
+
+    /// Returns `a^(p^power)`.
+    pub fn bls12_381_fp12_frobenius(
+        &mut self,
+        a: &Bls12381Fp12Variable,
+        power: usize,
+    ) -> Bls12381Fp12Variable {
+        // The Frobenius map conjugates the coefficients, and maps `w^k` to a constant multiple of
+        // itself.
+        Bls12381Fp12Variable {
+            coeffs: array::from_fn(|k| {
+                let coefficient = if power % 2 == 1 {
+                    self.bls12_381_fp2_conjugate(&a.coeffs[k])
+                } else {
+                    a.coeffs[k].clone()
+                };
+                let gamma = self.constant::<Bls12381Fp2Variable>(frobenius_coefficient(power, k));
+                self.bls12_381_fp2_mul(&coefficient, &gamma)
+            }),
+        }
+    }
+
+    /// Returns the inverse of `a`, asserting that `a` is not zero.
+    ///
+    /// The inverse is supplied by a hint and constrained so that `inverse * a == 1`.
+    pub fn bls12_381_fp12_inverse(&mut self, a: &Bls12381Fp12Variable) -> Bls12381Fp12Variable {
+        let mut input_stream = VariableStream::new();
+        input_stream.write(a);
+        let output_stream = self.hint(input_stream, Bls12381Fp12InverseHint);
+        let inverse = output_stream.read::<Bls12381Fp12Variable>(self);
+
+        let product = self.bls12_381_fp12_mul(&inverse, a);
+        let one = self.constant::<Bls12381Fp12Variable>(Bls12381Fp12::one());
+        self.assert_is_equal(product, one);
+        inverse
+    }
+
+    pub fn bls12_381_fp12_is_one(&mut self, a: &Bls12381Fp12Variable) -> BoolVariable {
+        let one = self.constant::<Bls12381Fp12Variable>(Bls12381Fp12::one());
+        self.is_equal(a.clone(), one)
+    }
+
+    /// Returns the product of `a` with the element whose coefficients are `b`, skipping the
+    /// coefficients that are known to be zero.
+    fn bls12_381_fp12_sparse_mul(
+        &mut self,
+        a: &Bls12381Fp12Variable,
+        b: &[Option<&Bls12381Fp2Variable>],
+    ) -> Bls12381Fp12Variable {
+        // Each coefficient of the product is reduced once, after all of its terms are added.
+        let mut sums: [Fp2ProductSum; 6] = Default::default();
+        for (i, a_i) in a.coeffs.iter().enumerate() {
+            for (j, b_j) in b.iter().enumerate() {
+                if let Some(b_j) = b_j {
+                    self.bls12_381_fp2_add_product(&mut sums[(i + j) % 6], a_i, b_j, i + j >= 6, 1);
+                }
+            }
+        }
+        Bls12381Fp12Variable {
+            coeffs: array::from_fn(|k| self.bls12_381_fp2_reduce_sum(&sums[k])),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::BigUint;
+
+    use super::*;
+    use crate::prelude::DefaultBuilder;
+
+    fn pow(a: &Bls12381Fp12, exponent: &BigUint) -> Bls12381Fp12 {
+        let mut result = Bls12381Fp12::one();
+        for i in (0..exponent.bits()).rev() {
+            result = result.mul(&result);
+            if exponent.bit(i) {
+                result = result.mul(a);
+            }
+        }
+        result
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_bls12_381_fp12_arithmetic() {
+        type V = Bls12381Fp12Variable;
+
+        let mut builder = DefaultBuilder::new();
+        let a = builder.read::<V>();
+        let b = builder.read::<V>();
+        let product = builder.bls12_381_fp12_mul(&a, &b);
+        let square = builder.bls12_381_fp12_square(&a);
+        let inverse = builder.bls12_381_fp12_inverse(&a);
+        let frobenius = builder.bls12_381_fp12_frobenius(&a, 1);
+        let frobenius_2 = builder.bls12_381_fp12_frobenius(&a, 2);
+        let conjugate = builder.bls12_381_fp12_conjugate(&a);
+        builder.write(product);
+        builder.write(square);
+        builder.write(inverse);
+        builder.write(frobenius);
+        builder.write(frobenius_2);
+        builder.write(conjugate);
+        let circuit = builder.mock_build();
+
+        let p = Bls12381Base::modulus();
+        let a = Bls12381Fp12 {
+            coeffs: array::from_fn(|i| {
+                Bls12381Fp2::new(&p - (i as u32 + 1), BigUint::from(i as u32 + 7) << 300)
+            }),
+        };
+        let b = Bls12381Fp12 {
+            coeffs: array::from_fn(|i| {
+                Bls12381Fp2::new(BigUint::from(i as u32 + 3) << 200, &p - (i as u32 + 5))
+            }),
+        };
+        let mut input = circuit.input();
+        input.write::<V>(a.clone());
+        input.write::<V>(b.clone());
+        let (_witness, mut output) = circuit.mock_prove(&input);
+
+        assert_eq!(output.read::<V>(), a.mul(&b));
+        assert_eq!(output.read::<V>(), a.mul(&a));
+        assert_eq!(output.read::<V>().mul(&a), Bls12381Fp12::one());
+        assert_eq!(output.read::<V>(), pow(&a, &p));
+        assert_eq!(output.read::<V>(), pow(&a, &(&p * &p)));
+        assert_eq!(output.read::<V>(), pow(&a, &p.pow(6)));
+    }
+}
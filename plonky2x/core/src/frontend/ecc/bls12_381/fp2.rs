@@ -0,0 +1,575 @@
+use num::{BigUint, One, Zero};
+use plonky2::hash::hash_types::RichField;
+use serde::{Deserialize, Serialize};
+
+use super::field::Bls12381Base;
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::builder::CircuitBuilder;
+use crate::frontend::ecc::nonnative::{
+    add_mod, inverse_mod, mul_mod, sub_mod, NonNativeField, NonNativeFieldVariable,
+};
+use crate::frontend::hint::simple::hint::Hint;
+use crate::frontend::uint::num::biguint::{BigUintTarget, CircuitBuilderBiguint};
+use crate::frontend::vars::{BoolVariable, CircuitVariable, ValueStream, Variable, VariableStream};
+
+/// An element of the base field of BLS12-381.
+pub type Bls12381FpVariable = NonNativeFieldVariable<Bls12381Base>;
+
+/// An element `c0 + c1 * u` of the quadratic extension of the base field of BLS12-381, where
+/// `u^2 = -1`, over which the coordinates of the points of G2 are defined.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bls12381Fp2 {
+    pub c0: BigUint,
+    pub c1: BigUint,
+}
+
+impl Bls12381Fp2 {
+    /// Returns `c0 + c1 * u`, reducing the coefficients modulo the order of the base field.
+    pub fn new(c0: BigUint, c1: BigUint) -> Self {
+        let p = Bls12381Base::modulus();
+        Self {
+            c0: c0 % &p,
+            c1: c1 % &p,
+        }
+    }
+
+    /// Parses the hex representations of the coefficients.
+    pub(crate) fn from_hex(c0: &str, c1: &str) -> Self {
+        Self::new(
+            BigUint::parse_bytes(c0.as_bytes(), 16).unwrap(),
+            BigUint::parse_bytes(c1.as_bytes(), 16).unwrap(),
+        )
+    }
+
+    pub fn zero() -> Self {
+        Self::new(BigUint::zero(), BigUint::zero())
+    }
+
+    pub fn one() -> Self {
+        Self::new(BigUint::one(), BigUint::zero())
+    }
+
+    /// Returns `1 + u`, which is neither a square nor a cube, and from which the extensions of
+    /// degree six and twelve are built.
+    pub fn nonresidue() -> Self {
+        Self::new(BigUint::one(), BigUint::one())
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.c0.is_zero() && self.c1.is_zero()
+    }
+
+    pub fn add(&self, other: &Self) -> Self {
+        let p = Bls12381Base::modulus();
+        Self {
+            c0: add_mod(&self.c0, &other.c0, &p),
+            c1: add_mod(&self.c1, &other.c1, &p),
+        }
+    }
+
+    pub fn sub(&self, other: &Self) -> Self {
+        let p = Bls12381Base::modulus();
+        Self {
+            c0: sub_mod(&self.c0, &other.c0, &p),
+            c1: sub_mod(&self.c1, &other.c1, &p),
+        }
+    }
+
+    pub fn neg(&self) -> Self {
+        Self::zero().sub(self)
+    }
+
+    pub fn mul(&self, other: &Self) -> Self {
+        let p = Bls12381Base::modulus();
+        let c0 = sub_mod(
+            &mul_mod(&self.c0, &other.c0, &p),
+            &mul_mod(&self.c1, &other.c1, &p),
+            &p,
+        );
+        let c1 = (&self.c0 * &other.c1 + &self.c1 * &other.c0) % &p;
+        Self { c0, c1 }
+    }
+
+    pub fn square(&self) -> Self {
+        self.mul(self)
+    }
+
+    /// Returns the product with a scalar of the base field.
+    pub fn scale(&self, scalar: &BigUint) -> Self {
+        Self::new(&self.c0 * scalar, &self.c1 * scalar)
+    }
+
+    /// Returns the product with `1 + u`.
+    pub fn mul_by_nonresidue(&self) -> Self {
+        let p = Bls12381Base::modulus();
+        Self {
+            c0: sub_mod(&self.c0, &self.c1, &p),
+            c1: add_mod(&self.c0, &self.c1, &p),
+        }
+    }
+
+    /// Returns `c0 - c1 * u`, which is the image of the element by the Frobenius map.
+    pub fn conjugate(&self) -> Self {
+        Self {
+            c0: self.c0.clone(),
+            c1: sub_mod(&BigUint::zero(), &self.c1, &Bls12381Base::modulus()),
+        }
+    }
+
+    /// Returns the inverse of the element, or zero if it is zero.
+    pub fn inverse(&self) -> Self {
+        let p = Bls12381Base::modulus();
+        let norm = (&self.c0 * &self.c0 + &self.c1 * &self.c1) % &p;
+        if norm.is_zero() {
+            return Self::zero();
+        }
+        self.conjugate().scale(&inverse_mod(&norm, &p))
+    }
+
+    pub fn pow(&self, exponent: &BigUint) -> Self {
+        let mut result = Self::one();
+        for i in (0..exponent.bits()).rev() {
+            result = result.square();
+            if exponent.bit(i) {
+                result = result.mul(self);
+            }
+        }
+        result
+    }
+
+    /// Returns a square root of the element, if there is one.
+    pub fn sqrt(&self) -> Option<Self> {
+        if self.is_zero() {
+            return Some(Self::zero());
+        }
+        let p = Bls12381Base::modulus();
+        let two_inverse = inverse_mod(&BigUint::from(2u32), &p);
+
+        // If `(x0 + x1 * u)^2 = c0 + c1 * u`, then `x0^2 = (c0 + n) / 2` and `x1 = c1 / (2 * x0)`
+        // where `n` is a square root of the norm `c0^2 + c1^2`.
+        let norm = (&self.c0 * &self.c0 + &self.c1 * &self.c1) % &p;
+        let n = sqrt_fp(&norm)?;
+        for n in [n.clone(), sub_mod(&BigUint::zero(), &n, &p)] {
+            let x0 = match sqrt_fp(&mul_mod(&add_mod(&self.c0, &n, &p), &two_inverse, &p)) {
+                Some(x0) if !x0.is_zero() => x0,
+                _ => continue,
+            };
+            let x1 = mul_mod(&self.c1, &inverse_mod(&(&x0 * 2u32 % &p), &p), &p);
+            let root = Self { c0: x0, c1: x1 };
+            if root.square() == *self {
+                return Some(root);
+            }
+        }
+
+        // Otherwise `c1 = 0` and `c0` is not a square in the base field, and the square root is
+        // `x1 * u` for `x1` a square root of `-c0`.
+        if self.c1.is_zero() {
+            let x1 = sqrt_fp(&sub_mod(&BigUint::zero(), &self.c0, &p))?;
+            return Some(Self {
+                c0: BigUint::zero(),
+                c1: x1,
+            });
+        }
+        None
+    }
+}
+
+/// Returns a square root of `a` in the base field, if there is one.
+fn sqrt_fp(a: &BigUint) -> Option<BigUint> {
+    // As `p = 3 mod 4`, a square root of `a` is `a^((p + 1) / 4)` if there is one.
+    let p = Bls12381Base::modulus();
+    let root = a.modpow(&((&p + 1u32) / 4u32), &p);
+    (mul_mod(&root, &root, &p) == *a).then_some(root)
+}
+
+/// An element of the quadratic extension of the base field of BLS12-381.
+#[derive(Debug, Clone)]
+pub struct Bls12381Fp2Variable {
+    pub c0: Bls12381FpVariable,
+    pub c1: Bls12381FpVariable,
+}
+
+impl CircuitVariable for Bls12381Fp2Variable {
+    type ValueType<F: RichField> = Bls12381Fp2;
+
+    fn init_unsafe<L: PlonkParameters<D>, const D: usize>(
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> Self {
+        Self {
+            c0: Bls12381FpVariable::init_unsafe(builder),
+            c1: Bls12381FpVariable::init_unsafe(builder),
+        }
+    }
+
+    fn variables(&self) -> Vec<Variable> {
+        [self.c0.variables(), self.c1.variables()].concat()
+    }
+
+    fn from_variables_unsafe(variables: &[Variable]) -> Self {
+        let (c0, c1) = variables.split_at(Bls12381FpVariable::nb_elements());
+        Self {
+            c0: Bls12381FpVariable::from_variables_unsafe(c0),
+            c1: Bls12381FpVariable::from_variables_unsafe(c1),
+        }
+    }
+
+    fn assert_is_valid<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        builder: &mut CircuitBuilder<L, D>,
+    ) {
+        self.c0.assert_is_valid(builder);
+        self.c1.assert_is_valid(builder);
+    }
+
+    fn nb_elements() -> usize {
+        2 * Bls12381FpVariable::nb_elements()
+    }
+
+    fn elements<F: RichField>(value: Self::ValueType<F>) -> Vec<F> {
+        [
+            Bls12381FpVariable::elements::<F>(value.c0),
+            Bls12381FpVariable::elements::<F>(value.c1),
+        ]
+        .concat()
+    }
+
+    fn from_elements<F: RichField>(elements: &[F]) -> Self::ValueType<F> {
+        let (c0, c1) = elements.split_at(Bls12381FpVariable::nb_elements());
+        Bls12381Fp2 {
+            c0: Bls12381FpVariable::from_elements::<F>(c0),
+            c1: Bls12381FpVariable::from_elements::<F>(c1),
+        }
+    }
+}
+
+/// A sum of products of elements of the base field, some of which are subtracted, that is only
+/// reduced once all the products are added.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct FpProductSum {
+    positive: Vec<BigUintTarget>,
+    negative: Vec<BigUintTarget>,
+}
+
+/// The sums of products of the two coefficients of an element of the quadratic extension.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Fp2ProductSum {
+    c0: FpProductSum,
+    c1: FpProductSum,
+}
+
+/// Computes the quotient of two elements of the quadratic extension, or zero if the divisor is
+/// zero.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Bls12381Fp2DivHint;
+
+impl<L: PlonkParameters<D>, const D: usize> Hint<L, D> for Bls12381Fp2DivHint {
+    fn hint(&self, input_stream: &mut ValueStream<L, D>, output_stream: &mut ValueStream<L, D>) {
+        let a = input_stream.read_value::<Bls12381Fp2Variable>();
+        let b = input_stream.read_value::<Bls12381Fp2Variable>();
+        output_stream.write_value::<Bls12381Fp2Variable>(a.mul(&b.inverse()));
+    }
+}
+
+/// Computes a square root of an element of the quadratic extension, or a square root of its
+/// product with `1 + u` if it is not a square.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Bls12381Fp2SqrtHint;
+
+impl<L: PlonkParameters<D>, const D: usize> Hint<L, D> for Bls12381Fp2SqrtHint {
+    fn hint(&self, input_stream: &mut ValueStream<L, D>, output_stream: &mut ValueStream<L, D>) {
+        let a = input_stream.read_value::<Bls12381Fp2Variable>();
+        let (is_square, root) = match a.sqrt() {
+            Some(root) => (true, root),
+            None => {
+                let root = a
+                    .mul_by_nonresidue()
+                    .sqrt()
+                    .expect("either a or a * (1 + u) is a square");
+                (false, root)
+            }
+        };
+        output_stream.write_value::<BoolVariable>(is_square);
+        output_stream.write_value::<Bls12381Fp2Variable>(root);
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    pub fn bls12_381_fp2_add(
+        &mut self,
+        a: &Bls12381Fp2Variable,
+        b: &Bls12381Fp2Variable,
+    ) -> Bls12381Fp2Variable {
+        Bls12381Fp2Variable {
+            c0: self.nonnative_add(&a.c0, &b.c0),
+            c1: self.nonnative_add(&a.c1, &b.c1),
+        }
+    }
+
+    pub fn bls12_381_fp2_sub(
+        &mut self,
+        a: &Bls12381Fp2Variable,
+        b: &Bls12381Fp2Variable,
+    ) -> Bls12381Fp2Variable {
+        Bls12381Fp2Variable {
+            c0: self.nonnative_sub(&a.c0, &b.c0),
+            c1: self.nonnative_sub(&a.c1, &b.c1),
+        }
+    }
+
+    pub fn bls12_381_fp2_neg(&mut self, a: &Bls12381Fp2Variable) -> Bls12381Fp2Variable {
+        Bls12381Fp2Variable {
+            c0: self.nonnative_neg(&a.c0),
+            c1: self.nonnative_neg(&a.c1),
+        }
+    }
+
+    /// Returns `c0 - c1 * u`, which is the image of `a` by the Frobenius map.
+    pub fn bls12_381_fp2_conjugate(&mut self, a: &Bls12381Fp2Variable) -> Bls12381Fp2Variable {
+        Bls12381Fp2Variable {
+            c0: a.c0.clone(),
+            c1: self.nonnative_neg(&a.c1),
+        }
+    }
+
+    pub fn bls12_381_fp2_mul(
+        &mut self,
+        a: &Bls12381Fp2Variable,
+        b: &Bls12381Fp2Variable,
+    ) -> Bls12381Fp2Variable {
+        let mut sum = Fp2ProductSum::default();
+        self.bls12_381_fp2_add_product(&mut sum, a, b, false, 1);
+        self.bls12_381_fp2_reduce_sum(&sum)
+    }
+
+    pub fn bls12_381_fp2_square(&mut self, a: &Bls12381Fp2Variable) -> Bls12381Fp2Variable {
+        self.bls12_381_fp2_mul(a, a)
+    }
+
+    /// Returns the product of `a` with an element of the base field.
+    pub fn bls12_381_fp2_mul_by_fp(
+        &mut self,
+        a: &Bls12381Fp2Variable,
+        b: &Bls12381FpVariable,
+    ) -> Bls12381Fp2Variable {
+        Bls12381Fp2Variable {
+            c0: self.nonnative_mul(&a.c0, b),
+            c1: self.nonnative_mul(&a.c1, b),
+        }
+    }
+
+    /// Returns the product of `a` with `1 + u`.
+    pub fn bls12_381_fp2_mul_by_nonresidue(
+        &mut self,
+        a: &Bls12381Fp2Variable,
+    ) -> Bls12381Fp2Variable {
+        Bls12381Fp2Variable {
+            c0: self.nonnative_sub(&a.c0, &a.c1),
+            c1: self.nonnative_add(&a.c0, &a.c1),
+        }
+    }
+
+    /// Returns `a / b`, asserting that `b` is not zero.
+    ///
+    /// The quotient is supplied by a hint and constrained so that `quotient * b == a`.
+    pub fn bls12_381_fp2_div(
+        &mut self,
+        a: &Bls12381Fp2Variable,
+        b: &Bls12381Fp2Variable,
+    ) -> Bls12381Fp2Variable {
+        let false_v = self._false();
+        let is_zero = self.bls12_381_fp2_is_zero(b);
+        self.assert_is_equal(is_zero, false_v);
+
+        let mut input_stream = VariableStream::new();
+        input_stream.write(a);
+        input_stream.write(b);
+        let output_stream = self.hint(input_stream, Bls12381Fp2DivHint);
+        let quotient = output_stream.read::<Bls12381Fp2Variable>(self);
+
+        let product = self.bls12_381_fp2_mul(&quotient, b);
+        self.assert_is_equal(product, a.clone());
+        quotient
+    }
+
+    /// Returns the inverse of `a`, asserting that `a` is not zero.
+    pub fn bls12_381_fp2_inverse(&mut self, a: &Bls12381Fp2Variable) -> Bls12381Fp2Variable {
+        let one = self.constant::<Bls12381Fp2Variable>(Bls12381Fp2::one());
+        self.bls12_381_fp2_div(&one, a)
+    }
+
+    pub fn bls12_381_fp2_is_zero(&mut self, a: &Bls12381Fp2Variable) -> BoolVariable {
+        let zero = self.constant::<Bls12381Fp2Variable>(Bls12381Fp2::zero());
+        self.is_equal(a.clone(), zero)
+    }
+
+    /// Returns the sign of `a` as defined by the `sgn0` function of RFC 9380, which is the parity
+    /// of `c0`, or the parity of `c1` if `c0` is zero.
+    pub fn bls12_381_fp2_sgn0(&mut self, a: &Bls12381Fp2Variable) -> BoolVariable {
+        let zero = self.constant::<Bls12381FpVariable>(BigUint::zero());
+        let sign_0 = self.nonnative_is_odd(&a.c0);
+        let zero_0 = self.is_equal(a.c0.clone(), zero);
+        let sign_1 = self.nonnative_is_odd(&a.c1);
+        let zero_0_and_sign_1 = self.and(zero_0, sign_1);
+        self.or(sign_0, zero_0_and_sign_1)
+    }
+
+    /// Returns whether `a` is a square, and a square root of `a` if it is one, or of `a * (1 + u)`
+    /// otherwise.
+    pub fn bls12_381_fp2_sqrt(
+        &mut self,
+        a: &Bls12381Fp2Variable,
+    ) -> (BoolVariable, Bls12381Fp2Variable) {
+        let mut input_stream = VariableStream::new();
+        input_stream.write(a);
+        let output_stream = self.hint(input_stream, Bls12381Fp2SqrtHint);
+        let is_square = output_stream.read::<BoolVariable>(self);
+        let root = output_stream.read::<Bls12381Fp2Variable>(self);
+
+        // As `1 + u` is not a square, exactly one of `a` and `a * (1 + u)` is a square unless `a`
+        // is zero, in which case the hint must claim that it is a square.
+        let a_times_nonresidue = self.bls12_381_fp2_mul_by_nonresidue(a);
+        let square = self.select(is_square, a.clone(), a_times_nonresidue);
+        let root_squared = self.bls12_381_fp2_square(&root);
+        self.assert_is_equal(root_squared, square);
+
+        let true_v = self._true();
+        let is_zero = self.bls12_381_fp2_is_zero(a);
+        let is_nonzero = self.not(is_zero);
+        let is_square_or_nonzero = self.or(is_square, is_nonzero);
+        self.assert_is_equal(is_square_or_nonzero, true_v);
+        (is_square, root)
+    }
+
+    /// Adds `multiplicity` times the product `a * b`, or `a * b * (1 + u)` if `times_nonresidue`
+    /// is true, to a sum of products.
+    pub(crate) fn bls12_381_fp2_add_product(
+        &mut self,
+        sum: &mut Fp2ProductSum,
+        a: &Bls12381Fp2Variable,
+        b: &Bls12381Fp2Variable,
+        times_nonresidue: bool,
+        multiplicity: usize,
+    ) {
+        let a0_b0 = self.api.mul_biguint(&a.c0.to_biguint(), &b.c0.to_biguint());
+        let a1_b1 = self.api.mul_biguint(&a.c1.to_biguint(), &b.c1.to_biguint());
+        let a0_b1 = self.api.mul_biguint(&a.c0.to_biguint(), &b.c1.to_biguint());
+        let a1_b0 = self.api.mul_biguint(&a.c1.to_biguint(), &b.c0.to_biguint());
+
+        // The product is `x0 + x1 * u` with `x0 = a0 * b0 - a1 * b1` and `x1 = a0 * b1 + a1 * b0`,
+        // and its product with `1 + u` is `(x0 - x1) + (x0 + x1) * u`.
+        let (c0_positive, c0_negative, c1_positive, c1_negative) = if times_nonresidue {
+            (
+                vec![a0_b0.clone()],
+                vec![a1_b1.clone(), a0_b1.clone(), a1_b0.clone()],
+                vec![a0_b0, a0_b1, a1_b0],
+                vec![a1_b1],
+            )
+        } else {
+            (vec![a0_b0], vec![a1_b1], vec![a0_b1, a1_b0], vec![])
+        };
+        for _ in 0..multiplicity {
+            sum.c0.positive.extend(c0_positive.iter().cloned());
+            sum.c0.negative.extend(c0_negative.iter().cloned());
+            sum.c1.positive.extend(c1_positive.iter().cloned());
+            sum.c1.negative.extend(c1_negative.iter().cloned());
+        }
+    }
+
+    /// Reduces a sum of products of elements of the quadratic extension.
+    pub(crate) fn bls12_381_fp2_reduce_sum(&mut self, sum: &Fp2ProductSum) -> Bls12381Fp2Variable {
+        Bls12381Fp2Variable {
+            c0: self.bls12_381_fp_reduce_sum(&sum.c0),
+            c1: self.bls12_381_fp_reduce_sum(&sum.c1),
+        }
+    }
+
+    /// Reduces a sum of products of reduced elements of the base field.
+    fn bls12_381_fp_reduce_sum(&mut self, sum: &FpProductSum) -> Bls12381FpVariable {
+        // Each subtracted product is less than `p^2`, so adding `p^2` for each of them keeps the
+        // difference nonnegative.
+        let total = if sum.negative.is_empty() {
+            self.bls12_381_sum_biguints(sum.positive.clone())
+        } else {
+            let p = Bls12381Base::modulus();
+            let offset = &p * &p * sum.negative.len() as u32;
+            let mut positive = sum.positive.clone();
+            positive.push(self.api.constant_biguint(&offset));
+            let positive = self.bls12_381_sum_biguints(positive);
+            let negative = self.bls12_381_sum_biguints(sum.negative.clone());
+            self.api.sub_biguint(&positive, &negative)
+        };
+        self.nonnative_reduce::<Bls12381Base>(&total)
+    }
+
+    /// Adds integers pairwise, so that the number of limbs only grows logarithmically with the
+    /// number of terms.
+    fn bls12_381_sum_biguints(&mut self, mut terms: Vec<BigUintTarget>) -> BigUintTarget {
+        if terms.is_empty() {
+            return self.api.zero_biguint();
+        }
+        while terms.len() > 1 {
+            terms = terms
+                .chunks(2)
+                .map(|chunk| match chunk {
+                    [a, b] => self.api.add_biguint(a, b),
+                    [a] => a.clone(),
+                    _ => unreachable!(),
+                })
+                .collect();
+        }
+        terms.pop().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::DefaultBuilder;
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_bls12_381_fp2_arithmetic() {
+        type V = Bls12381Fp2Variable;
+
+        let mut builder = DefaultBuilder::new();
+        let a = builder.read::<V>();
+        let b = builder.read::<V>();
+        let product = builder.bls12_381_fp2_mul(&a, &b);
+        let quotient = builder.bls12_381_fp2_div(&a, &b);
+        let sign = builder.bls12_381_fp2_sgn0(&a);
+        let (a_is_square, a_root) = builder.bls12_381_fp2_sqrt(&a);
+        let b_squared = builder.bls12_381_fp2_square(&b);
+        let (b_squared_is_square, b_root) = builder.bls12_381_fp2_sqrt(&b_squared);
+        builder.write(product);
+        builder.write(quotient);
+        builder.write(sign);
+        builder.write(a_is_square);
+        builder.write(a_root);
+        builder.write(b_squared_is_square);
+        builder.write(b_root);
+        let circuit = builder.mock_build();
+
+        let p = Bls12381Base::modulus();
+        let a = Bls12381Fp2::new(&p - 12345u32, BigUint::from(67890u32) << 300);
+        let b = Bls12381Fp2::new(BigUint::from(3u32) << 200, &p - 2u32);
+        let mut input = circuit.input();
+        input.write::<V>(a.clone());
+        input.write::<V>(b.clone());
+        let (_witness, mut output) = circuit.mock_prove(&input);
+
+        assert_eq!(output.read::<V>(), a.mul(&b));
+        assert_eq!(output.read::<V>().mul(&b), a);
+        assert_eq!(output.read::<BoolVariable>(), a.c0.bit(0));
+        let a_is_square = output.read::<BoolVariable>();
+        let a_root = output.read::<V>();
+        assert_eq!(a_is_square, a.sqrt().is_some());
+        if a_is_square {
+            assert_eq!(a_root.square(), a);
+        } else {
+            assert_eq!(a_root.square(), a.mul_by_nonresidue());
+        }
+        assert!(output.read::<BoolVariable>());
+        let b_root = output.read::<V>();
+        assert!(b_root == b || b_root == b.neg());
+    }
+}
@@ -0,0 +1,253 @@
+use num::{BigUint, Zero};
+
+use super::field::{Bls12381Base, Bls12381Scalar};
+use super::g2::BLS12_381_X;
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::builder::CircuitBuilder;
+use crate::frontend::ecc::nonnative::{NonNativeField, NonNativeFieldVariable};
+use crate::frontend::ecc::weierstrass::{
+    neg_native, offset_point, WeierstrassCurve, WeierstrassPoint, WeierstrassPointVariable,
+};
+use crate::frontend::eth::vars::BLSPubkeyVariable;
+use crate::frontend::uint::num::biguint::CircuitBuilderBiguint;
+use crate::frontend::uint::uint512::U512Variable;
+use crate::frontend::vars::{ArrayVariable, BoolVariable, ByteVariable, EvmVariable};
+
+/// The x coordinate of the generator of G1.
+const GENERATOR_X: &str = "17f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb";
+
+/// The y coordinate of the generator of G1.
+const GENERATOR_Y: &str = "08b3f481e3aaa0f1a09e30ed741d8ae4fcf5e095d5d00af600db18cb2c04b3edd03cc744a2888ae40caa232946c5e7e1";
+
+/// The cube root of unity `beta` of the base field such that `(beta * x, y) == -z^2 * (x, y)` for
+/// the points `(x, y)` of G1, where `z` is the parameter of BLS12-381.
+const BETA: &str =
+    "5f19672fdf76ce51ba69c6076a0f77eaddb3a93be6f89688de17d813620a00022e01fffffffefffe";
+
+/// A point of G1 in affine coordinates, other than the point at infinity.
+pub type Bls12381G1PointVariable = WeierstrassPointVariable<Bls12381G1>;
+
+/// The value of a `Bls12381G1PointVariable`.
+pub type Bls12381G1Point = WeierstrassPoint;
+
+/// The curve `y^2 = x^3 + 4` over the base field of BLS12-381, whose subgroup of order the
+/// scalar field is G1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bls12381G1;
+
+impl WeierstrassCurve for Bls12381G1 {
+    type BaseField = Bls12381Base;
+    type ScalarField = Bls12381Scalar;
+
+    const OFFSET_SEED: &'static [u8] = b"plonky2x bls12-381 g1 offset";
+
+    fn a() -> BigUint {
+        BigUint::zero()
+    }
+
+    fn b() -> BigUint {
+        BigUint::from(4u32)
+    }
+
+    fn generator() -> (BigUint, BigUint) {
+        (
+            BigUint::parse_bytes(GENERATOR_X.as_bytes(), 16).unwrap(),
+            BigUint::parse_bytes(GENERATOR_Y.as_bytes(), 16).unwrap(),
+        )
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Computes the aggregate of the public keys whose bit is set in `bitfield`, as for the
+    /// participants of a sync committee.
+    ///
+    /// The public keys are decompressed and checked to be on the curve, but are not checked to be
+    /// in the subgroup of G1. The additions start from a point of unknown discrete logarithm, so
+    /// that none of them is a doubling or at infinity unless the aggregate itself is at infinity,
+    /// which makes the circuit unsatisfiable. In particular, at least one bit must be set.
+    pub fn bls12_381_aggregate_pubkeys<const N: usize>(
+        &mut self,
+        pubkeys: &ArrayVariable<BLSPubkeyVariable, N>,
+        bitfield: &ArrayVariable<BoolVariable, N>,
+    ) -> Bls12381G1PointVariable {
+        let offset = offset_point::<Bls12381G1>();
+        let correction = neg_native::<Bls12381G1>(&offset);
+        let mut acc = self.constant::<Bls12381G1PointVariable>(offset.into());
+        for (pubkey, bit) in pubkeys.as_slice().iter().zip(bitfield.as_slice()) {
+            let point = self.bls12_381_g1_decompress(pubkey);
+            let sum = self.weierstrass_add(&acc, &point);
+            acc = self.select(*bit, sum, acc);
+        }
+        let correction = self.constant::<Bls12381G1PointVariable>(correction.into());
+        self.weierstrass_add(&acc, &correction)
+    }
+
+    /// Returns `|z| * point`, where `z` is the negative parameter of BLS12-381.
+    ///
+    /// The intermediate multiples of `point` are computed with incomplete formulas, so the circuit
+    /// is unsatisfiable if one of them is at infinity or equal to `point` up to sign, which only
+    /// happens for points of small order.
+    fn bls12_381_g1_mul_by_abs_x(
+        &mut self,
+        point: &Bls12381G1PointVariable,
+    ) -> Bls12381G1PointVariable {
+        let mut acc = point.clone();
+        for i in (0..63).rev() {
+            acc = self.weierstrass_double(&acc);
+            if (BLS12_381_X >> i) & 1 == 1 {
+                acc = self.weierstrass_add(&acc, point);
+            }
+        }
+        acc
+    }
+
+    /// Asserts that `point`, which must be on the curve, is in G1, by checking that the
+    /// endomorphism `(x, y) -> (beta * x, y)` maps it to `-z^2 * point`, where `z` is the parameter
+    /// of BLS12-381.
+    pub fn bls12_381_g1_assert_in_subgroup(&mut self, point: &Bls12381G1PointVariable) {
+        let beta = BigUint::parse_bytes(BETA.as_bytes(), 16).unwrap();
+        let beta = self.constant::<NonNativeFieldVariable<Bls12381Base>>(beta);
+        let endomorphism = Bls12381G1PointVariable {
+            x: self.nonnative_mul(&beta, &point.x),
+            y: point.y.clone(),
+        };
+        let multiple = self.bls12_381_g1_mul_by_abs_x(point);
+        let multiple = self.bls12_381_g1_mul_by_abs_x(&multiple);
+        let neg_y = self.nonnative_neg(&multiple.y);
+        let neg_multiple = Bls12381G1PointVariable {
+            x: multiple.x,
+            y: neg_y,
+        };
+        self.assert_is_equal(endomorphism, neg_multiple);
+    }
+
+    /// Decompresses a public key in the compressed encoding of G1 points of the ZCash
+    /// serialization format, asserting that it is a valid point other than the point at infinity.
+    pub fn bls12_381_g1_decompress(
+        &mut self,
+        pubkey: &BLSPubkeyVariable,
+    ) -> Bls12381G1PointVariable {
+        let true_v = self._true();
+        let false_v = self._false();
+
+        // The three most significant bits of the encoding are the compression flag, the infinity
+        // flag and whether the y coordinate is the largest of the two square roots.
+        let [is_compressed, is_infinity, is_largest, rest @ ..] = pubkey.0 .0[0].as_be_bits();
+        self.assert_is_equal(is_compressed, true_v);
+        self.assert_is_equal(is_infinity, false_v);
+        let first_byte = ByteVariable::from_be_bits([
+            false_v, false_v, false_v, rest[0], rest[1], rest[2], rest[3], rest[4],
+        ]);
+
+        let zero_byte = self.constant::<ByteVariable>(0);
+        let mut x_bytes = vec![zero_byte; 16];
+        x_bytes.push(first_byte);
+        x_bytes.extend_from_slice(&pubkey.0 .0[1..]);
+        let x = U512Variable::decode(self, &x_bytes).to_biguint();
+        let x_is_reduced = self.nonnative_is_reduced::<Bls12381Base>(&x);
+        self.assert_is_equal(x_is_reduced, true_v);
+        let x = NonNativeFieldVariable::<Bls12381Base>::from_biguint_low(&x);
+
+        let (is_on_curve, point) = self.weierstrass_lift_x::<Bls12381G1>(&x, false_v);
+        self.assert_is_equal(is_on_curve, true_v);
+
+        // The largest square root is the one greater than `(p - 1) / 2`.
+        let root = point.y;
+        let half = self
+            .api
+            .constant_biguint(&((Bls12381Base::modulus() - 1u32) / 2u32));
+        let root_is_at_most_half = self.api.cmp_biguint(&root.to_biguint(), &half);
+        let root_is_largest = self.not(BoolVariable::from(root_is_at_most_half));
+        let neg_root = self.nonnative_neg(&root);
+        let has_sign = self.is_equal(root_is_largest, is_largest);
+        let y = self.select(has_sign, root, neg_root);
+        Bls12381G1PointVariable { x, y }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frontend::ecc::weierstrass::{add_native, double_native};
+    use crate::prelude::DefaultBuilder;
+
+    fn compress(point: &(BigUint, BigUint)) -> [u8; 48] {
+        let mut bytes = [0u8; 48];
+        let x = point.0.to_bytes_be();
+        bytes[48 - x.len()..].copy_from_slice(&x);
+        bytes[0] |= 0x80;
+        if point.1 > (Bls12381Base::modulus() - 1u32) / 2u32 {
+            bytes[0] |= 0x20;
+        }
+        bytes
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_bls12_381_aggregate_pubkeys() {
+        let mut builder = DefaultBuilder::new();
+        let pubkeys = builder.read::<ArrayVariable<BLSPubkeyVariable, 4>>();
+        let bitfield = builder.read::<ArrayVariable<BoolVariable, 4>>();
+        let aggregate = builder.bls12_381_aggregate_pubkeys(&pubkeys, &bitfield);
+        builder.write(aggregate);
+        let circuit = builder.mock_build();
+
+        let generator = Bls12381G1::generator();
+        assert_eq!(
+            &generator.1 * &generator.1 % Bls12381Base::modulus(),
+            (&generator.0 * &generator.0 * &generator.0 + 4u32) % Bls12381Base::modulus()
+        );
+        assert_eq!(
+            hex::encode(compress(&generator)),
+            format!("97{}", &GENERATOR_X[2..])
+        );
+
+        // The public keys are `G`, `2G`, `3G` and `4G`, of which the first, third and fourth
+        // participate, so that the aggregate is `8G`.
+        let mut points = vec![generator.clone(), double_native::<Bls12381G1>(&generator)];
+        points.push(add_native::<Bls12381G1>(&points[1], &generator));
+        points.push(double_native::<Bls12381G1>(&points[1]));
+        let expected = double_native::<Bls12381G1>(&points[3]);
+
+        let mut input = circuit.input();
+        input.write::<ArrayVariable<BLSPubkeyVariable, 4>>(points.iter().map(compress).collect());
+        input.write::<ArrayVariable<BoolVariable, 4>>(vec![true, false, true, true]);
+        let (_witness, mut output) = circuit.mock_prove(&input);
+        assert_eq!(output.read::<Bls12381G1PointVariable>(), expected.into());
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_bls12_381_g1_assert_in_subgroup() {
+        let mut builder = DefaultBuilder::new();
+        let point = builder.read::<Bls12381G1PointVariable>();
+        builder.bls12_381_g1_assert_in_subgroup(&point);
+        let circuit = builder.mock_build();
+
+        let generator = Bls12381G1::generator();
+        for point in [generator.clone(), double_native::<Bls12381G1>(&generator)] {
+            let mut input = circuit.input();
+            input.write::<Bls12381G1PointVariable>(point.into());
+            circuit.mock_prove(&input);
+        }
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    #[should_panic(expected = "was set twice with different values")]
+    fn test_bls12_381_g1_assert_in_subgroup_rejects_other_points() {
+        let mut builder = DefaultBuilder::new();
+        let point = builder.read::<Bls12381G1PointVariable>();
+        builder.bls12_381_g1_assert_in_subgroup(&point);
+        let circuit = builder.mock_build();
+
+        // The point of x coordinate 4 is on the curve but not in G1.
+        let point = Bls12381G1Point {
+            x: BigUint::from(4u32),
+            y: BigUint::parse_bytes(b"a989badd40d6212b33cffc3f3763e9bc760f988c9926b26da9dd85e928483446346b8ed00e1de5d5ea93e354abe706c", 16).unwrap(),
+        };
+        let mut input = circuit.input();
+        input.write::<Bls12381G1PointVariable>(point);
+        circuit.mock_prove(&input);
+    }
+}
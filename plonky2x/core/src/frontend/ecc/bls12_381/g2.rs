@@ -0,0 +1,365 @@
+use num::{BigUint, Zero};
+use plonky2::hash::hash_types::RichField;
+
+use super::field::Bls12381Base;
+use super::fp2::{Bls12381Fp2, Bls12381Fp2Variable, Bls12381FpVariable};
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::builder::CircuitBuilder;
+use crate::frontend::ecc::nonnative::{NonNativeField, NonNativeFieldVariable};
+use crate::frontend::eth::vars::BLSSignatureVariable;
+use crate::frontend::uint::num::biguint::CircuitBuilderBiguint;
+use crate::frontend::uint::uint512::U512Variable;
+use crate::frontend::vars::{BoolVariable, ByteVariable, CircuitVariable, EvmVariable, Variable};
+
+/// The absolute value of the parameter `x` of BLS12-381, which is negative.
+pub(crate) const BLS12_381_X: u64 = 0xd201000000010000;
+
+/// The coefficients of the x coordinate of the generator of G2.
+const GENERATOR_X: (&str, &str) = (
+    "024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8",
+    "13e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e",
+);
+
+/// The coefficients of the y coordinate of the generator of G2.
+const GENERATOR_Y: (&str, &str) = (
+    "0ce5d527727d6e118cc9cdc6da2e351aadfd9baa8cbdd3a76d429a695160d12c923ac9cc3baca289e193548608b82801",
+    "0606c4a02ea734cc32acd2b02bc28b99cb3e287e85a763af267492ab572e99ab3f370d275cec1da1aaa9075ff05f79be",
+);
+
+/// A point of the curve `y^2 = x^3 + 4 * (1 + u)` over the quadratic extension of the base field
+/// of BLS12-381, whose subgroup of order the scalar field is G2.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bls12381G2Point {
+    pub x: Bls12381Fp2,
+    pub y: Bls12381Fp2,
+}
+
+impl Bls12381G2Point {
+    /// The generator of G2.
+    pub fn generator() -> Self {
+        Self {
+            x: Bls12381Fp2::from_hex(GENERATOR_X.0, GENERATOR_X.1),
+            y: Bls12381Fp2::from_hex(GENERATOR_Y.0, GENERATOR_Y.1),
+        }
+    }
+}
+
+/// The coefficient `b` of the equation of the curve over which G2 is defined.
+fn g2_b() -> Bls12381Fp2 {
+    Bls12381Fp2::nonresidue().scale(&BigUint::from(4u32))
+}
+
+/// Returns the constants by which the endomorphism `psi` multiplies the conjugates of the
+/// coordinates of a point, which are `1 / (1 + u)^((p - 1) / 3)` and `1 / (1 + u)^((p - 1) / 2)`.
+fn psi_coefficients() -> (Bls12381Fp2, Bls12381Fp2) {
+    let p = Bls12381Base::modulus();
+    let nonresidue = Bls12381Fp2::nonresidue();
+    (
+        nonresidue.pow(&((&p - 1u32) / 3u32)).inverse(),
+        nonresidue.pow(&((&p - 1u32) / 2u32)).inverse(),
+    )
+}
+
+/// A point of the curve over which G2 is defined, in affine coordinates, other than the point at
+/// infinity.
+#[derive(Debug, Clone)]
+pub struct Bls12381G2PointVariable {
+    pub x: Bls12381Fp2Variable,
+    pub y: Bls12381Fp2Variable,
+}
+
+impl CircuitVariable for Bls12381G2PointVariable {
+    type ValueType<F: RichField> = Bls12381G2Point;
+
+    fn init_unsafe<L: PlonkParameters<D>, const D: usize>(
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> Self {
+        Self {
+            x: Bls12381Fp2Variable::init_unsafe(builder),
+            y: Bls12381Fp2Variable::init_unsafe(builder),
+        }
+    }
+
+    fn variables(&self) -> Vec<Variable> {
+        [self.x.variables(), self.y.variables()].concat()
+    }
+
+    fn from_variables_unsafe(variables: &[Variable]) -> Self {
+        let (x, y) = variables.split_at(Bls12381Fp2Variable::nb_elements());
+        Self {
+            x: Bls12381Fp2Variable::from_variables_unsafe(x),
+            y: Bls12381Fp2Variable::from_variables_unsafe(y),
+        }
+    }
+
+    fn assert_is_valid<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        builder: &mut CircuitBuilder<L, D>,
+    ) {
+        self.x.assert_is_valid(builder);
+        self.y.assert_is_valid(builder);
+    }
+
+    fn nb_elements() -> usize {
+        2 * Bls12381Fp2Variable::nb_elements()
+    }
+
+    fn elements<F: RichField>(value: Self::ValueType<F>) -> Vec<F> {
+        [
+            Bls12381Fp2Variable::elements::<F>(value.x),
+            Bls12381Fp2Variable::elements::<F>(value.y),
+        ]
+        .concat()
+    }
+
+    fn from_elements<F: RichField>(elements: &[F]) -> Self::ValueType<F> {
+        let (x, y) = elements.split_at(Bls12381Fp2Variable::nb_elements());
+        Bls12381G2Point {
+            x: Bls12381Fp2Variable::from_elements::<F>(x),
+            y: Bls12381Fp2Variable::from_elements::<F>(y),
+        }
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Returns `a + b`, asserting that the points have distinct x coordinates, which excludes
+    /// doubling and sums at infinity.
+    pub fn bls12_381_g2_add(
+        &mut self,
+        a: &Bls12381G2PointVariable,
+        b: &Bls12381G2PointVariable,
+    ) -> Bls12381G2PointVariable {
+        self.bls12_381_g2_add_with_slope(a, b).0
+    }
+
+    /// Returns `a - b`, asserting that the points have distinct x coordinates.
+    pub fn bls12_381_g2_sub(
+        &mut self,
+        a: &Bls12381G2PointVariable,
+        b: &Bls12381G2PointVariable,
+    ) -> Bls12381G2PointVariable {
+        let neg_b = self.bls12_381_g2_neg(b);
+        self.bls12_381_g2_add(a, &neg_b)
+    }
+
+    /// Returns `2 * a`, asserting that `a` is not of order two.
+    pub fn bls12_381_g2_double(&mut self, a: &Bls12381G2PointVariable) -> Bls12381G2PointVariable {
+        self.bls12_381_g2_double_with_slope(a).0
+    }
+
+    pub fn bls12_381_g2_neg(&mut self, a: &Bls12381G2PointVariable) -> Bls12381G2PointVariable {
+        Bls12381G2PointVariable {
+            x: a.x.clone(),
+            y: self.bls12_381_fp2_neg(&a.y),
+        }
+    }
+
+    /// Returns `a + b` and the slope of the line through the points, asserting that the points
+    /// have distinct x coordinates.
+    pub(crate) fn bls12_381_g2_add_with_slope(
+        &mut self,
+        a: &Bls12381G2PointVariable,
+        b: &Bls12381G2PointVariable,
+    ) -> (Bls12381G2PointVariable, Bls12381Fp2Variable) {
+        let dy = self.bls12_381_fp2_sub(&b.y, &a.y);
+        let dx = self.bls12_381_fp2_sub(&b.x, &a.x);
+        let lambda = self.bls12_381_fp2_div(&dy, &dx);
+        let sum = self.bls12_381_g2_chord(&lambda, a, &b.x);
+        (sum, lambda)
+    }
+
+    /// Returns `2 * a` and the slope of the tangent at `a`, asserting that `a` is not of order
+    /// two.
+    pub(crate) fn bls12_381_g2_double_with_slope(
+        &mut self,
+        a: &Bls12381G2PointVariable,
+    ) -> (Bls12381G2PointVariable, Bls12381Fp2Variable) {
+        let three = self.constant::<Bls12381FpVariable>(BigUint::from(3u32));
+        let x_squared = self.bls12_381_fp2_square(&a.x);
+        let numerator = self.bls12_381_fp2_mul_by_fp(&x_squared, &three);
+        let denominator = self.bls12_381_fp2_add(&a.y, &a.y);
+        let lambda = self.bls12_381_fp2_div(&numerator, &denominator);
+        let double = self.bls12_381_g2_chord(&lambda, a, &a.x);
+        (double, lambda)
+    }
+
+    /// Asserts that `point` is on the curve over which G2 is defined.
+    pub fn bls12_381_g2_assert_on_curve(&mut self, point: &Bls12381G2PointVariable) {
+        let y_squared = self.bls12_381_fp2_square(&point.y);
+        let rhs = self.bls12_381_g2_curve_rhs(&point.x);
+        self.assert_is_equal(y_squared, rhs);
+    }
+
+    /// Returns `x * point`, where `x` is the negative parameter of BLS12-381.
+    ///
+    /// The intermediate multiples of `point` are computed with incomplete formulas, so the circuit
+    /// is unsatisfiable if one of them is at infinity or equal to `point` up to sign, which only
+    /// happens for points of small order.
+    pub fn bls12_381_g2_mul_by_x(
+        &mut self,
+        point: &Bls12381G2PointVariable,
+    ) -> Bls12381G2PointVariable {
+        let mut acc = point.clone();
+        for i in (0..63).rev() {
+            acc = self.bls12_381_g2_double(&acc);
+            if (BLS12_381_X >> i) & 1 == 1 {
+                acc = self.bls12_381_g2_add(&acc, point);
+            }
+        }
+        self.bls12_381_g2_neg(&acc)
+    }
+
+    /// Returns `psi(point)`, where `psi` is the endomorphism of the curve obtained by moving the
+    /// point to the curve over the extension of degree twelve, applying the Frobenius map and
+    /// moving it back. It acts on G2 as the multiplication by `p`.
+    pub fn bls12_381_g2_psi(&mut self, point: &Bls12381G2PointVariable) -> Bls12381G2PointVariable {
+        let (c1, c2) = psi_coefficients();
+        let c1 = self.constant::<Bls12381Fp2Variable>(c1);
+        let c2 = self.constant::<Bls12381Fp2Variable>(c2);
+        let x = self.bls12_381_fp2_conjugate(&point.x);
+        let y = self.bls12_381_fp2_conjugate(&point.y);
+        Bls12381G2PointVariable {
+            x: self.bls12_381_fp2_mul(&x, &c1),
+            y: self.bls12_381_fp2_mul(&y, &c2),
+        }
+    }
+
+    /// Asserts that `point`, which must be on the curve, is in G2, by checking that
+    /// `psi(point) == x * point`.
+    pub fn bls12_381_g2_assert_in_subgroup(&mut self, point: &Bls12381G2PointVariable) {
+        let psi = self.bls12_381_g2_psi(point);
+        let x_times_point = self.bls12_381_g2_mul_by_x(point);
+        self.assert_is_equal(psi, x_times_point);
+    }
+
+    /// Decompresses a signature in the compressed encoding of G2 points of the ZCash
+    /// serialization format, asserting that it is a point of G2 other than the point at infinity.
+    pub fn bls12_381_g2_decompress(
+        &mut self,
+        signature: &BLSSignatureVariable,
+    ) -> Bls12381G2PointVariable {
+        let true_v = self._true();
+        let false_v = self._false();
+
+        // The three most significant bits of the encoding are the compression flag, the infinity
+        // flag and whether the y coordinate is the largest of the two square roots.
+        let bytes = signature.0 .0;
+        let [is_compressed, is_infinity, is_largest, rest @ ..] = bytes[0].as_be_bits();
+        self.assert_is_equal(is_compressed, true_v);
+        self.assert_is_equal(is_infinity, false_v);
+        let first_byte = ByteVariable::from_be_bits([
+            false_v, false_v, false_v, rest[0], rest[1], rest[2], rest[3], rest[4],
+        ]);
+
+        // The first half of the encoding is the coefficient `c1` of the x coordinate, and the
+        // second half is `c0`.
+        let mut c1_bytes = vec![first_byte];
+        c1_bytes.extend_from_slice(&bytes[1..48]);
+        let x = Bls12381Fp2Variable {
+            c0: self.bls12_381_fp_decode(&bytes[48..]),
+            c1: self.bls12_381_fp_decode(&c1_bytes),
+        };
+
+        let rhs = self.bls12_381_g2_curve_rhs(&x);
+        let (is_on_curve, root) = self.bls12_381_fp2_sqrt(&rhs);
+        self.assert_is_equal(is_on_curve, true_v);
+
+        // The largest square root is the one whose coefficient `c1` is greater than `(p - 1) / 2`,
+        // or whose coefficient `c0` is if `c1` is zero.
+        let zero = self.constant::<Bls12381FpVariable>(BigUint::zero());
+        let c1_is_zero = self.is_equal(root.c1.clone(), zero);
+        let c0_is_largest = self.bls12_381_fp_is_largest(&root.c0);
+        let c1_is_largest = self.bls12_381_fp_is_largest(&root.c1);
+        let root_is_largest = self.select(c1_is_zero, c0_is_largest, c1_is_largest);
+        let neg_root = self.bls12_381_fp2_neg(&root);
+        let has_sign = self.is_equal(root_is_largest, is_largest);
+        let y = self.select(has_sign, root, neg_root);
+
+        let point = Bls12381G2PointVariable { x, y };
+        self.bls12_381_g2_assert_in_subgroup(&point);
+        point
+    }
+
+    /// Returns the third point on the line of slope `lambda` through `a` and a point of x
+    /// coordinate `other_x`, reflected over the x axis.
+    fn bls12_381_g2_chord(
+        &mut self,
+        lambda: &Bls12381Fp2Variable,
+        a: &Bls12381G2PointVariable,
+        other_x: &Bls12381Fp2Variable,
+    ) -> Bls12381G2PointVariable {
+        let lambda_squared = self.bls12_381_fp2_square(lambda);
+        let x = self.bls12_381_fp2_sub(&lambda_squared, &a.x);
+        let x = self.bls12_381_fp2_sub(&x, other_x);
+        let dx = self.bls12_381_fp2_sub(&a.x, &x);
+        let y = self.bls12_381_fp2_mul(lambda, &dx);
+        let y = self.bls12_381_fp2_sub(&y, &a.y);
+        Bls12381G2PointVariable { x, y }
+    }
+
+    /// Returns `x^3 + 4 * (1 + u)`.
+    fn bls12_381_g2_curve_rhs(&mut self, x: &Bls12381Fp2Variable) -> Bls12381Fp2Variable {
+        let b = self.constant::<Bls12381Fp2Variable>(g2_b());
+        let x_squared = self.bls12_381_fp2_square(x);
+        let x_cubed = self.bls12_381_fp2_mul(&x_squared, x);
+        self.bls12_381_fp2_add(&x_cubed, &b)
+    }
+
+    /// Decodes 48 big-endian bytes as an element of the base field, asserting that they are
+    /// reduced.
+    fn bls12_381_fp_decode(&mut self, bytes: &[ByteVariable]) -> Bls12381FpVariable {
+        assert_eq!(bytes.len(), 48);
+        let true_v = self._true();
+        let zero_byte = self.constant::<ByteVariable>(0);
+        let mut padded_bytes = vec![zero_byte; 16];
+        padded_bytes.extend_from_slice(bytes);
+        let value = U512Variable::decode(self, &padded_bytes).to_biguint();
+        let is_reduced = self.nonnative_is_reduced::<Bls12381Base>(&value);
+        self.assert_is_equal(is_reduced, true_v);
+        NonNativeFieldVariable::from_biguint_low(&value)
+    }
+
+    /// Returns whether `a` is greater than `(p - 1) / 2`, which makes it the largest of `a` and
+    /// `-a`.
+    fn bls12_381_fp_is_largest(&mut self, a: &Bls12381FpVariable) -> BoolVariable {
+        let half = self
+            .api
+            .constant_biguint(&((Bls12381Base::modulus() - 1u32) / 2u32));
+        let is_at_most_half = self.api.cmp_biguint(&a.to_biguint(), &half);
+        self.not(BoolVariable::from(is_at_most_half))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::DefaultBuilder;
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_bls12_381_g2_decompress() {
+        let mut builder = DefaultBuilder::new();
+        let signature = builder.read::<BLSSignatureVariable>();
+        let point = builder.bls12_381_g2_decompress(&signature);
+        builder.bls12_381_g2_assert_on_curve(&point);
+        builder.write(point);
+        let circuit = builder.mock_build();
+
+        // The signature of 32 zero bytes of the `sign` test vectors of the consensus specs.
+        let signature = hex::decode("b6ed936746e01f8ecf281f020953fbf1f01debd5657c4a383940b020b26507f6076334f91e2366c96e9ab279fb5158090352ea1c5b0c9274504f4f0e7053af24802e51e4568d164fe986834f41e55c8e850ce1f98458c0cfc9ab380b55285a55").unwrap();
+        let mut input = circuit.input();
+        input.write::<BLSSignatureVariable>(signature.try_into().unwrap());
+        let (_witness, mut output) = circuit.mock_prove(&input);
+        let expected = Bls12381G2Point {
+            x: Bls12381Fp2::from_hex(
+                "0352ea1c5b0c9274504f4f0e7053af24802e51e4568d164fe986834f41e55c8e850ce1f98458c0cfc9ab380b55285a55",
+                "16ed936746e01f8ecf281f020953fbf1f01debd5657c4a383940b020b26507f6076334f91e2366c96e9ab279fb515809",
+            ),
+            y: Bls12381Fp2::from_hex(
+                "00b38d4a887b0c5caf93d6117e24b634b88526619d9be852058cd13aff5fdc1066646398aaafc081f1b9abca8a96932b",
+                "154c3a476f62b2604b66c348869691c626663aeb781f4fad5195efd509f5008b9ebed4c55fb1a138554d8e0ecb42628e",
+            ),
+        };
+        assert_eq!(output.read::<Bls12381G2PointVariable>(), expected);
+    }
+}
@@ -0,0 +1,346 @@
+use num::BigUint;
+
+use super::field::Bls12381Base;
+use super::fp2::{Bls12381Fp2, Bls12381Fp2Variable};
+use super::g2::Bls12381G2PointVariable;
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::builder::CircuitBuilder;
+use crate::frontend::ecc::nonnative::NonNativeField;
+use crate::frontend::uint::uint512::U512Variable;
+use crate::frontend::vars::{ByteVariable, EvmVariable};
+
+/// The domain separation tag of the proof of possession scheme of BLS signatures, which is the
+/// one used by the consensus layer of Ethereum.
+pub const BLS_SIG_DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+/// The number of bytes of the output of `expand_message_xmd` that are mapped to two elements of
+/// the quadratic extension, each of whose two coefficients takes 64 bytes.
+const EXPANDED_MESSAGE_LEN: usize = 256;
+
+/// The coefficients, from the constant one, of the numerator of the x coordinate of the isogeny
+/// of degree three from the curve `y^2 = x^3 + 240 * u * x + 1012 * (1 + u)` to the curve of G2.
+const ISO_X_NUMERATOR: [(&str, &str); 4] = [
+    (
+        "5c759507e8e333ebb5b7a9a47d7ed8532c52d39fd3a042a88b58423c50ae15d5c2638e343d9c71c6238aaaaaaaa97d6",
+        "5c759507e8e333ebb5b7a9a47d7ed8532c52d39fd3a042a88b58423c50ae15d5c2638e343d9c71c6238aaaaaaaa97d6",
+    ),
+    (
+        "0",
+        "11560bf17baa99bc32126fced787c88f984f87adf7ae0c7f9a208c6b4f20a4181472aaa9cb8d555526a9ffffffffc71a",
+    ),
+    (
+        "11560bf17baa99bc32126fced787c88f984f87adf7ae0c7f9a208c6b4f20a4181472aaa9cb8d555526a9ffffffffc71e",
+        "8ab05f8bdd54cde190937e76bc3e447cc27c3d6fbd7063fcd104635a790520c0a395554e5c6aaaa9354ffffffffe38d",
+    ),
+    (
+        "171d6541fa38ccfaed6dea691f5fb614cb14b4e7f4e810aa22d6108f142b85757098e38d0f671c7188e2aaaaaaaa5ed1",
+        "0",
+    ),
+];
+
+/// The coefficients of the denominator of the x coordinate of the isogeny.
+const ISO_X_DENOMINATOR: [(&str, &str); 3] = [
+    (
+        "0",
+        "1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaa63",
+    ),
+    (
+        "c",
+        "1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaa9f",
+    ),
+    ("1", "0"),
+];
+
+/// The coefficients of the numerator of the y coordinate of the isogeny.
+const ISO_Y_NUMERATOR: [(&str, &str); 4] = [
+    (
+        "1530477c7ab4113b59a4c18b076d11930f7da5d4a07f649bf54439d87d27e500fc8c25ebf8c92f6812cfc71c71c6d706",
+        "1530477c7ab4113b59a4c18b076d11930f7da5d4a07f649bf54439d87d27e500fc8c25ebf8c92f6812cfc71c71c6d706",
+    ),
+    (
+        "0",
+        "5c759507e8e333ebb5b7a9a47d7ed8532c52d39fd3a042a88b58423c50ae15d5c2638e343d9c71c6238aaaaaaaa97be",
+    ),
+    (
+        "11560bf17baa99bc32126fced787c88f984f87adf7ae0c7f9a208c6b4f20a4181472aaa9cb8d555526a9ffffffffc71c",
+        "8ab05f8bdd54cde190937e76bc3e447cc27c3d6fbd7063fcd104635a790520c0a395554e5c6aaaa9354ffffffffe38f",
+    ),
+    (
+        "124c9ad43b6cf79bfbf7043de3811ad0761b0f37a1e26286b0e977c69aa274524e79097a56dc4bd9e1b371c71c718b10",
+        "0",
+    ),
+];
+
+/// The coefficients of the denominator of the y coordinate of the isogeny.
+const ISO_Y_DENOMINATOR: [(&str, &str); 4] = [
+    (
+        "1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffa8fb",
+        "1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffa8fb",
+    ),
+    (
+        "0",
+        "1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffa9d3",
+    ),
+    (
+        "12",
+        "1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaa99",
+    ),
+    ("1", "0"),
+];
+
+/// The coefficient `a` of the curve isogenous to the curve of G2 on which the simplified SWU map
+/// is defined.
+fn iso_a() -> Bls12381Fp2 {
+    Bls12381Fp2::new(BigUint::from(0u32), BigUint::from(240u32))
+}
+
+/// The coefficient `b` of the isogenous curve.
+fn iso_b() -> Bls12381Fp2 {
+    Bls12381Fp2::new(BigUint::from(1012u32), BigUint::from(1012u32))
+}
+
+/// The non-square `Z = -(2 + u)` of the simplified SWU map.
+fn sswu_z() -> Bls12381Fp2 {
+    let p = Bls12381Base::modulus();
+    Bls12381Fp2::new(&p - 2u32, &p - 1u32)
+}
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Hashes `message` to a point of G2 with the `BLS12381G2_XMD:SHA-256_SSWU_RO_` suite of
+    /// RFC 9380 and the domain separation tag `dst`.
+    pub fn bls12_381_hash_to_g2(
+        &mut self,
+        message: &[ByteVariable],
+        dst: &[u8],
+    ) -> Bls12381G2PointVariable {
+        let [u0, u1] = self.bls12_381_hash_to_field(message, dst);
+        let q0 = self.bls12_381_map_to_curve(&u0);
+        let q1 = self.bls12_381_map_to_curve(&u1);
+        let sum = self.bls12_381_g2_add(&q0, &q1);
+        self.bls12_381_g2_clear_cofactor(&sum)
+    }
+
+    /// Returns `len` pseudorandom bytes derived from `message` with the `expand_message_xmd`
+    /// function of RFC 9380 instantiated with SHA-256.
+    fn bls12_381_expand_message_xmd(
+        &mut self,
+        message: &[ByteVariable],
+        dst: &[u8],
+        len: usize,
+    ) -> Vec<ByteVariable> {
+        assert!(dst.len() <= 255, "the domain separation tag is too long");
+        assert!(len % 32 == 0 && len / 32 <= 255, "invalid output length");
+
+        let mut dst_prime = dst.to_vec();
+        dst_prime.push(dst.len() as u8);
+        let dst_prime = self.bls12_381_constant_bytes(&dst_prime);
+
+        let mut message_prime = self.bls12_381_constant_bytes(&[0u8; 64]);
+        message_prime.extend_from_slice(message);
+        message_prime.extend(self.bls12_381_constant_bytes(&[(len >> 8) as u8, len as u8, 0]));
+        message_prime.extend_from_slice(&dst_prime);
+        let b_0 = self.sha256(&message_prime).as_bytes();
+
+        let mut output = Vec::new();
+        let mut b_i = self.bls12_381_constant_bytes(&[0u8; 32]);
+        for i in 1..=len / 32 {
+            // The first block is `H(b_0 || 1 || dst_prime)`, as `b_0` xored with zeros is `b_0`.
+            let mut input = b_0
+                .iter()
+                .zip(b_i.iter())
+                .map(|(a, b)| self.xor(*a, *b))
+                .collect::<Vec<_>>();
+            input.extend(self.bls12_381_constant_bytes(&[i as u8]));
+            input.extend_from_slice(&dst_prime);
+            b_i = self.sha256(&input).as_bytes().to_vec();
+            output.extend_from_slice(&b_i);
+        }
+        output
+    }
+
+    /// Hashes `message` to two elements of the quadratic extension with the `hash_to_field`
+    /// function of RFC 9380.
+    fn bls12_381_hash_to_field(
+        &mut self,
+        message: &[ByteVariable],
+        dst: &[u8],
+    ) -> [Bls12381Fp2Variable; 2] {
+        let bytes = self.bls12_381_expand_message_xmd(message, dst, EXPANDED_MESSAGE_LEN);
+        let coefficients = bytes
+            .chunks(64)
+            .map(|chunk| {
+                let value = U512Variable::decode(self, chunk).to_biguint();
+                self.nonnative_reduce::<Bls12381Base>(&value)
+            })
+            .collect::<Vec<_>>();
+        [
+            Bls12381Fp2Variable {
+                c0: coefficients[0].clone(),
+                c1: coefficients[1].clone(),
+            },
+            Bls12381Fp2Variable {
+                c0: coefficients[2].clone(),
+                c1: coefficients[3].clone(),
+            },
+        ]
+    }
+
+    /// Maps an element of the quadratic extension to the curve of G2, with the simplified SWU map
+    /// to an isogenous curve followed by the isogeny.
+    fn bls12_381_map_to_curve(&mut self, u: &Bls12381Fp2Variable) -> Bls12381G2PointVariable {
+        let point = self.bls12_381_sswu(u);
+        self.bls12_381_iso_map(&point)
+    }
+
+    /// Maps an element of the quadratic extension to the curve `y^2 = x^3 + a * x + b` isogenous
+    /// to the curve of G2, with the simplified SWU map of RFC 9380.
+    fn bls12_381_sswu(&mut self, u: &Bls12381Fp2Variable) -> Bls12381G2PointVariable {
+        let (a, b, z) = (iso_a(), iso_b(), sswu_z());
+        let neg_b_over_a = b.neg().mul(&a.inverse());
+        let b_over_z_a = b.mul(&z.mul(&a).inverse());
+        let a = self.constant::<Bls12381Fp2Variable>(a);
+        let b = self.constant::<Bls12381Fp2Variable>(b);
+        let z = self.constant::<Bls12381Fp2Variable>(z);
+        let one = self.constant::<Bls12381Fp2Variable>(Bls12381Fp2::one());
+
+        // The first candidate is `x1 = -b / a * (1 + 1 / (z^2 * u^4 + z * u^2))`, or `b / (z * a)`
+        // in the exceptional case where the denominator is zero.
+        let u_squared = self.bls12_381_fp2_square(u);
+        let z_u_squared = self.bls12_381_fp2_mul(&z, &u_squared);
+        let denominator = self.bls12_381_fp2_square(&z_u_squared);
+        let denominator = self.bls12_381_fp2_add(&denominator, &z_u_squared);
+        let is_exceptional = self.bls12_381_fp2_is_zero(&denominator);
+        let denominator = self.select(is_exceptional, one.clone(), denominator);
+        let inverse = self.bls12_381_fp2_inverse(&denominator);
+        let one_plus_inverse = self.bls12_381_fp2_add(&one, &inverse);
+        let neg_b_over_a = self.constant::<Bls12381Fp2Variable>(neg_b_over_a);
+        let x1 = self.bls12_381_fp2_mul(&neg_b_over_a, &one_plus_inverse);
+        let b_over_z_a = self.constant::<Bls12381Fp2Variable>(b_over_z_a);
+        let x1 = self.select(is_exceptional, b_over_z_a, x1);
+
+        // The second candidate is `x2 = z * u^2 * x1`. As `z` is not a square, at least one of
+        // `g(x1)` and `g(x2) = z^3 * u^6 * g(x1)` is a square.
+        let x2 = self.bls12_381_fp2_mul(&z_u_squared, &x1);
+        let g_x1 = self.bls12_381_iso_curve_rhs(&x1, &a, &b);
+        let g_x2 = self.bls12_381_iso_curve_rhs(&x2, &a, &b);
+        let (g_x1_is_square, y1) = self.bls12_381_fp2_sqrt(&g_x1);
+        let (g_x2_is_square, y2) = self.bls12_381_fp2_sqrt(&g_x2);
+        let true_v = self._true();
+        let either_is_square = self.or(g_x1_is_square, g_x2_is_square);
+        self.assert_is_equal(either_is_square, true_v);
+        let x = self.select(g_x1_is_square, x1, x2);
+        let y = self.select(g_x1_is_square, y1, y2);
+
+        // The sign of `y` is the sign of `u`.
+        let u_sign = self.bls12_381_fp2_sgn0(u);
+        let y_sign = self.bls12_381_fp2_sgn0(&y);
+        let neg_y = self.bls12_381_fp2_neg(&y);
+        let has_sign = self.is_equal(u_sign, y_sign);
+        let y = self.select(has_sign, y, neg_y);
+        Bls12381G2PointVariable { x, y }
+    }
+
+    /// Maps a point of the isogenous curve to the curve of G2, asserting that the denominators of
+    /// the isogeny are not zero.
+    fn bls12_381_iso_map(&mut self, point: &Bls12381G2PointVariable) -> Bls12381G2PointVariable {
+        let x_numerator = self.bls12_381_iso_polynomial(&ISO_X_NUMERATOR, &point.x);
+        let x_denominator = self.bls12_381_iso_polynomial(&ISO_X_DENOMINATOR, &point.x);
+        let y_numerator = self.bls12_381_iso_polynomial(&ISO_Y_NUMERATOR, &point.x);
+        let y_denominator = self.bls12_381_iso_polynomial(&ISO_Y_DENOMINATOR, &point.x);
+        let x = self.bls12_381_fp2_div(&x_numerator, &x_denominator);
+        let y = self.bls12_381_fp2_div(&y_numerator, &y_denominator);
+        let y = self.bls12_381_fp2_mul(&point.y, &y);
+        Bls12381G2PointVariable { x, y }
+    }
+
+    /// Multiplies a point of the curve of G2 by the effective cofactor of RFC 9380, which maps it
+    /// to G2, with the endomorphism `psi` as in appendix G.3 of the RFC.
+    fn bls12_381_g2_clear_cofactor(
+        &mut self,
+        point: &Bls12381G2PointVariable,
+    ) -> Bls12381G2PointVariable {
+        let t1 = self.bls12_381_g2_mul_by_x(point);
+        let t2 = self.bls12_381_g2_psi(point);
+        let t3 = self.bls12_381_g2_double(point);
+        let t3 = self.bls12_381_g2_psi(&t3);
+        let t3 = self.bls12_381_g2_psi(&t3);
+        let t3 = self.bls12_381_g2_sub(&t3, &t2);
+        let t2 = self.bls12_381_g2_add(&t1, &t2);
+        let t2 = self.bls12_381_g2_mul_by_x(&t2);
+        let t3 = self.bls12_381_g2_add(&t3, &t2);
+        let t3 = self.bls12_381_g2_sub(&t3, &t1);
+        self.bls12_381_g2_sub(&t3, point)
+    }
+
+    /// Evaluates a polynomial with constant coefficients, from the constant one, at `x`.
+    fn bls12_381_iso_polynomial(
+        &mut self,
+        coefficients: &[(&str, &str)],
+        x: &Bls12381Fp2Variable,
+    ) -> Bls12381Fp2Variable {
+        let (highest, rest) = coefficients.split_last().unwrap();
+        let mut acc =
+            self.constant::<Bls12381Fp2Variable>(Bls12381Fp2::from_hex(highest.0, highest.1));
+        for coefficient in rest.iter().rev() {
+            let coefficient = self.constant::<Bls12381Fp2Variable>(Bls12381Fp2::from_hex(
+                coefficient.0,
+                coefficient.1,
+            ));
+            acc = self.bls12_381_fp2_mul(&acc, x);
+            acc = self.bls12_381_fp2_add(&acc, &coefficient);
+        }
+        acc
+    }
+
+    /// Returns `x^3 + a * x + b`.
+    fn bls12_381_iso_curve_rhs(
+        &mut self,
+        x: &Bls12381Fp2Variable,
+        a: &Bls12381Fp2Variable,
+        b: &Bls12381Fp2Variable,
+    ) -> Bls12381Fp2Variable {
+        let x_squared = self.bls12_381_fp2_square(x);
+        let x_squared_plus_a = self.bls12_381_fp2_add(&x_squared, a);
+        let rhs = self.bls12_381_fp2_mul(&x_squared_plus_a, x);
+        self.bls12_381_fp2_add(&rhs, b)
+    }
+
+    fn bls12_381_constant_bytes(&mut self, bytes: &[u8]) -> Vec<ByteVariable> {
+        bytes
+            .iter()
+            .map(|byte| self.constant::<ByteVariable>(*byte))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frontend::ecc::bls12_381::g2::Bls12381G2Point;
+    use crate::prelude::DefaultBuilder;
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_bls12_381_hash_to_g2() {
+        // The test vector of RFC 9380 for the empty message.
+        let mut builder = DefaultBuilder::new();
+        let point = builder
+            .bls12_381_hash_to_g2(&[], b"QUUX-V01-CS02-with-BLS12381G2_XMD:SHA-256_SSWU_RO_");
+        builder.write(point);
+        let circuit = builder.mock_build();
+
+        let input = circuit.input();
+        let (_witness, mut output) = circuit.mock_prove(&input);
+        let expected = Bls12381G2Point {
+            x: Bls12381Fp2::from_hex(
+                "0141ebfbdca40eb85b87142e130ab689c673cf60f1a3e98d69335266f30d9b8d4ac44c1038e9dcdd5393faf5c41fb78a",
+                "05cb8437535e20ecffaef7752baddf98034139c38452458baeefab379ba13dff5bf5dd71b72418717047f5b0f37da03d",
+            ),
+            y: Bls12381Fp2::from_hex(
+                "0503921d7f6a12805e72940b963c0cf3471c7b2a524950ca195d11062ee75ec076daf2d4bc358c4b190c0c98064fdd92",
+                "12424ac32561493f3fe3c260708a12b7c620e7be00099a974e259ddc7d1f6395c3c811cdd19f1e8dbf3e9ecfdcbab8d6",
+            ),
+        };
+        assert_eq!(output.read::<Bls12381G2PointVariable>(), expected);
+    }
+}
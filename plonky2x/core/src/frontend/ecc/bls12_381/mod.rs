@@ -0,0 +1,8 @@
+pub mod field;
+pub mod fp12;
+pub mod fp2;
+pub mod g1;
+pub mod g2;
+pub mod hash_to_curve;
+pub mod pairing;
+pub mod signature;
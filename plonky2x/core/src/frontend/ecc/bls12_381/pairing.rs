@@ -0,0 +1,172 @@
+use num::{BigUint, Zero};
+
+use super::fp12::{Bls12381Fp12, Bls12381Fp12Variable};
+use super::fp2::{Bls12381Fp2Variable, Bls12381FpVariable};
+use super::g1::Bls12381G1PointVariable;
+use super::g2::{Bls12381G2PointVariable, BLS12_381_X};
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::builder::CircuitBuilder;
+use crate::frontend::vars::BoolVariable;
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Returns whether the product of the pairings of the pairs of points of G1 and G2 is one.
+    ///
+    /// The points of G2 must be in G2, as the multiples of them computed by the Miller loop use
+    /// incomplete formulas that make the circuit unsatisfiable for points of small order.
+    pub fn bls12_381_pairing_check(
+        &mut self,
+        pairs: &[(Bls12381G1PointVariable, Bls12381G2PointVariable)],
+    ) -> BoolVariable {
+        let f = self.bls12_381_miller_loop(pairs);
+        let f = self.bls12_381_final_exponentiation(&f);
+        self.bls12_381_fp12_is_one(&f)
+    }
+
+    /// Computes the product of the Miller loops of the optimal ate pairing of the pairs of
+    /// points, with the lines multiplied by `w^3` which is cancelled by the final exponentiation.
+    fn bls12_381_miller_loop(
+        &mut self,
+        pairs: &[(Bls12381G1PointVariable, Bls12381G2PointVariable)],
+    ) -> Bls12381Fp12Variable {
+        let mut f = self.constant::<Bls12381Fp12Variable>(Bls12381Fp12::one());
+        let mut ts = pairs.iter().map(|(_, q)| q.clone()).collect::<Vec<_>>();
+        for i in (0..63).rev() {
+            f = self.bls12_381_fp12_square(&f);
+            for ((p, _), t) in pairs.iter().zip(ts.iter_mut()) {
+                let (double, lambda) = self.bls12_381_g2_double_with_slope(t);
+                f = self.bls12_381_mul_by_line_eval(&f, &lambda, t, p);
+                *t = double;
+            }
+            if (BLS12_381_X >> i) & 1 == 1 {
+                for ((p, q), t) in pairs.iter().zip(ts.iter_mut()) {
+                    let (sum, lambda) = self.bls12_381_g2_add_with_slope(t, q);
+                    f = self.bls12_381_mul_by_line_eval(&f, &lambda, t, p);
+                    *t = sum;
+                }
+            }
+        }
+
+        // The loop computes the function of `|x|`, which is conjugated as `x` is negative.
+        self.bls12_381_fp12_conjugate(&f)
+    }
+
+    /// Multiplies `f` by the line of slope `lambda` through `t`, evaluated at `p` and multiplied by
+    /// `w^3`, which is `(lambda * x_t - y_t) - lambda * x_p * w^2 + y_p * w^3`.
+    fn bls12_381_mul_by_line_eval(
+        &mut self,
+        f: &Bls12381Fp12Variable,
+        lambda: &Bls12381Fp2Variable,
+        t: &Bls12381G2PointVariable,
+        p: &Bls12381G1PointVariable,
+    ) -> Bls12381Fp12Variable {
+        let lambda_x_t = self.bls12_381_fp2_mul(lambda, &t.x);
+        let l0 = self.bls12_381_fp2_sub(&lambda_x_t, &t.y);
+        let neg_lambda = self.bls12_381_fp2_neg(lambda);
+        let l2 = self.bls12_381_fp2_mul_by_fp(&neg_lambda, &p.x);
+        let zero = self.constant::<Bls12381FpVariable>(BigUint::zero());
+        let l3 = Bls12381Fp2Variable {
+            c0: p.y.clone(),
+            c1: zero,
+        };
+        self.bls12_381_fp12_mul_by_line(f, &l0, &l2, &l3)
+    }
+
+    /// Raises the output of the Miller loop to the power `3 * (p^12 - 1) / r`, which is a
+    /// nondegenerate pairing as `3` is coprime with `r`.
+    fn bls12_381_final_exponentiation(&mut self, f: &Bls12381Fp12Variable) -> Bls12381Fp12Variable {
+        // The easy part raises `f` to the power `(p^6 - 1) * (p^2 + 1)`, which maps it to the
+        // cyclotomic subgroup, where the inverse is the conjugate.
+        let f_inverse = self.bls12_381_fp12_inverse(f);
+        let f_conjugate = self.bls12_381_fp12_conjugate(f);
+        let f = self.bls12_381_fp12_mul(&f_conjugate, &f_inverse);
+        let f_frobenius = self.bls12_381_fp12_frobenius(&f, 2);
+        let f = self.bls12_381_fp12_mul(&f_frobenius, &f);
+
+        // The hard part raises it to the power `3 * (p^4 - p^2 + 1) / r`, which is
+        // `(x - 1)^2 * (x + p) * (x^2 + p^2 - 1) + 3`.
+        let t = self.bls12_381_fp12_exp_by_x(&f);
+        let f_conjugate = self.bls12_381_fp12_conjugate(&f);
+        let t = self.bls12_381_fp12_mul(&t, &f_conjugate);
+
+        let t_x = self.bls12_381_fp12_exp_by_x(&t);
+        let t_conjugate = self.bls12_381_fp12_conjugate(&t);
+        let t = self.bls12_381_fp12_mul(&t_x, &t_conjugate);
+
+        let t_x = self.bls12_381_fp12_exp_by_x(&t);
+        let t_frobenius = self.bls12_381_fp12_frobenius(&t, 1);
+        let t = self.bls12_381_fp12_mul(&t_x, &t_frobenius);
+
+        let t_x = self.bls12_381_fp12_exp_by_x(&t);
+        let t_x_x = self.bls12_381_fp12_exp_by_x(&t_x);
+        let t_frobenius = self.bls12_381_fp12_frobenius(&t, 2);
+        let t_conjugate = self.bls12_381_fp12_conjugate(&t);
+        let t = self.bls12_381_fp12_mul(&t_x_x, &t_frobenius);
+        let t = self.bls12_381_fp12_mul(&t, &t_conjugate);
+
+        let f_squared = self.bls12_381_fp12_square(&f);
+        let f_cubed = self.bls12_381_fp12_mul(&f_squared, &f);
+        self.bls12_381_fp12_mul(&t, &f_cubed)
+    }
+
+    /// Returns `f^x` for an element `f` of the cyclotomic subgroup, where `x` is the negative
+    /// parameter of BLS12-381.
+    fn bls12_381_fp12_exp_by_x(&mut self, f: &Bls12381Fp12Variable) -> Bls12381Fp12Variable {
+        let mut acc = f.clone();
+        for i in (0..63).rev() {
+            acc = self.bls12_381_fp12_square(&acc);
+            if (BLS12_381_X >> i) & 1 == 1 {
+                acc = self.bls12_381_fp12_mul(&acc, f);
+            }
+        }
+        self.bls12_381_fp12_conjugate(&acc)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frontend::ecc::bls12_381::fp2::Bls12381Fp2;
+    use crate::frontend::ecc::bls12_381::g1::Bls12381G1;
+    use crate::frontend::ecc::bls12_381::g2::Bls12381G2Point;
+    use crate::frontend::ecc::weierstrass::{double_native, neg_native, WeierstrassCurve};
+    use crate::prelude::DefaultBuilder;
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_bls12_381_pairing_check() {
+        type G1 = Bls12381G1PointVariable;
+        type G2 = Bls12381G2PointVariable;
+
+        let mut builder = DefaultBuilder::new();
+        let p = builder.read::<G1>();
+        let q = builder.read::<G2>();
+        let r = builder.read::<G1>();
+        let s = builder.read::<G2>();
+        let is_one = builder.bls12_381_pairing_check(&[(p, q), (r, s)]);
+        builder.write(is_one);
+        let circuit = builder.mock_build();
+
+        // `e(2 * G1, G2) * e(-G1, 2 * G2) == 1`, while `e(2 * G1, G2) * e(G1, 2 * G2) != 1`.
+        let g1 = Bls12381G1::generator();
+        let g2 = Bls12381G2Point::generator();
+        let two_g2 = Bls12381G2Point {
+            x: Bls12381Fp2::from_hex(
+                "1638533957d540a9d2370f17cc7ed5863bc0b995b8825e0ee1ea1e1e4d00dbae81f14b0bf3611b78c952aacab827a053",
+                "0a4edef9c1ed7f729f520e47730a124fd70662a904ba1074728114d1031e1572c6c886f6b57ec72a6178288c47c33577",
+            ),
+            y: Bls12381Fp2::from_hex(
+                "0468fb440d82b0630aeb8dca2b5256789a66da69bf91009cbfe6bd221e47aa8ae88dece9764bf3bd999d95d71e4c9899",
+                "0f6d4552fa65dd2638b361543f887136a43253d9c66c411697003f7a13c308f5422e1aa0a59c8967acdefd8b6e36ccf3",
+            ),
+        };
+        for (r, expected) in [(neg_native::<Bls12381G1>(&g1), true), (g1.clone(), false)] {
+            let mut input = circuit.input();
+            input.write::<G1>(double_native::<Bls12381G1>(&g1).into());
+            input.write::<G2>(g2.clone());
+            input.write::<G1>(r.into());
+            input.write::<G2>(two_g2.clone());
+            let (_witness, mut output) = circuit.mock_prove(&input);
+            assert_eq!(output.read::<BoolVariable>(), expected);
+        }
+    }
+}
@@ -0,0 +1,88 @@
+use super::g1::{Bls12381G1, Bls12381G1PointVariable};
+use super::hash_to_curve::BLS_SIG_DST;
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::builder::CircuitBuilder;
+use crate::frontend::ecc::weierstrass::{neg_native, WeierstrassCurve};
+use crate::frontend::eth::vars::{BLSPubkeyVariable, BLSSignatureVariable};
+use crate::frontend::vars::ByteVariable;
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Asserts that `signature` is a valid BLS signature of `message` by `pubkey`, in the proof of
+    /// possession scheme used by the consensus layer of Ethereum.
+    ///
+    /// The signature is decompressed and checked to be in G2, and the check is
+    /// `e(pubkey, H(message)) * e(-G1, signature) == 1`. The public key is not checked to be in
+    /// G1, which is done once when it is registered, as for the validators of the beacon chain.
+    pub fn bls_verify(
+        &mut self,
+        pubkey: &BLSPubkeyVariable,
+        message: &[ByteVariable],
+        signature: &BLSSignatureVariable,
+    ) {
+        let pubkey = self.bls12_381_g1_decompress(pubkey);
+        self.bls12_381_verify_signature(&pubkey, message, signature);
+    }
+
+    /// Asserts that `signature` is a valid BLS signature of `message` by the point `pubkey` of
+    /// G1, such as the aggregate of the public keys of the signers of an aggregate signature.
+    pub fn bls12_381_verify_signature(
+        &mut self,
+        pubkey: &Bls12381G1PointVariable,
+        message: &[ByteVariable],
+        signature: &BLSSignatureVariable,
+    ) {
+        let true_v = self._true();
+        let signature = self.bls12_381_g2_decompress(signature);
+        let hash = self.bls12_381_hash_to_g2(message, BLS_SIG_DST);
+
+        let neg_generator = neg_native::<Bls12381G1>(&Bls12381G1::generator());
+        let neg_generator = self.constant::<Bls12381G1PointVariable>(neg_generator.into());
+        let is_valid =
+            self.bls12_381_pairing_check(&[(pubkey.clone(), hash), (neg_generator, signature)]);
+        self.assert_is_equal(is_valid, true_v);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::{ArrayVariable, DefaultBuilder};
+
+    /// The public key of the secret key
+    /// `0x263dbd792f5b1be47ed85f8938c0f29586af0d3ac7b977f21c278fe1462040e3` of the `sign` test
+    /// vectors of the consensus specs.
+    const PUBKEY: &str = "a491d1b0ecd9bb917989f0e74f0dea0422eac4a873e5e2644f368dffb9a6e20fd6e10c1b77654d067c0618f6e5a7f79a";
+
+    /// The signature of 32 zero bytes by the secret key.
+    const SIGNATURE: &str = "b6ed936746e01f8ecf281f020953fbf1f01debd5657c4a383940b020b26507f6076334f91e2366c96e9ab279fb5158090352ea1c5b0c9274504f4f0e7053af24802e51e4568d164fe986834f41e55c8e850ce1f98458c0cfc9ab380b55285a55";
+
+    fn prove_bls_verify(message: [u8; 32]) {
+        let mut builder = DefaultBuilder::new();
+        let pubkey = builder.read::<BLSPubkeyVariable>();
+        let message_var = builder.read::<ArrayVariable<ByteVariable, 32>>();
+        let signature = builder.read::<BLSSignatureVariable>();
+        builder.bls_verify(&pubkey, message_var.as_slice(), &signature);
+        let circuit = builder.mock_build();
+
+        let mut input = circuit.input();
+        input.write::<BLSPubkeyVariable>(hex::decode(PUBKEY).unwrap().try_into().unwrap());
+        input.write::<ArrayVariable<ByteVariable, 32>>(message.to_vec());
+        input.write::<BLSSignatureVariable>(hex::decode(SIGNATURE).unwrap().try_into().unwrap());
+        circuit.mock_prove(&input);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_bls_verify() {
+        prove_bls_verify([0u8; 32]);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    #[should_panic(expected = "was set twice with different values")]
+    fn test_bls_verify_rejects_wrong_message() {
+        let mut message = [0u8; 32];
+        message[31] = 1;
+        prove_bls_verify(message);
+    }
+}
@@ -126,6 +126,23 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         self.curta_eddsa_verify_sigs(msg_array, Some(msg_len_vec), sig_array, pub_key_array);
     }
 
+    /// Verifies a single eddsa signature of `message` by `pubkey`. If message_byte_length is None,
+    /// then the message should have the length of MAX_MSG_LENGTH_BYTES.
+    pub fn curta_eddsa_verify_sig<const MAX_MSG_LENGTH_BYTES: usize>(
+        &mut self,
+        message: BytesVariable<MAX_MSG_LENGTH_BYTES>,
+        message_byte_length: Option<U32Variable>,
+        signature: EDDSASignatureVariable,
+        pubkey: CompressedEdwardsYVariable,
+    ) {
+        self.curta_eddsa_verify_sigs::<MAX_MSG_LENGTH_BYTES, 1>(
+            ArrayVariable::from(vec![message]),
+            message_byte_length.map(|length| ArrayVariable::from(vec![length])),
+            ArrayVariable::from(vec![signature]),
+            ArrayVariable::from(vec![pubkey]),
+        );
+    }
+
     /// This function will verify a set of eddsa signatures. If message_byte_lengths is None, then
     /// all the messages should have the length of MAX_MSG_LENGTH_BYTES.
     pub fn curta_eddsa_verify_sigs<
@@ -341,6 +358,39 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_curta_eddsa_verify_sig() {
+        utils::setup_logger();
+
+        let mut builder = DefaultBuilder::new();
+        let pkey = builder.read::<CompressedEdwardsYVariable>();
+        let signature = builder.read::<EDDSASignatureVariable>();
+        let message = builder.read::<BytesVariable<MAX_MSG_LEN_BYTES>>();
+        let message_len = builder.read::<U32Variable>();
+        builder.curta_eddsa_verify_sig(message, Some(message_len), signature, pkey);
+        let circuit = builder.build();
+
+        let mut test_message = b"single signature".to_vec();
+        let test_message_len = test_message.len() as u32;
+        let test_signing_key = SigningKey::generate(&mut OsRng);
+        let test_pub_key = test_signing_key.verifying_key();
+        let test_signature = test_signing_key.sign(&test_message);
+        test_message.resize(MAX_MSG_LEN_BYTES, 0);
+
+        let mut input = circuit.input();
+        input.write::<CompressedEdwardsYVariable>(CompressedEdwardsY(test_pub_key.to_bytes()));
+        input.write::<EDDSASignatureVariable>(EDDSASignatureVariableValue {
+            r: CompressedEdwardsY(*test_signature.r_bytes()),
+            s: U256::from_little_endian(test_signature.s_bytes()),
+        });
+        input.write::<BytesVariable<MAX_MSG_LEN_BYTES>>(test_message.try_into().unwrap());
+        input.write::<U32Variable>(test_message_len);
+
+        let (proof, output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+    }
+
     #[test]
     #[should_panic]
     #[cfg_attr(feature = "ci", ignore)]
@@ -0,0 +1,71 @@
+use std::fmt::Debug;
+
+use plonky2::hash::hash_types::RichField;
+use plonky2x_derive::CircuitVariable;
+
+use super::weierstrass::{WeierstrassCurve, WeierstrassPointVariable};
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::builder::CircuitBuilder;
+use crate::frontend::vars::{
+    BoolVariable, Bytes32Variable, CircuitVariable, U256Variable, Variable,
+};
+
+/// An ECDSA signature over a Weierstrass curve.
+#[derive(Debug, Clone, Copy, CircuitVariable)]
+#[value_name(ECDSASignature)]
+#[value_derive(PartialEq, Eq)]
+pub struct ECDSASignatureVariable {
+    pub r: U256Variable,
+    pub s: U256Variable,
+}
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Returns whether `signature` is a valid ECDSA signature of `msg_hash` by `pubkey` over the
+    /// curve `C`.
+    ///
+    /// A signature whose `r` or `s` is zero or not reduced modulo the order of the curve is
+    /// invalid. The public key is asserted to be on the curve. Signatures with a high `s` are
+    /// accepted, as malleability is left to the caller.
+    pub fn weierstrass_ecdsa_verify<C: WeierstrassCurve>(
+        &mut self,
+        msg_hash: Bytes32Variable,
+        signature: &ECDSASignatureVariable,
+        pubkey: &WeierstrassPointVariable<C>,
+    ) -> BoolVariable {
+        let one = self.one::<U256Variable>();
+        let r_valid = self.ecdsa_is_valid_scalar::<C>(signature.r);
+        let s_valid = self.ecdsa_is_valid_scalar::<C>(signature.s);
+
+        // Invalid scalars are replaced by one, so that the rest of the circuit is satisfiable.
+        let r = self.select(r_valid, signature.r, one);
+        let s = self.select(s_valid, signature.s, one);
+        let r = self.nonnative_from_u256::<C::ScalarField>(r);
+        let s = self.nonnative_from_u256::<C::ScalarField>(s);
+
+        let z = self.bytes32_to_u256(msg_hash);
+        let z = self.nonnative_from_u256::<C::ScalarField>(z);
+        let s_inverse = self.nonnative_inverse(&s);
+        let u1 = self.nonnative_mul(&z, &s_inverse);
+        let u2 = self.nonnative_mul(&r, &s_inverse);
+
+        self.weierstrass_assert_on_curve(pubkey);
+        let point = self.weierstrass_double_scalar_mul(&u1, &u2, pubkey);
+        let x = self.nonnative_reduce::<C::ScalarField>(&point.x.to_biguint());
+
+        let x_matches = self.is_equal(x, r);
+        let is_valid = self.and(r_valid, s_valid);
+        self.and(is_valid, x_matches)
+    }
+
+    /// Returns whether `scalar` is a nonzero element of the scalar field of the curve `C`.
+    pub(crate) fn ecdsa_is_valid_scalar<C: WeierstrassCurve>(
+        &mut self,
+        scalar: U256Variable,
+    ) -> BoolVariable {
+        let zero = self.zero::<U256Variable>();
+        let is_zero = self.is_equal(scalar, zero);
+        let is_nonzero = self.not(is_zero);
+        let is_reduced = self.nonnative_u256_is_reduced::<C::ScalarField>(scalar);
+        self.and(is_nonzero, is_reduced)
+    }
+}
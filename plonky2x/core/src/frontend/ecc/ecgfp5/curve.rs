@@ -0,0 +1,324 @@
+use num::BigUint;
+use plonky2::field::types::Field;
+use plonky2::hash::hash_types::RichField;
+use plonky2x_derive::CircuitVariable;
+
+use super::field::{
+    gfp5_add_native, gfp5_div_native, gfp5_mul_native, gfp5_sub_native, GFp5Variable,
+};
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::builder::CircuitBuilder;
+use crate::frontend::vars::{BoolVariable, CircuitVariable, Variable};
+
+/// The coefficients of the x coordinate of the generator.
+const GENERATOR_X: [u64; 5] = [
+    13751537904594739872,
+    8859161600321272310,
+    2721769971267987483,
+    1199172087896026830,
+    5553044710935820496,
+];
+
+/// The coefficients of the y coordinate of the generator.
+const GENERATOR_Y: [u64; 5] = [
+    18146711375748607373,
+    11035877302916390812,
+    12326466918881407707,
+    17988590196751087656,
+    10165058723446039306,
+];
+
+/// The seed of the point that scalar multiplications start from. Its x coordinate is the first
+/// of the four little-endian words of the SHA-256 digest of the seed, completed with zero, whose
+/// first coefficient is incremented until it is the x coordinate of a point.
+pub(crate) const OFFSET_SEED: &[u8] = b"plonky2x ecgfp5 offset";
+
+/// The coefficients of the x coordinate of the offset point.
+const OFFSET_X: [u64; 5] = [
+    8943088080484656674,
+    15192531939720001552,
+    16237121513019857217,
+    12963481270193667005,
+    0,
+];
+
+/// The coefficients of the y coordinate of the offset point.
+const OFFSET_Y: [u64; 5] = [
+    7348200967106014260,
+    9348688909954023711,
+    10821044929797244741,
+    8226203343319792947,
+    3722794535420779247,
+];
+
+/// A point of EcGFp5 in affine coordinates, other than the point at infinity.
+///
+/// EcGFp5 is the curve `y^2 = x * (x^2 + 2 * x + 263 * z)` over `GF(p^5)`, whose order is twice
+/// the order of `EcGFp5Scalar`. The only point of order two is `(0, 0)`.
+#[derive(Debug, Clone, Copy, CircuitVariable)]
+#[value_name(EcGFp5Point)]
+#[value_derive(PartialEq, Eq, Copy)]
+pub struct EcGFp5PointVariable {
+    pub x: GFp5Variable,
+    pub y: GFp5Variable,
+}
+
+/// Returns the coefficient `a` of the equation `y^2 = x^3 + a * x^2 + b * x` of the curve.
+fn curve_a<F: Field>() -> [F; 5] {
+    [F::TWO, F::ZERO, F::ZERO, F::ZERO, F::ZERO]
+}
+
+/// Returns the coefficient `b` of the equation `y^2 = x^3 + a * x^2 + b * x` of the curve.
+fn curve_b<F: Field>() -> [F; 5] {
+    [
+        F::ZERO,
+        F::from_canonical_u64(263),
+        F::ZERO,
+        F::ZERO,
+        F::ZERO,
+    ]
+}
+
+/// Returns the generator of the subgroup of prime order.
+pub(crate) fn generator<F: RichField>() -> EcGFp5Point<F> {
+    EcGFp5Point {
+        x: GENERATOR_X.map(F::from_canonical_u64),
+        y: GENERATOR_Y.map(F::from_canonical_u64),
+    }
+}
+
+/// Returns the point that scalar multiplications start from, whose discrete logarithm is unknown.
+pub(crate) fn offset_point<F: RichField>() -> EcGFp5Point<F> {
+    EcGFp5Point {
+        x: OFFSET_X.map(F::from_canonical_u64),
+        y: OFFSET_Y.map(F::from_canonical_u64),
+    }
+}
+
+/// Returns `x^3 + a * x^2 + b * x`.
+fn curve_rhs_native<F: Field>(x: &[F; 5]) -> [F; 5] {
+    let x_squared = gfp5_mul_native(x, x);
+    let a_x = gfp5_mul_native(&curve_a(), x);
+    let inner = gfp5_add_native(&gfp5_add_native(&x_squared, &a_x), &curve_b());
+    gfp5_mul_native(x, &inner)
+}
+
+/// Returns whether `point` is on the curve.
+pub(crate) fn is_on_curve_native<F: RichField>(point: &EcGFp5Point<F>) -> bool {
+    gfp5_mul_native(&point.y, &point.y) == curve_rhs_native(&point.x)
+}
+
+/// Returns `-a`.
+pub(crate) fn neg_native<F: RichField>(a: &EcGFp5Point<F>) -> EcGFp5Point<F> {
+    EcGFp5Point {
+        x: a.x,
+        y: gfp5_sub_native(&[F::ZERO; 5], &a.y),
+    }
+}
+
+/// Returns `a + b`, or `None` if the sum is the point at infinity.
+pub(crate) fn add_native<F: RichField>(
+    a: &EcGFp5Point<F>,
+    b: &EcGFp5Point<F>,
+) -> Option<EcGFp5Point<F>> {
+    if a.x != b.x {
+        let slope = gfp5_div_native(&gfp5_sub_native(&b.y, &a.y), &gfp5_sub_native(&b.x, &a.x))?;
+        Some(chord_native(&slope, a, &b.x))
+    } else if a.y == b.y {
+        double_native(a)
+    } else {
+        None
+    }
+}
+
+/// Returns `2 * a`, or `None` if `a` is the point of order two.
+pub(crate) fn double_native<F: RichField>(a: &EcGFp5Point<F>) -> Option<EcGFp5Point<F>> {
+    let three = F::from_canonical_u64(3);
+    let x_squared = gfp5_mul_native(&a.x, &a.x);
+    let numerator = gfp5_add_native(
+        &gfp5_add_native(
+            &x_squared.map(|c| three * c),
+            &gfp5_mul_native(&curve_a::<F>().map(|c| c.double()), &a.x),
+        ),
+        &curve_b(),
+    );
+    let slope = gfp5_div_native(&numerator, &a.y.map(|c| c.double()))?;
+    Some(chord_native(&slope, a, &a.x))
+}
+
+/// Returns `scalar * point`, or `None` if the product is the point at infinity.
+pub(crate) fn mul_native<F: RichField>(
+    scalar: &BigUint,
+    point: &EcGFp5Point<F>,
+) -> Option<EcGFp5Point<F>> {
+    let mut result: Option<EcGFp5Point<F>> = None;
+    for i in (0..scalar.bits()).rev() {
+        result = result.and_then(|r| double_native(&r));
+        if scalar.bit(i) {
+            result = match result {
+                Some(r) => add_native(&r, point),
+                None => Some(*point),
+            };
+        }
+    }
+    result
+}
+
+/// Returns the third point of the line of slope `slope` through `a` and a point of x coordinate
+/// `other_x`, negated.
+fn chord_native<F: RichField>(
+    slope: &[F; 5],
+    a: &EcGFp5Point<F>,
+    other_x: &[F; 5],
+) -> EcGFp5Point<F> {
+    let x = gfp5_sub_native(
+        &gfp5_sub_native(
+            &gfp5_sub_native(&gfp5_mul_native(slope, slope), &curve_a()),
+            &a.x,
+        ),
+        other_x,
+    );
+    let y = gfp5_sub_native(&gfp5_mul_native(slope, &gfp5_sub_native(&a.x, &x)), &a.y);
+    EcGFp5Point { x, y }
+}
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Returns whether `point` is on the curve.
+    pub fn ecgfp5_is_on_curve(&mut self, point: &EcGFp5PointVariable) -> BoolVariable {
+        let y_squared = self.gfp5_mul(&point.y, &point.y);
+        let rhs = self.ecgfp5_curve_rhs(&point.x);
+        self.is_equal(y_squared, rhs)
+    }
+
+    /// Returns `a + b`, asserting that the x coordinates of the points are distinct.
+    ///
+    /// The formula is incomplete, so that doublings and sums at infinity make the circuit
+    /// unsatisfiable.
+    pub fn ecgfp5_add(
+        &mut self,
+        a: &EcGFp5PointVariable,
+        b: &EcGFp5PointVariable,
+    ) -> EcGFp5PointVariable {
+        let dy = self.gfp5_sub(&b.y, &a.y);
+        let dx = self.gfp5_sub(&b.x, &a.x);
+        let slope = self.gfp5_div(&dy, &dx);
+        self.ecgfp5_chord(&slope, a, &b.x)
+    }
+
+    /// Returns `2 * a`, asserting that `a` is not the point of order two.
+    pub fn ecgfp5_double(&mut self, a: &EcGFp5PointVariable) -> EcGFp5PointVariable {
+        let curve_a = curve_a::<L::Field>().map(|c| c.double());
+        let two_a = self.constant::<GFp5Variable>(curve_a);
+        let curve_b = self.constant::<GFp5Variable>(curve_b());
+        let x_squared = self.gfp5_mul(&a.x, &a.x);
+        let three_x_squared = self.gfp5_mul_const(L::Field::from_canonical_u64(3), &x_squared);
+        let two_a_x = self.gfp5_mul(&two_a, &a.x);
+        let numerator = self.gfp5_add(&three_x_squared, &two_a_x);
+        let numerator = self.gfp5_add(&numerator, &curve_b);
+        let two_y = self.gfp5_mul_const(L::Field::TWO, &a.y);
+        let slope = self.gfp5_div(&numerator, &two_y);
+        self.ecgfp5_chord(&slope, a, &a.x)
+    }
+
+    /// Returns `-a`.
+    pub fn ecgfp5_neg(&mut self, a: &EcGFp5PointVariable) -> EcGFp5PointVariable {
+        EcGFp5PointVariable {
+            x: a.x,
+            y: self.gfp5_neg(&a.y),
+        }
+    }
+
+    /// Returns the third point of the line of slope `slope` through `a` and a point of x
+    /// coordinate `other_x`, negated.
+    fn ecgfp5_chord(
+        &mut self,
+        slope: &GFp5Variable,
+        a: &EcGFp5PointVariable,
+        other_x: &GFp5Variable,
+    ) -> EcGFp5PointVariable {
+        let curve_a = self.constant::<GFp5Variable>(curve_a());
+        let slope_squared = self.gfp5_mul(slope, slope);
+        let x = self.gfp5_sub(&slope_squared, &curve_a);
+        let x = self.gfp5_sub(&x, &a.x);
+        let x = self.gfp5_sub(&x, other_x);
+        let dx = self.gfp5_sub(&a.x, &x);
+        let y = self.gfp5_mul(slope, &dx);
+        let y = self.gfp5_sub(&y, &a.y);
+        EcGFp5PointVariable { x, y }
+    }
+
+    /// Returns `x^3 + a * x^2 + b * x`.
+    fn ecgfp5_curve_rhs(&mut self, x: &GFp5Variable) -> GFp5Variable {
+        let curve_a = self.constant::<GFp5Variable>(curve_a());
+        let curve_b = self.constant::<GFp5Variable>(curve_b());
+        let x_squared = self.gfp5_mul(x, x);
+        let a_x = self.gfp5_mul(&curve_a, x);
+        let inner = self.gfp5_add(&x_squared, &a_x);
+        let inner = self.gfp5_add(&inner, &curve_b);
+        self.gfp5_mul(x, &inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use plonky2::field::goldilocks_field::GoldilocksField;
+    use sha2::{Digest, Sha256};
+
+    use super::*;
+    use crate::frontend::ecc::ecgfp5::field::EcGFp5Scalar;
+    use crate::frontend::ecc::nonnative::NonNativeField;
+    use crate::prelude::DefaultBuilder;
+
+    type F = GoldilocksField;
+
+    #[test]
+    fn test_ecgfp5_constants() {
+        let generator = generator::<F>();
+        let n = EcGFp5Scalar::modulus();
+        assert!(is_on_curve_native(&generator));
+        assert_eq!(mul_native(&n, &generator), None);
+        assert_eq!(
+            mul_native(&(&n - 1u32), &generator),
+            Some(neg_native(&generator))
+        );
+
+        let offset = offset_point::<F>();
+        let digest = Sha256::digest(OFFSET_SEED);
+        let mut x = [F::ZERO; 5];
+        for (coefficient, word) in x.iter_mut().zip(digest.chunks(8)) {
+            *coefficient = F::from_noncanonical_u64(u64::from_le_bytes(word.try_into().unwrap()));
+        }
+        assert_eq!(offset.x, x);
+        assert!(is_on_curve_native(&offset));
+    }
+
+    #[test]
+    fn test_ecgfp5_add_double() {
+        let mut builder = DefaultBuilder::new();
+        let a = builder.read::<EcGFp5PointVariable>();
+        let b = builder.read::<EcGFp5PointVariable>();
+        let sum = builder.ecgfp5_add(&a, &b);
+        let double = builder.ecgfp5_double(&a);
+        let is_on_curve = builder.ecgfp5_is_on_curve(&sum);
+        builder.write(sum);
+        builder.write(double);
+        builder.write(is_on_curve);
+        let circuit = builder.mock_build();
+
+        let a = generator::<F>();
+        let b = mul_native(&BigUint::from(5u32), &a).unwrap();
+        let mut input = circuit.input();
+        input.write::<EcGFp5PointVariable>(a);
+        input.write::<EcGFp5PointVariable>(b);
+        let (_witness, mut output) = circuit.mock_prove(&input);
+        assert_eq!(
+            output.read::<EcGFp5PointVariable>(),
+            mul_native(&BigUint::from(6u32), &a).unwrap()
+        );
+        assert_eq!(
+            output.read::<EcGFp5PointVariable>(),
+            double_native(&a).unwrap()
+        );
+        assert!(output.read::<BoolVariable>());
+    }
+}
@@ -0,0 +1,239 @@
+use num::BigUint;
+use num_bigint::RandBigInt;
+use plonky2::field::goldilocks_field::GoldilocksField;
+use plonky2::field::types::PrimeField64;
+use plonky2::hash::hash_types::RichField;
+use plonky2::hash::poseidon::PoseidonHash;
+use plonky2::plonk::config::Hasher;
+use plonky2x_derive::CircuitVariable;
+use rand::Rng;
+use sha2::{Digest, Sha512};
+
+use super::curve::{
+    double_native, generator, mul_native, offset_point, EcGFp5Point, EcGFp5PointVariable,
+};
+use super::field::EcGFp5Scalar;
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::builder::CircuitBuilder;
+use crate::frontend::ecc::nonnative::{add_mod, mul_mod, NonNativeField, NonNativeFieldVariable};
+use crate::frontend::vars::{BoolVariable, CircuitVariable, Variable};
+
+/// The number of bits of the challenge, which is the integer of the four elements of a Poseidon
+/// hash.
+const CHALLENGE_BITS: usize = 256;
+
+/// An EdDSA signature over EcGFp5, with the nonce point `r` and the scalar `s`.
+#[derive(Debug, Clone, CircuitVariable)]
+#[value_name(EcGFp5Signature)]
+#[value_derive(PartialEq, Eq)]
+pub struct EcGFp5SignatureVariable {
+    pub r: EcGFp5PointVariable,
+    pub s: NonNativeFieldVariable<EcGFp5Scalar>,
+}
+
+/// Returns the challenge of a signature, which is the Poseidon hash of the nonce point, the
+/// public key and the message, as the little-endian integer of its elements.
+fn challenge_native<F: RichField>(
+    r: &EcGFp5Point<F>,
+    pubkey: &EcGFp5Point<F>,
+    msg: &[F],
+) -> BigUint {
+    let elements = [&r.x[..], &r.y, &pubkey.x, &pubkey.y, msg].concat();
+    let hash = PoseidonHash::hash_no_pad(&elements);
+    hash.elements
+        .iter()
+        .rev()
+        .fold(BigUint::from(0u32), |acc, element| {
+            (acc << 64) + element.to_canonical_u64()
+        })
+}
+
+/// Returns a secret key drawn from `rng` and its public key.
+pub fn ecgfp5_eddsa_keygen<R: Rng + ?Sized>(
+    rng: &mut R,
+) -> (BigUint, EcGFp5Point<GoldilocksField>) {
+    let secret = rng.gen_biguint_range(&BigUint::from(1u32), &EcGFp5Scalar::modulus());
+    let pubkey = ecgfp5_eddsa_public_key(&secret);
+    (secret, pubkey)
+}
+
+/// Returns the public key `secret * G` of a secret key, which must be a nonzero scalar.
+pub fn ecgfp5_eddsa_public_key(secret: &BigUint) -> EcGFp5Point<GoldilocksField> {
+    mul_native(secret, &generator()).expect("the secret key is zero")
+}
+
+/// Signs `msg` with `secret`.
+///
+/// The nonce is derived deterministically from the secret key and the message, as the SHA-512
+/// digest of their little-endian encodings reduced modulo the order of the curve.
+pub fn ecgfp5_eddsa_sign(
+    secret: &BigUint,
+    msg: &[GoldilocksField],
+) -> EcGFp5Signature<GoldilocksField> {
+    let n = EcGFp5Scalar::modulus();
+    let mut hasher = Sha512::new();
+    hasher.update(secret.to_bytes_le());
+    for element in msg {
+        hasher.update(element.to_canonical_u64().to_le_bytes());
+    }
+    let nonce = BigUint::from_bytes_le(&hasher.finalize()) % &n;
+
+    let r = mul_native(&nonce, &generator()).expect("the nonce is zero");
+    let pubkey = ecgfp5_eddsa_public_key(secret);
+    let challenge = challenge_native(&r, &pubkey, msg);
+    let s = add_mod(&nonce, &mul_mod(&challenge, secret, &n), &n);
+    EcGFp5Signature { r, s }
+}
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Returns whether `signature` is a valid EdDSA signature of `msg` by `pubkey` over EcGFp5,
+    /// with the Poseidon hash of the nonce point, the public key and the message as the
+    /// challenge.
+    ///
+    /// The verification is cofactored: the signature is valid if `2 * s * G == 2 * (r + e * A)`,
+    /// and the points must be on the curve. As the coordinates are native, the circuit must be
+    /// over the Goldilocks field.
+    ///
+    /// The multiplications start from a point of unknown discrete logarithm, so that no sum is at
+    /// infinity or a doubling unless the inputs are chosen to make the circuit unsatisfiable,
+    /// which includes a public key of `±G`.
+    pub fn ecgfp5_eddsa_verify(
+        &mut self,
+        msg: &[Variable],
+        signature: &EcGFp5SignatureVariable,
+        pubkey: &EcGFp5PointVariable,
+    ) -> BoolVariable {
+        let r_on_curve = self.ecgfp5_is_on_curve(&signature.r);
+        let pubkey_on_curve = self.ecgfp5_is_on_curve(pubkey);
+
+        let targets = [signature.r.variables(), pubkey.variables(), msg.to_vec()]
+            .concat()
+            .into_iter()
+            .map(|variable| variable.0)
+            .collect::<Vec<_>>();
+        let hash = self.api.hash_n_to_hash_no_pad::<PoseidonHash>(targets);
+        let e_bits = hash
+            .elements
+            .iter()
+            .flat_map(|element| self.ecgfp5_canonical_bits(Variable(*element)))
+            .collect::<Vec<_>>();
+        let s_bits = signature
+            .s
+            .limbs
+            .iter()
+            .flat_map(|limb| self.api.split_le(limb.variable.0, 32))
+            .map(BoolVariable::from)
+            .collect::<Vec<_>>();
+
+        // Computes `s * G - e * A` with the table `[G, G, -A, G - A]` of the pairs of bits, where
+        // the first entry is never added.
+        let g = self.constant::<EcGFp5PointVariable>(generator());
+        let neg_pubkey = self.ecgfp5_neg(pubkey);
+        let g_minus_pubkey = self.ecgfp5_add(&g, &neg_pubkey);
+        let false_v = self._false();
+        let offset = offset_point::<L::Field>();
+        let mut acc = self.constant::<EcGFp5PointVariable>(offset);
+        for i in (0..s_bits.len()).rev() {
+            acc = self.ecgfp5_double(&acc);
+            let s_bit = s_bits[i];
+            let e_bit = if i < CHALLENGE_BITS {
+                e_bits[i]
+            } else {
+                false_v
+            };
+            let with_pubkey = self.select(s_bit, g_minus_pubkey, neg_pubkey);
+            let term = self.select(e_bit, with_pubkey, g);
+            let sum = self.ecgfp5_add(&acc, &term);
+            let is_added = self.or(s_bit, e_bit);
+            acc = self.select(is_added, sum, acc);
+        }
+
+        // The accumulator is `2^k * O + s * G - e * A` for `k` bits, which is compared to
+        // `2^k * O + r` after a doubling to clear the cofactor.
+        let mut correction = offset;
+        for _ in 0..=s_bits.len() {
+            correction = double_native(&correction).expect("the offset has a small order");
+        }
+        let correction = self.constant::<EcGFp5PointVariable>(correction);
+        let double_r = self.ecgfp5_double(&signature.r);
+        let expected = self.ecgfp5_add(&double_r, &correction);
+        let double_acc = self.ecgfp5_double(&acc);
+        let is_valid = self.is_equal(double_acc, expected);
+        let is_valid = self.and(is_valid, r_on_curve);
+        self.and(is_valid, pubkey_on_curve)
+    }
+
+    /// Returns the 64 little-endian bits of the canonical representative of `element`.
+    fn ecgfp5_canonical_bits(&mut self, element: Variable) -> Vec<BoolVariable> {
+        let bits = self
+            .api
+            .split_le(element.0, 64)
+            .into_iter()
+            .map(BoolVariable::from)
+            .collect::<Vec<_>>();
+
+        // The bits are at least `p = 2^64 - 2^32 + 1` if and only if the high half is all ones
+        // and the low half is not zero.
+        let true_v = self._true();
+        let false_v = self._false();
+        let mut high_is_max = true_v;
+        for bit in bits[32..].iter() {
+            high_is_max = self.and(high_is_max, *bit);
+        }
+        let mut low_is_zero = true_v;
+        for bit in bits[..32].iter() {
+            let not_bit = self.not(*bit);
+            low_is_zero = self.and(low_is_zero, not_bit);
+        }
+        let low_is_nonzero = self.not(low_is_zero);
+        let is_overflow = self.and(high_is_max, low_is_nonzero);
+        self.assert_is_equal(is_overflow, false_v);
+        bits
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use plonky2::field::types::Field;
+
+    use super::*;
+    use crate::prelude::DefaultBuilder;
+
+    type F = GoldilocksField;
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_ecgfp5_eddsa_verify() {
+        let mut builder = DefaultBuilder::new();
+        let msg = (0..3)
+            .map(|_| builder.read::<Variable>())
+            .collect::<Vec<_>>();
+        let signature = builder.read::<EcGFp5SignatureVariable>();
+        let pubkey = builder.read::<EcGFp5PointVariable>();
+        let is_valid = builder.ecgfp5_eddsa_verify(&msg, &signature, &pubkey);
+        builder.write(is_valid);
+        let circuit = builder.mock_build();
+
+        let mut rng = rand::thread_rng();
+        let (secret, pubkey) = ecgfp5_eddsa_keygen(&mut rng);
+        let (_, other_pubkey) = ecgfp5_eddsa_keygen(&mut rng);
+        let msg = [1u64, 2, 3].map(F::from_canonical_u64);
+        let signature = ecgfp5_eddsa_sign(&secret, &msg);
+        let other_msg = [1u64, 2, 4].map(F::from_canonical_u64);
+
+        for (msg, pubkey, expected) in [
+            (msg, pubkey, true),
+            (other_msg, pubkey, false),
+            (msg, other_pubkey, false),
+        ] {
+            let mut input = circuit.input();
+            for element in msg {
+                input.write::<Variable>(element);
+            }
+            input.write::<EcGFp5SignatureVariable>(signature.clone());
+            input.write::<EcGFp5PointVariable>(pubkey);
+            let (_witness, mut output) = circuit.mock_prove(&input);
+            assert_eq!(output.read::<BoolVariable>(), expected);
+        }
+    }
+}
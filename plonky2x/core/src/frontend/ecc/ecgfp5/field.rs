@@ -0,0 +1,279 @@
+use num::BigUint;
+use plonky2::field::types::Field;
+use plonky2::hash::hash_types::RichField;
+use serde::{Deserialize, Serialize};
+
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::builder::CircuitBuilder;
+use crate::frontend::ecc::nonnative::NonNativeField;
+use crate::frontend::hint::simple::hint::Hint;
+use crate::frontend::vars::{CircuitVariable, ValueStream, Variable, VariableStream};
+
+/// The degree of the extension of the base field.
+const DEGREE: usize = 5;
+
+/// The constant `W` of the irreducible polynomial `z^5 - W` of the extension.
+const W: u64 = 3;
+
+/// The order of the subgroup of EcGFp5 of prime order.
+const SCALAR_MODULUS: &str = "1067993516717146951041484916571792702745057740581727230159139685185762082554198619328292418486241";
+
+/// The field of the scalars of EcGFp5, whose order is the order of its subgroup of prime order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EcGFp5Scalar;
+
+impl NonNativeField for EcGFp5Scalar {
+    fn modulus() -> BigUint {
+        BigUint::parse_bytes(SCALAR_MODULUS.as_bytes(), 10).unwrap()
+    }
+}
+
+/// An element of the extension `GF(p^5) = GF(p)[z] / (z^5 - 3)` of the Goldilocks field, as its
+/// coefficients in `1, z, ..., z^4`.
+///
+/// The arithmetic is native, so that it is only meaningful for circuits over the Goldilocks
+/// field.
+#[derive(Debug, Clone, Copy)]
+pub struct GFp5Variable(pub [Variable; DEGREE]);
+
+impl CircuitVariable for GFp5Variable {
+    type ValueType<F: RichField> = [F; DEGREE];
+
+    fn init_unsafe<L: PlonkParameters<D>, const D: usize>(
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> Self {
+        Self([(); DEGREE].map(|_| Variable::init_unsafe(builder)))
+    }
+
+    fn variables(&self) -> Vec<Variable> {
+        self.0.to_vec()
+    }
+
+    fn from_variables_unsafe(variables: &[Variable]) -> Self {
+        Self(variables.try_into().unwrap())
+    }
+
+    fn assert_is_valid<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        _builder: &mut CircuitBuilder<L, D>,
+    ) {
+    }
+
+    fn nb_elements() -> usize {
+        DEGREE
+    }
+
+    fn elements<F: RichField>(value: Self::ValueType<F>) -> Vec<F> {
+        value.to_vec()
+    }
+
+    fn from_elements<F: RichField>(elements: &[F]) -> Self::ValueType<F> {
+        elements.try_into().unwrap()
+    }
+}
+
+/// Returns `a + b` in `GF(p^5)`.
+pub(crate) fn gfp5_add_native<F: Field>(a: &[F; DEGREE], b: &[F; DEGREE]) -> [F; DEGREE] {
+    core::array::from_fn(|i| a[i] + b[i])
+}
+
+/// Returns `a - b` in `GF(p^5)`.
+pub(crate) fn gfp5_sub_native<F: Field>(a: &[F; DEGREE], b: &[F; DEGREE]) -> [F; DEGREE] {
+    core::array::from_fn(|i| a[i] - b[i])
+}
+
+/// Returns `a * b` in `GF(p^5)`.
+pub(crate) fn gfp5_mul_native<F: Field>(a: &[F; DEGREE], b: &[F; DEGREE]) -> [F; DEGREE] {
+    let w = F::from_canonical_u64(W);
+    let mut product = [F::ZERO; DEGREE];
+    for i in 0..DEGREE {
+        for j in 0..DEGREE {
+            if i + j < DEGREE {
+                product[i + j] += a[i] * b[j];
+            } else {
+                product[i + j - DEGREE] += w * a[i] * b[j];
+            }
+        }
+    }
+    product
+}
+
+/// Returns `a / b` in `GF(p^5)`, or `None` if `b` is zero.
+///
+/// The quotient is the solution of the linear system of the multiplication by `b`, which is
+/// solved by Gaussian elimination.
+pub(crate) fn gfp5_div_native<F: Field>(a: &[F; DEGREE], b: &[F; DEGREE]) -> Option<[F; DEGREE]> {
+    // The coefficient `k` of `b * q` is `sum(rows[k][j] * q[j])`, with the right-hand side `a[k]`
+    // as the last column.
+    let w = F::from_canonical_u64(W);
+    let mut rows = (0..DEGREE)
+        .map(|k| {
+            let mut row = (0..DEGREE)
+                .map(|j| {
+                    if j <= k {
+                        b[k - j]
+                    } else {
+                        w * b[k + DEGREE - j]
+                    }
+                })
+                .collect::<Vec<_>>();
+            row.push(a[k]);
+            row
+        })
+        .collect::<Vec<_>>();
+
+    for column in 0..DEGREE {
+        let pivot = (column..DEGREE).find(|&k| rows[k][column].is_nonzero())?;
+        rows.swap(column, pivot);
+        let inverse = rows[column][column].inverse();
+        for entry in rows[column].iter_mut() {
+            *entry *= inverse;
+        }
+        for k in 0..DEGREE {
+            let factor = rows[k][column];
+            if k != column && factor.is_nonzero() {
+                for j in column..=DEGREE {
+                    let entry = rows[column][j];
+                    rows[k][j] -= factor * entry;
+                }
+            }
+        }
+    }
+    Some(core::array::from_fn(|k| rows[k][DEGREE]))
+}
+
+/// Computes the quotient of two elements of `GF(p^5)`, or zero if the divisor is zero.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GFp5DivHint;
+
+impl<L: PlonkParameters<D>, const D: usize> Hint<L, D> for GFp5DivHint {
+    fn hint(&self, input_stream: &mut ValueStream<L, D>, output_stream: &mut ValueStream<L, D>) {
+        let a = input_stream.read_value::<GFp5Variable>();
+        let b = input_stream.read_value::<GFp5Variable>();
+        let quotient = gfp5_div_native(&a, &b).unwrap_or([L::Field::ZERO; DEGREE]);
+        output_stream.write_value::<GFp5Variable>(quotient);
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Returns `a + b` in `GF(p^5)`.
+    pub fn gfp5_add(&mut self, a: &GFp5Variable, b: &GFp5Variable) -> GFp5Variable {
+        GFp5Variable(core::array::from_fn(|i| {
+            Variable(self.api.add(a.0[i].0, b.0[i].0))
+        }))
+    }
+
+    /// Returns `a - b` in `GF(p^5)`.
+    pub fn gfp5_sub(&mut self, a: &GFp5Variable, b: &GFp5Variable) -> GFp5Variable {
+        GFp5Variable(core::array::from_fn(|i| {
+            Variable(self.api.sub(a.0[i].0, b.0[i].0))
+        }))
+    }
+
+    /// Returns `-a` in `GF(p^5)`.
+    pub fn gfp5_neg(&mut self, a: &GFp5Variable) -> GFp5Variable {
+        GFp5Variable(core::array::from_fn(|i| Variable(self.api.neg(a.0[i].0))))
+    }
+
+    /// Returns `c * a` in `GF(p^5)` for a constant `c` of the base field.
+    pub fn gfp5_mul_const(&mut self, c: L::Field, a: &GFp5Variable) -> GFp5Variable {
+        GFp5Variable(core::array::from_fn(|i| {
+            Variable(self.api.mul_const(c, a.0[i].0))
+        }))
+    }
+
+    /// Returns `a * b` in `GF(p^5)`, as the product of the polynomials reduced by `z^5 = 3`.
+    pub fn gfp5_mul(&mut self, a: &GFp5Variable, b: &GFp5Variable) -> GFp5Variable {
+        let w = L::Field::from_canonical_u64(W);
+        let zero = self.api.zero();
+        let mut product = [zero; DEGREE];
+        for i in 0..DEGREE {
+            for j in 0..DEGREE {
+                let (k, c) = if i + j < DEGREE {
+                    (i + j, L::Field::ONE)
+                } else {
+                    (i + j - DEGREE, w)
+                };
+                product[k] = self
+                    .api
+                    .arithmetic(c, L::Field::ONE, a.0[i].0, b.0[j].0, product[k]);
+            }
+        }
+        GFp5Variable(product.map(Variable))
+    }
+
+    /// Returns `a / b` in `GF(p^5)`, asserting that `b` is not zero.
+    ///
+    /// The quotient is supplied by a hint and constrained so that `quotient * b == a`.
+    pub fn gfp5_div(&mut self, a: &GFp5Variable, b: &GFp5Variable) -> GFp5Variable {
+        let zero = self.constant::<GFp5Variable>([L::Field::ZERO; DEGREE]);
+        let false_v = self._false();
+        let is_zero = self.is_equal(*b, zero);
+        self.assert_is_equal(is_zero, false_v);
+
+        let mut input_stream = VariableStream::new();
+        input_stream.write(a);
+        input_stream.write(b);
+        let output_stream = self.hint(input_stream, GFp5DivHint);
+        let quotient = output_stream.read::<GFp5Variable>(self);
+
+        let product = self.gfp5_mul(&quotient, b);
+        self.assert_is_equal(product, *a);
+        quotient
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use plonky2::field::goldilocks_field::GoldilocksField;
+
+    use super::*;
+    use crate::prelude::DefaultBuilder;
+
+    type F = GoldilocksField;
+
+    #[test]
+    fn test_gfp5_arithmetic() {
+        let mut builder = DefaultBuilder::new();
+        let a = builder.read::<GFp5Variable>();
+        let b = builder.read::<GFp5Variable>();
+        let sum = builder.gfp5_add(&a, &b);
+        let difference = builder.gfp5_sub(&a, &b);
+        let product = builder.gfp5_mul(&a, &b);
+        let quotient = builder.gfp5_div(&a, &b);
+        builder.write(sum);
+        builder.write(difference);
+        builder.write(product);
+        builder.write(quotient);
+        let circuit = builder.mock_build();
+
+        let a = [1u64, 2, 3, 4, 5].map(F::from_canonical_u64);
+        let b = [
+            F::NEG_ONE,
+            F::ZERO,
+            F::TWO,
+            F::ZERO,
+            F::from_canonical_u64(1 << 40),
+        ];
+        let product = gfp5_mul_native(&a, &b);
+        let quotient = gfp5_div_native(&a, &b).unwrap();
+        assert_eq!(gfp5_mul_native(&quotient, &b), a);
+        assert_eq!(gfp5_div_native(&a, &[F::ZERO; DEGREE]), None);
+
+        // The powers of `z` wrap around to multiples of `W`.
+        let z = [F::ZERO, F::ONE, F::ZERO, F::ZERO, F::ZERO];
+        let z4 = [F::ZERO, F::ZERO, F::ZERO, F::ZERO, F::ONE];
+        let mut expected = [F::ZERO; DEGREE];
+        expected[0] = F::from_canonical_u64(W);
+        assert_eq!(gfp5_mul_native(&z, &z4), expected);
+
+        let mut input = circuit.input();
+        input.write::<GFp5Variable>(a);
+        input.write::<GFp5Variable>(b);
+        let (_witness, mut output) = circuit.mock_prove(&input);
+        assert_eq!(output.read::<GFp5Variable>(), gfp5_add_native(&a, &b));
+        assert_eq!(output.read::<GFp5Variable>(), gfp5_sub_native(&a, &b));
+        assert_eq!(output.read::<GFp5Variable>(), product);
+        assert_eq!(output.read::<GFp5Variable>(), quotient);
+    }
+}
@@ -0,0 +1,3 @@
+pub mod curve;
+pub mod eddsa;
+pub mod field;
@@ -1 +1,9 @@
+pub mod bls12_381;
 pub mod curve25519;
+pub mod ecdsa;
+pub mod ecgfp5;
+pub mod msm;
+pub mod nonnative;
+pub mod p256;
+pub mod secp256k1;
+pub mod weierstrass;
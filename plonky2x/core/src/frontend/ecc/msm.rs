@@ -0,0 +1,280 @@
+use num::BigUint;
+
+use super::nonnative::{NonNativeField, NonNativeFieldVariable};
+use super::weierstrass::{
+    double_native, mul_native, neg_native, offset_point, WeierstrassCurve, WeierstrassPoint,
+    WeierstrassPointVariable,
+};
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::builder::CircuitBuilder;
+use crate::frontend::uint::num::biguint::CircuitBuilderBiguint;
+use crate::frontend::vars::BoolVariable;
+
+/// The number of bits of the windows of the scalars.
+const WINDOW_BITS: usize = 4;
+
+/// The digit of a window whose signed digit is zero, which is the bias of the digits.
+const WINDOW_BIAS: usize = 1 << (WINDOW_BITS - 1);
+
+/// Returns the number of windows of a scalar of the field `P`, with one more window than the
+/// number of bits requires for the carry of the bias.
+fn nb_windows<P: NonNativeField>() -> usize {
+    32 * P::nb_limbs() / WINDOW_BITS + 1
+}
+
+/// Returns the bias added to a scalar of the field `P` to make its windows signed, which is
+/// `WINDOW_BIAS` in every window.
+fn scalar_bias<P: NonNativeField>() -> BigUint {
+    (0..nb_windows::<P>()).fold(BigUint::from(0u32), |bias, _| {
+        (bias << WINDOW_BITS) + WINDOW_BIAS
+    })
+}
+
+/// Returns the table of `(digit - WINDOW_BIAS) * point` for every digit of a window, where the
+/// entry of the zero digit is `point`, as it is never added.
+fn fixed_base_table<C: WeierstrassCurve>(point: &(BigUint, BigUint)) -> Vec<(BigUint, BigUint)> {
+    let multiples = (1..=WINDOW_BIAS)
+        .map(|k| mul_native::<C>(&BigUint::from(k), point).expect("the point has a small order"))
+        .collect::<Vec<_>>();
+    (0..2 * WINDOW_BIAS)
+        .map(|digit| match digit {
+            d if d > WINDOW_BIAS => multiples[d - WINDOW_BIAS - 1].clone(),
+            d if d < WINDOW_BIAS => neg_native::<C>(&multiples[WINDOW_BIAS - d - 1]),
+            _ => point.clone(),
+        })
+        .collect()
+}
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Returns `sum(scalars[i] * points[i])`.
+    ///
+    /// The scalars are recoded in signed windows of `WINDOW_BITS` bits, whose doublings are shared
+    /// by all the points. The table of each point is computed in the circuit, with half as many
+    /// additions as for unsigned windows as the negative multiples are negations.
+    ///
+    /// The multiplications start from a point of unknown discrete logarithm, so that no sum is at
+    /// infinity or a doubling unless the inputs are chosen to make the circuit unsatisfiable,
+    /// which includes a result at infinity. The points must not have a small order.
+    pub fn weierstrass_msm<C: WeierstrassCurve>(
+        &mut self,
+        scalars: &[NonNativeFieldVariable<C::ScalarField>],
+        points: &[WeierstrassPointVariable<C>],
+    ) -> WeierstrassPointVariable<C> {
+        let tables = points
+            .iter()
+            .map(|point| self.weierstrass_variable_base_table(point))
+            .collect::<Vec<_>>();
+        self.weierstrass_windowed_msm(scalars, &tables)
+    }
+
+    /// Returns `sum(scalars[i] * points[i])` for points known when building the circuit, such as
+    /// the generator.
+    ///
+    /// The tables of the points are constants, so that the multiplications only cost the shared
+    /// doublings and one addition per window and point. See `weierstrass_msm` for the conditions
+    /// on the inputs.
+    pub fn weierstrass_fixed_base_msm<C: WeierstrassCurve>(
+        &mut self,
+        scalars: &[NonNativeFieldVariable<C::ScalarField>],
+        points: &[WeierstrassPoint],
+    ) -> WeierstrassPointVariable<C> {
+        let tables = points
+            .iter()
+            .map(|point| self.weierstrass_fixed_base_table((point.x.clone(), point.y.clone())))
+            .collect::<Vec<_>>();
+        self.weierstrass_windowed_msm(scalars, &tables)
+    }
+
+    /// Returns the table of `(digit - WINDOW_BIAS) * point` for every digit of a window, as
+    /// constants.
+    pub(crate) fn weierstrass_fixed_base_table<C: WeierstrassCurve>(
+        &mut self,
+        point: (BigUint, BigUint),
+    ) -> Vec<WeierstrassPointVariable<C>> {
+        fixed_base_table::<C>(&point)
+            .into_iter()
+            .map(|entry| self.constant::<WeierstrassPointVariable<C>>(entry.into()))
+            .collect()
+    }
+
+    /// Returns the table of `(digit - WINDOW_BIAS) * point` for every digit of a window, where the
+    /// entry of the zero digit is `point`, as it is never added.
+    pub(crate) fn weierstrass_variable_base_table<C: WeierstrassCurve>(
+        &mut self,
+        point: &WeierstrassPointVariable<C>,
+    ) -> Vec<WeierstrassPointVariable<C>> {
+        let mut multiples = vec![point.clone(), self.weierstrass_double(point)];
+        for _ in 2..WINDOW_BIAS {
+            let multiple = self.weierstrass_add(&multiples[multiples.len() - 1], point);
+            multiples.push(multiple);
+        }
+        (0..2 * WINDOW_BIAS)
+            .map(|digit| match digit {
+                d if d > WINDOW_BIAS => multiples[d - WINDOW_BIAS - 1].clone(),
+                d if d < WINDOW_BIAS => {
+                    let multiple = &multiples[WINDOW_BIAS - d - 1];
+                    WeierstrassPointVariable {
+                        x: multiple.x.clone(),
+                        y: self.nonnative_neg(&multiple.y),
+                    }
+                }
+                _ => point.clone(),
+            })
+            .collect()
+    }
+
+    /// Returns `sum(scalars[i] * P_i)`, where `tables[i]` is the table of the point `P_i`.
+    pub(crate) fn weierstrass_windowed_msm<C: WeierstrassCurve>(
+        &mut self,
+        scalars: &[NonNativeFieldVariable<C::ScalarField>],
+        tables: &[Vec<WeierstrassPointVariable<C>>],
+    ) -> WeierstrassPointVariable<C> {
+        assert!(!scalars.is_empty(), "the MSM must have at least one term");
+        assert_eq!(scalars.len(), tables.len());
+        let digits = scalars
+            .iter()
+            .map(|scalar| self.weierstrass_signed_windows(scalar))
+            .collect::<Vec<_>>();
+
+        // The offset is doubled `WINDOW_BITS` times per window but the first, so that the
+        // correction is the negation of its multiple by two to that number of doublings.
+        let nb_windows = nb_windows::<C::ScalarField>();
+        let offset = offset_point::<C>();
+        let mut correction = offset.clone();
+        for _ in 0..WINDOW_BITS * (nb_windows - 1) {
+            correction = double_native::<C>(&correction);
+        }
+        let correction = neg_native::<C>(&correction);
+        let correction = self.constant::<WeierstrassPointVariable<C>>(correction.into());
+
+        let mut acc = self.constant::<WeierstrassPointVariable<C>>(offset.into());
+        for window in (0..nb_windows).rev() {
+            if window + 1 < nb_windows {
+                for _ in 0..WINDOW_BITS {
+                    acc = self.weierstrass_double(&acc);
+                }
+            }
+            for (digits, table) in digits.iter().zip(tables) {
+                let bits = &digits[window];
+                let term = self.weierstrass_table_lookup(table, bits);
+                let sum = self.weierstrass_add(&acc, &term);
+
+                // The signed digit is zero when the digit is `WINDOW_BIAS`.
+                let mut is_zero = bits[WINDOW_BITS - 1];
+                for bit in bits[..WINDOW_BITS - 1].iter() {
+                    let not_bit = self.not(*bit);
+                    is_zero = self.and(is_zero, not_bit);
+                }
+                acc = self.select(is_zero, acc, sum);
+            }
+        }
+        self.weierstrass_add(&acc, &correction)
+    }
+
+    /// Returns the little-endian bits of the windows of `scalar + bias`, from the least
+    /// significant window, whose digits minus `WINDOW_BIAS` are the signed windows of `scalar`.
+    fn weierstrass_signed_windows<P: NonNativeField>(
+        &mut self,
+        scalar: &NonNativeFieldVariable<P>,
+    ) -> Vec<Vec<BoolVariable>> {
+        let bias = self.api.constant_biguint(&scalar_bias::<P>());
+        let biased = self.api.add_biguint(&scalar.to_biguint(), &bias);
+        let bits = biased
+            .limbs
+            .iter()
+            .flat_map(|limb| self.api.split_le(limb.target, 32))
+            .map(BoolVariable::from)
+            .collect::<Vec<_>>();
+
+        // As the scalar has one window less than the bias, the sum has no bit above the windows.
+        let nb_bits = WINDOW_BITS * nb_windows::<P>();
+        let false_v = self._false();
+        for bit in bits[nb_bits..].iter() {
+            self.assert_is_equal(*bit, false_v);
+        }
+        bits[..nb_bits]
+            .chunks(WINDOW_BITS)
+            .map(|window| window.to_vec())
+            .collect()
+    }
+
+    /// Returns the entry of `table` at the index of little-endian bits `bits`.
+    fn weierstrass_table_lookup<C: WeierstrassCurve>(
+        &mut self,
+        table: &[WeierstrassPointVariable<C>],
+        bits: &[BoolVariable],
+    ) -> WeierstrassPointVariable<C> {
+        assert_eq!(table.len(), 1 << bits.len());
+        let mut entries = table.to_vec();
+        for bit in bits.iter() {
+            let mut selected = Vec::with_capacity(entries.len() / 2);
+            for pair in entries.chunks(2) {
+                selected.push(self.select(*bit, pair[1].clone(), pair[0].clone()));
+            }
+            entries = selected;
+        }
+        entries.remove(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frontend::ecc::nonnative::NonNativeField;
+    use crate::frontend::ecc::secp256k1::curve::Secp256k1;
+    use crate::frontend::ecc::secp256k1::field::Secp256k1Scalar;
+    use crate::frontend::ecc::weierstrass::add_native;
+    use crate::prelude::DefaultBuilder;
+
+    type Scalar = NonNativeFieldVariable<Secp256k1Scalar>;
+    type Point = WeierstrassPointVariable<Secp256k1>;
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_weierstrass_msm() {
+        let generator = Secp256k1::generator();
+        let points =
+            [3u32, 5, 7].map(|k| mul_native::<Secp256k1>(&BigUint::from(k), &generator).unwrap());
+        let fixed_point = mul_native::<Secp256k1>(&BigUint::from(11u32), &generator).unwrap();
+
+        let mut builder = DefaultBuilder::new();
+        let scalars = (0..3).map(|_| builder.read::<Scalar>()).collect::<Vec<_>>();
+        let variable_points = (0..3).map(|_| builder.read::<Point>()).collect::<Vec<_>>();
+        let result = builder.weierstrass_msm(&scalars, &variable_points);
+        builder.write(result);
+        let fixed_points: [WeierstrassPoint; 2] =
+            [generator.clone().into(), fixed_point.clone().into()];
+        let result = builder.weierstrass_fixed_base_msm::<Secp256k1>(&scalars[..2], &fixed_points);
+        builder.write(result);
+        let circuit = builder.mock_build();
+
+        // The scalars include zero windows, the largest scalar and a scalar of a single window.
+        let n = Secp256k1Scalar::modulus();
+        let scalars = [
+            BigUint::from(0x1000_0008u32),
+            &n - 1u32,
+            BigUint::from(9u32),
+        ];
+        let expected = scalars
+            .iter()
+            .zip(points.iter())
+            .map(|(scalar, point)| mul_native::<Secp256k1>(scalar, point).unwrap())
+            .reduce(|a, b| add_native::<Secp256k1>(&a, &b))
+            .unwrap();
+        let expected_fixed = add_native::<Secp256k1>(
+            &mul_native::<Secp256k1>(&scalars[0], &generator).unwrap(),
+            &mul_native::<Secp256k1>(&scalars[1], &fixed_point).unwrap(),
+        );
+
+        let mut input = circuit.input();
+        for scalar in scalars.iter() {
+            input.write::<Scalar>(scalar.clone());
+        }
+        for point in points {
+            input.write::<Point>(point.into());
+        }
+        let (_witness, mut output) = circuit.mock_prove(&input);
+        assert_eq!(output.read::<Point>(), expected.into());
+        assert_eq!(output.read::<Point>(), expected_fixed.into());
+    }
+}
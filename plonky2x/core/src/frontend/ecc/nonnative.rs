@@ -0,0 +1,375 @@
+use core::fmt::Debug;
+use core::marker::PhantomData;
+
+use itertools::Itertools;
+use num::{BigUint, One, Zero};
+use plonky2::field::types::{Field, PrimeField64};
+use plonky2::hash::hash_types::RichField;
+use serde::{Deserialize, Serialize};
+
+use crate::frontend::hint::simple::hint::Hint;
+use crate::frontend::uint::num::biguint::{BigUintTarget, CircuitBuilderBiguint};
+use crate::frontend::uint::num::u32::gadgets::arithmetic_u32::U32Target;
+use crate::prelude::{
+    BoolVariable, CircuitBuilder, CircuitVariable, PlonkParameters, U256Variable, U32Variable,
+    ValueStream, Variable, VariableStream,
+};
+
+/// A prime field which is not the native field of the circuit.
+///
+/// Elements are represented as `NonNativeFieldVariable`s, whose 32-bit limbs are reduced modulo
+/// the order of the field.
+pub trait NonNativeField: 'static + Debug + Clone + Send + Sync {
+    /// The order of the field.
+    fn modulus() -> BigUint;
+
+    /// The number of 32-bit limbs of an element of the field.
+    fn nb_limbs() -> usize {
+        Self::modulus().to_u32_digits().len()
+    }
+}
+
+/// Returns `a + b mod modulus`.
+pub(crate) fn add_mod(a: &BigUint, b: &BigUint, modulus: &BigUint) -> BigUint {
+    (a + b) % modulus
+}
+
+/// Returns `a - b mod modulus`, for `b` less than `modulus`.
+pub(crate) fn sub_mod(a: &BigUint, b: &BigUint, modulus: &BigUint) -> BigUint {
+    (a + modulus - b) % modulus
+}
+
+/// Returns `a * b mod modulus`.
+pub(crate) fn mul_mod(a: &BigUint, b: &BigUint, modulus: &BigUint) -> BigUint {
+    a * b % modulus
+}
+
+/// Returns the inverse of `a` modulo the prime `modulus`.
+pub(crate) fn inverse_mod(a: &BigUint, modulus: &BigUint) -> BigUint {
+    a.modpow(&(modulus - 2u32), modulus)
+}
+
+/// Returns the limbs of a value of at most `nb_limbs` 32-bit limbs, padded with zeros.
+pub(crate) fn to_padded_limbs(value: &BigUint, nb_limbs: usize) -> Vec<u32> {
+    let mut limbs = value.to_u32_digits();
+    assert!(
+        limbs.len() <= nb_limbs,
+        "value does not fit in {} limbs",
+        nb_limbs
+    );
+    limbs.resize(nb_limbs, 0);
+    limbs
+}
+
+/// An element of the nonnative field `P`, as little-endian 32-bit limbs reduced modulo the order
+/// of the field.
+#[derive(Debug, Clone)]
+pub struct NonNativeFieldVariable<P> {
+    pub limbs: Vec<U32Variable>,
+    _marker: PhantomData<P>,
+}
+
+impl<P: NonNativeField> NonNativeFieldVariable<P> {
+    fn new(limbs: Vec<U32Variable>) -> Self {
+        assert_eq!(limbs.len(), P::nb_limbs());
+        Self {
+            limbs,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the element as an integer.
+    pub(crate) fn to_biguint(&self) -> BigUintTarget {
+        BigUintTarget {
+            limbs: self.limbs.iter().map(|x| U32Target::from(*x)).collect(),
+        }
+    }
+
+    /// Takes the least significant limbs of an integer, which must be reduced with no higher limb
+    /// set.
+    pub(crate) fn from_biguint_low(value: &BigUintTarget) -> Self {
+        Self::new(
+            value.limbs[..P::nb_limbs()]
+                .iter()
+                .map(|x| (*x).into())
+                .collect(),
+        )
+    }
+}
+
+impl<P: NonNativeField> CircuitVariable for NonNativeFieldVariable<P> {
+    type ValueType<F: RichField> = BigUint;
+
+    fn init_unsafe<L: PlonkParameters<D>, const D: usize>(
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> Self {
+        Self::new(
+            (0..P::nb_limbs())
+                .map(|_| U32Variable::init_unsafe(builder))
+                .collect(),
+        )
+    }
+
+    fn variables(&self) -> Vec<Variable> {
+        self.limbs.iter().map(|limb| limb.variable).collect()
+    }
+
+    fn from_variables_unsafe(variables: &[Variable]) -> Self {
+        Self::new(
+            variables
+                .iter()
+                .map(|variable| U32Variable::from_variables_unsafe(&[*variable]))
+                .collect(),
+        )
+    }
+
+    fn assert_is_valid<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        builder: &mut CircuitBuilder<L, D>,
+    ) {
+        for limb in self.limbs.iter() {
+            limb.assert_is_valid(builder);
+        }
+        let true_v = builder._true();
+        let is_reduced = builder.nonnative_is_reduced::<P>(&self.to_biguint());
+        builder.assert_is_equal(is_reduced, true_v);
+    }
+
+    fn nb_elements() -> usize {
+        P::nb_limbs()
+    }
+
+    fn elements<F: RichField>(value: Self::ValueType<F>) -> Vec<F> {
+        to_padded_limbs(&value, P::nb_limbs())
+            .into_iter()
+            .map(F::from_canonical_u32)
+            .collect()
+    }
+
+    fn from_elements<F: RichField>(elements: &[F]) -> Self::ValueType<F> {
+        let limbs = elements
+            .iter()
+            .map(|element| element.to_canonical_u64() as u32)
+            .collect_vec();
+        BigUint::from_slice(&limbs)
+    }
+}
+
+/// Computes the quotient of two elements of a nonnative field, or zero if the divisor is zero.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NonNativeDivHint {
+    /// The limbs of the order of the field.
+    modulus: Vec<u32>,
+}
+
+impl<L: PlonkParameters<D>, const D: usize> Hint<L, D> for NonNativeDivHint {
+    fn hint(&self, input_stream: &mut ValueStream<L, D>, output_stream: &mut ValueStream<L, D>) {
+        let nb_limbs = self.modulus.len();
+        let modulus = BigUint::from_slice(&self.modulus);
+        let a = BigUint::from_slice(&input_stream.read_vec::<U32Variable>(nb_limbs));
+        let b = BigUint::from_slice(&input_stream.read_vec::<U32Variable>(nb_limbs));
+        let quotient = if b.is_zero() {
+            BigUint::zero()
+        } else {
+            mul_mod(&a, &inverse_mod(&b, &modulus), &modulus)
+        };
+        for limb in to_padded_limbs(&quotient, nb_limbs) {
+            output_stream.write_value::<U32Variable>(limb);
+        }
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Returns `a + b` in the field `P`.
+    pub fn nonnative_add<P: NonNativeField>(
+        &mut self,
+        a: &NonNativeFieldVariable<P>,
+        b: &NonNativeFieldVariable<P>,
+    ) -> NonNativeFieldVariable<P> {
+        let sum = self.api.add_biguint(&a.to_biguint(), &b.to_biguint());
+        self.nonnative_reduce::<P>(&sum)
+    }
+
+    /// Returns `a - b` in the field `P`.
+    pub fn nonnative_sub<P: NonNativeField>(
+        &mut self,
+        a: &NonNativeFieldVariable<P>,
+        b: &NonNativeFieldVariable<P>,
+    ) -> NonNativeFieldVariable<P> {
+        let modulus = self.api.constant_biguint(&P::modulus());
+        let neg_b = self.api.sub_biguint(&modulus, &b.to_biguint());
+        let difference = self.api.add_biguint(&a.to_biguint(), &neg_b);
+        self.nonnative_reduce::<P>(&difference)
+    }
+
+    /// Returns `-a` in the field `P`.
+    pub fn nonnative_neg<P: NonNativeField>(
+        &mut self,
+        a: &NonNativeFieldVariable<P>,
+    ) -> NonNativeFieldVariable<P> {
+        let modulus = self.api.constant_biguint(&P::modulus());
+        let neg_a = self.api.sub_biguint(&modulus, &a.to_biguint());
+        self.nonnative_reduce::<P>(&neg_a)
+    }
+
+    /// Returns `a * b` in the field `P`.
+    pub fn nonnative_mul<P: NonNativeField>(
+        &mut self,
+        a: &NonNativeFieldVariable<P>,
+        b: &NonNativeFieldVariable<P>,
+    ) -> NonNativeFieldVariable<P> {
+        let product = self.api.mul_biguint(&a.to_biguint(), &b.to_biguint());
+        self.nonnative_reduce::<P>(&product)
+    }
+
+    /// Returns `a / b` in the field `P`, asserting that `b` is not zero.
+    ///
+    /// The quotient is supplied by a hint and constrained so that `quotient * b == a`.
+    pub fn nonnative_div<P: NonNativeField>(
+        &mut self,
+        a: &NonNativeFieldVariable<P>,
+        b: &NonNativeFieldVariable<P>,
+    ) -> NonNativeFieldVariable<P> {
+        let zero = self.constant::<NonNativeFieldVariable<P>>(BigUint::zero());
+        let false_v = self._false();
+        let is_zero = self.is_equal(b.clone(), zero);
+        self.assert_is_equal(is_zero, false_v);
+
+        let mut input_stream = VariableStream::new();
+        input_stream.write(a);
+        input_stream.write(b);
+        let hint = NonNativeDivHint {
+            modulus: P::modulus().to_u32_digits(),
+        };
+        let output_stream = self.hint(input_stream, hint);
+        let quotient = output_stream.read::<NonNativeFieldVariable<P>>(self);
+
+        let product = self.nonnative_mul(&quotient, b);
+        self.assert_is_equal(product, a.clone());
+        quotient
+    }
+
+    /// Returns the inverse of `a` in the field `P`, asserting that `a` is not zero.
+    pub fn nonnative_inverse<P: NonNativeField>(
+        &mut self,
+        a: &NonNativeFieldVariable<P>,
+    ) -> NonNativeFieldVariable<P> {
+        let one = self.constant::<NonNativeFieldVariable<P>>(BigUint::one());
+        self.nonnative_div(&one, a)
+    }
+
+    /// Returns whether the integer representative of `a` is odd.
+    pub fn nonnative_is_odd<P: NonNativeField>(
+        &mut self,
+        a: &NonNativeFieldVariable<P>,
+    ) -> BoolVariable {
+        let bits = self.api.split_le(a.limbs[0].variable.0, 32);
+        BoolVariable::from(bits[0])
+    }
+
+    /// Reduces an integer modulo the order of the field `P`.
+    pub fn nonnative_from_u256<P: NonNativeField>(
+        &mut self,
+        a: U256Variable,
+    ) -> NonNativeFieldVariable<P> {
+        self.nonnative_reduce::<P>(&a.to_biguint())
+    }
+
+    /// Returns the integer representative of `a`, for a field `P` of at most 256 bits.
+    pub fn nonnative_to_u256<P: NonNativeField>(
+        &mut self,
+        a: &NonNativeFieldVariable<P>,
+    ) -> U256Variable {
+        let zero = self.constant::<U32Variable>(0);
+        let mut limbs = a.limbs.clone();
+        assert!(limbs.len() <= 8, "the field does not fit in 256 bits");
+        limbs.resize(8, zero);
+        U256Variable::from_biguint_low(&BigUintTarget {
+            limbs: limbs.into_iter().map(U32Target::from).collect(),
+        })
+    }
+
+    /// Returns whether the integer `a` is less than the order of the field `P`.
+    pub fn nonnative_u256_is_reduced<P: NonNativeField>(
+        &mut self,
+        a: U256Variable,
+    ) -> BoolVariable {
+        self.nonnative_is_reduced::<P>(&a.to_biguint())
+    }
+
+    /// Returns whether an integer of any number of limbs is less than the order of the field `P`.
+    pub(crate) fn nonnative_is_reduced<P: NonNativeField>(
+        &mut self,
+        a: &BigUintTarget,
+    ) -> BoolVariable {
+        let max = self.api.constant_biguint(&(P::modulus() - 1u32));
+        BoolVariable::from(self.api.cmp_biguint(a, &max))
+    }
+
+    /// Reduces an integer of any number of limbs modulo the order of the field `P`.
+    pub(crate) fn nonnative_reduce<P: NonNativeField>(
+        &mut self,
+        a: &BigUintTarget,
+    ) -> NonNativeFieldVariable<P> {
+        let modulus = self.api.constant_biguint(&P::modulus());
+        let remainder = self.api.rem_biguint(a, &modulus);
+        NonNativeFieldVariable::from_biguint_low(&remainder)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frontend::ecc::bls12_381::field::Bls12381Base;
+    use crate::frontend::ecc::secp256k1::field::Secp256k1Base;
+    use crate::prelude::DefaultBuilder;
+
+    fn test_arithmetic<P: NonNativeField>(a: BigUint, b: BigUint) {
+        type V<P> = NonNativeFieldVariable<P>;
+
+        let mut builder = DefaultBuilder::new();
+        let a_var = builder.read::<V<P>>();
+        let b_var = builder.read::<V<P>>();
+        let sum = builder.nonnative_add(&a_var, &b_var);
+        let difference = builder.nonnative_sub(&b_var, &a_var);
+        let product = builder.nonnative_mul(&a_var, &b_var);
+        let quotient = builder.nonnative_div(&a_var, &b_var);
+        let inverse = builder.nonnative_inverse(&b_var);
+        builder.write(sum);
+        builder.write(difference);
+        builder.write(product);
+        builder.write(quotient);
+        builder.write(inverse);
+        let circuit = builder.mock_build();
+
+        let modulus = P::modulus();
+        let mut input = circuit.input();
+        input.write::<V<P>>(a.clone());
+        input.write::<V<P>>(b.clone());
+        let (_witness, mut output) = circuit.mock_prove(&input);
+
+        assert_eq!(output.read::<V<P>>(), add_mod(&a, &b, &modulus));
+        assert_eq!(output.read::<V<P>>(), sub_mod(&b, &a, &modulus));
+        assert_eq!(output.read::<V<P>>(), mul_mod(&a, &b, &modulus));
+        let quotient = output.read::<V<P>>();
+        assert_eq!(mul_mod(&quotient, &b, &modulus), a);
+        let inverse = output.read::<V<P>>();
+        assert_eq!(mul_mod(&inverse, &b, &modulus), BigUint::one());
+    }
+
+    #[test]
+    fn test_nonnative_arithmetic() {
+        let modulus = Secp256k1Base::modulus();
+        let a = &modulus - 12345u32;
+        let b = BigUint::from(67890u32) << 200;
+        test_arithmetic::<Secp256k1Base>(a, b);
+    }
+
+    #[test]
+    fn test_nonnative_arithmetic_381_bits() {
+        let modulus = Bls12381Base::modulus();
+        let a = &modulus - 12345u32;
+        let b = BigUint::from(67890u32) << 300;
+        test_arithmetic::<Bls12381Base>(a, b);
+    }
+}
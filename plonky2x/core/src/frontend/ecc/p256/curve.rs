@@ -0,0 +1,51 @@
+use num::BigUint;
+
+use super::field::{P256Base, P256Scalar};
+use crate::frontend::ecc::nonnative::NonNativeField;
+use crate::frontend::ecc::weierstrass::{
+    WeierstrassCurve, WeierstrassPoint, WeierstrassPointVariable,
+};
+
+/// A point of P-256 in affine coordinates, other than the point at infinity.
+pub type P256PointVariable = WeierstrassPointVariable<P256>;
+
+/// The value of a `P256PointVariable`.
+pub type P256Point = WeierstrassPoint;
+
+/// The curve P-256, also known as secp256r1, `y^2 = x^3 - 3 * x + b`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct P256;
+
+impl WeierstrassCurve for P256 {
+    type BaseField = P256Base;
+    type ScalarField = P256Scalar;
+
+    const OFFSET_SEED: &'static [u8] = b"plonky2x p256 offset";
+
+    fn a() -> BigUint {
+        P256Base::modulus() - 3u32
+    }
+
+    fn b() -> BigUint {
+        BigUint::parse_bytes(
+            b"5ac635d8aa3a93e7b3ebbd55769886bc651d06b0cc53b0f63bce3c3e27d2604b",
+            16,
+        )
+        .unwrap()
+    }
+
+    fn generator() -> (BigUint, BigUint) {
+        (
+            BigUint::parse_bytes(
+                b"6b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c296",
+                16,
+            )
+            .unwrap(),
+            BigUint::parse_bytes(
+                b"4fe342e2fe1a7f9b8ee7eb4a7c0f9e162bce33576b315ececbb6406837bf51f5",
+                16,
+            )
+            .unwrap(),
+        )
+    }
+}
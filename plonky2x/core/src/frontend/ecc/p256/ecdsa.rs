@@ -0,0 +1,77 @@
+use super::curve::{P256PointVariable, P256};
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::builder::CircuitBuilder;
+use crate::frontend::ecc::ecdsa::ECDSASignatureVariable;
+use crate::frontend::vars::{BoolVariable, Bytes32Variable};
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Returns whether `signature` is a valid ECDSA signature of `msg_hash` by `pubkey` over
+    /// P-256, as used by WebAuthn. See `weierstrass_ecdsa_verify` for the checks on the signature.
+    pub fn p256_ecdsa_verify(
+        &mut self,
+        msg_hash: Bytes32Variable,
+        signature: &ECDSASignatureVariable,
+        pubkey: &P256PointVariable,
+    ) -> BoolVariable {
+        self.weierstrass_ecdsa_verify::<P256>(msg_hash, signature, pubkey)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ethers::types::{H256, U256};
+    use ethers::utils::keccak256;
+    use num::BigUint;
+
+    use super::*;
+    use crate::frontend::ecc::ecdsa::ECDSASignature;
+    use crate::frontend::ecc::nonnative::{add_mod, inverse_mod, mul_mod, NonNativeField};
+    use crate::frontend::ecc::p256::field::{P256Base, P256Scalar};
+    use crate::frontend::ecc::weierstrass::{mul_native, WeierstrassCurve};
+    use crate::prelude::DefaultBuilder;
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_p256_ecdsa_verify() {
+        let mut builder = DefaultBuilder::new();
+        let msg_hash = builder.read::<Bytes32Variable>();
+        let signature = builder.read::<ECDSASignatureVariable>();
+        let pubkey = builder.read::<P256PointVariable>();
+        let is_valid = builder.p256_ecdsa_verify(msg_hash, &signature, &pubkey);
+        builder.write(is_valid);
+        let circuit = builder.mock_build();
+
+        // The order of the generator is `n`, so that `(n - 1) * G = -G`.
+        let n = P256Scalar::modulus();
+        let generator = P256::generator();
+        let minus_generator = mul_native::<P256>(&(&n - 1u32), &generator).unwrap();
+        assert_eq!(minus_generator.0, generator.0);
+        assert_eq!(minus_generator.1, P256Base::modulus() - &generator.1);
+
+        let secret_key = BigUint::from_bytes_be(&keccak256(b"secret key")) % &n;
+        let nonce = BigUint::from_bytes_be(&keccak256(b"nonce")) % &n;
+        let msg_hash = H256(keccak256(b"message"));
+        let pubkey = mul_native::<P256>(&secret_key, &generator).unwrap();
+        let r = mul_native::<P256>(&nonce, &generator).unwrap().0 % &n;
+        let z = BigUint::from_bytes_be(msg_hash.as_bytes()) % &n;
+        let s = mul_mod(
+            &inverse_mod(&nonce, &n),
+            &add_mod(&z, &mul_mod(&r, &secret_key, &n), &n),
+            &n,
+        );
+        let r = U256::from_big_endian(&r.to_bytes_be());
+        let s = U256::from_big_endian(&s.to_bytes_be());
+
+        for (signature, expected) in [
+            (ECDSASignature { r, s }, true),
+            (ECDSASignature { r, s: s + 1 }, false),
+        ] {
+            let mut input = circuit.input();
+            input.write::<Bytes32Variable>(msg_hash);
+            input.write::<ECDSASignatureVariable>(signature);
+            input.write::<P256PointVariable>(pubkey.into());
+            let (_witness, mut output) = circuit.mock_prove(&input);
+            assert_eq!(output.read::<BoolVariable>(), expected);
+        }
+    }
+}
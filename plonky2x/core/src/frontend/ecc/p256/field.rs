@@ -0,0 +1,31 @@
+use num::BigUint;
+
+use crate::frontend::ecc::nonnative::NonNativeField;
+
+/// The base field of P-256, over which the coordinates of its points are defined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct P256Base;
+
+impl NonNativeField for P256Base {
+    fn modulus() -> BigUint {
+        BigUint::parse_bytes(
+            b"ffffffff00000001000000000000000000000000ffffffffffffffffffffffff",
+            16,
+        )
+        .unwrap()
+    }
+}
+
+/// The scalar field of P-256, whose order is the order of its generator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct P256Scalar;
+
+impl NonNativeField for P256Scalar {
+    fn modulus() -> BigUint {
+        BigUint::parse_bytes(
+            b"ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551",
+            16,
+        )
+        .unwrap()
+    }
+}
@@ -0,0 +1,3 @@
+pub mod curve;
+pub mod ecdsa;
+pub mod field;
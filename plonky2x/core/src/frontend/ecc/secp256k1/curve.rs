@@ -0,0 +1,118 @@
+use num::{BigUint, Zero};
+
+use super::field::{Secp256k1Base, Secp256k1Scalar};
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::builder::CircuitBuilder;
+use crate::frontend::ecc::weierstrass::{
+    WeierstrassCurve, WeierstrassPoint, WeierstrassPointVariable,
+};
+use crate::frontend::eth::vars::Secp256k1PubkeyVariable;
+use crate::frontend::vars::{Bytes32Variable, BytesVariable};
+
+/// A point of secp256k1 in affine coordinates, other than the point at infinity.
+pub type Secp256k1PointVariable = WeierstrassPointVariable<Secp256k1>;
+
+/// The value of a `Secp256k1PointVariable`.
+pub type Secp256k1Point = WeierstrassPoint;
+
+/// The curve secp256k1, `y^2 = x^3 + 7`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Secp256k1;
+
+impl WeierstrassCurve for Secp256k1 {
+    type BaseField = Secp256k1Base;
+    type ScalarField = Secp256k1Scalar;
+
+    const OFFSET_SEED: &'static [u8] = b"plonky2x secp256k1 offset";
+
+    fn a() -> BigUint {
+        BigUint::zero()
+    }
+
+    fn b() -> BigUint {
+        BigUint::from(7u32)
+    }
+
+    fn generator() -> (BigUint, BigUint) {
+        (
+            BigUint::parse_bytes(
+                b"79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+                16,
+            )
+            .unwrap(),
+            BigUint::parse_bytes(
+                b"483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8",
+                16,
+            )
+            .unwrap(),
+        )
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Returns the point of an uncompressed public key, asserting that its coordinates are
+    /// reduced. The point is not checked to be on the curve.
+    pub fn secp256k1_point_from_pubkey(
+        &mut self,
+        pubkey: &Secp256k1PubkeyVariable,
+    ) -> Secp256k1PointVariable {
+        let true_v = self._true();
+        let x = self.bytes32_to_u256(Bytes32Variable::from(&pubkey.0 .0[..32]));
+        let y = self.bytes32_to_u256(Bytes32Variable::from(&pubkey.0 .0[32..]));
+        let x_is_reduced = self.nonnative_u256_is_reduced::<Secp256k1Base>(x);
+        let y_is_reduced = self.nonnative_u256_is_reduced::<Secp256k1Base>(y);
+        let is_reduced = self.and(x_is_reduced, y_is_reduced);
+        self.assert_is_equal(is_reduced, true_v);
+        Secp256k1PointVariable {
+            x: self.nonnative_from_u256(x),
+            y: self.nonnative_from_u256(y),
+        }
+    }
+
+    /// Returns the uncompressed public key of a point.
+    pub fn secp256k1_point_to_pubkey(
+        &mut self,
+        point: &Secp256k1PointVariable,
+    ) -> Secp256k1PubkeyVariable {
+        let x = self.nonnative_to_u256(&point.x);
+        let y = self.nonnative_to_u256(&point.y);
+        let x = self.u256_to_bytes32(x);
+        let y = self.u256_to_bytes32(y);
+        let bytes = [x.as_bytes(), y.as_bytes()].concat();
+        Secp256k1PubkeyVariable(BytesVariable(bytes.try_into().unwrap()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ethers::core::k256::ecdsa::SigningKey;
+
+    use super::*;
+    use crate::frontend::ecc::weierstrass::mul_native;
+    use crate::prelude::DefaultBuilder;
+
+    #[test]
+    fn test_secp256k1_pubkey_conversion() {
+        let mut builder = DefaultBuilder::new();
+        let pubkey = builder.read::<Secp256k1PubkeyVariable>();
+        let point = builder.secp256k1_point_from_pubkey(&pubkey);
+        builder.weierstrass_assert_on_curve(&point);
+        let pubkey = builder.secp256k1_point_to_pubkey(&point);
+        builder.write(point);
+        builder.write(pubkey);
+        let circuit = builder.mock_build();
+
+        let secret_key = [0x42; 32];
+        let signing_key = SigningKey::from_slice(&secret_key).unwrap();
+        let encoded = signing_key.verifying_key().to_encoded_point(false);
+        let pubkey: [u8; 64] = encoded.as_bytes()[1..].try_into().unwrap();
+        let scalar = BigUint::from_bytes_be(&secret_key);
+        let point = mul_native::<Secp256k1>(&scalar, &Secp256k1::generator()).unwrap();
+
+        let mut input = circuit.input();
+        input.write::<Secp256k1PubkeyVariable>(pubkey);
+        let (_witness, mut output) = circuit.mock_prove(&input);
+        assert_eq!(output.read::<Secp256k1PointVariable>(), point.into());
+        assert_eq!(output.read::<Secp256k1PubkeyVariable>(), pubkey);
+    }
+}
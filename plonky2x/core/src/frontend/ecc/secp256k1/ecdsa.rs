@@ -0,0 +1,166 @@
+use ethers::types::{H160, U256};
+use num::{BigUint, One, Zero};
+
+use super::curve::{Secp256k1, Secp256k1PointVariable};
+use super::field::{Secp256k1Base, Secp256k1Scalar};
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::builder::CircuitBuilder;
+use crate::frontend::ecc::ecdsa::ECDSASignatureVariable;
+use crate::frontend::ecc::nonnative::NonNativeFieldVariable;
+use crate::frontend::eth::vars::AddressVariable;
+use crate::frontend::vars::{BoolVariable, Bytes32Variable, U256Variable};
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Returns whether `signature` is a valid ECDSA signature of `msg_hash` by `pubkey` over
+    /// secp256k1. See `weierstrass_ecdsa_verify` for the checks on the signature.
+    pub fn ecdsa_verify(
+        &mut self,
+        msg_hash: Bytes32Variable,
+        signature: &ECDSASignatureVariable,
+        pubkey: &Secp256k1PointVariable,
+    ) -> BoolVariable {
+        self.weierstrass_ecdsa_verify::<Secp256k1>(msg_hash, signature, pubkey)
+    }
+
+    /// Recovers the address of the signer of `msg_hash` from the signature `(v, r, s)`, with the
+    /// semantics of the `ecrecover` precompile of the EVM.
+    ///
+    /// The zero address is returned if `v` is neither 27 nor 28, if `r` or `s` is zero or not
+    /// reduced modulo the order of the curve, or if `r` is not the x coordinate of a point. As for
+    /// the precompile, signatures with a high `s` are accepted. The circuit is unsatisfiable for
+    /// the degenerate signatures whose public key is the point at infinity.
+    pub fn ecrecover(
+        &mut self,
+        msg_hash: Bytes32Variable,
+        v: U256Variable,
+        r: U256Variable,
+        s: U256Variable,
+    ) -> AddressVariable {
+        let one = self.one::<U256Variable>();
+        let v_even = self.constant::<U256Variable>(U256::from(27));
+        let v_odd = self.constant::<U256Variable>(U256::from(28));
+        let is_even = self.is_equal(v, v_even);
+        let is_odd = self.is_equal(v, v_odd);
+        let v_valid = self.or(is_even, is_odd);
+        let r_valid = self.ecdsa_is_valid_scalar::<Secp256k1>(r);
+        let s_valid = self.ecdsa_is_valid_scalar::<Secp256k1>(s);
+        let r = self.select(r_valid, r, one);
+        let s = self.select(s_valid, s, one);
+
+        // The point `R` of the signature has x coordinate `r`, which is reduced as `r < n < p`,
+        // and a y coordinate of the parity given by `v`.
+        let nonce_x = self.nonnative_from_u256::<Secp256k1Base>(r);
+        let (r_on_curve, nonce_point) = self.weierstrass_lift_x::<Secp256k1>(&nonce_x, is_odd);
+        let is_valid = self.and(v_valid, r_valid);
+        let is_valid = self.and(is_valid, s_valid);
+        let is_valid = self.and(is_valid, r_on_curve);
+
+        // The public key is `r^-1 * (s * R - z * G)`. Invalid signatures recover `R` instead, so
+        // that the circuit stays satisfiable.
+        let zero = self.constant::<NonNativeFieldVariable<Secp256k1Scalar>>(BigUint::zero());
+        let one = self.constant::<NonNativeFieldVariable<Secp256k1Scalar>>(BigUint::one());
+        let r = self.nonnative_from_u256::<Secp256k1Scalar>(r);
+        let s = self.nonnative_from_u256::<Secp256k1Scalar>(s);
+        let z = self.bytes32_to_u256(msg_hash);
+        let z = self.nonnative_from_u256::<Secp256k1Scalar>(z);
+        let neg_z = self.nonnative_neg(&z);
+        let r_inverse = self.nonnative_inverse(&r);
+        let u1 = self.nonnative_mul(&neg_z, &r_inverse);
+        let u2 = self.nonnative_mul(&s, &r_inverse);
+        let u1 = self.select(is_valid, u1, zero);
+        let u2 = self.select(is_valid, u2, one);
+        let pubkey = self.weierstrass_double_scalar_mul(&u1, &u2, &nonce_point);
+
+        let pubkey = self.secp256k1_point_to_pubkey(&pubkey);
+        let address = self.pubkey_to_address(&pubkey);
+        let zero_address = self.constant::<AddressVariable>(H160::zero());
+        self.select(is_valid, address, zero_address)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ethers::signers::{LocalWallet, Signer};
+    use ethers::types::{Signature, H256};
+
+    use super::*;
+    use crate::frontend::ecc::ecdsa::ECDSASignature;
+    use crate::frontend::ecc::nonnative::NonNativeField;
+    use crate::frontend::ecc::secp256k1::curve::Secp256k1Point;
+    use crate::prelude::DefaultBuilder;
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_ecdsa_verify() {
+        let mut builder = DefaultBuilder::new();
+        let msg_hash = builder.read::<Bytes32Variable>();
+        let signature = builder.read::<ECDSASignatureVariable>();
+        let pubkey = builder.read::<Secp256k1PointVariable>();
+        let is_valid = builder.ecdsa_verify(msg_hash, &signature, &pubkey);
+        builder.write(is_valid);
+        let circuit = builder.mock_build();
+
+        let wallet = LocalWallet::from_bytes(&[0x42; 32]).unwrap();
+        let point = wallet.signer().verifying_key().to_encoded_point(false);
+        let pubkey = Secp256k1Point {
+            x: BigUint::from_bytes_be(point.x().unwrap()),
+            y: BigUint::from_bytes_be(point.y().unwrap()),
+        };
+        let msg_hash = H256::repeat_byte(0x17);
+        let signature = wallet.sign_hash(msg_hash).unwrap();
+        let signature = ECDSASignature {
+            r: signature.r,
+            s: signature.s,
+        };
+
+        for (hash, expected) in [(msg_hash, true), (H256::repeat_byte(0x18), false)] {
+            let mut input = circuit.input();
+            input.write::<Bytes32Variable>(hash);
+            input.write::<ECDSASignatureVariable>(signature.clone());
+            input.write::<Secp256k1PointVariable>(pubkey.clone());
+            let (_witness, mut output) = circuit.mock_prove(&input);
+            assert_eq!(output.read::<BoolVariable>(), expected);
+        }
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_ecrecover() {
+        let mut builder = DefaultBuilder::new();
+        let msg_hash = builder.read::<Bytes32Variable>();
+        let v = builder.read::<U256Variable>();
+        let r = builder.read::<U256Variable>();
+        let s = builder.read::<U256Variable>();
+        let address = builder.ecrecover(msg_hash, v, r, s);
+        builder.write(address);
+        let circuit = builder.mock_build();
+
+        let wallet = LocalWallet::from_bytes(&[0x42; 32]).unwrap();
+        let msg_hash = H256::repeat_byte(0x17);
+        let signature = wallet.sign_hash(msg_hash).unwrap();
+        let other_hash = H256::repeat_byte(0x18);
+        let other_signer = signature.recover(other_hash).unwrap();
+
+        // The signature with `n - s` and the other parity of `v` recovers the same address.
+        let malleated = Signature {
+            r: signature.r,
+            s: U256::from_big_endian(&Secp256k1Scalar::modulus().to_bytes_be()) - signature.s,
+            v: 55 - signature.v,
+        };
+        let invalid_v = Signature { v: 29, ..signature };
+        for (hash, signature, expected) in [
+            (msg_hash, signature, wallet.address()),
+            (msg_hash, malleated, wallet.address()),
+            (other_hash, signature, other_signer),
+            (msg_hash, invalid_v, H160::zero()),
+        ] {
+            let mut input = circuit.input();
+            input.write::<Bytes32Variable>(hash);
+            input.write::<U256Variable>(U256::from(signature.v));
+            input.write::<U256Variable>(signature.r);
+            input.write::<U256Variable>(signature.s);
+            let (_witness, mut output) = circuit.mock_prove(&input);
+            assert_eq!(output.read::<AddressVariable>(), expected);
+        }
+    }
+}
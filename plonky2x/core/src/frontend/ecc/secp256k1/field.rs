@@ -0,0 +1,31 @@
+use num::BigUint;
+
+use crate::frontend::ecc::nonnative::NonNativeField;
+
+/// The base field of secp256k1, over which the coordinates of its points are defined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Secp256k1Base;
+
+impl NonNativeField for Secp256k1Base {
+    fn modulus() -> BigUint {
+        BigUint::parse_bytes(
+            b"fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f",
+            16,
+        )
+        .unwrap()
+    }
+}
+
+/// The scalar field of secp256k1, whose order is the order of its generator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Secp256k1Scalar;
+
+impl NonNativeField for Secp256k1Scalar {
+    fn modulus() -> BigUint {
+        BigUint::parse_bytes(
+            b"fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141",
+            16,
+        )
+        .unwrap()
+    }
+}
@@ -0,0 +1,4 @@
+pub mod curve;
+pub mod ecdsa;
+pub mod field;
+pub mod schnorr;
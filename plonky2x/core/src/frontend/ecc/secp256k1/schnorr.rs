@@ -0,0 +1,159 @@
+use ethers::types::H256;
+use sha2::{Digest, Sha256};
+
+use super::curve::Secp256k1;
+use super::field::{Secp256k1Base, Secp256k1Scalar};
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::builder::CircuitBuilder;
+use crate::frontend::vars::{BoolVariable, Bytes32Variable, BytesVariable, U256Variable};
+
+/// The tag of the hash of the challenge of BIP-340 signatures.
+const CHALLENGE_TAG: &[u8] = b"BIP0340/challenge";
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Returns whether `signature` is a valid BIP-340 Schnorr signature of the 32-byte message
+    /// `msg` by the x-only public key `pubkey`.
+    ///
+    /// The signature is `r || s`, with `r` the x coordinate of the nonce point, which has an even
+    /// y coordinate, and `s` a scalar. The public key is the x coordinate of the point with an
+    /// even y coordinate. The circuit is unsatisfiable for the degenerate signatures whose nonce
+    /// point would be at infinity.
+    pub fn bip340_verify(
+        &mut self,
+        msg: Bytes32Variable,
+        pubkey: Bytes32Variable,
+        signature: BytesVariable<64>,
+    ) -> BoolVariable {
+        let zero = self.zero::<U256Variable>();
+        let false_v = self._false();
+
+        // An x coordinate of the public key which is not reduced is replaced by zero, and a scalar
+        // which is not reduced by zero, so that the rest of the circuit is satisfiable.
+        let pubkey_x = self.bytes32_to_u256(pubkey);
+        let pubkey_reduced = self.nonnative_u256_is_reduced::<Secp256k1Base>(pubkey_x);
+        let pubkey_x = self.select(pubkey_reduced, pubkey_x, zero);
+        let pubkey_x = self.nonnative_from_u256::<Secp256k1Base>(pubkey_x);
+        let (pubkey_on_curve, pubkey_point) =
+            self.weierstrass_lift_x::<Secp256k1>(&pubkey_x, false_v);
+        let r = Bytes32Variable::from(&signature.0[..32]);
+        let s = self.bytes32_to_u256(Bytes32Variable::from(&signature.0[32..]));
+        let s_reduced = self.nonnative_u256_is_reduced::<Secp256k1Scalar>(s);
+        let s = self.select(s_reduced, s, zero);
+        let s = self.nonnative_from_u256::<Secp256k1Scalar>(s);
+
+        // The challenge is `e = hash_BIP0340/challenge(r || pubkey || msg) mod n`, with the tagged
+        // hash `sha256(sha256(tag) || sha256(tag) || data)`.
+        let tag_hash =
+            self.constant::<Bytes32Variable>(H256::from_slice(&Sha256::digest(CHALLENGE_TAG)));
+        let mut preimage = tag_hash.as_bytes().to_vec();
+        preimage.extend(tag_hash.as_bytes());
+        preimage.extend(r.as_bytes());
+        preimage.extend(pubkey.as_bytes());
+        preimage.extend(msg.as_bytes());
+        let e = self.sha256(&preimage);
+        let e = self.bytes32_to_u256(e);
+        let e = self.nonnative_from_u256::<Secp256k1Scalar>(e);
+        let neg_e = self.nonnative_neg(&e);
+
+        // The nonce point is `s * G - e * P`, whose x coordinate must be `r` with an even y.
+        let nonce_point = self.weierstrass_double_scalar_mul(&s, &neg_e, &pubkey_point);
+        let nonce_y_is_odd = self.nonnative_is_odd(&nonce_point.y);
+        let nonce_y_is_even = self.not(nonce_y_is_odd);
+        let r = self.bytes32_to_u256(r);
+        let nonce_x = self.nonnative_to_u256(&nonce_point.x);
+        let x_matches = self.is_equal(nonce_x, r);
+
+        let is_valid = self.and(pubkey_reduced, pubkey_on_curve);
+        let is_valid = self.and(is_valid, s_reduced);
+        let is_valid = self.and(is_valid, nonce_y_is_even);
+        self.and(is_valid, x_matches)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ethers::utils::keccak256;
+    use num::BigUint;
+
+    use super::*;
+    use crate::frontend::ecc::nonnative::{add_mod, mul_mod, NonNativeField};
+    use crate::frontend::ecc::weierstrass::{mul_native, WeierstrassCurve};
+    use crate::prelude::DefaultBuilder;
+
+    fn tagged_hash(tag: &[u8], data: &[u8]) -> [u8; 32] {
+        let tag_hash = Sha256::digest(tag);
+        Sha256::new()
+            .chain_update(tag_hash)
+            .chain_update(tag_hash)
+            .chain_update(data)
+            .finalize()
+            .into()
+    }
+
+    fn to_bytes(value: &BigUint) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        let be_bytes = value.to_bytes_be();
+        bytes[32 - be_bytes.len()..].copy_from_slice(&be_bytes);
+        bytes
+    }
+
+    /// Signs `msg` as in BIP-340, with a nonce derived from the secret key and the message.
+    fn sign(secret_key: &BigUint, msg: [u8; 32]) -> ([u8; 32], [u8; 64]) {
+        let n = Secp256k1Scalar::modulus();
+        let generator = Secp256k1::generator();
+        let pubkey = mul_native::<Secp256k1>(secret_key, &generator).unwrap();
+        let secret_key = if pubkey.1.bit(0) {
+            &n - secret_key
+        } else {
+            secret_key.clone()
+        };
+        let nonce_seed = [to_bytes(&secret_key).as_slice(), &msg].concat();
+        let nonce = BigUint::from_bytes_be(&keccak256(nonce_seed)) % &n;
+        let nonce_point = mul_native::<Secp256k1>(&nonce, &generator).unwrap();
+        let nonce = if nonce_point.1.bit(0) {
+            &n - nonce
+        } else {
+            nonce
+        };
+
+        let pubkey = to_bytes(&pubkey.0);
+        let r = to_bytes(&nonce_point.0);
+        let challenge = tagged_hash(CHALLENGE_TAG, &[r, pubkey, msg].concat());
+        let e = BigUint::from_bytes_be(&challenge) % &n;
+        let s = add_mod(&nonce, &mul_mod(&e, &secret_key, &n), &n);
+        (pubkey, [r, to_bytes(&s)].concat().try_into().unwrap())
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_bip340_verify() {
+        let mut builder = DefaultBuilder::new();
+        let msg = builder.read::<Bytes32Variable>();
+        let pubkey = builder.read::<Bytes32Variable>();
+        let signature = builder.read::<BytesVariable<64>>();
+        let is_valid = builder.bip340_verify(msg, pubkey, signature);
+        builder.write(is_valid);
+        let circuit = builder.mock_build();
+
+        let msg = keccak256(b"taproot");
+        let n = Secp256k1Scalar::modulus();
+        let secret_key = BigUint::from_bytes_be(&keccak256(b"secret key")) % n;
+        let (pubkey, signature) = sign(&secret_key, msg);
+        let mut tampered = signature;
+        tampered[63] ^= 1;
+        let p = Secp256k1Base::modulus();
+
+        for (pubkey, signature, expected) in [
+            (pubkey, signature, true),
+            (pubkey, tampered, false),
+            (to_bytes(&p), signature, false),
+        ] {
+            let mut input = circuit.input();
+            input.write::<Bytes32Variable>(H256(msg));
+            input.write::<Bytes32Variable>(H256(pubkey));
+            input.write::<BytesVariable<64>>(signature);
+            let (_witness, mut output) = circuit.mock_prove(&input);
+            assert_eq!(output.read::<BoolVariable>(), expected);
+        }
+    }
+}
@@ -0,0 +1,402 @@
+use std::fmt::Debug;
+
+use ethers::utils::keccak256;
+use num::{BigUint, Zero};
+use plonky2::hash::hash_types::RichField;
+use serde::{Deserialize, Serialize};
+
+use super::nonnative::{
+    add_mod, inverse_mod, mul_mod, sub_mod, to_padded_limbs, NonNativeField, NonNativeFieldVariable,
+};
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::builder::CircuitBuilder;
+use crate::frontend::hint::simple::hint::Hint;
+use crate::frontend::vars::{
+    BoolVariable, CircuitVariable, U32Variable, ValueStream, Variable, VariableStream,
+};
+
+/// A curve `y^2 = x^3 + a * x + b` over a nonnative field, with no point of order two.
+///
+/// The order of the base field must be `3 mod 4`, so that square roots are computed by
+/// exponentiation.
+pub trait WeierstrassCurve: 'static + Debug + Clone + Send + Sync {
+    /// The field the coordinates of the points are defined over.
+    type BaseField: NonNativeField;
+
+    /// The field of the scalars, whose order is the order of the generator.
+    type ScalarField: NonNativeField;
+
+    /// The seed of the point that scalar multiplications start from.
+    const OFFSET_SEED: &'static [u8];
+
+    /// The coefficient `a` of the equation of the curve.
+    fn a() -> BigUint;
+
+    /// The coefficient `b` of the equation of the curve.
+    fn b() -> BigUint;
+
+    /// The generator of the curve.
+    fn generator() -> (BigUint, BigUint);
+}
+
+/// A point of the Weierstrass curve `C` in affine coordinates, other than the point at infinity.
+#[derive(Debug, Clone)]
+pub struct WeierstrassPointVariable<C: WeierstrassCurve> {
+    pub x: NonNativeFieldVariable<C::BaseField>,
+    pub y: NonNativeFieldVariable<C::BaseField>,
+}
+
+/// The value of a `WeierstrassPointVariable`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WeierstrassPoint {
+    pub x: BigUint,
+    pub y: BigUint,
+}
+
+impl From<(BigUint, BigUint)> for WeierstrassPoint {
+    fn from((x, y): (BigUint, BigUint)) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<C: WeierstrassCurve> CircuitVariable for WeierstrassPointVariable<C> {
+    type ValueType<F: RichField> = WeierstrassPoint;
+
+    fn init_unsafe<L: PlonkParameters<D>, const D: usize>(
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> Self {
+        Self {
+            x: NonNativeFieldVariable::init_unsafe(builder),
+            y: NonNativeFieldVariable::init_unsafe(builder),
+        }
+    }
+
+    fn variables(&self) -> Vec<Variable> {
+        [self.x.variables(), self.y.variables()].concat()
+    }
+
+    fn from_variables_unsafe(variables: &[Variable]) -> Self {
+        let (x, y) = variables.split_at(NonNativeFieldVariable::<C::BaseField>::nb_elements());
+        Self {
+            x: NonNativeFieldVariable::from_variables_unsafe(x),
+            y: NonNativeFieldVariable::from_variables_unsafe(y),
+        }
+    }
+
+    fn assert_is_valid<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        builder: &mut CircuitBuilder<L, D>,
+    ) {
+        self.x.assert_is_valid(builder);
+        self.y.assert_is_valid(builder);
+    }
+
+    fn nb_elements() -> usize {
+        2 * NonNativeFieldVariable::<C::BaseField>::nb_elements()
+    }
+
+    fn elements<F: RichField>(value: Self::ValueType<F>) -> Vec<F> {
+        [
+            NonNativeFieldVariable::<C::BaseField>::elements::<F>(value.x),
+            NonNativeFieldVariable::<C::BaseField>::elements::<F>(value.y),
+        ]
+        .concat()
+    }
+
+    fn from_elements<F: RichField>(elements: &[F]) -> Self::ValueType<F> {
+        let (x, y) = elements.split_at(NonNativeFieldVariable::<C::BaseField>::nb_elements());
+        WeierstrassPoint {
+            x: NonNativeFieldVariable::<C::BaseField>::from_elements::<F>(x),
+            y: NonNativeFieldVariable::<C::BaseField>::from_elements::<F>(y),
+        }
+    }
+}
+
+/// Returns the sum of two points with distinct x coordinates.
+pub(crate) fn add_native<C: WeierstrassCurve>(
+    a: &(BigUint, BigUint),
+    b: &(BigUint, BigUint),
+) -> (BigUint, BigUint) {
+    let p = C::BaseField::modulus();
+    let lambda = mul_mod(
+        &sub_mod(&b.1, &a.1, &p),
+        &inverse_mod(&sub_mod(&b.0, &a.0, &p), &p),
+        &p,
+    );
+    chord::<C>(&lambda, a, &b.0)
+}
+
+/// Returns the double of a point.
+pub(crate) fn double_native<C: WeierstrassCurve>(a: &(BigUint, BigUint)) -> (BigUint, BigUint) {
+    let p = C::BaseField::modulus();
+    let numerator = add_mod(&(&a.0 * &a.0 * 3u32), &C::a(), &p);
+    let lambda = mul_mod(&numerator, &inverse_mod(&(&a.1 * 2u32 % &p), &p), &p);
+    chord::<C>(&lambda, a, &a.0)
+}
+
+/// Returns the negation of a point.
+pub(crate) fn neg_native<C: WeierstrassCurve>(a: &(BigUint, BigUint)) -> (BigUint, BigUint) {
+    let p = C::BaseField::modulus();
+    (a.0.clone(), sub_mod(&BigUint::zero(), &a.1, &p))
+}
+
+/// Returns `scalar * point`, or `None` if it is the point at infinity.
+pub(crate) fn mul_native<C: WeierstrassCurve>(
+    scalar: &BigUint,
+    point: &(BigUint, BigUint),
+) -> Option<(BigUint, BigUint)> {
+    let mut acc: Option<(BigUint, BigUint)> = None;
+    for i in (0..scalar.bits()).rev() {
+        acc = acc.as_ref().map(double_native::<C>);
+        if scalar.bit(i) {
+            acc = match acc {
+                None => Some(point.clone()),
+                Some(acc) if acc.0 != point.0 => Some(add_native::<C>(&acc, point)),
+                Some(acc) if acc.1 == point.1 => Some(double_native::<C>(&acc)),
+                Some(_) => None,
+            };
+        }
+    }
+    acc
+}
+
+/// Returns the point scalar multiplications start from, which is the first point whose x
+/// coordinate is the keccak256 digest of the offset seed of the curve plus a counter.
+pub(crate) fn offset_point<C: WeierstrassCurve>() -> (BigUint, BigUint) {
+    let p = C::BaseField::modulus();
+    let mut x = BigUint::from_bytes_be(&keccak256(C::OFFSET_SEED)) % &p;
+    loop {
+        if let Some(y) = sqrt_native(&curve_rhs(&x, &C::a(), &C::b(), &p), &p) {
+            return (x, y);
+        }
+        x += 1u32;
+    }
+}
+
+/// Returns the third point on the line of slope `lambda` through `a` and a point of x coordinate
+/// `other_x`, reflected over the x axis.
+fn chord<C: WeierstrassCurve>(
+    lambda: &BigUint,
+    a: &(BigUint, BigUint),
+    other_x: &BigUint,
+) -> (BigUint, BigUint) {
+    let p = C::BaseField::modulus();
+    let x = sub_mod(
+        &sub_mod(&mul_mod(lambda, lambda, &p), &a.0, &p),
+        other_x,
+        &p,
+    );
+    let y = sub_mod(&mul_mod(lambda, &sub_mod(&a.0, &x, &p), &p), &a.1, &p);
+    (x, y)
+}
+
+/// Returns `x^3 + a * x + b`, which is the square of the y coordinate of a point of x coordinate
+/// `x`.
+fn curve_rhs(x: &BigUint, a: &BigUint, b: &BigUint, p: &BigUint) -> BigUint {
+    ((x * x + a) * x + b) % p
+}
+
+/// Returns a square root of `a` modulo the prime `p = 3 mod 4`, if there is one.
+fn sqrt_native(a: &BigUint, p: &BigUint) -> Option<BigUint> {
+    // As `p = 3 mod 4`, a square root of `a` is `a^((p + 1) / 4)` if there is one.
+    let root = a.modpow(&((p + 1u32) / 4u32), p);
+    (mul_mod(&root, &root, p) == *a).then_some(root)
+}
+
+/// Computes a square root of `x^3 + a * x + b`, which is the y coordinate of a point of x
+/// coordinate `x`, or a square root of its negation if there is no such point.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WeierstrassLiftXHint {
+    /// The limbs of the order of the base field.
+    modulus: Vec<u32>,
+    /// The limbs of the coefficient `a` of the curve.
+    a: Vec<u32>,
+    /// The limbs of the coefficient `b` of the curve.
+    b: Vec<u32>,
+}
+
+impl<L: PlonkParameters<D>, const D: usize> Hint<L, D> for WeierstrassLiftXHint {
+    fn hint(&self, input_stream: &mut ValueStream<L, D>, output_stream: &mut ValueStream<L, D>) {
+        let nb_limbs = self.modulus.len();
+        let p = BigUint::from_slice(&self.modulus);
+        let x = BigUint::from_slice(&input_stream.read_vec::<U32Variable>(nb_limbs));
+        let rhs = curve_rhs(
+            &x,
+            &BigUint::from_slice(&self.a),
+            &BigUint::from_slice(&self.b),
+            &p,
+        );
+        let (is_on_curve, root) = match sqrt_native(&rhs, &p) {
+            Some(root) => (true, root),
+            None => {
+                let neg_rhs = sub_mod(&BigUint::zero(), &rhs, &p);
+                let root = sqrt_native(&neg_rhs, &p)
+                    .expect("either x^3 + a * x + b or its negation is a square");
+                (false, root)
+            }
+        };
+        output_stream.write_value::<BoolVariable>(is_on_curve);
+        for limb in to_padded_limbs(&root, nb_limbs) {
+            output_stream.write_value::<U32Variable>(limb);
+        }
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Returns whether there is a point of x coordinate `x`, and the point whose y coordinate is
+    /// odd if `odd_y` is true, or the generator if there is no such point.
+    pub fn weierstrass_lift_x<C: WeierstrassCurve>(
+        &mut self,
+        x: &NonNativeFieldVariable<C::BaseField>,
+        odd_y: BoolVariable,
+    ) -> (BoolVariable, WeierstrassPointVariable<C>) {
+        let mut input_stream = VariableStream::new();
+        input_stream.write(x);
+        let hint = WeierstrassLiftXHint {
+            modulus: C::BaseField::modulus().to_u32_digits(),
+            a: C::a().to_u32_digits(),
+            b: C::b().to_u32_digits(),
+        };
+        let output_stream = self.hint(input_stream, hint);
+        let is_on_curve = output_stream.read::<BoolVariable>(self);
+        let root = output_stream.read::<NonNativeFieldVariable<C::BaseField>>(self);
+
+        // As `-1` is not a square modulo `p`, exactly one of `x^3 + a * x + b` and its negation,
+        // which are never zero on a curve without points of order two, is a square.
+        let rhs = self.weierstrass_curve_rhs::<C>(x);
+        let neg_rhs = self.nonnative_neg(&rhs);
+        let square = self.select(is_on_curve, rhs, neg_rhs);
+        let root_squared = self.nonnative_mul(&root, &root);
+        self.assert_is_equal(root_squared, square);
+
+        // The two square roots are nonzero and have distinct parities, as `p` is odd.
+        let root_is_odd = self.nonnative_is_odd(&root);
+        let neg_root = self.nonnative_neg(&root);
+        let has_parity = self.is_equal(root_is_odd, odd_y);
+        let y = self.select(has_parity, root, neg_root);
+
+        let generator = self.constant::<WeierstrassPointVariable<C>>(C::generator().into());
+        let point = WeierstrassPointVariable { x: x.clone(), y };
+        let point = self.select(is_on_curve, point, generator);
+        (is_on_curve, point)
+    }
+
+    /// Asserts that `point` is on the curve.
+    pub fn weierstrass_assert_on_curve<C: WeierstrassCurve>(
+        &mut self,
+        point: &WeierstrassPointVariable<C>,
+    ) {
+        let y_squared = self.nonnative_mul(&point.y, &point.y);
+        let rhs = self.weierstrass_curve_rhs::<C>(&point.x);
+        self.assert_is_equal(y_squared, rhs);
+    }
+
+    /// Returns `a + b`, asserting that the points have distinct x coordinates, which excludes
+    /// doubling and sums at infinity.
+    pub fn weierstrass_add<C: WeierstrassCurve>(
+        &mut self,
+        a: &WeierstrassPointVariable<C>,
+        b: &WeierstrassPointVariable<C>,
+    ) -> WeierstrassPointVariable<C> {
+        let dy = self.nonnative_sub(&b.y, &a.y);
+        let dx = self.nonnative_sub(&b.x, &a.x);
+        let lambda = self.nonnative_div(&dy, &dx);
+        self.weierstrass_chord(&lambda, a, &b.x)
+    }
+
+    /// Returns `2 * a`.
+    pub fn weierstrass_double<C: WeierstrassCurve>(
+        &mut self,
+        a: &WeierstrassPointVariable<C>,
+    ) -> WeierstrassPointVariable<C> {
+        let three = self.constant::<NonNativeFieldVariable<C::BaseField>>(BigUint::from(3u32));
+        let coefficient = self.constant::<NonNativeFieldVariable<C::BaseField>>(C::a());
+        let x_squared = self.nonnative_mul(&a.x, &a.x);
+        let numerator = self.nonnative_mul(&three, &x_squared);
+        let numerator = self.nonnative_add(&numerator, &coefficient);
+        let denominator = self.nonnative_add(&a.y, &a.y);
+        let lambda = self.nonnative_div(&numerator, &denominator);
+        self.weierstrass_chord(&lambda, a, &a.x)
+    }
+
+    /// Returns `a * G + b * point`, where `G` is the generator of the curve, as a
+    /// multi-scalar multiplication with a fixed and a variable base. See `weierstrass_msm` for
+    /// the conditions on the inputs.
+    pub fn weierstrass_double_scalar_mul<C: WeierstrassCurve>(
+        &mut self,
+        a: &NonNativeFieldVariable<C::ScalarField>,
+        b: &NonNativeFieldVariable<C::ScalarField>,
+        point: &WeierstrassPointVariable<C>,
+    ) -> WeierstrassPointVariable<C> {
+        let tables = [
+            self.weierstrass_fixed_base_table(C::generator()),
+            self.weierstrass_variable_base_table(point),
+        ];
+        self.weierstrass_windowed_msm(&[a.clone(), b.clone()], &tables)
+    }
+
+    /// Returns the third point on the line of slope `lambda` through `a` and a point of x
+    /// coordinate `other_x`, reflected over the x axis.
+    fn weierstrass_chord<C: WeierstrassCurve>(
+        &mut self,
+        lambda: &NonNativeFieldVariable<C::BaseField>,
+        a: &WeierstrassPointVariable<C>,
+        other_x: &NonNativeFieldVariable<C::BaseField>,
+    ) -> WeierstrassPointVariable<C> {
+        let lambda_squared = self.nonnative_mul(lambda, lambda);
+        let x = self.nonnative_sub(&lambda_squared, &a.x);
+        let x = self.nonnative_sub(&x, other_x);
+        let dx = self.nonnative_sub(&a.x, &x);
+        let y = self.nonnative_mul(lambda, &dx);
+        let y = self.nonnative_sub(&y, &a.y);
+        WeierstrassPointVariable { x, y }
+    }
+
+    /// Returns `x^3 + a * x + b`.
+    fn weierstrass_curve_rhs<C: WeierstrassCurve>(
+        &mut self,
+        x: &NonNativeFieldVariable<C::BaseField>,
+    ) -> NonNativeFieldVariable<C::BaseField> {
+        let a = self.constant::<NonNativeFieldVariable<C::BaseField>>(C::a());
+        let b = self.constant::<NonNativeFieldVariable<C::BaseField>>(C::b());
+        let x_squared = self.nonnative_mul(x, x);
+        let x_squared_plus_a = self.nonnative_add(&x_squared, &a);
+        let rhs = self.nonnative_mul(&x_squared_plus_a, x);
+        self.nonnative_add(&rhs, &b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frontend::ecc::secp256k1::curve::Secp256k1;
+    use crate::frontend::ecc::secp256k1::field::Secp256k1Scalar;
+    use crate::prelude::DefaultBuilder;
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_weierstrass_double_scalar_mul() {
+        type Scalar = NonNativeFieldVariable<Secp256k1Scalar>;
+        type Point = WeierstrassPointVariable<Secp256k1>;
+
+        let mut builder = DefaultBuilder::new();
+        let a = builder.read::<Scalar>();
+        let b = builder.read::<Scalar>();
+        let point = builder.read::<Point>();
+        builder.weierstrass_assert_on_curve(&point);
+        let result = builder.weierstrass_double_scalar_mul(&a, &b, &point);
+        builder.write(result);
+        let circuit = builder.mock_build();
+
+        // With `point = 2G`, `3 * G + 5 * point = 13 * G`.
+        let generator = Secp256k1::generator();
+        let two_g = double_native::<Secp256k1>(&generator);
+        let thirteen_g = mul_native::<Secp256k1>(&BigUint::from(13u32), &generator).unwrap();
+        let mut input = circuit.input();
+        input.write::<Scalar>(BigUint::from(3u32));
+        input.write::<Scalar>(BigUint::from(5u32));
+        input.write::<Point>(two_g.into());
+        let (_witness, mut output) = circuit.mock_prove(&input);
+        assert_eq!(output.read::<Point>(), thirteen_g.into());
+    }
+}
@@ -0,0 +1,274 @@
+use plonky2::field::types::Field;
+
+use super::vars::{AbiToken, AbiType, ABI_WORD_LEN};
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::builder::CircuitBuilder;
+use crate::frontend::vars::{
+    ByteVariable, Bytes32Variable, EvmVariable, U32Variable, Variable, VariableBytesVariable,
+};
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Decodes the arguments of a call to the function with the given `selector` from its
+    /// `calldata`, according to the types of `schema`.
+    ///
+    /// The calldata is the selector followed by the ABI encoding of the arguments. See
+    /// `abi_decode` for the checks on the encoding.
+    pub fn abi_decode_calldata<const MAX: usize>(
+        &mut self,
+        calldata: &VariableBytesVariable<MAX>,
+        selector: [u8; 4],
+        schema: &[AbiType],
+    ) -> Vec<AbiToken> {
+        for (byte, expected) in calldata.data.as_slice()[..4].iter().zip(selector) {
+            let expected = self.constant::<ByteVariable>(expected);
+            self.assert_is_equal(*byte, expected);
+        }
+        self.abi_decode_at(calldata, 4, schema)
+    }
+
+    /// Decodes the ABI encoding `data`, such as the data of a log, according to the types of
+    /// `schema`.
+    ///
+    /// The encoding is checked to be well formed: the padding of every word is zero, the offset of
+    /// every dynamic value points past the head, and every dynamic value fits in its maximum
+    /// length and in the data. The elements of a dynamic value past its length are zero.
+    pub fn abi_decode<const MAX: usize>(
+        &mut self,
+        data: &VariableBytesVariable<MAX>,
+        schema: &[AbiType],
+    ) -> Vec<AbiToken> {
+        self.abi_decode_at(data, 0, schema)
+    }
+
+    /// Decodes the ABI encoding starting at `base` in `data`, whose offsets are relative to
+    /// `base`.
+    fn abi_decode_at<const MAX: usize>(
+        &mut self,
+        data: &VariableBytesVariable<MAX>,
+        base: usize,
+        schema: &[AbiType],
+    ) -> Vec<AbiToken> {
+        let true_v = self._true();
+        let zero_byte = self.constant::<ByteVariable>(0);
+        let const_32 = self.constant::<Variable>(L::Field::from_canonical_usize(ABI_WORD_LEN));
+        let head_len = ABI_WORD_LEN * schema.len();
+        assert!(
+            base + head_len <= MAX,
+            "the head of the encoding is {} bytes long, more than {}",
+            head_len,
+            MAX - base
+        );
+        let base_variable = self.constant::<Variable>(L::Field::from_canonical_usize(base));
+        let head_len_variable = self.constant::<Variable>(L::Field::from_canonical_usize(head_len));
+        let head_end = self.add(base_variable, head_len_variable);
+        let has_head = self.lte(head_end, data.length.variable);
+        self.assert_is_equal(has_head, true_v);
+
+        // Dynamic values are read from the data padded with zeros, so that reading the largest
+        // value allowed by the schema stays within bounds.
+        let max_tail_len = schema
+            .iter()
+            .map(|ty| match ty {
+                AbiType::Bytes(max_len) => *max_len,
+                AbiType::Array(max_len) => ABI_WORD_LEN * max_len,
+                _ => 0,
+            })
+            .max()
+            .unwrap_or(0);
+        let mut padded = data.data.as_vec();
+        padded.resize(MAX + ABI_WORD_LEN + max_tail_len, zero_byte);
+
+        let mut tokens = Vec::with_capacity(schema.len());
+        for (i, ty) in schema.iter().enumerate() {
+            let start = base + ABI_WORD_LEN * i;
+            let word = &data.data.as_slice()[start..start + ABI_WORD_LEN];
+            let token = match *ty {
+                AbiType::Uint(len) => {
+                    assert!(len <= ABI_WORD_LEN);
+                    self.abi_assert_zero(&word[..ABI_WORD_LEN - len]);
+                    AbiToken::Uint(word[ABI_WORD_LEN - len..].to_vec())
+                }
+                AbiType::FixedBytes(len) => {
+                    assert!(len <= ABI_WORD_LEN);
+                    self.abi_assert_zero(&word[len..]);
+                    AbiToken::FixedBytes(word[..len].to_vec())
+                }
+                AbiType::Bytes(max_len) | AbiType::Array(max_len) => {
+                    let offset = self.abi_decode_u32_word(word);
+                    let is_past_head = self.lte(head_len_variable, offset.variable);
+                    self.assert_is_equal(is_past_head, true_v);
+
+                    let position = self.add(base_variable, offset.variable);
+                    let length_word = self.select_subarray::<ABI_WORD_LEN>(&padded, position);
+                    let length = self.abi_decode_u32_word(&length_word);
+                    let max_len_variable =
+                        self.constant::<Variable>(L::Field::from_canonical_usize(max_len));
+                    let fits = self.lte(length.variable, max_len_variable);
+                    self.assert_is_equal(fits, true_v);
+
+                    let element_len = if matches!(ty, AbiType::Array(_)) {
+                        ABI_WORD_LEN
+                    } else {
+                        1
+                    };
+                    let element_len_variable =
+                        self.constant::<Variable>(L::Field::from_canonical_usize(element_len));
+                    let bytes_len = self.mul(length.variable, element_len_variable);
+                    let bytes_start = self.add(position, const_32);
+                    let bytes_end = self.add(bytes_start, bytes_len);
+                    let is_in_data = self.lte(bytes_end, data.length.variable);
+                    self.assert_is_equal(is_in_data, true_v);
+
+                    let bytes = (0..element_len * max_len)
+                        .map(|k| {
+                            let k = self.constant::<Variable>(L::Field::from_canonical_usize(k));
+                            let index = self.add(bytes_start, k);
+                            let [byte] = self.select_subarray::<1>(&padded, index);
+                            let in_range = self.lt(k, bytes_len);
+                            self.select(in_range, byte, zero_byte)
+                        })
+                        .collect::<Vec<_>>();
+                    match ty {
+                        AbiType::Bytes(_) => AbiToken::Bytes(bytes, length),
+                        _ => AbiToken::Array(
+                            bytes
+                                .chunks_exact(ABI_WORD_LEN)
+                                .map(Bytes32Variable::from)
+                                .collect(),
+                            length,
+                        ),
+                    }
+                }
+            };
+            tokens.push(token);
+        }
+        tokens
+    }
+
+    /// Decodes a word holding an offset or a length, asserting that it fits in 4 bytes.
+    fn abi_decode_u32_word(&mut self, word: &[ByteVariable]) -> U32Variable {
+        self.abi_assert_zero(&word[..ABI_WORD_LEN - 4]);
+        U32Variable::decode(self, &word[ABI_WORD_LEN - 4..])
+    }
+
+    /// Asserts that all of `bytes` are zero.
+    fn abi_assert_zero(&mut self, bytes: &[ByteVariable]) {
+        let zero_byte = self.constant::<ByteVariable>(0);
+        for byte in bytes.iter() {
+            self.assert_is_equal(*byte, zero_byte);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ethers::abi::{encode, Token};
+    use ethers::types::{H160, H256, U256};
+    use ethers::utils::id;
+
+    use super::*;
+    use crate::frontend::eth::vars::AddressVariable;
+    use crate::frontend::uint::uint64::U64Variable;
+    use crate::frontend::vars::{ArrayVariable, U256Variable};
+    use crate::prelude::DefaultBuilder;
+
+    const SIGNATURE: &str = "claim(uint256,address,bytes,bytes32[],uint64)";
+    const SCHEMA: [AbiType; 5] = [
+        AbiType::Uint(32),
+        AbiType::Uint(20),
+        AbiType::Bytes(64),
+        AbiType::Array(3),
+        AbiType::Uint(8),
+    ];
+    const MAX_LEN: usize = 400;
+
+    fn encode_claim(memo: Vec<u8>, proofs: Vec<H256>) -> Vec<u8> {
+        let mut calldata = id(SIGNATURE).to_vec();
+        calldata.extend(encode(&[
+            Token::Uint(U256::from(1_000_000u64)),
+            Token::Address(H160::repeat_byte(0x11)),
+            Token::Bytes(memo),
+            Token::Array(
+                proofs
+                    .into_iter()
+                    .map(|proof| Token::FixedBytes(proof.as_bytes().to_vec()))
+                    .collect(),
+            ),
+            Token::Uint(U256::from(1_700_000_000u64)),
+        ]));
+        calldata
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_abi_decode_calldata() {
+        let mut builder = DefaultBuilder::new();
+        let calldata = builder.read::<VariableBytesVariable<MAX_LEN>>();
+        let tokens = builder.abi_decode_calldata(&calldata, id(SIGNATURE), &SCHEMA);
+        let (
+            AbiToken::Uint(amount),
+            AbiToken::Uint(to),
+            AbiToken::Bytes(memo, memo_len),
+            AbiToken::Array(proofs, num_proofs),
+            AbiToken::Uint(deadline),
+        ) = (&tokens[0], &tokens[1], &tokens[2], &tokens[3], &tokens[4])
+        else {
+            panic!("the tokens do not match the schema");
+        };
+        let amount = U256Variable::decode(&mut builder, amount);
+        let to = AddressVariable::decode(&mut builder, to);
+        let deadline = U64Variable::decode(&mut builder, deadline);
+        builder.write(amount);
+        builder.write(to);
+        builder.write(VariableBytesVariable::<64> {
+            data: ArrayVariable::new(memo.clone()),
+            length: *memo_len,
+        });
+        builder.write(ArrayVariable::<Bytes32Variable, 3>::new(proofs.clone()));
+        builder.write(*num_proofs);
+        builder.write(deadline);
+        let circuit = builder.mock_build();
+
+        for (memo, proofs) in [
+            (b"first claim".to_vec(), vec![H256::repeat_byte(1)]),
+            (vec![0xab; 64], (1..=3).map(H256::repeat_byte).collect()),
+            (vec![], vec![]),
+        ] {
+            let mut input = circuit.input();
+            input.write::<VariableBytesVariable<MAX_LEN>>(encode_claim(
+                memo.clone(),
+                proofs.clone(),
+            ));
+            let (_witness, mut output) = circuit.mock_prove(&input);
+
+            assert_eq!(output.read::<U256Variable>(), U256::from(1_000_000u64));
+            assert_eq!(output.read::<AddressVariable>(), H160::repeat_byte(0x11));
+            assert_eq!(output.read::<VariableBytesVariable<64>>(), memo);
+            let mut padded_proofs = proofs.clone();
+            padded_proofs.resize(3, H256::zero());
+            assert_eq!(
+                output.read::<ArrayVariable<Bytes32Variable, 3>>(),
+                padded_proofs
+            );
+            assert_eq!(output.read::<U32Variable>(), proofs.len() as u32);
+            assert_eq!(output.read::<U64Variable>(), 1_700_000_000);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_abi_decode_offset_in_head() {
+        let mut builder = DefaultBuilder::new();
+        let data = builder.read::<VariableBytesVariable<MAX_LEN>>();
+        builder.abi_decode(&data, &SCHEMA);
+        let circuit = builder.mock_build();
+
+        // Point the offset of the memo at the head of the encoding.
+        let mut data = encode_claim(b"memo".to_vec(), vec![])[4..].to_vec();
+        data[2 * ABI_WORD_LEN + ABI_WORD_LEN - 1] = ABI_WORD_LEN as u8;
+        let mut input = circuit.input();
+        input.write::<VariableBytesVariable<MAX_LEN>>(data);
+        circuit.mock_prove(&input);
+    }
+}
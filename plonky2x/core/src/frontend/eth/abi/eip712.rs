@@ -0,0 +1,178 @@
+use ethers::utils::keccak256;
+use plonky2::field::types::Field;
+
+use super::vars::{AbiToken, ABI_WORD_LEN};
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::builder::CircuitBuilder;
+use crate::frontend::eth::vars::AddressVariable;
+use crate::frontend::uint::uint256::U256Variable;
+use crate::frontend::vars::{
+    ByteVariable, Bytes32Variable, CircuitVariable, U32Variable, Variable,
+};
+
+/// The type of the EIP-712 domain with a name, a version, a chain id and a verifying contract.
+pub const EIP712_DOMAIN_TYPE: &str =
+    "EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)";
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Computes the EIP-712 `hashStruct` of a struct, which is
+    /// `keccak256(typeHash || encodeData(fields))`, with `typeHash` the keccak256 digest of the
+    /// encoded type, such as `Mail(address from,address to,string contents)`.
+    ///
+    /// Atomic fields are padded to 32 bytes as by `abi.encode`, while `bytes` and `string` fields
+    /// and arrays are replaced by the keccak256 digest of their contents. A field holding a struct
+    /// is given as a fixed-size token of its `hashStruct`.
+    pub fn eip712_hash_struct(
+        &mut self,
+        encoded_type: &str,
+        fields: &[AbiToken],
+    ) -> Bytes32Variable {
+        let type_hash = self.constant::<Bytes32Variable>(keccak256(encoded_type).into());
+        let mut encoding = type_hash.as_bytes().to_vec();
+        for field in fields.iter() {
+            let word = self.eip712_encode_field(field);
+            encoding.extend(word.as_bytes());
+        }
+        self.keccak256(&encoding)
+    }
+
+    /// Computes the EIP-712 domain separator of a domain of type `EIP712_DOMAIN_TYPE`.
+    pub fn eip712_domain_separator(
+        &mut self,
+        name: &str,
+        version: &str,
+        chain_id: U256Variable,
+        verifying_contract: AddressVariable,
+    ) -> Bytes32Variable {
+        let name_hash = self.constant::<Bytes32Variable>(keccak256(name).into());
+        let version_hash = self.constant::<Bytes32Variable>(keccak256(version).into());
+        let fields = [
+            AbiToken::FixedBytes(name_hash.as_bytes().to_vec()),
+            AbiToken::FixedBytes(version_hash.as_bytes().to_vec()),
+            AbiToken::uint(self, &chain_id),
+            AbiToken::uint(self, &verifying_contract),
+        ];
+        self.eip712_hash_struct(EIP712_DOMAIN_TYPE, &fields)
+    }
+
+    /// Computes the EIP-712 digest `keccak256("\x19\x01" || domainSeparator || hashStruct)`, which
+    /// is the message signed for typed data.
+    pub fn eip712_digest(
+        &mut self,
+        domain_separator: Bytes32Variable,
+        struct_hash: Bytes32Variable,
+    ) -> Bytes32Variable {
+        let mut encoding = vec![
+            self.constant::<ByteVariable>(0x19),
+            self.constant::<ByteVariable>(0x01),
+        ];
+        encoding.extend(domain_separator.as_bytes());
+        encoding.extend(struct_hash.as_bytes());
+        self.keccak256(&encoding)
+    }
+
+    /// Encodes a field of a struct as the 32-byte word of `encodeData`.
+    fn eip712_encode_field(&mut self, field: &AbiToken) -> Bytes32Variable {
+        let zero = self.constant::<ByteVariable>(0);
+        match field {
+            AbiToken::Uint(bytes) => {
+                assert!(bytes.len() <= ABI_WORD_LEN);
+                let mut word = vec![zero; ABI_WORD_LEN - bytes.len()];
+                word.extend(bytes);
+                Bytes32Variable::from(word.as_slice())
+            }
+            AbiToken::FixedBytes(bytes) => {
+                assert!(bytes.len() <= ABI_WORD_LEN);
+                let mut word = bytes.clone();
+                word.resize(ABI_WORD_LEN, zero);
+                Bytes32Variable::from(word.as_slice())
+            }
+            AbiToken::Bytes(bytes, length) => self.keccak256_variable(bytes, *length),
+            AbiToken::Array(words, count) => {
+                let bytes = words
+                    .iter()
+                    .flat_map(|word| word.as_bytes())
+                    .collect::<Vec<_>>();
+                let const_32 =
+                    self.constant::<Variable>(L::Field::from_canonical_usize(ABI_WORD_LEN));
+                let length = self.mul(count.variable, const_32);
+                let length = U32Variable::from_variables(self, &[length]);
+                self.keccak256_variable(&bytes, length)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ethers::abi::{encode, Token};
+    use ethers::types::{H160, H256, U256};
+
+    use super::*;
+    use crate::frontend::vars::{ArrayVariable, VariableBytesVariable};
+    use crate::prelude::DefaultBuilder;
+
+    const ORDER_TYPE: &str = "Order(address maker,uint256 amount,bytes data,bytes32[] proofs)";
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_eip712_digest() {
+        let mut builder = DefaultBuilder::new();
+        let chain_id = builder.read::<U256Variable>();
+        let verifying_contract = builder.read::<AddressVariable>();
+        let maker = builder.read::<AddressVariable>();
+        let amount = builder.read::<U256Variable>();
+        let data = builder.read::<VariableBytesVariable<48>>();
+        let proofs = builder.read::<ArrayVariable<Bytes32Variable, 2>>();
+        let num_proofs = builder.read::<U32Variable>();
+
+        let domain_separator =
+            builder.eip712_domain_separator("Exchange", "1", chain_id, verifying_contract);
+        let fields = [
+            AbiToken::uint(&mut builder, &maker),
+            AbiToken::uint(&mut builder, &amount),
+            AbiToken::bytes(&data),
+            AbiToken::array(&proofs, num_proofs),
+        ];
+        let struct_hash = builder.eip712_hash_struct(ORDER_TYPE, &fields);
+        let digest = builder.eip712_digest(domain_separator, struct_hash);
+        builder.write(domain_separator);
+        builder.write(digest);
+        let circuit = builder.mock_build();
+
+        let chain_id = U256::from(1);
+        let verifying_contract = H160::repeat_byte(0xcc);
+        let maker = H160::repeat_byte(0x11);
+        let amount = U256::from(10).pow(U256::from(18));
+        let data = b"fill or kill".to_vec();
+        let proofs = vec![H256::repeat_byte(0xaa)];
+
+        let mut input = circuit.input();
+        input.write::<U256Variable>(chain_id);
+        input.write::<AddressVariable>(verifying_contract);
+        input.write::<AddressVariable>(maker);
+        input.write::<U256Variable>(amount);
+        input.write::<VariableBytesVariable<48>>(data.clone());
+        input.write::<ArrayVariable<Bytes32Variable, 2>>(vec![proofs[0], H256::zero()]);
+        input.write::<U32Variable>(proofs.len() as u32);
+        let (_witness, mut output) = circuit.mock_prove(&input);
+
+        let domain_separator = keccak256(encode(&[
+            Token::FixedBytes(keccak256(EIP712_DOMAIN_TYPE).to_vec()),
+            Token::FixedBytes(keccak256("Exchange").to_vec()),
+            Token::FixedBytes(keccak256("1").to_vec()),
+            Token::Uint(chain_id),
+            Token::Address(verifying_contract),
+        ]));
+        let struct_hash = keccak256(encode(&[
+            Token::FixedBytes(keccak256(ORDER_TYPE).to_vec()),
+            Token::Address(maker),
+            Token::Uint(amount),
+            Token::FixedBytes(keccak256(&data).to_vec()),
+            Token::FixedBytes(keccak256(proofs[0]).to_vec()),
+        ]));
+        let digest = keccak256([&[0x19, 0x01], &domain_separator[..], &struct_hash[..]].concat());
+        assert_eq!(output.read::<Bytes32Variable>(), H256(domain_separator));
+        assert_eq!(output.read::<Bytes32Variable>(), H256(digest));
+    }
+}
@@ -0,0 +1,347 @@
+use plonky2::field::types::Field;
+
+use super::vars::{AbiToken, ABI_WORD_LEN};
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::builder::CircuitBuilder;
+use crate::frontend::vars::{
+    ArrayVariable, BoolVariable, ByteVariable, CircuitVariable, EvmVariable, U32Variable, Variable,
+    VariableBytesVariable,
+};
+
+/// Bytes written to the output of an encoding at a possibly variable offset.
+///
+/// The bytes past the length of the piece are zero, and `min_offset` and `max_offset` are static
+/// bounds on `offset`, which limit the positions the piece can be written at.
+struct AbiPiece {
+    bytes: Vec<Variable>,
+    offset: Variable,
+    min_offset: usize,
+    max_offset: usize,
+}
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Computes `abi.encode(tokens)`, up to `MAX` bytes.
+    ///
+    /// Every static token takes one word of the head. A dynamic token takes one word of the head
+    /// holding the offset of its tail, which is its length followed by its bytes, padded to a
+    /// multiple of 32 bytes.
+    pub fn abi_encode<const MAX: usize>(
+        &mut self,
+        tokens: &[AbiToken],
+    ) -> VariableBytesVariable<MAX> {
+        let const_32 = self.constant::<Variable>(L::Field::from_canonical_usize(ABI_WORD_LEN));
+        let head_len = ABI_WORD_LEN * tokens.len();
+
+        let mut pieces = Vec::new();
+        let mut tail_offset = self.constant::<Variable>(L::Field::from_canonical_usize(head_len));
+        let (mut min_tail_offset, mut max_tail_offset) = (head_len, head_len);
+        for (i, token) in tokens.iter().enumerate() {
+            let head_offset =
+                self.constant::<Variable>(L::Field::from_canonical_usize(ABI_WORD_LEN * i));
+            let word = match token {
+                AbiToken::Uint(bytes) => self.abi_word(bytes, true),
+                AbiToken::FixedBytes(bytes) => self.abi_word(bytes, false),
+                AbiToken::Bytes(..) | AbiToken::Array(..) => {
+                    let (data, count, padded_len) = self.abi_tail_data(token);
+                    let offset = U32Variable::from_variables(self, &[tail_offset]);
+                    let offset_bytes = offset.encode(self);
+                    let count_bytes = count.encode(self);
+                    let max_padded_len = data.len().div_ceil(ABI_WORD_LEN) * ABI_WORD_LEN;
+                    let mut tail = self.abi_word(&count_bytes, true);
+                    tail.extend(data);
+                    pieces.push(AbiPiece {
+                        bytes: tail,
+                        offset: tail_offset,
+                        min_offset: min_tail_offset,
+                        max_offset: max_tail_offset,
+                    });
+
+                    tail_offset = self.add(tail_offset, const_32);
+                    tail_offset = self.add(tail_offset, padded_len);
+                    min_tail_offset += ABI_WORD_LEN;
+                    max_tail_offset += ABI_WORD_LEN + max_padded_len;
+                    self.abi_word(&offset_bytes, true)
+                }
+            };
+            pieces.push(AbiPiece {
+                bytes: word,
+                offset: head_offset,
+                min_offset: ABI_WORD_LEN * i,
+                max_offset: ABI_WORD_LEN * i,
+            });
+        }
+
+        assert!(
+            max_tail_offset <= MAX,
+            "the encoding can be {} bytes long, more than {}",
+            max_tail_offset,
+            MAX
+        );
+        self.abi_concat(&pieces, tail_offset)
+    }
+
+    /// Computes `abi.encodePacked(tokens)`, up to `MAX` bytes.
+    ///
+    /// Tokens are concatenated without padding, each integer taking as many bytes as its type,
+    /// except for the elements of arrays which take 32 bytes each.
+    pub fn abi_encode_packed<const MAX: usize>(
+        &mut self,
+        tokens: &[AbiToken],
+    ) -> VariableBytesVariable<MAX> {
+        let mut pieces = Vec::new();
+        let mut offset = self.zero::<Variable>();
+        let (mut min_offset, mut max_offset) = (0, 0);
+        for token in tokens.iter() {
+            let (bytes, len, min_len) = match token {
+                AbiToken::Uint(bytes) | AbiToken::FixedBytes(bytes) => {
+                    let len =
+                        self.constant::<Variable>(L::Field::from_canonical_usize(bytes.len()));
+                    let bytes = bytes
+                        .iter()
+                        .map(|byte| byte.to_variable(self))
+                        .collect::<Vec<_>>();
+                    let min_len = bytes.len();
+                    (bytes, len, min_len)
+                }
+                AbiToken::Bytes(bytes, length) => {
+                    let masked = self.abi_masked_bytes(bytes, length.variable);
+                    (masked, length.variable, 0)
+                }
+                AbiToken::Array(..) => {
+                    // The bytes of an array are already a multiple of 32 bytes long.
+                    let (masked, _, len) = self.abi_tail_data(token);
+                    (masked, len, 0)
+                }
+            };
+            let max_len = bytes.len();
+            pieces.push(AbiPiece {
+                bytes,
+                offset,
+                min_offset,
+                max_offset,
+            });
+            offset = self.add(offset, len);
+            min_offset += min_len;
+            max_offset += max_len;
+        }
+
+        assert!(
+            max_offset <= MAX,
+            "the encoding can be {} bytes long, more than {}",
+            max_offset,
+            MAX
+        );
+        self.abi_concat(&pieces, offset)
+    }
+
+    /// Pads `bytes` to a 32-byte word, on the left if `left_pad` and on the right otherwise.
+    fn abi_word(&mut self, bytes: &[ByteVariable], left_pad: bool) -> Vec<Variable> {
+        assert!(bytes.len() <= ABI_WORD_LEN);
+        let zero = self.zero::<Variable>();
+        let bytes = bytes
+            .iter()
+            .map(|byte| byte.to_variable(self))
+            .collect::<Vec<_>>();
+        let padding = vec![zero; ABI_WORD_LEN - bytes.len()];
+        if left_pad {
+            [padding, bytes].concat()
+        } else {
+            [bytes, padding].concat()
+        }
+    }
+
+    /// Returns `bytes` with the bytes past `length` set to zero, asserting that `length` is at
+    /// most the number of bytes.
+    fn abi_masked_bytes(&mut self, bytes: &[ByteVariable], length: Variable) -> Vec<Variable> {
+        let zero = self.zero::<Variable>();
+        let true_v = self._true();
+        let max_len = self.constant::<Variable>(L::Field::from_canonical_usize(bytes.len()));
+        let fits = self.lte(length, max_len);
+        self.assert_is_equal(fits, true_v);
+        bytes
+            .iter()
+            .enumerate()
+            .map(|(k, byte)| {
+                let k = self.constant::<Variable>(L::Field::from_canonical_usize(k));
+                let in_range = self.lt(k, length);
+                let byte = byte.to_variable(self);
+                self.select(in_range, byte, zero)
+            })
+            .collect()
+    }
+
+    /// Returns the masked bytes of the tail of a dynamic token, after its length word, together
+    /// with its number of elements and the length of its bytes padded to a multiple of 32.
+    fn abi_tail_data(&mut self, token: &AbiToken) -> (Vec<Variable>, U32Variable, Variable) {
+        match token {
+            AbiToken::Bytes(bytes, length) => {
+                let data = self.abi_masked_bytes(bytes, length.variable);
+                let padded_len = self.abi_padded_len(*length);
+                (data, *length, padded_len)
+            }
+            AbiToken::Array(words, count) => {
+                let bytes = words
+                    .iter()
+                    .flat_map(|word| word.as_bytes())
+                    .collect::<Vec<_>>();
+                let const_32 =
+                    self.constant::<Variable>(L::Field::from_canonical_usize(ABI_WORD_LEN));
+                let len = self.mul(count.variable, const_32);
+                let data = self.abi_masked_bytes(&bytes, len);
+                (data, *count, len)
+            }
+            _ => panic!("only dynamic tokens have a tail"),
+        }
+    }
+
+    /// Returns `length` rounded up to a multiple of 32.
+    fn abi_padded_len(&mut self, length: U32Variable) -> Variable {
+        let zero = self.zero::<Variable>();
+        let const_32 = self.constant::<Variable>(L::Field::from_canonical_usize(ABI_WORD_LEN));
+        let bits = self
+            .api
+            .split_le(length.variable.0, 32)
+            .into_iter()
+            .map(BoolVariable::from)
+            .collect::<Vec<_>>();
+        let mut rem = zero;
+        for (i, bit) in bits.iter().take(5).enumerate() {
+            let power = self.constant::<Variable>(L::Field::from_canonical_u64(1 << i));
+            let term = self.mul(bit.variable, power);
+            rem = self.add(rem, term);
+        }
+        let is_aligned = self.is_equal(rem, zero);
+        let padding = self.sub(const_32, rem);
+        let padding = self.select(is_aligned, zero, padding);
+        self.add(length.variable, padding)
+    }
+
+    /// Writes `pieces` at their offsets into `MAX` zero bytes, returning the first `length`.
+    ///
+    /// Each byte of the output is the sum of the bytes written at its position, of which there is
+    /// at most one. A piece at a constant offset is written directly.
+    fn abi_concat<const MAX: usize>(
+        &mut self,
+        pieces: &[AbiPiece],
+        length: Variable,
+    ) -> VariableBytesVariable<MAX> {
+        let zero = self.zero::<Variable>();
+        let mut output = vec![zero; MAX];
+        for piece in pieces.iter() {
+            if piece.min_offset == piece.max_offset {
+                for (k, byte) in piece.bytes.iter().enumerate() {
+                    let j = piece.min_offset + k;
+                    output[j] = self.add(output[j], *byte);
+                }
+                continue;
+            }
+            if piece.bytes.is_empty() {
+                continue;
+            }
+            let end = (piece.max_offset + piece.bytes.len()).min(MAX);
+            for (j, output_byte) in output
+                .iter_mut()
+                .enumerate()
+                .take(end)
+                .skip(piece.min_offset)
+            {
+                let position = self.constant::<Variable>(L::Field::from_canonical_usize(j));
+                let shift = self.sub(position, piece.offset);
+                let first = j.saturating_sub(piece.max_offset);
+                let last = (j - piece.min_offset).min(piece.bytes.len() - 1);
+                for k in first..=last {
+                    let k_variable = self.constant::<Variable>(L::Field::from_canonical_usize(k));
+                    let is_at_position = self.is_equal(shift, k_variable);
+                    let contribution = self.mul(is_at_position.variable, piece.bytes[k]);
+                    *output_byte = self.add(*output_byte, contribution);
+                }
+            }
+        }
+
+        let data = output
+            .into_iter()
+            .map(|byte| ByteVariable::from_variable(self, byte))
+            .collect::<Vec<_>>();
+        VariableBytesVariable {
+            data: ArrayVariable::new(data),
+            length: U32Variable::from_variables(self, &[length]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ethers::abi::{encode, Token};
+    use ethers::types::{H160, H256, U256};
+
+    use super::*;
+    use crate::frontend::eth::vars::AddressVariable;
+    use crate::frontend::uint::uint64::U64Variable;
+    use crate::frontend::vars::{Bytes32Variable, U256Variable};
+    use crate::prelude::DefaultBuilder;
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_abi_encode() {
+        let mut builder = DefaultBuilder::new();
+        let amount = builder.read::<U256Variable>();
+        let owner = builder.read::<AddressVariable>();
+        let root = builder.read::<Bytes32Variable>();
+        let data = builder.read::<VariableBytesVariable<40>>();
+        let nonce = builder.read::<U64Variable>();
+        let salt = builder.read::<VariableBytesVariable<8>>();
+
+        let tokens = vec![
+            AbiToken::uint(&mut builder, &amount),
+            AbiToken::uint(&mut builder, &owner),
+            AbiToken::fixed_bytes(&mut builder, &root),
+            AbiToken::bytes(&data),
+            AbiToken::uint(&mut builder, &nonce),
+            AbiToken::bytes(&salt),
+        ];
+        let encoded = builder.abi_encode::<352>(&tokens);
+        let packed = builder.abi_encode_packed::<140>(&tokens);
+        builder.write(encoded);
+        builder.write(packed);
+        let circuit = builder.mock_build();
+
+        let amount = U256::from(123_456_789u64) << 128;
+        let owner = H160::repeat_byte(0x11);
+        let root = H256::repeat_byte(0x22);
+        let nonce = 42u64;
+        for (data, salt) in [
+            ((1..=40).collect::<Vec<u8>>(), vec![0xaa; 8]),
+            (vec![0x33; 5], vec![]),
+            (vec![0x44; 32], vec![0xbb; 1]),
+        ] {
+            let mut input = circuit.input();
+            input.write::<U256Variable>(amount);
+            input.write::<AddressVariable>(owner);
+            input.write::<Bytes32Variable>(root);
+            input.write::<VariableBytesVariable<40>>(data.clone());
+            input.write::<U64Variable>(nonce);
+            input.write::<VariableBytesVariable<8>>(salt.clone());
+            let (_witness, mut output) = circuit.mock_prove(&input);
+
+            let expected = encode(&[
+                Token::Uint(amount),
+                Token::Address(owner),
+                Token::FixedBytes(root.as_bytes().to_vec()),
+                Token::Bytes(data.clone()),
+                Token::Uint(U256::from(nonce)),
+                Token::Bytes(salt.clone()),
+            ]);
+            assert_eq!(output.read::<VariableBytesVariable<352>>(), expected);
+
+            let mut amount_bytes = [0u8; 32];
+            amount.to_big_endian(&mut amount_bytes);
+            let mut expected_packed = amount_bytes.to_vec();
+            expected_packed.extend(owner.as_bytes());
+            expected_packed.extend(root.as_bytes());
+            expected_packed.extend(&data);
+            expected_packed.extend(nonce.to_be_bytes());
+            expected_packed.extend(&salt);
+            assert_eq!(output.read::<VariableBytesVariable<140>>(), expected_packed);
+        }
+    }
+}
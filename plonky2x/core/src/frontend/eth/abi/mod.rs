@@ -0,0 +1,4 @@
+pub mod decode;
+pub mod eip712;
+pub mod encode;
+pub mod vars;
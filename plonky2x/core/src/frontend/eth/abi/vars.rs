@@ -0,0 +1,82 @@
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::builder::CircuitBuilder;
+use crate::frontend::vars::{
+    ArrayVariable, ByteVariable, Bytes32Variable, EvmVariable, U32Variable, VariableBytesVariable,
+};
+
+/// The size of a word of the ABI encoding.
+pub const ABI_WORD_LEN: usize = 32;
+
+/// The Solidity type of a value of an ABI encoding, as part of the schema of a decoding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AbiType {
+    /// An unsigned integer of the given number of bytes, such as `uint256` (32), `address` (20)
+    /// or `bool` (1).
+    Uint(usize),
+    /// A fixed-size byte array `bytesN` of the given number of bytes.
+    FixedBytes(usize),
+    /// A dynamic byte string `bytes` of at most the given number of bytes.
+    Bytes(usize),
+    /// A dynamic array of at most the given number of 32-byte elements, such as `uint256[]` or
+    /// `bytes32[]`.
+    Array(usize),
+}
+
+impl AbiType {
+    /// Whether values of this type are encoded in the tail of the encoding.
+    pub fn is_dynamic(&self) -> bool {
+        matches!(self, Self::Bytes(_) | Self::Array(_))
+    }
+}
+
+/// A value to be ABI encoded, as a Solidity type.
+#[derive(Debug, Clone)]
+pub enum AbiToken {
+    /// A value encoded like an unsigned integer, such as a `uintN`, an `address` or a `bool`, given
+    /// by its big-endian bytes. It is left padded to 32 bytes by `abi.encode`.
+    Uint(Vec<ByteVariable>),
+    /// A fixed-size byte array `bytesN`. It is right padded to 32 bytes by `abi.encode`.
+    FixedBytes(Vec<ByteVariable>),
+    /// A dynamic byte string `bytes`, whose first `length` bytes are the value.
+    Bytes(Vec<ByteVariable>, U32Variable),
+    /// A dynamic array of 32-byte words, whose first `length` words are the value.
+    Array(Vec<Bytes32Variable>, U32Variable),
+}
+
+impl AbiToken {
+    /// A token for an unsigned integer, an address or a bool.
+    pub fn uint<V: EvmVariable, L: PlonkParameters<D>, const D: usize>(
+        builder: &mut CircuitBuilder<L, D>,
+        value: &V,
+    ) -> Self {
+        let bytes = value.encode(builder);
+        assert!(bytes.len() <= 32, "an integer is at most 32 bytes long");
+        Self::Uint(bytes)
+    }
+
+    /// A token for a fixed-size byte array, such as a `Bytes32Variable`.
+    pub fn fixed_bytes<V: EvmVariable, L: PlonkParameters<D>, const D: usize>(
+        builder: &mut CircuitBuilder<L, D>,
+        value: &V,
+    ) -> Self {
+        let bytes = value.encode(builder);
+        assert!(
+            bytes.len() <= 32,
+            "a fixed-size byte array is at most 32 bytes long"
+        );
+        Self::FixedBytes(bytes)
+    }
+
+    /// A token for a dynamic byte string.
+    pub fn bytes<const MAX: usize>(value: &VariableBytesVariable<MAX>) -> Self {
+        Self::Bytes(value.data.as_vec(), value.length)
+    }
+
+    /// A token for a dynamic array of 32-byte words.
+    pub fn array<const MAX: usize>(
+        words: &ArrayVariable<Bytes32Variable, MAX>,
+        length: U32Variable,
+    ) -> Self {
+        Self::Array(words.as_vec(), length)
+    }
+}
@@ -0,0 +1,439 @@
+use std::fmt::Debug;
+
+use ethers::types::H256;
+use plonky2::hash::hash_types::RichField;
+use plonky2x_derive::CircuitVariable;
+
+use super::inclusion::VALIDATOR_PROOF_DEPTH;
+use super::light_client::compute_domain;
+use super::vars::{BeaconAttestationDataVariable, BeaconValidatorVariable};
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::builder::CircuitBuilder;
+use crate::frontend::ecc::bls12_381::g1::Bls12381G1PointVariable;
+use crate::frontend::eth::vars::BLSSignatureVariable;
+use crate::frontend::ssz::types::{SSZType, SSZValueVariable};
+use crate::frontend::uint::uint64::U64Variable;
+use crate::frontend::vars::{
+    ArrayVariable, BoolVariable, Bytes32Variable, CircuitVariable, SSZVariable, Variable,
+};
+use crate::utils::hash::sha256;
+
+/// The domain type of the signatures of attestations.
+pub const DOMAIN_BEACON_ATTESTER: [u8; 4] = [1, 0, 0, 0];
+
+/// Returns the domain of the signatures of attestations for the fork `fork_version` of the chain
+/// of genesis validators root `genesis_validators_root`.
+pub fn compute_attester_domain(fork_version: [u8; 4], genesis_validators_root: H256) -> H256 {
+    compute_domain(
+        DOMAIN_BEACON_ATTESTER,
+        fork_version,
+        genesis_validators_root,
+    )
+}
+
+/// Returns the root of the committee of validators at `indices`, the `hash_tree_root` of the
+/// indices as a `Vector[ValidatorIndex, N]`, as computed by `beacon_committee_root`.
+pub fn compute_committee_root(indices: &[u64]) -> H256 {
+    let mut nodes = indices
+        .chunks(4)
+        .map(|chunk| {
+            let mut leaf = [0u8; 32];
+            for (i, index) in chunk.iter().enumerate() {
+                leaf[8 * i..8 * (i + 1)].copy_from_slice(&index.to_le_bytes());
+            }
+            leaf
+        })
+        .collect::<Vec<_>>();
+    nodes.resize(nodes.len().max(1).next_power_of_two(), [0u8; 32]);
+    while nodes.len() > 1 {
+        nodes = nodes
+            .chunks(2)
+            .map(|pair| sha256(&[pair[0], pair[1]].concat()))
+            .collect();
+    }
+    H256(nodes[0])
+}
+
+/// The data that the BLS signature of an attestation is verified against: the aggregate of the
+/// public keys of the attesters and the signing root of the attestation data.
+#[derive(Debug, Clone, CircuitVariable)]
+#[value_name(BeaconAttestationAggregateValue)]
+pub struct BeaconAttestationAggregateVariable {
+    pub aggregate_pubkey: Bls12381G1PointVariable,
+    pub signing_root: Bytes32Variable,
+}
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Computes the signing root of attestation data with `domain`, as computed by
+    /// `compute_attester_domain`.
+    pub fn beacon_attestation_signing_root(
+        &mut self,
+        data: &BeaconAttestationDataVariable,
+        domain: Bytes32Variable,
+    ) -> Bytes32Variable {
+        let data_root = data.hash_tree_root(self);
+        self.sha256_pair(data_root, domain)
+    }
+
+    /// Computes the root of the committee of validators at `indices`, the `hash_tree_root` of the
+    /// indices as a `Vector[ValidatorIndex, N]`.
+    pub fn beacon_committee_root<const N: usize>(
+        &mut self,
+        indices: &ArrayVariable<U64Variable, N>,
+    ) -> Bytes32Variable {
+        let elements = indices
+            .as_slice()
+            .iter()
+            .map(|index| SSZValueVariable::from_u64(self, *index))
+            .collect();
+        self.ssz_merkleize(
+            &SSZType::vector(SSZType::uint64(), N),
+            &SSZValueVariable::Composite(elements),
+        )
+    }
+
+    /// Verifies the signature of an attestation by the validators at `indices` in the state of
+    /// `state_root` whose aggregation bit is set, and returns the data it was verified against.
+    ///
+    /// The indices are asserted to be the committee of root `committee_root`, as computed by
+    /// `beacon_committee_root`, the validators are checked against the state, their public keys
+    /// are aggregated and the BLS signature is checked against the aggregate public key and the
+    /// signing root of `data`. The shuffling that assigns the committee to `data.slot` and
+    /// `data.index` is not computed in the circuit, so `committee_root` should be a public input
+    /// that the verifier computes from the shuffling with `compute_committee_root`. At least one
+    /// of the aggregation bits must be set.
+    #[allow(clippy::too_many_arguments)]
+    pub fn beacon_attestation_aggregate<const N: usize>(
+        &mut self,
+        state_root: Bytes32Variable,
+        data: &BeaconAttestationDataVariable,
+        committee_root: Bytes32Variable,
+        indices: &ArrayVariable<U64Variable, N>,
+        validators: &ArrayVariable<BeaconValidatorVariable, N>,
+        proofs: &ArrayVariable<ArrayVariable<Bytes32Variable, VALIDATOR_PROOF_DEPTH>, N>,
+        aggregation_bits: &ArrayVariable<BoolVariable, N>,
+        domain: Bytes32Variable,
+        signature: &BLSSignatureVariable,
+    ) -> BeaconAttestationAggregateVariable {
+        let root = self.beacon_committee_root(indices);
+        self.assert_is_equal(root, committee_root);
+        for i in 0..N {
+            self.beacon_verify_validator(state_root, indices[i], &validators[i], &proofs[i]);
+        }
+        let pubkeys = ArrayVariable::<_, N>::new(
+            validators
+                .as_slice()
+                .iter()
+                .map(|validator| validator.pubkey)
+                .collect(),
+        );
+        let aggregate_pubkey = self.bls12_381_aggregate_pubkeys(&pubkeys, aggregation_bits);
+        let signing_root = self.beacon_attestation_signing_root(data, domain);
+        self.bls12_381_verify_signature(&aggregate_pubkey, &signing_root.as_bytes(), signature);
+        BeaconAttestationAggregateVariable {
+            aggregate_pubkey,
+            signing_root,
+        }
+    }
+
+    /// Returns whether two attestation data are slashable, that is a double vote, two different
+    /// data for the same target epoch, or a surround vote, the first surrounding the second.
+    pub fn beacon_is_slashable_attestation_data(
+        &mut self,
+        data_1: &BeaconAttestationDataVariable,
+        data_2: &BeaconAttestationDataVariable,
+    ) -> BoolVariable {
+        let is_same_data = self.is_equal(*data_1, *data_2);
+        let is_different_data = self.not(is_same_data);
+        let is_same_target = self.is_equal(data_1.target.epoch, data_2.target.epoch);
+        let is_double_vote = self.and(is_different_data, is_same_target);
+
+        let source_is_before = self.lt(data_1.source.epoch, data_2.source.epoch);
+        let target_is_after = self.lt(data_2.target.epoch, data_1.target.epoch);
+        let is_surround_vote = self.and(source_is_before, target_is_after);
+
+        self.or(is_double_vote, is_surround_vote)
+    }
+
+    /// Asserts that two attestation data are slashable, as in
+    /// `beacon_is_slashable_attestation_data`.
+    pub fn beacon_assert_slashable_attestation_data(
+        &mut self,
+        data_1: &BeaconAttestationDataVariable,
+        data_2: &BeaconAttestationDataVariable,
+    ) {
+        let is_slashable = self.beacon_is_slashable_attestation_data(data_1, data_2);
+        let true_v = self._true();
+        self.assert_is_equal(is_slashable, true_v);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::BigUint;
+
+    use super::*;
+    use crate::frontend::ecc::bls12_381::field::Bls12381Base;
+    use crate::frontend::ecc::bls12_381::g1::Bls12381G1;
+    use crate::frontend::ecc::nonnative::NonNativeField;
+    use crate::frontend::ecc::weierstrass::{mul_native, WeierstrassCurve};
+    use crate::frontend::eth::beacon::inclusion::VALIDATOR_BASE_GINDEX;
+    use crate::frontend::eth::beacon::vars::{BeaconAttestationDataValue, BeaconCheckpointValue};
+    use crate::prelude::{DefaultBuilder, GoldilocksField};
+    use crate::utils::bytes32;
+    use crate::utils::eth::beacon::BeaconValidator;
+
+    type F = GoldilocksField;
+
+    const N: usize = 4;
+
+    const SIGNATURE: &str = "b55f840175bc382433276b51a8d7a2d7d3c1b7667df9f9dc45030f9324cd6886e11bdb05cedc2344d874321b6eeed4bd1670b9afb2cf6af993d77280be3fcbd86a2e828cf5ebcceccc23c2af73e893bb644bbe156e0fb0e2bda766eee3f48fb4";
+
+    fn hash_pair(left: &[u8], right: &[u8]) -> [u8; 32] {
+        sha256(&[left, right].concat())
+    }
+
+    fn compress(point: &(BigUint, BigUint)) -> [u8; 48] {
+        let mut bytes = [0u8; 48];
+        let x = point.0.to_bytes_be();
+        bytes[48 - x.len()..].copy_from_slice(&x);
+        bytes[0] |= 0x80;
+        if point.1 > (Bls12381Base::modulus() - 1u32) / 2u32 {
+            bytes[0] |= 0x20;
+        }
+        bytes
+    }
+
+    fn attestation_data(
+        slot: u64,
+        source_epoch: u64,
+        target_epoch: u64,
+        byte: u8,
+    ) -> BeaconAttestationDataValue<F> {
+        BeaconAttestationDataValue {
+            slot,
+            index: 7,
+            beacon_block_root: H256::repeat_byte(byte),
+            source: BeaconCheckpointValue {
+                epoch: source_epoch,
+                root: H256::repeat_byte(1),
+            },
+            target: BeaconCheckpointValue {
+                epoch: target_epoch,
+                root: H256::repeat_byte(2),
+            },
+        }
+    }
+
+    #[test]
+    fn test_beacon_is_slashable_attestation_data() {
+        let mut builder = DefaultBuilder::new();
+        let data_1 = builder.read::<BeaconAttestationDataVariable>();
+        let data_2 = builder.read::<BeaconAttestationDataVariable>();
+        let is_slashable = builder.beacon_is_slashable_attestation_data(&data_1, &data_2);
+        builder.write(is_slashable);
+        let circuit = builder.mock_build();
+
+        for (data_1, data_2, expected) in [
+            // The same data is not slashable.
+            (
+                attestation_data(320, 9, 10, 3),
+                attestation_data(320, 9, 10, 3),
+                false,
+            ),
+            // A double vote for the target epoch 10.
+            (
+                attestation_data(320, 9, 10, 3),
+                attestation_data(321, 9, 10, 4),
+                true,
+            ),
+            // The first vote surrounds the second.
+            (
+                attestation_data(400, 8, 12, 3),
+                attestation_data(360, 9, 11, 4),
+                true,
+            ),
+            // The second vote surrounds the first, which is slashable in the other order.
+            (
+                attestation_data(360, 9, 11, 4),
+                attestation_data(400, 8, 12, 3),
+                false,
+            ),
+            // Consecutive votes are not slashable.
+            (
+                attestation_data(320, 9, 10, 3),
+                attestation_data(352, 10, 11, 4),
+                false,
+            ),
+        ] {
+            let mut input = circuit.input();
+            input.write::<BeaconAttestationDataVariable>(data_1);
+            input.write::<BeaconAttestationDataVariable>(data_2);
+            let (_witness, mut output) = circuit.mock_prove(&input);
+            assert_eq!(output.read::<BoolVariable>(), expected);
+        }
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_beacon_attestation_signing_root() {
+        let mut builder = DefaultBuilder::new();
+        let data = builder.read::<BeaconAttestationDataVariable>();
+        let domain = builder.read::<Bytes32Variable>();
+        let signing_root = builder.beacon_attestation_signing_root(&data, domain);
+        builder.write(signing_root);
+        let circuit = builder.mock_build();
+
+        let data = attestation_data(320, 9, 10, 3);
+        let domain = compute_attester_domain([4, 0, 0, 0], H256::repeat_byte(5));
+        let hash_pair = |left: &[u8], right: &[u8]| sha256(&[left, right].concat());
+        let uint64 = |value: u64| {
+            let mut chunk = [0u8; 32];
+            chunk[..8].copy_from_slice(&value.to_le_bytes());
+            chunk
+        };
+        let source = hash_pair(&uint64(data.source.epoch), data.source.root.as_bytes());
+        let target = hash_pair(&uint64(data.target.epoch), data.target.root.as_bytes());
+        let zero = [0u8; 32];
+        let data_root = hash_pair(
+            &hash_pair(
+                &hash_pair(&uint64(data.slot), &uint64(data.index)),
+                &hash_pair(data.beacon_block_root.as_bytes(), &source),
+            ),
+            &hash_pair(&hash_pair(&target, &zero), &hash_pair(&zero, &zero)),
+        );
+        let expected = hash_pair(&data_root, domain.as_bytes());
+
+        let mut input = circuit.input();
+        input.write::<BeaconAttestationDataVariable>(data);
+        input.write::<Bytes32Variable>(domain);
+        let (_witness, mut output) = circuit.mock_prove(&input);
+        assert_eq!(output.read::<Bytes32Variable>(), H256(expected));
+    }
+
+    #[test]
+    fn test_compute_committee_root() {
+        let mut builder = DefaultBuilder::new();
+        let indices = builder.read::<ArrayVariable<U64Variable, 6>>();
+        let committee_root = builder.beacon_committee_root(&indices);
+        builder.write(committee_root);
+        let circuit = builder.mock_build();
+
+        let indices = vec![3u64, 141, 59, 26, 535, 897];
+        let mut input = circuit.input();
+        input.write::<ArrayVariable<U64Variable, 6>>(indices.clone());
+        let (_witness, mut output) = circuit.mock_prove(&input);
+        assert_eq!(
+            output.read::<Bytes32Variable>(),
+            compute_committee_root(&indices)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_beacon_attestation_aggregate() {
+        let mut builder = DefaultBuilder::new();
+        let state_root = builder.read::<Bytes32Variable>();
+        let data = builder.read::<BeaconAttestationDataVariable>();
+        let committee_root = builder.read::<Bytes32Variable>();
+        let indices = builder.read::<ArrayVariable<U64Variable, N>>();
+        let validators = builder.read::<ArrayVariable<BeaconValidatorVariable, N>>();
+        let proofs = builder
+            .read::<ArrayVariable<ArrayVariable<Bytes32Variable, VALIDATOR_PROOF_DEPTH>, N>>();
+        let aggregation_bits = builder.read::<ArrayVariable<BoolVariable, N>>();
+        let domain = builder.read::<Bytes32Variable>();
+        let signature = builder.read::<BLSSignatureVariable>();
+        let aggregate = builder.beacon_attestation_aggregate(
+            state_root,
+            &data,
+            committee_root,
+            &indices,
+            &validators,
+            &proofs,
+            &aggregation_bits,
+            domain,
+            &signature,
+        );
+        builder.write(aggregate);
+        let circuit = builder.mock_build();
+
+        // The committee is the validators `0` to `3` with the public keys of `G`, `2G`, `3G` and
+        // `4G`, of which all but the second attest, so that the aggregate is `8G`.
+        let generator = Bls12381G1::generator();
+        let validators = (1..=N as u32)
+            .map(|k| {
+                let point = mul_native::<Bls12381G1>(&BigUint::from(k), &generator).unwrap();
+                BeaconValidator {
+                    pubkey: format!("0x{}", hex::encode(compress(&point))),
+                    withdrawal_credentials: format!("0x{}", hex::encode([k as u8; 32])),
+                    effective_balance: 32000000000,
+                    slashed: false,
+                    activation_eligibility_epoch: "0".to_string(),
+                    activation_epoch: "0".to_string(),
+                    exit_epoch: "18446744073709551615".to_string(),
+                    withdrawable_epoch: "18446744073709551615".to_string(),
+                }
+            })
+            .collect::<Vec<_>>();
+        let indices = (0..N as u64).collect::<Vec<_>>();
+        let expected_aggregate =
+            mul_native::<Bls12381G1>(&BigUint::from(8u32), &generator).unwrap();
+
+        // The validators share their path to the state root above the subtree of their four
+        // leaves, whose siblings are arbitrary.
+        let leaves = validators
+            .iter()
+            .map(|validator| validator.ssz_hash_tree_root().0)
+            .collect::<Vec<_>>();
+        let pairs = [
+            hash_pair(&leaves[0], &leaves[1]),
+            hash_pair(&leaves[2], &leaves[3]),
+        ];
+        let siblings = (2..VALIDATOR_PROOF_DEPTH)
+            .map(|level| [level as u8; 32])
+            .collect::<Vec<_>>();
+        let mut state_root = hash_pair(&pairs[0], &pairs[1]);
+        for (level, sibling) in (2..VALIDATOR_PROOF_DEPTH).zip(siblings.iter()) {
+            state_root = if (VALIDATOR_BASE_GINDEX >> level) & 1 == 1 {
+                hash_pair(sibling, &state_root)
+            } else {
+                hash_pair(&state_root, sibling)
+            };
+        }
+        let proofs = (0..N)
+            .map(|i| {
+                [leaves[i ^ 1], pairs[(i >> 1) ^ 1]]
+                    .iter()
+                    .chain(siblings.iter())
+                    .map(|node| H256(*node))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        let data = attestation_data(320, 9, 10, 3);
+        let domain = compute_attester_domain([4, 0, 0, 0], H256::repeat_byte(5));
+        let signing_root =
+            bytes32!("0x960d6f0698e8eb128415cabe8a4d517967fd0a316a1ee05ae46d63b43b367f25");
+
+        // The signature of the signing root by the secret key `8`, the sum of the secret keys of
+        // the attesters.
+        let signature = hex::decode(SIGNATURE).unwrap().try_into().unwrap();
+
+        let mut input = circuit.input();
+        input.write::<Bytes32Variable>(H256(state_root));
+        input.write::<BeaconAttestationDataVariable>(data);
+        input.write::<Bytes32Variable>(compute_committee_root(&indices));
+        input.write::<ArrayVariable<U64Variable, N>>(indices);
+        input.write::<ArrayVariable<BeaconValidatorVariable, N>>(validators);
+        input.write::<ArrayVariable<ArrayVariable<Bytes32Variable, VALIDATOR_PROOF_DEPTH>, N>>(
+            proofs,
+        );
+        input.write::<ArrayVariable<BoolVariable, N>>(vec![true, false, true, true]);
+        input.write::<Bytes32Variable>(domain);
+        input.write::<BLSSignatureVariable>(signature);
+        let (_witness, mut output) = circuit.mock_prove(&input);
+        let aggregate = output.read::<BeaconAttestationAggregateVariable>();
+        assert_eq!(aggregate.aggregate_pubkey, expected_aggregate.into());
+        assert_eq!(aggregate.signing_root, signing_root);
+    }
+}
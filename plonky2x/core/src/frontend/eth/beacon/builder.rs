@@ -2,14 +2,15 @@ use array_macro::array;
 use ethers::types::{H256, U256};
 
 use super::generators::{
-    BeaconAllWithdrawalsHint, BeaconBalanceBatchWitnessHint, BeaconBalanceGenerator,
-    BeaconBalanceWitnessHint, BeaconBalancesGenerator, BeaconBlockRootsHint, BeaconGraffitiHint,
+    BeaconAllWithdrawalsHint, BeaconBalanceBatchWitnessHint, BeaconBalanceByIndexHint,
+    BeaconBalanceWitnessHint, BeaconBalancesHint, BeaconBlockRootsHint, BeaconGraffitiHint,
     BeaconHeaderHint, BeaconHeadersFromOffsetRangeHint, BeaconHistoricalBlockHint,
     BeaconPartialBalancesHint, BeaconPartialValidatorsHint, BeaconValidatorBatchHint,
-    BeaconValidatorGenerator, BeaconValidatorSubtreeHint, BeaconValidatorSubtreePoseidonHint,
-    BeaconValidatorSubtreesHint, BeaconValidatorsHint, BeaconWithdrawalGenerator,
-    BeaconWithdrawalsGenerator, CompressedBeaconValidatorBatchHint, CLOSE_SLOT_BLOCK_ROOT_DEPTH,
-    FAR_SLOT_BLOCK_ROOT_DEPTH, FAR_SLOT_HISTORICAL_SUMMARY_DEPTH,
+    BeaconValidatorByIndexHint, BeaconValidatorByPubkeyHint, BeaconValidatorSubtreeHint,
+    BeaconValidatorSubtreePoseidonHint, BeaconValidatorSubtreesHint, BeaconValidatorsHint,
+    BeaconWithdrawalHint, BeaconWithdrawalsHint, CompressedBeaconValidatorBatchHint,
+    BALANCE_PROOF_DEPTH, CLOSE_SLOT_BLOCK_ROOT_DEPTH, FAR_SLOT_BLOCK_ROOT_DEPTH,
+    FAR_SLOT_HISTORICAL_SUMMARY_DEPTH, VALIDATOR_PROOF_DEPTH,
 };
 use super::vars::{
     BeaconBalancesVariable, BeaconHeaderVariable, BeaconValidatorVariable,
@@ -19,6 +20,7 @@ use super::vars::{
 use crate::backend::circuit::PlonkParameters;
 use crate::frontend::builder::CircuitBuilder;
 use crate::frontend::eth::vars::BLSPubkeyVariable;
+use crate::frontend::hint::synchronous::Async;
 use crate::frontend::uint::uint64::U64Variable;
 use crate::frontend::vars::{
     Bytes32Variable, CircuitVariable, EvmVariable, SSZVariable, VariableStream,
@@ -145,19 +147,25 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         validators: BeaconValidatorsVariable,
         index: U64Variable,
     ) -> BeaconValidatorVariable {
-        let generator =
-            BeaconValidatorGenerator::new_with_index_variable(self, validators.block_root, index);
-        self.add_simple_generator(generator.clone());
-        let validator_root = self.ssz_hash_tree_root(generator.validator);
+        let mut input_stream = VariableStream::new();
+        input_stream.write(&validators.block_root);
+        input_stream.write(&index);
+        let output_stream = self.async_hint(input_stream, BeaconValidatorByIndexHint {});
+
+        let validator = output_stream.read::<BeaconValidatorVariable>(self);
+        let _validator_idx = output_stream.read::<U64Variable>(self);
+        let proof =
+            output_stream.read::<ArrayVariable<Bytes32Variable, VALIDATOR_PROOF_DEPTH>>(self);
+        let validator_root = self.ssz_hash_tree_root(validator);
         let mut gindex = self.constant::<U64Variable>(VALIDATOR_BASE_GINDEX);
         gindex = self.add(gindex, index);
         self.ssz_verify_proof(
             validators.validators_root,
             validator_root,
-            &generator.proof,
+            proof.as_slice(),
             gindex,
         );
-        generator.validator
+        validator
     }
 
     /// Witness the first B validators from a given start index.
@@ -170,7 +178,7 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         input_stream.write(&balances.block_root);
         input_stream.write(&start_idx);
         let hint = BeaconValidatorBatchHint::<B> {};
-        let output_stream = self.hint(input_stream, hint);
+        let output_stream = self.async_hint(input_stream, Async(hint));
         output_stream.read::<ArrayVariable<BeaconValidatorVariable, B>>(self)
     }
 
@@ -184,7 +192,7 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         input_stream.write(&balances.block_root);
         input_stream.write(&start_idx);
         let hint = CompressedBeaconValidatorBatchHint::<B> {};
-        let output_stream = self.hint(input_stream, hint);
+        let output_stream = self.async_hint(input_stream, Async(hint));
         output_stream.read::<ArrayVariable<CompressedBeaconValidatorVariable, B>>(self)
     }
 
@@ -194,18 +202,25 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         validators: BeaconValidatorsVariable,
         index: u64,
     ) -> BeaconValidatorVariable {
-        let generator =
-            BeaconValidatorGenerator::new_with_index_const(self, validators.block_root, index);
-        self.add_simple_generator(generator.clone());
-        let validator_root = self.ssz_hash_tree_root(generator.validator);
+        let index_variable = self.constant::<U64Variable>(index);
+        let mut input_stream = VariableStream::new();
+        input_stream.write(&validators.block_root);
+        input_stream.write(&index_variable);
+        let output_stream = self.async_hint(input_stream, BeaconValidatorByIndexHint {});
+
+        let validator = output_stream.read::<BeaconValidatorVariable>(self);
+        let _validator_idx = output_stream.read::<U64Variable>(self);
+        let proof =
+            output_stream.read::<ArrayVariable<Bytes32Variable, VALIDATOR_PROOF_DEPTH>>(self);
+        let validator_root = self.ssz_hash_tree_root(validator);
         let gindex = VALIDATOR_BASE_GINDEX + index;
         self.ssz_verify_proof_const(
             validators.validators_root,
             validator_root,
-            &generator.proof,
+            proof.as_slice(),
             gindex,
         );
-        generator.validator
+        validator
     }
 
     /// Gets a validator from a given pubkey. Returns the validator index along with the validator
@@ -215,36 +230,39 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         validators: BeaconValidatorsVariable,
         pubkey: BLSPubkeyVariable,
     ) -> (U64Variable, BeaconValidatorVariable) {
-        let generator =
-            BeaconValidatorGenerator::new_with_pubkey_variable(self, validators.block_root, pubkey);
-        self.add_simple_generator(generator.clone());
-        let validator_root = self.ssz_hash_tree_root(generator.validator);
+        let mut input_stream = VariableStream::new();
+        input_stream.write(&validators.block_root);
+        input_stream.write(&pubkey);
+        let output_stream = self.async_hint(input_stream, BeaconValidatorByPubkeyHint {});
+
+        let validator = output_stream.read::<BeaconValidatorVariable>(self);
+        let validator_idx = output_stream.read::<U64Variable>(self);
+        let proof =
+            output_stream.read::<ArrayVariable<Bytes32Variable, VALIDATOR_PROOF_DEPTH>>(self);
+        let validator_root = self.ssz_hash_tree_root(validator);
         let mut gindex = self.constant::<U64Variable>(VALIDATOR_BASE_GINDEX);
-        gindex = self.add(gindex, generator.validator_idx);
+        gindex = self.add(gindex, validator_idx);
         self.ssz_verify_proof(
             validators.validators_root,
             validator_root,
-            &generator.proof,
+            proof.as_slice(),
             gindex,
         );
-        self.assert_is_equal(generator.validator.pubkey, pubkey);
-        (generator.validator_idx, generator.validator)
+        self.assert_is_equal(validator.pubkey, pubkey);
+        (validator_idx, validator)
     }
 
     /// Get the balances for a given block root.
     pub fn beacon_get_balances(&mut self, block_root: Bytes32Variable) -> BeaconBalancesVariable {
-        let generator =
-            BeaconBalancesGenerator::new(self, self.beacon_client.clone().unwrap(), block_root);
-        self.add_simple_generator(generator.clone());
-        self.ssz_verify_proof_const(
-            block_root,
-            generator.balances_root,
-            &generator.proof,
-            BALANCES_ROOT_GINDEX,
-        );
+        let mut input_stream = VariableStream::new();
+        input_stream.write(&block_root);
+        let output_stream = self.async_hint(input_stream, Async(BeaconBalancesHint));
+        let balances_root = output_stream.read_unsafe::<Bytes32Variable>(self);
+        let proof = array![_ => output_stream.read_unsafe::<Bytes32Variable>(self); 8];
+        self.ssz_verify_proof_const(block_root, balances_root, &proof, BALANCES_ROOT_GINDEX);
         BeaconBalancesVariable {
             block_root,
-            root: generator.balances_root,
+            root: balances_root,
         }
     }
 
@@ -362,7 +380,7 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         input_stream.write(&index);
 
         let hint = BeaconBalanceWitnessHint {};
-        let output_stream = self.hint(input_stream, hint);
+        let output_stream = self.async_hint(input_stream, Async(hint));
         output_stream.read::<U64Variable>(self)
     }
 
@@ -376,7 +394,7 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         input_stream.write(&balances.block_root);
         input_stream.write(&start_idx);
         let hint = BeaconBalanceBatchWitnessHint::<B> {};
-        let output_stream = self.hint(input_stream, hint);
+        let output_stream = self.async_hint(input_stream, Async(hint));
         output_stream.read::<ArrayVariable<U64Variable, B>>(self)
     }
 
@@ -386,40 +404,41 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         balances: BeaconBalancesVariable,
         index: U64Variable,
     ) -> U64Variable {
-        let generator =
-            BeaconBalanceGenerator::new_with_index_variable(self, balances.block_root, index);
-        self.add_simple_generator(generator.clone());
+        let mut input_stream = VariableStream::new();
+        input_stream.write(&balances.block_root);
+        input_stream.write(&index);
+        let output_stream = self.async_hint(input_stream, BeaconBalanceByIndexHint {});
+
+        let balance = output_stream.read::<U64Variable>(self);
+        let balance_leaf = output_stream.read::<Bytes32Variable>(self);
+        let proof = output_stream.read::<ArrayVariable<Bytes32Variable, BALANCE_PROOF_DEPTH>>(self);
+        let _gindex = output_stream.read::<U64Variable>(self);
         let mut gindex = self.constant::<U64Variable>(BALANCE_BASE_GINDEX);
         let four = self.constant::<U64Variable>(4);
 
         let offset = self.div(index, four);
         gindex = self.add(gindex, offset);
 
-        self.ssz_verify_proof(
-            balances.root,
-            generator.balance_leaf,
-            &generator.proof,
-            gindex,
-        );
+        self.ssz_verify_proof(balances.root, balance_leaf, proof.as_slice(), gindex);
 
         let index = self.rem(index, four);
         let bits = self.to_le_bits(index);
         let first_half: BytesVariable<16> =
-            BytesVariable::<16>(generator.balance_leaf.0 .0[..16].try_into().unwrap());
+            BytesVariable::<16>(balance_leaf.0 .0[..16].try_into().unwrap());
         let second_half: BytesVariable<16> =
-            BytesVariable::<16>(generator.balance_leaf.0 .0[16..].try_into().unwrap());
+            BytesVariable::<16>(balance_leaf.0 .0[16..].try_into().unwrap());
         let half = self.select(bits[1], second_half, first_half);
         let first_quarter: BytesVariable<8> = BytesVariable::<8>(half.0[..8].try_into().unwrap());
         let second_quarter: BytesVariable<8> = BytesVariable::<8>(half.0[8..].try_into().unwrap());
         let quarter = self.select(bits[0], second_quarter, first_quarter);
 
-        let balance_bytes = generator.balance.encode(self);
+        let balance_bytes = balance.encode(self);
         let quarter_bytes = quarter.0;
         for i in 0..8 {
             self.assert_is_equal(balance_bytes[7 - i], quarter_bytes[i]);
         }
 
-        generator.balance
+        balance
     }
 
     /// Get the withdrawals for a given block root.
@@ -427,18 +446,20 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         &mut self,
         block_root: Bytes32Variable,
     ) -> BeaconWithdrawalsVariable {
-        let generator =
-            BeaconWithdrawalsGenerator::new(self, self.beacon_client.clone().unwrap(), block_root);
-        self.add_simple_generator(generator.clone());
+        let mut input_stream = VariableStream::new();
+        input_stream.write(&block_root);
+        let output_stream = self.async_hint(input_stream, Async(BeaconWithdrawalsHint));
+        let withdrawals_root = output_stream.read::<Bytes32Variable>(self);
+        let proof = array![_ => output_stream.read::<Bytes32Variable>(self); 11];
         self.ssz_verify_proof_const(
             block_root,
-            generator.withdrawals_root,
-            &generator.proof,
+            withdrawals_root,
+            &proof,
             WITHDRAWALS_ROOT_GINDEX,
         );
         BeaconWithdrawalsVariable {
             block_root,
-            withdrawals_root: generator.withdrawals_root,
+            withdrawals_root,
         }
     }
 
@@ -483,18 +504,18 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         withdrawals: BeaconWithdrawalsVariable,
         idx: U64Variable,
     ) -> BeaconWithdrawalVariable {
-        let generator = BeaconWithdrawalGenerator::new(
-            self,
-            self.beacon_client.clone().unwrap(),
-            withdrawals,
-            idx,
-        );
-        self.add_simple_generator(generator.clone());
+        let mut input_stream = VariableStream::new();
+        input_stream.write(&withdrawals.block_root);
+        input_stream.write(&idx);
+        let output_stream = self.async_hint(input_stream, Async(BeaconWithdrawalHint));
+        let _withdrawal_root = output_stream.read::<Bytes32Variable>(self);
+        let withdrawal = output_stream.read::<BeaconWithdrawalVariable>(self);
+        let proof = array![_ => output_stream.read::<Bytes32Variable>(self); 5];
         let mut gindex = self.constant::<U64Variable>(WITHDRAWAL_BASE_GINDEX);
         gindex = self.add(gindex, idx);
-        let leaf = self.ssz_hash_tree_root(generator.withdrawal);
-        self.ssz_verify_proof(withdrawals.withdrawals_root, leaf, &generator.proof, gindex);
-        generator.withdrawal
+        let leaf = self.ssz_hash_tree_root(withdrawal);
+        self.ssz_verify_proof(withdrawals.withdrawals_root, leaf, &proof, gindex);
+        withdrawal
     }
 
     /// Get block header from block root.
@@ -595,7 +616,7 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
     ) -> ArrayVariable<Bytes32Variable, SLOTS_PER_HISTORICAL_ROOT> {
         let mut input = VariableStream::new();
         input.write(&block_root);
-        let output = self.hint(input, BeaconBlockRootsHint {});
+        let output = self.async_hint(input, Async(BeaconBlockRootsHint {}));
         let block_roots_root = output.read::<Bytes32Variable>(self);
         let proof = output.read::<ArrayVariable<Bytes32Variable, BLOCK_ROOTS_PROOF_DEPTH>>(self);
         let block_roots =
@@ -614,7 +635,7 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
     pub fn beacon_get_graffiti(&mut self, block_root: Bytes32Variable) -> Bytes32Variable {
         let mut input = VariableStream::new();
         input.write(&block_root);
-        let output = self.hint(input, BeaconGraffitiHint {});
+        let output = self.async_hint(input, Async(BeaconGraffitiHint {}));
         let graffiti = output.read::<Bytes32Variable>(self);
         let proof = output.read::<ArrayVariable<Bytes32Variable, GRAFFITI_PROOF_DEPTH>>(self);
         self.ssz_verify_proof_const(
@@ -636,12 +657,18 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         input.write(&end_block_root);
         input.write(&start_offset);
         input.write(&end_offset);
-        let output = self.hint(input, BeaconHeadersFromOffsetRangeHint::<B> {});
+        let output = self.async_hint(input, Async(BeaconHeadersFromOffsetRangeHint::<B> {}));
         output.read::<ArrayVariable<Bytes32Variable, B>>(self)
     }
 
-    /// Verify a simple serialize (ssz) merkle proof with a dynamic index.
-    #[allow(unused_variables)]
+    /// Verifies an SSZ merkle proof of `leaf` at the generalized index `gindex` in the tree with
+    /// the given `root`, where `gindex` may depend on the witness.
+    ///
+    /// `branch` lists the siblings from the leaf up, and the path is given by the bits of
+    /// `gindex` below its leading one, which are derived in the circuit. `gindex` is constrained to
+    /// have depth `branch.len()`, i.e. to be in `[2^depth, 2^(depth + 1))`, so that the proof
+    /// cannot be for another node of the tree. Each level is hashed once with `sha256_pair`, after
+    /// ordering the node and its sibling by the corresponding bit of `gindex`.
     pub fn ssz_verify_proof(
         &mut self,
         root: Bytes32Variable,
@@ -649,8 +676,23 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         branch: &[Bytes32Variable],
         gindex: U64Variable,
     ) {
-        let expected_root = self.ssz_restore_merkle_root(leaf, branch, gindex);
-        self.assert_is_equal(root, expected_root);
+        let depth = branch.len();
+        assert!(depth < 64, "ssz proofs must be shallower than 64 levels");
+        let bits = self.to_le_bits(gindex);
+        let (true_v, false_v) = (self._true(), self._false());
+        self.assert_is_equal(bits[depth], true_v);
+        for bit in bits[depth + 1..].iter() {
+            self.assert_is_equal(*bit, false_v);
+        }
+
+        let mut hash = leaf;
+        for (bit, sibling) in bits.iter().zip(branch.iter()) {
+            // A set bit means that the current node is the right child.
+            let left = self.select(*bit, *sibling, hash);
+            let right = self.select(*bit, hash, *sibling);
+            hash = self.sha256_pair(left, right);
+        }
+        self.assert_is_equal(hash, root);
     }
 
     /// Verify a simple serialize (ssz) merkle proof with a constant index.
@@ -666,6 +708,29 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         self.assert_is_equal(root, expected_root);
     }
 
+    /// Verifies an SSZ merkle proof with a dynamic index. An alias of `ssz_verify_proof`.
+    pub fn verify_ssz_proof(
+        &mut self,
+        root: Bytes32Variable,
+        leaf: Bytes32Variable,
+        branch: &[Bytes32Variable],
+        gindex: U64Variable,
+    ) {
+        self.ssz_verify_proof(root, leaf, branch, gindex);
+    }
+
+    /// Verifies a binary sha256 merkle proof of `leaf` at the generalized index `index` in the tree
+    /// with the given `root`, as in the beacon chain. See `ssz_verify_proof`.
+    pub fn verify_merkle_proof_sha256(
+        &mut self,
+        leaf: Bytes32Variable,
+        branch: &[Bytes32Variable],
+        index: U64Variable,
+        root: Bytes32Variable,
+    ) {
+        self.ssz_verify_proof(root, leaf, branch, index);
+    }
+
     /// Computes the expected merkle root given a leaf, branch, and dynamic index.
     pub fn ssz_restore_merkle_root(
         &mut self,
@@ -736,6 +801,7 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
 pub(crate) mod tests {
     use std::env;
 
+    use ethers::types::H256;
     use log::debug;
 
     use crate::backend::circuit::DefaultParameters;
@@ -743,12 +809,89 @@ pub(crate) mod tests {
     use crate::frontend::eth::vars::BLSPubkeyVariable;
     use crate::frontend::uint::uint64::U64Variable;
     use crate::frontend::vars::Bytes32Variable;
+    use crate::prelude::ArrayVariable;
     use crate::utils::eth::beacon::BeaconClient;
+    use crate::utils::hash::sha256;
     use crate::utils::{bytes, bytes32};
 
     type L = DefaultParameters;
     const D: usize = 2;
 
+    /// Returns the layers of the sha256 tree of depth `depth` whose leaf `i` is
+    /// `H256::repeat_byte(i)`, from the leaves up to the root.
+    fn sha256_tree_layers(depth: usize) -> Vec<Vec<H256>> {
+        let mut layers = vec![(0..1u8 << depth).map(H256::repeat_byte).collect::<Vec<_>>()];
+        while layers.last().unwrap().len() > 1 {
+            let layer = layers
+                .last()
+                .unwrap()
+                .chunks_exact(2)
+                .map(|pair| H256::from(sha256(&[pair[0].0, pair[1].0].concat())))
+                .collect::<Vec<_>>();
+            layers.push(layer);
+        }
+        layers
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_verify_merkle_proof_sha256() {
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let leaf = builder.read::<Bytes32Variable>();
+        let branch = builder.read::<ArrayVariable<Bytes32Variable, 3>>();
+        let index = builder.read::<U64Variable>();
+        let root = builder.read::<Bytes32Variable>();
+        builder.verify_merkle_proof_sha256(leaf, branch.as_slice(), index, root);
+        let circuit = builder.build();
+
+        // A tree of 8 leaves, whose leaf `i` has the generalized index `8 + i`.
+        let layers = sha256_tree_layers(3);
+        let root = layers[3][0];
+
+        let i = 6usize;
+        let branch = (0..3)
+            .map(|level| layers[level][(i >> level) ^ 1])
+            .collect::<Vec<_>>();
+        let mut input = circuit.input();
+        input.write::<Bytes32Variable>(layers[0][i]);
+        input.write::<ArrayVariable<Bytes32Variable, 3>>(branch);
+        input.write::<U64Variable>(8 + i as u64);
+        input.write::<Bytes32Variable>(root);
+
+        let (proof, output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_ssz_verify_proof() {
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let root = builder.read::<Bytes32Variable>();
+        let leaf = builder.read::<Bytes32Variable>();
+        let branch = builder.read::<ArrayVariable<Bytes32Variable, 4>>();
+        let index = builder.read::<U64Variable>();
+        // The generalized index of the leaf `index` of a tree of depth 4.
+        let base = builder.constant::<U64Variable>(16);
+        let gindex = builder.add(base, index);
+        builder.ssz_verify_proof(root, leaf, branch.as_slice(), gindex);
+        let circuit = builder.mock_build();
+
+        let layers = sha256_tree_layers(4);
+        let root = layers[4][0];
+
+        for i in [0usize, 5, 15] {
+            let branch = (0..4)
+                .map(|level| layers[level][(i >> level) ^ 1])
+                .collect::<Vec<_>>();
+            let mut input = circuit.input();
+            input.write::<Bytes32Variable>(root);
+            input.write::<Bytes32Variable>(layers[0][i]);
+            input.write::<ArrayVariable<Bytes32Variable, 4>>(branch);
+            input.write::<U64Variable>(i as u64);
+            circuit.mock_prove(&input);
+        }
+    }
+
     #[test]
     #[cfg_attr(feature = "ci", ignore)]
     fn test_beacon_get_partial_validators() {
@@ -1,23 +1,107 @@
 use core::marker::PhantomData;
 use std::env;
 
+use anyhow::{ensure, Context, Result};
 use array_macro::array;
+use async_trait::async_trait;
 use plonky2::iop::generator::{GeneratedValues, SimpleGenerator};
 use plonky2::iop::target::Target;
 use plonky2::iop::witness::PartitionWitness;
 use plonky2::plonk::circuit_data::CommonCircuitData;
 use plonky2::util::serialization::{Buffer, IoResult, Read, Write};
+use serde::{Deserialize, Serialize};
 
 use crate::backend::circuit::PlonkParameters;
 use crate::frontend::builder::CircuitBuilder;
 use crate::frontend::eth::vars::BLSPubkeyVariable;
+use crate::frontend::hint::asynchronous::hint::AsyncHint;
 use crate::frontend::uint::uint64::U64Variable;
-use crate::frontend::vars::{Bytes32Variable, CircuitVariable};
-use crate::utils::eth::beacon::BeaconClient;
+use crate::frontend::vars::{ArrayVariable, Bytes32Variable, CircuitVariable, ValueStream};
+use crate::utils::eth::beacon::{BeaconClient, GetBeaconBalance};
 use crate::utils::eth::BLSPubkey;
 use crate::utils::{bytes32, hex};
 
-const DEPTH: usize = 39;
+/// The depth of the proof of a balance leaf from the balances root.
+pub const BALANCE_PROOF_DEPTH: usize = 39;
+
+/// Writes the balance, the leaf containing it, its proof from the balances root, and the gindex
+/// of the leaf.
+fn write_balance<L: PlonkParameters<D>, const D: usize>(
+    output_stream: &mut ValueStream<L, D>,
+    result: GetBeaconBalance,
+) -> Result<()> {
+    ensure!(
+        result.proof.len() >= BALANCE_PROOF_DEPTH,
+        "the proof of the balance has {} nodes, expected {}",
+        result.proof.len(),
+        BALANCE_PROOF_DEPTH
+    );
+    let gindex = result
+        .gindex
+        .to_string()
+        .parse::<u64>()
+        .context("the gindex of the balance does not fit in a u64")?;
+    output_stream.write_value::<U64Variable>(result.balance);
+    output_stream.write_value::<Bytes32Variable>(bytes32!(result.balance_leaf));
+    output_stream.write_value::<ArrayVariable<Bytes32Variable, BALANCE_PROOF_DEPTH>>(
+        result.proof[..BALANCE_PROOF_DEPTH]
+            .iter()
+            .map(|x| bytes32!(*x))
+            .collect(),
+    );
+    output_stream.write_value::<U64Variable>(gindex);
+    Ok(())
+}
+
+/// Input: (block_root: bytes32, validator_idx: u64)
+/// Output: (balance: u64, balance_leaf: bytes32, proof: [bytes32; 39], gindex: u64)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BeaconBalanceByIndexHint {}
+
+#[async_trait]
+impl<L: PlonkParameters<D>, const D: usize> AsyncHint<L, D> for BeaconBalanceByIndexHint {
+    async fn try_hint_fn(&self, mut input_stream: ValueStream<L, D>) -> Result<ValueStream<L, D>> {
+        let block_root = input_stream.read_value::<Bytes32Variable>();
+        let validator_idx = input_stream.read_value::<U64Variable>();
+
+        let client = BeaconClient::new(env::var("CONSENSUS_RPC_URL")?);
+        let result = client
+            .get_validator_balance_v2_async(hex!(block_root.as_bytes()).to_string(), validator_idx)
+            .await
+            .with_context(|| format!("failed to get the balance of validator {}", validator_idx))?;
+
+        let mut output_stream = ValueStream::new();
+        write_balance(&mut output_stream, result)?;
+        Ok(output_stream)
+    }
+}
+
+/// Input: (block_root: bytes32, pubkey: BLSPubkey)
+/// Output: (balance: u64, balance_leaf: bytes32, proof: [bytes32; 39], gindex: u64)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BeaconBalanceByPubkeyHint {}
+
+#[async_trait]
+impl<L: PlonkParameters<D>, const D: usize> AsyncHint<L, D> for BeaconBalanceByPubkeyHint {
+    async fn try_hint_fn(&self, mut input_stream: ValueStream<L, D>) -> Result<ValueStream<L, D>> {
+        let block_root = input_stream.read_value::<Bytes32Variable>();
+        let pubkey = input_stream.read_value::<BLSPubkeyVariable>();
+
+        let client = BeaconClient::new(env::var("CONSENSUS_RPC_URL")?);
+        let pubkey = hex!(pubkey);
+        let result = client
+            .get_validator_balance_by_pubkey_v2_async(
+                hex!(block_root.as_bytes()).to_string(),
+                pubkey.clone(),
+            )
+            .await
+            .with_context(|| format!("failed to get the balance of validator {}", pubkey))?;
+
+        let mut output_stream = ValueStream::new();
+        write_balance(&mut output_stream, result)?;
+        Ok(output_stream)
+    }
+}
 
 #[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone)]
@@ -28,6 +112,9 @@ pub enum BeaconBalanceInput {
     PubkeyVariable(BLSPubkeyVariable),
 }
 
+#[deprecated(
+    note = "use `BeaconBalanceByIndexHint` or `BeaconBalanceByPubkeyHint`, which the hint tape records"
+)]
 #[derive(Debug, Clone)]
 pub struct BeaconBalanceGenerator<L: PlonkParameters<D>, const D: usize> {
     client: BeaconClient,
@@ -35,11 +122,12 @@ pub struct BeaconBalanceGenerator<L: PlonkParameters<D>, const D: usize> {
     input: BeaconBalanceInput,
     pub balance: U64Variable,
     pub balance_leaf: Bytes32Variable,
-    pub proof: [Bytes32Variable; DEPTH],
+    pub proof: [Bytes32Variable; BALANCE_PROOF_DEPTH],
     pub gindex: U64Variable,
     _phantom: PhantomData<L>,
 }
 
+#[allow(deprecated)]
 impl<L: PlonkParameters<D>, const D: usize> BeaconBalanceGenerator<L, D> {
     pub fn new_with_index_const(
         builder: &mut CircuitBuilder<L, D>,
@@ -52,7 +140,7 @@ impl<L: PlonkParameters<D>, const D: usize> BeaconBalanceGenerator<L, D> {
             input: BeaconBalanceInput::IndexConst(validator_idx),
             balance: builder.init_unsafe::<U64Variable>(),
             balance_leaf: builder.init_unsafe::<Bytes32Variable>(),
-            proof: array![_ => builder.init_unsafe::<Bytes32Variable>(); DEPTH],
+            proof: array![_ => builder.init_unsafe::<Bytes32Variable>(); BALANCE_PROOF_DEPTH],
             gindex: builder.init::<U64Variable>(),
             _phantom: PhantomData,
         }
@@ -69,7 +157,7 @@ impl<L: PlonkParameters<D>, const D: usize> BeaconBalanceGenerator<L, D> {
             input: BeaconBalanceInput::IndexVariable(validator_idx),
             balance: builder.init::<U64Variable>(),
             balance_leaf: builder.init::<Bytes32Variable>(),
-            proof: array![_ => builder.init::<Bytes32Variable>(); DEPTH],
+            proof: array![_ => builder.init::<Bytes32Variable>(); BALANCE_PROOF_DEPTH],
             gindex: builder.init::<U64Variable>(),
             _phantom: PhantomData,
         }
@@ -86,7 +174,7 @@ impl<L: PlonkParameters<D>, const D: usize> BeaconBalanceGenerator<L, D> {
             input: BeaconBalanceInput::PubkeyConst(pubkey),
             balance: builder.init::<U64Variable>(),
             balance_leaf: builder.init::<Bytes32Variable>(),
-            proof: array![_ => builder.init::<Bytes32Variable>(); DEPTH],
+            proof: array![_ => builder.init::<Bytes32Variable>(); BALANCE_PROOF_DEPTH],
             gindex: builder.init::<U64Variable>(),
             _phantom: PhantomData,
         }
@@ -103,7 +191,7 @@ impl<L: PlonkParameters<D>, const D: usize> BeaconBalanceGenerator<L, D> {
             input: BeaconBalanceInput::PubkeyVariable(pubkey),
             balance: builder.init::<U64Variable>(),
             balance_leaf: builder.init::<Bytes32Variable>(),
-            proof: array![_ => builder.init::<Bytes32Variable>(); DEPTH],
+            proof: array![_ => builder.init::<Bytes32Variable>(); BALANCE_PROOF_DEPTH],
             gindex: builder.init::<U64Variable>(),
             _phantom: PhantomData,
         }
@@ -114,6 +202,7 @@ impl<L: PlonkParameters<D>, const D: usize> BeaconBalanceGenerator<L, D> {
     }
 }
 
+#[allow(deprecated)]
 impl<L: PlonkParameters<D>, const D: usize> SimpleGenerator<L::Field, D>
     for BeaconBalanceGenerator<L, D>
 {
@@ -170,7 +259,7 @@ impl<L: PlonkParameters<D>, const D: usize> SimpleGenerator<L::Field, D>
         self.balance.set(out_buffer, result.balance);
         self.balance_leaf
             .set(out_buffer, bytes32!(result.balance_leaf));
-        for i in 0..DEPTH {
+        for i in 0..BALANCE_PROOF_DEPTH {
             self.proof[i].set(out_buffer, bytes32!(result.proof[i]));
         }
         self.gindex.set(
@@ -206,7 +295,7 @@ impl<L: PlonkParameters<D>, const D: usize> SimpleGenerator<L::Field, D>
         }
         dst.write_target_vec(&self.balance.targets())?;
         dst.write_target_vec(&self.balance_leaf.targets())?;
-        for i in 0..DEPTH {
+        for i in 0..BALANCE_PROOF_DEPTH {
             dst.write_target_vec(&self.proof[i].targets())?;
         }
         dst.write_target_vec(&self.gindex.targets())?;
@@ -239,7 +328,7 @@ impl<L: PlonkParameters<D>, const D: usize> SimpleGenerator<L::Field, D>
         let balance = U64Variable::from_targets(&src.read_target_vec()?);
         let balance_leaf = Bytes32Variable::from_targets(&src.read_target_vec()?);
         let mut proof = Vec::new();
-        for i in 0..DEPTH {
+        for i in 0..BALANCE_PROOF_DEPTH {
             proof.push(Bytes32Variable::from_targets(&src.read_target_vec()?));
         }
         let gindex = U64Variable::from_targets(&src.read_target_vec()?);
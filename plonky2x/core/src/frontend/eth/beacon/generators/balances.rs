@@ -7,15 +7,37 @@ use plonky2::iop::target::Target;
 use plonky2::iop::witness::PartitionWitness;
 use plonky2::plonk::circuit_data::CommonCircuitData;
 use plonky2::util::serialization::{Buffer, IoResult, Read, Write};
+use serde::{Deserialize, Serialize};
 
 use crate::backend::circuit::PlonkParameters;
 use crate::frontend::builder::CircuitBuilder;
-use crate::frontend::vars::{Bytes32Variable, CircuitVariable};
+use crate::frontend::hint::simple::hint::Hint;
+use crate::frontend::vars::{ArrayVariable, Bytes32Variable, CircuitVariable, ValueStream};
 use crate::utils::eth::beacon::BeaconClient;
 use crate::utils::{bytes32, hex};
 
 const DEPTH: usize = 8;
 
+/// Input: (block_root: bytes32)
+/// Output: (balances_root: bytes32, proof: [bytes32; 8])
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BeaconBalancesHint;
+
+impl<L: PlonkParameters<D>, const D: usize> Hint<L, D> for BeaconBalancesHint {
+    fn hint(&self, input_stream: &mut ValueStream<L, D>, output_stream: &mut ValueStream<L, D>) {
+        let client = BeaconClient::new(env::var("CONSENSUS_RPC_URL").unwrap());
+        let block_root = input_stream.read_value::<Bytes32Variable>();
+        let result = client
+            .get_balances_root(hex!(block_root.as_bytes()).to_string())
+            .expect("failed to get balances root");
+        output_stream.write_value::<Bytes32Variable>(bytes32!(result.balances_root));
+        output_stream.write_value::<ArrayVariable<Bytes32Variable, DEPTH>>(
+            result.proof[..DEPTH].iter().map(|p| bytes32!(p)).collect(),
+        );
+    }
+}
+
+#[deprecated(note = "use `BeaconBalancesHint`, which the hint tape records")]
 #[derive(Debug, Clone)]
 pub struct BeaconBalancesGenerator<L: PlonkParameters<D>, const D: usize> {
     client: BeaconClient,
@@ -25,6 +47,7 @@ pub struct BeaconBalancesGenerator<L: PlonkParameters<D>, const D: usize> {
     _phantom: PhantomData<L>,
 }
 
+#[allow(deprecated)]
 impl<L: PlonkParameters<D>, const D: usize> BeaconBalancesGenerator<L, D> {
     pub fn new(
         builder: &mut CircuitBuilder<L, D>,
@@ -45,6 +68,7 @@ impl<L: PlonkParameters<D>, const D: usize> BeaconBalancesGenerator<L, D> {
     }
 }
 
+#[allow(deprecated)]
 impl<L: PlonkParameters<D>, const D: usize> SimpleGenerator<L::Field, D>
     for BeaconBalancesGenerator<L, D>
 {
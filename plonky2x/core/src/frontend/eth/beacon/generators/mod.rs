@@ -16,9 +16,13 @@ mod validators;
 mod withdrawal;
 mod withdrawals;
 pub use all_withdrawals::BeaconAllWithdrawalsHint;
+#[allow(deprecated)]
 pub use balance::BeaconBalanceGenerator;
+pub use balance::{BeaconBalanceByIndexHint, BeaconBalanceByPubkeyHint, BALANCE_PROOF_DEPTH};
 pub use balance_witness::{BeaconBalanceBatchWitnessHint, BeaconBalanceWitnessHint};
+#[allow(deprecated)]
 pub use balances::BeaconBalancesGenerator;
+pub use balances::BeaconBalancesHint;
 pub use block_roots::BeaconBlockRootsHint;
 pub use graffiti::BeaconGraffitiHint;
 pub use header::BeaconHeaderHint;
@@ -29,13 +33,23 @@ pub use historical::{
 };
 pub use partial_balances::BeaconPartialBalancesHint;
 pub use partial_validators::BeaconPartialValidatorsHint;
+#[allow(deprecated)]
 pub use validator::BeaconValidatorGenerator;
+pub use validator::{
+    BeaconValidatorByIndexHint, BeaconValidatorByPubkeyHint, VALIDATOR_PROOF_DEPTH,
+};
 pub use validator_subtree::{
     BeaconValidatorSubtreeHint, BeaconValidatorSubtreePoseidonHint, BeaconValidatorSubtreesHint,
 };
 pub use validator_witness::{
     BeaconValidatorBatchHint, BeaconValidatorHint, CompressedBeaconValidatorBatchHint,
 };
-pub use validators::{BeaconValidatorsGenerator, BeaconValidatorsHint};
+#[allow(deprecated)]
+pub use validators::BeaconValidatorsGenerator;
+pub use validators::BeaconValidatorsHint;
+#[allow(deprecated)]
 pub use withdrawal::BeaconWithdrawalGenerator;
+pub use withdrawal::BeaconWithdrawalHint;
+#[allow(deprecated)]
 pub use withdrawals::BeaconWithdrawalsGenerator;
+pub use withdrawals::BeaconWithdrawalsHint;
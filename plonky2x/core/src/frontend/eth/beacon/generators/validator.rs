@@ -1,22 +1,97 @@
 use core::marker::PhantomData;
 use std::env;
 
+use anyhow::{ensure, Context, Result};
+use async_trait::async_trait;
 use plonky2::iop::generator::{GeneratedValues, SimpleGenerator};
 use plonky2::iop::target::Target;
 use plonky2::iop::witness::PartitionWitness;
 use plonky2::plonk::circuit_data::CommonCircuitData;
 use plonky2::util::serialization::{Buffer, IoResult, Read, Write};
+use serde::{Deserialize, Serialize};
 
 use crate::backend::circuit::PlonkParameters;
 use crate::frontend::builder::CircuitBuilder;
 use crate::frontend::eth::beacon::vars::BeaconValidatorVariable;
 use crate::frontend::eth::vars::BLSPubkeyVariable;
+use crate::frontend::hint::asynchronous::hint::AsyncHint;
 use crate::frontend::uint::uint64::U64Variable;
-use crate::frontend::vars::{Bytes32Variable, CircuitVariable};
-use crate::utils::eth::beacon::BeaconClient;
+use crate::frontend::vars::{ArrayVariable, Bytes32Variable, CircuitVariable, ValueStream};
+use crate::utils::eth::beacon::{BeaconClient, GetBeaconValidator};
 use crate::utils::{bytes32, hex};
 
-const DEPTH: usize = 41;
+/// The depth of the proof of a validator from the validators root.
+pub const VALIDATOR_PROOF_DEPTH: usize = 41;
+
+/// Writes the validator, its index, and its proof from the validators root.
+fn write_validator<L: PlonkParameters<D>, const D: usize>(
+    output_stream: &mut ValueStream<L, D>,
+    result: GetBeaconValidator,
+) -> Result<()> {
+    ensure!(
+        result.proof.len() >= VALIDATOR_PROOF_DEPTH,
+        "the proof of validator {} has {} nodes, expected {}",
+        result.validator_idx,
+        result.proof.len(),
+        VALIDATOR_PROOF_DEPTH
+    );
+    output_stream.write_value::<BeaconValidatorVariable>(result.validator);
+    output_stream.write_value::<U64Variable>(result.validator_idx);
+    output_stream.write_value::<ArrayVariable<Bytes32Variable, VALIDATOR_PROOF_DEPTH>>(
+        result.proof[..VALIDATOR_PROOF_DEPTH]
+            .iter()
+            .map(|x| bytes32!(*x))
+            .collect(),
+    );
+    Ok(())
+}
+
+/// Input: (block_root: bytes32, validator_idx: u64)
+/// Output: (validator: BeaconValidator, validator_idx: u64, proof: [bytes32; 41])
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BeaconValidatorByIndexHint {}
+
+#[async_trait]
+impl<L: PlonkParameters<D>, const D: usize> AsyncHint<L, D> for BeaconValidatorByIndexHint {
+    async fn try_hint_fn(&self, mut input_stream: ValueStream<L, D>) -> Result<ValueStream<L, D>> {
+        let block_root = input_stream.read_value::<Bytes32Variable>();
+        let validator_idx = input_stream.read_value::<U64Variable>();
+
+        let client = BeaconClient::new(env::var("CONSENSUS_RPC_URL")?);
+        let result = client
+            .get_validator_async(hex!(block_root.as_bytes()).to_string(), validator_idx)
+            .await
+            .with_context(|| format!("failed to get validator {}", validator_idx))?;
+
+        let mut output_stream = ValueStream::new();
+        write_validator(&mut output_stream, result)?;
+        Ok(output_stream)
+    }
+}
+
+/// Input: (block_root: bytes32, pubkey: BLSPubkey)
+/// Output: (validator: BeaconValidator, validator_idx: u64, proof: [bytes32; 41])
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BeaconValidatorByPubkeyHint {}
+
+#[async_trait]
+impl<L: PlonkParameters<D>, const D: usize> AsyncHint<L, D> for BeaconValidatorByPubkeyHint {
+    async fn try_hint_fn(&self, mut input_stream: ValueStream<L, D>) -> Result<ValueStream<L, D>> {
+        let block_root = input_stream.read_value::<Bytes32Variable>();
+        let pubkey = input_stream.read_value::<BLSPubkeyVariable>();
+
+        let client = BeaconClient::new(env::var("CONSENSUS_RPC_URL")?);
+        let pubkey = hex!(pubkey);
+        let result = client
+            .get_validator_by_pubkey_async(hex!(block_root.as_bytes()).to_string(), pubkey.clone())
+            .await
+            .with_context(|| format!("failed to get validator {}", pubkey))?;
+
+        let mut output_stream = ValueStream::new();
+        write_validator(&mut output_stream, result)?;
+        Ok(output_stream)
+    }
+}
 
 #[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone)]
@@ -26,6 +101,9 @@ pub enum BeaconValidatorGeneratorInput {
     PubkeyVariable(BLSPubkeyVariable),
 }
 
+#[deprecated(
+    note = "use `BeaconValidatorByIndexHint` or `BeaconValidatorByPubkeyHint`, which the hint tape records"
+)]
 #[derive(Debug, Clone)]
 pub struct BeaconValidatorGenerator<L: PlonkParameters<D>, const D: usize> {
     client: BeaconClient,
@@ -37,6 +115,7 @@ pub struct BeaconValidatorGenerator<L: PlonkParameters<D>, const D: usize> {
     _phantom: PhantomData<L>,
 }
 
+#[allow(deprecated)]
 impl<L: PlonkParameters<D>, const D: usize> BeaconValidatorGenerator<L, D> {
     pub fn new_with_index_const(
         builder: &mut CircuitBuilder<L, D>,
@@ -49,8 +128,8 @@ impl<L: PlonkParameters<D>, const D: usize> BeaconValidatorGenerator<L, D> {
             input: BeaconValidatorGeneratorInput::IndexConst(validator_idx),
             validator: builder.init::<BeaconValidatorVariable>(),
             validator_idx: builder.init::<U64Variable>(),
-            // proof: array![_ => builder.init::<Bytes32Variable>(); DEPTH].to_vec(),
-            proof: (0..DEPTH)
+            // proof: array![_ => builder.init::<Bytes32Variable>(); VALIDATOR_PROOF_DEPTH].to_vec(),
+            proof: (0..VALIDATOR_PROOF_DEPTH)
                 .map(|_| builder.init::<Bytes32Variable>())
                 .collect::<Vec<_>>(),
             _phantom: PhantomData,
@@ -68,7 +147,7 @@ impl<L: PlonkParameters<D>, const D: usize> BeaconValidatorGenerator<L, D> {
             input: BeaconValidatorGeneratorInput::IndexVariable(validator_idx),
             validator: builder.init::<BeaconValidatorVariable>(),
             validator_idx: builder.init::<U64Variable>(),
-            proof: (0..DEPTH)
+            proof: (0..VALIDATOR_PROOF_DEPTH)
                 .map(|_| builder.init::<Bytes32Variable>())
                 .collect::<Vec<_>>(),
             _phantom: PhantomData,
@@ -86,7 +165,7 @@ impl<L: PlonkParameters<D>, const D: usize> BeaconValidatorGenerator<L, D> {
             input: BeaconValidatorGeneratorInput::PubkeyVariable(pubkey),
             validator: builder.init::<BeaconValidatorVariable>(),
             validator_idx: builder.init::<U64Variable>(),
-            proof: (0..DEPTH)
+            proof: (0..VALIDATOR_PROOF_DEPTH)
                 .map(|_| builder.init::<Bytes32Variable>())
                 .collect::<Vec<_>>(),
             _phantom: PhantomData,
@@ -98,6 +177,7 @@ impl<L: PlonkParameters<D>, const D: usize> BeaconValidatorGenerator<L, D> {
     }
 }
 
+#[allow(deprecated)]
 impl<L: PlonkParameters<D>, const D: usize> SimpleGenerator<L::Field, D>
     for BeaconValidatorGenerator<L, D>
 {
@@ -146,7 +226,7 @@ impl<L: PlonkParameters<D>, const D: usize> SimpleGenerator<L::Field, D>
         };
         self.validator.set(out_buffer, result.validator);
         self.validator_idx.set(out_buffer, result.validator_idx);
-        for i in 0..DEPTH {
+        for i in 0..VALIDATOR_PROOF_DEPTH {
             self.proof[i].set(out_buffer, bytes32!(result.proof[i]));
         }
     }
@@ -174,7 +254,7 @@ impl<L: PlonkParameters<D>, const D: usize> SimpleGenerator<L::Field, D>
         }
         dst.write_target_vec(&self.validator.targets())?;
         dst.write_target_vec(&self.validator_idx.targets())?;
-        for i in 0..DEPTH {
+        for i in 0..VALIDATOR_PROOF_DEPTH {
             dst.write_target_vec(&self.proof[i].targets())?;
         }
         Ok(())
@@ -201,7 +281,7 @@ impl<L: PlonkParameters<D>, const D: usize> SimpleGenerator<L::Field, D>
         };
         let validator = BeaconValidatorVariable::from_targets(&src.read_target_vec()?);
         let validator_idx = U64Variable::from_targets(&src.read_target_vec()?);
-        let proof = (0..DEPTH)
+        let proof = (0..VALIDATOR_PROOF_DEPTH)
             .map(|_| Bytes32Variable::from_targets(&src.read_target_vec().unwrap()))
             .collect::<Vec<_>>();
         let consensus_rpc = env::var("CONSENSUS_RPC_URL").unwrap();
@@ -56,6 +56,7 @@ impl<L: PlonkParameters<D>, const D: usize> AsyncHint<L, D> for BeaconValidators
     }
 }
 
+#[deprecated(note = "use `BeaconValidatorsHint`, which the hint tape records")]
 #[derive(Debug, Clone)]
 pub struct BeaconValidatorsGenerator<L: PlonkParameters<D>, const D: usize> {
     block_root: Bytes32Variable,
@@ -64,6 +65,7 @@ pub struct BeaconValidatorsGenerator<L: PlonkParameters<D>, const D: usize> {
     _phantom: PhantomData<L>,
 }
 
+#[allow(deprecated)]
 impl<L: PlonkParameters<D>, const D: usize> BeaconValidatorsGenerator<L, D> {
     pub fn new(builder: &mut CircuitBuilder<L, D>, block_root: Bytes32Variable) -> Self {
         Self {
@@ -81,6 +83,7 @@ impl<L: PlonkParameters<D>, const D: usize> BeaconValidatorsGenerator<L, D> {
     }
 }
 
+#[allow(deprecated)]
 impl<L: PlonkParameters<D>, const D: usize> SimpleGenerator<L::Field, D>
     for BeaconValidatorsGenerator<L, D>
 {
@@ -145,6 +148,7 @@ impl<L: PlonkParameters<D>, const D: usize> SimpleGenerator<L::Field, D>
 }
 
 #[cfg(test)]
+#[allow(deprecated)]
 pub(crate) mod tests {
 
     use plonky2::iop::witness::PartialWitness;
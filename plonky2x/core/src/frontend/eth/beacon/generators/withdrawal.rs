@@ -8,19 +8,51 @@ use plonky2::iop::target::Target;
 use plonky2::iop::witness::PartitionWitness;
 use plonky2::plonk::circuit_data::CommonCircuitData;
 use plonky2::util::serialization::{Buffer, IoResult, Read, Write};
+use serde::{Deserialize, Serialize};
 
 use crate::backend::circuit::PlonkParameters;
 use crate::frontend::builder::CircuitBuilder;
 use crate::frontend::eth::beacon::vars::{
     BeaconWithdrawalValue, BeaconWithdrawalVariable, BeaconWithdrawalsVariable,
 };
+use crate::frontend::hint::simple::hint::Hint;
 use crate::frontend::uint::uint64::U64Variable;
-use crate::frontend::vars::{Bytes32Variable, CircuitVariable};
+use crate::frontend::vars::{ArrayVariable, Bytes32Variable, CircuitVariable, ValueStream};
 use crate::utils::eth::beacon::BeaconClient;
 use crate::utils::{bytes32, hex};
 
 const DEPTH: usize = 5;
 
+/// Input: (block_root: bytes32, idx: u64)
+/// Output: (withdrawal_root: bytes32, withdrawal: BeaconWithdrawal, proof: [bytes32; 5])
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BeaconWithdrawalHint;
+
+impl<L: PlonkParameters<D>, const D: usize> Hint<L, D> for BeaconWithdrawalHint {
+    fn hint(&self, input_stream: &mut ValueStream<L, D>, output_stream: &mut ValueStream<L, D>) {
+        let client = BeaconClient::new(env::var("CONSENSUS_RPC_URL").unwrap());
+        let block_root = input_stream.read_value::<Bytes32Variable>();
+        let idx = input_stream.read_value::<U64Variable>();
+        let result = client
+            .get_withdrawal(hex!(block_root.as_bytes()).to_string(), idx)
+            .expect("failed to get withdrawal");
+
+        let withdrawal = BeaconWithdrawalValue {
+            index: result.withdrawal.index,
+            validator_index: result.withdrawal.validator_index,
+            address: result.withdrawal.address.parse::<Address>().unwrap(),
+            amount: U256::from_dec_str(result.withdrawal.amount.to_string().as_str()).unwrap(),
+        };
+
+        output_stream.write_value::<Bytes32Variable>(bytes32!(result.withdrawal_root));
+        output_stream.write_value::<BeaconWithdrawalVariable>(withdrawal);
+        output_stream.write_value::<ArrayVariable<Bytes32Variable, DEPTH>>(
+            result.proof[..DEPTH].iter().map(|p| bytes32!(p)).collect(),
+        );
+    }
+}
+
+#[deprecated(note = "use `BeaconWithdrawalHint`, which the hint tape records")]
 #[derive(Debug, Clone)]
 pub struct BeaconWithdrawalGenerator<L: PlonkParameters<D>, const D: usize> {
     client: BeaconClient,
@@ -32,6 +64,7 @@ pub struct BeaconWithdrawalGenerator<L: PlonkParameters<D>, const D: usize> {
     _phantom: PhantomData<L>,
 }
 
+#[allow(deprecated)]
 impl<L: PlonkParameters<D>, const D: usize> BeaconWithdrawalGenerator<L, D> {
     pub fn new(
         builder: &mut CircuitBuilder<L, D>,
@@ -55,6 +88,7 @@ impl<L: PlonkParameters<D>, const D: usize> BeaconWithdrawalGenerator<L, D> {
     }
 }
 
+#[allow(deprecated)]
 impl<L: PlonkParameters<D>, const D: usize> SimpleGenerator<L::Field, D>
     for BeaconWithdrawalGenerator<L, D>
 {
@@ -6,15 +6,37 @@ use plonky2::iop::target::Target;
 use plonky2::iop::witness::PartitionWitness;
 use plonky2::plonk::circuit_data::CommonCircuitData;
 use plonky2::util::serialization::{Buffer, IoResult, Read, Write};
+use serde::{Deserialize, Serialize};
 
 use crate::backend::circuit::PlonkParameters;
 use crate::frontend::builder::CircuitBuilder;
-use crate::frontend::vars::{Bytes32Variable, CircuitVariable};
+use crate::frontend::hint::simple::hint::Hint;
+use crate::frontend::vars::{ArrayVariable, Bytes32Variable, CircuitVariable, ValueStream};
 use crate::utils::eth::beacon::BeaconClient;
 use crate::utils::{bytes32, hex};
 
 const DEPTH: usize = 11;
 
+/// Input: (block_root: bytes32)
+/// Output: (withdrawals_root: bytes32, proof: [bytes32; 11])
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BeaconWithdrawalsHint;
+
+impl<L: PlonkParameters<D>, const D: usize> Hint<L, D> for BeaconWithdrawalsHint {
+    fn hint(&self, input_stream: &mut ValueStream<L, D>, output_stream: &mut ValueStream<L, D>) {
+        let client = BeaconClient::new(env::var("CONSENSUS_RPC_URL").unwrap());
+        let block_root = input_stream.read_value::<Bytes32Variable>();
+        let result = client
+            .get_withdrawals(hex!(block_root.as_bytes()).to_string())
+            .expect("failed to get withdrawals");
+        output_stream.write_value::<Bytes32Variable>(bytes32!(result.withdrawals_root));
+        output_stream.write_value::<ArrayVariable<Bytes32Variable, DEPTH>>(
+            result.proof[..DEPTH].iter().map(|p| bytes32!(p)).collect(),
+        );
+    }
+}
+
+#[deprecated(note = "use `BeaconWithdrawalsHint`, which the hint tape records")]
 #[derive(Debug, Clone)]
 pub struct BeaconWithdrawalsGenerator<L: PlonkParameters<D>, const D: usize> {
     client: BeaconClient,
@@ -24,6 +46,7 @@ pub struct BeaconWithdrawalsGenerator<L: PlonkParameters<D>, const D: usize> {
     _phantom: PhantomData<L>,
 }
 
+#[allow(deprecated)]
 impl<L: PlonkParameters<D>, const D: usize> BeaconWithdrawalsGenerator<L, D> {
     pub fn new(
         builder: &mut CircuitBuilder<L, D>,
@@ -46,6 +69,7 @@ impl<L: PlonkParameters<D>, const D: usize> BeaconWithdrawalsGenerator<L, D> {
     }
 }
 
+#[allow(deprecated)]
 impl<L: PlonkParameters<D>, const D: usize> SimpleGenerator<L::Field, D>
     for BeaconWithdrawalsGenerator<L, D>
 {
@@ -0,0 +1,274 @@
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::builder::CircuitBuilder;
+use crate::frontend::uint::uint64::U64Variable;
+use crate::frontend::vars::{ArrayVariable, Bytes32Variable};
+
+/// Beacon chain constant SLOTS_PER_HISTORICAL_ROOT.
+pub const SLOTS_PER_HISTORICAL_ROOT: u64 = 8192;
+
+/// The log2 of SLOTS_PER_HISTORICAL_ROOT.
+pub const SLOTS_PER_HISTORICAL_ROOT_LOG2: usize = 13;
+
+/// The log2 of HISTORICAL_ROOTS_LIMIT, which is also the limit of the historical summaries.
+pub const HISTORICAL_ROOTS_LIMIT_LOG2: usize = 24;
+
+/// The first slot of Capella, from which the historical summaries replace the historical roots.
+pub const CAPELLA_FORK_SLOT: u64 = 194048 * 32;
+
+/// The depth of the proof from stateRoot -> state.block_roots[i].
+pub const STATE_BLOCK_ROOT_PROOF_DEPTH: usize = 5 + SLOTS_PER_HISTORICAL_ROOT_LOG2;
+
+/// The depth of the proof from stateRoot -> state.historical_roots[i] or
+/// state.historical_summaries[i], including the length mix-in.
+pub const HISTORICAL_ACCUMULATOR_PROOF_DEPTH: usize = 5 + HISTORICAL_ROOTS_LIMIT_LOG2 + 1;
+
+/// The depth of the proof from the root of a historical batch or summary -> block_roots[i].
+pub const BATCH_BLOCK_ROOT_PROOF_DEPTH: usize = 1 + SLOTS_PER_HISTORICAL_ROOT_LOG2;
+
+/// The gindex for stateRoot -> state.block_roots[0].
+pub const STATE_BLOCK_ROOTS_BASE_GINDEX: u64 = 37 << SLOTS_PER_HISTORICAL_ROOT_LOG2;
+
+/// The gindex for stateRoot -> state.historical_roots[0].
+pub const HISTORICAL_ROOTS_BASE_GINDEX: u64 = 39 << (HISTORICAL_ROOTS_LIMIT_LOG2 + 1);
+
+/// The gindex for stateRoot -> state.historical_summaries[0].
+pub const HISTORICAL_SUMMARIES_BASE_GINDEX: u64 = 59 << (HISTORICAL_ROOTS_LIMIT_LOG2 + 1);
+
+/// The gindex for the root of a historical batch or summary -> block_roots[0].
+pub const BATCH_BLOCK_ROOTS_BASE_GINDEX: u64 = 2 << SLOTS_PER_HISTORICAL_ROOT_LOG2;
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Verifies a proof of the root of the block at `slot` against the block roots of a state at
+    /// `state_slot`, which holds the last SLOTS_PER_HISTORICAL_ROOT slots before `state_slot`.
+    pub fn beacon_verify_recent_block_root(
+        &mut self,
+        state_root: Bytes32Variable,
+        state_slot: U64Variable,
+        slot: U64Variable,
+        block_root: Bytes32Variable,
+        proof: &ArrayVariable<Bytes32Variable, STATE_BLOCK_ROOT_PROOF_DEPTH>,
+    ) {
+        let true_v = self._true();
+        let is_before_state = self.lt(slot, state_slot);
+        self.assert_is_equal(is_before_state, true_v);
+        let distance = self.sub(state_slot, slot);
+        let slots_per_historical_root = self.constant::<U64Variable>(SLOTS_PER_HISTORICAL_ROOT);
+        let is_recent = self.lte(distance, slots_per_historical_root);
+        self.assert_is_equal(is_recent, true_v);
+
+        let index = self.rem(slot, slots_per_historical_root);
+        let base = self.constant::<U64Variable>(STATE_BLOCK_ROOTS_BASE_GINDEX);
+        let gindex = self.add(base, index);
+        self.ssz_verify_proof(state_root, block_root, proof.as_slice(), gindex);
+    }
+
+    /// Verifies a proof of the root of the block at `slot`, from Capella on, through the
+    /// historical summary of its period in a state.
+    ///
+    /// `summary_proof` proves `summary_root`, the root of the historical summary, against the
+    /// state root, and `block_root_proof` proves `block_root` against `summary_root`.
+    pub fn beacon_verify_historical_summary_block_root(
+        &mut self,
+        state_root: Bytes32Variable,
+        slot: U64Variable,
+        block_root: Bytes32Variable,
+        summary_root: Bytes32Variable,
+        summary_proof: &ArrayVariable<Bytes32Variable, HISTORICAL_ACCUMULATOR_PROOF_DEPTH>,
+        block_root_proof: &ArrayVariable<Bytes32Variable, BATCH_BLOCK_ROOT_PROOF_DEPTH>,
+    ) {
+        let capella_slot = self.constant::<U64Variable>(CAPELLA_FORK_SLOT);
+        let is_after_capella = self.gte(slot, capella_slot);
+        let true_v = self._true();
+        self.assert_is_equal(is_after_capella, true_v);
+        let slots_since_capella = self.sub(slot, capella_slot);
+        self.beacon_verify_historical_accumulator_entry(
+            state_root,
+            HISTORICAL_SUMMARIES_BASE_GINDEX,
+            slots_since_capella,
+            summary_root,
+            summary_proof,
+        );
+        self.beacon_verify_batch_block_root(summary_root, slot, block_root, block_root_proof);
+    }
+
+    /// Verifies a proof of the root of the block at `slot`, before Capella, through the legacy
+    /// historical root of its period in a state.
+    ///
+    /// `batch_proof` proves `batch_root`, the root of the historical batch, against the state
+    /// root, and `block_root_proof` proves `block_root` against `batch_root`.
+    pub fn beacon_verify_historical_root_block_root(
+        &mut self,
+        state_root: Bytes32Variable,
+        slot: U64Variable,
+        block_root: Bytes32Variable,
+        batch_root: Bytes32Variable,
+        batch_proof: &ArrayVariable<Bytes32Variable, HISTORICAL_ACCUMULATOR_PROOF_DEPTH>,
+        block_root_proof: &ArrayVariable<Bytes32Variable, BATCH_BLOCK_ROOT_PROOF_DEPTH>,
+    ) {
+        let capella_slot = self.constant::<U64Variable>(CAPELLA_FORK_SLOT);
+        let is_before_capella = self.lt(slot, capella_slot);
+        let true_v = self._true();
+        self.assert_is_equal(is_before_capella, true_v);
+        self.beacon_verify_historical_accumulator_entry(
+            state_root,
+            HISTORICAL_ROOTS_BASE_GINDEX,
+            slot,
+            batch_root,
+            batch_proof,
+        );
+        self.beacon_verify_batch_block_root(batch_root, slot, block_root, block_root_proof);
+    }
+
+    /// Verifies a proof of the entry of a historical accumulator list for the period of the slot
+    /// `slot_offset`, counted from the first slot of the list.
+    fn beacon_verify_historical_accumulator_entry(
+        &mut self,
+        state_root: Bytes32Variable,
+        base_gindex: u64,
+        slot_offset: U64Variable,
+        entry_root: Bytes32Variable,
+        proof: &ArrayVariable<Bytes32Variable, HISTORICAL_ACCUMULATOR_PROOF_DEPTH>,
+    ) {
+        let slots_per_historical_root = self.constant::<U64Variable>(SLOTS_PER_HISTORICAL_ROOT);
+        let index = self.div(slot_offset, slots_per_historical_root);
+        self.beacon_assert_index_bits(index, HISTORICAL_ROOTS_LIMIT_LOG2);
+        let base = self.constant::<U64Variable>(base_gindex);
+        let gindex = self.add(base, index);
+        self.ssz_verify_proof(state_root, entry_root, proof.as_slice(), gindex);
+    }
+
+    /// Verifies a proof of the root of the block at `slot` against the root of the historical
+    /// batch or summary of its period.
+    fn beacon_verify_batch_block_root(
+        &mut self,
+        batch_root: Bytes32Variable,
+        slot: U64Variable,
+        block_root: Bytes32Variable,
+        proof: &ArrayVariable<Bytes32Variable, BATCH_BLOCK_ROOT_PROOF_DEPTH>,
+    ) {
+        let slots_per_historical_root = self.constant::<U64Variable>(SLOTS_PER_HISTORICAL_ROOT);
+        let index = self.rem(slot, slots_per_historical_root);
+        let base = self.constant::<U64Variable>(BATCH_BLOCK_ROOTS_BASE_GINDEX);
+        let gindex = self.add(base, index);
+        self.ssz_verify_proof(batch_root, block_root, proof.as_slice(), gindex);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ethers::types::H256;
+    use rand::Rng;
+
+    use super::*;
+    use crate::prelude::DefaultBuilder;
+    use crate::utils::hash::sha256;
+
+    /// Returns a random branch for `leaf` at `gindex` and the root that it proves.
+    fn random_proof(leaf: H256, gindex: u64, depth: usize) -> (Vec<H256>, H256) {
+        let mut rng = rand::thread_rng();
+        let branch = (0..depth).map(|_| H256(rng.gen())).collect::<Vec<_>>();
+        let mut node = leaf;
+        for (level, sibling) in branch.iter().enumerate() {
+            let data = if (gindex >> level) & 1 == 1 {
+                [sibling.as_bytes(), node.as_bytes()].concat()
+            } else {
+                [node.as_bytes(), sibling.as_bytes()].concat()
+            };
+            node = H256(sha256(&data));
+        }
+        (branch, node)
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_beacon_verify_historical_block_roots() {
+        type BatchProof = ArrayVariable<Bytes32Variable, BATCH_BLOCK_ROOT_PROOF_DEPTH>;
+        type AccumulatorProof = ArrayVariable<Bytes32Variable, HISTORICAL_ACCUMULATOR_PROOF_DEPTH>;
+
+        let mut builder = DefaultBuilder::new();
+        let state_slot = builder.read::<U64Variable>();
+        let recent_slot = builder.read::<U64Variable>();
+        let recent_root = builder.read::<Bytes32Variable>();
+        let recent_state_root = builder.read::<Bytes32Variable>();
+        let recent_proof =
+            builder.read::<ArrayVariable<Bytes32Variable, STATE_BLOCK_ROOT_PROOF_DEPTH>>();
+        builder.beacon_verify_recent_block_root(
+            recent_state_root,
+            state_slot,
+            recent_slot,
+            recent_root,
+            &recent_proof,
+        );
+        for is_summary in [true, false] {
+            let slot = builder.read::<U64Variable>();
+            let block_root = builder.read::<Bytes32Variable>();
+            let state_root = builder.read::<Bytes32Variable>();
+            let entry_root = builder.read::<Bytes32Variable>();
+            let entry_proof = builder.read::<AccumulatorProof>();
+            let block_root_proof = builder.read::<BatchProof>();
+            if is_summary {
+                builder.beacon_verify_historical_summary_block_root(
+                    state_root,
+                    slot,
+                    block_root,
+                    entry_root,
+                    &entry_proof,
+                    &block_root_proof,
+                );
+            } else {
+                builder.beacon_verify_historical_root_block_root(
+                    state_root,
+                    slot,
+                    block_root,
+                    entry_root,
+                    &entry_proof,
+                    &block_root_proof,
+                );
+            }
+        }
+        let circuit = builder.mock_build();
+
+        let mut input = circuit.input();
+        let state_slot = 7500000u64;
+        let recent_slot = state_slot - 100;
+        let recent_root = H256::repeat_byte(1);
+        let (recent_proof, recent_state_root) = random_proof(
+            recent_root,
+            STATE_BLOCK_ROOTS_BASE_GINDEX + recent_slot % SLOTS_PER_HISTORICAL_ROOT,
+            STATE_BLOCK_ROOT_PROOF_DEPTH,
+        );
+        input.write::<U64Variable>(state_slot);
+        input.write::<U64Variable>(recent_slot);
+        input.write::<Bytes32Variable>(recent_root);
+        input.write::<Bytes32Variable>(recent_state_root);
+        input.write::<ArrayVariable<Bytes32Variable, STATE_BLOCK_ROOT_PROOF_DEPTH>>(recent_proof);
+
+        for (slot, base_gindex, first_slot) in [
+            (
+                7000123u64,
+                HISTORICAL_SUMMARIES_BASE_GINDEX,
+                CAPELLA_FORK_SLOT,
+            ),
+            (4700013u64, HISTORICAL_ROOTS_BASE_GINDEX, 0),
+        ] {
+            let block_root = H256::repeat_byte(2);
+            let (block_root_proof, entry_root) = random_proof(
+                block_root,
+                BATCH_BLOCK_ROOTS_BASE_GINDEX + slot % SLOTS_PER_HISTORICAL_ROOT,
+                BATCH_BLOCK_ROOT_PROOF_DEPTH,
+            );
+            let (entry_proof, state_root) = random_proof(
+                entry_root,
+                base_gindex + (slot - first_slot) / SLOTS_PER_HISTORICAL_ROOT,
+                HISTORICAL_ACCUMULATOR_PROOF_DEPTH,
+            );
+            input.write::<U64Variable>(slot);
+            input.write::<Bytes32Variable>(block_root);
+            input.write::<Bytes32Variable>(state_root);
+            input.write::<Bytes32Variable>(entry_root);
+            input.write::<AccumulatorProof>(entry_proof);
+            input.write::<BatchProof>(block_root_proof);
+        }
+        circuit.mock_prove(&input);
+    }
+}
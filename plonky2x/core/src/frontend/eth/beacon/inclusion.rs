@@ -0,0 +1,306 @@
+use super::vars::{BeaconValidatorVariable, BeaconWithdrawalVariable};
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::builder::CircuitBuilder;
+use crate::frontend::uint::uint64::U64Variable;
+use crate::frontend::vars::{ArrayVariable, Bytes32Variable, EvmVariable, SSZVariable};
+
+/// The number of balances packed in a leaf of the balances list.
+pub const BALANCES_PER_LEAF: usize = 4;
+
+/// The log2 of the limit of the validators and balances lists.
+pub const VALIDATOR_REGISTRY_LIMIT_LOG2: usize = 40;
+
+/// The depth of the proof from stateRoot -> validators[i], including the length mix-in.
+pub const VALIDATOR_PROOF_DEPTH: usize = 5 + VALIDATOR_REGISTRY_LIMIT_LOG2 + 1;
+
+/// The depth of the proof from stateRoot -> the leaf of balances[i], including the length mix-in.
+pub const BALANCE_PROOF_DEPTH: usize = 5 + VALIDATOR_REGISTRY_LIMIT_LOG2 - 2 + 1;
+
+/// The gindex for stateRoot -> validators[0].
+pub const VALIDATOR_BASE_GINDEX: u64 = 43 << (VALIDATOR_REGISTRY_LIMIT_LOG2 + 1);
+
+/// The gindex for stateRoot -> the leaf of balances[0].
+pub const BALANCE_BASE_GINDEX: u64 = 44 << (VALIDATOR_REGISTRY_LIMIT_LOG2 - 2 + 1);
+
+/// The log2 of MAX_WITHDRAWALS_PER_PAYLOAD.
+pub const MAX_WITHDRAWALS_PER_PAYLOAD_LOG2: usize = 4;
+
+/// The depth of the proof from blockRoot -> withdrawalsRoot, through the body and the execution
+/// payload of Capella.
+pub const WITHDRAWALS_ROOT_PROOF_DEPTH: usize = 11;
+
+/// The depth of the proof from blockRoot -> withdrawals[i], including the length mix-in.
+pub const WITHDRAWAL_PROOF_DEPTH: usize =
+    WITHDRAWALS_ROOT_PROOF_DEPTH + MAX_WITHDRAWALS_PER_PAYLOAD_LOG2 + 1;
+
+/// The gindex for blockRoot -> withdrawalsRoot.
+pub const WITHDRAWALS_ROOT_GINDEX: u64 = 3230;
+
+/// The gindex for blockRoot -> withdrawals[0].
+pub const WITHDRAWAL_BASE_GINDEX: u64 =
+    WITHDRAWALS_ROOT_GINDEX << (MAX_WITHDRAWALS_PER_PAYLOAD_LOG2 + 1);
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Verifies a proof of the validator at `index` against a state root.
+    pub fn beacon_verify_validator(
+        &mut self,
+        state_root: Bytes32Variable,
+        index: U64Variable,
+        validator: &BeaconValidatorVariable,
+        proof: &ArrayVariable<Bytes32Variable, VALIDATOR_PROOF_DEPTH>,
+    ) {
+        self.beacon_assert_index_bits(index, VALIDATOR_REGISTRY_LIMIT_LOG2);
+        let leaf = validator.hash_tree_root(self);
+        let base = self.constant::<U64Variable>(VALIDATOR_BASE_GINDEX);
+        let gindex = self.add(base, index);
+        self.ssz_verify_proof(state_root, leaf, proof.as_slice(), gindex);
+    }
+
+    /// Verifies a proof of the leaf of the balance of the validator at `index` against a state
+    /// root, and returns the balance.
+    pub fn beacon_verify_balance(
+        &mut self,
+        state_root: Bytes32Variable,
+        index: U64Variable,
+        leaf: Bytes32Variable,
+        proof: &ArrayVariable<Bytes32Variable, BALANCE_PROOF_DEPTH>,
+    ) -> U64Variable {
+        self.beacon_assert_index_bits(index, VALIDATOR_REGISTRY_LIMIT_LOG2);
+        let balances_per_leaf = self.constant::<U64Variable>(BALANCES_PER_LEAF as u64);
+        let offset = self.div(index, balances_per_leaf);
+        let base = self.constant::<U64Variable>(BALANCE_BASE_GINDEX);
+        let gindex = self.add(base, offset);
+        self.ssz_verify_proof(state_root, leaf, proof.as_slice(), gindex);
+        self.beacon_select_balance(leaf, index)
+    }
+
+    /// Verifies a proof of the withdrawals root of the execution payload of a block against the
+    /// root of the block.
+    pub fn beacon_verify_withdrawals_root(
+        &mut self,
+        block_root: Bytes32Variable,
+        withdrawals_root: Bytes32Variable,
+        proof: &ArrayVariable<Bytes32Variable, WITHDRAWALS_ROOT_PROOF_DEPTH>,
+    ) {
+        self.ssz_verify_proof_const(
+            block_root,
+            withdrawals_root,
+            proof.as_slice(),
+            WITHDRAWALS_ROOT_GINDEX,
+        );
+    }
+
+    /// Verifies a proof of the withdrawal at position `index` of the withdrawals of the execution
+    /// payload of a block against the root of the block.
+    ///
+    /// The positions after the length of the list hold zero leaves, which do not match the root of
+    /// any withdrawal, so that only the withdrawals of the block can be proven.
+    pub fn beacon_verify_withdrawal(
+        &mut self,
+        block_root: Bytes32Variable,
+        index: U64Variable,
+        withdrawal: &BeaconWithdrawalVariable,
+        proof: &ArrayVariable<Bytes32Variable, WITHDRAWAL_PROOF_DEPTH>,
+    ) {
+        self.beacon_assert_index_bits(index, MAX_WITHDRAWALS_PER_PAYLOAD_LOG2);
+        let leaf = withdrawal.hash_tree_root(self);
+        let base = self.constant::<U64Variable>(WITHDRAWAL_BASE_GINDEX);
+        let gindex = self.add(base, index);
+        self.ssz_verify_proof(block_root, leaf, proof.as_slice(), gindex);
+    }
+
+    /// Decodes the four little-endian balances packed in a leaf of the balances list.
+    pub fn beacon_decode_balances_leaf(
+        &mut self,
+        leaf: Bytes32Variable,
+    ) -> [U64Variable; BALANCES_PER_LEAF] {
+        let bytes = leaf.as_bytes();
+        core::array::from_fn(|i| {
+            let mut balance = bytes[8 * i..8 * (i + 1)].to_vec();
+            balance.reverse();
+            U64Variable::decode(self, &balance)
+        })
+    }
+
+    /// Returns the balance of the validator at `index` from the leaf of the balances list that
+    /// contains it.
+    pub fn beacon_select_balance(
+        &mut self,
+        leaf: Bytes32Variable,
+        index: U64Variable,
+    ) -> U64Variable {
+        let balances = self.beacon_decode_balances_leaf(leaf);
+        let bits = self.to_le_bits(index);
+        let low = self.select(bits[0], balances[1], balances[0]);
+        let high = self.select(bits[0], balances[3], balances[2]);
+        self.select(bits[1], high, low)
+    }
+
+    /// Asserts that `index` fits in `nb_bits` bits, which is below the limit of a list of at most
+    /// `2^nb_bits` elements, so that its proofs cannot reach the length of the list.
+    pub(crate) fn beacon_assert_index_bits(&mut self, index: U64Variable, nb_bits: usize) {
+        let bits = self.to_le_bits(index);
+        let false_v = self._false();
+        for bit in bits[nb_bits..].iter() {
+            self.assert_is_equal(*bit, false_v);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ethers::types::{H160, H256, U256};
+    use rand::Rng;
+
+    use super::*;
+    use crate::frontend::eth::beacon::vars::BeaconWithdrawalValue;
+    use crate::prelude::DefaultBuilder;
+    use crate::utils::bytes32;
+    use crate::utils::eth::beacon::BeaconValidator;
+    use crate::utils::hash::sha256;
+
+    /// Returns a random branch for `leaf` at `gindex` and the root that it proves.
+    fn random_proof(leaf: H256, gindex: u64, depth: usize) -> (Vec<H256>, H256) {
+        let mut rng = rand::thread_rng();
+        let branch = (0..depth).map(|_| H256(rng.gen())).collect::<Vec<_>>();
+        let mut node = leaf;
+        for (level, sibling) in branch.iter().enumerate() {
+            let data = if (gindex >> level) & 1 == 1 {
+                [sibling.as_bytes(), node.as_bytes()].concat()
+            } else {
+                [node.as_bytes(), sibling.as_bytes()].concat()
+            };
+            node = H256(sha256(&data));
+        }
+        (branch, node)
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_beacon_verify_validator_and_balance() {
+        let mut builder = DefaultBuilder::new();
+        let validator_state_root = builder.read::<Bytes32Variable>();
+        let balance_state_root = builder.read::<Bytes32Variable>();
+        let index = builder.read::<U64Variable>();
+        let validator = builder.read::<BeaconValidatorVariable>();
+        let validator_proof =
+            builder.read::<ArrayVariable<Bytes32Variable, VALIDATOR_PROOF_DEPTH>>();
+        let leaf = builder.read::<Bytes32Variable>();
+        let balance_proof = builder.read::<ArrayVariable<Bytes32Variable, BALANCE_PROOF_DEPTH>>();
+        builder.beacon_verify_validator(validator_state_root, index, &validator, &validator_proof);
+        let balance =
+            builder.beacon_verify_balance(balance_state_root, index, leaf, &balance_proof);
+        builder.write(balance);
+        let circuit = builder.mock_build();
+
+        let index = 1234567u64;
+        let validator = BeaconValidator {
+            pubkey: "0x933ad9491b62059dd065b560d256d8957a8c402cc6e8d8ee7290ae11e8f7329267a8811c397529dac52ae1342ba58c95".to_string(),
+            withdrawal_credentials: "0x0100000000000000000000000d369bb49efa5100fd3b86a9f828c55da04d2d50".to_string(),
+            effective_balance: 32000000000,
+            slashed: false,
+            activation_eligibility_epoch: "0".to_string(),
+            activation_epoch: "0".to_string(),
+            exit_epoch: "18446744073709551615".to_string(),
+            withdrawable_epoch: "18446744073709551615".to_string(),
+        };
+        let validator_root =
+            bytes32!("0x2baf4065b5d6246410518c7981e5507ce82d46d87f8099df52c396c3b62b0fd5");
+        let (validator_proof, validator_state_root) = random_proof(
+            validator_root,
+            VALIDATOR_BASE_GINDEX + index,
+            VALIDATOR_PROOF_DEPTH,
+        );
+
+        let balances = [31999999999u64, 32000000000, 32000012345, 0];
+        let mut leaf = [0u8; 32];
+        for (i, balance) in balances.iter().enumerate() {
+            leaf[8 * i..8 * (i + 1)].copy_from_slice(&balance.to_le_bytes());
+        }
+        let leaf = H256(leaf);
+        let (balance_proof, balance_state_root) = random_proof(
+            leaf,
+            BALANCE_BASE_GINDEX + index / BALANCES_PER_LEAF as u64,
+            BALANCE_PROOF_DEPTH,
+        );
+
+        let mut input = circuit.input();
+        input.write::<Bytes32Variable>(validator_state_root);
+        input.write::<Bytes32Variable>(balance_state_root);
+        input.write::<U64Variable>(index);
+        input.write::<BeaconValidatorVariable>(validator);
+        input.write::<ArrayVariable<Bytes32Variable, VALIDATOR_PROOF_DEPTH>>(validator_proof);
+        input.write::<Bytes32Variable>(leaf);
+        input.write::<ArrayVariable<Bytes32Variable, BALANCE_PROOF_DEPTH>>(balance_proof);
+        let (_witness, mut output) = circuit.mock_prove(&input);
+        assert_eq!(
+            output.read::<U64Variable>(),
+            balances[index as usize % BALANCES_PER_LEAF]
+        );
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_beacon_verify_withdrawal() {
+        let mut builder = DefaultBuilder::new();
+        let block_root = builder.read::<Bytes32Variable>();
+        let withdrawals_root = builder.read::<Bytes32Variable>();
+        let withdrawals_root_proof =
+            builder.read::<ArrayVariable<Bytes32Variable, WITHDRAWALS_ROOT_PROOF_DEPTH>>();
+        let index = builder.read::<U64Variable>();
+        let withdrawal = builder.read::<BeaconWithdrawalVariable>();
+        let withdrawal_proof =
+            builder.read::<ArrayVariable<Bytes32Variable, WITHDRAWAL_PROOF_DEPTH>>();
+        builder.beacon_verify_withdrawals_root(
+            block_root,
+            withdrawals_root,
+            &withdrawals_root_proof,
+        );
+        builder.beacon_verify_withdrawal(block_root, index, &withdrawal, &withdrawal_proof);
+        let circuit = builder.mock_build();
+
+        let index = 3u64;
+        let withdrawal = BeaconWithdrawalValue {
+            index: 21470366,
+            validator_index: 596573,
+            address: H160::repeat_byte(0xab),
+            amount: U256::from(17429563u64),
+        };
+        let mut chunks = [[0u8; 32]; 4];
+        chunks[0][..8].copy_from_slice(&withdrawal.index.to_le_bytes());
+        chunks[1][..8].copy_from_slice(&withdrawal.validator_index.to_le_bytes());
+        chunks[2][..20].copy_from_slice(withdrawal.address.as_bytes());
+        withdrawal.amount.to_little_endian(&mut chunks[3]);
+        let leaf = sha256(
+            &[
+                sha256(&[chunks[0], chunks[1]].concat()),
+                sha256(&[chunks[2], chunks[3]].concat()),
+            ]
+            .concat(),
+        );
+
+        // The proof of the withdrawal goes through the withdrawals root.
+        let (list_proof, withdrawals_root) = random_proof(
+            H256(leaf),
+            (1 << (MAX_WITHDRAWALS_PER_PAYLOAD_LOG2 + 1)) + index,
+            MAX_WITHDRAWALS_PER_PAYLOAD_LOG2 + 1,
+        );
+        let (withdrawals_root_proof, block_root) = random_proof(
+            withdrawals_root,
+            WITHDRAWALS_ROOT_GINDEX,
+            WITHDRAWALS_ROOT_PROOF_DEPTH,
+        );
+        let withdrawal_proof = [list_proof, withdrawals_root_proof.clone()].concat();
+
+        let mut input = circuit.input();
+        input.write::<Bytes32Variable>(block_root);
+        input.write::<Bytes32Variable>(withdrawals_root);
+        input.write::<ArrayVariable<Bytes32Variable, WITHDRAWALS_ROOT_PROOF_DEPTH>>(
+            withdrawals_root_proof,
+        );
+        input.write::<U64Variable>(index);
+        input.write::<BeaconWithdrawalVariable>(withdrawal);
+        input.write::<ArrayVariable<Bytes32Variable, WITHDRAWAL_PROOF_DEPTH>>(withdrawal_proof);
+        circuit.mock_prove(&input);
+    }
+}
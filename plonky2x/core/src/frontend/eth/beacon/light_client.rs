@@ -0,0 +1,303 @@
+use std::fmt::Debug;
+
+use ethers::types::H256;
+use plonky2::field::types::Field;
+use plonky2::hash::hash_types::RichField;
+use plonky2x_derive::CircuitVariable;
+
+use super::vars::BeaconHeaderVariable;
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::builder::CircuitBuilder;
+use crate::frontend::ecc::bls12_381::g1::Bls12381G1PointVariable;
+use crate::frontend::eth::vars::{BLSPubkeyVariable, BLSSignatureVariable};
+use crate::frontend::vars::{
+    ArrayVariable, BoolVariable, Bytes32Variable, CircuitVariable, SSZVariable, Variable,
+};
+use crate::utils::hash::sha256;
+
+/// The number of validators of a sync committee.
+pub const SYNC_COMMITTEE_SIZE: usize = 512;
+
+/// The depth of the proof from stateRoot -> currentSyncCommittee or nextSyncCommittee, from
+/// Altair to Deneb.
+pub const SYNC_COMMITTEE_PROOF_DEPTH: usize = 5;
+
+/// The gindex for stateRoot -> currentSyncCommittee.
+pub const CURRENT_SYNC_COMMITTEE_GINDEX: u64 = 54;
+
+/// The gindex for stateRoot -> nextSyncCommittee.
+pub const NEXT_SYNC_COMMITTEE_GINDEX: u64 = 55;
+
+/// The domain type of the signatures of sync committees.
+pub const DOMAIN_SYNC_COMMITTEE: [u8; 4] = [7, 0, 0, 0];
+
+/// Returns the domain of the signatures of type `domain_type` for the fork `fork_version` of the
+/// chain of genesis validators root `genesis_validators_root`.
+pub fn compute_domain(
+    domain_type: [u8; 4],
+    fork_version: [u8; 4],
+    genesis_validators_root: H256,
+) -> H256 {
+    let mut version = [0u8; 32];
+    version[..4].copy_from_slice(&fork_version);
+    let fork_data_root = sha256(&[&version[..], genesis_validators_root.as_bytes()].concat());
+    let mut domain = [0u8; 32];
+    domain[..4].copy_from_slice(&domain_type);
+    domain[4..].copy_from_slice(&fork_data_root[..28]);
+    H256(domain)
+}
+
+/// Returns the domain of the signatures of sync committees for the fork `fork_version` of the
+/// chain of genesis validators root `genesis_validators_root`.
+pub fn compute_sync_committee_domain(fork_version: [u8; 4], genesis_validators_root: H256) -> H256 {
+    compute_domain(DOMAIN_SYNC_COMMITTEE, fork_version, genesis_validators_root)
+}
+
+/// The data that the BLS signature of a sync aggregate is verified against: the aggregate of the
+/// public keys of the participants and the signing root of the header.
+#[derive(Debug, Clone, CircuitVariable)]
+#[value_name(BeaconSyncAggregateValue)]
+pub struct BeaconSyncAggregateVariable {
+    pub aggregate_pubkey: Bls12381G1PointVariable,
+    pub signing_root: Bytes32Variable,
+}
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Computes the root of a sync committee from the public keys of its validators and their
+    /// aggregate.
+    pub fn beacon_sync_committee_root<const N: usize>(
+        &mut self,
+        pubkeys: &ArrayVariable<BLSPubkeyVariable, N>,
+        aggregate_pubkey: BLSPubkeyVariable,
+    ) -> Bytes32Variable {
+        let roots = pubkeys
+            .as_slice()
+            .iter()
+            .map(|pubkey| pubkey.hash_tree_root(self))
+            .collect::<Vec<_>>();
+        let pubkeys_root = self.ssz_merkleize_chunks(&roots, N);
+        let aggregate_pubkey_root = aggregate_pubkey.hash_tree_root(self);
+        self.sha256_pair(pubkeys_root, aggregate_pubkey_root)
+    }
+
+    /// Verifies a proof of the root of the current sync committee against a state root.
+    pub fn beacon_verify_current_sync_committee(
+        &mut self,
+        state_root: Bytes32Variable,
+        committee_root: Bytes32Variable,
+        proof: &ArrayVariable<Bytes32Variable, SYNC_COMMITTEE_PROOF_DEPTH>,
+    ) {
+        self.ssz_verify_proof_const(
+            state_root,
+            committee_root,
+            proof.as_slice(),
+            CURRENT_SYNC_COMMITTEE_GINDEX,
+        );
+    }
+
+    /// Verifies a proof of the root of the next sync committee against a state root.
+    pub fn beacon_verify_next_sync_committee(
+        &mut self,
+        state_root: Bytes32Variable,
+        committee_root: Bytes32Variable,
+        proof: &ArrayVariable<Bytes32Variable, SYNC_COMMITTEE_PROOF_DEPTH>,
+    ) {
+        self.ssz_verify_proof_const(
+            state_root,
+            committee_root,
+            proof.as_slice(),
+            NEXT_SYNC_COMMITTEE_GINDEX,
+        );
+    }
+
+    /// Returns the number of participants of a sync aggregate.
+    pub fn beacon_sync_committee_participation<const N: usize>(
+        &mut self,
+        bits: &ArrayVariable<BoolVariable, N>,
+    ) -> Variable {
+        let mut count = self.zero::<Variable>();
+        for bit in bits.as_slice() {
+            count = self.add(count, bit.variable);
+        }
+        count
+    }
+
+    /// Asserts that at least two thirds of the sync committee participate in a sync aggregate.
+    pub fn beacon_assert_sync_committee_supermajority<const N: usize>(
+        &mut self,
+        bits: &ArrayVariable<BoolVariable, N>,
+    ) {
+        let count = self.beacon_sync_committee_participation(bits);
+        let three = self.constant::<Variable>(L::Field::from_canonical_u64(3));
+        let weighted_count = self.mul(count, three);
+        let threshold = self.constant::<Variable>(L::Field::from_canonical_usize(2 * N));
+        let is_supermajority = self.gte(weighted_count, threshold);
+        let true_v = self._true();
+        self.assert_is_equal(is_supermajority, true_v);
+    }
+
+    /// Verifies the signature of a sync aggregate over `header`, asserting that at least two
+    /// thirds of the committee participate, and returns the data it was verified against.
+    ///
+    /// The public keys of the participants are decompressed and aggregated, and the signing root
+    /// is the root of the header with `domain`, as computed by `compute_sync_committee_domain`.
+    /// The BLS signature is then checked against the aggregate public key and the signing root.
+    pub fn beacon_sync_aggregate<const N: usize>(
+        &mut self,
+        header: &BeaconHeaderVariable,
+        pubkeys: &ArrayVariable<BLSPubkeyVariable, N>,
+        bits: &ArrayVariable<BoolVariable, N>,
+        domain: Bytes32Variable,
+        signature: &BLSSignatureVariable,
+    ) -> BeaconSyncAggregateVariable {
+        self.beacon_assert_sync_committee_supermajority(bits);
+        let aggregate_pubkey = self.bls12_381_aggregate_pubkeys(pubkeys, bits);
+        let header_root = header.hash_tree_root(self);
+        let signing_root = self.sha256_pair(header_root, domain);
+        self.bls12_381_verify_signature(&aggregate_pubkey, &signing_root.as_bytes(), signature);
+        BeaconSyncAggregateVariable {
+            aggregate_pubkey,
+            signing_root,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::BigUint;
+
+    use super::*;
+    use crate::frontend::ecc::bls12_381::field::Bls12381Base;
+    use crate::frontend::ecc::bls12_381::g1::Bls12381G1;
+    use crate::frontend::ecc::nonnative::NonNativeField;
+    use crate::frontend::ecc::weierstrass::{add_native, mul_native, WeierstrassCurve};
+    use crate::frontend::eth::beacon::vars::BeaconHeaderValue;
+    use crate::prelude::DefaultBuilder;
+
+    const N: usize = 4;
+
+    const SIGNATURE: &str = "b0d812404e3036d675c582ed7716205df15ca82e2e2936017bc5941991c46db348232ec118f7ec158149e737d9f6dd6b1012fe25336a924155acf286cab38e255dd082eeb7eb679e63529536f6a91d765cd3001b5ce10048eaefeaf75306cc27";
+
+    fn hash_pair(left: &[u8], right: &[u8]) -> [u8; 32] {
+        sha256(&[left, right].concat())
+    }
+
+    fn compress(point: &(BigUint, BigUint)) -> [u8; 48] {
+        let mut bytes = [0u8; 48];
+        let x = point.0.to_bytes_be();
+        bytes[48 - x.len()..].copy_from_slice(&x);
+        bytes[0] |= 0x80;
+        if point.1 > (Bls12381Base::modulus() - 1u32) / 2u32 {
+            bytes[0] |= 0x20;
+        }
+        bytes
+    }
+
+    fn pubkey_root(pubkey: &[u8; 48]) -> [u8; 32] {
+        let mut high = [0u8; 32];
+        high[..16].copy_from_slice(&pubkey[32..]);
+        hash_pair(&pubkey[..32], &high)
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_beacon_sync_aggregate() {
+        let mut builder = DefaultBuilder::new();
+        let state_root = builder.read::<Bytes32Variable>();
+        let pubkeys = builder.read::<ArrayVariable<BLSPubkeyVariable, N>>();
+        let aggregate_pubkey = builder.read::<BLSPubkeyVariable>();
+        let proof = builder.read::<ArrayVariable<Bytes32Variable, SYNC_COMMITTEE_PROOF_DEPTH>>();
+        let header = builder.read::<BeaconHeaderVariable>();
+        let bits = builder.read::<ArrayVariable<BoolVariable, N>>();
+        let domain = builder.read::<Bytes32Variable>();
+        let signature = builder.read::<BLSSignatureVariable>();
+        let committee_root = builder.beacon_sync_committee_root(&pubkeys, aggregate_pubkey);
+        builder.beacon_verify_next_sync_committee(state_root, committee_root, &proof);
+        let sync_aggregate =
+            builder.beacon_sync_aggregate(&header, &pubkeys, &bits, domain, &signature);
+        builder.write(sync_aggregate);
+        let circuit = builder.mock_build();
+
+        // The committee has the public keys of `G`, `2G`, `3G` and `4G`, of which all but the
+        // second participate, so that the aggregate is `8G`.
+        let generator = Bls12381G1::generator();
+        let points = (1..=N as u32)
+            .map(|k| mul_native::<Bls12381G1>(&BigUint::from(k), &generator).unwrap())
+            .collect::<Vec<_>>();
+        let pubkeys = points.iter().map(compress).collect::<Vec<_>>();
+        let aggregate = points
+            .iter()
+            .cloned()
+            .reduce(|a, b| add_native::<Bls12381G1>(&a, &b))
+            .unwrap();
+        let aggregate_pubkey = compress(&aggregate);
+        let expected_aggregate =
+            mul_native::<Bls12381G1>(&BigUint::from(8u32), &generator).unwrap();
+
+        // The state is a tree of 32 leaves whose leaf 23 is the root of the next committee.
+        let roots = pubkeys.iter().map(pubkey_root).collect::<Vec<_>>();
+        let committee_root = hash_pair(
+            &hash_pair(
+                &hash_pair(&roots[0], &roots[1]),
+                &hash_pair(&roots[2], &roots[3]),
+            ),
+            &pubkey_root(&aggregate_pubkey),
+        );
+        let mut layers = vec![(0..32u8).map(|i| [i; 32]).collect::<Vec<_>>()];
+        layers[0][23] = committee_root;
+        while layers.last().unwrap().len() > 1 {
+            let layer = layers
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| hash_pair(&pair[0], &pair[1]))
+                .collect::<Vec<_>>();
+            layers.push(layer);
+        }
+        let proof = (0..SYNC_COMMITTEE_PROOF_DEPTH)
+            .map(|level| H256(layers[level][(23 >> level) ^ 1]))
+            .collect::<Vec<_>>();
+
+        let header = BeaconHeaderValue {
+            slot: 7404237,
+            proposer_index: 1234,
+            parent_root: H256::repeat_byte(1),
+            state_root: H256(layers[5][0]),
+            body_root: H256::repeat_byte(3),
+        };
+        let domain = compute_sync_committee_domain([3, 0, 0, 0], H256::repeat_byte(4));
+        let mut slot = [0u8; 32];
+        slot[..8].copy_from_slice(&header.slot.to_le_bytes());
+        let mut proposer_index = [0u8; 32];
+        proposer_index[..8].copy_from_slice(&header.proposer_index.to_le_bytes());
+        let header_root = hash_pair(
+            &hash_pair(
+                &hash_pair(&slot, &proposer_index),
+                &hash_pair(header.parent_root.as_bytes(), header.state_root.as_bytes()),
+            ),
+            &hash_pair(
+                &hash_pair(header.body_root.as_bytes(), &[0u8; 32]),
+                &hash_pair(&[0u8; 32], &[0u8; 32]),
+            ),
+        );
+        let signing_root = H256(hash_pair(&header_root, domain.as_bytes()));
+
+        // The signature of the signing root by the secret key `8`, the sum of the secret keys of
+        // the participants.
+        let signature = hex::decode(SIGNATURE).unwrap().try_into().unwrap();
+
+        let mut input = circuit.input();
+        input.write::<Bytes32Variable>(header.state_root);
+        input.write::<ArrayVariable<BLSPubkeyVariable, N>>(pubkeys);
+        input.write::<BLSPubkeyVariable>(aggregate_pubkey);
+        input.write::<ArrayVariable<Bytes32Variable, SYNC_COMMITTEE_PROOF_DEPTH>>(proof);
+        input.write::<BeaconHeaderVariable>(header);
+        input.write::<ArrayVariable<BoolVariable, N>>(vec![true, false, true, true]);
+        input.write::<Bytes32Variable>(domain);
+        input.write::<BLSSignatureVariable>(signature);
+        let (_witness, mut output) = circuit.mock_prove(&input);
+        let sync_aggregate = output.read::<BeaconSyncAggregateVariable>();
+        assert_eq!(sync_aggregate.aggregate_pubkey, expected_aggregate.into());
+        assert_eq!(sync_aggregate.signing_root, signing_root);
+    }
+}
@@ -1,3 +1,8 @@
+pub mod attestation;
 pub mod builder;
 pub mod generators;
+pub mod historical;
+pub mod inclusion;
+pub mod light_client;
+pub mod rpc;
 pub mod vars;
@@ -0,0 +1,585 @@
+use std::collections::HashMap;
+use std::env;
+use std::fmt::Debug;
+use std::sync::Mutex;
+
+use anyhow::{anyhow, ensure, Context, Result};
+use ethers::types::H256;
+use lazy_static::lazy_static;
+use log::debug;
+use plonky2::hash::hash_types::RichField;
+use plonky2x_derive::CircuitVariable;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::light_client::{SYNC_COMMITTEE_PROOF_DEPTH, SYNC_COMMITTEE_SIZE};
+use super::vars::{BeaconHeaderValue, BeaconHeaderVariable, BeaconValidatorVariable};
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::builder::CircuitBuilder;
+use crate::frontend::eth::rpc::impl_rpc_hint;
+use crate::frontend::eth::vars::{BLSPubkeyVariable, BLSSignatureVariable};
+use crate::frontend::hint::asynchronous::policy::HintPolicy;
+use crate::frontend::uint::uint64::U64Variable;
+use crate::frontend::vars::{
+    ArrayVariable, BoolVariable, Bytes32Variable, CircuitVariable, ValueStream, Variable,
+    VariableStream,
+};
+use crate::utils::eth::beacon::BeaconValidator;
+
+lazy_static! {
+    /// The responses of the beacon node, keyed by their path, which holds the slot, block root,
+    /// state root or sync committee period they are for.
+    static ref RESPONSE_CACHE: Mutex<HashMap<String, Value>> = Mutex::new(HashMap::new());
+}
+
+/// Fetches the witness of the beacon gadgets from a beacon node, whose URL is given by the
+/// `CONSENSUS_RPC_URL` environment variable by default.
+///
+/// The responses are cached for the lifetime of the process, so that hints querying the same
+/// slot or root only reach the node once. The values fetched by the hints are not constrained:
+/// they are shaped to be checked by the SSZ gadgets that consume them, such as
+/// `beacon_header_root` for headers or `beacon_verify_current_sync_committee` for sync committees.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BeaconHintProvider {
+    rpc_var: String,
+    policy: HintPolicy,
+}
+
+impl Default for BeaconHintProvider {
+    fn default() -> Self {
+        Self::new("CONSENSUS_RPC_URL")
+    }
+}
+
+impl BeaconHintProvider {
+    /// Creates a provider reading the URL of the beacon node from the environment variable
+    /// `rpc_var` when the hints are run, so that the URL is not serialized with the circuit.
+    pub fn new(rpc_var: &str) -> Self {
+        Self {
+            rpc_var: rpc_var.to_string(),
+            policy: HintPolicy::default(),
+        }
+    }
+
+    /// Sets the retry and timeout policy of the requests to the beacon node.
+    pub fn with_policy(mut self, policy: HintPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Clears the responses cached by all providers.
+    pub fn clear_cache() {
+        RESPONSE_CACHE.lock().unwrap().clear();
+    }
+
+    /// Fetches the response of the beacon node to `path`, or returns it from the cache.
+    async fn fetch(&self, path: &str) -> Result<Value> {
+        let cached = RESPONSE_CACHE.lock().unwrap().get(path).cloned();
+        if let Some(body) = cached {
+            return Ok(body);
+        }
+
+        dotenv::dotenv().ok();
+        let rpc_url = env::var(&self.rpc_var)
+            .with_context(|| format!("{} environment variable was not found", self.rpc_var))?;
+        let endpoint = format!("{}{}", rpc_url.trim_end_matches('/'), path);
+        debug!("fetching {}", path);
+        let client = reqwest::Client::new();
+        let (client, endpoint) = (&client, &endpoint);
+        let body = self
+            .policy
+            .run(path, move || get_json(client, endpoint, path))
+            .await?;
+        RESPONSE_CACHE
+            .lock()
+            .unwrap()
+            .insert(path.to_string(), body.clone());
+        Ok(body)
+    }
+
+    /// Fetches the `data` of the response of the beacon node to `path`.
+    async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
+        let mut body = self.fetch(path).await?;
+        let data = body
+            .get_mut("data")
+            .map(Value::take)
+            .ok_or_else(|| anyhow!("the response to {} has no data", path))?;
+        serde_json::from_value(data).with_context(|| format!("failed to parse {}", path))
+    }
+
+    /// Fetches the response of the beacon node to `path`, which is a list rather than an object
+    /// with data.
+    async fn get_list<T: DeserializeOwned>(&self, path: &str) -> Result<Vec<T>> {
+        let body = self.fetch(path).await?;
+        serde_json::from_value(body).with_context(|| format!("failed to parse {}", path))
+    }
+}
+
+/// Fetches the JSON body of the response to `endpoint`, the URL of `path`.
+async fn get_json(client: &reqwest::Client, endpoint: &str, path: &str) -> Result<Value> {
+    let response = client.get(endpoint).send().await?;
+    let status = response.status();
+    ensure!(status.is_success(), "{} returned {}", path, status);
+    Ok(response.json().await?)
+}
+
+/// Decodes a hex string with a `0x` prefix into `N` bytes.
+fn decode_hex<const N: usize>(value: &str) -> Result<[u8; N]> {
+    let bytes = hex::decode(value.trim_start_matches("0x"))?;
+    bytes
+        .try_into()
+        .map_err(|bytes: Vec<u8>| anyhow!("expected {} bytes, got {}", N, bytes.len()))
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct HeaderJson {
+    slot: String,
+    proposer_index: String,
+    parent_root: H256,
+    state_root: H256,
+    body_root: H256,
+}
+
+impl HeaderJson {
+    fn to_value<F: RichField>(&self) -> Result<BeaconHeaderValue<F>> {
+        Ok(BeaconHeaderValue {
+            slot: self.slot.parse()?,
+            proposer_index: self.proposer_index.parse()?,
+            parent_root: self.parent_root,
+            state_root: self.state_root,
+            body_root: self.body_root,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct LightClientHeaderJson {
+    beacon: HeaderJson,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct SyncCommitteeJson {
+    pubkeys: Vec<String>,
+    aggregate_pubkey: String,
+}
+
+impl SyncCommitteeJson {
+    fn to_values(&self) -> Result<(Vec<[u8; 48]>, [u8; 48])> {
+        ensure!(
+            self.pubkeys.len() == SYNC_COMMITTEE_SIZE,
+            "the sync committee has {} validators",
+            self.pubkeys.len()
+        );
+        let pubkeys = self
+            .pubkeys
+            .iter()
+            .map(|pubkey| decode_hex(pubkey))
+            .collect::<Result<Vec<_>>>()?;
+        Ok((pubkeys, decode_hex(&self.aggregate_pubkey)?))
+    }
+}
+
+/// Checks that a branch of the light client API has the depth of the gadgets, which changes with
+/// the layout of the state.
+fn sync_committee_branch(branch: &[H256]) -> Result<Vec<H256>> {
+    ensure!(
+        branch.len() == SYNC_COMMITTEE_PROOF_DEPTH,
+        "the sync committee branch has {} nodes, but SYNC_COMMITTEE_PROOF_DEPTH is {}",
+        branch.len(),
+        SYNC_COMMITTEE_PROOF_DEPTH
+    );
+    Ok(branch.to_vec())
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct ValidatorJson {
+    pubkey: String,
+    withdrawal_credentials: String,
+    effective_balance: String,
+    slashed: bool,
+    activation_eligibility_epoch: String,
+    activation_epoch: String,
+    exit_epoch: String,
+    withdrawable_epoch: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct ValidatorResponseJson {
+    balance: String,
+    validator: ValidatorJson,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct BootstrapJson {
+    header: LightClientHeaderJson,
+    current_sync_committee: SyncCommitteeJson,
+    current_sync_committee_branch: Vec<H256>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct SyncAggregateJson {
+    sync_committee_bits: String,
+    sync_committee_signature: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct UpdateJson {
+    attested_header: LightClientHeaderJson,
+    next_sync_committee: SyncCommitteeJson,
+    next_sync_committee_branch: Vec<H256>,
+    sync_aggregate: SyncAggregateJson,
+    signature_slot: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct VersionedJson<T> {
+    data: T,
+}
+
+/// The light client bootstrap of a block: its header and the current sync committee, with the
+/// proof of the committee against the state root of the header.
+#[derive(Debug, Clone, CircuitVariable)]
+#[value_name(BeaconLightClientBootstrapValue)]
+pub struct BeaconLightClientBootstrapVariable {
+    pub header: BeaconHeaderVariable,
+    pub current_sync_committee_pubkeys: ArrayVariable<BLSPubkeyVariable, SYNC_COMMITTEE_SIZE>,
+    pub current_sync_committee_aggregate_pubkey: BLSPubkeyVariable,
+    pub current_sync_committee_branch: ArrayVariable<Bytes32Variable, SYNC_COMMITTEE_PROOF_DEPTH>,
+}
+
+/// The light client update of a sync committee period: an attested header with the aggregate
+/// that signs it, and the next sync committee, with its proof against the state root of the
+/// attested header.
+#[derive(Debug, Clone, CircuitVariable)]
+#[value_name(BeaconLightClientUpdateValue)]
+pub struct BeaconLightClientUpdateVariable {
+    pub attested_header: BeaconHeaderVariable,
+    pub next_sync_committee_pubkeys: ArrayVariable<BLSPubkeyVariable, SYNC_COMMITTEE_SIZE>,
+    pub next_sync_committee_aggregate_pubkey: BLSPubkeyVariable,
+    pub next_sync_committee_branch: ArrayVariable<Bytes32Variable, SYNC_COMMITTEE_PROOF_DEPTH>,
+    pub sync_committee_bits: ArrayVariable<BoolVariable, SYNC_COMMITTEE_SIZE>,
+    pub sync_committee_signature: BLSSignatureVariable,
+    pub signature_slot: U64Variable,
+}
+
+/// Fetches the root of the block at a slot with `/eth/v1/beacon/blocks/{slot}/root`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BeaconRpcBlockRootHint {
+    provider: BeaconHintProvider,
+}
+
+impl BeaconRpcBlockRootHint {
+    async fn fetch<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        input_stream: &mut ValueStream<L, D>,
+        output_stream: &mut ValueStream<L, D>,
+    ) -> Result<()> {
+        #[derive(Deserialize)]
+        struct RootJson {
+            root: H256,
+        }
+
+        let slot = input_stream.read_value::<U64Variable>();
+        let path = format!("/eth/v1/beacon/blocks/{}/root", slot);
+        let response: RootJson = self.provider.get(&path).await?;
+        output_stream.write_value::<Bytes32Variable>(response.root);
+        Ok(())
+    }
+}
+
+impl_rpc_hint!(BeaconRpcBlockRootHint);
+
+/// Fetches a block header with `/eth/v1/beacon/headers/{block_root}`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BeaconRpcHeaderHint {
+    provider: BeaconHintProvider,
+}
+
+impl BeaconRpcHeaderHint {
+    async fn fetch<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        input_stream: &mut ValueStream<L, D>,
+        output_stream: &mut ValueStream<L, D>,
+    ) -> Result<()> {
+        #[derive(Deserialize)]
+        struct SignedHeaderJson {
+            message: HeaderJson,
+        }
+        #[derive(Deserialize)]
+        struct HeaderResponseJson {
+            header: SignedHeaderJson,
+        }
+
+        let block_root = input_stream.read_value::<Bytes32Variable>();
+        let path = format!("/eth/v1/beacon/headers/{:?}", block_root);
+        let response: HeaderResponseJson = self.provider.get(&path).await?;
+        let header = response.header.message.to_value()?;
+        output_stream.write_value::<BeaconHeaderVariable>(header);
+        Ok(())
+    }
+}
+
+impl_rpc_hint!(BeaconRpcHeaderHint);
+
+/// Fetches a validator and its balance with
+/// `/eth/v1/beacon/states/{state_root}/validators/{index}`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BeaconRpcValidatorHint {
+    provider: BeaconHintProvider,
+}
+
+impl BeaconRpcValidatorHint {
+    async fn fetch<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        input_stream: &mut ValueStream<L, D>,
+        output_stream: &mut ValueStream<L, D>,
+    ) -> Result<()> {
+        let state_root = input_stream.read_value::<Bytes32Variable>();
+        let index = input_stream.read_value::<U64Variable>();
+        let path = format!(
+            "/eth/v1/beacon/states/{:?}/validators/{}",
+            state_root, index
+        );
+        let response: ValidatorResponseJson = self.provider.get(&path).await?;
+        let validator = response.validator;
+        output_stream.write_value::<BeaconValidatorVariable>(BeaconValidator {
+            pubkey: validator.pubkey,
+            withdrawal_credentials: validator.withdrawal_credentials,
+            effective_balance: validator.effective_balance.parse()?,
+            slashed: validator.slashed,
+            activation_eligibility_epoch: validator.activation_eligibility_epoch,
+            activation_epoch: validator.activation_epoch,
+            exit_epoch: validator.exit_epoch,
+            withdrawable_epoch: validator.withdrawable_epoch,
+        });
+        output_stream.write_value::<U64Variable>(response.balance.parse()?);
+        Ok(())
+    }
+}
+
+impl_rpc_hint!(BeaconRpcValidatorHint);
+
+/// Fetches the light client bootstrap of a block with
+/// `/eth/v1/beacon/light_client/bootstrap/{block_root}`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BeaconRpcLightClientBootstrapHint {
+    provider: BeaconHintProvider,
+}
+
+impl BeaconRpcLightClientBootstrapHint {
+    async fn fetch<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        input_stream: &mut ValueStream<L, D>,
+        output_stream: &mut ValueStream<L, D>,
+    ) -> Result<()> {
+        let block_root = input_stream.read_value::<Bytes32Variable>();
+        let path = format!("/eth/v1/beacon/light_client/bootstrap/{:?}", block_root);
+        let bootstrap: BootstrapJson = self.provider.get(&path).await?;
+        let (pubkeys, aggregate_pubkey) = bootstrap.current_sync_committee.to_values()?;
+        output_stream.write_value::<BeaconLightClientBootstrapVariable>(
+            BeaconLightClientBootstrapValue {
+                header: bootstrap.header.beacon.to_value()?,
+                current_sync_committee_pubkeys: pubkeys,
+                current_sync_committee_aggregate_pubkey: aggregate_pubkey,
+                current_sync_committee_branch: sync_committee_branch(
+                    &bootstrap.current_sync_committee_branch,
+                )?,
+            },
+        );
+        Ok(())
+    }
+}
+
+impl_rpc_hint!(BeaconRpcLightClientBootstrapHint);
+
+/// Fetches the light client update of a sync committee period with
+/// `/eth/v1/beacon/light_client/updates?start_period={period}&count=1`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BeaconRpcLightClientUpdateHint {
+    provider: BeaconHintProvider,
+}
+
+impl BeaconRpcLightClientUpdateHint {
+    async fn fetch<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        input_stream: &mut ValueStream<L, D>,
+        output_stream: &mut ValueStream<L, D>,
+    ) -> Result<()> {
+        let period = input_stream.read_value::<U64Variable>();
+        let path = format!(
+            "/eth/v1/beacon/light_client/updates?start_period={}&count=1",
+            period
+        );
+        let updates: Vec<VersionedJson<UpdateJson>> = self.provider.get_list(&path).await?;
+        let update = updates
+            .into_iter()
+            .next()
+            .with_context(|| format!("there is no update for period {}", period))?
+            .data;
+
+        let (pubkeys, aggregate_pubkey) = update.next_sync_committee.to_values()?;
+        let bits: [u8; SYNC_COMMITTEE_SIZE / 8] =
+            decode_hex(&update.sync_aggregate.sync_committee_bits)?;
+        output_stream.write_value::<BeaconLightClientUpdateVariable>(
+            BeaconLightClientUpdateValue {
+                attested_header: update.attested_header.beacon.to_value()?,
+                next_sync_committee_pubkeys: pubkeys,
+                next_sync_committee_aggregate_pubkey: aggregate_pubkey,
+                next_sync_committee_branch: sync_committee_branch(
+                    &update.next_sync_committee_branch,
+                )?,
+                // Bitvectors are serialized from the least significant bit of the first byte.
+                sync_committee_bits: (0..SYNC_COMMITTEE_SIZE)
+                    .map(|i| (bits[i / 8] >> (i % 8)) & 1 == 1)
+                    .collect(),
+                sync_committee_signature: decode_hex(
+                    &update.sync_aggregate.sync_committee_signature,
+                )?,
+                signature_slot: update.signature_slot.parse()?,
+            },
+        );
+        Ok(())
+    }
+}
+
+impl_rpc_hint!(BeaconRpcLightClientUpdateHint);
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Fetches the root of the block at `slot`, which must not be empty.
+    pub fn beacon_rpc_get_block_root(
+        &mut self,
+        provider: &BeaconHintProvider,
+        slot: U64Variable,
+    ) -> Bytes32Variable {
+        let mut input_stream = VariableStream::new();
+        input_stream.write(&slot);
+        let hint = BeaconRpcBlockRootHint {
+            provider: provider.clone(),
+        };
+        let output_stream = self.async_hint(input_stream, hint);
+        output_stream.read::<Bytes32Variable>(self)
+    }
+
+    /// Fetches the header of the block of `block_root`, as checked by `beacon_header_root`.
+    pub fn beacon_rpc_get_header(
+        &mut self,
+        provider: &BeaconHintProvider,
+        block_root: Bytes32Variable,
+    ) -> BeaconHeaderVariable {
+        let mut input_stream = VariableStream::new();
+        input_stream.write(&block_root);
+        let hint = BeaconRpcHeaderHint {
+            provider: provider.clone(),
+        };
+        let output_stream = self.async_hint(input_stream, hint);
+        output_stream.read::<BeaconHeaderVariable>(self)
+    }
+
+    /// Fetches the validator at `index` in the state of `state_root` and its balance.
+    ///
+    /// The beacon API serves no proofs of validators, which are to be verified against the state
+    /// root with `beacon_verify_validator` and `beacon_verify_balance`.
+    pub fn beacon_rpc_get_validator(
+        &mut self,
+        provider: &BeaconHintProvider,
+        state_root: Bytes32Variable,
+        index: U64Variable,
+    ) -> (BeaconValidatorVariable, U64Variable) {
+        let mut input_stream = VariableStream::new();
+        input_stream.write(&state_root);
+        input_stream.write(&index);
+        let hint = BeaconRpcValidatorHint {
+            provider: provider.clone(),
+        };
+        let output_stream = self.async_hint(input_stream, hint);
+        let validator = output_stream.read::<BeaconValidatorVariable>(self);
+        let balance = output_stream.read::<U64Variable>(self);
+        (validator, balance)
+    }
+
+    /// Fetches the light client bootstrap of the block of `block_root`, whose sync committee is
+    /// checked by `beacon_sync_committee_root` and `beacon_verify_current_sync_committee`.
+    pub fn beacon_rpc_get_light_client_bootstrap(
+        &mut self,
+        provider: &BeaconHintProvider,
+        block_root: Bytes32Variable,
+    ) -> BeaconLightClientBootstrapVariable {
+        let mut input_stream = VariableStream::new();
+        input_stream.write(&block_root);
+        let hint = BeaconRpcLightClientBootstrapHint {
+            provider: provider.clone(),
+        };
+        let output_stream = self.async_hint(input_stream, hint);
+        output_stream.read::<BeaconLightClientBootstrapVariable>(self)
+    }
+
+    /// Fetches the light client update of the sync committee `period`, whose aggregate is checked
+    /// by `beacon_sync_aggregate` and whose next sync committee is checked by
+    /// `beacon_sync_committee_root` and `beacon_verify_next_sync_committee`.
+    pub fn beacon_rpc_get_light_client_update(
+        &mut self,
+        provider: &BeaconHintProvider,
+        period: U64Variable,
+    ) -> BeaconLightClientUpdateVariable {
+        let mut input_stream = VariableStream::new();
+        input_stream.write(&period);
+        let hint = BeaconRpcLightClientUpdateHint {
+            provider: provider.clone(),
+        };
+        let output_stream = self.async_hint(input_stream, hint);
+        output_stream.read::<BeaconLightClientUpdateVariable>(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::prelude::DefaultBuilder;
+
+    #[test]
+    fn test_beacon_rpc_get_header_from_cache() {
+        // The provider reads an unset variable, so that the header can only come from the cache.
+        let provider = BeaconHintProvider::new("BEACON_RPC_TEST_UNSET_URL");
+        let block_root = H256::repeat_byte(0x11);
+        RESPONSE_CACHE.lock().unwrap().insert(
+            format!("/eth/v1/beacon/headers/{:?}", block_root),
+            json!({
+                "execution_optimistic": false,
+                "finalized": true,
+                "data": {
+                    "root": block_root,
+                    "canonical": true,
+                    "header": {
+                        "message": {
+                            "slot": "7200000",
+                            "proposer_index": "42",
+                            "parent_root": H256::repeat_byte(0x22),
+                            "state_root": H256::repeat_byte(0x33),
+                            "body_root": H256::repeat_byte(0x44),
+                        },
+                        "signature": "0x00",
+                    },
+                },
+            }),
+        );
+
+        let mut builder = DefaultBuilder::new();
+        let root = builder.read::<Bytes32Variable>();
+        let header = builder.beacon_rpc_get_header(&provider, root);
+        builder.write(header);
+        let circuit = builder.mock_build();
+
+        let mut input = circuit.input();
+        input.write::<Bytes32Variable>(block_root);
+        let (_witness, mut output) = circuit.mock_prove(&input);
+        let header = output.read::<BeaconHeaderVariable>();
+        assert_eq!(header.slot, 7200000);
+        assert_eq!(header.proposer_index, 42);
+        assert_eq!(header.parent_root, H256::repeat_byte(0x22));
+        assert_eq!(header.state_root, H256::repeat_byte(0x33));
+        assert_eq!(header.body_root, H256::repeat_byte(0x44));
+    }
+}
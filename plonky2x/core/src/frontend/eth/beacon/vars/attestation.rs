@@ -0,0 +1,55 @@
+use std::fmt::Debug;
+
+use plonky2::hash::hash_types::RichField;
+use plonky2x_derive::CircuitVariable;
+
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::builder::CircuitBuilder;
+use crate::frontend::uint::uint64::U64Variable;
+use crate::frontend::vars::{Bytes32Variable, CircuitVariable, SSZVariable};
+use crate::prelude::Variable;
+
+/// A checkpoint of the beacon chain, the root of the block at the start of an epoch.
+#[derive(Debug, Clone, Copy, CircuitVariable)]
+#[value_name(BeaconCheckpointValue)]
+pub struct BeaconCheckpointVariable {
+    pub epoch: U64Variable,
+    pub root: Bytes32Variable,
+}
+
+/// The data of an attestation, which is what the validators of a committee sign.
+#[derive(Debug, Clone, Copy, CircuitVariable)]
+#[value_name(BeaconAttestationDataValue)]
+pub struct BeaconAttestationDataVariable {
+    pub slot: U64Variable,
+    pub index: U64Variable,
+    pub beacon_block_root: Bytes32Variable,
+    pub source: BeaconCheckpointVariable,
+    pub target: BeaconCheckpointVariable,
+}
+
+impl SSZVariable for BeaconCheckpointVariable {
+    fn hash_tree_root<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> Bytes32Variable {
+        let epoch_leaf = self.epoch.hash_tree_root(builder);
+        builder.sha256_pair(epoch_leaf, self.root)
+    }
+}
+
+impl SSZVariable for BeaconAttestationDataVariable {
+    fn hash_tree_root<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> Bytes32Variable {
+        let leaves = [
+            self.slot.hash_tree_root(builder),
+            self.index.hash_tree_root(builder),
+            self.beacon_block_root,
+            self.source.hash_tree_root(builder),
+            self.target.hash_tree_root(builder),
+        ];
+        builder.ssz_merkleize_chunks(&leaves, leaves.len())
+    }
+}
@@ -6,10 +6,33 @@ use plonky2x_derive::CircuitVariable;
 use crate::backend::circuit::PlonkParameters;
 use crate::frontend::builder::CircuitBuilder;
 use crate::frontend::uint::uint64::U64Variable;
-use crate::frontend::vars::{Bytes32Variable, CircuitVariable, SSZVariable};
+use crate::frontend::vars::{
+    ArrayVariable, Bytes32Variable, BytesVariable, CircuitVariable, EvmVariable, SSZVariable,
+};
 use crate::prelude::{ByteVariable, Variable};
 use crate::utils::bytes32;
 
+/// The number of bytes of the SSZ serialization of a block header.
+pub const BEACON_HEADER_SSZ_LEN: usize = 112;
+
+/// The depth of the tree of the fields of a block header.
+pub const BEACON_HEADER_PROOF_DEPTH: usize = 3;
+
+/// The gindex for blockRoot -> slot.
+pub const BEACON_HEADER_SLOT_GINDEX: u64 = 8;
+
+/// The gindex for blockRoot -> proposerIndex.
+pub const BEACON_HEADER_PROPOSER_INDEX_GINDEX: u64 = 9;
+
+/// The gindex for blockRoot -> parentRoot.
+pub const BEACON_HEADER_PARENT_ROOT_GINDEX: u64 = 10;
+
+/// The gindex for blockRoot -> stateRoot.
+pub const BEACON_HEADER_STATE_ROOT_GINDEX: u64 = 11;
+
+/// The gindex for blockRoot -> bodyRoot.
+pub const BEACON_HEADER_BODY_ROOT_GINDEX: u64 = 12;
+
 #[derive(Debug, Copy, Clone, CircuitVariable)]
 #[value_name(BeaconHeaderValue)]
 pub struct BeaconHeaderVariable {
@@ -74,15 +97,71 @@ impl SSZVariable for BeaconHeaderVariable {
     }
 }
 
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Decodes a block header from its SSZ serialization, whose integers are little-endian.
+    pub fn beacon_decode_header(
+        &mut self,
+        bytes: &BytesVariable<BEACON_HEADER_SSZ_LEN>,
+    ) -> BeaconHeaderVariable {
+        let mut slot = bytes[0..8].to_vec();
+        slot.reverse();
+        let mut proposer_index = bytes[8..16].to_vec();
+        proposer_index.reverse();
+        BeaconHeaderVariable {
+            slot: U64Variable::decode(self, &slot),
+            proposer_index: U64Variable::decode(self, &proposer_index),
+            parent_root: Bytes32Variable::from(&bytes[16..48]),
+            state_root: Bytes32Variable::from(&bytes[48..80]),
+            body_root: Bytes32Variable::from(&bytes[80..112]),
+        }
+    }
+
+    /// Computes the root of a block header.
+    pub fn beacon_header_root(&mut self, header: &BeaconHeaderVariable) -> Bytes32Variable {
+        header.hash_tree_root(self)
+    }
+
+    /// Verifies a proof of `state_root` against the trusted root of its block header.
+    pub fn beacon_verify_header_state_root(
+        &mut self,
+        block_root: Bytes32Variable,
+        state_root: Bytes32Variable,
+        proof: &ArrayVariable<Bytes32Variable, BEACON_HEADER_PROOF_DEPTH>,
+    ) {
+        self.ssz_verify_proof_const(
+            block_root,
+            state_root,
+            proof.as_slice(),
+            BEACON_HEADER_STATE_ROOT_GINDEX,
+        );
+    }
+
+    /// Verifies a proof of `body_root` against the trusted root of its block header.
+    pub fn beacon_verify_header_body_root(
+        &mut self,
+        block_root: Bytes32Variable,
+        body_root: Bytes32Variable,
+        proof: &ArrayVariable<Bytes32Variable, BEACON_HEADER_PROOF_DEPTH>,
+    ) {
+        self.ssz_verify_proof_const(
+            block_root,
+            body_root,
+            proof.as_slice(),
+            BEACON_HEADER_BODY_ROOT_GINDEX,
+        );
+    }
+}
+
 #[cfg(test)]
 mod test {
     use std::env;
 
-    use ethers::types::U64;
+    use ethers::types::{H256, U64};
 
     use super::*;
     use crate::prelude::DefaultParameters;
     use crate::utils::eth::beacon::BeaconClient;
+    use crate::utils::hash::sha256;
 
     type L = DefaultParameters;
     const D: usize = 2;
@@ -116,4 +195,80 @@ mod test {
         let input = circuit.input();
         circuit.mock_prove(&input);
     }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_beacon_header_proofs() {
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let bytes = builder.read::<BytesVariable<BEACON_HEADER_SSZ_LEN>>();
+        let state_proof =
+            builder.read::<ArrayVariable<Bytes32Variable, BEACON_HEADER_PROOF_DEPTH>>();
+        let body_proof =
+            builder.read::<ArrayVariable<Bytes32Variable, BEACON_HEADER_PROOF_DEPTH>>();
+        let header = builder.beacon_decode_header(&bytes);
+        let block_root = builder.beacon_header_root(&header);
+        builder.beacon_verify_header_state_root(block_root, header.state_root, &state_proof);
+        builder.beacon_verify_header_body_root(block_root, header.body_root, &body_proof);
+        builder.write(header);
+        builder.write(block_root);
+        let circuit = builder.mock_build();
+
+        let header = BeaconHeaderValue::<<L as PlonkParameters<D>>::Field> {
+            slot: 7404237,
+            proposer_index: 1234,
+            parent_root: H256::repeat_byte(1),
+            state_root: H256::repeat_byte(2),
+            body_root: H256::repeat_byte(3),
+        };
+        let mut serialized = Vec::new();
+        serialized.extend(header.slot.to_le_bytes());
+        serialized.extend(header.proposer_index.to_le_bytes());
+        serialized.extend(header.parent_root.as_bytes());
+        serialized.extend(header.state_root.as_bytes());
+        serialized.extend(header.body_root.as_bytes());
+
+        // The leaves are the chunks of the fields, padded with zeros to a power of two.
+        let mut layers = vec![serialized
+            .chunks(8)
+            .take(2)
+            .map(|chunk| H256::from_slice(&[chunk, &[0u8; 24]].concat()))
+            .chain(serialized[16..].chunks(32).map(H256::from_slice))
+            .chain([H256::zero(); 3])
+            .collect::<Vec<_>>()];
+        while layers.last().unwrap().len() > 1 {
+            let layer = layers
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| H256(sha256(&[pair[0].as_bytes(), pair[1].as_bytes()].concat())))
+                .collect::<Vec<_>>();
+            layers.push(layer);
+        }
+        let proof = |gindex: u64| {
+            (0..BEACON_HEADER_PROOF_DEPTH)
+                .map(|level| {
+                    let index =
+                        (gindex >> level) as usize - (1 << (BEACON_HEADER_PROOF_DEPTH - level));
+                    layers[level][index ^ 1]
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let mut input = circuit.input();
+        input.write::<BytesVariable<BEACON_HEADER_SSZ_LEN>>(serialized.clone().try_into().unwrap());
+        input.write::<ArrayVariable<Bytes32Variable, BEACON_HEADER_PROOF_DEPTH>>(proof(
+            BEACON_HEADER_STATE_ROOT_GINDEX,
+        ));
+        input.write::<ArrayVariable<Bytes32Variable, BEACON_HEADER_PROOF_DEPTH>>(proof(
+            BEACON_HEADER_BODY_ROOT_GINDEX,
+        ));
+        let (_witness, mut output) = circuit.mock_prove(&input);
+        let decoded = output.read::<BeaconHeaderVariable>();
+        assert_eq!(decoded.slot, header.slot);
+        assert_eq!(decoded.proposer_index, header.proposer_index);
+        assert_eq!(decoded.parent_root, header.parent_root);
+        assert_eq!(decoded.state_root, header.state_root);
+        assert_eq!(decoded.body_root, header.body_root);
+        assert_eq!(output.read::<Bytes32Variable>(), layers[3][0]);
+    }
 }
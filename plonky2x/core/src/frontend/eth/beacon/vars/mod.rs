@@ -1,3 +1,4 @@
+mod attestation;
 mod balances;
 mod compressed_validator;
 mod header;
@@ -6,6 +7,7 @@ mod validators;
 mod withdrawal;
 mod withdrawals;
 
+pub use attestation::*;
 pub use balances::*;
 pub use compressed_validator::*;
 pub use header::*;
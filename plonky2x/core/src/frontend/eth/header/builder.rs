@@ -0,0 +1,357 @@
+use plonky2::field::types::Field;
+
+use super::vars::{HeaderVariable, LOGS_BLOOM_LEN};
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::builder::CircuitBuilder;
+use crate::frontend::eth::rlp::vars::RLPFieldVariable;
+use crate::frontend::uint::uint64::U64Variable;
+use crate::frontend::vars::{
+    ArrayVariable, BoolVariable, ByteVariable, Bytes32Variable, BytesVariable, CircuitVariable,
+    EvmVariable, U32Variable, Variable,
+};
+
+/// The number of fields of a header before London.
+const MIN_HEADER_FIELDS: usize = 15;
+
+/// The maximum number of fields of a header, since Prague.
+pub(crate) const MAX_HEADER_FIELDS: usize = 21;
+
+/// The offset of the logs bloom in the encoding of a header, which is preceded by the 3-byte list
+/// header, the 186 bytes of the first six fields and its own 3-byte string header.
+const LOGS_BLOOM_OFFSET: usize = 192;
+
+/// The number of bits of the logs bloom set for every address or topic.
+const LOGS_BLOOM_HASHES: usize = 3;
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Decodes the RLP encoding of a block header, padded to `ENCODING_LEN` bytes.
+    ///
+    /// The header is the list `[parent_hash, ommers_hash, coinbase, state_root,
+    /// transactions_root, receipts_root, logs_bloom, difficulty, number, gas_limit, gas_used,
+    /// timestamp, extra_data, mix_hash, nonce]`, to which London appends `base_fee`, Shanghai
+    /// `withdrawals_root`, and Cancun `blob_gas_used`, `excess_blob_gas` and
+    /// `parent_beacon_block_root`. Fields missing from the header are decoded as zero.
+    pub fn decode_header<const ENCODING_LEN: usize>(
+        &mut self,
+        rlp_bytes: &ArrayVariable<ByteVariable, ENCODING_LEN>,
+    ) -> HeaderVariable {
+        let node = rlp_bytes
+            .as_slice()
+            .iter()
+            .map(|byte| byte.to_variable(self))
+            .collect::<Vec<_>>();
+        let zero = self.zero::<Variable>();
+        let true_v = self._true();
+        let (fields, num_fields) = self.rlp_decode_list::<MAX_HEADER_FIELDS>(&node, zero);
+        let min_fields =
+            self.constant::<Variable>(L::Field::from_canonical_usize(MIN_HEADER_FIELDS));
+        let has_min_fields = self.lte(min_fields, num_fields);
+        self.assert_is_equal(has_min_fields, true_v);
+
+        // The fields before the logs bloom have a fixed length, so it is read at a fixed offset.
+        let bloom_offset =
+            self.constant::<Variable>(L::Field::from_canonical_usize(LOGS_BLOOM_OFFSET));
+        let bloom_len = self.constant::<Variable>(L::Field::from_canonical_usize(LOGS_BLOOM_LEN));
+        self.assert_is_equal(fields[6].offset, bloom_offset);
+        self.assert_is_equal(fields[6].len, bloom_len);
+        let logs_bloom = BytesVariable(
+            rlp_bytes.as_slice()[LOGS_BLOOM_OFFSET..LOGS_BLOOM_OFFSET + LOGS_BLOOM_LEN]
+                .try_into()
+                .unwrap(),
+        );
+
+        let base_fee = self.rlp_field_scalar(&node, &fields[15]);
+        HeaderVariable {
+            parent_hash: self.header_hash_field(&node, &fields, num_fields, 0),
+            state_root: self.header_hash_field(&node, &fields, num_fields, 3),
+            transactions_root: self.header_hash_field(&node, &fields, num_fields, 4),
+            receipts_root: self.header_hash_field(&node, &fields, num_fields, 5),
+            logs_bloom,
+            number: self.header_u64_field(&node, &fields[8]),
+            timestamp: self.header_u64_field(&node, &fields[11]),
+            base_fee: self.bytes32_to_u256(base_fee),
+            withdrawals_root: self.header_hash_field(&node, &fields, num_fields, 16),
+            blob_gas_used: self.header_u64_field(&node, &fields[17]),
+            excess_blob_gas: self.header_u64_field(&node, &fields[18]),
+            parent_beacon_block_root: self.header_hash_field(&node, &fields, num_fields, 19),
+            num_fields,
+        }
+    }
+
+    /// Returns the block hash of a header, which is the keccak256 digest of its RLP encoding,
+    /// padded to `ENCODING_LEN` bytes.
+    pub fn header_hash<const ENCODING_LEN: usize>(
+        &mut self,
+        rlp_bytes: &ArrayVariable<ByteVariable, ENCODING_LEN>,
+    ) -> Bytes32Variable {
+        let node = rlp_bytes
+            .as_slice()
+            .iter()
+            .map(|byte| byte.to_variable(self))
+            .collect::<Vec<_>>();
+        let zero = self.zero::<Variable>();
+        let (payload_start, payload_len, _) = self.rlp_item_header(&node, zero);
+        let len = self.add(payload_start, payload_len);
+        let len = U32Variable::from_variables(self, &[len]);
+        self.keccak256_variable(rlp_bytes.as_slice(), len)
+    }
+
+    /// Verifies that `headers` is a chain of consecutive blocks, from the oldest to the newest,
+    /// and returns the hashes of the oldest and of the newest block.
+    ///
+    /// Every header must have the hash of the previous one as its parent hash, the number of the
+    /// previous one plus one as its number, and a later timestamp.
+    pub fn verify_header_chain<const ENCODING_LEN: usize, const N: usize>(
+        &mut self,
+        headers: &ArrayVariable<ArrayVariable<ByteVariable, ENCODING_LEN>, N>,
+    ) -> (Bytes32Variable, Bytes32Variable) {
+        assert!(N > 0, "the chain must have at least one header");
+        let true_v = self._true();
+        let one = self.one::<U64Variable>();
+
+        let mut hashes = Vec::with_capacity(N);
+        let mut previous: Option<HeaderVariable> = None;
+        for rlp_bytes in headers.as_slice().iter() {
+            let header = self.decode_header(rlp_bytes);
+            if let Some(parent) = previous {
+                self.assert_is_equal(header.parent_hash, *hashes.last().unwrap());
+                let expected_number = self.add(parent.number, one);
+                self.assert_is_equal(header.number, expected_number);
+                let is_later = self.lt(parent.timestamp, header.timestamp);
+                self.assert_is_equal(is_later, true_v);
+            }
+            hashes.push(self.header_hash(rlp_bytes));
+            previous = Some(header);
+        }
+        (hashes[0], hashes[N - 1])
+    }
+
+    /// Returns whether `value`, the bytes of an address or of a topic, may be in `logs_bloom`.
+    ///
+    /// The value is in the bloom if the three bits given by the low 11 bits of the first three
+    /// pairs of bytes of its keccak256 digest are set, counting from the last byte of the bloom.
+    /// A value that is not in the bloom was not logged, but a value in the bloom may not have been.
+    pub fn logs_bloom_contains(
+        &mut self,
+        logs_bloom: &BytesVariable<LOGS_BLOOM_LEN>,
+        value: &[ByteVariable],
+    ) -> BoolVariable {
+        let hash = self.keccak256(value).as_bytes();
+        let const_255 = self.constant::<Variable>(L::Field::from_canonical_u8(255));
+        let mut contains = self._true();
+        for i in 0..LOGS_BLOOM_HASHES {
+            // The bit index is `((hash[2i] & 7) << 8) | hash[2i + 1]`, of which the low 3 bits
+            // index the bit in its byte and the high 8 bits the byte from the end of the bloom.
+            let high_bits = hash[2 * i].as_le_bits();
+            let low_bits = hash[2 * i + 1].as_le_bits();
+            let byte_bits = low_bits[3..8].iter().chain(high_bits[..3].iter());
+            let mut byte_from_end = self.zero::<Variable>();
+            for (k, bit) in byte_bits.enumerate() {
+                let power = self.constant::<Variable>(L::Field::from_canonical_u64(1 << k));
+                let term = self.mul(bit.variable, power);
+                byte_from_end = self.add(byte_from_end, term);
+            }
+            let byte_index = self.sub(const_255, byte_from_end);
+            let [byte] = self.select_subarray::<1>(&logs_bloom.0, byte_index);
+
+            let mut bit_index = self.zero::<Variable>();
+            for (k, bit) in low_bits[..3].iter().enumerate() {
+                let power = self.constant::<Variable>(L::Field::from_canonical_u64(1 << k));
+                let term = self.mul(bit.variable, power);
+                bit_index = self.add(bit_index, term);
+            }
+            let is_set = self.select_index(&byte.as_le_bits(), bit_index);
+            contains = self.and(contains, is_set);
+        }
+        contains
+    }
+
+    /// Returns the hash at `fields[index]`, asserting that it is 32 bytes long if the header has
+    /// this field.
+    fn header_hash_field(
+        &mut self,
+        node: &[Variable],
+        fields: &[RLPFieldVariable],
+        num_fields: Variable,
+        index: usize,
+    ) -> Bytes32Variable {
+        let zero = self.zero::<Variable>();
+        let const_32 = self.constant::<Variable>(L::Field::from_canonical_u8(32));
+        let index_variable = self.constant::<Variable>(L::Field::from_canonical_usize(index));
+        let is_present = self.lt(index_variable, num_fields);
+        let expected_len = self.select(is_present, const_32, zero);
+        self.assert_is_equal(fields[index].len, expected_len);
+        self.rlp_field_scalar(node, &fields[index])
+    }
+
+    /// Returns the scalar `field` as a `U64Variable`, asserting that it fits in 8 bytes.
+    fn header_u64_field(&mut self, node: &[Variable], field: &RLPFieldVariable) -> U64Variable {
+        let true_v = self._true();
+        let const_8 = self.constant::<Variable>(L::Field::from_canonical_u8(8));
+        let fits = self.lte(field.len, const_8);
+        self.assert_is_equal(fits, true_v);
+        let scalar = self.rlp_field_scalar(node, field);
+        U64Variable::decode(self, &scalar.as_bytes()[24..])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ethers::types::{Bloom, BloomInput, H160, H256, U256};
+    use ethers::utils::keccak256;
+    use ethers::utils::rlp::RlpStream;
+
+    use super::*;
+    use crate::frontend::eth::header::vars::Header;
+    use crate::frontend::eth::vars::AddressVariable;
+    use crate::prelude::{DefaultBuilder, GoldilocksField};
+
+    type F = GoldilocksField;
+
+    /// A logs bloom holding the address `0x1111..` and the topic `0xeeee..`.
+    fn test_bloom() -> Bloom {
+        let mut bloom = Bloom::zero();
+        bloom.accrue(BloomInput::Raw(H160::repeat_byte(0x11).as_bytes()));
+        bloom.accrue(BloomInput::Raw(H256::repeat_byte(0xee).as_bytes()));
+        bloom
+    }
+
+    /// Encodes a header with `num_fields` fields, where the hash at `i` is filled with `i + 1`.
+    fn encode_header(num_fields: usize, parent_hash: H256, number: u64, timestamp: u64) -> Vec<u8> {
+        let mut stream = RlpStream::new_list(num_fields);
+        for i in 0..num_fields {
+            match i {
+                0 => stream.append(&parent_hash.as_bytes().to_vec()),
+                2 => stream.append(&vec![0x11u8; 20]),
+                6 => stream.append(&test_bloom().as_bytes().to_vec()),
+                7 | 9 | 10 | 14 | 17 | 18 => stream.append(&(i as u64)),
+                8 => stream.append(&number),
+                11 => stream.append(&timestamp),
+                12 => stream.append(&b"extra".to_vec()),
+                15 => stream.append(&U256::from(25_000_000_000u64)),
+                _ => stream.append(&H256::repeat_byte(i as u8 + 1).as_bytes().to_vec()),
+            };
+        }
+        stream.out().to_vec()
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_decode_header() {
+        const ENCODING_LEN: usize = 640;
+
+        let mut builder = DefaultBuilder::new();
+        let rlp_bytes = builder.read::<ArrayVariable<ByteVariable, ENCODING_LEN>>();
+        let header = builder.decode_header(&rlp_bytes);
+        builder.write(header);
+        let circuit = builder.mock_build();
+
+        // London, Shanghai and Cancun headers.
+        for num_fields in [16usize, 17, 20] {
+            let mut encoding =
+                encode_header(num_fields, H256::repeat_byte(1), 18_000_000, 1_700_000_000);
+            encoding.resize(ENCODING_LEN, 0);
+            let mut input = circuit.input();
+            input.write::<ArrayVariable<ByteVariable, ENCODING_LEN>>(encoding);
+            let (_witness, mut output) = circuit.mock_prove(&input);
+
+            let hash_if_present = |i: usize| {
+                if i < num_fields {
+                    H256::repeat_byte(i as u8 + 1)
+                } else {
+                    H256::zero()
+                }
+            };
+            let u64_if_present = |i: usize| if i < num_fields { i as u64 } else { 0 };
+            assert_eq!(
+                output.read::<HeaderVariable>(),
+                Header {
+                    parent_hash: hash_if_present(0),
+                    state_root: hash_if_present(3),
+                    transactions_root: hash_if_present(4),
+                    receipts_root: hash_if_present(5),
+                    logs_bloom: test_bloom().0,
+                    number: 18_000_000,
+                    timestamp: 1_700_000_000,
+                    base_fee: U256::from(25_000_000_000u64),
+                    withdrawals_root: hash_if_present(16),
+                    blob_gas_used: u64_if_present(17),
+                    excess_blob_gas: u64_if_present(18),
+                    parent_beacon_block_root: hash_if_present(19),
+                    num_fields: F::from_canonical_usize(num_fields),
+                }
+            );
+        }
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_verify_header_chain() {
+        const ENCODING_LEN: usize = 640;
+        const N: usize = 3;
+
+        let mut builder = DefaultBuilder::new();
+        let headers = builder.read::<ArrayVariable<ArrayVariable<ByteVariable, ENCODING_LEN>, N>>();
+        let (oldest, newest) = builder.verify_header_chain(&headers);
+        builder.write(oldest);
+        builder.write(newest);
+        let circuit = builder.mock_build();
+
+        let mut parent_hash = H256::repeat_byte(0xaa);
+        let mut encodings = Vec::new();
+        let mut hashes = Vec::new();
+        for i in 0..N as u64 {
+            let encoding = encode_header(20, parent_hash, 18_000_000 + i, 1_700_000_000 + 12 * i);
+            parent_hash = H256(keccak256(&encoding));
+            hashes.push(parent_hash);
+            encodings.push(encoding);
+        }
+
+        let mut input = circuit.input();
+        input.write::<ArrayVariable<ArrayVariable<ByteVariable, ENCODING_LEN>, N>>(
+            encodings
+                .into_iter()
+                .map(|mut encoding| {
+                    encoding.resize(ENCODING_LEN, 0);
+                    encoding
+                })
+                .collect(),
+        );
+        let (_witness, mut output) = circuit.mock_prove(&input);
+        assert_eq!(output.read::<Bytes32Variable>(), hashes[0]);
+        assert_eq!(output.read::<Bytes32Variable>(), hashes[N - 1]);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_logs_bloom_contains() {
+        let mut builder = DefaultBuilder::new();
+        let logs_bloom = builder.read::<BytesVariable<LOGS_BLOOM_LEN>>();
+        let address = builder.read::<AddressVariable>();
+        let topics = builder.read::<ArrayVariable<Bytes32Variable, 2>>();
+        let contains_address = builder.logs_bloom_contains(&logs_bloom, &address.0 .0);
+        builder.write(contains_address);
+        for topic in topics.as_slice().iter() {
+            let contains_topic = builder.logs_bloom_contains(&logs_bloom, &topic.as_bytes());
+            builder.write(contains_topic);
+        }
+        let circuit = builder.mock_build();
+
+        let bloom = test_bloom();
+        let absent_topic = H256::repeat_byte(0x01);
+        let mut input = circuit.input();
+        input.write::<BytesVariable<LOGS_BLOOM_LEN>>(bloom.0);
+        input.write::<AddressVariable>(H160::repeat_byte(0x11));
+        input.write::<ArrayVariable<Bytes32Variable, 2>>(vec![
+            H256::repeat_byte(0xee),
+            absent_topic,
+        ]);
+        let (_witness, mut output) = circuit.mock_prove(&input);
+        assert!(output.read::<BoolVariable>());
+        assert!(output.read::<BoolVariable>());
+        assert_eq!(
+            output.read::<BoolVariable>(),
+            bloom.contains_input(BloomInput::Raw(absent_topic.as_bytes()))
+        );
+    }
+}
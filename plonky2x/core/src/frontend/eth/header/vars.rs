@@ -0,0 +1,38 @@
+use std::fmt::Debug;
+
+use plonky2::hash::hash_types::RichField;
+use plonky2x_derive::CircuitVariable;
+
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::builder::CircuitBuilder;
+use crate::frontend::uint::uint64::U64Variable;
+use crate::frontend::vars::{Bytes32Variable, BytesVariable, CircuitVariable, U256Variable};
+use crate::prelude::Variable;
+
+/// The size of the logs bloom of a block header.
+pub const LOGS_BLOOM_LEN: usize = 256;
+
+/// The fields of a block header decoded in-circuit from its RLP encoding.
+///
+/// Fields introduced by later hard forks are zero for blocks that predate them: `base_fee` for
+/// London, `withdrawals_root` for Shanghai, and the blob gas fields and
+/// `parent_beacon_block_root` for Cancun. `num_fields` is the number of fields of the header,
+/// which identifies its fork.
+#[derive(Debug, Clone, Copy, CircuitVariable)]
+#[value_name(Header)]
+#[value_derive(PartialEq, Eq)]
+pub struct HeaderVariable {
+    pub parent_hash: Bytes32Variable,
+    pub state_root: Bytes32Variable,
+    pub transactions_root: Bytes32Variable,
+    pub receipts_root: Bytes32Variable,
+    pub logs_bloom: BytesVariable<LOGS_BLOOM_LEN>,
+    pub number: U64Variable,
+    pub timestamp: U64Variable,
+    pub base_fee: U256Variable,
+    pub withdrawals_root: Bytes32Variable,
+    pub blob_gas_used: U64Variable,
+    pub excess_blob_gas: U64Variable,
+    pub parent_beacon_block_root: Bytes32Variable,
+    pub num_fields: Variable,
+}
@@ -0,0 +1,221 @@
+use ethers::types::H256;
+use num::{BigUint, One};
+
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::builder::CircuitBuilder;
+use crate::frontend::ecc::bls12_381::field::Bls12381Scalar;
+use crate::frontend::ecc::bls12_381::g1::{Bls12381G1, Bls12381G1PointVariable};
+use crate::frontend::ecc::bls12_381::g2::{Bls12381G2Point, Bls12381G2PointVariable};
+use crate::frontend::ecc::nonnative::NonNativeFieldVariable;
+use crate::frontend::ecc::weierstrass::WeierstrassCurve;
+use crate::frontend::eth::vars::BLSPubkeyVariable;
+use crate::frontend::uint::uint256::U256Variable;
+use crate::frontend::vars::{ByteVariable, Bytes32Variable, BytesVariable, EvmVariable};
+use crate::utils::hash::sha256;
+
+/// The number of bytes of a KZG commitment, which is a compressed point of G1.
+pub const KZG_COMMITMENT_LEN: usize = 48;
+
+/// The number of bytes of a KZG proof, which is a compressed point of G1.
+pub const KZG_PROOF_LEN: usize = 48;
+
+/// The version byte of the versioned hashes of KZG commitments, from EIP-4844.
+pub const VERSIONED_HASH_VERSION_KZG: u8 = 0x01;
+
+/// Returns the versioned hash of a KZG commitment, which is its SHA-256 digest with the first byte
+/// replaced by the version.
+pub fn kzg_to_versioned_hash(commitment: &[u8; KZG_COMMITMENT_LEN]) -> H256 {
+    let mut hash = sha256(commitment);
+    hash[0] = VERSIONED_HASH_VERSION_KZG;
+    H256(hash)
+}
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Computes the versioned hash of a KZG commitment, as in `kzg_to_versioned_hash`.
+    pub fn kzg_to_versioned_hash(
+        &mut self,
+        commitment: &BytesVariable<KZG_COMMITMENT_LEN>,
+    ) -> Bytes32Variable {
+        let hash = self.sha256(&commitment.0);
+        let mut bytes = hash.as_bytes();
+        bytes[0] = self.constant::<ByteVariable>(VERSIONED_HASH_VERSION_KZG);
+        Bytes32Variable::from(bytes)
+    }
+
+    /// Verifies that `versioned_hash`, such as a blob hash of a transaction, is the versioned hash
+    /// of `commitment`, and returns the point of G1 that the commitment encodes.
+    ///
+    /// The commitment is decompressed and checked to be on the curve, but not to be in the
+    /// subgroup of G1, which is checked by `kzg_verify_proof`, and the commitment of the empty
+    /// blob, which is the point at infinity, is not supported.
+    pub fn kzg_verify_versioned_hash(
+        &mut self,
+        commitment: &BytesVariable<KZG_COMMITMENT_LEN>,
+        versioned_hash: Bytes32Variable,
+    ) -> Bls12381G1PointVariable {
+        let expected_hash = self.kzg_to_versioned_hash(commitment);
+        self.assert_is_equal(expected_hash, versioned_hash);
+        self.bls12_381_g1_decompress(&BLSPubkeyVariable(*commitment))
+    }
+
+    /// Verifies a KZG proof that the polynomial of `commitment`, as returned by
+    /// `kzg_verify_versioned_hash`, evaluates to `y` at `z`, as the point evaluation precompile of
+    /// EIP-4844 does.
+    ///
+    /// `z` and `y` are big-endian elements of the scalar field, and `setup_g2` is the point
+    /// `tau * G2` of the trusted setup. The commitment and the proof are checked to be in G1, and
+    /// the check is `e(commitment - y * G1 + z * proof, G2) * e(-proof, setup_g2) == 1`. The proofs
+    /// of the constant polynomials, which are the point at infinity, are not supported.
+    pub fn kzg_verify_proof(
+        &mut self,
+        commitment: &Bls12381G1PointVariable,
+        z: Bytes32Variable,
+        y: Bytes32Variable,
+        proof: &BytesVariable<KZG_PROOF_LEN>,
+        setup_g2: &Bls12381G2Point,
+    ) {
+        let true_v = self._true();
+        let proof = self.bls12_381_g1_decompress(&BLSPubkeyVariable(*proof));
+        self.bls12_381_g1_assert_in_subgroup(commitment);
+        self.bls12_381_g1_assert_in_subgroup(&proof);
+
+        let z = self.kzg_decode_scalar(z);
+        let y = self.kzg_decode_scalar(y);
+        let neg_y = self.nonnative_neg(&y);
+        let one = self.constant::<NonNativeFieldVariable<Bls12381Scalar>>(BigUint::one());
+        let tables = [
+            self.weierstrass_fixed_base_table::<Bls12381G1>(Bls12381G1::generator()),
+            self.weierstrass_variable_base_table(&proof),
+            self.weierstrass_variable_base_table(commitment),
+        ];
+        let lhs = self.weierstrass_windowed_msm(&[neg_y, z, one], &tables);
+
+        let neg_proof = Bls12381G1PointVariable {
+            x: proof.x.clone(),
+            y: self.nonnative_neg(&proof.y),
+        };
+        let generator = self.constant::<Bls12381G2PointVariable>(Bls12381G2Point::generator());
+        let setup_g2 = self.constant::<Bls12381G2PointVariable>(setup_g2.clone());
+        let is_valid = self.bls12_381_pairing_check(&[(lhs, generator), (neg_proof, setup_g2)]);
+        self.assert_is_equal(is_valid, true_v);
+    }
+
+    /// Decodes a big-endian element of the scalar field of BLS12-381, asserting that it is
+    /// reduced.
+    fn kzg_decode_scalar(
+        &mut self,
+        bytes: Bytes32Variable,
+    ) -> NonNativeFieldVariable<Bls12381Scalar> {
+        let true_v = self._true();
+        let value = U256Variable::decode(self, &bytes.as_bytes()).to_biguint();
+        let is_reduced = self.nonnative_is_reduced::<Bls12381Scalar>(&value);
+        self.assert_is_equal(is_reduced, true_v);
+        NonNativeFieldVariable::from_biguint_low(&value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frontend::ecc::bls12_381::field::Bls12381Base;
+    use crate::frontend::ecc::bls12_381::fp2::Bls12381Fp2;
+    use crate::frontend::ecc::nonnative::NonNativeField;
+    use crate::frontend::ecc::weierstrass::mul_native;
+    use crate::prelude::DefaultBuilder;
+    use crate::utils::bytes;
+
+    /// The secret of the trusted setup of the tests.
+    const TAU: u64 = 0x1234567;
+
+    fn compress(point: &(BigUint, BigUint)) -> [u8; 48] {
+        let mut bytes = [0u8; 48];
+        let x = point.0.to_bytes_be();
+        bytes[48 - x.len()..].copy_from_slice(&x);
+        bytes[0] |= 0x80;
+        if point.1 > (Bls12381Base::modulus() - 1u32) / 2u32 {
+            bytes[0] |= 0x20;
+        }
+        bytes
+    }
+
+    /// Returns `TAU * G2`.
+    fn setup_g2() -> Bls12381G2Point {
+        Bls12381G2Point {
+            x: Bls12381Fp2::from_hex(
+                "0e2c1d2736f8f721cd5ed79528c704e9c3073baa5f42f5e300b7591d7f71ead93d6bd730abe44c35a85b5592c89f6455",
+                "195430fc3a9c714f47096c7f1a4f894a99fbfbb28af4bceb9b9b4c507bcc3348a42f6eab72e66e855d68063b386315ac",
+            ),
+            y: Bls12381Fp2::from_hex(
+                "16668269ca44e4ca0c2efd8cf772dd9d51002d420b53c747dfdab627d2b63fb0e5032c7b0dee4378dbd7ce42ffa7740e",
+                "150d1abb87be12ae98333414c2877df5f424f8897c0895061f2950259aa0f1e4a12c35a078e36d3f6976b4a9fee7f1c4",
+            ),
+        }
+    }
+
+    fn prove_kzg_proof(y: u64) {
+        let mut builder = DefaultBuilder::new();
+        let commitment = builder.read::<BytesVariable<KZG_COMMITMENT_LEN>>();
+        let versioned_hash = builder.read::<Bytes32Variable>();
+        let z = builder.read::<Bytes32Variable>();
+        let y_var = builder.read::<Bytes32Variable>();
+        let proof = builder.read::<BytesVariable<KZG_PROOF_LEN>>();
+        let point = builder.kzg_verify_versioned_hash(&commitment, versioned_hash);
+        builder.kzg_verify_proof(&point, z, y_var, &proof, &setup_g2());
+        let circuit = builder.mock_build();
+
+        // The polynomial is `3 + 5 * X + 7 * X^2`, which is `905` at `11`, and the quotient of its
+        // difference with `905` by `X - 11` is `82 + 7 * X`.
+        let tau = BigUint::from(TAU);
+        let generator = Bls12381G1::generator();
+        let evaluation = BigUint::from(3u32) + &tau * 5u32 + &tau * &tau * 7u32;
+        let quotient = &tau * 7u32 + 82u32;
+        let commitment = compress(&mul_native::<Bls12381G1>(&evaluation, &generator).unwrap());
+        let proof = compress(&mul_native::<Bls12381G1>(&quotient, &generator).unwrap());
+
+        let mut input = circuit.input();
+        input.write::<BytesVariable<KZG_COMMITMENT_LEN>>(commitment);
+        input.write::<Bytes32Variable>(kzg_to_versioned_hash(&commitment));
+        input.write::<Bytes32Variable>(H256::from_low_u64_be(11));
+        input.write::<Bytes32Variable>(H256::from_low_u64_be(y));
+        input.write::<BytesVariable<KZG_PROOF_LEN>>(proof);
+        circuit.mock_prove(&input);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_kzg_verify_proof() {
+        prove_kzg_proof(905);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    #[should_panic(expected = "was set twice with different values")]
+    fn test_kzg_verify_proof_rejects_wrong_evaluation() {
+        prove_kzg_proof(906);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_kzg_verify_versioned_hash() {
+        let mut builder = DefaultBuilder::new();
+        let commitment = builder.read::<BytesVariable<KZG_COMMITMENT_LEN>>();
+        let versioned_hash = builder.read::<Bytes32Variable>();
+        let point = builder.kzg_verify_versioned_hash(&commitment, versioned_hash);
+        builder.write(point);
+        let circuit = builder.mock_build();
+
+        // The commitment of the blob whose polynomial is the constant one is the generator.
+        let commitment: [u8; KZG_COMMITMENT_LEN] = bytes!("0x97f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb");
+        let versioned_hash = kzg_to_versioned_hash(&commitment);
+        assert_eq!(versioned_hash.0[0], VERSIONED_HASH_VERSION_KZG);
+
+        let mut input = circuit.input();
+        input.write::<BytesVariable<KZG_COMMITMENT_LEN>>(commitment);
+        input.write::<Bytes32Variable>(versioned_hash);
+        let (_witness, mut output) = circuit.mock_prove(&input);
+        assert_eq!(
+            output.read::<Bls12381G1PointVariable>(),
+            Bls12381G1::generator().into()
+        );
+    }
+}
@@ -1,6 +1,12 @@
+pub mod abi;
 pub mod beacon;
+pub mod header;
+pub mod kzg;
 pub mod mpt;
+pub mod receipt;
 pub mod rlp;
+pub mod rpc;
 pub mod storage;
+pub mod transaction;
 pub mod utils;
 pub mod vars;
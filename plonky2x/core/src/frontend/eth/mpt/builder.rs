@@ -1,9 +1,11 @@
+use ethers::types::H256;
 use starkyx::math::field::Field;
 
+use crate::frontend::eth::rlp::vars::RLPFieldVariable;
 use crate::frontend::vars::Nibbles;
 use crate::prelude::{
-    ArrayVariable, ByteVariable, Bytes32Variable, CircuitBuilder, CircuitVariable, PlonkParameters,
-    U32Variable, Variable,
+    ArrayVariable, BoolVariable, ByteVariable, Bytes32Variable, CircuitBuilder, CircuitVariable,
+    PlonkParameters, U32Variable, Variable,
 };
 
 pub fn transform_proof_to_padded<const ENCODING_LEN: usize, const PROOF_LEN: usize>(
@@ -39,23 +41,62 @@ pub fn transform_proof_to_padded<const ENCODING_LEN: usize, const PROOF_LEN: usi
     (padded_elements, lengths)
 }
 
+/// The result of walking an MPT proof with `mpt_lookup`.
+#[derive(Debug, Clone)]
+pub struct MPTLookup<const ENCODING_LEN: usize> {
+    /// The item of `node` holding the value of the key.
+    pub value: RLPFieldVariable,
+    /// The encoding of the node holding the key.
+    pub node: ArrayVariable<ByteVariable, ENCODING_LEN>,
+    /// The length of the encoding of the node holding the key.
+    pub node_len: U32Variable,
+    /// Whether the proof shows that the key is not in the trie.
+    pub excluded: BoolVariable,
+}
+
 impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
-    const PREFIX_EXTENSION_EVEN: u8 = 0;
     const PREFIX_EXTENSION_ODD: u8 = 1;
     const PREFIX_LEAF_EVEN: u8 = 2;
     const PREFIX_LEAF_ODD: u8 = 3;
-    /// Get the validators for a given block root.
-    /// P is the number of proof elements to be considered
-    pub fn verify_mpt_proof<const ENCODING_LEN: usize, const PROOF_LEN: usize>(
+
+    /// Walks the MPT proof of `key` from `root` and returns the node holding the value of `key`,
+    /// or whether the proof shows that `key` is not in the trie.
+    ///
+    /// The trie path is the keccak256 of `key`, as in the state and storage tries. See
+    /// `mpt_lookup_path` for how the proof is checked.
+    pub fn mpt_lookup<const ENCODING_LEN: usize, const PROOF_LEN: usize>(
         &mut self,
-        key: Bytes32Variable,
+        key: &[ByteVariable],
         proof: ArrayVariable<ArrayVariable<ByteVariable, ENCODING_LEN>, PROOF_LEN>,
         len_nodes: ArrayVariable<U32Variable, PROOF_LEN>,
         root: Bytes32Variable,
-        value: Bytes32Variable,
-    ) {
-        const ELEMENT_LEN: usize = 32; // Maximum size of list element
+    ) -> MPTLookup<ENCODING_LEN> {
+        let hash_key = self.keccak256(key);
+        let key_path = hash_key.as_bytes().to_vec().to_nibbles(self);
+        let key_path_len = self.constant::<Variable>(L::Field::from_canonical_u8(64));
+        self.mpt_lookup_path(&key_path, key_path_len, proof, len_nodes, root)
+    }
+
+    /// Walks the MPT proof of the path made of the first `key_path_len` nibbles of `key_path` from
+    /// `root`, and returns the node holding the value at that path, or whether the proof shows that
+    /// there is none.
+    ///
+    /// Every node is hashed in-circuit and checked against the reference held by its parent, and
+    /// decoded with the constrained `rlp_decode_list`, whose items must cover exactly the hashed
+    /// bytes of the node. The nibbles of the path are matched against the key at extension and leaf
+    /// nodes. Exclusion is proven by an empty child in a branch node, or by a leaf or extension
+    /// node whose path diverges from the key. `PROOF_LEN` is the maximum number of nodes, and
+    /// unused nodes must have length zero.
+    pub fn mpt_lookup_path<const ENCODING_LEN: usize, const PROOF_LEN: usize>(
+        &mut self,
+        key_path: &[ByteVariable],
+        key_path_len: Variable,
+        proof: ArrayVariable<ArrayVariable<ByteVariable, ENCODING_LEN>, PROOF_LEN>,
+        len_nodes: ArrayVariable<U32Variable, PROOF_LEN>,
+        root: Bytes32Variable,
+    ) -> MPTLookup<ENCODING_LEN> {
         const LIST_LEN: usize = 17; // Maximum length of the list for each proof element
+        const PATH_LEN: usize = 33; // A prefix byte followed by at most 32 bytes of nibbles
 
         let tree_radix = self.constant::<Variable>(L::Field::from_canonical_u8(16u8));
         let branch_node_length = self.constant::<Variable>(L::Field::from_canonical_u8(17u8));
@@ -63,34 +104,39 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
             self.constant::<Variable>(L::Field::from_canonical_u8(2u8));
         let prefix_leaf_even = self.constant::<ByteVariable>(Self::PREFIX_LEAF_EVEN);
         let prefix_leaf_odd = self.constant::<ByteVariable>(Self::PREFIX_LEAF_ODD);
-        let prefix_extension_even = self.constant::<ByteVariable>(Self::PREFIX_EXTENSION_EVEN);
         let prefix_extension_odd = self.constant::<ByteVariable>(Self::PREFIX_EXTENSION_ODD);
+        let zero = self.zero::<Variable>();
         let one: Variable = self.one::<Variable>();
         let two = self.constant::<Variable>(L::Field::from_canonical_u8(2));
-        let const_64 = self.constant::<Variable>(L::Field::from_canonical_u8(64));
-        let const_32 = self.constant::<U32Variable>(32u32);
-        let const_128 = self.constant::<U32Variable>(128u32);
+        let const_32 = self.constant::<Variable>(L::Field::from_canonical_u8(32));
+        let empty_list = self.constant::<Variable>(L::Field::from_canonical_u8(0xc0));
+        let true_v = self._true();
 
         let mut current_key_idx = self.zero::<Variable>();
         let mut finished = self._false();
-
-        let mut padded_root = root.as_bytes().to_vec();
-        while padded_root.len() < ELEMENT_LEN {
-            padded_root.push(self.constant::<ByteVariable>(0));
-        }
-        let mut current_node_id = ArrayVariable::<ByteVariable, ELEMENT_LEN>::new(padded_root);
-        let hash_key = self.keccak256_witness(&key.as_bytes());
-        let key_path: ArrayVariable<ByteVariable, 64> =
-            hash_key.as_bytes().to_vec().to_nibbles(self).into();
+        let mut excluded = self._false();
+        let mut value_node = proof[0].clone();
+        let mut value_node_len = len_nodes[0];
+        let mut value = RLPFieldVariable {
+            start: zero,
+            offset: zero,
+            len: zero,
+            is_list: self._false(),
+        };
+
+        // The reference to the next node: its hash, or its encoding if it is shorter than 32 bytes.
+        let mut current_node_id = ArrayVariable::<ByteVariable, 32>::new(root.as_bytes().to_vec());
+        let mut current_node_id_len = const_32;
 
         for i in 0..PROOF_LEN {
             let current_node = proof[i].clone();
-            let current_node_hash =
-                self.keccak256_variable_witness(current_node.as_slice(), len_nodes[i].variable);
+            let node_len = len_nodes[i].variable;
+            let current_node_hash = self.keccak256_variable(current_node.as_slice(), len_nodes[i]);
 
             if i == 0 {
                 self.assert_is_equal(current_node_hash, root);
             } else {
+                // Nodes shorter than 32 bytes are inlined in their parent instead of hashed.
                 let first_32_bytes_eq = self.is_equal::<Bytes32Variable>(
                     current_node[0..32].into(),
                     current_node_id[0..32].into(),
@@ -99,40 +145,58 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
                     current_node_hash,
                     current_node_id.as_slice()[0..32].into(),
                 );
-                let node_len_le_32 = self.lt(len_nodes[i], const_32);
-                let case_len_le_32 = self.and(node_len_le_32, first_32_bytes_eq);
-                let inter = self.not(node_len_le_32);
-                let case_len_gt_32 = self.and(inter, hash_eq);
-                let equality_fulfilled = self.or(case_len_le_32, case_len_gt_32);
+                let node_len_lt_32 = self.lt(node_len, const_32);
+                let inlined_len_eq = self.is_equal(current_node_id_len, node_len);
+                let case_len_lt_32 = self.and(node_len_lt_32, first_32_bytes_eq);
+                let case_len_lt_32 = self.and(case_len_lt_32, inlined_len_eq);
+                let node_len_gte_32 = self.not(node_len_lt_32);
+                let is_hash_reference = self.is_equal(current_node_id_len, const_32);
+                let case_len_gte_32 = self.and(node_len_gte_32, hash_eq);
+                let case_len_gte_32 = self.and(case_len_gte_32, is_hash_reference);
+                let equality_fulfilled = self.or(case_len_lt_32, case_len_gte_32);
                 let checked_equality = self.or(equality_fulfilled, finished);
-                let t = self._true();
-                self.assert_is_equal(checked_equality, t);
+                self.assert_is_equal(checked_equality, true_v);
             }
 
-            let (decoded_list, decoded_element_lens, len_decoded_list) = self
-                .decode_element_as_list::<ENCODING_LEN, LIST_LEN, ELEMENT_LEN>(
-                    current_node,
-                    len_nodes[i].variable,
-                    finished,
-                );
-
-            let is_branch = self.is_equal(len_decoded_list, branch_node_length);
-            let is_leaf = self.is_equal(len_decoded_list, leaf_or_extension_node_length);
-            let key_terminated = self.is_equal(current_key_idx, const_64);
-            let path = decoded_list[0].as_slice().to_vec().to_nibbles(self);
+            // Nodes after the end of the proof are decoded as an empty list.
+            let mut encoding = current_node
+                .as_slice()
+                .iter()
+                .map(|byte| byte.to_variable(self))
+                .collect::<Vec<_>>();
+            encoding[0] = self.select(finished, empty_list, encoding[0]);
+            let (fields, num_items) = self.rlp_decode_list::<LIST_LEN>(&encoding, zero);
+
+            // The list must end at the end of the hashed bytes, so that no item is read from the
+            // padding of the node.
+            let (list_offset, list_len, _) = self.rlp_item_header(&encoding, zero);
+            let list_end = self.add(list_offset, list_len);
+            let expected_end = self.select(finished, one, node_len);
+            self.assert_is_equal(list_end, expected_end);
+
+            let is_branch = self.is_equal(num_items, branch_node_length);
+            let is_leaf = self.is_equal(num_items, leaf_or_extension_node_length);
+            let is_node = self.or(is_branch, is_leaf);
+            let is_node_valid = self.or(is_node, finished);
+            self.assert_is_equal(is_node_valid, true_v);
+
+            let key_terminated = self.is_equal(current_key_idx, key_path_len);
+            let path_bytes = self.rlp_field_bytes::<PATH_LEN>(&encoding, &fields[0]);
+            let path = path_bytes.as_slice().to_vec().to_nibbles(self);
             let prefix = path[0];
             let prefix_leaf_even = self.is_equal(prefix, prefix_leaf_even);
             let prefix_leaf_odd = self.is_equal(prefix, prefix_leaf_odd);
-            let prefix_extension_even = self.is_equal(prefix, prefix_extension_even);
             let prefix_extension_odd = self.is_equal(prefix, prefix_extension_odd);
+            let is_leaf_prefix = self.or(prefix_leaf_even, prefix_leaf_odd);
+            let is_odd_prefix = self.or(prefix_extension_odd, prefix_leaf_odd);
 
-            let offset_even = self.mul(prefix_extension_even.variable, two);
-            let offset_odd = self.mul(prefix_extension_odd.variable, one);
-            let offset = self.add(offset_even, offset_odd);
-            let branch_key = self.select_array(key_path.clone().as_slice(), current_key_idx);
+            // An even path skips the prefix nibble and a padding nibble, an odd one only the prefix.
+            let offset = self.sub(two, is_odd_prefix.variable);
+            let branch_key = self.select_array(key_path, current_key_idx);
             let branch_key_variable = branch_key.to_variable(self);
 
-            // Case 1
+            // The child of a branch node is the value slot if the key ends here, and the nibble of
+            // the key otherwise. The child of a leaf or extension node is its second item.
             let is_branch_and_key_terminated = self.and(is_branch, key_terminated);
             let case_1_value = self.mul(is_branch_and_key_terminated.variable, tree_radix);
             let b = self.not(key_terminated);
@@ -144,58 +208,183 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
             let case_3_value = self.mul(is_leaf.variable, one);
 
             let c = self.add(case_1_value, case_2_value);
-            let updated_current_node_id_idx = self.add(c, case_3_value); // TODO: make this more concise
-
-            let updated_current_node_id =
-                self.select_array(decoded_list.as_slice(), updated_current_node_id_idx);
-            // If finished == 1, then we should not update the current_node_id
-            current_node_id = self.select_array(
-                &[updated_current_node_id, current_node_id],
-                finished.variable,
-            );
+            let child_index = self.add(c, case_3_value);
+            let child = self.select_array(&fields, child_index);
+
+            // The path of a leaf or extension node must continue the key, and the path of a leaf
+            // must also end it. Otherwise the key is not in the trie.
+            let not_finished = self.not(finished);
+            let do_path_remainder_check = self.and(not_finished, is_leaf);
+            let e = self.mul(fields[0].len, two);
+            let path_len = self.sub(e, offset);
+            let path_end = self.add(current_key_idx, path_len);
+            let path_fits = self.lte(path_end, key_path_len);
+            let path_ends_key = self.is_equal(path_end, key_path_len);
+            let not_leaf_prefix = self.not(is_leaf_prefix);
+            let path_end_valid = self.or(not_leaf_prefix, path_ends_key);
+            let path_eq = self.subarray_equal(&path, offset, key_path, current_key_idx, path_len);
+            let mut path_matches = self.and(path_fits, path_end_valid);
+            path_matches = self.and(path_matches, path_eq);
+            let path_mismatch = self.not(path_matches);
+            let path_excluded = self.and(do_path_remainder_check, path_mismatch);
+
+            // An empty child in a branch node on the path of the key.
+            let child_empty = self.is_equal(child.len, zero);
+            let mut branch_excluded = self.and(not_finished, is_branch_and_key_not_terminated);
+            branch_excluded = self.and(branch_excluded, child_empty);
+
+            let node_excluded = self.or(path_excluded, branch_excluded);
+            excluded = self.or(excluded, node_excluded);
 
-            let mut do_path_remainder_check = self.not(finished);
-            do_path_remainder_check = self.and(do_path_remainder_check, is_leaf);
-            let d = self.or(prefix_extension_even, prefix_extension_odd);
-            do_path_remainder_check = self.and(do_path_remainder_check, d);
+            current_key_idx = self.add(current_key_idx, is_branch_and_key_not_terminated.variable);
+            let check_length = self.mul(path_len, do_path_remainder_check.variable);
+            current_key_idx = self.add(current_key_idx, check_length);
+
+            let is_leaf_node = self.and(is_leaf, is_leaf_prefix);
+            let l = self.or(is_branch_and_key_terminated, is_leaf_node);
+            let m = self.or(l, node_excluded);
+
+            let is_value_node = self.and(not_finished, l);
+            value_node = self.select(is_value_node, current_node, value_node);
+            value_node_len = self.select(is_value_node, len_nodes[i], value_node_len);
+            value = self.select(is_value_node, child, value);
+
+            // The reference to the next node. A child shorter than 32 bytes is inlined as a list,
+            // and referenced by its whole encoding instead of its payload. Values can be longer
+            // than a reference, so the reference is only read if the walk continues.
+            let child_end = self.add(child.offset, child.len);
+            let inlined_len = self.sub(child_end, child.start);
+            let reference_offset = self.select(child.is_list, child.start, child.offset);
+            let reference_len = self.select(child.is_list, inlined_len, child.len);
+            let is_last_node = self.or(finished, m);
+            let reference_len = self.select(is_last_node, zero, reference_len);
+            let reference = RLPFieldVariable {
+                start: child.start,
+                offset: reference_offset,
+                len: reference_len,
+                is_list: child.is_list,
+            };
+            let reference_bytes = self.rlp_field_bytes::<32>(&encoding, &reference);
+            current_node_id = self.select(finished, current_node_id, reference_bytes);
+            current_node_id_len = self.select(finished, current_node_id_len, reference_len);
+            finished = self.or(finished, m);
+        }
 
-            let e = self.mul(decoded_element_lens[0], two);
-            let f = self.mul(offset, do_path_remainder_check.variable);
-            let mut check_length = self.sub(e, f);
-            check_length = self.mul(check_length, do_path_remainder_check.variable);
+        // The proof must reach a value or show that there is none.
+        self.assert_is_equal(finished, true_v);
 
-            self.assert_subarray_equal(
-                &path,
-                offset,
-                key_path.as_slice(),
-                current_key_idx,
-                check_length,
-            );
+        MPTLookup {
+            value,
+            node: value_node,
+            node_len: value_node_len,
+            excluded,
+        }
+    }
 
-            current_key_idx = self.add(current_key_idx, is_branch_and_key_not_terminated.variable);
-            let j = self.mul(is_leaf.variable, check_length);
-            current_key_idx = self.add(current_key_idx, j);
+    /// Returns the trie path of the item at `index` in a transactions or receipts trie, which is the
+    /// RLP encoding of the index, as nibbles together with its number of nibbles. The index is
+    /// constrained to be less than `2^16`.
+    pub fn mpt_index_key_path(&mut self, index: Variable) -> (Vec<ByteVariable>, Variable) {
+        let bits = self
+            .api
+            .split_le(index.0, 16)
+            .into_iter()
+            .map(BoolVariable::from)
+            .collect::<Vec<_>>();
+        let byte_from_le_bits = |bits: &[BoolVariable]| {
+            let mut be_bits: [BoolVariable; 8] = bits.try_into().unwrap();
+            be_bits.reverse();
+            ByteVariable::from_be_bits(be_bits)
+        };
+        let low = byte_from_le_bits(&bits[..8]);
+        let high = byte_from_le_bits(&bits[8..]);
+
+        // The index is encoded as 0x80 if it is zero, as itself below 0x80, and otherwise as
+        // 0x80 + its length in bytes followed by its big-endian bytes.
+        let zero = self.zero::<Variable>();
+        let const_128 = self.constant::<Variable>(L::Field::from_canonical_u8(0x80));
+        let is_zero = self.is_equal(index, zero);
+        let is_below_128 = self.lt(index, const_128);
+        let high_variable = high.to_variable(self);
+        let is_one_byte = self.is_equal(high_variable, zero);
+        let is_two_bytes = self.not(is_one_byte);
+
+        let empty_prefix = self.constant::<ByteVariable>(0x80);
+        let one_byte_prefix = self.constant::<ByteVariable>(0x81);
+        let two_bytes_prefix = self.constant::<ByteVariable>(0x82);
+        let zero_byte = self.constant::<ByteVariable>(0);
+
+        let mut first = self.select(is_one_byte, one_byte_prefix, two_bytes_prefix);
+        first = self.select(is_below_128, low, first);
+        first = self.select(is_zero, empty_prefix, first);
+        let second = self.select(is_two_bytes, high, low);
+        let third = self.select(is_two_bytes, low, zero_byte);
+
+        let one = self.one::<Variable>();
+        let two = self.constant::<Variable>(L::Field::from_canonical_u8(2));
+        let three = self.constant::<Variable>(L::Field::from_canonical_u8(3));
+        let mut len = self.select(is_two_bytes, three, two);
+        len = self.select(is_below_128, one, len);
+        let nb_nibbles = self.mul(len, two);
 
-            let prefix_leaf_even_and_leaf = self.and(prefix_leaf_even, is_leaf);
-            let prefix_leaf_odd_and_leaf = self.and(prefix_leaf_odd, is_leaf);
-            let l = self.or(is_branch_and_key_terminated, prefix_leaf_even_and_leaf);
-            let m = self.or(l, prefix_leaf_odd_and_leaf);
-            finished = self.or(finished, m);
-        }
+        (vec![first, second, third].to_nibbles(self), nb_nibbles)
+    }
 
-        // Can be unsafe because `current_node_id` comes from a ByteVariable.
-        let current_node_id_u32 =
-            U32Variable::from_variables_unsafe(&[current_node_id[0].to_variable(self)]);
-        let current_node_len = self.sub(current_node_id_u32, const_128);
-        let lhs_offset = self.sub(const_32, current_node_len);
-
-        self.assert_subarray_equal(
-            &value.as_bytes(),
-            lhs_offset.variable,
-            current_node_id.as_slice(),
-            one,
-            current_node_len.variable,
+    /// Returns the value stored at `key` in the trie with the given `root`, left-padded to 32
+    /// bytes, or zero if the proof shows that `key` is not in the trie. See `mpt_lookup` for the
+    /// layout of the proof.
+    pub fn get_mpt_value<const ENCODING_LEN: usize, const PROOF_LEN: usize>(
+        &mut self,
+        key: Bytes32Variable,
+        proof: ArrayVariable<ArrayVariable<ByteVariable, ENCODING_LEN>, PROOF_LEN>,
+        len_nodes: ArrayVariable<U32Variable, PROOF_LEN>,
+        root: Bytes32Variable,
+    ) -> Bytes32Variable {
+        let lookup = self.mpt_lookup(&key.as_bytes(), proof, len_nodes, root);
+        let zero = self.zero::<Variable>();
+        let false_v = self._false();
+        let node = lookup
+            .node
+            .as_slice()
+            .iter()
+            .map(|byte| byte.to_variable(self))
+            .collect::<Vec<_>>();
+
+        // The value is itself RLP encoded, and its encoding must be the whole value item.
+        let value_start = lookup.value.offset;
+        let (offset, len, is_list) = self.rlp_item_header(&node, value_start);
+        let end = self.add(offset, len);
+        let item_end = self.add(lookup.value.offset, lookup.value.len);
+        let included = self.not(lookup.excluded);
+        self.assert_is_equal_if(included, end, item_end);
+        self.assert_is_equal_if(included, is_list, false_v);
+
+        let len = self.select(lookup.excluded, zero, len);
+        let value = self.rlp_field_scalar(
+            &node,
+            &RLPFieldVariable {
+                start: value_start,
+                offset,
+                len,
+                is_list,
+            },
         );
+        let zero_value = self.constant::<Bytes32Variable>(H256::zero());
+        self.select(lookup.excluded, zero_value, value)
+    }
+
+    /// Asserts that `value` is stored at `key` in the trie with the given `root`. See `mpt_lookup`
+    /// for the layout of the proof.
+    pub fn verify_mpt_proof<const ENCODING_LEN: usize, const PROOF_LEN: usize>(
+        &mut self,
+        key: Bytes32Variable,
+        proof: ArrayVariable<ArrayVariable<ByteVariable, ENCODING_LEN>, PROOF_LEN>,
+        len_nodes: ArrayVariable<U32Variable, PROOF_LEN>,
+        root: Bytes32Variable,
+        value: Bytes32Variable,
+    ) {
+        let stored_value = self.get_mpt_value(key, proof, len_nodes, root);
+        self.assert_is_equal(stored_value, value);
     }
 }
 
@@ -209,30 +398,11 @@ mod tests {
     use crate::prelude::DefaultBuilder;
     use crate::utils;
 
-    #[test]
-    #[cfg_attr(feature = "ci", ignore)]
-    fn test_mpt_circuit() {
-        utils::setup_logger();
-        let storage_result: EIP1186ProofResponse =
-            read_fixture("./src/frontend/eth/mpt/fixtures/example.json");
-
-        let storage_proof = storage_result.storage_proof[0]
-            .proof
-            .iter()
-            .map(|b| b.to_vec())
-            .collect::<Vec<Vec<u8>>>();
-        let root = storage_result.storage_hash;
-        let key = storage_result.storage_proof[0].key;
-        let value = storage_result.storage_proof[0].value;
-
-        debug!("root {:?} key {:?} value {:?}", root, key, value);
-
-        let value_as_h256 = u256_to_h256_be(value);
-        debug!("value_as_h256 {:?}", value_as_h256);
-
-        const ENCODING_LEN: usize = 600;
-        const PROOF_LEN: usize = 16;
+    const ENCODING_LEN: usize = 600;
+    const PROOF_LEN: usize = 16;
 
+    /// Checks in a mock circuit that the storage proof shows `value` at `key` under `root`.
+    fn mock_prove_storage(storage_proof: Vec<Vec<u8>>, key: H256, root: H256, value: H256) {
         let (proof_as_fixed, lengths_as_fixed) =
             transform_proof_to_padded::<ENCODING_LEN, PROOF_LEN>(storage_proof);
         let len_nodes_value = lengths_as_fixed
@@ -263,8 +433,54 @@ mod tests {
         );
         input.write::<ArrayVariable<U32Variable, PROOF_LEN>>(len_nodes_value);
         input.write::<Bytes32Variable>(root);
-        input.write::<Bytes32Variable>(value_as_h256);
+        input.write::<Bytes32Variable>(value);
 
         let (_witness, mut _output) = circuit.mock_prove(&input);
     }
+
+    fn storage_fixture() -> (Vec<Vec<u8>>, H256, H256, H256) {
+        let storage_result: EIP1186ProofResponse =
+            read_fixture("./src/frontend/eth/mpt/fixtures/example.json");
+        let storage_proof = storage_result.storage_proof[0]
+            .proof
+            .iter()
+            .map(|b| b.to_vec())
+            .collect::<Vec<Vec<u8>>>();
+        let root = storage_result.storage_hash;
+        let key = storage_result.storage_proof[0].key;
+        let value = u256_to_h256_be(storage_result.storage_proof[0].value);
+        (storage_proof, key, root, value)
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_mpt_circuit() {
+        utils::setup_logger();
+        let (storage_proof, key, root, value_as_h256) = storage_fixture();
+        debug!("root {:?} key {:?} value {:?}", root, key, value_as_h256);
+
+        mock_prove_storage(storage_proof, key, root, value_as_h256);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    #[should_panic(expected = "was set twice with different values")]
+    fn test_mpt_rejects_forged_value() {
+        let (storage_proof, key, root, mut value) = storage_fixture();
+        value.0[31] ^= 1;
+        mock_prove_storage(storage_proof, key, root, value);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    #[should_panic(expected = "was set twice with different values")]
+    fn test_mpt_rejects_forged_node() {
+        let (mut storage_proof, key, root, mut value) = storage_fixture();
+        // Change the value in the leaf, which no longer matches the hash held by its parent.
+        let leaf = storage_proof.last_mut().unwrap();
+        let last = leaf.len() - 1;
+        leaf[last] ^= 1;
+        value.0[31] ^= 1;
+        mock_prove_storage(storage_proof, key, root, value);
+    }
 }
@@ -1,6 +1,5 @@
-use std::marker::PhantomData;
+use plonky2::field::types::Field;
 
-use super::generators::SubarrayEqualGenerator;
 use crate::prelude::{BoolVariable, ByteVariable, CircuitBuilder, PlonkParameters, Variable};
 
 // Checks that a[a_offset:a_offset+len] = b[b_offset:b_offset+len]
@@ -14,7 +13,10 @@ pub fn subarray_equal(a: &[u8], a_offset: usize, b: &[u8], b_offset: usize, len:
 }
 
 impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
-    #[allow(unused_variables, dead_code)]
+    /// Returns whether `a[a_offset..a_offset + len] == b[b_offset..b_offset + len]`.
+    ///
+    /// At most `a.len()` elements are compared, so `len` should not exceed it. Indices past the end
+    /// of either array read its first element.
     pub fn subarray_equal(
         &mut self,
         a: &[ByteVariable],
@@ -23,10 +25,31 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         b_offset: Variable,
         len: Variable,
     ) -> BoolVariable {
-        todo!();
+        // Selecting over variables is cheaper than selecting over the bits of each byte.
+        let a = a.iter().map(|x| x.to_variable(self)).collect::<Vec<_>>();
+        let b = b.iter().map(|x| x.to_variable(self)).collect::<Vec<_>>();
+
+        let mut equal = self._true();
+        let mut within = self._true();
+        for i in 0..a.len() {
+            let index = self.constant::<Variable>(L::Field::from_canonical_usize(i));
+            let is_end = self.is_equal(index, len);
+            let not_end = self.not(is_end);
+            within = self.and(within, not_end);
+
+            let a_index = self.add(a_offset, index);
+            let b_index = self.add(b_offset, index);
+            let a_i = self.select_array(&a, a_index);
+            let b_i = self.select_array(&b, b_index);
+            let is_equal = self.is_equal(a_i, b_i);
+            let not_within = self.not(within);
+            let is_valid = self.or(not_within, is_equal);
+            equal = self.and(equal, is_valid);
+        }
+        equal
     }
 
-    #[allow(unused_variables, dead_code)]
+    /// Asserts that `a[a_offset..a_offset + len] == b[b_offset..b_offset + len]`.
     pub fn assert_subarray_equal(
         &mut self,
         a: &[ByteVariable],
@@ -35,19 +58,45 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         b_offset: Variable,
         len: Variable,
     ) {
-        // TODO: Need to constrain generator result.
-        let generator: SubarrayEqualGenerator<L, D> = SubarrayEqualGenerator {
-            a: a.to_vec(),
-            a_offset,
-            b: b.to_vec(),
-            b_offset,
-            len,
-            _phantom: PhantomData::<L>,
-        };
-        self.add_simple_generator(generator);
+        let equal = self.subarray_equal(a, a_offset, b, b_offset, len);
+        let true_v = self._true();
+        self.assert_is_equal(equal, true_v);
     }
 }
 
-pub(crate) mod tests {
-    // TODO add a test for subarray_equal
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn test_subarray_equal() {
+        let mut builder = DefaultBuilder::new();
+        let a = builder.read::<ArrayVariable<ByteVariable, 8>>();
+        let b = builder.read::<ArrayVariable<ByteVariable, 8>>();
+        let a_offset = builder.read::<Variable>();
+        let b_offset = builder.read::<Variable>();
+        let len = builder.read::<Variable>();
+        let equal = builder.subarray_equal(a.as_slice(), a_offset, b.as_slice(), b_offset, len);
+        builder.write(equal);
+        let circuit = builder.build();
+
+        let a = vec![1u8, 2, 3, 4, 5, 6, 7, 8];
+        let b = vec![9u8, 9, 3, 4, 5, 9, 9, 9];
+        for (a_offset, b_offset, len, expected) in [
+            (2, 2, 3, true),
+            (2, 2, 4, false),
+            (3, 3, 0, true),
+            (0, 2, 3, false),
+        ] {
+            let mut input = circuit.input();
+            input.write::<ArrayVariable<ByteVariable, 8>>(a.clone());
+            input.write::<ArrayVariable<ByteVariable, 8>>(b.clone());
+            input.write::<Variable>(GoldilocksField::from_canonical_usize(a_offset));
+            input.write::<Variable>(GoldilocksField::from_canonical_usize(b_offset));
+            input.write::<Variable>(GoldilocksField::from_canonical_usize(len));
+            let (proof, mut output) = circuit.prove(&input);
+            circuit.verify(&proof, &input, &output);
+            assert_eq!(output.read::<BoolVariable>(), expected);
+        }
+    }
 }
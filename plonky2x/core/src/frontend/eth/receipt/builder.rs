@@ -0,0 +1,209 @@
+use plonky2::field::types::Field;
+
+use super::vars::{EthReceiptLogVariable, EthTrieProofVariable, MAX_LOG_TOPICS};
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::builder::CircuitBuilder;
+use crate::frontend::eth::vars::AddressVariable;
+use crate::frontend::vars::{ArrayVariable, Bytes32Variable, BytesVariable, Variable};
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Verifies the receipt of the transaction at `tx_index` against `receipts_root`, and returns
+    /// the log at `log_index` in the receipt.
+    ///
+    /// The receipt is decoded from the leaf of the proof, either as a typed receipt, which is a
+    /// type byte followed by the RLP list of its fields, or as a legacy receipt, which is the list
+    /// itself. The whole leaf must fit in `ENCODING_LEN` bytes, and the data of the log in
+    /// `MAX_DATA_LEN` bytes.
+    pub fn get_receipt_log<
+        const ENCODING_LEN: usize,
+        const PROOF_LEN: usize,
+        const MAX_DATA_LEN: usize,
+    >(
+        &mut self,
+        receipts_root: Bytes32Variable,
+        tx_index: Variable,
+        log_index: usize,
+        proof: &EthTrieProofVariable<ENCODING_LEN, PROOF_LEN>,
+    ) -> EthReceiptLogVariable<MAX_DATA_LEN> {
+        let (key_path, key_path_len) = self.mpt_index_key_path(tx_index);
+        let receipt = self.mpt_lookup_path(
+            &key_path,
+            key_path_len,
+            proof.proof.clone(),
+            proof.proof_lens.clone(),
+            receipts_root,
+        );
+        let true_v = self._true();
+        let false_v = self._false();
+        self.assert_is_equal(receipt.excluded, false_v);
+
+        let node = receipt
+            .node
+            .as_slice()
+            .iter()
+            .map(|byte| byte.to_variable(self))
+            .collect::<Vec<_>>();
+        let zero = self.zero::<Variable>();
+
+        // The leaf is the list [path, receipt].
+        let (leaf, _) = self.rlp_decode_list::<2>(&node, zero);
+
+        // List prefixes are at least 0xc0, while transaction types are below 0x80.
+        let const_0xc0 = self.constant::<Variable>(L::Field::from_canonical_u8(0xc0));
+        let first_byte = self.select_array(&node, leaf[1].offset);
+        let is_typed = self.lt(first_byte, const_0xc0);
+        let fields_start = self.add(leaf[1].offset, is_typed.variable);
+
+        // The receipt is the list [status, cumulative_gas_used, logs_bloom, logs].
+        let (fields, _) = self.rlp_decode_list::<4>(&node, fields_start);
+        let logs = fields[3];
+        self.assert_is_equal(logs.is_list, true_v);
+        let logs_end = self.add(logs.offset, logs.len);
+
+        let mut log_start = logs.offset;
+        for _ in 0..log_index {
+            let (start, len, _) = self.rlp_item_header(&node, log_start);
+            log_start = self.add(start, len);
+        }
+
+        // The log is the list [address, topics, data].
+        let (log, _) = self.rlp_decode_list::<3>(&node, log_start);
+        let log_end = self.add(log[2].offset, log[2].len);
+        let is_log_in_receipt = self.lte(log_end, logs_end);
+        self.assert_is_equal(is_log_in_receipt, true_v);
+
+        let const_20 = self.constant::<Variable>(L::Field::from_canonical_u8(20));
+        self.assert_is_equal(log[0].len, const_20);
+        let address_bytes = self.rlp_field_bytes::<20>(&node, &log[0]);
+        let address = AddressVariable(BytesVariable(address_bytes.as_slice().try_into().unwrap()));
+
+        // Each topic is a string of 32 bytes.
+        let (topic_fields, num_topics) =
+            self.rlp_decode_list::<MAX_LOG_TOPICS>(&node, log[1].start);
+        let const_32 = self.constant::<Variable>(L::Field::from_canonical_u8(32));
+        let mut topics = Vec::with_capacity(MAX_LOG_TOPICS);
+        for (i, topic) in topic_fields.iter().enumerate() {
+            let index = self.constant::<Variable>(L::Field::from_canonical_usize(i));
+            let is_present = self.lt(index, num_topics);
+            let topic_len = self.select(is_present, const_32, zero);
+            self.assert_is_equal(topic.len, topic_len);
+            let topic_bytes = self.rlp_field_bytes::<32>(&node, topic);
+            topics.push(Bytes32Variable::from(topic_bytes.as_slice()));
+        }
+
+        let data_is_list = log[2].is_list;
+        self.assert_is_equal(data_is_list, false_v);
+        let data = self.rlp_field_bytes::<MAX_DATA_LEN>(&node, &log[2]);
+
+        EthReceiptLogVariable {
+            address,
+            topics: ArrayVariable::new(topics),
+            num_topics,
+            data,
+            data_len: log[2].len,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ethers::types::{Address, H256};
+    use ethers::utils::keccak256;
+    use ethers::utils::rlp::RlpStream;
+
+    use super::*;
+    use crate::frontend::eth::receipt::vars::{EthReceiptLog, EthTrieProof};
+    use crate::prelude::{DefaultBuilder, GoldilocksField};
+
+    type F = GoldilocksField;
+
+    /// Encodes a leaf node with the given hex-prefix encoded path.
+    fn leaf(path: &[u8], value: &[u8]) -> Vec<u8> {
+        let mut stream = RlpStream::new_list(2);
+        stream.append(&path.to_vec());
+        stream.append(&value.to_vec());
+        stream.out().to_vec()
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_get_receipt_log() {
+        const ENCODING_LEN: usize = 512;
+        const PROOF_LEN: usize = 2;
+        const MAX_DATA_LEN: usize = 64;
+
+        let address = Address::from_low_u64_be(0xdead);
+        let topics = [H256::repeat_byte(1), H256::repeat_byte(2)];
+        let data = vec![7u8; 40];
+
+        // A typed receipt with a single log.
+        let mut stream = RlpStream::new_list(4);
+        stream.append(&1u8);
+        stream.append(&21000u64);
+        stream.append(&vec![0u8; 256]);
+        stream.begin_list(1);
+        stream.begin_list(3);
+        stream.append(&address.as_bytes().to_vec());
+        stream.begin_list(topics.len());
+        for topic in topics.iter() {
+            stream.append(&topic.as_bytes().to_vec());
+        }
+        stream.append(&data);
+        let mut typed_receipt = vec![0x02];
+        typed_receipt.extend(stream.out().to_vec());
+
+        // A legacy receipt without logs.
+        let mut stream = RlpStream::new_list(4);
+        stream.append(&1u8);
+        stream.append(&42000u64);
+        stream.append(&vec![0u8; 256]);
+        stream.begin_list(0);
+        let legacy_receipt = stream.out().to_vec();
+
+        // The trie of two receipts, at the paths rlp(0) = 0x80 and rlp(1) = 0x01, is a branch
+        // node whose children at nibbles 0 and 8 are leaves with one nibble left.
+        let leaf_1 = leaf(&[0x31], &legacy_receipt);
+        let leaf_0 = leaf(&[0x30], &typed_receipt);
+        let mut stream = RlpStream::new_list(17);
+        for nibble in 0..16 {
+            match nibble {
+                0 => stream.append(&keccak256(&leaf_1).to_vec()),
+                8 => stream.append(&keccak256(&leaf_0).to_vec()),
+                _ => stream.append_empty_data(),
+            };
+        }
+        stream.append_empty_data();
+        let branch = stream.out().to_vec();
+        let receipts_root = H256(keccak256(&branch));
+
+        let mut builder = DefaultBuilder::new();
+        let root = builder.read::<Bytes32Variable>();
+        let tx_index = builder.read::<Variable>();
+        let proof = builder.read::<EthTrieProofVariable<ENCODING_LEN, PROOF_LEN>>();
+        let log = builder
+            .get_receipt_log::<ENCODING_LEN, PROOF_LEN, MAX_DATA_LEN>(root, tx_index, 0, &proof);
+        builder.write(log);
+        let circuit = builder.mock_build();
+
+        let mut input = circuit.input();
+        input.write::<Bytes32Variable>(receipts_root);
+        input.write::<Variable>(F::ZERO);
+        input.write::<EthTrieProofVariable<ENCODING_LEN, PROOF_LEN>>(EthTrieProof::from_nodes(
+            vec![branch, leaf_0],
+        ));
+        let (_witness, mut output) = circuit.mock_prove(&input);
+
+        let mut expected_data = data.clone();
+        expected_data.resize(MAX_DATA_LEN, 0);
+        assert_eq!(
+            output.read::<EthReceiptLogVariable<MAX_DATA_LEN>>(),
+            EthReceiptLog {
+                address,
+                topics: vec![topics[0], topics[1], H256::zero(), H256::zero()],
+                num_topics: F::from_canonical_usize(topics.len()),
+                data: expected_data,
+                data_len: F::from_canonical_usize(data.len()),
+            }
+        );
+    }
+}
@@ -0,0 +1,53 @@
+use std::fmt::Debug;
+
+use plonky2::hash::hash_types::RichField;
+use plonky2x_derive::CircuitVariable;
+
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::builder::CircuitBuilder;
+use crate::frontend::eth::mpt::builder::transform_proof_to_padded;
+use crate::frontend::eth::vars::AddressVariable;
+use crate::frontend::vars::{Bytes32Variable, CircuitVariable};
+use crate::prelude::{ArrayVariable, ByteVariable, U32Variable, Variable};
+
+/// The maximum number of topics of a log.
+pub const MAX_LOG_TOPICS: usize = 4;
+
+/// A proof of an item of a transactions or receipts trie, from the root of the trie to the leaf
+/// holding the item.
+///
+/// Every node is padded to `ENCODING_LEN` bytes, and the proof is padded with empty nodes to
+/// `PROOF_LEN` nodes.
+#[derive(Debug, Clone, CircuitVariable)]
+#[value_name(EthTrieProof)]
+pub struct EthTrieProofVariable<const ENCODING_LEN: usize, const PROOF_LEN: usize> {
+    pub proof: ArrayVariable<ArrayVariable<ByteVariable, ENCODING_LEN>, PROOF_LEN>,
+    pub proof_lens: ArrayVariable<U32Variable, PROOF_LEN>,
+}
+
+impl<const ENCODING_LEN: usize, const PROOF_LEN: usize, F: RichField>
+    EthTrieProof<ENCODING_LEN, PROOF_LEN, F>
+{
+    /// Builds the witness from the encoded nodes of the proof, from the root to the leaf.
+    pub fn from_nodes(nodes: Vec<Vec<u8>>) -> Self {
+        let (proof, proof_lens) = transform_proof_to_padded::<ENCODING_LEN, PROOF_LEN>(nodes);
+        Self {
+            proof,
+            proof_lens: proof_lens.into_iter().map(|len| len as u32).collect(),
+        }
+    }
+}
+
+/// A log emitted by a transaction, as decoded from its receipt.
+///
+/// The topics past `num_topics` and the bytes of the data past `data_len` are zero.
+#[derive(Debug, Clone, CircuitVariable)]
+#[value_name(EthReceiptLog)]
+#[value_derive(PartialEq, Eq)]
+pub struct EthReceiptLogVariable<const MAX_DATA_LEN: usize> {
+    pub address: AddressVariable,
+    pub topics: ArrayVariable<Bytes32Variable, MAX_LOG_TOPICS>,
+    pub num_topics: Variable,
+    pub data: ArrayVariable<ByteVariable, MAX_DATA_LEN>,
+    pub data_len: Variable,
+}
@@ -3,11 +3,12 @@ use starkyx::math::field::Field;
 use starkyx::math::prelude::PrimeField64;
 
 use super::utils::decode_padded_mpt_node;
+use super::vars::RLPFieldVariable;
 use crate::frontend::eth::rlp::utils::MAX_RLP_ITEM_SIZE;
 use crate::frontend::hint::simple::hint::Hint;
 use crate::prelude::{
-    ArrayVariable, BoolVariable, ByteVariable, CircuitBuilder, PlonkParameters, ValueStream,
-    Variable, VariableStream,
+    ArrayVariable, BoolVariable, ByteVariable, Bytes32Variable, CircuitBuilder, PlonkParameters,
+    ValueStream, Variable, VariableStream,
 };
 
 /// A Hint structure to decode an RLP-encoded string.
@@ -51,7 +52,73 @@ impl<L: PlonkParameters<D>, const D: usize, const ENCODING_LEN: usize, const LIS
     }
 }
 
+/// Returns the offset and the length of the payload of the RLP item at `encoding[start]`. Bytes
+/// past the end of `encoding` are read as zeros.
+fn rlp_payload_bounds(encoding: &[u8], start: usize) -> (usize, usize) {
+    let byte = |i: usize| encoding.get(i).copied().unwrap_or(0) as usize;
+    let be_len = |len_of_len: usize| {
+        (1..=len_of_len).fold(0usize, |acc, i| {
+            acc.saturating_mul(256).saturating_add(byte(start + i))
+        })
+    };
+    let prefix = byte(start);
+    match prefix {
+        0x00..=0x7f => (start, 1),
+        0x80..=0xb7 => (start + 1, prefix - 0x80),
+        0xb8..=0xbf => (start + 1 + prefix - 0xb7, be_len(prefix - 0xb7)),
+        0xc0..=0xf7 => (start + 1, prefix - 0xc0),
+        _ => (start + 1 + prefix - 0xf7, be_len(prefix - 0xf7)),
+    }
+}
+
+/// A Hint structure to locate the items of an RLP-encoded list.
+///
+/// The encoding is given as `encoding_len` variables, followed by the offset of the list in the
+/// encoding. The offsets of the first `LIST_LEN` items of the list are returned, padded with the
+/// end of the list, together with the number of items found.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct RLPListHint<const LIST_LEN: usize> {
+    encoding_len: usize,
+}
+
+impl<L: PlonkParameters<D>, const D: usize, const LIST_LEN: usize> Hint<L, D>
+    for RLPListHint<LIST_LEN>
+{
+    fn hint(&self, input_stream: &mut ValueStream<L, D>, output_stream: &mut ValueStream<L, D>) {
+        let encoding = input_stream
+            .read_vec::<Variable>(self.encoding_len)
+            .into_iter()
+            .map(|byte| byte.as_canonical_u64() as u8)
+            .collect::<Vec<_>>();
+        let start = input_stream.read_value::<Variable>().as_canonical_u64() as usize;
+
+        let (list_offset, list_len) = rlp_payload_bounds(&encoding, start);
+        let list_end = list_offset.saturating_add(list_len).min(encoding.len());
+        let mut starts = Vec::with_capacity(LIST_LEN);
+        let mut pos = list_offset;
+        while pos < list_end && starts.len() < LIST_LEN {
+            starts.push(pos);
+            let (offset, len) = rlp_payload_bounds(&encoding, pos);
+            pos = offset.saturating_add(len);
+        }
+        let num_items = starts.len();
+        starts.resize(LIST_LEN, list_end);
+
+        output_stream.write_value::<ArrayVariable<Variable, LIST_LEN>>(
+            starts
+                .into_iter()
+                .map(L::Field::from_canonical_usize)
+                .collect::<Vec<_>>(),
+        );
+        output_stream.write_value::<Variable>(L::Field::from_canonical_usize(num_items));
+    }
+}
+
 impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Decodes an RLP list with a hint.
+    ///
+    /// The decoded items are not constrained to match `encoded`, so the result must not be
+    /// trusted. Use `rlp_decode_list` to decode a list in the circuit.
     pub fn decode_element_as_list<
         const ENCODING_LEN: usize,
         const LIST_LEN: usize,
@@ -83,12 +150,360 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
 
         (decoded_list, decoded_element_lens, len_decoded_list)
     }
+
+    /// Parses the header of the RLP item starting at `encoding[start]`, whose bytes are given as
+    /// variables, and returns the offset and the length of its payload and whether it is a list.
+    ///
+    /// Long items are supported with lengths of up to two bytes, which covers any item that fits in
+    /// a circuit. The item is asserted to end within `encoding`.
+    pub fn rlp_item_header(
+        &mut self,
+        encoding: &[Variable],
+        start: Variable,
+    ) -> (Variable, Variable, BoolVariable) {
+        let zero = self.zero::<Variable>();
+        let one = self.one::<Variable>();
+        let true_v = self._true();
+        let const_0x80 = self.constant::<Variable>(L::Field::from_canonical_u8(0x80));
+        let const_0xb7 = self.constant::<Variable>(L::Field::from_canonical_u8(0xb7));
+        let const_0xc0 = self.constant::<Variable>(L::Field::from_canonical_u8(0xc0));
+        let const_0xf7 = self.constant::<Variable>(L::Field::from_canonical_u8(0xf7));
+        let const_256 = self.constant::<Variable>(L::Field::from_canonical_usize(256));
+        let two = self.constant::<Variable>(L::Field::from_canonical_u8(2));
+
+        let prefix = self.select_array(encoding, start);
+        let is_single_byte = self.lt(prefix, const_0x80);
+        let is_long_string_or_list = self.gt(prefix, const_0xb7);
+        let is_list = self.gte(prefix, const_0xc0);
+        let is_long_list = self.gt(prefix, const_0xf7);
+        let is_string = self.not(is_list);
+        let is_long_string = self.and(is_long_string_or_list, is_string);
+        let is_long = self.or(is_long_string, is_long_list);
+
+        // The payload length of short items, and the length of the length of long items.
+        let string_offset = self.select(is_long_string, const_0xb7, const_0x80);
+        let list_offset = self.select(is_long_list, const_0xf7, const_0xc0);
+        let offset = self.select(is_list, list_offset, string_offset);
+        let short_len = self.sub(prefix, offset);
+
+        let len_of_len_valid = self.lte(short_len, two);
+        let is_len_valid = self.not(is_long);
+        let is_len_valid = self.or(is_len_valid, len_of_len_valid);
+        self.assert_is_equal(is_len_valid, true_v);
+
+        let first_len_index = self.add(start, one);
+        let first_len_byte = self.select_array(encoding, first_len_index);
+        let second_len_index = self.add(first_len_index, one);
+        let second_len_byte = self.select_array(encoding, second_len_index);
+        let high_len_byte = self.mul(first_len_byte, const_256);
+        let two_byte_len = self.add(high_len_byte, second_len_byte);
+        let has_two_byte_len = self.is_equal(short_len, two);
+        let long_len = self.select(has_two_byte_len, two_byte_len, first_len_byte);
+
+        let header_len = self.add(one, short_len);
+        let header_len = self.select(is_long, header_len, one);
+        let header_len = self.select(is_single_byte, zero, header_len);
+        let payload_start = self.add(start, header_len);
+        let payload_len = self.select(is_long, long_len, short_len);
+        let payload_len = self.select(is_single_byte, one, payload_len);
+        self.rlp_assert_within(encoding, payload_start, payload_len);
+
+        (payload_start, payload_len, is_list)
+    }
+
+    /// Asserts that the `len` bytes at `encoding[offset]` are within `encoding`, since
+    /// `select_array` would read `encoding[0]` for an index out of range.
+    fn rlp_assert_within(&mut self, encoding: &[Variable], offset: Variable, len: Variable) {
+        let true_v = self._true();
+        let encoding_len =
+            self.constant::<Variable>(L::Field::from_canonical_usize(encoding.len()));
+        let end = self.add(offset, len);
+        let is_within = self.lte(end, encoding_len);
+        self.assert_is_equal(is_within, true_v);
+    }
+
+    /// Decodes the RLP list at `encoding[start]`, whose bytes are given as variables, and returns
+    /// its first `LIST_LEN` fields together with its number of items.
+    ///
+    /// The offsets of the items are provided by a hint, and constrained to be consecutive items that
+    /// cover the payload of the list exactly, so the list must have at most `LIST_LEN` items.
+    pub fn rlp_decode_list<const LIST_LEN: usize>(
+        &mut self,
+        encoding: &[Variable],
+        start: Variable,
+    ) -> (Vec<RLPFieldVariable>, Variable) {
+        let mut input_stream = VariableStream::new();
+        input_stream.write_slice(encoding);
+        input_stream.write(&start);
+        let hint = RLPListHint::<LIST_LEN> {
+            encoding_len: encoding.len(),
+        };
+        let output_stream = self.hint(input_stream, hint);
+        let starts = output_stream.read::<ArrayVariable<Variable, LIST_LEN>>(self);
+        let num_items = output_stream.read::<Variable>(self);
+
+        let zero = self.zero::<Variable>();
+        let true_v = self._true();
+        let max_items = self.constant::<Variable>(L::Field::from_canonical_usize(LIST_LEN));
+        let num_items_valid = self.lte(num_items, max_items);
+        self.assert_is_equal(num_items_valid, true_v);
+
+        let (list_offset, list_len, is_list) = self.rlp_item_header(encoding, start);
+        self.assert_is_equal(is_list, true_v);
+
+        // Every item starts where the previous one ends. Unused items are parsed at the header of
+        // the list, which is known to be well formed.
+        let mut next_start = list_offset;
+        let mut fields = Vec::with_capacity(LIST_LEN);
+        for i in 0..LIST_LEN {
+            let index = self.constant::<Variable>(L::Field::from_canonical_usize(i));
+            let is_item = self.lt(index, num_items);
+            let hinted_start = self.select(is_item, starts[i], next_start);
+            self.assert_is_equal(hinted_start, next_start);
+
+            let item_start = self.select(is_item, next_start, start);
+            let (offset, len, is_list) = self.rlp_item_header(encoding, item_start);
+            let end = self.add(offset, len);
+            next_start = self.select(is_item, end, next_start);
+
+            let len = self.select(is_item, len, zero);
+            let is_list = self.and(is_item, is_list);
+            fields.push(RLPFieldVariable {
+                start: item_start,
+                offset,
+                len,
+                is_list,
+            });
+        }
+        let list_end = self.add(list_offset, list_len);
+        self.assert_is_equal(next_start, list_end);
+
+        (fields, num_items)
+    }
+
+    /// Returns the payload of `field`, padded with zeros to `N` bytes, asserting that it fits and
+    /// that it is within `encoding`.
+    pub fn rlp_field_bytes<const N: usize>(
+        &mut self,
+        encoding: &[Variable],
+        field: &RLPFieldVariable,
+    ) -> ArrayVariable<ByteVariable, N> {
+        let zero = self.zero::<Variable>();
+        let true_v = self._true();
+        let max_len = self.constant::<Variable>(L::Field::from_canonical_usize(N));
+        let fits = self.lte(field.len, max_len);
+        self.assert_is_equal(fits, true_v);
+        self.rlp_assert_within(encoding, field.offset, field.len);
+
+        let bytes = (0..N)
+            .map(|i| {
+                let i = self.constant::<Variable>(L::Field::from_canonical_usize(i));
+                let in_payload = self.lt(i, field.len);
+                let index = self.add(field.offset, i);
+                let byte = self.select_array(encoding, index);
+                let byte = self.select(in_payload, byte, zero);
+                ByteVariable::from_variable(self, byte)
+            })
+            .collect::<Vec<_>>();
+        ArrayVariable::new(bytes)
+    }
+
+    /// Returns the payload of `field` as a big-endian scalar, left padded with zeros to 32 bytes,
+    /// asserting that it fits and that it is within `encoding`.
+    pub fn rlp_field_scalar(
+        &mut self,
+        encoding: &[Variable],
+        field: &RLPFieldVariable,
+    ) -> Bytes32Variable {
+        let zero = self.zero::<Variable>();
+        let true_v = self._true();
+        let const_32 = self.constant::<Variable>(L::Field::from_canonical_usize(32));
+        let fits = self.lte(field.len, const_32);
+        self.assert_is_equal(fits, true_v);
+        self.rlp_assert_within(encoding, field.offset, field.len);
+
+        let lhs_offset = self.sub(const_32, field.len);
+        let bytes: [ByteVariable; 32] = core::array::from_fn(|i| {
+            let i = self.constant::<Variable>(L::Field::from_canonical_usize(i));
+            let in_payload = self.lte(lhs_offset, i);
+            let shifted = self.sub(i, lhs_offset);
+            let index = self.add(field.offset, shifted);
+            let byte = self.select_array(encoding, index);
+            let byte = self.select(in_payload, byte, zero);
+            ByteVariable::from_variable(self, byte)
+        });
+        Bytes32Variable::from(bytes)
+    }
+
+    /// Returns the header of an RLP item whose payload has length `len`, as three bytes of which
+    /// only the first `header_len` are used, together with `header_len`.
+    ///
+    /// `short_prefix` is `0x80` for strings and `0xc0` for lists, and `long_prefix` is `0xb7` for
+    /// strings and `0xf7` for lists. The length is constrained to be less than `2^16`.
+    fn rlp_length_header(
+        &mut self,
+        len: Variable,
+        short_prefix: u8,
+        long_prefix: u8,
+    ) -> ([Variable; 3], Variable) {
+        let bits = self
+            .api
+            .split_le(len.0, 16)
+            .into_iter()
+            .map(BoolVariable::from)
+            .collect::<Vec<_>>();
+        let mut len_bytes = bits
+            .chunks_exact(8)
+            .map(|le_bits| {
+                let mut be_bits: [BoolVariable; 8] = le_bits.try_into().unwrap();
+                be_bits.reverse();
+                ByteVariable::from_be_bits(be_bits).to_variable(self)
+            })
+            .collect::<Vec<_>>();
+        let high_byte = len_bytes.pop().unwrap();
+        let low_byte = len_bytes.pop().unwrap();
+
+        let zero = self.zero::<Variable>();
+        let one = self.one::<Variable>();
+        let two = self.constant::<Variable>(L::Field::from_canonical_u8(2));
+        let three = self.constant::<Variable>(L::Field::from_canonical_u8(3));
+        let const_55 = self.constant::<Variable>(L::Field::from_canonical_u8(55));
+        let short_prefix = self.constant::<Variable>(L::Field::from_canonical_u8(short_prefix));
+        let long_prefix = self.constant::<Variable>(L::Field::from_canonical_u8(long_prefix));
+
+        // Payloads of up to 55 bytes have a one-byte header, and longer ones are prefixed by the
+        // length of their big-endian length.
+        let is_short = self.lte(len, const_55);
+        let has_high_byte = self.is_equal(high_byte, zero);
+        let has_high_byte = self.not(has_high_byte);
+        let short_header = self.add(short_prefix, len);
+        let len_of_len = self.add(one, has_high_byte.variable);
+        let long_header = self.add(long_prefix, len_of_len);
+
+        let first = self.select(is_short, short_header, long_header);
+        let second = self.select(has_high_byte, high_byte, low_byte);
+        let long_header_len = self.select(has_high_byte, three, two);
+        let header_len = self.select(is_short, one, long_header_len);
+        ([first, second, low_byte], header_len)
+    }
+
+    /// Writes `header` followed by `payload`, where only the first `header_len` bytes of the
+    /// header are used and `header_len` is at most 3.
+    fn rlp_prepend_header(
+        &mut self,
+        header: [Variable; 3],
+        header_len: Variable,
+        payload: &[Variable],
+    ) -> Vec<Variable> {
+        let zero = self.zero::<Variable>();
+        let header_len_is = (0..4)
+            .map(|t| {
+                let t = self.constant::<Variable>(L::Field::from_canonical_usize(t));
+                self.is_equal(header_len, t)
+            })
+            .collect::<Vec<_>>();
+        (0..payload.len() + 3)
+            .map(|k| {
+                let mut byte = payload.get(k).copied().unwrap_or(zero);
+                for (t, is_header_len) in header_len_is.iter().enumerate().skip(1) {
+                    let candidate = if k < t {
+                        header[k]
+                    } else {
+                        payload.get(k - t).copied().unwrap_or(zero)
+                    };
+                    byte = self.select(*is_header_len, candidate, byte);
+                }
+                byte
+            })
+            .collect()
+    }
+
+    /// Encodes the byte strings `items`, each given with its length, as an RLP list and returns the
+    /// encoding padded with zeros to `ENCODING_LEN` bytes, together with its length.
+    ///
+    /// Bytes of an item past its length are ignored. The payload of each item and of the list must
+    /// be shorter than `2^16` bytes, and the encoding must fit in `ENCODING_LEN` bytes.
+    pub fn rlp_encode_list<const ENCODING_LEN: usize>(
+        &mut self,
+        items: &[(Vec<ByteVariable>, Variable)],
+    ) -> (ArrayVariable<ByteVariable, ENCODING_LEN>, Variable) {
+        let zero = self.zero::<Variable>();
+        let one = self.one::<Variable>();
+        let true_v = self._true();
+        let const_0x80 = self.constant::<Variable>(L::Field::from_canonical_u8(0x80));
+
+        // The encoding of each item, with the bytes past its length set to zero.
+        let mut encodings = Vec::with_capacity(items.len());
+        let mut payload_len = zero;
+        for (bytes, len) in items.iter() {
+            let max_len = self.constant::<Variable>(L::Field::from_canonical_usize(bytes.len()));
+            let fits = self.lte(*len, max_len);
+            self.assert_is_equal(fits, true_v);
+            let payload = bytes
+                .iter()
+                .enumerate()
+                .map(|(k, byte)| {
+                    let k = self.constant::<Variable>(L::Field::from_canonical_usize(k));
+                    let in_payload = self.lt(k, *len);
+                    let byte = byte.to_variable(self);
+                    self.select(in_payload, byte, zero)
+                })
+                .collect::<Vec<_>>();
+
+            // A single byte below 0x80 is its own encoding.
+            let (header, header_len) = self.rlp_length_header(*len, 0x80, 0xb7);
+            let is_one_byte = self.is_equal(*len, one);
+            let first = payload.first().copied().unwrap_or(zero);
+            let is_small = self.lt(first, const_0x80);
+            let is_single_byte = self.and(is_one_byte, is_small);
+            let header_len = self.select(is_single_byte, zero, header_len);
+
+            let encoding = self.rlp_prepend_header(header, header_len, &payload);
+            let encoding_len = self.add(header_len, *len);
+            payload_len = self.add(payload_len, encoding_len);
+            encodings.push((encoding, encoding_len));
+        }
+
+        let (header, header_len) = self.rlp_length_header(payload_len, 0xc0, 0xf7);
+        let total_len = self.add(header_len, payload_len);
+        let max_len = self.constant::<Variable>(L::Field::from_canonical_usize(ENCODING_LEN));
+        let fits = self.lte(total_len, max_len);
+        self.assert_is_equal(fits, true_v);
+
+        // Each byte of the output is the sum of the bytes written at its position, of which there
+        // is at most one.
+        let mut offsets = Vec::with_capacity(encodings.len());
+        let mut offset = header_len;
+        for (_, encoding_len) in encodings.iter() {
+            offsets.push(offset);
+            offset = self.add(offset, *encoding_len);
+        }
+        let header = self.rlp_prepend_header(header, header_len, &[]);
+        let bytes = (0..ENCODING_LEN)
+            .map(|j| {
+                let mut byte = header.get(j).copied().unwrap_or(zero);
+                let position = self.constant::<Variable>(L::Field::from_canonical_usize(j));
+                for ((encoding, _), offset) in encodings.iter().zip(offsets.iter()) {
+                    let shift = self.sub(position, *offset);
+                    for (k, encoded_byte) in encoding.iter().enumerate().take(j + 1) {
+                        let k = self.constant::<Variable>(L::Field::from_canonical_usize(k));
+                        let is_at_position = self.is_equal(shift, k);
+                        let contribution = self.mul(is_at_position.variable, *encoded_byte);
+                        byte = self.add(byte, contribution);
+                    }
+                }
+                ByteVariable::from_variable(self, byte)
+            })
+            .collect::<Vec<_>>();
+
+        (ArrayVariable::new(bytes), total_len)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::backend::circuit::DefaultParameters;
+    use crate::backend::circuit::{CircuitBuild, DefaultParameters, GateRegistry, HintRegistry};
     use crate::prelude::{DefaultBuilder, GoldilocksField};
     use crate::utils::bytes;
 
@@ -228,4 +643,226 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_rlp_item_header() {
+        type F = GoldilocksField;
+        const ENCODING_LEN: usize = 600;
+
+        let mut builder: CircuitBuilder<DefaultParameters, 2> = DefaultBuilder::new();
+        let encoding = builder.read::<ArrayVariable<ByteVariable, ENCODING_LEN>>();
+        let start = builder.read::<Variable>();
+        let encoding = encoding
+            .as_slice()
+            .iter()
+            .map(|byte| byte.to_variable(&mut builder))
+            .collect::<Vec<_>>();
+        let (payload_start, payload_len, is_list) = builder.rlp_item_header(&encoding, start);
+        builder.write(payload_start);
+        builder.write(payload_len);
+        builder.write(is_list);
+        let circuit = builder.build();
+
+        for (encoding, start, expected_start, expected_len, expected_is_list) in [
+            ([0x00, 0x05, 0x00, 0x00], 1, 1, 1, false),
+            ([0x83, 0xab, 0xcd, 0xef], 0, 1, 3, false),
+            ([0x80, 0x00, 0x00, 0x00], 0, 1, 0, false),
+            ([0x00, 0xb8, 0x38, 0x00], 1, 3, 56, false),
+            ([0xc3, 0x01, 0x02, 0x03], 0, 1, 3, true),
+            ([0xf9, 0x02, 0x11, 0xa0], 0, 3, 0x211, true),
+        ] {
+            let mut encoding = encoding.to_vec();
+            encoding.resize(ENCODING_LEN, 0);
+            let mut input = circuit.input();
+            input.write::<ArrayVariable<ByteVariable, ENCODING_LEN>>(encoding);
+            input.write::<Variable>(F::from_canonical_usize(start));
+            let (proof, mut output) = circuit.prove(&input);
+            circuit.verify(&proof, &input, &output);
+            assert_eq!(
+                output.read::<Variable>(),
+                F::from_canonical_usize(expected_start)
+            );
+            assert_eq!(
+                output.read::<Variable>(),
+                F::from_canonical_usize(expected_len)
+            );
+            assert_eq!(output.read::<BoolVariable>(), expected_is_list);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "was set twice with different values")]
+    fn test_rlp_item_header_rejects_out_of_bounds() {
+        type F = GoldilocksField;
+        let mut builder: CircuitBuilder<DefaultParameters, 2> = DefaultBuilder::new();
+        let encoding = builder.read::<ArrayVariable<ByteVariable, 4>>();
+        let start = builder.read::<Variable>();
+        let encoding = encoding
+            .as_slice()
+            .iter()
+            .map(|byte| byte.to_variable(&mut builder))
+            .collect::<Vec<_>>();
+        let (payload_start, payload_len, _) = builder.rlp_item_header(&encoding, start);
+        builder.write(payload_start);
+        builder.write(payload_len);
+        let circuit = builder.build();
+
+        // The string at offset 1 claims 3 bytes, but the encoding ends after 2.
+        let mut input = circuit.input();
+        input.write::<ArrayVariable<ByteVariable, 4>>(vec![0x00, 0x83, 0xab, 0xcd]);
+        input.write::<Variable>(F::from_canonical_usize(1));
+        circuit.prove(&input);
+    }
+
+    #[test]
+    fn test_rlp_decode_list() {
+        type F = GoldilocksField;
+        const ENCODING_LEN: usize = 16;
+        const LIST_LEN: usize = 5;
+
+        let mut builder: CircuitBuilder<DefaultParameters, 2> = DefaultBuilder::new();
+        let encoding = builder.read::<ArrayVariable<ByteVariable, ENCODING_LEN>>();
+        let encoding = encoding
+            .as_slice()
+            .iter()
+            .map(|byte| byte.to_variable(&mut builder))
+            .collect::<Vec<_>>();
+        let start = builder.zero::<Variable>();
+        let (fields, num_items) = builder.rlp_decode_list::<LIST_LEN>(&encoding, start);
+        let scalar = builder.rlp_field_scalar(&encoding, &fields[1]);
+        let bytes = builder.rlp_field_bytes::<4>(&encoding, &fields[3]);
+        builder.write(num_items);
+        for field in fields.iter() {
+            builder.write(*field);
+        }
+        builder.write(scalar);
+        builder.write(bytes);
+        let circuit = builder.build();
+
+        // The list [0x05, 0x0400, [], "dog"].
+        let mut encoding: Vec<u8> = bytes!("0xc905820400c083646f67");
+        encoding.resize(ENCODING_LEN, 0);
+        let mut input = circuit.input();
+        input.write::<ArrayVariable<ByteVariable, ENCODING_LEN>>(encoding);
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        assert_eq!(output.read::<Variable>(), F::from_canonical_usize(4));
+        let expected = [
+            (1, 1, 1, false),
+            (2, 3, 2, false),
+            (5, 6, 0, true),
+            (6, 7, 3, false),
+        ];
+        for (start, offset, len, is_list) in expected {
+            let field = output.read::<RLPFieldVariable>();
+            assert_eq!(field.start, F::from_canonical_usize(start));
+            assert_eq!(field.offset, F::from_canonical_usize(offset));
+            assert_eq!(field.len, F::from_canonical_usize(len));
+            assert_eq!(field.is_list, is_list);
+        }
+        let unused = output.read::<RLPFieldVariable>();
+        assert_eq!(unused.len, F::from_canonical_usize(0));
+
+        let mut expected_scalar = [0u8; 32];
+        expected_scalar[30] = 0x04;
+        assert_eq!(output.read::<Bytes32Variable>().0, expected_scalar);
+        assert_eq!(
+            output.read::<ArrayVariable<ByteVariable, 4>>(),
+            vec![0x64, 0x6f, 0x67, 0x00]
+        );
+    }
+
+    #[test]
+    fn test_rlp_decode_list_serialization() {
+        type F = GoldilocksField;
+        const ENCODING_LEN: usize = 16;
+
+        let mut builder: CircuitBuilder<DefaultParameters, 2> = DefaultBuilder::new();
+        let encoding = builder.read::<ArrayVariable<ByteVariable, ENCODING_LEN>>();
+        let encoding = encoding
+            .as_slice()
+            .iter()
+            .map(|byte| byte.to_variable(&mut builder))
+            .collect::<Vec<_>>();
+        let start = builder.zero::<Variable>();
+        let (_, num_items) = builder.rlp_decode_list::<4>(&encoding, start);
+        builder.write(num_items);
+        let circuit = builder.build();
+
+        let gate_serializer = GateRegistry::<DefaultParameters, 2>::new();
+        let hint_serializer = HintRegistry::<DefaultParameters, 2>::new();
+        let bytes = circuit
+            .serialize(&gate_serializer, &hint_serializer)
+            .unwrap();
+        let circuit =
+            CircuitBuild::deserialize(&bytes, &gate_serializer, &hint_serializer).unwrap();
+
+        // The list [0x05, 0x0400, [], "dog"].
+        let mut encoding: Vec<u8> = bytes!("0xc905820400c083646f67");
+        encoding.resize(ENCODING_LEN, 0);
+        let mut input = circuit.input();
+        input.write::<ArrayVariable<ByteVariable, ENCODING_LEN>>(encoding);
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+        assert_eq!(output.read::<Variable>(), F::from_canonical_usize(4));
+    }
+
+    #[test]
+    fn test_rlp_encode_list() {
+        type F = GoldilocksField;
+        const ENCODING_LEN: usize = 96;
+        let capacities = [1usize, 1, 4, 60];
+
+        let mut builder: CircuitBuilder<DefaultParameters, 2> = DefaultBuilder::new();
+        let items = capacities
+            .iter()
+            .map(|capacity| {
+                let bytes = (0..*capacity)
+                    .map(|_| builder.read::<ByteVariable>())
+                    .collect::<Vec<_>>();
+                (bytes, builder.read::<Variable>())
+            })
+            .collect::<Vec<_>>();
+        let (encoding, len) = builder.rlp_encode_list::<ENCODING_LEN>(&items);
+        builder.write(encoding);
+        builder.write(len);
+        let circuit = builder.build();
+
+        // A long list with a long string, and a short list with a single byte item.
+        let cases = [
+            [vec![0x00], vec![0x85], b"dog".to_vec(), vec![0x42; 60]],
+            [vec![0x05], vec![0x85], b"dog".to_vec(), vec![0x42; 10]],
+        ];
+        for (i, case) in cases.iter().enumerate() {
+            let lens = [i, 1, 3, case[3].len()];
+            let mut input = circuit.input();
+            for ((bytes, len), capacity) in case.iter().zip(lens).zip(capacities) {
+                let mut padded = bytes.clone();
+                padded.resize(capacity, 0xff);
+                for byte in padded {
+                    input.write::<ByteVariable>(byte);
+                }
+                input.write::<Variable>(F::from_canonical_usize(len));
+            }
+            let (proof, mut output) = circuit.prove(&input);
+            circuit.verify(&proof, &input, &output);
+
+            let mut stream = ethers::utils::rlp::RlpStream::new_list(case.len());
+            for (bytes, len) in case.iter().zip(lens) {
+                stream.append(&bytes[..len].to_vec());
+            }
+            let mut expected = stream.out().to_vec();
+            let expected_len = expected.len();
+            expected.resize(ENCODING_LEN, 0);
+            assert_eq!(
+                output.read::<ArrayVariable<ByteVariable, ENCODING_LEN>>(),
+                expected
+            );
+            assert_eq!(
+                output.read::<Variable>(),
+                F::from_canonical_usize(expected_len)
+            );
+        }
+    }
 }
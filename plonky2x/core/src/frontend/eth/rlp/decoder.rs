@@ -13,10 +13,11 @@
 //!     3'. Circuit Padded MPT : (ArrayVariable<ArrayVariable<ByteVariable, 32>, 17>, Variable)
 //!         TODO: This will be a new type called MPTFixedSizeVariable.
 //!
-//! We offer three decoding methods:
+//! We offer four decoding methods:
 //! - decode: 1 -> 1'
 //! - decode_padded_mpt_node: 2 -> 2'
 //! - DecodeHint: 3 -> 3'
+//! - rlp_decode_list: 3 -> the offsets and lengths of the items, constrained in-circuit
 //!
 //! Type conversions can be done as following:
 //! - Conversions between encoded types
@@ -34,11 +34,12 @@ impl From<&RLPItem> for RLPItemFixedSize {
                 panic!("not implemented yet")
             }
             RLPItem::String(data) => {
-                // Copy data into self.data.
-                // If data.len() > MAX_RLP_ITEM_SIZE, panic.
+                // Copy data into self.data. Strings longer than MAX_RLP_ITEM_SIZE, such as the
+                // accounts in state trie leaves, are truncated but keep their full length.
                 let len = data.len();
                 let mut array = [0; MAX_RLP_ITEM_SIZE];
-                array[..data.len()].copy_from_slice(data);
+                let copied = len.min(MAX_RLP_ITEM_SIZE);
+                array[..copied].copy_from_slice(&data[..copied]);
 
                 RLPItemFixedSize { data: array, len }
             }
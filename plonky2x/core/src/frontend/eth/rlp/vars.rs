@@ -0,0 +1,22 @@
+use std::fmt::Debug;
+
+use plonky2::hash::hash_types::RichField;
+use plonky2x_derive::CircuitVariable;
+
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::builder::CircuitBuilder;
+use crate::frontend::vars::CircuitVariable;
+use crate::prelude::{BoolVariable, Variable};
+
+/// An item of a decoded RLP list, located in the encoding of the list.
+///
+/// `start` is the offset of the encoding of the item, including its header, while `offset` and
+/// `len` locate its payload. Unused items of a list have a zero length.
+#[derive(Debug, Clone, Copy, CircuitVariable)]
+#[value_name(RLPField)]
+pub struct RLPFieldVariable {
+    pub start: Variable,
+    pub offset: Variable,
+    pub len: Variable,
+    pub is_list: BoolVariable,
+}
@@ -0,0 +1,688 @@
+use core::time::Duration;
+use std::collections::HashMap;
+use std::env;
+use std::sync::Mutex;
+
+use anyhow::{anyhow, ensure, Context, Result};
+use ethers::providers::{Http, Middleware, Provider};
+use ethers::types::transaction::eip2718::TypedTransaction;
+use ethers::types::{Address, Block, Bytes, EIP1186ProofResponse, TransactionRequest, H256};
+use ethers::utils::keccak256;
+use ethers::utils::rlp::RlpStream;
+use lazy_static::lazy_static;
+use plonky2::field::types::Field;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tokio::sync::oneshot;
+
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::builder::CircuitBuilder;
+use crate::frontend::eth::receipt::vars::{EthReceiptLogVariable, MAX_LOG_TOPICS};
+use crate::frontend::eth::storage::vars::EthStorageProofVariable;
+use crate::frontend::eth::utils::u256_to_h256_be;
+use crate::frontend::eth::vars::AddressVariable;
+use crate::frontend::hint::asynchronous::policy::HintPolicy;
+use crate::frontend::uint::uint64::U64Variable;
+use crate::frontend::vars::{
+    ArrayVariable, ByteVariable, Bytes32Variable, BytesVariable, U32Variable, ValueStream,
+    Variable, VariableStream,
+};
+
+/// The maximum number of `eth_getProof` requests in a batched JSON-RPC request.
+const MAX_PROOF_BATCH_SIZE: usize = 100;
+
+/// How long the first `eth_getProof` request of a batch waits for other requests to join it.
+const PROOF_BATCH_WINDOW: Duration = Duration::from_millis(20);
+
+/// An `eth_getProof` request waiting for its batch to be sent.
+#[derive(Debug)]
+struct PendingProof {
+    address: Address,
+    slot: H256,
+    number: u64,
+    tx: oneshot::Sender<Result<EIP1186ProofResponse>>,
+}
+
+lazy_static! {
+    /// The `eth_getProof` requests waiting for their batch to be sent, by chain id.
+    static ref PENDING_PROOFS: Mutex<HashMap<u64, Vec<PendingProof>>> = Mutex::new(HashMap::new());
+}
+
+/// Fetches the witness of the Ethereum gadgets from the RPC of a chain, whose URL is given by the
+/// `RPC_{chain_id}` environment variable.
+///
+/// The requests are sent with the retries, timeout, and rate limit of the `policy` of the
+/// provider. If `batch_proofs` is set, the `eth_getProof` requests of the hints that run at the
+/// same time are aggregated in batched JSON-RPC requests.
+///
+/// The values fetched by the hints of the provider are not constrained: they are shaped to be
+/// checked by the gadgets that consume them, such as `decode_header` and `header_hash` for headers
+/// or `verify_storage_proof` for storage proofs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EthHintProvider {
+    pub chain_id: u64,
+    pub policy: HintPolicy,
+    pub batch_proofs: bool,
+}
+
+impl EthHintProvider {
+    pub fn new(chain_id: u64) -> Self {
+        Self {
+            chain_id,
+            policy: HintPolicy::default(),
+            batch_proofs: false,
+        }
+    }
+
+    pub fn with_policy(mut self, policy: HintPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Aggregates the `eth_getProof` requests of the hints in batched JSON-RPC requests.
+    pub fn with_batched_proofs(mut self) -> Self {
+        self.batch_proofs = true;
+        self
+    }
+
+    /// Returns the provider of the chain of the execution client of `builder`.
+    pub fn from_builder<L: PlonkParameters<D>, const D: usize>(
+        builder: &CircuitBuilder<L, D>,
+    ) -> Self {
+        Self::new(builder.get_chain_id())
+    }
+
+    /// Returns the URL of the RPC of the chain.
+    fn rpc_url(&self) -> Result<String> {
+        dotenv::dotenv().ok();
+        let rpc_var = format!("RPC_{}", self.chain_id);
+        env::var(&rpc_var)
+            .with_context(|| format!("{} environment variable was not found", rpc_var))
+    }
+
+    /// Returns the RPC client of the chain.
+    pub fn client(&self) -> Result<Provider<Http>> {
+        let rpc_url = self.rpc_url()?;
+        Provider::<Http>::try_from(rpc_url.as_str())
+            .with_context(|| format!("RPC_{} is not a valid RPC URL", self.chain_id))
+    }
+
+    /// Fetches the proofs of `slot` of `address` at the block `number`, in a batch with the other
+    /// requests sent within `PROOF_BATCH_WINDOW`.
+    async fn get_proof_batched(
+        &self,
+        address: Address,
+        slot: H256,
+        number: u64,
+    ) -> Result<EIP1186ProofResponse> {
+        let (tx, rx) = oneshot::channel();
+        let is_first = {
+            let mut pending = PENDING_PROOFS.lock().unwrap();
+            let requests = pending.entry(self.chain_id).or_default();
+            requests.push(PendingProof {
+                address,
+                slot,
+                number,
+                tx,
+            });
+            requests.len() == 1
+        };
+
+        // The first request of a batch sends it after the window, independently of the hint.
+        if is_first {
+            let provider = *self;
+            tokio::spawn(async move {
+                tokio::time::sleep(PROOF_BATCH_WINDOW).await;
+                let mut requests = PENDING_PROOFS
+                    .lock()
+                    .unwrap()
+                    .remove(&provider.chain_id)
+                    .unwrap_or_default();
+                while !requests.is_empty() {
+                    let batch_size = requests.len().min(MAX_PROOF_BATCH_SIZE);
+                    let batch = requests.drain(..batch_size).collect::<Vec<_>>();
+                    provider.send_proof_batch(batch).await;
+                }
+            });
+        }
+
+        rx.await
+            .context("the batch of eth_getProof requests was dropped")?
+    }
+
+    /// Sends a batch of `eth_getProof` requests, and sends back the responses to the requests.
+    async fn send_proof_batch(&self, batch: Vec<PendingProof>) {
+        let body = batch
+            .iter()
+            .enumerate()
+            .map(|(id, request)| {
+                json!({
+                    "jsonrpc": "2.0",
+                    "id": id,
+                    "method": "eth_getProof",
+                    "params": [request.address, [request.slot], format!("0x{:x}", request.number)],
+                })
+            })
+            .collect::<Vec<_>>();
+        let client = reqwest::Client::new();
+        let responses = match self.rpc_url() {
+            Ok(rpc_url) => {
+                let (client, rpc_url, body) = (&client, &rpc_url, &body);
+                let name = format!("batch of {} eth_getProof requests", batch.len());
+                self.policy
+                    .run(&name, move || post_batch(client, rpc_url, body))
+                    .await
+            }
+            Err(e) => Err(e),
+        };
+
+        let mut responses = match responses {
+            Ok(responses) => responses,
+            Err(e) => {
+                for request in batch {
+                    request.tx.send(Err(anyhow!("{:#}", e))).ok();
+                }
+                return;
+            }
+        };
+        for (id, request) in batch.into_iter().enumerate() {
+            let response = responses
+                .iter_mut()
+                .find(|response| response["id"].as_u64() == Some(id as u64))
+                .map(Value::take);
+            let result = match response {
+                Some(mut response) if !response["result"].is_null() => {
+                    serde_json::from_value(response["result"].take())
+                        .context("failed to parse the response to eth_getProof")
+                }
+                Some(response) => Err(anyhow!("eth_getProof failed: {}", response["error"])),
+                None => Err(anyhow!("the batch has no response to eth_getProof")),
+            };
+            request.tx.send(result).ok();
+        }
+    }
+}
+
+/// Posts a batched JSON-RPC request, and returns the responses.
+async fn post_batch(client: &reqwest::Client, rpc_url: &str, body: &[Value]) -> Result<Vec<Value>> {
+    let response = client
+        .post(rpc_url)
+        .json(body)
+        .send()
+        .await?
+        .error_for_status()?;
+    Ok(response.json().await?)
+}
+
+/// Returns the RLP encoding of the header of `block`, which is checked against the hash of the
+/// block.
+///
+/// The fields introduced by hard forks are appended up to the first one that the block lacks.
+pub fn encode_header(block: &Block<H256>) -> Result<Vec<u8>> {
+    let mut stream = RlpStream::new();
+    stream.begin_unbounded_list();
+    stream.append(&block.parent_hash.as_bytes().to_vec());
+    stream.append(&block.uncles_hash.as_bytes().to_vec());
+    let coinbase = block.author.context("the block has no coinbase")?;
+    stream.append(&coinbase.as_bytes().to_vec());
+    stream.append(&block.state_root.as_bytes().to_vec());
+    stream.append(&block.transactions_root.as_bytes().to_vec());
+    stream.append(&block.receipts_root.as_bytes().to_vec());
+    let logs_bloom = block.logs_bloom.context("the block has no logs bloom")?;
+    stream.append(&logs_bloom.as_bytes().to_vec());
+    stream.append(&block.difficulty);
+    stream.append(&block.number.context("the block is pending")?.as_u64());
+    stream.append(&block.gas_limit);
+    stream.append(&block.gas_used);
+    stream.append(&block.timestamp);
+    stream.append(&block.extra_data.to_vec());
+    let mix_hash = block.mix_hash.context("the block has no mix hash")?;
+    stream.append(&mix_hash.as_bytes().to_vec());
+    let nonce = block.nonce.context("the block has no nonce")?;
+    stream.append(&nonce.as_bytes().to_vec());
+    if let Some(base_fee) = block.base_fee_per_gas {
+        stream.append(&base_fee);
+        if let Some(withdrawals_root) = block.withdrawals_root {
+            stream.append(&withdrawals_root.as_bytes().to_vec());
+            if let (Some(blob_gas_used), Some(excess_blob_gas), Some(parent_beacon_block_root)) = (
+                block.blob_gas_used,
+                block.excess_blob_gas,
+                block.parent_beacon_block_root,
+            ) {
+                stream.append(&blob_gas_used);
+                stream.append(&excess_blob_gas);
+                stream.append(&parent_beacon_block_root.as_bytes().to_vec());
+                if let Some(requests_hash) = block.other.get_deserialized::<H256>("requestsHash") {
+                    stream.append(&requests_hash?.as_bytes().to_vec());
+                }
+            }
+        }
+    }
+    stream.finalize_unbounded_list();
+
+    let encoding = stream.out().to_vec();
+    let hash = block.hash.context("the block is pending")?;
+    ensure!(
+        H256(keccak256(&encoding)) == hash,
+        "the encoding of the header of block {:?} does not match its hash",
+        hash
+    );
+    Ok(encoding)
+}
+
+/// Pads `bytes` with zeros to `len` bytes.
+fn pad_bytes(bytes: &[u8], len: usize) -> Result<Vec<u8>> {
+    ensure!(
+        bytes.len() <= len,
+        "{} bytes do not fit in {} bytes",
+        bytes.len(),
+        len
+    );
+    let mut padded = bytes.to_vec();
+    padded.resize(len, 0);
+    Ok(padded)
+}
+
+/// Writes the nodes of an MPT proof, each padded to `encoding_len` bytes and padded with empty
+/// nodes to `proof_len` nodes, followed by the lengths of the nodes.
+fn write_proof<L: PlonkParameters<D>, const D: usize>(
+    output_stream: &mut ValueStream<L, D>,
+    nodes: &[Bytes],
+    encoding_len: usize,
+    proof_len: usize,
+) -> Result<()> {
+    ensure!(
+        nodes.len() <= proof_len,
+        "the proof has {} nodes, but PROOF_LEN is {}",
+        nodes.len(),
+        proof_len
+    );
+    let empty_node = Bytes::default();
+    for i in 0..proof_len {
+        let node = nodes.get(i).unwrap_or(&empty_node);
+        let node = pad_bytes(node, encoding_len).context("a node does not fit in ENCODING_LEN")?;
+        for byte in node {
+            output_stream.write_value::<ByteVariable>(byte);
+        }
+    }
+    for i in 0..proof_len {
+        let len = nodes.get(i).map_or(0, |node| node.len());
+        output_stream.write_value::<U32Variable>(len as u32);
+    }
+    Ok(())
+}
+
+/// Implements `AsyncHint` for an RPC hint, so that the errors of its `fetch` method are returned
+/// by `try_hint_fn`.
+macro_rules! impl_rpc_hint {
+    ($hint:ty) => {
+        #[async_trait::async_trait]
+        impl<L: crate::backend::circuit::PlonkParameters<D>, const D: usize>
+            crate::frontend::hint::asynchronous::hint::AsyncHint<L, D> for $hint
+        {
+            async fn hint(
+                &self,
+                input_stream: &mut crate::frontend::vars::ValueStream<L, D>,
+                output_stream: &mut crate::frontend::vars::ValueStream<L, D>,
+            ) {
+                self.fetch(input_stream, output_stream)
+                    .await
+                    .expect("Failed to fetch the hint from the RPC");
+            }
+
+            async fn try_hint_fn(
+                &self,
+                mut input_stream: crate::frontend::vars::ValueStream<L, D>,
+            ) -> anyhow::Result<crate::frontend::vars::ValueStream<L, D>> {
+                let mut output_stream = crate::frontend::vars::ValueStream::new();
+                self.fetch(&mut input_stream, &mut output_stream).await?;
+                Ok(output_stream)
+            }
+        }
+    };
+}
+
+pub(crate) use impl_rpc_hint;
+
+/// Fetches the header of a block with `eth_getBlockByNumber`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EthBlockByNumberHint {
+    provider: EthHintProvider,
+    encoding_len: usize,
+}
+
+impl EthBlockByNumberHint {
+    async fn fetch<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        input_stream: &mut ValueStream<L, D>,
+        output_stream: &mut ValueStream<L, D>,
+    ) -> Result<()> {
+        let number = input_stream.read_value::<U64Variable>();
+        let client = &self.provider.client()?;
+        let block = self
+            .provider
+            .policy
+            .run("eth_getBlockByNumber", move || async move {
+                client.get_block(number).await.map_err(anyhow::Error::from)
+            })
+            .await?
+            .with_context(|| format!("block {} was not found", number))?;
+        let encoding = encode_header(&block)?;
+        let encoding = pad_bytes(&encoding, self.encoding_len)
+            .context("the header does not fit in ENCODING_LEN")?;
+        for byte in encoding {
+            output_stream.write_value::<ByteVariable>(byte);
+        }
+        Ok(())
+    }
+}
+
+impl_rpc_hint!(EthBlockByNumberHint);
+
+/// Fetches the account and storage proofs of a storage slot with `eth_getProof`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EthGetProofHint {
+    provider: EthHintProvider,
+    encoding_len: usize,
+    proof_len: usize,
+}
+
+impl EthGetProofHint {
+    async fn fetch<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        input_stream: &mut ValueStream<L, D>,
+        output_stream: &mut ValueStream<L, D>,
+    ) -> Result<()> {
+        let number = input_stream.read_value::<U64Variable>();
+        let address = input_stream.read_value::<AddressVariable>();
+        let slot = input_stream.read_value::<Bytes32Variable>();
+        let response = if self.provider.batch_proofs {
+            self.provider
+                .get_proof_batched(address, slot, number)
+                .await?
+        } else {
+            let client = &self.provider.client()?;
+            self.provider
+                .policy
+                .run("eth_getProof", move || async move {
+                    client
+                        .get_proof(address, vec![slot], Some(number.into()))
+                        .await
+                        .map_err(anyhow::Error::from)
+                })
+                .await?
+        };
+        let storage_proof = response
+            .storage_proof
+            .first()
+            .context("the response has no storage proof")?;
+        write_proof(
+            output_stream,
+            &response.account_proof,
+            self.encoding_len,
+            self.proof_len,
+        )?;
+        write_proof(
+            output_stream,
+            &storage_proof.proof,
+            self.encoding_len,
+            self.proof_len,
+        )?;
+        output_stream.write_value::<Bytes32Variable>(u256_to_h256_be(storage_proof.value));
+        Ok(())
+    }
+}
+
+impl_rpc_hint!(EthGetProofHint);
+
+/// Fetches a log of the receipt of a transaction with `eth_getTransactionReceipt`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EthTransactionReceiptHint {
+    provider: EthHintProvider,
+    log_index: usize,
+    max_data_len: usize,
+}
+
+impl EthTransactionReceiptHint {
+    async fn fetch<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        input_stream: &mut ValueStream<L, D>,
+        output_stream: &mut ValueStream<L, D>,
+    ) -> Result<()> {
+        let transaction_hash = input_stream.read_value::<Bytes32Variable>();
+        let client = &self.provider.client()?;
+        let receipt = self
+            .provider
+            .policy
+            .run("eth_getTransactionReceipt", move || async move {
+                client
+                    .get_transaction_receipt(transaction_hash)
+                    .await
+                    .map_err(anyhow::Error::from)
+            })
+            .await?
+            .with_context(|| format!("transaction {:?} was not found", transaction_hash))?;
+        let block_number = receipt.block_number.context("the transaction is pending")?;
+        let log = receipt.logs.get(self.log_index).with_context(|| {
+            format!(
+                "transaction {:?} has no log at index {}",
+                transaction_hash, self.log_index
+            )
+        })?;
+        ensure!(
+            log.topics.len() <= MAX_LOG_TOPICS,
+            "the log has {} topics",
+            log.topics.len()
+        );
+        let data = pad_bytes(&log.data, self.max_data_len)
+            .context("the data of the log does not fit in MAX_DATA_LEN")?;
+
+        output_stream.write_value::<U64Variable>(block_number.as_u64());
+        output_stream.write_value::<Variable>(L::Field::from_canonical_u64(
+            receipt.transaction_index.as_u64(),
+        ));
+        output_stream.write_value::<AddressVariable>(log.address);
+        for i in 0..MAX_LOG_TOPICS {
+            let topic = log.topics.get(i).copied().unwrap_or_default();
+            output_stream.write_value::<Bytes32Variable>(topic);
+        }
+        output_stream.write_value::<Variable>(L::Field::from_canonical_usize(log.topics.len()));
+        for byte in data {
+            output_stream.write_value::<ByteVariable>(byte);
+        }
+        output_stream.write_value::<Variable>(L::Field::from_canonical_usize(log.data.len()));
+        Ok(())
+    }
+}
+
+impl_rpc_hint!(EthTransactionReceiptHint);
+
+/// Fetches the output of a call to a contract with `eth_call`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EthCallHint {
+    provider: EthHintProvider,
+    calldata_len: usize,
+    output_len: usize,
+}
+
+impl EthCallHint {
+    async fn fetch<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        input_stream: &mut ValueStream<L, D>,
+        output_stream: &mut ValueStream<L, D>,
+    ) -> Result<()> {
+        let number = input_stream.read_value::<U64Variable>();
+        let to = input_stream.read_value::<AddressVariable>();
+        let calldata = input_stream.read_vec::<ByteVariable>(self.calldata_len);
+        let transaction: TypedTransaction = TransactionRequest::new().to(to).data(calldata).into();
+        let (client, transaction) = (&self.provider.client()?, &transaction);
+        let output = self
+            .provider
+            .policy
+            .run("eth_call", move || async move {
+                client
+                    .call(transaction, Some(number.into()))
+                    .await
+                    .map_err(anyhow::Error::from)
+            })
+            .await?;
+        let padded_output = pad_bytes(&output, self.output_len)
+            .context("the output of the call does not fit in OUTPUT_LEN")?;
+        for byte in padded_output {
+            output_stream.write_value::<ByteVariable>(byte);
+        }
+        output_stream.write_value::<Variable>(L::Field::from_canonical_usize(output.len()));
+        Ok(())
+    }
+}
+
+impl_rpc_hint!(EthCallHint);
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Fetches the RLP encoding of the header of the block `number` with `eth_getBlockByNumber`,
+    /// padded to `ENCODING_LEN` bytes, as consumed by `decode_header` and `header_hash`.
+    pub fn eth_rpc_get_block_by_number<const ENCODING_LEN: usize>(
+        &mut self,
+        provider: &EthHintProvider,
+        number: U64Variable,
+    ) -> ArrayVariable<ByteVariable, ENCODING_LEN> {
+        let mut input_stream = VariableStream::new();
+        input_stream.write(&number);
+        let hint = EthBlockByNumberHint {
+            provider: *provider,
+            encoding_len: ENCODING_LEN,
+        };
+        let output_stream = self.async_hint(input_stream, hint);
+        output_stream.read::<ArrayVariable<ByteVariable, ENCODING_LEN>>(self)
+    }
+
+    /// Fetches the proofs of `slot` of `address` at the block `block_number` with `eth_getProof`,
+    /// as consumed by `verify_storage_proof`, and the value of the slot.
+    pub fn eth_rpc_get_proof<const ENCODING_LEN: usize, const PROOF_LEN: usize>(
+        &mut self,
+        provider: &EthHintProvider,
+        block_number: U64Variable,
+        address: AddressVariable,
+        slot: Bytes32Variable,
+    ) -> (
+        EthStorageProofVariable<ENCODING_LEN, PROOF_LEN>,
+        Bytes32Variable,
+    ) {
+        let mut input_stream = VariableStream::new();
+        input_stream.write(&block_number);
+        input_stream.write(&address);
+        input_stream.write(&slot);
+        let hint = EthGetProofHint {
+            provider: *provider,
+            encoding_len: ENCODING_LEN,
+            proof_len: PROOF_LEN,
+        };
+        let output_stream = self.async_hint(input_stream, hint);
+        let proof = output_stream.read::<EthStorageProofVariable<ENCODING_LEN, PROOF_LEN>>(self);
+        let value = output_stream.read::<Bytes32Variable>(self);
+        (proof, value)
+    }
+
+    /// Fetches the log at `log_index` of the receipt of a transaction with
+    /// `eth_getTransactionReceipt`, with the number of its block and its index in the block.
+    ///
+    /// The response has no proof of the receipt, so that the log must be checked against the
+    /// output of `get_receipt_log`, given a proof of the receipts trie of the block.
+    pub fn eth_rpc_get_transaction_receipt<const MAX_DATA_LEN: usize>(
+        &mut self,
+        provider: &EthHintProvider,
+        transaction_hash: Bytes32Variable,
+        log_index: usize,
+    ) -> (U64Variable, Variable, EthReceiptLogVariable<MAX_DATA_LEN>) {
+        let mut input_stream = VariableStream::new();
+        input_stream.write(&transaction_hash);
+        let hint = EthTransactionReceiptHint {
+            provider: *provider,
+            log_index,
+            max_data_len: MAX_DATA_LEN,
+        };
+        let output_stream = self.async_hint(input_stream, hint);
+        let block_number = output_stream.read::<U64Variable>(self);
+        let tx_index = output_stream.read::<Variable>(self);
+        let log = output_stream.read::<EthReceiptLogVariable<MAX_DATA_LEN>>(self);
+        (block_number, tx_index, log)
+    }
+
+    /// Fetches the output of calling `to` with `calldata` at the block `block_number` with
+    /// `eth_call`, padded to `OUTPUT_LEN` bytes, and its length.
+    ///
+    /// The output cannot be proven from the state root alone, so that it is only suited to
+    /// computing witness, such as the storage slots to prove with `eth_rpc_get_proof`.
+    pub fn eth_rpc_call<const CALLDATA_LEN: usize, const OUTPUT_LEN: usize>(
+        &mut self,
+        provider: &EthHintProvider,
+        block_number: U64Variable,
+        to: AddressVariable,
+        calldata: &BytesVariable<CALLDATA_LEN>,
+    ) -> (ArrayVariable<ByteVariable, OUTPUT_LEN>, Variable) {
+        let mut input_stream = VariableStream::new();
+        input_stream.write(&block_number);
+        input_stream.write(&to);
+        input_stream.write(calldata);
+        let hint = EthCallHint {
+            provider: *provider,
+            calldata_len: CALLDATA_LEN,
+            output_len: OUTPUT_LEN,
+        };
+        let output_stream = self.async_hint(input_stream, hint);
+        let output = output_stream.read::<ArrayVariable<ByteVariable, OUTPUT_LEN>>(self);
+        let output_len = output_stream.read::<Variable>(self);
+        (output, output_len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ethers::providers::{Http, Provider};
+
+    use super::*;
+    use crate::prelude::DefaultBuilder;
+    use crate::utils::{address, bytes32};
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_eth_rpc_get_proof() {
+        const HEADER_LEN: usize = 640;
+        const ENCODING_LEN: usize = 600;
+        const PROOF_LEN: usize = 12;
+
+        dotenv::dotenv().ok();
+        let rpc_url = env::var("RPC_1").unwrap();
+        let provider = Provider::<Http>::try_from(rpc_url).unwrap();
+
+        let mut builder = DefaultBuilder::new();
+        builder.set_execution_client(provider);
+        let rpc = EthHintProvider::from_builder(&builder);
+        let number = builder.read::<U64Variable>();
+        let address = builder.read::<AddressVariable>();
+        let slot = builder.read::<Bytes32Variable>();
+
+        let header_rlp = builder.eth_rpc_get_block_by_number::<HEADER_LEN>(&rpc, number);
+        let header = builder.decode_header(&header_rlp);
+        builder.assert_is_equal(header.number, number);
+        let (proof, value) =
+            builder.eth_rpc_get_proof::<ENCODING_LEN, PROOF_LEN>(&rpc, number, address, slot);
+        let stored_value = builder.verify_storage_proof(header.state_root, address, slot, &proof);
+        builder.assert_is_equal(stored_value, value);
+        builder.write(value);
+        let circuit = builder.mock_build();
+
+        // The values are taken from Ethereum block https://etherscan.io/block/17880427.
+        let mut input = circuit.input();
+        input.write::<U64Variable>(17880427);
+        input.write::<AddressVariable>(address!("0x55032650b14df07b85bF18A3a3eC8E0Af2e028d5"));
+        input.write::<Bytes32Variable>(bytes32!(
+            "0xad3228b676f7d3cd4284a5443f17f1962b36e491b30a40b2405849e597ba5fb5"
+        ));
+        let (_witness, mut output) = circuit.mock_prove(&input);
+        assert_eq!(
+            output.read::<Bytes32Variable>(),
+            bytes32!("0x0000000000000000000000dd4bc51496dc93a0c47008e820e0d80745476f2201"),
+        );
+    }
+}
@@ -1,14 +1,18 @@
 use ethers::types::Address;
+use plonky2::field::types::Field;
 
-use super::generators::{
-    EthBlockGenerator, EthLogGenerator, EthStorageKeyGenerator, EthStorageProofHint,
+use super::generators::{EthBlockHint, EthLogHint, EthStorageKeyGenerator, EthStorageProofHint};
+use super::vars::{
+    EthAccountProofVariable, EthAccountVariable, EthHeaderVariable, EthLogVariable,
+    EthStorageProofVariable,
 };
-use super::vars::{EthAccountVariable, EthHeaderVariable, EthLogVariable};
 use crate::backend::circuit::PlonkParameters;
 use crate::frontend::builder::CircuitBuilder;
+use crate::frontend::eth::abi::vars::AbiToken;
+use crate::frontend::eth::mpt::builder::MPTLookup;
 use crate::frontend::eth::vars::AddressVariable;
 use crate::frontend::uint::uint256::U256Variable;
-use crate::frontend::vars::{Bytes32Variable, VariableStream};
+use crate::frontend::vars::{Bytes32Variable, Variable, VariableStream};
 
 impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
     pub fn get_storage_key_at_witness(
@@ -22,6 +26,56 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         value
     }
 
+    /// Returns the storage slot of the value at `key` of a mapping at `base_slot`, which is
+    /// `keccak256(abi.encode(key, base_slot))`.
+    ///
+    /// The key must be a value type, such as an integer, an address or a `bytes32`.
+    pub fn mapping_storage_slot(
+        &mut self,
+        key: AbiToken,
+        base_slot: U256Variable,
+    ) -> Bytes32Variable {
+        assert!(
+            matches!(key, AbiToken::Uint(_) | AbiToken::FixedBytes(_)),
+            "the key of a mapping must be a value type"
+        );
+        let base_slot = AbiToken::uint(self, &base_slot);
+        let encoding = self.abi_encode::<64>(&[key, base_slot]);
+        self.keccak256(encoding.data.as_slice())
+    }
+
+    /// Returns the storage slot of the value at `keys` of nested mappings at `base_slot`, such as
+    /// `allowance[owner][spender]`, starting from the outermost key.
+    pub fn nested_mapping_storage_slot(
+        &mut self,
+        keys: &[AbiToken],
+        base_slot: U256Variable,
+    ) -> Bytes32Variable {
+        assert!(!keys.is_empty(), "at least one key is required");
+        let mut slot = self.u256_to_bytes32(base_slot);
+        for key in keys.iter() {
+            let base_slot = self.bytes32_to_u256(slot);
+            slot = self.mapping_storage_slot(key.clone(), base_slot);
+        }
+        slot
+    }
+
+    /// Returns the storage slot of the element at `index` of a dynamic array at `base_slot`, which
+    /// is `keccak256(base_slot) + index`, wrapping around modulo `2^256`.
+    ///
+    /// The element is assumed to take one slot.
+    pub fn array_storage_slot(
+        &mut self,
+        base_slot: U256Variable,
+        index: U256Variable,
+    ) -> Bytes32Variable {
+        let base_slot = self.u256_to_bytes32(base_slot);
+        let data_slot = self.keccak256(&base_slot.as_bytes());
+        let data_slot = self.bytes32_to_u256(data_slot);
+        let slot = self.add(data_slot, index);
+        self.u256_to_bytes32(slot)
+    }
+
     pub fn eth_get_storage_at_witness(
         &mut self,
         block_hash: Bytes32Variable,
@@ -43,10 +97,13 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         &mut self,
         block_hash: Bytes32Variable,
     ) -> EthHeaderVariable {
-        let generator = EthBlockGenerator::new(self, block_hash);
-        let value = generator.value;
-        self.add_simple_generator(generator);
-        value
+        let mut input_stream = VariableStream::new();
+        input_stream.write(&block_hash);
+
+        let hint = EthBlockHint::new(self);
+        let output_stream = self.async_hint(input_stream, hint);
+
+        output_stream.read::<EthHeaderVariable>(self)
     }
 
     pub fn eth_get_account(
@@ -57,16 +114,100 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         todo!()
     }
 
+    /// Verifies the account proof of `address` against `state_root` and the storage proof of
+    /// `slot` against the storage root of the account, and returns the value of the slot. The
+    /// value is zero if the slot is not set, but the account must exist.
+    pub fn verify_storage_proof<const ENCODING_LEN: usize, const PROOF_LEN: usize>(
+        &mut self,
+        state_root: Bytes32Variable,
+        address: AddressVariable,
+        slot: Bytes32Variable,
+        proof_nodes: &EthStorageProofVariable<ENCODING_LEN, PROOF_LEN>,
+    ) -> Bytes32Variable {
+        let account = self.mpt_lookup(
+            &address.0 .0,
+            proof_nodes.account_proof.clone(),
+            proof_nodes.account_proof_lens.clone(),
+            state_root,
+        );
+        let false_v = self._false();
+        self.assert_is_equal(account.excluded, false_v);
+
+        let storage_root = self.eth_decode_account(&account).storage_hash;
+        self.get_mpt_value(
+            slot,
+            proof_nodes.storage_proof.clone(),
+            proof_nodes.storage_proof_lens.clone(),
+            storage_root,
+        )
+    }
+
+    /// Verifies the account proof of `address` against `state_root` and returns the fields of the
+    /// account, decoded from the RLP of the state trie leaf. The account must exist.
+    pub fn get_account<const ENCODING_LEN: usize, const PROOF_LEN: usize>(
+        &mut self,
+        state_root: Bytes32Variable,
+        address: AddressVariable,
+        proof: &EthAccountProofVariable<ENCODING_LEN, PROOF_LEN>,
+    ) -> EthAccountVariable {
+        let account = self.mpt_lookup(
+            &address.0 .0,
+            proof.proof.clone(),
+            proof.proof_lens.clone(),
+            state_root,
+        );
+        let false_v = self._false();
+        self.assert_is_equal(account.excluded, false_v);
+
+        self.eth_decode_account(&account)
+    }
+
+    /// Decodes the account held by a state trie leaf, which is the list [path, account], where the
+    /// account is a string holding the list [nonce, balance, storage_root, code_hash].
+    fn eth_decode_account<const ENCODING_LEN: usize>(
+        &mut self,
+        leaf: &MPTLookup<ENCODING_LEN>,
+    ) -> EthAccountVariable {
+        let node = leaf
+            .node
+            .as_slice()
+            .iter()
+            .map(|byte| byte.to_variable(self))
+            .collect::<Vec<_>>();
+        let zero = self.zero::<Variable>();
+        let (fields, _) = self.rlp_decode_list::<2>(&node, zero);
+        let (account, _) = self.rlp_decode_list::<4>(&node, fields[1].offset);
+
+        let const_32 = self.constant::<Variable>(L::Field::from_canonical_u8(32));
+        self.assert_is_equal(account[2].len, const_32);
+        self.assert_is_equal(account[3].len, const_32);
+        let nonce = self.rlp_field_scalar(&node, &account[0]);
+        let balance = self.rlp_field_scalar(&node, &account[1]);
+        let storage_hash = self.rlp_field_scalar(&node, &account[2]);
+        let code_hash = self.rlp_field_scalar(&node, &account[3]);
+
+        EthAccountVariable {
+            balance: self.bytes32_to_u256(balance),
+            code_hash,
+            nonce: self.bytes32_to_u256(nonce),
+            storage_hash,
+        }
+    }
+
     pub fn eth_get_transaction_log_witness(
         &mut self,
         transaction_hash: Bytes32Variable,
         block_hash: Bytes32Variable,
         log_index: u64,
     ) -> EthLogVariable {
-        let generator = EthLogGenerator::new(self, transaction_hash, block_hash, log_index);
-        let value = generator.clone().value;
-        self.add_simple_generator(generator);
-        value
+        let mut input_stream = VariableStream::new();
+        input_stream.write(&transaction_hash);
+        input_stream.write(&block_hash);
+
+        let hint = EthLogHint::new(self, log_index);
+        let output_stream = self.async_hint(input_stream, hint);
+
+        output_stream.read::<EthLogVariable>(self)
     }
 }
 
@@ -75,13 +216,18 @@ mod tests {
     use std::env;
 
     use ethers::providers::{Http, Provider};
-    use ethers::types::{U256, U64};
+    use ethers::types::{H256, U256, U64};
+    use ethers::utils::keccak256;
     use log::debug;
 
     use super::*;
     use crate::backend::circuit::{CircuitBuild, DefaultParameters, GateRegistry, HintRegistry};
+    use crate::frontend::eth::mpt::utils::read_fixture;
     use crate::frontend::eth::storage::utils::get_map_storage_location;
-    use crate::frontend::eth::storage::vars::{EthHeader, EthLog};
+    use crate::frontend::eth::storage::vars::{
+        EthAccountProof, EthHeader, EthLog, EthStorageProof,
+    };
+    use crate::frontend::eth::utils::u256_to_h256_be;
     use crate::prelude::DefaultBuilder;
     use crate::utils::{self, address, bytes32};
 
@@ -217,6 +363,145 @@ mod tests {
         }
     }
 
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_verify_storage_proof() {
+        utils::setup_logger();
+        let response = read_fixture("./src/frontend/eth/mpt/fixtures/example.json");
+        // The state root of block 17880427, against which the fixture was fetched.
+        let state_root =
+            bytes32!("0xff90251f501c864f21d696c811af4c3aa987006916bd0e31a6c06cc612e7632e");
+
+        const ENCODING_LEN: usize = 600;
+        const PROOF_LEN: usize = 10;
+
+        let mut builder = DefaultBuilder::new();
+        let state_root_variable = builder.read::<Bytes32Variable>();
+        let address = builder.read::<AddressVariable>();
+        let slot = builder.read::<Bytes32Variable>();
+        let proof_nodes = builder.read::<EthStorageProofVariable<ENCODING_LEN, PROOF_LEN>>();
+        let value = builder.verify_storage_proof(state_root_variable, address, slot, &proof_nodes);
+        builder.write(value);
+        let circuit = builder.mock_build();
+
+        let mut input = circuit.input();
+        input.write::<Bytes32Variable>(state_root);
+        input.write::<AddressVariable>(response.address);
+        input.write::<Bytes32Variable>(response.storage_proof[0].key);
+        input.write::<EthStorageProofVariable<ENCODING_LEN, PROOF_LEN>>(
+            EthStorageProof::from_proof_response(&response),
+        );
+        let (_witness, mut output) = circuit.mock_prove(&input);
+
+        assert_eq!(
+            output.read::<Bytes32Variable>(),
+            u256_to_h256_be(response.storage_proof[0].value)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_get_account() {
+        utils::setup_logger();
+        let response = read_fixture("./src/frontend/eth/mpt/fixtures/example.json");
+        // The state root of block 17880427, against which the fixture was fetched.
+        let state_root =
+            bytes32!("0xff90251f501c864f21d696c811af4c3aa987006916bd0e31a6c06cc612e7632e");
+
+        const ENCODING_LEN: usize = 600;
+        const PROOF_LEN: usize = 10;
+
+        let mut builder = DefaultBuilder::new();
+        let state_root_variable = builder.read::<Bytes32Variable>();
+        let address = builder.read::<AddressVariable>();
+        let proof = builder.read::<EthAccountProofVariable<ENCODING_LEN, PROOF_LEN>>();
+        let account = builder.get_account(state_root_variable, address, &proof);
+        builder.write(account);
+        let circuit = builder.mock_build();
+
+        let mut input = circuit.input();
+        input.write::<Bytes32Variable>(state_root);
+        input.write::<AddressVariable>(response.address);
+        input.write::<EthAccountProofVariable<ENCODING_LEN, PROOF_LEN>>(
+            EthAccountProof::from_proof_response(&response),
+        );
+        let (_witness, mut output) = circuit.mock_prove(&input);
+
+        let account = output.read::<EthAccountVariable>();
+        assert_eq!(account.nonce, U256::from(response.nonce.as_u64()));
+        assert_eq!(account.balance, response.balance);
+        assert_eq!(account.storage_hash, response.storage_hash);
+        assert_eq!(account.code_hash, response.code_hash);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    #[should_panic(expected = "was set twice with different values")]
+    fn test_verify_storage_proof_rejects_tampered_proof() {
+        let response = read_fixture("./src/frontend/eth/mpt/fixtures/example.json");
+        let state_root =
+            bytes32!("0xff90251f501c864f21d696c811af4c3aa987006916bd0e31a6c06cc612e7632e");
+
+        const ENCODING_LEN: usize = 600;
+        const PROOF_LEN: usize = 10;
+
+        let mut builder = DefaultBuilder::new();
+        let state_root_variable = builder.read::<Bytes32Variable>();
+        let address = builder.read::<AddressVariable>();
+        let slot = builder.read::<Bytes32Variable>();
+        let proof_nodes = builder.read::<EthStorageProofVariable<ENCODING_LEN, PROOF_LEN>>();
+        let value = builder.verify_storage_proof(state_root_variable, address, slot, &proof_nodes);
+        builder.write(value);
+        let circuit = builder.mock_build();
+
+        // Change the value held by the leaf of the storage proof.
+        let mut proof =
+            EthStorageProof::<ENCODING_LEN, PROOF_LEN, _>::from_proof_response(&response);
+        let leaf = response.storage_proof[0].proof.len() - 1;
+        let last = proof.storage_proof_lens[leaf] as usize - 1;
+        proof.storage_proof[leaf][last] ^= 1;
+
+        let mut input = circuit.input();
+        input.write::<Bytes32Variable>(state_root);
+        input.write::<AddressVariable>(response.address);
+        input.write::<Bytes32Variable>(response.storage_proof[0].key);
+        input.write::<EthStorageProofVariable<ENCODING_LEN, PROOF_LEN>>(proof);
+        circuit.mock_prove(&input);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    #[should_panic(expected = "was set twice with different values")]
+    fn test_get_account_rejects_tampered_proof() {
+        let response = read_fixture("./src/frontend/eth/mpt/fixtures/example.json");
+        let state_root =
+            bytes32!("0xff90251f501c864f21d696c811af4c3aa987006916bd0e31a6c06cc612e7632e");
+
+        const ENCODING_LEN: usize = 600;
+        const PROOF_LEN: usize = 10;
+
+        let mut builder = DefaultBuilder::new();
+        let state_root_variable = builder.read::<Bytes32Variable>();
+        let address = builder.read::<AddressVariable>();
+        let proof = builder.read::<EthAccountProofVariable<ENCODING_LEN, PROOF_LEN>>();
+        let account = builder.get_account(state_root_variable, address, &proof);
+        builder.write(account);
+        let circuit = builder.mock_build();
+
+        // Change the code hash held by the leaf of the account proof.
+        let mut proof =
+            EthAccountProof::<ENCODING_LEN, PROOF_LEN, _>::from_proof_response(&response);
+        let leaf = response.account_proof.len() - 1;
+        let last = proof.proof_lens[leaf] as usize - 1;
+        proof.proof[leaf][last] ^= 1;
+
+        let mut input = circuit.input();
+        input.write::<Bytes32Variable>(state_root);
+        input.write::<AddressVariable>(response.address);
+        input.write::<EthAccountProofVariable<ENCODING_LEN, PROOF_LEN>>(proof);
+        circuit.mock_prove(&input);
+    }
+
     #[test]
     #[cfg_attr(feature = "ci", ignore)]
     #[allow(non_snake_case)]
@@ -417,4 +702,45 @@ mod tests {
             .serialize(&gate_serializer, &hint_serializer)
             .unwrap();
     }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_storage_slots() {
+        let mut builder = DefaultBuilder::new();
+        let owner = builder.read::<AddressVariable>();
+        let spender = builder.read::<AddressVariable>();
+        let base_slot = builder.read::<U256Variable>();
+        let index = builder.read::<U256Variable>();
+
+        let owner_key = AbiToken::uint(&mut builder, &owner);
+        let spender_key = AbiToken::uint(&mut builder, &spender);
+        let balance_slot = builder.mapping_storage_slot(owner_key.clone(), base_slot);
+        let allowance_slot =
+            builder.nested_mapping_storage_slot(&[owner_key, spender_key], base_slot);
+        let element_slot = builder.array_storage_slot(base_slot, index);
+        builder.write(balance_slot);
+        builder.write(allowance_slot);
+        builder.write(element_slot);
+        let circuit = builder.mock_build();
+
+        let owner = address!("0x0000000000000000000000000000000000000001");
+        let spender = address!("0x00000000000000000000000000000000deadbeef");
+        let base_slot = 3u128;
+        let mut input = circuit.input();
+        input.write::<AddressVariable>(owner);
+        input.write::<AddressVariable>(spender);
+        input.write::<U256Variable>(U256::from(base_slot));
+        input.write::<U256Variable>(U256::MAX);
+        let (_witness, mut output) = circuit.mock_prove(&input);
+
+        let balance_slot = get_map_storage_location(base_slot, H256::from(owner));
+        let allowance_slot = H256(keccak256(
+            [H256::from(spender).as_bytes(), balance_slot.as_bytes()].concat(),
+        ));
+        let data_slot = U256::from(keccak256(u256_to_h256_be(U256::from(base_slot))));
+        let element_slot = u256_to_h256_be(data_slot.overflowing_add(U256::MAX).0);
+        assert_eq!(output.read::<Bytes32Variable>(), balance_slot);
+        assert_eq!(output.read::<Bytes32Variable>(), allowance_slot);
+        assert_eq!(output.read::<Bytes32Variable>(), element_slot);
+    }
 }
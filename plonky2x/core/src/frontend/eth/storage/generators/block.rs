@@ -1,6 +1,8 @@
 use core::fmt::Debug;
 use core::marker::PhantomData;
 
+use anyhow::{Context, Result};
+use async_trait::async_trait;
 use ethers::providers::Middleware;
 use ethers::types::{Block, H256};
 use plonky2::iop::generator::{GeneratedValues, SimpleGenerator};
@@ -8,14 +10,69 @@ use plonky2::iop::target::Target;
 use plonky2::iop::witness::PartitionWitness;
 use plonky2::plonk::circuit_data::CommonCircuitData;
 use plonky2::util::serialization::{Buffer, IoResult, Read, Write};
+use serde::{Deserialize, Serialize};
 use tokio::runtime::Runtime;
 
 use crate::backend::circuit::PlonkParameters;
 use crate::frontend::builder::CircuitBuilder;
 use crate::frontend::eth::storage::vars::{EthHeader, EthHeaderVariable};
-use crate::frontend::vars::{Bytes32Variable, CircuitVariable};
+use crate::frontend::hint::asynchronous::hint::AsyncHint;
+use crate::frontend::vars::{Bytes32Variable, CircuitVariable, ValueStream};
 use crate::utils::eth::get_provider;
 
+/// Input: (block_hash: bytes32)
+/// Output: (header: EthHeader)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EthBlockHint<L: PlonkParameters<D>, const D: usize> {
+    chain_id: u64,
+    _phantom: PhantomData<L>,
+}
+
+impl<L: PlonkParameters<D>, const D: usize> EthBlockHint<L, D> {
+    pub fn new(builder: &CircuitBuilder<L, D>) -> EthBlockHint<L, D> {
+        let chain_id = builder.get_chain_id();
+        EthBlockHint {
+            chain_id,
+            _phantom: PhantomData::<L>,
+        }
+    }
+}
+
+#[async_trait]
+impl<L: PlonkParameters<D>, const D: usize> AsyncHint<L, D> for EthBlockHint<L, D> {
+    async fn try_hint_fn(&self, mut input_stream: ValueStream<L, D>) -> Result<ValueStream<L, D>> {
+        let block_hash = input_stream.read_value::<Bytes32Variable>();
+
+        let provider = get_provider(self.chain_id);
+        let result = provider
+            .get_block(block_hash)
+            .await
+            .context("failed to get block from RPC")?
+            .with_context(|| format!("no block found with hash {:?}", block_hash))?;
+
+        let value = EthHeader {
+            parent_hash: result.parent_hash,
+            uncle_hash: result.uncles_hash,
+            coinbase: result.author.context("the block has no coinbase")?,
+            root: result.state_root,
+            tx_hash: result.transactions_root,
+            receipt_hash: result.receipts_root,
+            // bloom: H256::from_slice(&result.logs_bloom.expect("No bloom").0),
+            difficulty: result.difficulty,
+            number: result.number.context("the block is pending")?.as_u64(),
+            gas_limit: result.gas_limit,
+            gas_used: result.gas_used,
+            time: result.timestamp,
+            // extra: result.extra_data,
+        };
+
+        let mut output_stream = ValueStream::new();
+        output_stream.write_value::<EthHeaderVariable>(value);
+        Ok(output_stream)
+    }
+}
+
+#[deprecated(note = "use `EthBlockHint`, which the hint tape records")]
 #[derive(Debug, Clone)]
 pub struct EthBlockGenerator<L: PlonkParameters<D>, const D: usize> {
     block_hash: Bytes32Variable,
@@ -24,6 +81,7 @@ pub struct EthBlockGenerator<L: PlonkParameters<D>, const D: usize> {
     _phantom: PhantomData<L>,
 }
 
+#[allow(deprecated)]
 impl<L: PlonkParameters<D>, const D: usize> EthBlockGenerator<L, D> {
     pub fn new(
         builder: &mut CircuitBuilder<L, D>,
@@ -44,6 +102,7 @@ impl<L: PlonkParameters<D>, const D: usize> EthBlockGenerator<L, D> {
     }
 }
 
+#[allow(deprecated)]
 impl<L: PlonkParameters<D>, const D: usize> SimpleGenerator<L::Field, D>
     for EthBlockGenerator<L, D>
 {
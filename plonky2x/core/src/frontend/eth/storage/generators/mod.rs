@@ -1,7 +1,9 @@
 mod block;
 mod storage;
 
+#[allow(deprecated)]
 pub use block::EthBlockGenerator;
-pub use storage::{
-    EthLogGenerator, EthStorageKeyGenerator, EthStorageProofGenerator, EthStorageProofHint,
-};
+pub use block::EthBlockHint;
+#[allow(deprecated)]
+pub use storage::{EthLogGenerator, EthStorageProofGenerator};
+pub use storage::{EthLogHint, EthStorageKeyGenerator, EthStorageProofHint};
@@ -1,6 +1,7 @@
 use core::fmt::Debug;
 use core::marker::PhantomData;
 
+use anyhow::{ensure, Context, Result};
 use async_trait::async_trait;
 use ethers::providers::Middleware;
 use ethers::types::{EIP1186ProofResponse, TransactionReceipt};
@@ -63,6 +64,153 @@ impl<L: PlonkParameters<D>, const D: usize> AsyncHint<L, D> for EthStorageProofH
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct EthStorageKeyGenerator<L: PlonkParameters<D>, const D: usize> {
+    mapping_location: U256Variable,
+    map_key: Bytes32Variable,
+    pub value: Bytes32Variable,
+    _phantom: PhantomData<L>,
+}
+
+impl<L: PlonkParameters<D>, const D: usize> EthStorageKeyGenerator<L, D> {
+    pub fn new(
+        builder: &mut CircuitBuilder<L, D>,
+        mapping_location: U256Variable,
+        map_key: Bytes32Variable,
+    ) -> EthStorageKeyGenerator<L, D> {
+        let value = builder.init::<Bytes32Variable>();
+        EthStorageKeyGenerator {
+            mapping_location,
+            map_key,
+            value,
+            _phantom: PhantomData,
+        }
+    }
+
+    pub fn id() -> String {
+        "EthStorageKeyGenerator".to_string()
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize> SimpleGenerator<L::Field, D>
+    for EthStorageKeyGenerator<L, D>
+{
+    fn id(&self) -> String {
+        Self::id()
+    }
+
+    fn dependencies(&self) -> Vec<Target> {
+        let mut targets = Vec::new();
+        targets.extend(self.mapping_location.targets());
+        targets.extend(self.map_key.targets());
+        targets
+    }
+
+    fn run_once(
+        &self,
+        witness: &PartitionWitness<L::Field>,
+        buffer: &mut GeneratedValues<L::Field>,
+    ) {
+        let mapping_location = self.mapping_location.get(witness);
+        let map_key = self.map_key.get(witness);
+
+        let location = get_map_storage_location(mapping_location.as_u128(), map_key);
+        self.value.set(buffer, location);
+    }
+
+    #[allow(unused_variables)]
+    fn serialize(
+        &self,
+        dst: &mut Vec<u8>,
+        common_data: &CommonCircuitData<L::Field, D>,
+    ) -> IoResult<()> {
+        dst.write_target_vec(&self.mapping_location.targets())?;
+        dst.write_target_vec(&self.map_key.targets())?;
+        dst.write_target_vec(&self.value.targets())
+    }
+
+    #[allow(unused_variables)]
+    fn deserialize(
+        src: &mut Buffer,
+        common_data: &CommonCircuitData<L::Field, D>,
+    ) -> IoResult<Self> {
+        let mapping_location_targets = src.read_target_vec()?;
+        let mapping_location = U256Variable::from_targets(&mapping_location_targets);
+
+        let map_key_targets = src.read_target_vec()?;
+        let map_key = Bytes32Variable::from_targets(&map_key_targets);
+
+        let value_targets = src.read_target_vec()?;
+        let value = Bytes32Variable::from_targets(&value_targets);
+
+        Ok(Self {
+            mapping_location,
+            map_key,
+            value,
+            _phantom: PhantomData::<L>,
+        })
+    }
+}
+
+/// Input: (transaction_hash: bytes32, block_hash: bytes32)
+/// Output: (log: EthLog)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EthLogHint<L: PlonkParameters<D>, const D: usize> {
+    chain_id: u64,
+    log_index: u64,
+    _phantom: PhantomData<L>,
+}
+
+impl<L: PlonkParameters<D>, const D: usize> EthLogHint<L, D> {
+    pub fn new(builder: &CircuitBuilder<L, D>, log_index: u64) -> EthLogHint<L, D> {
+        let chain_id = builder.get_chain_id();
+        EthLogHint {
+            chain_id,
+            log_index,
+            _phantom: PhantomData::<L>,
+        }
+    }
+}
+
+#[async_trait]
+impl<L: PlonkParameters<D>, const D: usize> AsyncHint<L, D> for EthLogHint<L, D> {
+    async fn try_hint_fn(&self, mut input_stream: ValueStream<L, D>) -> Result<ValueStream<L, D>> {
+        let transaction_hash = input_stream.read_value::<Bytes32Variable>();
+        // block_hash is unused
+        let _block_hash = input_stream.read_value::<Bytes32Variable>();
+
+        let provider = get_provider(self.chain_id);
+        let result = provider
+            .get_transaction_receipt(transaction_hash)
+            .await
+            .context("failed to call get_transaction_receipt")?
+            .with_context(|| format!("no receipt found for transaction {:?}", transaction_hash))?;
+
+        let log = result.logs.get(self.log_index as usize).with_context(|| {
+            format!(
+                "transaction {:?} has no log at index {}",
+                transaction_hash, self.log_index
+            )
+        })?;
+        ensure!(
+            log.topics.len() >= 3,
+            "log {} of transaction {:?} has fewer than 3 topics",
+            self.log_index,
+            transaction_hash
+        );
+        let value = EthLog {
+            address: log.address,
+            topics: [log.topics[0], log.topics[1], log.topics[2]].to_vec(),
+            data_hash: ethers::types::H256::from_slice(sha2::Sha256::digest(&log.data).as_ref()),
+        };
+
+        let mut output_stream = ValueStream::new();
+        output_stream.write_value::<EthLogVariable>(value);
+        Ok(output_stream)
+    }
+}
+
+#[deprecated(note = "use `EthStorageProofHint`, which the hint tape records")]
 #[derive(Debug, Clone)]
 pub struct EthStorageProofGenerator<L: PlonkParameters<D>, const D: usize> {
     block_hash: Bytes32Variable,
@@ -73,6 +221,7 @@ pub struct EthStorageProofGenerator<L: PlonkParameters<D>, const D: usize> {
     _phantom: PhantomData<L>,
 }
 
+#[allow(deprecated)]
 impl<L: PlonkParameters<D>, const D: usize> EthStorageProofGenerator<L, D> {
     pub fn new(
         builder: &mut CircuitBuilder<L, D>,
@@ -97,6 +246,7 @@ impl<L: PlonkParameters<D>, const D: usize> EthStorageProofGenerator<L, D> {
     }
 }
 
+#[allow(deprecated)]
 impl<L: PlonkParameters<D>, const D: usize> SimpleGenerator<L::Field, D>
     for EthStorageProofGenerator<L, D>
 {
@@ -184,94 +334,7 @@ impl<L: PlonkParameters<D>, const D: usize> SimpleGenerator<L::Field, D>
     }
 }
 
-#[derive(Debug, Clone)]
-pub struct EthStorageKeyGenerator<L: PlonkParameters<D>, const D: usize> {
-    mapping_location: U256Variable,
-    map_key: Bytes32Variable,
-    pub value: Bytes32Variable,
-    _phantom: PhantomData<L>,
-}
-
-impl<L: PlonkParameters<D>, const D: usize> EthStorageKeyGenerator<L, D> {
-    pub fn new(
-        builder: &mut CircuitBuilder<L, D>,
-        mapping_location: U256Variable,
-        map_key: Bytes32Variable,
-    ) -> EthStorageKeyGenerator<L, D> {
-        let value = builder.init::<Bytes32Variable>();
-        EthStorageKeyGenerator {
-            mapping_location,
-            map_key,
-            value,
-            _phantom: PhantomData,
-        }
-    }
-
-    pub fn id() -> String {
-        "EthStorageKeyGenerator".to_string()
-    }
-}
-
-impl<L: PlonkParameters<D>, const D: usize> SimpleGenerator<L::Field, D>
-    for EthStorageKeyGenerator<L, D>
-{
-    fn id(&self) -> String {
-        Self::id()
-    }
-
-    fn dependencies(&self) -> Vec<Target> {
-        let mut targets = Vec::new();
-        targets.extend(self.mapping_location.targets());
-        targets.extend(self.map_key.targets());
-        targets
-    }
-
-    fn run_once(
-        &self,
-        witness: &PartitionWitness<L::Field>,
-        buffer: &mut GeneratedValues<L::Field>,
-    ) {
-        let mapping_location = self.mapping_location.get(witness);
-        let map_key = self.map_key.get(witness);
-
-        let location = get_map_storage_location(mapping_location.as_u128(), map_key);
-        self.value.set(buffer, location);
-    }
-
-    #[allow(unused_variables)]
-    fn serialize(
-        &self,
-        dst: &mut Vec<u8>,
-        common_data: &CommonCircuitData<L::Field, D>,
-    ) -> IoResult<()> {
-        dst.write_target_vec(&self.mapping_location.targets())?;
-        dst.write_target_vec(&self.map_key.targets())?;
-        dst.write_target_vec(&self.value.targets())
-    }
-
-    #[allow(unused_variables)]
-    fn deserialize(
-        src: &mut Buffer,
-        common_data: &CommonCircuitData<L::Field, D>,
-    ) -> IoResult<Self> {
-        let mapping_location_targets = src.read_target_vec()?;
-        let mapping_location = U256Variable::from_targets(&mapping_location_targets);
-
-        let map_key_targets = src.read_target_vec()?;
-        let map_key = Bytes32Variable::from_targets(&map_key_targets);
-
-        let value_targets = src.read_target_vec()?;
-        let value = Bytes32Variable::from_targets(&value_targets);
-
-        Ok(Self {
-            mapping_location,
-            map_key,
-            value,
-            _phantom: PhantomData::<L>,
-        })
-    }
-}
-
+#[deprecated(note = "use `EthLogHint`, which the hint tape records")]
 #[derive(Debug, Clone)]
 pub struct EthLogGenerator<L: PlonkParameters<D>, const D: usize> {
     transaction_hash: Bytes32Variable,
@@ -282,6 +345,7 @@ pub struct EthLogGenerator<L: PlonkParameters<D>, const D: usize> {
     _phantom: PhantomData<L>,
 }
 
+#[allow(deprecated)]
 impl<L: PlonkParameters<D>, const D: usize> EthLogGenerator<L, D> {
     pub fn new(
         builder: &mut CircuitBuilder<L, D>,
@@ -306,6 +370,7 @@ impl<L: PlonkParameters<D>, const D: usize> EthLogGenerator<L, D> {
     }
 }
 
+#[allow(deprecated)]
 impl<L: PlonkParameters<D>, const D: usize> SimpleGenerator<L::Field, D> for EthLogGenerator<L, D> {
     fn id(&self) -> String {
         Self::id()
@@ -1,13 +1,15 @@
 use std::fmt::Debug;
 
+use ethers::types::EIP1186ProofResponse;
 use plonky2::hash::hash_types::RichField;
 use plonky2x_derive::CircuitVariable;
 
 use crate::backend::circuit::PlonkParameters;
 use crate::frontend::builder::CircuitBuilder;
+use crate::frontend::eth::mpt::builder::transform_proof_to_padded;
 use crate::frontend::eth::vars::AddressVariable;
 use crate::frontend::vars::{Bytes32Variable, CircuitVariable, U256Variable};
-use crate::prelude::{ArrayVariable, Variable};
+use crate::prelude::{ArrayVariable, ByteVariable, U32Variable, Variable};
 
 #[derive(Debug, Clone, Copy, CircuitVariable)]
 #[value_name(EthProof)]
@@ -32,3 +34,73 @@ pub struct EthLogVariable {
     pub topics: ArrayVariable<Bytes32Variable, 3>,
     pub data_hash: Bytes32Variable,
 }
+
+/// The account proof of an address, as returned by `eth_getProof`.
+///
+/// Every node is padded to `ENCODING_LEN` bytes, and the proof is padded with empty nodes to
+/// `PROOF_LEN` nodes.
+#[derive(Debug, Clone, CircuitVariable)]
+#[value_name(EthAccountProof)]
+pub struct EthAccountProofVariable<const ENCODING_LEN: usize, const PROOF_LEN: usize> {
+    pub proof: ArrayVariable<ArrayVariable<ByteVariable, ENCODING_LEN>, PROOF_LEN>,
+    pub proof_lens: ArrayVariable<U32Variable, PROOF_LEN>,
+}
+
+impl<const ENCODING_LEN: usize, const PROOF_LEN: usize, F: RichField>
+    EthAccountProof<ENCODING_LEN, PROOF_LEN, F>
+{
+    /// Builds the witness from the response of `eth_getProof`.
+    pub fn from_proof_response(response: &EIP1186ProofResponse) -> Self {
+        let (proof, proof_lens) = transform_proof_to_padded::<ENCODING_LEN, PROOF_LEN>(
+            response
+                .account_proof
+                .iter()
+                .map(|node| node.to_vec())
+                .collect(),
+        );
+        Self {
+            proof,
+            proof_lens: proof_lens.into_iter().map(|len| len as u32).collect(),
+        }
+    }
+}
+
+/// The account and storage proofs of a storage slot, as returned by `eth_getProof`.
+///
+/// Every node is padded to `ENCODING_LEN` bytes, and each proof is padded with empty nodes to
+/// `PROOF_LEN` nodes.
+#[derive(Debug, Clone, CircuitVariable)]
+#[value_name(EthStorageProof)]
+pub struct EthStorageProofVariable<const ENCODING_LEN: usize, const PROOF_LEN: usize> {
+    pub account_proof: ArrayVariable<ArrayVariable<ByteVariable, ENCODING_LEN>, PROOF_LEN>,
+    pub account_proof_lens: ArrayVariable<U32Variable, PROOF_LEN>,
+    pub storage_proof: ArrayVariable<ArrayVariable<ByteVariable, ENCODING_LEN>, PROOF_LEN>,
+    pub storage_proof_lens: ArrayVariable<U32Variable, PROOF_LEN>,
+}
+
+impl<const ENCODING_LEN: usize, const PROOF_LEN: usize, F: RichField>
+    EthStorageProof<ENCODING_LEN, PROOF_LEN, F>
+{
+    /// Builds the witness from the response of `eth_getProof` for a single storage slot.
+    pub fn from_proof_response(response: &EIP1186ProofResponse) -> Self {
+        assert_eq!(
+            response.storage_proof.len(),
+            1,
+            "expected the proof of a single storage slot"
+        );
+        let pad = |proof: &[ethers::types::Bytes]| {
+            let (nodes, lens) = transform_proof_to_padded::<ENCODING_LEN, PROOF_LEN>(
+                proof.iter().map(|node| node.to_vec()).collect(),
+            );
+            (nodes, lens.into_iter().map(|len| len as u32).collect())
+        };
+        let (account_proof, account_proof_lens) = pad(&response.account_proof);
+        let (storage_proof, storage_proof_lens) = pad(&response.storage_proof[0].proof);
+        Self {
+            account_proof,
+            account_proof_lens,
+            storage_proof,
+            storage_proof_lens,
+        }
+    }
+}
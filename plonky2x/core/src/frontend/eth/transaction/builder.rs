@@ -0,0 +1,241 @@
+use plonky2::field::types::Field;
+
+use super::vars::TransactionVariable;
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::builder::CircuitBuilder;
+use crate::frontend::eth::receipt::vars::EthTrieProofVariable;
+use crate::frontend::eth::vars::AddressVariable;
+use crate::frontend::vars::{Bytes32Variable, BytesVariable, Variable};
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Verifies the transaction at `tx_index` against `transactions_root`, and decodes it.
+    ///
+    /// Legacy transactions are the RLP list
+    /// `[nonce, gas_price, gas_limit, to, value, data, v, r, s]`. Typed transactions are a type
+    /// byte followed by the RLP list of their fields, which starts with the chain id: EIP-2930
+    /// transactions (type `1`) have the same fields as legacy ones up to the data, while EIP-1559
+    /// transactions (type `2`) replace the gas price with the priority fee and the max fee.
+    pub fn get_transaction<
+        const ENCODING_LEN: usize,
+        const PROOF_LEN: usize,
+        const MAX_DATA_LEN: usize,
+    >(
+        &mut self,
+        transactions_root: Bytes32Variable,
+        tx_index: Variable,
+        proof: &EthTrieProofVariable<ENCODING_LEN, PROOF_LEN>,
+    ) -> TransactionVariable<MAX_DATA_LEN> {
+        let (key_path, key_path_len) = self.mpt_index_key_path(tx_index);
+        let transaction = self.mpt_lookup_path(
+            &key_path,
+            key_path_len,
+            proof.proof.clone(),
+            proof.proof_lens.clone(),
+            transactions_root,
+        );
+        let true_v = self._true();
+        let false_v = self._false();
+        self.assert_is_equal(transaction.excluded, false_v);
+
+        let node = transaction
+            .node
+            .as_slice()
+            .iter()
+            .map(|byte| byte.to_variable(self))
+            .collect::<Vec<_>>();
+        let zero = self.zero::<Variable>();
+        let one = self.one::<Variable>();
+        let two = self.constant::<Variable>(L::Field::from_canonical_u8(2));
+
+        // The leaf is the list [path, transaction].
+        let (leaf, _) = self.rlp_decode_list::<2>(&node, zero);
+
+        // List prefixes are at least 0xc0, while transaction types are below 0x80.
+        let const_0xc0 = self.constant::<Variable>(L::Field::from_canonical_u8(0xc0));
+        let first_byte = self.select_array(&node, leaf[1].offset);
+        let is_typed = self.lt(first_byte, const_0xc0);
+        let is_eip2930 = self.is_equal(first_byte, one);
+        let is_eip1559 = self.is_equal(first_byte, two);
+        let is_known_type = self.or(is_eip2930, is_eip1559);
+        let is_legacy = self.not(is_typed);
+        let is_valid_type = self.or(is_legacy, is_known_type);
+        self.assert_is_equal(is_valid_type, true_v);
+        let tx_type = self.select(is_typed, first_byte, zero);
+
+        // Legacy transactions have 9 fields, EIP-2930 transactions 11 and EIP-1559 ones 12.
+        let fields_start = self.add(leaf[1].offset, is_typed.variable);
+        let (fields, num_fields) = self.rlp_decode_list::<12>(&node, fields_start);
+        let legacy_num_fields = self.constant::<Variable>(L::Field::from_canonical_u8(9));
+        let typed_num_fields = self.add(legacy_num_fields, two);
+        let typed_num_fields = self.add(typed_num_fields, is_eip1559.variable);
+        let expected_num_fields = self.select(is_typed, typed_num_fields, legacy_num_fields);
+        self.assert_is_equal(num_fields, expected_num_fields);
+
+        // Typed transactions start with the chain id, and EIP-1559 transactions replace the gas
+        // price with two fee fields, which shifts the fields that follow.
+        let nonce = self.select_array(&fields, is_typed.variable);
+        let shift = self.add(is_typed.variable, is_eip1559.variable);
+        let gas_limit_index = self.add(two, shift);
+        let gas_limit = self.select_array(&fields, gas_limit_index);
+        let to_index = self.add(gas_limit_index, one);
+        let to = self.select_array(&fields, to_index);
+        let value_index = self.add(to_index, one);
+        let value = self.select_array(&fields, value_index);
+        let data_index = self.add(value_index, one);
+        let data = self.select_array(&fields, data_index);
+
+        // The recipient is empty for contract creations.
+        let const_20 = self.constant::<Variable>(L::Field::from_canonical_u8(20));
+        let is_creation = self.is_equal(to.len, zero);
+        let is_call = self.is_equal(to.len, const_20);
+        let is_valid_to = self.or(is_creation, is_call);
+        self.assert_is_equal(is_valid_to, true_v);
+        let to_bytes = self.rlp_field_bytes::<20>(&node, &to);
+
+        let nonce = self.rlp_field_scalar(&node, &nonce);
+        let gas_limit = self.rlp_field_scalar(&node, &gas_limit);
+        let value = self.rlp_field_scalar(&node, &value);
+
+        TransactionVariable {
+            tx_type,
+            nonce: self.bytes32_to_u256(nonce),
+            gas_limit: self.bytes32_to_u256(gas_limit),
+            to: AddressVariable(BytesVariable(to_bytes.as_slice().try_into().unwrap())),
+            value: self.bytes32_to_u256(value),
+            data: self.rlp_field_bytes::<MAX_DATA_LEN>(&node, &data),
+            data_len: data.len,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ethers::types::{Address, H256, U256};
+    use ethers::utils::keccak256;
+    use ethers::utils::rlp::RlpStream;
+
+    use super::*;
+    use crate::frontend::eth::receipt::vars::EthTrieProof;
+    use crate::frontend::eth::transaction::vars::Transaction;
+    use crate::prelude::{DefaultBuilder, GoldilocksField};
+
+    type F = GoldilocksField;
+
+    /// Encodes a leaf node with the given hex-prefix encoded path.
+    fn leaf(path: &[u8], value: &[u8]) -> Vec<u8> {
+        let mut stream = RlpStream::new_list(2);
+        stream.append(&path.to_vec());
+        stream.append(&value.to_vec());
+        stream.out().to_vec()
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_get_transaction() {
+        const ENCODING_LEN: usize = 256;
+        const PROOF_LEN: usize = 2;
+        const MAX_DATA_LEN: usize = 64;
+
+        // An EIP-1559 call.
+        let to = Address::from_low_u64_be(0xbeef);
+        let call_data = vec![0xab; 36];
+        let mut stream = RlpStream::new_list(12);
+        stream.append(&1u64);
+        stream.append(&7u64);
+        stream.append(&1_000_000_000u64);
+        stream.append(&30_000_000_000u64);
+        stream.append(&100_000u64);
+        stream.append(&to.as_bytes().to_vec());
+        stream.append(&1_000_000_000_000_000_000u64);
+        stream.append(&call_data);
+        stream.begin_list(0);
+        stream.append(&1u8);
+        stream.append(&H256::repeat_byte(3).as_bytes().to_vec());
+        stream.append(&H256::repeat_byte(4).as_bytes().to_vec());
+        let mut eip1559_tx = vec![0x02];
+        eip1559_tx.extend(stream.out().to_vec());
+
+        // A legacy contract creation.
+        let init_code = vec![0x60; 10];
+        let mut stream = RlpStream::new_list(9);
+        stream.append(&0u64);
+        stream.append(&20_000_000_000u64);
+        stream.append(&21_000u64);
+        stream.append_empty_data();
+        stream.append(&0u64);
+        stream.append(&init_code);
+        stream.append(&27u8);
+        stream.append(&H256::repeat_byte(5).as_bytes().to_vec());
+        stream.append(&H256::repeat_byte(6).as_bytes().to_vec());
+        let legacy_tx = stream.out().to_vec();
+
+        // The trie of two transactions, at the paths rlp(0) = 0x80 and rlp(1) = 0x01, is a
+        // branch node whose children at nibbles 0 and 8 are leaves with one nibble left.
+        let leaf_1 = leaf(&[0x31], &legacy_tx);
+        let leaf_0 = leaf(&[0x30], &eip1559_tx);
+        let mut stream = RlpStream::new_list(17);
+        for nibble in 0..16 {
+            match nibble {
+                0 => stream.append(&keccak256(&leaf_1).to_vec()),
+                8 => stream.append(&keccak256(&leaf_0).to_vec()),
+                _ => stream.append_empty_data(),
+            };
+        }
+        stream.append_empty_data();
+        let branch = stream.out().to_vec();
+        let transactions_root = H256(keccak256(&branch));
+
+        let mut builder = DefaultBuilder::new();
+        let root = builder.read::<Bytes32Variable>();
+        let tx_index = builder.read::<Variable>();
+        let proof = builder.read::<EthTrieProofVariable<ENCODING_LEN, PROOF_LEN>>();
+        let transaction = builder
+            .get_transaction::<ENCODING_LEN, PROOF_LEN, MAX_DATA_LEN>(root, tx_index, &proof);
+        builder.write(transaction);
+        let circuit = builder.mock_build();
+
+        let pad = |data: &[u8]| {
+            let mut padded = data.to_vec();
+            padded.resize(MAX_DATA_LEN, 0);
+            padded
+        };
+        let cases = [
+            (
+                0u64,
+                leaf_0,
+                Transaction {
+                    tx_type: F::from_canonical_u8(2),
+                    nonce: U256::from(7),
+                    gas_limit: U256::from(100_000),
+                    to,
+                    value: U256::from(1_000_000_000_000_000_000u64),
+                    data: pad(&call_data),
+                    data_len: F::from_canonical_usize(call_data.len()),
+                },
+            ),
+            (
+                1u64,
+                leaf_1,
+                Transaction {
+                    tx_type: F::ZERO,
+                    nonce: U256::zero(),
+                    gas_limit: U256::from(21_000),
+                    to: Address::zero(),
+                    value: U256::zero(),
+                    data: pad(&init_code),
+                    data_len: F::from_canonical_usize(init_code.len()),
+                },
+            ),
+        ];
+        for (index, leaf, expected) in cases {
+            let mut input = circuit.input();
+            input.write::<Bytes32Variable>(transactions_root);
+            input.write::<Variable>(F::from_canonical_u64(index));
+            input.write::<EthTrieProofVariable<ENCODING_LEN, PROOF_LEN>>(EthTrieProof::from_nodes(
+                vec![branch.clone(), leaf],
+            ));
+            let (_witness, mut output) = circuit.mock_prove(&input);
+            assert_eq!(output.read::<TransactionVariable<MAX_DATA_LEN>>(), expected);
+        }
+    }
+}
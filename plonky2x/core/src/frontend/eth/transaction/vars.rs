@@ -0,0 +1,28 @@
+use std::fmt::Debug;
+
+use plonky2::hash::hash_types::RichField;
+use plonky2x_derive::CircuitVariable;
+
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::builder::CircuitBuilder;
+use crate::frontend::eth::vars::AddressVariable;
+use crate::frontend::vars::{CircuitVariable, U256Variable};
+use crate::prelude::{ArrayVariable, ByteVariable, Variable};
+
+/// A transaction, as decoded from its envelope in the transactions trie.
+///
+/// `tx_type` is `0` for legacy transactions, `1` for EIP-2930 and `2` for EIP-1559 transactions.
+/// The address `to` is zero for contract creations, and the bytes of the data past `data_len`
+/// are zero.
+#[derive(Debug, Clone, CircuitVariable)]
+#[value_name(Transaction)]
+#[value_derive(PartialEq, Eq)]
+pub struct TransactionVariable<const MAX_DATA_LEN: usize> {
+    pub tx_type: Variable,
+    pub nonce: U256Variable,
+    pub gas_limit: U256Variable,
+    pub to: AddressVariable,
+    pub value: U256Variable,
+    pub data: ArrayVariable<ByteVariable, MAX_DATA_LEN>,
+    pub data_len: Variable,
+}
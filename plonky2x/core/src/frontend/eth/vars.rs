@@ -50,6 +50,98 @@ impl CircuitVariable for BLSPubkeyVariable {
     }
 }
 
+/// A BLS signature, in the compressed encoding of G2 points of the ZCash serialization format.
+#[derive(Debug, Clone, Copy)]
+pub struct BLSSignatureVariable(pub BytesVariable<96>);
+
+impl CircuitVariable for BLSSignatureVariable {
+    type ValueType<F: RichField> = [u8; 96];
+
+    fn init_unsafe<L: PlonkParameters<D>, const D: usize>(
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> Self {
+        Self(BytesVariable::init_unsafe(builder))
+    }
+
+    fn nb_elements() -> usize {
+        BytesVariable::<96>::nb_elements()
+    }
+
+    fn elements<F: RichField>(value: Self::ValueType<F>) -> Vec<F> {
+        BytesVariable::<96>::elements(value)
+    }
+
+    fn from_elements<F: RichField>(elements: &[F]) -> Self::ValueType<F> {
+        BytesVariable::<96>::from_elements(elements)
+    }
+
+    fn variables(&self) -> Vec<Variable> {
+        self.0.variables()
+    }
+
+    fn from_variables_unsafe(variables: &[Variable]) -> Self {
+        Self(BytesVariable::from_variables_unsafe(variables))
+    }
+
+    fn assert_is_valid<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        builder: &mut CircuitBuilder<L, D>,
+    ) {
+        self.0.assert_is_valid(builder);
+    }
+}
+
+/// An uncompressed secp256k1 public key, the 32-byte big-endian coordinates `x || y` without the
+/// `0x04` prefix.
+#[derive(Debug, Clone, Copy)]
+pub struct Secp256k1PubkeyVariable(pub BytesVariable<64>);
+
+impl CircuitVariable for Secp256k1PubkeyVariable {
+    type ValueType<F: RichField> = [u8; 64];
+
+    fn init_unsafe<L: PlonkParameters<D>, const D: usize>(
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> Self {
+        Self(BytesVariable::init_unsafe(builder))
+    }
+
+    fn nb_elements() -> usize {
+        BytesVariable::<64>::nb_elements()
+    }
+
+    fn elements<F: RichField>(value: Self::ValueType<F>) -> Vec<F> {
+        BytesVariable::<64>::elements(value)
+    }
+
+    fn from_elements<F: RichField>(elements: &[F]) -> Self::ValueType<F> {
+        BytesVariable::<64>::from_elements(elements)
+    }
+
+    fn variables(&self) -> Vec<Variable> {
+        self.0.variables()
+    }
+
+    fn from_variables_unsafe(variables: &[Variable]) -> Self {
+        Self(BytesVariable::from_variables_unsafe(variables))
+    }
+
+    fn assert_is_valid<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        builder: &mut CircuitBuilder<L, D>,
+    ) {
+        self.0.assert_is_valid(builder);
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Returns the Ethereum address of a secp256k1 public key, which is the last 20 bytes of the
+    /// keccak256 digest of its uncompressed coordinates.
+    pub fn pubkey_to_address(&mut self, pubkey: &Secp256k1PubkeyVariable) -> AddressVariable {
+        let hash = self.keccak256(&pubkey.0 .0);
+        AddressVariable(BytesVariable(hash.as_bytes()[12..].try_into().unwrap()))
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct AddressVariable(pub BytesVariable<20>);
 
@@ -90,6 +182,33 @@ impl CircuitVariable for AddressVariable {
     }
 }
 
+impl AddressVariable {
+    /// Left-pads the address with 12 zero bytes, matching the ABI encoding of an `address`.
+    pub fn to_bytes32<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> Bytes32Variable {
+        let zero = builder.constant::<ByteVariable>(0);
+        let mut bytes = vec![zero; 12];
+        bytes.extend(self.0 .0);
+        Bytes32Variable::from(bytes.as_slice())
+    }
+
+    /// Takes the low 20 bytes of a left-padded Bytes32Variable, asserting that the 12 padding
+    /// bytes are zero.
+    pub fn from_bytes32<L: PlonkParameters<D>, const D: usize>(
+        builder: &mut CircuitBuilder<L, D>,
+        bytes32: Bytes32Variable,
+    ) -> Self {
+        let bytes = bytes32.as_bytes();
+        let zero = builder.constant::<ByteVariable>(0);
+        for byte in bytes[..12].iter() {
+            builder.assert_is_equal(*byte, zero);
+        }
+        Self(BytesVariable(bytes[12..].try_into().unwrap()))
+    }
+}
+
 impl EvmVariable for AddressVariable {
     fn encode<L: PlonkParameters<D>, const D: usize>(
         &self,
@@ -114,6 +233,22 @@ impl EvmVariable for AddressVariable {
     }
 }
 
+impl SSZVariable for BLSPubkeyVariable {
+    /// The public key is packed in two chunks, the second of which is padded with zeros.
+    fn hash_tree_root<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> Bytes32Variable {
+        let zero = builder.constant::<ByteVariable>(0);
+        let bytes = self.0 .0;
+        let mut high = bytes[32..].to_vec();
+        high.extend([zero; 16]);
+        let low = Bytes32Variable::from(&bytes[..32]);
+        let high = Bytes32Variable::from(high.as_slice());
+        builder.sha256_pair(low, high)
+    }
+}
+
 impl SSZVariable for AddressVariable {
     fn hash_tree_root<L: PlonkParameters<D>, const D: usize>(
         &self,
@@ -126,3 +261,56 @@ impl SSZVariable for AddressVariable {
         Bytes32Variable(BytesVariable::<32>(bytes.try_into().unwrap()))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use ethers::core::k256::ecdsa::SigningKey;
+    use ethers::types::H256;
+    use ethers::utils::secret_key_to_address;
+
+    use super::{AddressVariable, Secp256k1PubkeyVariable};
+    use crate::prelude::*;
+
+    type L = DefaultParameters;
+    const D: usize = 2;
+
+    #[test]
+    fn test_address_bytes32_conversion() {
+        let mut builder = CircuitBuilder::<L, D>::new();
+
+        let address_value = address!("0x55032650b14df07b85bF18A3a3eC8E0Af2e028d5");
+        let address = builder.constant::<AddressVariable>(address_value);
+
+        let padded = address.to_bytes32(&mut builder);
+        let expected = builder.constant::<Bytes32Variable>(H256::from(address_value));
+        builder.assert_is_equal(padded, expected);
+
+        let unpadded = AddressVariable::from_bytes32(&mut builder, padded);
+        builder.assert_is_equal(unpadded, address);
+
+        let circuit = builder.build();
+        let pw = PartialWitness::new();
+        let proof = circuit.data.prove(pw).unwrap();
+        circuit.data.verify(proof).unwrap();
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_pubkey_to_address() {
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let pubkey = builder.read::<Secp256k1PubkeyVariable>();
+        let address = builder.pubkey_to_address(&pubkey);
+        builder.write(address);
+        let circuit = builder.mock_build();
+
+        let key = SigningKey::from_slice(&[0x42; 32]).unwrap();
+        let point = key.verifying_key().to_encoded_point(false);
+        let mut input = circuit.input();
+        input.write::<Secp256k1PubkeyVariable>(point.as_bytes()[1..].try_into().unwrap());
+        let (_witness, mut output) = circuit.mock_prove(&input);
+        assert_eq!(
+            output.read::<AddressVariable>(),
+            secret_key_to_address(&key)
+        );
+    }
+}
@@ -68,7 +68,9 @@ impl<L: PlonkParameters<D>, const D: usize> FoldBuilderMethods<L, D> for Circuit
         // Save cyclic inner circuit to build folder.
         let circuit_id = inner_circuit.id();
         let circuit_path = format!("./build/{}.circuit", circuit_id);
-        inner_circuit.save(&circuit_path, &gate_serializer, &generator_serializer);
+        inner_circuit
+            .save(&circuit_path, &gate_serializer, &generator_serializer)
+            .unwrap();
 
         // Generate cyclic proofs using generator.
         let final_proof = self.add_virtual_proof_with_pis(&inner_circuit.data.common);
@@ -0,0 +1,286 @@
+//! The Blake2b-512 hash function over boolean variables.
+
+use itertools::Itertools;
+use plonky2::iop::target::BoolTarget;
+
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::builder::CircuitBuilder;
+use crate::prelude::{BoolVariable, ByteVariable, BytesVariable};
+
+/// A 64-bit word of the Blake2b state, as little-endian bits.
+type Word = [BoolVariable; 64];
+
+/// The number of bytes in a Blake2b block.
+const BLAKE2B_BLOCK_SIZE: usize = 128;
+
+/// The number of rounds of the Blake2b compression function.
+const BLAKE2B_ROUNDS: usize = 12;
+
+const BLAKE2B_IV: [u64; 8] = [
+    0x6a09e667f3bcc908,
+    0xbb67ae8584caa73b,
+    0x3c6ef372fe94f82b,
+    0xa54ff53a5f1d36f1,
+    0x510e527fade682d1,
+    0x9b05688c2b3e6c1f,
+    0x1f83d9abfb41bd6b,
+    0x5be0cd19137e2179,
+];
+
+/// The message word permutations, one per round modulo 10.
+const BLAKE2B_SIGMA: [[usize; 16]; 10] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+    [11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+    [7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+    [9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+    [2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+    [12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+    [13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+    [6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+    [10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0],
+];
+
+/// The indices into the working vector of the eight mixing steps of a round.
+const BLAKE2B_MIX_INDICES: [[usize; 4]; 8] = [
+    [0, 4, 8, 12],
+    [1, 5, 9, 13],
+    [2, 6, 10, 14],
+    [3, 7, 11, 15],
+    [0, 5, 10, 15],
+    [1, 6, 11, 12],
+    [2, 7, 8, 13],
+    [3, 4, 9, 14],
+];
+
+/// Rotates a word to the right by `n` bits.
+fn rotate_right(word: &Word, n: usize) -> Word {
+    core::array::from_fn(|i| word[(i + n) % 64])
+}
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Computes the unkeyed Blake2b digest of `input` with a 64-byte output.
+    ///
+    /// The message length is fixed at circuit build time, so the byte counter and the
+    /// finalization flag of each compression are constants.
+    pub fn blake2b_512(&mut self, input: &[ByteVariable]) -> BytesVariable<64> {
+        let zero_byte = self.constant::<ByteVariable>(0);
+        let nb_blocks = input.len().div_ceil(BLAKE2B_BLOCK_SIZE).max(1);
+        let mut padded = input.to_vec();
+        padded.resize(nb_blocks * BLAKE2B_BLOCK_SIZE, zero_byte);
+
+        // The parameter block sets a digest length of 64 bytes, no key, and fanout and depth 1.
+        let mut h: [Word; 8] = core::array::from_fn(|i| self.blake2b_constant_word(BLAKE2B_IV[i]));
+        h[0] = self.blake2b_xor_constant(&h[0], 0x01010040);
+
+        for (i, block) in padded.chunks_exact(BLAKE2B_BLOCK_SIZE).enumerate() {
+            let is_last = i == nb_blocks - 1;
+            let counter = if is_last {
+                input.len()
+            } else {
+                (i + 1) * BLAKE2B_BLOCK_SIZE
+            };
+            let m: [Word; 16] = block
+                .chunks_exact(8)
+                .map(|word_bytes| {
+                    word_bytes
+                        .iter()
+                        .flat_map(|byte| byte.as_le_bits())
+                        .collect_vec()
+                        .try_into()
+                        .unwrap()
+                })
+                .collect_vec()
+                .try_into()
+                .unwrap();
+            self.blake2b_compress(&mut h, &m, counter as u64, is_last);
+        }
+
+        let digest: [ByteVariable; 64] = h
+            .iter()
+            .flat_map(|word| {
+                word.chunks_exact(8).map(|le_bits| {
+                    let mut be_bits: [_; 8] = le_bits.try_into().unwrap();
+                    be_bits.reverse();
+                    ByteVariable::from_be_bits(be_bits)
+                })
+            })
+            .collect_vec()
+            .try_into()
+            .unwrap();
+        BytesVariable(digest)
+    }
+
+    /// The Blake2b compression function `F`, with a message byte counter below 2^64.
+    fn blake2b_compress(&mut self, h: &mut [Word; 8], m: &[Word; 16], counter: u64, is_last: bool) {
+        let mut v: [Word; 16] = core::array::from_fn(|i| {
+            if i < 8 {
+                h[i]
+            } else {
+                self.blake2b_constant_word(BLAKE2B_IV[i - 8])
+            }
+        });
+        v[12] = self.blake2b_xor_constant(&v[12], counter);
+        if is_last {
+            v[14] = self.blake2b_xor_constant(&v[14], u64::MAX);
+        }
+
+        for round in 0..BLAKE2B_ROUNDS {
+            let s = BLAKE2B_SIGMA[round % 10];
+            for (j, [a, b, c, d]) in BLAKE2B_MIX_INDICES.into_iter().enumerate() {
+                self.blake2b_mix(&mut v, a, b, c, d, &m[s[2 * j]], &m[s[2 * j + 1]]);
+            }
+        }
+
+        for i in 0..8 {
+            let hv = self.blake2b_xor(&h[i], &v[i]);
+            h[i] = self.blake2b_xor(&hv, &v[i + 8]);
+        }
+    }
+
+    /// The Blake2b mixing function `G`.
+    #[allow(clippy::too_many_arguments)]
+    fn blake2b_mix(
+        &mut self,
+        v: &mut [Word; 16],
+        a: usize,
+        b: usize,
+        c: usize,
+        d: usize,
+        x: &Word,
+        y: &Word,
+    ) {
+        v[a] = self.blake2b_add(&[&v[a], &v[b], x]);
+        let da = self.blake2b_xor(&v[d], &v[a]);
+        v[d] = rotate_right(&da, 32);
+        v[c] = self.blake2b_add(&[&v[c], &v[d]]);
+        let bc = self.blake2b_xor(&v[b], &v[c]);
+        v[b] = rotate_right(&bc, 24);
+        v[a] = self.blake2b_add(&[&v[a], &v[b], y]);
+        let da = self.blake2b_xor(&v[d], &v[a]);
+        v[d] = rotate_right(&da, 16);
+        v[c] = self.blake2b_add(&[&v[c], &v[d]]);
+        let bc = self.blake2b_xor(&v[b], &v[c]);
+        v[b] = rotate_right(&bc, 63);
+    }
+
+    fn blake2b_constant_word(&mut self, value: u64) -> Word {
+        core::array::from_fn(|i| self.constant::<BoolVariable>((value >> i) & 1 == 1))
+    }
+
+    fn blake2b_xor(&mut self, a: &Word, b: &Word) -> Word {
+        core::array::from_fn(|i| self.xor(a[i], b[i]))
+    }
+
+    fn blake2b_xor_constant(&mut self, word: &Word, value: u64) -> Word {
+        core::array::from_fn(|i| {
+            if (value >> i) & 1 == 1 {
+                self.not(word[i])
+            } else {
+                word[i]
+            }
+        })
+    }
+
+    /// Adds up to three words modulo 2^64.
+    ///
+    /// Each 32-bit half is summed in the field together with the carry of the lower half, and the
+    /// sum is split into 34 bits: the lower 32 bits are the result and the upper two the carry.
+    fn blake2b_add(&mut self, words: &[&Word]) -> Word {
+        assert!(words.len() <= 3, "at most three words can be added");
+        let mut result = Vec::with_capacity(64);
+        let mut carry = None;
+        for half in 0..2 {
+            let mut terms = words
+                .iter()
+                .map(|word| {
+                    let bits = word[32 * half..32 * (half + 1)]
+                        .iter()
+                        .map(|bit| -> BoolTarget { (*bit).into() });
+                    self.api.le_sum(bits)
+                })
+                .collect_vec();
+            terms.extend(carry);
+            let sum = self.api.add_many(terms);
+            let sum_bits = self.api.split_le(sum, 34);
+            result.extend(sum_bits[..32].iter().map(|bit| BoolVariable::from(*bit)));
+            carry = Some(self.api.le_sum(sum_bits[32..].iter()));
+        }
+        result.try_into().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crypto::blake2b::Blake2b;
+    use crypto::digest::Digest;
+
+    use crate::prelude::*;
+
+    fn blake2b_512(message: &[u8]) -> [u8; 64] {
+        let mut hasher = Blake2b::new(64);
+        hasher.input(message);
+        let mut digest = [0u8; 64];
+        hasher.result(&mut digest);
+        digest
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_blake2b_512() {
+        let mut builder = DefaultBuilder::new();
+
+        // Lengths around the block size of 128 bytes, including the empty message.
+        let lengths = [0usize, 3, 127, 128, 129, 256];
+        for length in lengths.iter() {
+            let message = (0..*length)
+                .map(|_| builder.read::<ByteVariable>())
+                .collect::<Vec<_>>();
+            let digest = builder.blake2b_512(&message);
+            builder.write(digest);
+        }
+
+        let circuit = builder.build();
+        let messages = lengths
+            .iter()
+            .map(|length| (0..*length).map(|i| (i * 11 + 1) as u8).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let mut input = circuit.input();
+        for byte in messages.iter().flatten() {
+            input.write::<ByteVariable>(*byte);
+        }
+
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        for message in messages.iter() {
+            let digest = output.read::<BytesVariable<64>>();
+            assert_eq!(digest, blake2b_512(message));
+        }
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_blake2b_512_abc() {
+        let mut builder = DefaultBuilder::new();
+        let message = b"abc"
+            .iter()
+            .map(|byte| builder.constant::<ByteVariable>(*byte))
+            .collect::<Vec<_>>();
+        let digest = builder.blake2b_512(&message);
+        builder.write(digest);
+
+        let circuit = builder.build();
+        let input = circuit.input();
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        // The test vector of RFC 7693, appendix A.
+        let expected = hex::decode(
+            "ba80a53f981c4d0d6a2797b69f12f6e94c212f14685ac4b74b12bb6fdbffa2d1\
+             7d87c5392aab792dc252d5de4533cc9518d38aa8dbf1925ab92386edd4009923",
+        )
+        .unwrap();
+        assert_eq!(output.read::<BytesVariable<64>>().to_vec(), expected);
+    }
+}
@@ -1,5 +1,8 @@
+use crate::frontend::builder::bits::Endianness;
 use crate::frontend::builder::CircuitBuilder;
-use crate::prelude::{BoolVariable, CircuitVariable, PlonkParameters, U32Variable, Variable};
+use crate::prelude::{
+    BoolVariable, CircuitVariable, PlonkParameters, U32Variable, U64Variable, Variable,
+};
 
 impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
     pub fn reshape(&self, arr: Vec<BoolVariable>) -> Vec<[BoolVariable; 32]> {
@@ -76,6 +79,14 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
             let c_u32 = c_u64.limbs[0];
 
             c_u32.to_be_bits(self).to_vec().try_into().unwrap()
+        } else if S == 64 {
+            let a_u64 = self.from_bits::<U64Variable>(&a, Endianness::Big);
+            let b_u64 = self.from_bits::<U64Variable>(&b, Endianness::Big);
+            let c_u64 = self.add(a_u64, b_u64);
+
+            self.to_bits(&c_u64, 64, Endianness::Big)
+                .try_into()
+                .unwrap()
         } else {
             todo!();
         }
@@ -0,0 +1,103 @@
+//! The keccak-f[1600] permutation over boolean variables.
+
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::builder::CircuitBuilder;
+use crate::prelude::BoolVariable;
+
+/// A 64-bit lane of the keccak state, as little-endian bits.
+pub type Lane = [BoolVariable; 64];
+
+/// The rotation offsets of the rho step, indexed by `x + 5 * y`.
+pub(super) const RHO_OFFSETS: [usize; 25] = [
+    0, 1, 62, 28, 27, 36, 44, 6, 55, 20, 3, 10, 43, 25, 39, 41, 45, 15, 21, 8, 18, 2, 61, 56, 14,
+];
+
+/// The round constants of the iota step.
+pub(super) const ROUND_CONSTANTS: [u64; 24] = [
+    0x0000000000000001,
+    0x0000000000008082,
+    0x800000000000808A,
+    0x8000000080008000,
+    0x000000000000808B,
+    0x0000000080000001,
+    0x8000000080008081,
+    0x8000000000008009,
+    0x000000000000008A,
+    0x0000000000000088,
+    0x0000000080008009,
+    0x000000008000000A,
+    0x000000008000808B,
+    0x800000000000008B,
+    0x8000000000008089,
+    0x8000000000008003,
+    0x8000000000008002,
+    0x8000000000000080,
+    0x000000000000800A,
+    0x800000008000000A,
+    0x8000000080008081,
+    0x8000000000008080,
+    0x0000000080000001,
+    0x8000000080008008,
+];
+
+/// Rotates a lane to the left by `n` bits.
+fn rotate_left(lane: &Lane, n: usize) -> Lane {
+    core::array::from_fn(|i| lane[(i + 64 - n) % 64])
+}
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    pub(super) fn xor_lanes(&mut self, a: &Lane, b: &Lane) -> Lane {
+        core::array::from_fn(|i| self.xor(a[i], b[i]))
+    }
+
+    /// Applies the keccak-f[1600] permutation to `state`, whose lanes are indexed by `x + 5 * y`.
+    pub(crate) fn keccak_f(&mut self, state: &mut [Lane; 25]) {
+        for round_constant in ROUND_CONSTANTS {
+            // Theta.
+            let c: [Lane; 5] = core::array::from_fn(|x| {
+                let c012: Lane = core::array::from_fn(|i| {
+                    self.xor3(
+                        state[x][i].variable,
+                        state[x + 5][i].variable,
+                        state[x + 10][i].variable,
+                    )
+                });
+                let c34 = self.xor_lanes(&state[x + 15], &state[x + 20]);
+                self.xor_lanes(&c012, &c34)
+            });
+            for x in 0..5 {
+                let d = self.xor_lanes(&c[(x + 4) % 5], &rotate_left(&c[(x + 1) % 5], 1));
+                for y in 0..5 {
+                    state[x + 5 * y] = self.xor_lanes(&state[x + 5 * y], &d);
+                }
+            }
+
+            // Rho and pi.
+            let mut b = *state;
+            for x in 0..5 {
+                for y in 0..5 {
+                    b[y + 5 * ((2 * x + 3 * y) % 5)] =
+                        rotate_left(&state[x + 5 * y], RHO_OFFSETS[x + 5 * y]);
+                }
+            }
+
+            // Chi.
+            for x in 0..5 {
+                for y in 0..5 {
+                    state[x + 5 * y] = core::array::from_fn(|i| {
+                        let not_next = self.not(b[(x + 1) % 5 + 5 * y][i]);
+                        let and = self.and(not_next, b[(x + 2) % 5 + 5 * y][i]);
+                        self.xor(b[x + 5 * y][i], and)
+                    });
+                }
+            }
+
+            // Iota.
+            for i in 0..64 {
+                if (round_constant >> i) & 1 == 1 {
+                    state[0][i] = self.not(state[0][i]);
+                }
+            }
+        }
+    }
+}
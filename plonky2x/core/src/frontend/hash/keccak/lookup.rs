@@ -0,0 +1,157 @@
+//! The keccak-f[1600] permutation over bytes, with the bitwise operations done by lookups.
+//!
+//! Every lane is held as eight little-endian byte variables. XOR and `!a & b` are looked up in
+//! tables indexed by `256 * a + b`, and the bit shifts of rotations in tables indexed by a byte,
+//! which replaces eight boolean gates per byte with a single lookup.
+
+use plonky2::field::types::Field;
+
+use super::keccakf::{RHO_OFFSETS, ROUND_CONSTANTS};
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::builder::lookup::LookupTable;
+use crate::frontend::builder::CircuitBuilder;
+use crate::prelude::Variable;
+
+/// A 64-bit lane of the keccak state, as little-endian byte variables.
+pub type ByteLane = [Variable; 8];
+
+/// The lookup tables used by the byte-wise keccak-f permutation.
+#[derive(Debug, Clone)]
+pub(crate) struct KeccakLookupTables {
+    xor: LookupTable,
+    and_not: LookupTable,
+    /// `shl[s - 1]` maps a byte `b` to `(b << s) & 0xff`, for `s` in `1..8`.
+    shl: Vec<LookupTable>,
+    /// `shr[s - 1]` maps a byte `b` to `b >> s`, for `s` in `1..8`.
+    shr: Vec<LookupTable>,
+}
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Returns the keccak lookup tables, adding them to the circuit the first time they are
+    /// requested.
+    fn keccak_lookup_tables(&mut self) -> KeccakLookupTables {
+        if let Some(tables) = self.keccak_lookup_tables.clone() {
+            return tables;
+        }
+        let pairs = (0..=u16::MAX).collect::<Vec<_>>();
+        let tables = KeccakLookupTables {
+            xor: self.add_lookup_table_from_fn(&pairs, |x| (x >> 8) ^ (x & 0xff)),
+            and_not: self.add_lookup_table_from_fn(&pairs, |x| !(x >> 8) & x & 0xff),
+            shl: (1..8)
+                .map(|s| {
+                    let table = (0..256u16).map(|b| (b, (b << s) & 0xff)).collect();
+                    self.add_lookup_table(table)
+                })
+                .collect(),
+            shr: (1..8)
+                .map(|s| {
+                    let table = (0..256u16).map(|b| (b, b >> s)).collect();
+                    self.add_lookup_table(table)
+                })
+                .collect(),
+        };
+        self.keccak_lookup_tables = Some(tables.clone());
+        tables
+    }
+
+    /// Looks up `table` at `256 * a + b`.
+    fn keccak_lookup_pair(&mut self, table: LookupTable, a: Variable, b: Variable) -> Variable {
+        let index = self
+            .api
+            .mul_const_add(L::Field::from_canonical_u16(256), a.0, b.0);
+        self.lookup(table, Variable(index))
+    }
+
+    fn keccak_xor_byte_lanes(
+        &mut self,
+        tables: &KeccakLookupTables,
+        a: &ByteLane,
+        b: &ByteLane,
+    ) -> ByteLane {
+        core::array::from_fn(|i| self.keccak_lookup_pair(tables.xor, a[i], b[i]))
+    }
+
+    /// XORs two lanes whose bytes are in `[0, 256)`.
+    pub(super) fn keccak_xor_byte_lanes_lookup(&mut self, a: &ByteLane, b: &ByteLane) -> ByteLane {
+        let tables = self.keccak_lookup_tables();
+        self.keccak_xor_byte_lanes(&tables, a, b)
+    }
+
+    /// Rotates a lane to the left by `n` bits.
+    fn keccak_rotate_byte_lane(
+        &mut self,
+        tables: &KeccakLookupTables,
+        lane: &ByteLane,
+        n: usize,
+    ) -> ByteLane {
+        let (q, s) = (n / 8, n % 8);
+        let moved: ByteLane = core::array::from_fn(|i| lane[(i + 8 - q) % 8]);
+        if s == 0 {
+            return moved;
+        }
+        // The high bits of each byte come from the byte itself and the low bits from the byte
+        // below it, so the two parts are disjoint and can be added.
+        core::array::from_fn(|i| {
+            let high = self.lookup(tables.shl[s - 1], moved[i]);
+            let low = self.lookup(tables.shr[8 - s - 1], moved[(i + 7) % 8]);
+            self.add(high, low)
+        })
+    }
+
+    /// Applies the keccak-f[1600] permutation to `state`, whose lanes are indexed by `x + 5 * y`
+    /// and whose bytes must all be in `[0, 256)`.
+    pub(crate) fn keccak_f_lookup(&mut self, state: &mut [ByteLane; 25]) {
+        let tables = self.keccak_lookup_tables();
+        for round_constant in ROUND_CONSTANTS {
+            // Theta.
+            let c: [ByteLane; 5] = core::array::from_fn(|x| {
+                let mut c = state[x];
+                for y in 1..5 {
+                    c = self.keccak_xor_byte_lanes(&tables, &c, &state[x + 5 * y]);
+                }
+                c
+            });
+            for x in 0..5 {
+                let rotated = self.keccak_rotate_byte_lane(&tables, &c[(x + 1) % 5], 1);
+                let d = self.keccak_xor_byte_lanes(&tables, &c[(x + 4) % 5], &rotated);
+                for y in 0..5 {
+                    state[x + 5 * y] = self.keccak_xor_byte_lanes(&tables, &state[x + 5 * y], &d);
+                }
+            }
+
+            // Rho and pi.
+            let mut b = *state;
+            for x in 0..5 {
+                for y in 0..5 {
+                    b[y + 5 * ((2 * x + 3 * y) % 5)] = self.keccak_rotate_byte_lane(
+                        &tables,
+                        &state[x + 5 * y],
+                        RHO_OFFSETS[x + 5 * y],
+                    );
+                }
+            }
+
+            // Chi.
+            for x in 0..5 {
+                for y in 0..5 {
+                    state[x + 5 * y] = core::array::from_fn(|i| {
+                        let and_not = self.keccak_lookup_pair(
+                            tables.and_not,
+                            b[(x + 1) % 5 + 5 * y][i],
+                            b[(x + 2) % 5 + 5 * y][i],
+                        );
+                        self.keccak_lookup_pair(tables.xor, b[x + 5 * y][i], and_not)
+                    });
+                }
+            }
+
+            // Iota.
+            for (i, byte) in round_constant.to_le_bytes().into_iter().enumerate() {
+                if byte != 0 {
+                    let constant = self.constant::<Variable>(L::Field::from_canonical_u8(byte));
+                    state[0][i] = self.keccak_lookup_pair(tables.xor, state[0][i], constant);
+                }
+            }
+        }
+    }
+}
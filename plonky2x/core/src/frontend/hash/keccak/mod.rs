@@ -2,14 +2,156 @@
 
 use core::marker::PhantomData;
 
+use itertools::Itertools;
+
 use self::keccak256::Keccak256Generator;
+use self::keccakf::Lane;
+use self::lookup::ByteLane;
 use crate::backend::circuit::PlonkParameters;
+use crate::frontend::hash::HashBackend;
 use crate::frontend::vars::Bytes32Variable;
-use crate::prelude::{ByteVariable, CircuitBuilder, Variable};
+use crate::prelude::{ByteVariable, CircuitBuilder, U32Variable, Variable};
 
 pub mod keccak256;
+mod keccakf;
+pub(crate) mod lookup;
+
+/// The number of bytes absorbed per permutation by keccak256.
+const KECCAK256_RATE: usize = 136;
 
 impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Computes the keccak256 digest of `bytes`, as used by Ethereum.
+    ///
+    /// The message is padded with the keccak `0x01 ... 0x80` padding and absorbed in blocks of
+    /// 136 bytes, each followed by a keccak-f[1600] permutation over boolean variables.
+    pub fn keccak256(&mut self, bytes: &[ByteVariable]) -> Bytes32Variable {
+        let nb_padding_bytes = KECCAK256_RATE - bytes.len() % KECCAK256_RATE;
+        let mut padding = vec![0u8; nb_padding_bytes];
+        padding[0] |= 0x01;
+        padding[nb_padding_bytes - 1] |= 0x80;
+
+        let mut padded = bytes.to_vec();
+        padded.extend(
+            padding
+                .into_iter()
+                .map(|byte| self.constant::<ByteVariable>(byte)),
+        );
+
+        self.keccak256_absorb(&padded).pop().unwrap()
+    }
+
+    /// Computes the keccak256 digest of the first `length` bytes of `bytes`, asserting that
+    /// `length <= bytes.len()`.
+    ///
+    /// The padding is selected in-circuit: bytes at or after `length` are replaced by zeros, the
+    /// byte at `length` is set to `0x01`, and the last byte of the block holding `length` gets its
+    /// top bit set. Every block of the maximum-length message is absorbed, and the digest after the
+    /// block holding `length` is selected.
+    pub fn keccak256_variable(
+        &mut self,
+        bytes: &[ByteVariable],
+        length: U32Variable,
+    ) -> Bytes32Variable {
+        let true_v = self._true();
+        let max_length = self.constant::<U32Variable>(bytes.len() as u32);
+        let is_length_valid = self.lte(length, max_length);
+        self.assert_is_equal(is_length_valid, true_v);
+
+        let rate = self.constant::<U32Variable>(KECCAK256_RATE as u32);
+        let last_block = self.div(length, rate);
+
+        let nb_blocks = bytes.len() / KECCAK256_RATE + 1;
+        let zero_byte = self.constant::<ByteVariable>(0x00);
+        let start_byte = self.constant::<ByteVariable>(0x01);
+
+        let mut padded = Vec::with_capacity(nb_blocks * KECCAK256_RATE);
+        let mut in_message = true_v;
+        for i in 0..nb_blocks * KECCAK256_RATE {
+            let index = self.constant::<U32Variable>(i as u32);
+            let is_start = self.is_equal(index, length);
+            let not_start = self.not(is_start);
+            in_message = self.and(in_message, not_start);
+
+            let byte = bytes.get(i).copied().unwrap_or(zero_byte);
+            let mut byte = self.select(in_message, byte, zero_byte);
+            byte = self.select(is_start, start_byte, byte);
+
+            if i % KECCAK256_RATE == KECCAK256_RATE - 1 {
+                let block = self.constant::<U32Variable>((i / KECCAK256_RATE) as u32);
+                let is_last_block = self.is_equal(block, last_block);
+                let mut bits = byte.as_be_bits();
+                bits[0] = self.or(bits[0], is_last_block);
+                byte = ByteVariable::from_be_bits(bits);
+            }
+            padded.push(byte);
+        }
+
+        let digests = self.keccak256_absorb(&padded);
+        self.select_array(&digests, last_block.variable)
+    }
+
+    /// Absorbs `padded`, a whole number of blocks, and returns the digest after each block.
+    fn keccak256_absorb(&mut self, padded: &[ByteVariable]) -> Vec<Bytes32Variable> {
+        if self.keccak256_backend == HashBackend::Lookup {
+            return self.keccak256_absorb_lookup(padded);
+        }
+        let false_v = self._false();
+        let mut state: [Lane; 25] = [[false_v; 64]; 25];
+        let mut digests = Vec::new();
+        for block in padded.chunks_exact(KECCAK256_RATE) {
+            // Each lane is made of 8 bytes in little-endian order.
+            for (i, lane_bytes) in block.chunks_exact(8).enumerate() {
+                let lane: Lane = lane_bytes
+                    .iter()
+                    .flat_map(|byte| byte.as_le_bits())
+                    .collect_vec()
+                    .try_into()
+                    .unwrap();
+                state[i] = self.xor_lanes(&state[i], &lane);
+            }
+            self.keccak_f(&mut state);
+
+            let digest: [ByteVariable; 32] = state[..4]
+                .iter()
+                .flat_map(|lane| {
+                    lane.chunks_exact(8).map(|le_bits| {
+                        let mut be_bits: [_; 8] = le_bits.try_into().unwrap();
+                        be_bits.reverse();
+                        ByteVariable::from_be_bits(be_bits)
+                    })
+                })
+                .collect_vec()
+                .try_into()
+                .unwrap();
+            digests.push(Bytes32Variable::from(digest));
+        }
+        digests
+    }
+
+    /// Same as `keccak256_absorb`, with the state held as bytes and permuted with lookups.
+    fn keccak256_absorb_lookup(&mut self, padded: &[ByteVariable]) -> Vec<Bytes32Variable> {
+        let zero = self.zero::<Variable>();
+        let mut state: [ByteLane; 25] = [[zero; 8]; 25];
+        let mut digests = Vec::new();
+        for block in padded.chunks_exact(KECCAK256_RATE) {
+            for (i, lane_bytes) in block.chunks_exact(8).enumerate() {
+                let lane: ByteLane = core::array::from_fn(|j| lane_bytes[j].to_variable(self));
+                state[i] = self.keccak_xor_byte_lanes_lookup(&state[i], &lane);
+            }
+            self.keccak_f_lookup(&mut state);
+
+            let digest: [ByteVariable; 32] = state[..4]
+                .iter()
+                .flatten()
+                .map(|byte| ByteVariable::from_variable(self, *byte))
+                .collect_vec()
+                .try_into()
+                .unwrap();
+            digests.push(Bytes32Variable::from(digest));
+        }
+        digests
+    }
+
     /// WARNING: DO NOT USE IN PRODUCTION, this is unconstrained!
     pub fn keccak256_witness(&mut self, bytes: &[ByteVariable]) -> Bytes32Variable {
         // TODO: Need to constrain generator result
@@ -67,4 +209,103 @@ mod tests {
         let input = circuit.input();
         let (_, _) = circuit.prove(&input);
     }
+
+    #[test]
+    fn test_keccak256_constrained() {
+        let mut builder = CircuitBuilder::<L, D>::new();
+
+        // Lengths around the rate of 136 bytes, including a one byte padding.
+        let lengths = [0usize, 32, 135, 136, 200];
+        for length in lengths.iter() {
+            let message = (0..*length)
+                .map(|_| builder.read::<ByteVariable>())
+                .collect::<Vec<_>>();
+            let digest = builder.keccak256(&message);
+            builder.write(digest);
+        }
+
+        let circuit = builder.build();
+        let messages = lengths
+            .iter()
+            .map(|length| (0..*length).map(|i| (i * 7 + 3) as u8).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let mut input = circuit.input();
+        for byte in messages.iter().flatten() {
+            input.write::<ByteVariable>(*byte);
+        }
+
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        for message in messages.iter() {
+            let digest = output.read::<Bytes32Variable>();
+            assert_eq!(digest.0, ethers::utils::keccak256(message));
+        }
+    }
+
+    #[test]
+    fn test_keccak256_variable() {
+        let mut builder = CircuitBuilder::<L, D>::new();
+
+        const MAX_LEN: usize = 150;
+        let message = (0..MAX_LEN)
+            .map(|_| builder.read::<ByteVariable>())
+            .collect::<Vec<_>>();
+        let length = builder.read::<U32Variable>();
+        let digest = builder.keccak256_variable(&message, length);
+        builder.write(digest);
+
+        let circuit = builder.build();
+        let message = (0..MAX_LEN).map(|i| (i * 13 + 5) as u8).collect::<Vec<_>>();
+        for length in [0usize, 17, 135, 136, MAX_LEN] {
+            let mut input = circuit.input();
+            for byte in message.iter() {
+                input.write::<ByteVariable>(*byte);
+            }
+            input.write::<U32Variable>(length as u32);
+
+            let (proof, mut output) = circuit.prove(&input);
+            circuit.verify(&proof, &input, &output);
+
+            let digest = output.read::<Bytes32Variable>();
+            assert_eq!(digest.0, ethers::utils::keccak256(&message[..length]));
+        }
+    }
+
+    #[test]
+    fn test_keccak256_lookup_backend() {
+        let mut builder = CircuitBuilder::<L, D>::new();
+        builder.set_keccak256_backend(HashBackend::Lookup);
+
+        const MAX_LEN: usize = 150;
+        let message = (0..MAX_LEN)
+            .map(|_| builder.read::<ByteVariable>())
+            .collect::<Vec<_>>();
+        let length = builder.read::<U32Variable>();
+        let fixed_digest = builder.keccak256(&message);
+        let variable_digest = builder.keccak256_variable(&message, length);
+        builder.write(fixed_digest);
+        builder.write(variable_digest);
+
+        let circuit = builder.build();
+        let message = (0..MAX_LEN).map(|i| (i * 31 + 9) as u8).collect::<Vec<_>>();
+        let length = 136;
+        let mut input = circuit.input();
+        for byte in message.iter() {
+            input.write::<ByteVariable>(*byte);
+        }
+        input.write::<U32Variable>(length as u32);
+
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        assert_eq!(
+            output.read::<Bytes32Variable>().0,
+            ethers::utils::keccak256(&message)
+        );
+        assert_eq!(
+            output.read::<Bytes32Variable>().0,
+            ethers::utils::keccak256(&message[..length])
+        );
+    }
 }
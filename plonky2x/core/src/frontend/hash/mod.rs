@@ -3,4 +3,17 @@ pub mod common;
 pub mod curta;
 pub mod keccak;
 pub mod poseidon;
+pub mod ripemd160;
 pub mod sha;
+
+/// The implementation used by the builder for a hash function.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HashBackend {
+    /// Every invocation is constrained with boolean gates in the circuit itself.
+    #[default]
+    Constrained,
+    /// Invocations are batched into a STARK whose proof is verified recursively in the circuit.
+    Curta,
+    /// Bitwise operations are done on bytes with lookup tables instead of on individual bits.
+    Lookup,
+}
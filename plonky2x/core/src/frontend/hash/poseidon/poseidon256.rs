@@ -1,4 +1,5 @@
 use array_macro::array;
+use plonky2::field::types::Field;
 use plonky2::hash::hash_types::{HashOut, HashOutTarget, RichField, NUM_HASH_OUT_ELTS};
 use plonky2::iop::target::BoolTarget;
 use plonky2::plonk::config::{AlgebraicHasher, GenericConfig};
@@ -8,6 +9,9 @@ use crate::frontend::builder::CircuitBuilder;
 use crate::frontend::vars::{ArrayVariable, Bytes32Variable};
 use crate::prelude::{BoolVariable, ByteVariable, BytesVariable, CircuitVariable, Variable};
 
+/// The number of bytes packed into a field element by `poseidon_hash_bytes`.
+const POSEIDON_BYTES_PER_ELEMENT: usize = 7;
+
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct PoseidonHashOutVariable {
     pub elements: ArrayVariable<Variable, 4>,
@@ -85,6 +89,44 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         self.poseidon_hash(&input)
     }
 
+    /// Computes the Poseidon hash of the variables of `values`, in order, with no padding.
+    pub fn poseidon_hash_variables<V: CircuitVariable>(
+        &mut self,
+        values: &[V],
+    ) -> PoseidonHashOutVariable
+    where
+        <<L as PlonkParameters<D>>::Config as GenericConfig<D>>::Hasher:
+            AlgebraicHasher<<L as PlonkParameters<D>>::Field>,
+    {
+        let variables = values
+            .iter()
+            .flat_map(|v| v.variables())
+            .collect::<Vec<_>>();
+        self.poseidon_hash(&variables)
+    }
+
+    /// Computes the Poseidon hash of `bytes`, packed into field elements.
+    ///
+    /// The bytes are packed little-endian into elements of 7 bytes, which always fit in the
+    /// Goldilocks field, and the number of bytes is hashed first so that messages of different
+    /// lengths do not collide.
+    pub fn poseidon_hash_bytes(&mut self, bytes: &[ByteVariable]) -> PoseidonHashOutVariable
+    where
+        <<L as PlonkParameters<D>>::Config as GenericConfig<D>>::Hasher:
+            AlgebraicHasher<<L as PlonkParameters<D>>::Field>,
+    {
+        let mut variables =
+            vec![self.constant::<Variable>(L::Field::from_canonical_usize(bytes.len()))];
+        for chunk in bytes.chunks(POSEIDON_BYTES_PER_ELEMENT) {
+            let bits = chunk
+                .iter()
+                .flat_map(|byte| byte.as_le_bits())
+                .map(|bit| -> BoolTarget { bit.into() });
+            variables.push(Variable(self.api.le_sum(bits)));
+        }
+        self.poseidon_hash(&variables)
+    }
+
     /// Note: This Poseidon implementation operates on bytes, not field elements. The input bytes to
     /// the Poseidon hash are converted into field elements internally. Specifically, we convert the
     /// [ByteVariable; N] into a [u32; N/4] and then represent the u32 as a [F; N/4]. We use u32's
@@ -153,11 +195,14 @@ impl From<PoseidonHashOutVariable> for HashOutTarget {
 #[cfg(test)]
 mod tests {
     use anyhow::Result;
-    use plonky2::plonk::config::GenericConfig;
+    use plonky2::field::types::Field;
+    use plonky2::hash::poseidon::PoseidonHash;
+    use plonky2::plonk::config::{GenericConfig, Hasher};
 
+    use super::PoseidonHashOutVariable;
     use crate::backend::circuit::{DefaultParameters, PlonkParameters};
     use crate::frontend::vars::Bytes32Variable;
-    use crate::prelude::CircuitBuilder;
+    use crate::prelude::{ArrayVariable, BytesVariable, CircuitBuilder, U64Variable};
     use crate::utils::{self, bytes32};
 
     #[test]
@@ -200,4 +245,57 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_poseidon_hash_variables_and_bytes() {
+        type L = DefaultParameters;
+        const D: usize = 2;
+        type F = <L as PlonkParameters<D>>::Field;
+        let mut builder = CircuitBuilder::<L, D>::new();
+
+        let values = builder.read::<ArrayVariable<U64Variable, 2>>();
+        let bytes = builder.read::<BytesVariable<10>>();
+        let values_hash = builder.poseidon_hash_variables(values.as_slice());
+        let bytes_hash = builder.poseidon_hash_bytes(&bytes.0);
+        builder.write(values_hash);
+        builder.write(bytes_hash);
+
+        let circuit = builder.build();
+        let bytes_value: [u8; 10] = core::array::from_fn(|i| (i * 29 + 1) as u8);
+        let mut input = circuit.input();
+        input.write::<ArrayVariable<U64Variable, 2>>(vec![0x0123_4567_89ab_cdef, 42]);
+        input.write::<BytesVariable<10>>(bytes_value);
+
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        // A u64 is made of two u32 limbs in little-endian order.
+        let expected_values_hash = PoseidonHash::hash_no_pad(&[
+            F::from_canonical_u64(0x89ab_cdef),
+            F::from_canonical_u64(0x0123_4567),
+            F::from_canonical_u64(42),
+            F::ZERO,
+        ]);
+        let pack = |chunk: &[u8]| {
+            F::from_canonical_u64(
+                chunk
+                    .iter()
+                    .rev()
+                    .fold(0u64, |acc, byte| (acc << 8) | *byte as u64),
+            )
+        };
+        let expected_bytes_hash = PoseidonHash::hash_no_pad(&[
+            F::from_canonical_u64(10),
+            pack(&bytes_value[..7]),
+            pack(&bytes_value[7..]),
+        ]);
+        assert_eq!(
+            output.read::<PoseidonHashOutVariable>(),
+            expected_values_hash
+        );
+        assert_eq!(
+            output.read::<PoseidonHashOutVariable>(),
+            expected_bytes_hash
+        );
+    }
 }
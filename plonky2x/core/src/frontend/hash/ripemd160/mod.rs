@@ -0,0 +1,254 @@
+//! An implementation of the RIPEMD-160 hash function in a plonky2 circuit.
+//!
+//! reference: https://homes.esat.kuleuven.be/~bosselae/ripemd160/pdf/AB-9601/AB-9601.pdf
+
+use itertools::Itertools;
+
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::builder::CircuitBuilder;
+use crate::frontend::hash::common::{and_arr, not_arr, xor2_arr, xor3_arr};
+use crate::frontend::vars::{BoolVariable, ByteVariable, BytesVariable, CircuitVariable};
+
+/// A 32-bit word, as big-endian bits.
+type Word = [BoolVariable; 32];
+
+const RIPEMD160_CHUNK_SIZE_BYTES: usize = 64;
+
+const RIPEMD160_INITIAL_HASH: [u32; 5] =
+    [0x67452301, 0xEFCDAB89, 0x98BADCFE, 0x10325476, 0xC3D2E1F0];
+
+/// The round constants of the left and right lines, one per group of 16 steps.
+const RIPEMD160_K_LEFT: [u32; 5] = [0x00000000, 0x5A827999, 0x6ED9EBA1, 0x8F1BBCDC, 0xA953FD4E];
+const RIPEMD160_K_RIGHT: [u32; 5] = [0x50A28BE6, 0x5C4DD124, 0x6D703EF3, 0x7A6D76E9, 0x00000000];
+
+/// The message word selected at each step of the left and right lines.
+const RIPEMD160_R_LEFT: [usize; 80] = [
+    0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, //
+    7, 4, 13, 1, 10, 6, 15, 3, 12, 0, 9, 5, 2, 14, 11, 8, //
+    3, 10, 14, 4, 9, 15, 8, 1, 2, 7, 0, 6, 13, 11, 5, 12, //
+    1, 9, 11, 10, 0, 8, 12, 4, 13, 3, 7, 15, 14, 5, 6, 2, //
+    4, 0, 5, 9, 7, 12, 2, 10, 14, 1, 3, 8, 11, 6, 15, 13,
+];
+const RIPEMD160_R_RIGHT: [usize; 80] = [
+    5, 14, 7, 0, 9, 2, 11, 4, 13, 6, 15, 8, 1, 10, 3, 12, //
+    6, 11, 3, 7, 0, 13, 5, 10, 14, 15, 8, 12, 4, 9, 1, 2, //
+    15, 5, 1, 3, 7, 14, 6, 9, 11, 8, 12, 2, 10, 0, 4, 13, //
+    8, 6, 4, 1, 3, 11, 15, 0, 5, 12, 2, 13, 9, 7, 10, 14, //
+    12, 15, 10, 4, 1, 5, 8, 7, 6, 2, 13, 14, 0, 3, 9, 11,
+];
+
+/// The left rotation applied at each step of the left and right lines.
+const RIPEMD160_S_LEFT: [usize; 80] = [
+    11, 14, 15, 12, 5, 8, 7, 9, 11, 13, 14, 15, 6, 7, 9, 8, //
+    7, 6, 8, 13, 11, 9, 7, 15, 7, 12, 15, 9, 11, 7, 13, 12, //
+    11, 13, 6, 7, 14, 9, 13, 15, 14, 8, 13, 6, 5, 12, 7, 5, //
+    11, 12, 14, 15, 14, 15, 9, 8, 9, 14, 5, 6, 8, 6, 5, 12, //
+    9, 15, 5, 11, 6, 8, 13, 12, 5, 12, 13, 14, 11, 8, 5, 6,
+];
+const RIPEMD160_S_RIGHT: [usize; 80] = [
+    8, 9, 9, 11, 13, 15, 15, 5, 7, 7, 8, 11, 14, 14, 12, 6, //
+    9, 13, 15, 7, 12, 8, 9, 11, 7, 7, 12, 7, 6, 15, 13, 11, //
+    9, 7, 15, 11, 8, 6, 6, 14, 12, 13, 5, 14, 13, 13, 7, 5, //
+    15, 5, 8, 11, 14, 14, 6, 14, 6, 9, 12, 9, 12, 5, 15, 8, //
+    8, 5, 12, 9, 12, 5, 14, 6, 8, 13, 6, 5, 15, 13, 11, 11,
+];
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Computes the RIPEMD-160 digest of `input`, whose length is known at compile time.
+    pub fn ripemd160(&mut self, input: &[ByteVariable]) -> BytesVariable<20> {
+        // The message is followed by 0x80, zeros up to 56 bytes modulo 64, and its length in bits
+        // as a little-endian u64.
+        let mut padding = vec![0x80u8];
+        while (input.len() + padding.len()) % RIPEMD160_CHUNK_SIZE_BYTES != 56 {
+            padding.push(0);
+        }
+        padding.extend(((input.len() as u64) * 8).to_le_bytes());
+        let mut padded = input.to_vec();
+        padded.extend(
+            padding
+                .into_iter()
+                .map(|byte| self.constant::<ByteVariable>(byte)),
+        );
+
+        let mut state = RIPEMD160_INITIAL_HASH.map(|x| self.ripemd160_const_word(x));
+        for chunk in padded.chunks_exact(RIPEMD160_CHUNK_SIZE_BYTES) {
+            // Message words are encoded in little-endian byte order.
+            let x: [Word; 16] = chunk
+                .chunks_exact(4)
+                .map(|word_bytes| {
+                    word_bytes
+                        .iter()
+                        .rev()
+                        .flat_map(|byte| byte.as_be_bits())
+                        .collect_vec()
+                        .try_into()
+                        .unwrap()
+                })
+                .collect_vec()
+                .try_into()
+                .unwrap();
+            self.ripemd160_compress(&mut state, &x);
+        }
+
+        let digest_bits = state
+            .iter()
+            .flat_map(|word| word.chunks_exact(8).rev().flatten().copied().collect_vec())
+            .map(|bit| bit.variable)
+            .collect_vec();
+        // Ok to use `from_variables_unsafe` as the state consists of 160 bits.
+        BytesVariable::<20>::from_variables_unsafe(&digest_bits)
+    }
+
+    /// Computes `RIPEMD-160(SHA-256(input))`, the hash used for Bitcoin P2PKH and P2WPKH outputs.
+    pub fn hash160(&mut self, input: &[ByteVariable]) -> BytesVariable<20> {
+        let sha256_digest = self.sha256(input);
+        self.ripemd160(&sha256_digest.as_bytes())
+    }
+
+    fn ripemd160_compress(&mut self, state: &mut [Word; 5], x: &[Word; 16]) {
+        let [mut al, mut bl, mut cl, mut dl, mut el] = *state;
+        let [mut ar, mut br, mut cr, mut dr, mut er] = *state;
+
+        for j in 0..80 {
+            let fl = self.ripemd160_f(j, bl, cl, dl);
+            let kl = self.ripemd160_const_word(RIPEMD160_K_LEFT[j / 16]);
+            let t = self.add_arr(al, fl);
+            let t = self.add_arr(t, x[RIPEMD160_R_LEFT[j]]);
+            let t = self.add_arr(t, kl);
+            let t = rotate_left(t, RIPEMD160_S_LEFT[j]);
+            let t = self.add_arr(t, el);
+            al = el;
+            el = dl;
+            dl = rotate_left(cl, 10);
+            cl = bl;
+            bl = t;
+
+            let fr = self.ripemd160_f(79 - j, br, cr, dr);
+            let kr = self.ripemd160_const_word(RIPEMD160_K_RIGHT[j / 16]);
+            let t = self.add_arr(ar, fr);
+            let t = self.add_arr(t, x[RIPEMD160_R_RIGHT[j]]);
+            let t = self.add_arr(t, kr);
+            let t = rotate_left(t, RIPEMD160_S_RIGHT[j]);
+            let t = self.add_arr(t, er);
+            ar = er;
+            er = dr;
+            dr = rotate_left(cr, 10);
+            cr = br;
+            br = t;
+        }
+
+        let t = self.add_arr(state[1], cl);
+        let t = self.add_arr(t, dr);
+        let h1 = self.add_arr(state[2], dl);
+        state[1] = self.add_arr(h1, er);
+        let h2 = self.add_arr(state[3], el);
+        state[2] = self.add_arr(h2, ar);
+        let h3 = self.add_arr(state[4], al);
+        state[3] = self.add_arr(h3, br);
+        let h4 = self.add_arr(state[0], bl);
+        state[4] = self.add_arr(h4, cr);
+        state[0] = t;
+    }
+
+    /// The boolean function of step `j`.
+    fn ripemd160_f(&mut self, j: usize, x: Word, y: Word, z: Word) -> Word {
+        match j / 16 {
+            0 => xor3_arr(x, y, z, self),
+            1 => {
+                let xy = and_arr(x, y, self);
+                let not_x = not_arr(x, self);
+                let not_xz = and_arr(not_x, z, self);
+                self.ripemd160_or(xy, not_xz)
+            }
+            2 => {
+                let not_y = not_arr(y, self);
+                let x_or_not_y = self.ripemd160_or(x, not_y);
+                xor2_arr(x_or_not_y, z, self)
+            }
+            3 => {
+                let xz = and_arr(x, z, self);
+                let not_z = not_arr(z, self);
+                let y_not_z = and_arr(y, not_z, self);
+                self.ripemd160_or(xz, y_not_z)
+            }
+            _ => {
+                let not_z = not_arr(z, self);
+                let y_or_not_z = self.ripemd160_or(y, not_z);
+                xor2_arr(x, y_or_not_z, self)
+            }
+        }
+    }
+
+    fn ripemd160_or(&mut self, a: Word, b: Word) -> Word {
+        core::array::from_fn(|i| self.or(a[i], b[i]))
+    }
+
+    fn ripemd160_const_word(&mut self, u: u32) -> Word {
+        core::array::from_fn(|i| self.constant::<BoolVariable>((u >> (31 - i)) & 1 == 1))
+    }
+}
+
+/// Rotates a word to the left by `n` bits.
+fn rotate_left(word: Word, n: usize) -> Word {
+    core::array::from_fn(|i| word[(i + n) % 32])
+}
+
+#[cfg(test)]
+mod tests {
+    use crypto::digest::Digest;
+    use crypto::ripemd160::Ripemd160;
+
+    use crate::prelude::*;
+    use crate::utils::hash::sha256;
+
+    fn ripemd160(message: &[u8]) -> [u8; 20] {
+        let mut hasher = Ripemd160::new();
+        hasher.input(message);
+        let mut digest = [0u8; 20];
+        hasher.result(&mut digest);
+        digest
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_ripemd160() {
+        let mut builder = DefaultBuilder::new();
+
+        // 55 bytes is the longest message fitting in one chunk with its padding and length.
+        let lengths = [0usize, 3, 55, 56, 64, 130];
+        for length in lengths.iter() {
+            let message = (0..*length)
+                .map(|_| builder.read::<ByteVariable>())
+                .collect::<Vec<_>>();
+            let digest = builder.ripemd160(&message);
+            builder.write(digest);
+        }
+        let public_key = (0..33)
+            .map(|_| builder.read::<ByteVariable>())
+            .collect::<Vec<_>>();
+        let hash160 = builder.hash160(&public_key);
+        builder.write(hash160);
+
+        let circuit = builder.build();
+        let messages = lengths
+            .iter()
+            .map(|length| (0..*length).map(|i| (i * 3 + 7) as u8).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let public_key = (0..33).map(|i| (i * 17 + 2) as u8).collect::<Vec<_>>();
+        let mut input = circuit.input();
+        for byte in messages.iter().flatten().chain(public_key.iter()) {
+            input.write::<ByteVariable>(*byte);
+        }
+
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        for message in messages.iter() {
+            assert_eq!(output.read::<BytesVariable<20>>(), ripemd160(message));
+        }
+        assert_eq!(
+            output.read::<BytesVariable<20>>(),
+            ripemd160(&sha256(&public_key))
+        );
+    }
+}
@@ -7,7 +7,10 @@ use itertools::Itertools;
 use crate::backend::circuit::PlonkParameters;
 use crate::frontend::builder::CircuitBuilder;
 use crate::frontend::hash::common::{and_arr, not_arr, xor2_arr, xor3_arr};
-use crate::frontend::vars::{BoolVariable, ByteVariable, Bytes32Variable, CircuitVariable};
+use crate::frontend::hash::HashBackend;
+use crate::frontend::vars::{
+    BoolVariable, ByteVariable, Bytes32Variable, CircuitVariable, U32Variable,
+};
 
 pub mod curta;
 pub mod pad;
@@ -32,12 +35,19 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
     }
 
     fn process_padded_message(&mut self, msg_input: &[ByteVariable]) -> Vec<BoolVariable> {
+        self.sha256_chunk_states(msg_input).pop().unwrap()
+    }
+
+    /// Returns the hash state, as 256 big-endian bits, after processing each 64 byte chunk of the
+    /// padded message.
+    fn sha256_chunk_states(&mut self, msg_input: &[ByteVariable]) -> Vec<Vec<BoolVariable>> {
         let msg_input_bits = msg_input
             .iter()
             .flat_map(|b| b.as_be_bits().to_vec())
             .collect_vec();
         let mut sha256_hash = self.get_inital_hash();
         let round_constants = self.get_round_constants();
+        let mut states = Vec::new();
 
         // Process the input with 512 bit chunks aka 64 byte chunks
         for chunk in msg_input_bits.chunks_exact(512) {
@@ -126,12 +136,20 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
             }
 
             sha256_hash = self.zip_add(sha256_hash, [a, b, c, d, e, f, g, h]);
+            states.push(sha256_hash.iter().flat_map(|x| x.to_vec()).collect());
         }
 
-        sha256_hash.iter().flat_map(|x| x.to_vec()).collect()
+        states
     }
 
+    /// Computes the SHA-256 digest of `input`, whose length is known at compile time.
+    ///
+    /// The message is padded with constants and every compression is done with boolean gates. For
+    /// large inputs, selecting `HashBackend::Curta` with `set_sha256_backend` is much cheaper.
     pub fn sha256(&mut self, input: &[ByteVariable]) -> Bytes32Variable {
+        if self.sha256_backend == HashBackend::Curta {
+            return self.curta_sha256(input);
+        }
         let padded = self.pad_message_sha256(input);
         let bool_digest: Vec<BoolVariable> = self.process_padded_message(&padded);
         assert_eq!(bool_digest.len(), 256);
@@ -141,6 +159,35 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         )
     }
 
+    /// Computes the SHA-256 digest of the first `length` bytes of `input`, asserting that
+    /// `length <= input.len()`.
+    ///
+    /// The padding is computed in-circuit, every chunk of the maximum-length message is compressed,
+    /// and the state after the chunk holding the encoded length is selected as the digest.
+    pub fn sha256_variable(
+        &mut self,
+        input: &[ByteVariable],
+        length: U32Variable,
+    ) -> Bytes32Variable {
+        if self.sha256_backend == HashBackend::Curta {
+            return self.curta_sha256_variable(input, length);
+        }
+        let padded = self.pad_sha256_variable_length(input, length);
+        let last_chunk = self.compute_sha256_last_chunk(length);
+        let digests = self
+            .sha256_chunk_states(&padded)
+            .iter()
+            .map(|bits| {
+                // Ok to use `from_variables_unsafe` as every state consists of 256 bits.
+                Bytes32Variable::from_variables_unsafe(
+                    &bits.iter().map(|b| b.variable).collect_vec(),
+                )
+            })
+            .collect_vec();
+        self.select_array(&digests, last_chunk.variable)
+    }
+
+    /// Computes the SHA-256 digest of the concatenation of `left` and `right`.
     pub fn sha256_pair(
         &mut self,
         left: Bytes32Variable,
@@ -282,4 +329,111 @@ mod tests {
             circuit.test_default_serializers();
         }
     }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_sha256_block_boundaries() {
+        let mut builder = CircuitBuilder::<L, D>::new();
+
+        // Lengths around the points where the padding spills into an extra block.
+        let lengths = [0usize, 55, 56, 64, 119];
+        for length in lengths.iter() {
+            let message = (0..*length)
+                .map(|_| builder.read::<ByteVariable>())
+                .collect::<Vec<_>>();
+            let digest = builder.sha256(&message);
+            builder.write(digest);
+        }
+
+        let circuit = builder.build();
+        let mut rng = thread_rng();
+        let messages = lengths
+            .iter()
+            .map(|length| (0..*length).map(|_| rng.gen::<u8>()).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let mut input = circuit.input();
+        for byte in messages.iter().flatten() {
+            input.write::<ByteVariable>(*byte);
+        }
+
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        for message in messages.iter() {
+            let digest = output.read::<Bytes32Variable>();
+            assert_eq!(digest.0, sha256(message));
+        }
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_sha256_variable_length() {
+        let mut builder = CircuitBuilder::<L, D>::new();
+
+        const MAX_LEN: usize = 100;
+        let message = (0..MAX_LEN)
+            .map(|_| builder.read::<ByteVariable>())
+            .collect::<Vec<_>>();
+        let length = builder.read::<U32Variable>();
+        let digest = builder.sha256_variable(&message, length);
+        builder.write(digest);
+
+        let circuit = builder.build();
+        let mut rng = thread_rng();
+        let message = (0..MAX_LEN).map(|_| rng.gen::<u8>()).collect::<Vec<_>>();
+        for length in [0usize, 10, 55, 56, 64, MAX_LEN] {
+            let mut input = circuit.input();
+            for byte in message.iter() {
+                input.write::<ByteVariable>(*byte);
+            }
+            input.write::<U32Variable>(length as u32);
+
+            let (proof, mut output) = circuit.prove(&input);
+            circuit.verify(&proof, &input, &output);
+
+            let digest = output.read::<Bytes32Variable>();
+            assert_eq!(digest.0, sha256(&message[..length]));
+        }
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_sha256_curta_backend() {
+        let mut builder = CircuitBuilder::<L, D>::new();
+        builder.set_sha256_backend(HashBackend::Curta);
+
+        const MAX_LEN: usize = 200;
+        let message = (0..MAX_LEN)
+            .map(|_| builder.read::<ByteVariable>())
+            .collect::<Vec<_>>();
+        let length = builder.read::<U32Variable>();
+        let fixed_digest = builder.sha256(&message);
+        let variable_digest = builder.sha256_variable(&message, length);
+        let pair_digest = builder.sha256_pair(fixed_digest, variable_digest);
+        builder.write(fixed_digest);
+        builder.write(variable_digest);
+        builder.write(pair_digest);
+
+        let circuit = builder.build();
+        let mut rng = thread_rng();
+        let message = (0..MAX_LEN).map(|_| rng.gen::<u8>()).collect::<Vec<_>>();
+        let length = 77;
+        let mut input = circuit.input();
+        for byte in message.iter() {
+            input.write::<ByteVariable>(*byte);
+        }
+        input.write::<U32Variable>(length as u32);
+
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        let fixed_digest = sha256(&message);
+        let variable_digest = sha256(&message[..length]);
+        assert_eq!(output.read::<Bytes32Variable>().0, fixed_digest);
+        assert_eq!(output.read::<Bytes32Variable>().0, variable_digest);
+        assert_eq!(
+            output.read::<Bytes32Variable>().0,
+            sha256(&[fixed_digest, variable_digest].concat())
+        );
+    }
 }
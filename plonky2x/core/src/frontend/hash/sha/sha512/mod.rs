@@ -1,2 +1,226 @@
+use ::starkyx::machine::hash::sha::algorithm::SHAPure;
+use ::starkyx::machine::hash::sha::sha512::SHA512;
+/// Implementation of sha512
+/// reference: https://github.com/thomdixon/pysha2/blob/master/sha2/sha512.py
+use itertools::Itertools;
+
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::builder::CircuitBuilder;
+use crate::frontend::hash::common::{and_arr, not_arr, xor2_arr, xor3_arr};
+use crate::frontend::vars::{
+    BoolVariable, ByteVariable, BytesVariable, CircuitVariable, U32Variable,
+};
+
 pub mod curta;
 pub mod pad;
+
+/// Implements SHA512 implementation for CircuitBuilder
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    fn sha512_const_be_bits(&mut self, u: u64) -> [BoolVariable; 64] {
+        u.to_be_bytes()
+            .iter()
+            .flat_map(|b| self.constant::<ByteVariable>(*b).as_be_bits().to_vec())
+            .collect::<Vec<BoolVariable>>()
+            .try_into()
+            .unwrap()
+    }
+
+    /// Returns the hash state, as 512 big-endian bits, after processing each 128 byte chunk of the
+    /// padded message.
+    fn sha512_chunk_states(&mut self, msg_input: &[ByteVariable]) -> Vec<Vec<BoolVariable>> {
+        let msg_input_bits = msg_input
+            .iter()
+            .flat_map(|b| b.as_be_bits().to_vec())
+            .collect_vec();
+        let mut sha512_hash = SHA512::INITIAL_HASH.map(|x| self.sha512_const_be_bits(x));
+        let round_constants = SHA512::ROUND_CONSTANTS.map(|x| self.sha512_const_be_bits(x));
+        let mut states = Vec::new();
+
+        // Process the input with 1024 bit chunks aka 128 byte chunks, as 16 words of 64 bits.
+        for chunk in msg_input_bits.chunks_exact(1024) {
+            let mut w: Vec<[BoolVariable; 64]> = chunk
+                .chunks_exact(64)
+                .map(|word| word.try_into().unwrap())
+                .collect();
+
+            for i in 16..80 {
+                let s0 = xor3_arr(
+                    self._right_rotate(w[i - 15], 1),
+                    self._right_rotate(w[i - 15], 8),
+                    self._shr(w[i - 15], 7),
+                    self,
+                );
+
+                let s1 = xor3_arr(
+                    self._right_rotate(w[i - 2], 19),
+                    self._right_rotate(w[i - 2], 61),
+                    self._shr(w[i - 2], 6),
+                    self,
+                );
+
+                let inter1 = self.add_arr(w[i - 16], s0);
+                let inter2 = self.add_arr(inter1, w[i - 7]);
+                w.push(self.add_arr(s1, inter2));
+            }
+            let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = sha512_hash;
+
+            for i in 0..80 {
+                let sum1 = xor3_arr(
+                    self._right_rotate(e, 14),
+                    self._right_rotate(e, 18),
+                    self._right_rotate(e, 41),
+                    self,
+                );
+                let ch = xor2_arr(
+                    and_arr(e, f, self),
+                    and_arr(not_arr(e, self), g, self),
+                    self,
+                );
+                let temp1 = self.add_arr(h, sum1);
+                let temp2 = self.add_arr(temp1, ch);
+                let temp3 = self.add_arr(temp2, round_constants[i]);
+                let final_temp1 = self.add_arr(temp3, w[i]);
+
+                let sum0 = xor3_arr(
+                    self._right_rotate(a, 28),
+                    self._right_rotate(a, 34),
+                    self._right_rotate(a, 39),
+                    self,
+                );
+
+                let maj = xor3_arr(
+                    and_arr(a, b, self),
+                    and_arr(a, c, self),
+                    and_arr(b, c, self),
+                    self,
+                );
+                let final_temp2 = self.add_arr(sum0, maj);
+
+                h = g;
+                g = f;
+                f = e;
+                e = self.add_arr(d, final_temp1);
+                d = c;
+                c = b;
+                b = a;
+                a = self.add_arr(final_temp1, final_temp2);
+            }
+
+            sha512_hash = self.zip_add(sha512_hash, [a, b, c, d, e, f, g, h]);
+            states.push(sha512_hash.iter().flat_map(|x| x.to_vec()).collect());
+        }
+
+        states
+    }
+
+    /// Computes the SHA-512 digest of `input`, whose length is known at compile time.
+    ///
+    /// The message is padded with constants and every compression is done with boolean gates over
+    /// 64-bit words. For large inputs, `curta_sha512` is much cheaper.
+    pub fn sha512(&mut self, input: &[ByteVariable]) -> BytesVariable<64> {
+        let padded = self.pad_message_sha512(input);
+        let bool_digest = self.sha512_chunk_states(&padded).pop().unwrap();
+        assert_eq!(bool_digest.len(), 512);
+        // Ok to use `from_variables_unsafe` as the state consists of 512 bits.
+        BytesVariable::<64>::from_variables_unsafe(
+            &bool_digest.iter().map(|b| b.variable).collect_vec(),
+        )
+    }
+
+    /// Computes the SHA-512 digest of the first `length` bytes of `input`, asserting that
+    /// `length <= input.len()`.
+    ///
+    /// The padding is computed in-circuit, every chunk of the maximum-length message is compressed,
+    /// and the state after the chunk holding the encoded length is selected as the digest.
+    pub fn sha512_variable(
+        &mut self,
+        input: &[ByteVariable],
+        length: U32Variable,
+    ) -> BytesVariable<64> {
+        let padded = self.pad_sha512_variable_length(input, length);
+        let last_chunk = self.compute_sha512_last_chunk(length);
+        let digests = self
+            .sha512_chunk_states(&padded)
+            .iter()
+            .map(|bits| {
+                // Ok to use `from_variables_unsafe` as every state consists of 512 bits.
+                BytesVariable::<64>::from_variables_unsafe(
+                    &bits.iter().map(|b| b.variable).collect_vec(),
+                )
+            })
+            .collect_vec();
+        self.select_array(&digests, last_chunk.variable)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::DefaultBuilder;
+    use crate::utils::hash::sha512;
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_sha512_block_boundaries() {
+        let mut builder = DefaultBuilder::new();
+
+        // 111 bytes is the longest message fitting in one chunk with its padding and length.
+        let lengths = [0usize, 3, 111, 112, 128, 200];
+        for length in lengths.iter() {
+            let message = (0..*length)
+                .map(|_| builder.read::<ByteVariable>())
+                .collect::<Vec<_>>();
+            let digest = builder.sha512(&message);
+            builder.write(digest);
+        }
+
+        let circuit = builder.build();
+        let messages = lengths
+            .iter()
+            .map(|length| (0..*length).map(|i| (i * 5 + 2) as u8).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let mut input = circuit.input();
+        for byte in messages.iter().flatten() {
+            input.write::<ByteVariable>(*byte);
+        }
+
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        for message in messages.iter() {
+            assert_eq!(output.read::<BytesVariable<64>>(), sha512(message));
+        }
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_sha512_variable_length() {
+        let mut builder = DefaultBuilder::new();
+
+        const MAX_LEN: usize = 150;
+        let message = (0..MAX_LEN)
+            .map(|_| builder.read::<ByteVariable>())
+            .collect::<Vec<_>>();
+        let length = builder.read::<U32Variable>();
+        let digest = builder.sha512_variable(&message, length);
+        builder.write(digest);
+
+        let circuit = builder.build();
+        let message = (0..MAX_LEN).map(|i| (i * 9 + 4) as u8).collect::<Vec<_>>();
+        for length in [0usize, 111, 112, MAX_LEN] {
+            let mut input = circuit.input();
+            for byte in message.iter() {
+                input.write::<ByteVariable>(*byte);
+            }
+            input.write::<U32Variable>(length as u32);
+
+            let (proof, mut output) = circuit.prove(&input);
+            circuit.verify(&proof, &input, &output);
+
+            assert_eq!(
+                output.read::<BytesVariable<64>>(),
+                sha512(&message[..length])
+            );
+        }
+    }
+}
@@ -25,11 +25,12 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
 #[cfg(test)]
 mod tests {
     use async_trait::async_trait;
+    use plonky2::iop::witness::PartialWitness;
     use serde::{Deserialize, Serialize};
     use tokio::time::{sleep, Duration};
 
     use super::*;
-    use crate::backend::circuit::CircuitBuild;
+    use crate::backend::circuit::{generate_witness, CircuitBuild};
     use crate::frontend::vars::ValueStream;
     use crate::prelude::{
         ByteVariable, DefaultBuilder, DefaultParameters, GateRegistry, HintRegistry,
@@ -148,4 +149,42 @@ mod tests {
         let byte_plus_one = output.read::<ByteVariable>();
         assert_eq!(byte_plus_one, 5u8);
     }
+
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    pub struct FailingAsyncHint;
+
+    #[async_trait]
+    impl<L: PlonkParameters<D>, const D: usize> AsyncHint<L, D> for FailingAsyncHint {
+        async fn try_hint_fn(
+            &self,
+            _input_stream: ValueStream<L, D>,
+        ) -> anyhow::Result<ValueStream<L, D>> {
+            Err(anyhow::anyhow!("the RPC is unavailable"))
+        }
+    }
+
+    #[test]
+    fn test_async_hint_error() {
+        let mut builder = DefaultBuilder::new();
+        let a = builder.read::<ByteVariable>();
+        let mut input_stream = VariableStream::new();
+        input_stream.write(&a);
+        let output_stream = builder.async_hint(input_stream, FailingAsyncHint);
+        let b = output_stream.read::<ByteVariable>(&mut builder);
+        builder.write(b);
+        let circuit = builder.mock_build();
+
+        let mut input = circuit.input();
+        input.write::<ByteVariable>(5u8);
+        let mut pw = PartialWitness::new();
+        circuit.io.set_witness(&mut pw, &input);
+        let error = generate_witness(
+            pw,
+            &circuit.data.prover_only,
+            &circuit.data.common,
+            &circuit.async_hints,
+        )
+        .unwrap_err();
+        assert!(format!("{:#}", error).contains("the RPC is unavailable"));
+    }
 }
@@ -1,6 +1,6 @@
 use core::fmt::Debug;
 
-use anyhow::Result;
+use anyhow::{anyhow, Context, Result};
 use log::trace;
 use plonky2::iop::generator::{GeneratedValues, WitnessGenerator};
 use plonky2::iop::target::Target;
@@ -36,11 +36,13 @@ pub trait AsyncHintRunner<L: PlonkParameters<D>, const D: usize>:
 {
     fn watch_list(&self) -> &[Variable];
 
+    /// Runs the hint, returning an error if the hint could not be sent to the handler or if its
+    /// output does not match the output variables.
     fn run(
         &mut self,
         witness: &PartitionWitness<L::Field>,
         out_buffer: &mut GeneratedValues<L::Field>,
-    ) -> HintPoll;
+    ) -> Result<HintPoll>;
 }
 
 #[derive(Debug)]
@@ -172,12 +174,12 @@ impl<L: PlonkParameters<D>, H: AsyncHint<L, D>, const D: usize> AsyncHintRunner<
         &mut self,
         witness: &PartitionWitness<L::Field>,
         out_buffer: &mut GeneratedValues<L::Field>,
-    ) -> HintPoll {
+    ) -> Result<HintPoll> {
         match self.state {
             HintPoll::InputPending => {
                 // Check if all input variables are set, otherwise return.
                 if !self.watch_list().iter().all(|v| witness.contains(v.0)) {
-                    return HintPoll::InputPending;
+                    return Ok(HintPoll::InputPending);
                 }
                 // Send the input to the hint.
                 trace!("Async Hint {:?} : Sending input to hint", H::id());
@@ -190,11 +192,12 @@ impl<L: PlonkParameters<D>, H: AsyncHint<L, D>, const D: usize> AsyncHintRunner<
 
                 let input_stream = ValueStream::<L, D>::from_values(input_values);
 
-                self.send(input_stream).unwrap();
+                self.send(input_stream)
+                    .with_context(|| format!("Async hint {} could not be sent", H::id()))?;
 
                 // Update and return the state.
                 self.state = HintPoll::Pending;
-                HintPoll::Pending
+                Ok(HintPoll::Pending)
             }
             HintPoll::Pending => {
                 // Check the hint channel for the output. If not ready, return `HintPoll::Pending`.
@@ -202,16 +205,24 @@ impl<L: PlonkParameters<D>, H: AsyncHint<L, D>, const D: usize> AsyncHintRunner<
                     trace!("Async Hint {:?} : recieved output from hint", H::id());
                     let output_values = output_stream.read_all();
                     let output_vars = self.output_stream.real_all();
-                    assert_eq!(output_values.len(), output_vars.len());
+                    if output_values.len() != output_vars.len() {
+                        return Err(anyhow!(
+                            "Async hint {} returned {} values for {} output variables",
+                            H::id(),
+                            output_values.len(),
+                            output_vars.len()
+                        ));
+                    }
 
                     for (var, val) in output_vars.iter().zip(output_values) {
                         var.set(out_buffer, *val)
                     }
-                    return HintPoll::Ready;
+                    self.state = HintPoll::Ready;
+                    return Ok(HintPoll::Ready);
                 }
-                HintPoll::Pending
+                Ok(HintPoll::Pending)
             }
-            HintPoll::Ready => HintPoll::Ready,
+            HintPoll::Ready => Ok(HintPoll::Ready),
         }
     }
 }
@@ -3,26 +3,30 @@ use tokio::sync::mpsc::UnboundedReceiver;
 use tokio::task::JoinSet;
 
 use super::channel::HintInMessage;
+use super::tape::HintTape;
 use crate::prelude::PlonkParameters;
 
 /// A handler for asynchronous hints.
 ///
 /// This handler is responsible for receiving hint requests, computing the hint, and sending the
-/// result back to the prover.
+/// result back to the prover. If the handler has a tape, the hints are recorded to or replayed
+/// from the tape.
 #[derive(Debug)]
 pub struct HintHandler<L: PlonkParameters<D>, const D: usize> {
     rx: UnboundedReceiver<HintInMessage<L, D>>,
+    tape: Option<HintTape>,
 }
 
 impl<L: PlonkParameters<D>, const D: usize> HintHandler<L, D> {
-    pub fn new(rx: UnboundedReceiver<HintInMessage<L, D>>) -> Self {
-        Self { rx }
+    pub fn new(rx: UnboundedReceiver<HintInMessage<L, D>>, tape: Option<HintTape>) -> Self {
+        Self { rx, tape }
     }
 
     /// Run the handler.
     ///
     /// The handler will wait for requests and spawns a new task for each request. Awaiting this
-    /// this method will return `Ok(())` when all tasks have finished, or `Err` if any task fails.
+    /// this method will return `Ok(())` when all tasks have finished, or `Err` if any task fails,
+    /// either with an error of its hint or with a panic.
     pub async fn run(&mut self) -> Result<()> {
         let mut set = JoinSet::new();
 
@@ -30,10 +34,15 @@ impl<L: PlonkParameters<D>, const D: usize> HintHandler<L, D> {
             tokio::select! {
                 Some(message) = self.rx.recv() => {
                     let HintInMessage { hint, tx, inputs } = message;
+                    let tape = self.tape.clone();
 
                     set.spawn(async move {
-                        let outputs = hint.hint_fn(inputs).await;
-                        tx.send(outputs)
+                        let outputs = match tape {
+                            Some(tape) => tape.run(hint.as_ref(), inputs).await?,
+                            None => hint.try_hint_fn(inputs).await?,
+                        };
+                        tx.send(outputs)?;
+                        Ok::<_, anyhow::Error>(())
                     });
                 }
                 Some(result) = set.join_next() => {
@@ -1,5 +1,6 @@
 use core::fmt::Debug;
 
+use anyhow::{Context, Result};
 use async_trait::async_trait;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
@@ -44,15 +45,21 @@ pub trait AsyncHint<L: PlonkParameters<D>, const D: usize>:
     'static + Debug + Clone + Send + Sync + Serialize + DeserializeOwned
 {
     /// The hint function.
+    ///
+    /// Only one of `hint` or `hint_fn` needs to be implemented, as each calls the other by default.
     async fn hint(
         &self,
         input_stream: &mut ValueStream<L, D>,
         output_stream: &mut ValueStream<L, D>,
-    );
+    ) {
+        let input_values = input_stream.read_all().to_vec();
+        let mut outputs = self.hint_fn(ValueStream::from_values(input_values)).await;
+        output_stream.write_slice(outputs.read_all());
+    }
 
     /// A version of the hint function that owns the input stream and returns the output stream.
     ///
-    /// Only one of `hint` or `hint_fn` needs to be implemented. By default, `hint_fn` calls `hint`.
+    /// Only one of `hint` or `hint_fn` needs to be implemented, as each calls the other by default.
     async fn hint_fn(&self, input_stream: ValueStream<L, D>) -> ValueStream<L, D> {
         let mut output_stream = ValueStream::new();
         self.hint(&mut input_stream.clone(), &mut output_stream)
@@ -60,6 +67,15 @@ pub trait AsyncHint<L: PlonkParameters<D>, const D: usize>:
         output_stream
     }
 
+    /// A fallible version of `hint_fn`.
+    ///
+    /// Hints that can fail, such as those that fetch data from an RPC, should implement this
+    /// method so that the error is returned by witness generation instead of panicking. By
+    /// default, `try_hint_fn` calls `hint_fn`.
+    async fn try_hint_fn(&self, input_stream: ValueStream<L, D>) -> Result<ValueStream<L, D>> {
+        Ok(self.hint_fn(input_stream).await)
+    }
+
     /// a unique identifier for this hint.
     ///
     /// By default, this is the type name of the hint. This function should be overwriten in case
@@ -76,8 +92,14 @@ pub trait AsyncHint<L: PlonkParameters<D>, const D: usize>:
 pub(crate) trait AnyAsyncHint<L: PlonkParameters<D>, const D: usize>:
     'static + Debug + Send + Send + Sync
 {
+    /// The identifier of the hint, as returned by `AsyncHint::id`.
+    fn id(&self) -> String;
+
+    /// The serialization of the hint, which identifies it together with its id.
+    fn to_bytes(&self) -> Result<Vec<u8>>;
+
     #[allow(unused_variables)]
-    async fn hint_fn(&self, input_stream: ValueStream<L, D>) -> ValueStream<L, D> {
+    async fn try_hint_fn(&self, input_stream: ValueStream<L, D>) -> Result<ValueStream<L, D>> {
         unimplemented!("Implement this method")
     }
 }
@@ -88,8 +110,20 @@ pub struct AnyHint<H>(pub H);
 
 #[async_trait]
 impl<L: PlonkParameters<D>, H: AsyncHint<L, D>, const D: usize> AnyAsyncHint<L, D> for AnyHint<H> {
-    async fn hint_fn(&self, input_stream: ValueStream<L, D>) -> ValueStream<L, D> {
-        self.0.hint_fn(input_stream).await
+    fn id(&self) -> String {
+        H::id()
+    }
+
+    fn to_bytes(&self) -> Result<Vec<u8>> {
+        bincode::serialize(&self.0)
+            .with_context(|| format!("Async hint {} could not be serialized", H::id()))
+    }
+
+    async fn try_hint_fn(&self, input_stream: ValueStream<L, D>) -> Result<ValueStream<L, D>> {
+        self.0
+            .try_hint_fn(input_stream)
+            .await
+            .with_context(|| format!("Async hint {} failed", H::id()))
     }
 }
 
@@ -3,4 +3,6 @@ pub mod channel;
 pub mod generator;
 pub mod handler;
 pub mod hint;
+pub mod policy;
 pub mod serializer;
+pub mod tape;
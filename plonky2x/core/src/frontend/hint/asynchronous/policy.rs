@@ -0,0 +1,184 @@
+use core::future::Future;
+use core::time::Duration;
+use std::sync::Mutex;
+
+use anyhow::{anyhow, Result};
+use lazy_static::lazy_static;
+use log::warn;
+use serde::{Deserialize, Serialize};
+use tokio::time::Instant;
+
+lazy_static! {
+    /// The global rate limiter of the requests of the hints.
+    static ref RATE_LIMITER: Mutex<RateLimiter> = Mutex::new(RateLimiter::default());
+}
+
+/// Spaces the requests of all the hints of the process evenly, so that there are at most
+/// `requests_per_second` requests per second.
+#[derive(Debug, Default)]
+struct RateLimiter {
+    interval: Option<Duration>,
+    next: Option<Instant>,
+}
+
+impl RateLimiter {
+    /// Reserves the next slot, and returns the time to wait before it.
+    fn reserve(&mut self) -> Option<Duration> {
+        let interval = self.interval?;
+        let now = Instant::now();
+        let slot = self.next.map_or(now, |next| next.max(now));
+        self.next = Some(slot + interval);
+        Some(slot - now)
+    }
+}
+
+/// Limits the requests of the hints run with a `HintPolicy` to `requests_per_second` requests per
+/// second across the whole process, or removes the limit if it is `None`.
+pub fn set_hint_rate_limit(requests_per_second: Option<u32>) {
+    let mut limiter = RATE_LIMITER.lock().unwrap();
+    limiter.interval = requests_per_second.map(|rps| Duration::from_secs(1) / rps.max(1));
+    limiter.next = None;
+}
+
+/// The retry, timeout, and backoff policy of a network-backed hint.
+///
+/// Each attempt is subject to the global rate limit set with `set_hint_rate_limit` and is timed
+/// out after `timeout`. A failed attempt is retried up to `max_retries` times, waiting
+/// `initial_backoff` before the first retry and doubling the wait up to `max_backoff`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HintPolicy {
+    pub max_retries: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    pub timeout: Duration,
+}
+
+impl Default for HintPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(10),
+            timeout: Duration::from_secs(60),
+        }
+    }
+}
+
+impl HintPolicy {
+    /// A policy which makes a single attempt.
+    pub fn no_retries() -> Self {
+        Self {
+            max_retries: 0,
+            ..Self::default()
+        }
+    }
+
+    pub fn with_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    pub fn with_backoff(mut self, initial_backoff: Duration, max_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Runs the request `f`, named `name` in the logs and errors, according to the policy.
+    pub async fn run<T, F, Fut>(&self, name: &str, mut f: F) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let mut backoff = self.initial_backoff;
+        let mut attempt = 0;
+        loop {
+            let wait = RATE_LIMITER.lock().unwrap().reserve();
+            if let Some(wait) = wait {
+                tokio::time::sleep(wait).await;
+            }
+
+            let result = match tokio::time::timeout(self.timeout, f()).await {
+                Ok(result) => result,
+                Err(_) => Err(anyhow!("{} timed out after {:?}", name, self.timeout)),
+            };
+            match result {
+                Ok(value) => return Ok(value),
+                Err(e) if attempt < self.max_retries => {
+                    attempt += 1;
+                    warn!(
+                        "{} failed, retrying in {:?} ({}/{}): {:#}",
+                        name, backoff, attempt, self.max_retries, e
+                    );
+                    tokio::time::sleep(backoff).await;
+                    backoff = (backoff * 2).min(self.max_backoff);
+                }
+                Err(e) => {
+                    return Err(e.context(format!(
+                        "{} failed after {} attempts",
+                        name,
+                        attempt + 1
+                    )))
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::sync::atomic::{AtomicU32, Ordering};
+
+    use anyhow::bail;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_hint_policy_retries() {
+        let policy = HintPolicy::default()
+            .with_retries(2)
+            .with_backoff(Duration::from_millis(1), Duration::from_millis(2));
+
+        // The request succeeds on the third attempt.
+        let attempts = &AtomicU32::new(0);
+        let value = policy
+            .run("flaky", || async move {
+                if attempts.fetch_add(1, Ordering::SeqCst) < 2 {
+                    bail!("unavailable");
+                }
+                Ok(7)
+            })
+            .await
+            .unwrap();
+        assert_eq!(value, 7);
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+
+        // The request fails after the retries are exhausted.
+        let attempts = &AtomicU32::new(0);
+        let error = policy
+            .run("broken", || async move {
+                attempts.fetch_add(1, Ordering::SeqCst);
+                Err::<(), _>(anyhow!("unavailable"))
+            })
+            .await
+            .unwrap_err();
+        assert!(format!("{:#}", error).contains("broken failed after 3 attempts"));
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+
+        // A request that does not complete in time is timed out.
+        let error = HintPolicy::no_retries()
+            .with_timeout(Duration::from_millis(1))
+            .run("slow", || async {
+                tokio::time::sleep(Duration::from_secs(10)).await;
+                Ok(())
+            })
+            .await
+            .unwrap_err();
+        assert!(format!("{:#}", error).contains("slow timed out"));
+    }
+}
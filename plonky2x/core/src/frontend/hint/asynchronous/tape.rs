@@ -0,0 +1,237 @@
+use alloc::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use anyhow::{Context, Result};
+use plonky2::field::types::{Field, PrimeField64};
+use serde::{Deserialize, Serialize};
+
+use super::hint::AnyAsyncHint;
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::vars::ValueStream;
+
+/// Whether a tape records the hints run during witness generation or replays them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HintTapeMode {
+    /// The hints are run and their inputs and outputs are recorded.
+    Record,
+    /// The hints are not run, and their outputs are read from the records.
+    Replay,
+}
+
+/// The inputs and outputs of a run of an asynchronous hint.
+///
+/// A hint is identified by its id and its serialization, so that hints of the same type with
+/// different parameters are recorded separately.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HintRecord {
+    pub id: String,
+    pub hint: Vec<u8>,
+    pub inputs: Vec<u64>,
+    pub outputs: Vec<u64>,
+}
+
+type HintKey = (String, Vec<u8>, Vec<u64>);
+
+/// A tape of the asynchronous hints of witness generation, which records the hints of a run so
+/// that they can be replayed later, for instance to reproduce a failure of the prover or to prove
+/// without network access.
+///
+/// Only asynchronous hints are recorded. The Ethereum and beacon gadgets of the builder all fetch
+/// their data through `async_hint`, wrapping their synchronous hints in `Async`, so a tape covers
+/// every RPC request they make. Synchronous hints and generators are run again on replay, which
+/// includes the deprecated network generators such as `BeaconBalancesGenerator` and
+/// `EthBlockGenerator` when they are added to a circuit by hand.
+#[derive(Debug, Clone)]
+pub struct HintTape {
+    mode: HintTapeMode,
+    records: Arc<Mutex<BTreeMap<HintKey, Vec<u64>>>>,
+}
+
+impl HintTape {
+    /// Creates an empty tape which records the hints it runs.
+    pub fn record() -> Self {
+        Self {
+            mode: HintTapeMode::Record,
+            records: Arc::new(Mutex::new(BTreeMap::new())),
+        }
+    }
+
+    /// Creates a tape which replays `records`.
+    pub fn replay(records: Vec<HintRecord>) -> Self {
+        let records = records
+            .into_iter()
+            .map(|record| ((record.id, record.hint, record.inputs), record.outputs))
+            .collect();
+        Self {
+            mode: HintTapeMode::Replay,
+            records: Arc::new(Mutex::new(records)),
+        }
+    }
+
+    /// Loads a tape saved with `save`, which replays its records.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let file = File::open(path)
+            .with_context(|| format!("failed to open hint tape {}", path.display()))?;
+        let records: Vec<HintRecord> = serde_json::from_reader(BufReader::new(file))
+            .with_context(|| format!("failed to parse hint tape {}", path.display()))?;
+        Ok(Self::replay(records))
+    }
+
+    /// Saves the records of the tape as JSON, sorted by hint and inputs.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        let file = File::create(path)
+            .with_context(|| format!("failed to create hint tape {}", path.display()))?;
+        serde_json::to_writer(BufWriter::new(file), &self.records())?;
+        Ok(())
+    }
+
+    pub fn mode(&self) -> HintTapeMode {
+        self.mode
+    }
+
+    /// Returns the records of the tape, sorted by hint and inputs.
+    pub fn records(&self) -> Vec<HintRecord> {
+        self.records
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|((id, hint, inputs), outputs)| HintRecord {
+                id: id.clone(),
+                hint: hint.clone(),
+                inputs: inputs.clone(),
+                outputs: outputs.clone(),
+            })
+            .collect()
+    }
+
+    /// Runs `hint` on `inputs`, or replays its outputs.
+    pub(crate) async fn run<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        hint: &dyn AnyAsyncHint<L, D>,
+        inputs: ValueStream<L, D>,
+    ) -> Result<ValueStream<L, D>> {
+        let key = (hint.id(), hint.to_bytes()?, to_u64s(&mut inputs.clone()));
+        match self.mode {
+            HintTapeMode::Record => {
+                let outputs = hint.try_hint_fn(inputs).await?;
+                self.records
+                    .lock()
+                    .unwrap()
+                    .insert(key, to_u64s(&mut outputs.clone()));
+                Ok(outputs)
+            }
+            HintTapeMode::Replay => {
+                let outputs = self
+                    .records
+                    .lock()
+                    .unwrap()
+                    .get(&key)
+                    .cloned()
+                    .with_context(|| {
+                        format!("Async hint {} was not recorded with these inputs", key.0)
+                    })?;
+                Ok(ValueStream::from_values(
+                    outputs
+                        .into_iter()
+                        .map(L::Field::from_canonical_u64)
+                        .collect(),
+                ))
+            }
+        }
+    }
+}
+
+fn to_u64s<L: PlonkParameters<D>, const D: usize>(stream: &mut ValueStream<L, D>) -> Vec<u64> {
+    stream
+        .read_all()
+        .iter()
+        .map(|value| value.to_canonical_u64())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use core::sync::atomic::{AtomicUsize, Ordering};
+    use std::env;
+
+    use async_trait::async_trait;
+    use plonky2::iop::witness::PartialWitness;
+
+    use super::*;
+    use crate::backend::circuit::generate_witness_with_tape;
+    use crate::frontend::hint::asynchronous::hint::AsyncHint;
+    use crate::prelude::{ByteVariable, DefaultBuilder, VariableStream};
+
+    static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    struct CountingAddOneHint;
+
+    #[async_trait]
+    impl<L: PlonkParameters<D>, const D: usize> AsyncHint<L, D> for CountingAddOneHint {
+        async fn hint(
+            &self,
+            input_stream: &mut ValueStream<L, D>,
+            output_stream: &mut ValueStream<L, D>,
+        ) {
+            CALLS.fetch_add(1, Ordering::SeqCst);
+            let byte = input_stream.read_value::<ByteVariable>();
+            output_stream.write_value::<ByteVariable>(byte + 1);
+        }
+    }
+
+    #[test]
+    fn test_hint_tape_record_and_replay() {
+        let mut builder = DefaultBuilder::new();
+        let a = builder.read::<ByteVariable>();
+        let mut input_stream = VariableStream::new();
+        input_stream.write(&a);
+        let output_stream = builder.async_hint(input_stream, CountingAddOneHint);
+        let b = output_stream.read::<ByteVariable>(&mut builder);
+        builder.write(b);
+        let circuit = builder.mock_build();
+
+        let mut input = circuit.input();
+        input.write::<ByteVariable>(5u8);
+
+        // Record the hint, which is run once.
+        let tape = HintTape::record();
+        let (_witness, mut output) = circuit.mock_prove_with_tape(&input, &tape);
+        assert_eq!(output.read::<ByteVariable>(), 6u8);
+        assert_eq!(CALLS.load(Ordering::SeqCst), 1);
+        assert_eq!(tape.records().len(), 1);
+
+        let path = env::temp_dir().join("plonky2x_test_hint_tape_record_and_replay.json");
+        tape.save(&path).unwrap();
+
+        // Replay the hint from the file, without running it.
+        let tape = HintTape::load(&path).unwrap();
+        assert_eq!(tape.mode(), HintTapeMode::Replay);
+        let (_witness, mut output) = circuit.mock_prove_with_tape(&input, &tape);
+        assert_eq!(output.read::<ByteVariable>(), 6u8);
+        assert_eq!(CALLS.load(Ordering::SeqCst), 1);
+
+        // Inputs that were not recorded cannot be replayed.
+        let mut input = circuit.input();
+        input.write::<ByteVariable>(7u8);
+        let mut pw = PartialWitness::new();
+        circuit.io.set_witness(&mut pw, &input);
+        let error = generate_witness_with_tape(
+            pw,
+            &circuit.data.prover_only,
+            &circuit.data.common,
+            &circuit.async_hints,
+            Some(tape),
+        )
+        .unwrap_err();
+        assert!(format!("{:#}", error).contains("was not recorded"));
+        assert_eq!(CALLS.load(Ordering::SeqCst), 1);
+
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -64,10 +64,8 @@ impl<L: PlonkParameters<D>, const D: usize, H: Hint<L, D>> WitnessGenerator<L::F
             .iter()
             .map(|v| v.get(witness))
             .collect::<Vec<_>>();
-        let mut input_stream = ValueStream::from_values(input_values);
-        let mut output_stream = ValueStream::new();
-
-        self.hint.hint(&mut input_stream, &mut output_stream);
+        let input_stream = ValueStream::from_values(input_values);
+        let mut output_stream = self.hint.hint_fn(input_stream);
 
         let output_values = output_stream.read_all();
         let output_vars = self.output_stream.real_all();
@@ -40,7 +40,22 @@ pub trait Hint<L: PlonkParameters<D>, const D: usize>:
     'static + Debug + Clone + Send + Sync + serde::Serialize + DeserializeOwned
 {
     /// the hint function.
-    fn hint(&self, input_stream: &mut ValueStream<L, D>, output_stream: &mut ValueStream<L, D>);
+    ///
+    /// Only one of `hint` or `hint_fn` needs to be implemented, as each calls the other by default.
+    fn hint(&self, input_stream: &mut ValueStream<L, D>, output_stream: &mut ValueStream<L, D>) {
+        let input_values = input_stream.read_all().to_vec();
+        let mut outputs = self.hint_fn(ValueStream::from_values(input_values));
+        output_stream.write_slice(outputs.read_all());
+    }
+
+    /// A version of the hint function that owns the input values and returns the output values.
+    ///
+    /// Only one of `hint` or `hint_fn` needs to be implemented, as each calls the other by default.
+    fn hint_fn(&self, mut input_stream: ValueStream<L, D>) -> ValueStream<L, D> {
+        let mut output_stream = ValueStream::new();
+        self.hint(&mut input_stream, &mut output_stream);
+        output_stream
+    }
 
     /// a unique identifier for this hint.
     ///
@@ -92,6 +107,44 @@ mod tests {
         }
     }
 
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    struct Swap;
+
+    impl<L: PlonkParameters<D>, const D: usize> Hint<L, D> for Swap {
+        fn hint_fn(&self, mut input_stream: ValueStream<L, D>) -> ValueStream<L, D> {
+            let a = input_stream.read_value::<ByteVariable>();
+            let b = input_stream.read_value::<ByteVariable>();
+            let mut output_stream = ValueStream::new();
+            output_stream.write_value::<ByteVariable>(b);
+            output_stream.write_value::<ByteVariable>(a);
+            output_stream
+        }
+    }
+
+    #[test]
+    fn test_hint_fn() {
+        let mut builder = DefaultBuilder::new();
+        let a = builder.read::<ByteVariable>();
+        let b = builder.read::<ByteVariable>();
+
+        let mut input_stream = VariableStream::new();
+        input_stream.write(&a);
+        input_stream.write(&b);
+        let output_stream = builder.hint(input_stream, Swap);
+        let c = output_stream.read::<ByteVariable>(&mut builder);
+        let d = output_stream.read::<ByteVariable>(&mut builder);
+        builder.write(c);
+        builder.write(d);
+        let circuit = builder.mock_build();
+
+        let mut input = circuit.input();
+        input.write::<ByteVariable>(5u8);
+        input.write::<ByteVariable>(9u8);
+        let (_witness, mut output) = circuit.mock_prove(&input);
+        assert_eq!(output.read::<ByteVariable>(), 9u8);
+        assert_eq!(output.read::<ByteVariable>(), 5u8);
+    }
+
     #[test]
     fn test_hint() {
         let mut builder = DefaultBuilder::new();
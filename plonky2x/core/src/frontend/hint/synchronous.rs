@@ -24,24 +24,11 @@ pub struct Async<H>(pub H);
 
 #[async_trait]
 impl<L: PlonkParameters<D>, H: Hint<L, D>, const D: usize> AsyncHint<L, D> for Async<H> {
-    async fn hint(
-        &self,
-        _input_stream: &mut ValueStream<L, D>,
-        _output_stream: &mut ValueStream<L, D>,
-    ) {
-        unimplemented!("Sync hints implement owned function hint_fn instead of hint")
-    }
-
-    /// A version of the hint function that owns the input stream and returns the output stream.
-    ///
-    /// Only one of `hint` or `hint_fn` needs to be implemented. By default, `hint_fn` calls `hint`.
     async fn hint_fn(&self, input_stream: ValueStream<L, D>) -> ValueStream<L, D> {
         let (tx, rx) = oneshot::channel();
         let hint = self.0.clone();
         rayon::spawn(move || {
-            let mut input_stream = input_stream;
-            let mut output_stream = ValueStream::new();
-            hint.hint(&mut input_stream, &mut output_stream);
+            let output_stream = hint.hint_fn(input_stream);
             tx.send(output_stream).unwrap();
         });
 
@@ -199,7 +199,9 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         // Save map circuit and map circuit input target to build folder.
         let map_circuit_id = map_circuit.id();
         let map_circuit_path = format!("./build/{}.circuit", map_circuit_id);
-        map_circuit.save(&map_circuit_path, &gate_serializer, &generator_serializer);
+        map_circuit
+            .save(&map_circuit_path, &gate_serializer, &generator_serializer)
+            .unwrap();
 
         // For each reduce layer, we build a reduce circuit which reduces two input proofs
         // to an output O.
@@ -215,11 +217,13 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
                 self.build_reduce::<Ctx, Output, ReduceFn>(child_circuit, &reduce_fn);
             let reduce_circuit_id = reduce_circuit.id();
             let reduce_circuit_path = format!("./build/{}.circuit", reduce_circuit_id);
-            reduce_circuit.save(
-                &reduce_circuit_path,
-                &gate_serializer,
-                &generator_serializer,
-            );
+            reduce_circuit
+                .save(
+                    &reduce_circuit_path,
+                    &gate_serializer,
+                    &generator_serializer,
+                )
+                .unwrap();
             reduce_circuits.push(reduce_circuit);
             debug!("succesfully built reduce circuit: id={}", reduce_circuit_id);
         }
@@ -296,7 +300,9 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         // Save map circuit and map circuit input target to build folder.
         let map_circuit_id = map_circuit.id();
         let map_circuit_path = format!("./build/{}.circuit", map_circuit_id);
-        map_circuit.save(&map_circuit_path, &gate_serializer, &generator_serializer);
+        map_circuit
+            .save(&map_circuit_path, &gate_serializer, &generator_serializer)
+            .unwrap();
 
         // For each reduce layer, we build a reduce circuit which reduces two input proofs
         // to an output O.
@@ -312,11 +318,13 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
                 self.build_reduce::<Ctx, Output, ReduceFn>(child_circuit, &reduce_fn);
             let reduce_circuit_id = reduce_circuit.id();
             let reduce_circuit_path = format!("./build/{}.circuit", reduce_circuit_id);
-            reduce_circuit.save(
-                &reduce_circuit_path,
-                &gate_serializer,
-                &generator_serializer,
-            );
+            reduce_circuit
+                .save(
+                    &reduce_circuit_path,
+                    &gate_serializer,
+                    &generator_serializer,
+                )
+                .unwrap();
             reduce_circuits.push(reduce_circuit);
             debug!("succesfully built reduce circuit: id={}", reduce_circuit_id);
         }
@@ -0,0 +1,249 @@
+use ethers::types::H256;
+use plonky2::plonk::config::{AlgebraicHasher, GenericConfig};
+
+use super::sparse::empty_subtree_hashes;
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::builder::bits::Endianness;
+use crate::frontend::hash::poseidon::poseidon256::PoseidonHashOutVariable;
+use crate::frontend::vars::Bytes32Variable;
+use crate::prelude::{ArrayVariable, CircuitBuilder, CircuitVariable, Variable};
+use crate::utils::hash::sha256;
+
+/// An append-only Merkle tree of depth `DEPTH`, in the style of the Eth2 deposit contract.
+///
+/// Leaves are inserted from left to right, so every node on the path of the next free index has
+/// an empty right sibling, and its left siblings are the "filled subtrees" kept by the contract.
+/// Appending a leaf is verified by recomputing the old root with an empty leaf at the insertion
+/// index and the new root with the new leaf, from the same siblings.
+pub trait IncrementalMerkleTree {
+    /// Asserts that `old_root` is the root of a tree of Poseidon digests with `index` leaves, and
+    /// returns the root after appending `leaf` at `index`.
+    fn poseidon_merkle_append<const DEPTH: usize>(
+        &mut self,
+        old_root: &PoseidonHashOutVariable,
+        leaf: &PoseidonHashOutVariable,
+        index: Variable,
+        filled_subtrees: &ArrayVariable<PoseidonHashOutVariable, DEPTH>,
+    ) -> PoseidonHashOutVariable;
+
+    /// Asserts that `old_root` is the root of a sha256 tree with `index` leaves, and returns the
+    /// root after appending `leaf` at `index`. Empty leaves are zero, as in the deposit contract.
+    fn sha256_merkle_append<const DEPTH: usize>(
+        &mut self,
+        old_root: Bytes32Variable,
+        leaf: Bytes32Variable,
+        index: Variable,
+        filled_subtrees: &ArrayVariable<Bytes32Variable, DEPTH>,
+    ) -> Bytes32Variable;
+}
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Verifies an append against `old_root` given the roots of the empty subtrees, and returns
+    /// the new root. The index is constrained to be less than `2^depth`.
+    fn merkle_append<V, H>(
+        &mut self,
+        old_root: V,
+        leaf: V,
+        index: Variable,
+        filled_subtrees: &[V],
+        zero_hashes: &[V],
+        mut hash_pair: H,
+    ) -> V
+    where
+        V: CircuitVariable,
+        H: FnMut(&mut Self, V, V) -> V,
+    {
+        let index_bits = self.to_bits(&index, filled_subtrees.len(), Endianness::Little);
+        let mut old_node = zero_hashes[0].clone();
+        let mut new_node = leaf;
+        for ((is_right, filled), zero) in
+            index_bits.into_iter().zip(filled_subtrees).zip(zero_hashes)
+        {
+            // A set bit means that the node is a right child, whose left sibling is filled.
+            // Otherwise its right sibling is still empty.
+            let left = self.select(is_right, filled.clone(), old_node.clone());
+            let right = self.select(is_right, old_node, zero.clone());
+            old_node = hash_pair(self, left, right);
+            let left = self.select(is_right, filled.clone(), new_node.clone());
+            let right = self.select(is_right, new_node, zero.clone());
+            new_node = hash_pair(self, left, right);
+        }
+        self.assert_is_equal(old_node, old_root);
+        new_node
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize> IncrementalMerkleTree for CircuitBuilder<L, D>
+where
+    <<L as PlonkParameters<D>>::Config as GenericConfig<D>>::Hasher:
+        AlgebraicHasher<<L as PlonkParameters<D>>::Field>,
+{
+    fn poseidon_merkle_append<const DEPTH: usize>(
+        &mut self,
+        old_root: &PoseidonHashOutVariable,
+        leaf: &PoseidonHashOutVariable,
+        index: Variable,
+        filled_subtrees: &ArrayVariable<PoseidonHashOutVariable, DEPTH>,
+    ) -> PoseidonHashOutVariable {
+        let zero_hashes = empty_subtree_hashes::<
+            L::Field,
+            <<L as PlonkParameters<D>>::Config as GenericConfig<D>>::Hasher,
+        >(DEPTH)
+        .into_iter()
+        .map(|hash| self.constant::<PoseidonHashOutVariable>(hash))
+        .collect::<Vec<_>>();
+        self.merkle_append(
+            old_root.clone(),
+            leaf.clone(),
+            index,
+            filled_subtrees.as_slice(),
+            &zero_hashes,
+            |builder, left, right| builder.poseidon_hash_pair(left, right),
+        )
+    }
+
+    fn sha256_merkle_append<const DEPTH: usize>(
+        &mut self,
+        old_root: Bytes32Variable,
+        leaf: Bytes32Variable,
+        index: Variable,
+        filled_subtrees: &ArrayVariable<Bytes32Variable, DEPTH>,
+    ) -> Bytes32Variable {
+        let mut zero_hash = [0u8; 32];
+        let mut zero_hashes = Vec::with_capacity(DEPTH);
+        for _ in 0..DEPTH {
+            zero_hashes.push(self.constant::<Bytes32Variable>(H256(zero_hash)));
+            zero_hash = sha256(&[zero_hash, zero_hash].concat());
+        }
+        self.merkle_append(
+            old_root,
+            leaf,
+            index,
+            filled_subtrees.as_slice(),
+            &zero_hashes,
+            |builder, left, right| builder.sha256_pair(left, right),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ethers::types::H256;
+    use plonky2::hash::hash_types::HashOut;
+    use plonky2::hash::poseidon::PoseidonHash;
+    use plonky2::plonk::config::Hasher;
+
+    use super::IncrementalMerkleTree;
+    use crate::frontend::hash::poseidon::poseidon256::PoseidonHashOutVariable;
+    use crate::prelude::*;
+    use crate::utils::hash::sha256;
+
+    type F = GoldilocksField;
+    const DEPTH: usize = 3;
+
+    /// Returns the root of the tree with the given leaves, padded with `zero`, and the left
+    /// siblings of the path of `index`.
+    fn root_and_filled_subtrees<T: Copy>(
+        leaves: &[T],
+        index: usize,
+        zero: T,
+        hash_pair: impl Fn(T, T) -> T,
+    ) -> (T, Vec<T>) {
+        let mut layer = leaves.to_vec();
+        layer.resize(1 << DEPTH, zero);
+        let mut filled = Vec::new();
+        for level in 0..DEPTH {
+            let position = index >> level;
+            filled.push(if position & 1 == 1 {
+                layer[position - 1]
+            } else {
+                zero
+            });
+            layer = layer
+                .chunks_exact(2)
+                .map(|pair| hash_pair(pair[0], pair[1]))
+                .collect();
+        }
+        (layer[0], filled)
+    }
+
+    fn poseidon_pair(left: HashOut<F>, right: HashOut<F>) -> HashOut<F> {
+        PoseidonHash::hash_no_pad(&[left.elements, right.elements].concat())
+    }
+
+    #[test]
+    fn test_poseidon_merkle_append() {
+        let mut builder = DefaultBuilder::new();
+
+        // Appends three leaves in a row, chaining the roots.
+        let mut root = builder.read::<PoseidonHashOutVariable>();
+        for _ in 0..3 {
+            let leaf = builder.read::<PoseidonHashOutVariable>();
+            let index = builder.read::<Variable>();
+            let filled_subtrees = builder.read::<ArrayVariable<PoseidonHashOutVariable, DEPTH>>();
+            root = builder.poseidon_merkle_append(&root, &leaf, index, &filled_subtrees);
+        }
+        builder.write(root);
+
+        let circuit = builder.build();
+
+        let start = 2;
+        let leaves = (0..5u64)
+            .map(|i| PoseidonHash::hash_no_pad(&[F::from_canonical_u64(i + 1)]))
+            .collect::<Vec<_>>();
+        let mut input = circuit.input();
+        let (old_root, _) =
+            root_and_filled_subtrees(&leaves[..start], start, HashOut::ZERO, poseidon_pair);
+        input.write::<PoseidonHashOutVariable>(old_root);
+        for index in start..start + 3 {
+            let (_, filled) =
+                root_and_filled_subtrees(&leaves[..index], index, HashOut::ZERO, poseidon_pair);
+            input.write::<PoseidonHashOutVariable>(leaves[index]);
+            input.write::<Variable>(F::from_canonical_usize(index));
+            input.write::<ArrayVariable<PoseidonHashOutVariable, DEPTH>>(filled);
+        }
+
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        let (expected_root, _) =
+            root_and_filled_subtrees(&leaves, leaves.len(), HashOut::ZERO, poseidon_pair);
+        assert_eq!(output.read::<PoseidonHashOutVariable>(), expected_root);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_sha256_merkle_append() {
+        let mut builder = DefaultBuilder::new();
+
+        let old_root = builder.read::<Bytes32Variable>();
+        let leaf = builder.read::<Bytes32Variable>();
+        let index = builder.read::<Variable>();
+        let filled_subtrees = builder.read::<ArrayVariable<Bytes32Variable, DEPTH>>();
+        let new_root = builder.sha256_merkle_append(old_root, leaf, index, &filled_subtrees);
+        builder.write(new_root);
+
+        let circuit = builder.build();
+
+        let sha256_pair =
+            |left: [u8; 32], right: [u8; 32]| -> [u8; 32] { sha256(&[left, right].concat()) };
+        let leaves = (0..6u8).map(|i| [i + 1; 32]).collect::<Vec<_>>();
+        let index = 5;
+        let (old_root, filled) =
+            root_and_filled_subtrees(&leaves[..index], index, [0u8; 32], sha256_pair);
+        let (expected_root, _) =
+            root_and_filled_subtrees(&leaves, leaves.len(), [0u8; 32], sha256_pair);
+
+        let mut input = circuit.input();
+        input.write::<Bytes32Variable>(H256(old_root));
+        input.write::<Bytes32Variable>(H256(leaves[index]));
+        input.write::<Variable>(F::from_canonical_usize(index));
+        input
+            .write::<ArrayVariable<Bytes32Variable, DEPTH>>(filled.into_iter().map(H256).collect());
+
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        assert_eq!(output.read::<Bytes32Variable>(), H256(expected_root));
+    }
+}
@@ -1,4 +1,8 @@
+pub mod incremental;
+pub mod multiproof;
+pub mod poseidon;
 pub mod simple;
+pub mod sparse;
 pub mod tendermint;
 pub mod tree;
 pub mod utils;
@@ -0,0 +1,304 @@
+use alloc::collections::{BTreeMap, BTreeSet};
+
+use plonky2::plonk::config::{AlgebraicHasher, GenericConfig};
+
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::hash::poseidon::poseidon256::PoseidonHashOutVariable;
+use crate::frontend::vars::Bytes32Variable;
+use crate::prelude::{CircuitBuilder, CircuitVariable};
+
+/// Returns the generalized indices of the siblings on the path from `index` to the root.
+fn branch_indices(index: u64) -> Vec<u64> {
+    let mut indices = Vec::new();
+    let mut node = index;
+    while node > 1 {
+        indices.push(node ^ 1);
+        node /= 2;
+    }
+    indices
+}
+
+/// Returns the generalized indices of the nodes on the path from `index` to the root, excluding
+/// the root.
+fn path_indices(index: u64) -> Vec<u64> {
+    let mut indices = Vec::new();
+    let mut node = index;
+    while node > 1 {
+        indices.push(node);
+        node /= 2;
+    }
+    indices
+}
+
+/// Asserts that the generalized `indices` of a multiproof are valid, distinct, and that none of
+/// them is an ancestor of another. A leaf given for an ancestor would take the place of the node
+/// computed from the leaves below it, which would then never be checked.
+fn assert_valid_indices(indices: &[u64]) {
+    let set = indices.iter().copied().collect::<BTreeSet<_>>();
+    assert!(!set.contains(&0), "0 is not a generalized index");
+    assert_eq!(
+        set.len(),
+        indices.len(),
+        "multiproof indices must be distinct"
+    );
+    for index in indices {
+        let mut ancestor = index / 2;
+        while ancestor > 0 {
+            assert!(
+                !set.contains(&ancestor),
+                "multiproof index {} is an ancestor of index {}",
+                ancestor,
+                index
+            );
+            ancestor /= 2;
+        }
+    }
+}
+
+/// Returns the generalized indices of the helper nodes of a multiproof for the leaves at
+/// `indices`, in the order in which they are expected by the verification, which is decreasing.
+///
+/// These are the siblings of the paths from the leaves to the root that cannot be computed from
+/// the leaves themselves, as in the SSZ multiproof specification.
+pub fn multiproof_helper_indices(indices: &[u64]) -> Vec<u64> {
+    assert_valid_indices(indices);
+    let mut helpers = BTreeSet::new();
+    let mut paths = BTreeSet::new();
+    for index in indices {
+        helpers.extend(branch_indices(*index));
+        paths.extend(path_indices(*index));
+    }
+    helpers.difference(&paths).rev().copied().collect()
+}
+
+/// Verification of Merkle multiproofs, which prove many leaves of a binary tree with a shared set
+/// of helper nodes. Every inner node on the paths of the leaves is hashed exactly once, which is
+/// much cheaper than verifying an independent proof per leaf.
+///
+/// Leaves are identified by constant generalized indices: the root is `1` and the children of
+/// node `i` are `2i` and `2i + 1`. The indices must be distinct and none of them may be an
+/// ancestor of another. The helper nodes are given in the order returned by
+/// `multiproof_helper_indices`.
+pub trait MerkleMultiproof {
+    /// Asserts that the Poseidon leaves are at the generalized `indices` of the tree with the
+    /// given `root`.
+    fn verify_poseidon_multiproof(
+        &mut self,
+        root: &PoseidonHashOutVariable,
+        leaves: &[PoseidonHashOutVariable],
+        indices: &[u64],
+        helpers: &[PoseidonHashOutVariable],
+    );
+
+    /// Asserts that the sha256 leaves are at the generalized `indices` of the tree with the given
+    /// `root`.
+    fn verify_sha256_multiproof(
+        &mut self,
+        root: Bytes32Variable,
+        leaves: &[Bytes32Variable],
+        indices: &[u64],
+        helpers: &[Bytes32Variable],
+    );
+}
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Computes the root of a tree from the leaves at `indices` and the multiproof `helpers`.
+    fn multiproof_root<V, H>(
+        &mut self,
+        leaves: &[V],
+        indices: &[u64],
+        helpers: &[V],
+        mut hash_pair: H,
+    ) -> V
+    where
+        V: CircuitVariable,
+        H: FnMut(&mut Self, V, V) -> V,
+    {
+        assert_eq!(leaves.len(), indices.len(), "every leaf must have an index");
+        let helper_indices = multiproof_helper_indices(indices);
+        assert_eq!(
+            helpers.len(),
+            helper_indices.len(),
+            "expected {} helper nodes",
+            helper_indices.len()
+        );
+
+        let mut nodes = BTreeMap::new();
+        nodes.extend(indices.iter().copied().zip(leaves.iter().cloned()));
+        nodes.extend(helper_indices.into_iter().zip(helpers.iter().cloned()));
+
+        // Parents always have smaller indices than their children, so hashing the nodes in
+        // decreasing order of index computes every parent after both of its children.
+        let mut keys = nodes.keys().rev().copied().collect::<Vec<_>>();
+        let mut pos = 0;
+        while pos < keys.len() {
+            let k = keys[pos];
+            if k > 1 && nodes.contains_key(&(k ^ 1)) && !nodes.contains_key(&(k / 2)) {
+                let left = nodes[&(k & !1)].clone();
+                let right = nodes[&(k | 1)].clone();
+                let parent = hash_pair(self, left, right);
+                nodes.insert(k / 2, parent);
+                keys.push(k / 2);
+            }
+            pos += 1;
+        }
+        nodes
+            .remove(&1)
+            .expect("the multiproof does not reach the root")
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize> MerkleMultiproof for CircuitBuilder<L, D>
+where
+    <<L as PlonkParameters<D>>::Config as GenericConfig<D>>::Hasher:
+        AlgebraicHasher<<L as PlonkParameters<D>>::Field>,
+{
+    fn verify_poseidon_multiproof(
+        &mut self,
+        root: &PoseidonHashOutVariable,
+        leaves: &[PoseidonHashOutVariable],
+        indices: &[u64],
+        helpers: &[PoseidonHashOutVariable],
+    ) {
+        let computed_root = self.multiproof_root(leaves, indices, helpers, |builder, l, r| {
+            builder.poseidon_hash_pair(l, r)
+        });
+        self.assert_is_equal(computed_root, root.clone());
+    }
+
+    fn verify_sha256_multiproof(
+        &mut self,
+        root: Bytes32Variable,
+        leaves: &[Bytes32Variable],
+        indices: &[u64],
+        helpers: &[Bytes32Variable],
+    ) {
+        let computed_root = self.multiproof_root(leaves, indices, helpers, |builder, l, r| {
+            builder.sha256_pair(l, r)
+        });
+        self.assert_is_equal(computed_root, root);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ethers::types::H256;
+    use plonky2::hash::hash_types::HashOut;
+    use plonky2::hash::poseidon::PoseidonHash;
+    use plonky2::plonk::config::Hasher;
+
+    use super::{multiproof_helper_indices, MerkleMultiproof};
+    use crate::frontend::hash::poseidon::poseidon256::PoseidonHashOutVariable;
+    use crate::prelude::*;
+    use crate::utils::hash::sha256;
+
+    type F = GoldilocksField;
+
+    /// Returns the nodes of a full tree with the given leaves, indexed by generalized index.
+    fn tree_nodes<T: Copy>(leaves: &[T], hash_pair: impl Fn(T, T) -> T) -> Vec<T> {
+        let n = leaves.len();
+        let mut nodes = vec![leaves[0]; 2 * n];
+        nodes[n..].copy_from_slice(leaves);
+        for i in (1..n).rev() {
+            nodes[i] = hash_pair(nodes[2 * i], nodes[2 * i + 1]);
+        }
+        nodes
+    }
+
+    #[test]
+    fn test_multiproof_helper_indices() {
+        // The example of the SSZ specification, for a tree of depth 3.
+        assert_eq!(multiproof_helper_indices(&[9, 10, 14]), vec![15, 11, 8, 6]);
+        assert_eq!(multiproof_helper_indices(&[8, 9]), vec![5, 3]);
+    }
+
+    #[test]
+    fn test_poseidon_multiproof() {
+        let indices = [9u64, 10, 14];
+        let helper_indices = multiproof_helper_indices(&indices);
+
+        let mut builder = DefaultBuilder::new();
+        let root = builder.read::<PoseidonHashOutVariable>();
+        let leaves = builder.read::<ArrayVariable<PoseidonHashOutVariable, 3>>();
+        let helpers = builder.read::<ArrayVariable<PoseidonHashOutVariable, 4>>();
+        builder.verify_poseidon_multiproof(&root, leaves.as_slice(), &indices, helpers.as_slice());
+        let circuit = builder.build();
+
+        let leaves = (0..8u64)
+            .map(|i| PoseidonHash::hash_no_pad(&[F::from_canonical_u64(i)]))
+            .collect::<Vec<_>>();
+        let nodes = tree_nodes(&leaves, |left: HashOut<F>, right: HashOut<F>| {
+            PoseidonHash::hash_no_pad(&[left.elements, right.elements].concat())
+        });
+
+        let mut input = circuit.input();
+        input.write::<PoseidonHashOutVariable>(nodes[1]);
+        input.write::<ArrayVariable<PoseidonHashOutVariable, 3>>(
+            indices.iter().map(|i| nodes[*i as usize]).collect(),
+        );
+        input.write::<ArrayVariable<PoseidonHashOutVariable, 4>>(
+            helper_indices.iter().map(|i| nodes[*i as usize]).collect(),
+        );
+
+        let (proof, output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_sha256_multiproof() {
+        let indices = [4u64, 5];
+        let helper_indices = multiproof_helper_indices(&indices);
+
+        let mut builder = DefaultBuilder::new();
+        let root = builder.read::<Bytes32Variable>();
+        let leaves = builder.read::<ArrayVariable<Bytes32Variable, 2>>();
+        let helpers = builder.read::<ArrayVariable<Bytes32Variable, 1>>();
+        builder.verify_sha256_multiproof(root, leaves.as_slice(), &indices, helpers.as_slice());
+        let circuit = builder.build();
+
+        let leaves = (0..4u8).map(|i| [i; 32]).collect::<Vec<_>>();
+        let nodes = tree_nodes(&leaves, |left: [u8; 32], right: [u8; 32]| {
+            sha256(&[left, right].concat())
+        });
+
+        let mut input = circuit.input();
+        input.write::<Bytes32Variable>(H256(nodes[1]));
+        input.write::<ArrayVariable<Bytes32Variable, 2>>(
+            indices.iter().map(|i| H256(nodes[*i as usize])).collect(),
+        );
+        input.write::<ArrayVariable<Bytes32Variable, 1>>(
+            helper_indices
+                .iter()
+                .map(|i| H256(nodes[*i as usize]))
+                .collect(),
+        );
+
+        let (proof, output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+    }
+
+    fn build_poseidon_multiproof(indices: &[u64]) {
+        let mut builder = DefaultBuilder::new();
+        let root = builder.read::<PoseidonHashOutVariable>();
+        let leaves = (0..indices.len())
+            .map(|_| builder.read::<PoseidonHashOutVariable>())
+            .collect::<Vec<_>>();
+        let helpers = (0..multiproof_helper_indices(indices).len())
+            .map(|_| builder.read::<PoseidonHashOutVariable>())
+            .collect::<Vec<_>>();
+        builder.verify_poseidon_multiproof(&root, &leaves, indices, &helpers);
+    }
+
+    #[test]
+    #[should_panic(expected = "multiproof indices must be distinct")]
+    fn test_multiproof_rejects_duplicate_indices() {
+        build_poseidon_multiproof(&[9, 9, 14]);
+    }
+
+    #[test]
+    #[should_panic(expected = "multiproof index 2 is an ancestor of index 4")]
+    fn test_multiproof_rejects_nested_indices() {
+        build_poseidon_multiproof(&[2, 4]);
+    }
+}
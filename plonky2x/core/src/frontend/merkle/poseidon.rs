@@ -0,0 +1,153 @@
+use itertools::Itertools;
+use plonky2::plonk::config::{AlgebraicHasher, GenericConfig};
+
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::hash::poseidon::poseidon256::PoseidonHashOutVariable;
+use crate::prelude::{ArrayVariable, BoolVariable, CircuitBuilder, Variable};
+
+/// A binary Merkle tree whose nodes are hashed with Poseidon.
+///
+/// Leaves are Poseidon digests, typically from `poseidon_hash_variables` or
+/// `poseidon_hash_bytes`, and every inner node is the Poseidon hash of its two children.
+pub trait MerkleTreeGadget {
+    /// Computes the root of the tree with the given leaves, whose number must be a power of two.
+    fn poseidon_merkle_root<const NB_LEAVES: usize>(
+        &mut self,
+        leaves: &ArrayVariable<PoseidonHashOutVariable, NB_LEAVES>,
+    ) -> PoseidonHashOutVariable;
+
+    /// Computes the root of the tree from `leaf`, its `index` and the siblings on its path, from
+    /// the leaf level up. The index is constrained to be less than `2^DEPTH`.
+    fn poseidon_merkle_root_from_proof<const DEPTH: usize>(
+        &mut self,
+        leaf: &PoseidonHashOutVariable,
+        index: Variable,
+        siblings: &ArrayVariable<PoseidonHashOutVariable, DEPTH>,
+    ) -> PoseidonHashOutVariable;
+
+    /// Asserts that `leaf` is at `index` in the tree with the given `root`.
+    fn verify_poseidon_merkle_proof<const DEPTH: usize>(
+        &mut self,
+        leaf: &PoseidonHashOutVariable,
+        index: Variable,
+        siblings: &ArrayVariable<PoseidonHashOutVariable, DEPTH>,
+        root: &PoseidonHashOutVariable,
+    );
+}
+
+impl<L: PlonkParameters<D>, const D: usize> MerkleTreeGadget for CircuitBuilder<L, D>
+where
+    <<L as PlonkParameters<D>>::Config as GenericConfig<D>>::Hasher:
+        AlgebraicHasher<<L as PlonkParameters<D>>::Field>,
+{
+    fn poseidon_merkle_root<const NB_LEAVES: usize>(
+        &mut self,
+        leaves: &ArrayVariable<PoseidonHashOutVariable, NB_LEAVES>,
+    ) -> PoseidonHashOutVariable {
+        assert!(
+            NB_LEAVES.is_power_of_two(),
+            "the number of leaves must be a power of two"
+        );
+        let mut nodes = leaves.as_vec();
+        while nodes.len() > 1 {
+            nodes = nodes
+                .chunks_exact(2)
+                .map(|pair| self.poseidon_hash_pair(pair[0].clone(), pair[1].clone()))
+                .collect_vec();
+        }
+        nodes.pop().unwrap()
+    }
+
+    fn poseidon_merkle_root_from_proof<const DEPTH: usize>(
+        &mut self,
+        leaf: &PoseidonHashOutVariable,
+        index: Variable,
+        siblings: &ArrayVariable<PoseidonHashOutVariable, DEPTH>,
+    ) -> PoseidonHashOutVariable {
+        let index_bits = self.api.split_le(index.0, DEPTH);
+        let mut node = leaf.clone();
+        for (bit, sibling) in index_bits.into_iter().zip_eq(siblings.as_slice()) {
+            // A set bit means that the current node is the right child.
+            let is_right = BoolVariable::from(bit);
+            let left = self.select(is_right, sibling.clone(), node.clone());
+            let right = self.select(is_right, node, sibling.clone());
+            node = self.poseidon_hash_pair(left, right);
+        }
+        node
+    }
+
+    fn verify_poseidon_merkle_proof<const DEPTH: usize>(
+        &mut self,
+        leaf: &PoseidonHashOutVariable,
+        index: Variable,
+        siblings: &ArrayVariable<PoseidonHashOutVariable, DEPTH>,
+        root: &PoseidonHashOutVariable,
+    ) {
+        let computed_root = self.poseidon_merkle_root_from_proof(leaf, index, siblings);
+        self.assert_is_equal(computed_root, root.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use plonky2::field::types::Field;
+    use plonky2::hash::hash_types::HashOut;
+    use plonky2::hash::poseidon::PoseidonHash;
+    use plonky2::plonk::config::Hasher;
+
+    use super::MerkleTreeGadget;
+    use crate::frontend::hash::poseidon::poseidon256::PoseidonHashOutVariable;
+    use crate::prelude::*;
+
+    type F = GoldilocksField;
+
+    fn hash_pair(left: HashOut<F>, right: HashOut<F>) -> HashOut<F> {
+        PoseidonHash::hash_no_pad(&[left.elements, right.elements].concat())
+    }
+
+    #[test]
+    fn test_poseidon_merkle_tree() {
+        let mut builder = DefaultBuilder::new();
+
+        let leaves = builder.read::<ArrayVariable<PoseidonHashOutVariable, 8>>();
+        let index = builder.read::<Variable>();
+        let siblings = builder.read::<ArrayVariable<PoseidonHashOutVariable, 3>>();
+
+        let root = builder.poseidon_merkle_root(&leaves);
+        let leaf = builder.select_array(leaves.as_slice(), index);
+        builder.verify_poseidon_merkle_proof(&leaf, index, &siblings, &root);
+        builder.write(root);
+
+        let circuit = builder.build();
+
+        let leaves = (0..8u64)
+            .map(|i| PoseidonHash::hash_no_pad(&[F::from_canonical_u64(i)]))
+            .collect::<Vec<_>>();
+        let mut layers = vec![leaves.clone()];
+        while layers.last().unwrap().len() > 1 {
+            let layer = layers
+                .last()
+                .unwrap()
+                .chunks_exact(2)
+                .map(|pair| hash_pair(pair[0], pair[1]))
+                .collect::<Vec<_>>();
+            layers.push(layer);
+        }
+        let expected_root = layers.last().unwrap()[0];
+
+        let index = 5usize;
+        let siblings = (0..3)
+            .map(|level| layers[level][(index >> level) ^ 1])
+            .collect::<Vec<_>>();
+
+        let mut input = circuit.input();
+        input.write::<ArrayVariable<PoseidonHashOutVariable, 8>>(leaves);
+        input.write::<Variable>(F::from_canonical_usize(index));
+        input.write::<ArrayVariable<PoseidonHashOutVariable, 3>>(siblings);
+
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        assert_eq!(output.read::<PoseidonHashOutVariable>(), expected_root);
+    }
+}
@@ -0,0 +1,229 @@
+use plonky2::hash::hash_types::{HashOut, RichField};
+use plonky2::plonk::config::{AlgebraicHasher, GenericConfig, Hasher};
+
+use super::poseidon::MerkleTreeGadget;
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::hash::poseidon::poseidon256::PoseidonHashOutVariable;
+use crate::prelude::*;
+
+/// A proof for the leaf at some key of a sparse Merkle tree of depth `DEPTH`.
+///
+/// Siblings that are the root of an empty subtree can be omitted from the witness by setting
+/// `is_default`, in which case the precomputed empty subtree hash of their level is used instead.
+#[derive(Debug, Clone, CircuitVariable)]
+#[value_name(SparseMerkleProof)]
+pub struct SparseMerkleProofVariable<const DEPTH: usize> {
+    pub siblings: ArrayVariable<PoseidonHashOutVariable, DEPTH>,
+    pub is_default: ArrayVariable<BoolVariable, DEPTH>,
+}
+
+/// Returns the roots of the empty subtrees of height `0..=depth`, where an empty leaf is the zero
+/// digest and every inner node is the hash of its two children.
+pub fn empty_subtree_hashes<F: RichField, H: AlgebraicHasher<F>>(depth: usize) -> Vec<HashOut<F>> {
+    let mut hashes = vec![HashOut::ZERO];
+    for _ in 0..depth {
+        let child = *hashes.last().unwrap();
+        hashes.push(H::hash_no_pad(&[child.elements, child.elements].concat()));
+    }
+    hashes
+}
+
+/// A sparse Merkle tree hashed with Poseidon, in which the leaf of a key is at the position given
+/// by its `DEPTH` low bits and empty leaves are the zero digest.
+///
+/// Membership proves that a non-empty leaf is at a key, and non-membership that the leaf at a key
+/// is empty, which is what nullifier sets and key registries need.
+pub trait SparseMerkleTree {
+    /// Computes the root of the tree from the `leaf` at `key` and its proof.
+    fn sparse_merkle_root<const DEPTH: usize>(
+        &mut self,
+        key: Variable,
+        leaf: &PoseidonHashOutVariable,
+        proof: &SparseMerkleProofVariable<DEPTH>,
+    ) -> PoseidonHashOutVariable;
+
+    /// Asserts that the non-empty `leaf` is at `key` in the tree with the given `root`.
+    fn verify_sparse_merkle_membership<const DEPTH: usize>(
+        &mut self,
+        root: &PoseidonHashOutVariable,
+        key: Variable,
+        leaf: &PoseidonHashOutVariable,
+        proof: &SparseMerkleProofVariable<DEPTH>,
+    );
+
+    /// Asserts that the leaf at `key` is empty in the tree with the given `root`.
+    fn verify_sparse_merkle_non_membership<const DEPTH: usize>(
+        &mut self,
+        root: &PoseidonHashOutVariable,
+        key: Variable,
+        proof: &SparseMerkleProofVariable<DEPTH>,
+    );
+}
+
+impl<L: PlonkParameters<D>, const D: usize> SparseMerkleTree for CircuitBuilder<L, D>
+where
+    <<L as PlonkParameters<D>>::Config as GenericConfig<D>>::Hasher:
+        AlgebraicHasher<<L as PlonkParameters<D>>::Field>,
+{
+    fn sparse_merkle_root<const DEPTH: usize>(
+        &mut self,
+        key: Variable,
+        leaf: &PoseidonHashOutVariable,
+        proof: &SparseMerkleProofVariable<DEPTH>,
+    ) -> PoseidonHashOutVariable {
+        let empty_hashes = empty_subtree_hashes::<
+            L::Field,
+            <<L as PlonkParameters<D>>::Config as GenericConfig<D>>::Hasher,
+        >(DEPTH);
+        let siblings = proof
+            .siblings
+            .as_slice()
+            .iter()
+            .zip(proof.is_default.as_slice())
+            .zip(empty_hashes)
+            .map(|((sibling, is_default), empty_hash)| {
+                let empty_hash = self.constant::<PoseidonHashOutVariable>(empty_hash);
+                self.select(*is_default, empty_hash, sibling.clone())
+            })
+            .collect::<Vec<_>>();
+        self.poseidon_merkle_root_from_proof(leaf, key, &ArrayVariable::new(siblings))
+    }
+
+    fn verify_sparse_merkle_membership<const DEPTH: usize>(
+        &mut self,
+        root: &PoseidonHashOutVariable,
+        key: Variable,
+        leaf: &PoseidonHashOutVariable,
+        proof: &SparseMerkleProofVariable<DEPTH>,
+    ) {
+        let empty_leaf = self.constant::<PoseidonHashOutVariable>(HashOut::ZERO);
+        let is_empty = self.is_equal(leaf.clone(), empty_leaf);
+        let false_v = self._false();
+        self.assert_is_equal(is_empty, false_v);
+
+        let computed_root = self.sparse_merkle_root(key, leaf, proof);
+        self.assert_is_equal(computed_root, root.clone());
+    }
+
+    fn verify_sparse_merkle_non_membership<const DEPTH: usize>(
+        &mut self,
+        root: &PoseidonHashOutVariable,
+        key: Variable,
+        proof: &SparseMerkleProofVariable<DEPTH>,
+    ) {
+        let empty_leaf = self.constant::<PoseidonHashOutVariable>(HashOut::ZERO);
+        let computed_root = self.sparse_merkle_root(key, &empty_leaf, proof);
+        self.assert_is_equal(computed_root, root.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use plonky2::hash::hash_types::HashOut;
+    use plonky2::hash::poseidon::PoseidonHash;
+    use plonky2::plonk::config::Hasher;
+
+    use super::{
+        empty_subtree_hashes, SparseMerkleProof, SparseMerkleProofVariable, SparseMerkleTree,
+    };
+    use crate::frontend::hash::poseidon::poseidon256::PoseidonHashOutVariable;
+    use crate::prelude::*;
+
+    type F = GoldilocksField;
+    const DEPTH: usize = 4;
+
+    /// Builds the tree with the given leaves and returns the root and the proof for `key`.
+    fn tree_proof(
+        leaves: &[(usize, HashOut<F>)],
+        key: usize,
+    ) -> (HashOut<F>, SparseMerkleProof<DEPTH, F>) {
+        let empty_hashes = empty_subtree_hashes::<F, PoseidonHash>(DEPTH);
+        let mut layer = vec![HashOut::ZERO; 1 << DEPTH];
+        for (index, leaf) in leaves {
+            layer[*index] = *leaf;
+        }
+
+        let mut siblings = Vec::new();
+        let mut is_default = Vec::new();
+        for (level, empty_hash) in empty_hashes.iter().take(DEPTH).enumerate() {
+            let sibling = layer[(key >> level) ^ 1];
+            // Default siblings are left out of the witness.
+            let default = sibling == *empty_hash;
+            siblings.push(if default { HashOut::ZERO } else { sibling });
+            is_default.push(default);
+            layer = layer
+                .chunks_exact(2)
+                .map(|pair| {
+                    PoseidonHash::hash_no_pad(&[pair[0].elements, pair[1].elements].concat())
+                })
+                .collect();
+        }
+        (
+            layer[0],
+            SparseMerkleProof {
+                siblings,
+                is_default,
+            },
+        )
+    }
+
+    #[test]
+    fn test_sparse_merkle_tree() {
+        let mut builder = DefaultBuilder::new();
+
+        let root = builder.read::<PoseidonHashOutVariable>();
+        let member_key = builder.read::<Variable>();
+        let member_leaf = builder.read::<PoseidonHashOutVariable>();
+        let member_proof = builder.read::<SparseMerkleProofVariable<DEPTH>>();
+        let absent_key = builder.read::<Variable>();
+        let absent_proof = builder.read::<SparseMerkleProofVariable<DEPTH>>();
+        builder.verify_sparse_merkle_membership(&root, member_key, &member_leaf, &member_proof);
+        builder.verify_sparse_merkle_non_membership(&root, absent_key, &absent_proof);
+
+        let circuit = builder.build();
+
+        let leaves = [3usize, 12].map(|index| {
+            (
+                index,
+                PoseidonHash::hash_no_pad(&[F::from_canonical_usize(index)]),
+            )
+        });
+        let (root, member_proof) = tree_proof(&leaves, 12);
+        let (_, absent_proof) = tree_proof(&leaves, 7);
+
+        let mut input = circuit.input();
+        input.write::<PoseidonHashOutVariable>(root);
+        input.write::<Variable>(F::from_canonical_usize(12));
+        input.write::<PoseidonHashOutVariable>(leaves[1].1);
+        input.write::<SparseMerkleProofVariable<DEPTH>>(member_proof);
+        input.write::<Variable>(F::from_canonical_usize(7));
+        input.write::<SparseMerkleProofVariable<DEPTH>>(absent_proof);
+
+        let (proof, output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+    }
+
+    #[test]
+    #[should_panic(expected = "was set twice with different values")]
+    fn test_sparse_merkle_non_membership_of_present_key() {
+        let mut builder = DefaultBuilder::new();
+
+        let root = builder.read::<PoseidonHashOutVariable>();
+        let key = builder.read::<Variable>();
+        let proof = builder.read::<SparseMerkleProofVariable<DEPTH>>();
+        builder.verify_sparse_merkle_non_membership(&root, key, &proof);
+
+        let circuit = builder.build();
+
+        let leaves = [(5usize, PoseidonHash::hash_no_pad(&[F::ONE]))];
+        let (root, proof) = tree_proof(&leaves, 5);
+
+        let mut input = circuit.input();
+        input.write::<PoseidonHashOutVariable>(root);
+        input.write::<Variable>(F::from_canonical_usize(5));
+        input.write::<SparseMerkleProofVariable<DEPTH>>(proof);
+
+        let (proof, output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+    }
+}
@@ -10,5 +10,6 @@ pub mod mapreduce;
 pub mod merkle;
 pub mod ops;
 pub mod recursion;
+pub mod ssz;
 pub mod uint;
 pub mod vars;
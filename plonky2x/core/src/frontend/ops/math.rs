@@ -209,7 +209,9 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
 
 mod tests {
     #[allow(unused_imports)]
-    use crate::prelude::{BoolVariable, DefaultBuilder, U32Variable};
+    use crate::prelude::{
+        BoolVariable, DefaultBuilder, Field, GoldilocksField, U32Variable, U64Variable, Variable,
+    };
 
     #[test]
     fn test_math_lt() {
@@ -326,4 +328,83 @@ mod tests {
             circuit.verify(&proof, &input, &output);
         }
     }
+
+    #[test]
+    fn test_math_variable_comparisons() {
+        use plonky2::field::types::PrimeField64;
+
+        type F = GoldilocksField;
+
+        let mut builder = DefaultBuilder::new();
+
+        let v0 = builder.read::<Variable>();
+        let v1 = builder.read::<Variable>();
+        let lt = builder.lt(v0, v1);
+        let lte = builder.lte(v0, v1);
+        let gt = builder.gt(v0, v1);
+        let gte = builder.gte(v0, v1);
+        builder.write(lt);
+        builder.write(lte);
+        builder.write(gt);
+        builder.write(gte);
+
+        let circuit = builder.build();
+
+        let test_cases = [
+            (F::ZERO, F::ONE),
+            (
+                F::from_canonical_u64(1 << 40),
+                F::from_canonical_u64(1 << 40),
+            ),
+            (F::NEG_ONE, F::from_canonical_u64(u32::MAX as u64)),
+            (F::from_canonical_u64(17), F::NEG_ONE),
+        ];
+
+        for (a, b) in test_cases {
+            let mut input = circuit.input();
+            input.write::<Variable>(a);
+            input.write::<Variable>(b);
+
+            let (proof, mut output) = circuit.prove(&input);
+            circuit.verify(&proof, &input, &output);
+
+            let (a, b) = (a.to_canonical_u64(), b.to_canonical_u64());
+            assert_eq!(output.read::<BoolVariable>(), a < b);
+            assert_eq!(output.read::<BoolVariable>(), a <= b);
+            assert_eq!(output.read::<BoolVariable>(), a > b);
+            assert_eq!(output.read::<BoolVariable>(), a >= b);
+        }
+    }
+
+    #[test]
+    fn test_math_u64_comparisons() {
+        let mut builder = DefaultBuilder::new();
+
+        let v0 = builder.read::<U64Variable>();
+        let v1 = builder.read::<U64Variable>();
+        let lt = builder.lt(v0, v1);
+        let gte = builder.gte(v0, v1);
+        builder.write(lt);
+        builder.write(gte);
+
+        let circuit = builder.build();
+
+        let test_cases = [
+            (18_000_000u64, 18_000_001u64),
+            (1_700_000_000u64, 1_600_000_000u64),
+            (u64::MAX, u64::MAX),
+        ];
+
+        for (a, b) in test_cases {
+            let mut input = circuit.input();
+            input.write::<U64Variable>(a);
+            input.write::<U64Variable>(b);
+
+            let (proof, mut output) = circuit.prove(&input);
+            circuit.verify(&proof, &input, &output);
+
+            assert_eq!(output.read::<BoolVariable>(), a < b);
+            assert_eq!(output.read::<BoolVariable>(), a >= b);
+        }
+    }
 }
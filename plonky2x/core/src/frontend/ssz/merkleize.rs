@@ -0,0 +1,315 @@
+use ethers::types::H256;
+
+use super::types::{SSZType, SSZValueVariable, BYTES_PER_CHUNK};
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::builder::CircuitBuilder;
+use crate::frontend::merkle::utils::log2_ceil_usize;
+use crate::frontend::uint::uint64::U64Variable;
+use crate::frontend::vars::{BoolVariable, ByteVariable, Bytes32Variable, EvmVariable};
+use crate::utils::hash::sha256;
+
+/// Returns the roots of the trees of zero chunks of depth `0` to `depth`.
+pub(crate) fn zero_hashes(depth: usize) -> Vec<H256> {
+    let mut hashes = vec![H256::zero()];
+    for _ in 0..depth {
+        let last = hashes[hashes.len() - 1];
+        hashes.push(H256(sha256(&[last.as_bytes(), last.as_bytes()].concat())));
+    }
+    hashes
+}
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Computes the `hash_tree_root` of `value`, whose type is `ty`.
+    ///
+    /// Lists are merkleized up to their limit, with the subtrees beyond the elements of the
+    /// circuit as constants, so that large limits only cost one hash per level. Panics if the
+    /// shape of `value` does not match `ty`.
+    pub fn ssz_merkleize(&mut self, ty: &SSZType, value: &SSZValueVariable) -> Bytes32Variable {
+        match (ty, value) {
+            (SSZType::Basic(size), SSZValueVariable::Basic(bytes)) => {
+                assert_eq!(bytes.len(), *size, "basic value of the wrong size");
+                self.ssz_pack(bytes)[0]
+            }
+            (SSZType::Vector(element, len), SSZValueVariable::Composite(elements)) => {
+                assert_eq!(elements.len(), *len, "vector of the wrong length");
+                let chunks = self.ssz_element_chunks(element, elements);
+                self.ssz_merkleize_chunks(&chunks, element.chunk_count(*len))
+            }
+            (SSZType::List(element, limit), SSZValueVariable::List(elements, length)) => {
+                assert!(elements.len() <= *limit, "list longer than its limit");
+                let is_enabled = self.ssz_enabled_elements(elements.len(), *length);
+                let chunks = match element.as_ref() {
+                    SSZType::Basic(_) => {
+                        let zero = self.constant::<ByteVariable>(0);
+                        let masked = elements
+                            .iter()
+                            .zip(is_enabled.iter())
+                            .map(|(element, enabled)| match element {
+                                SSZValueVariable::Basic(bytes) => SSZValueVariable::Basic(
+                                    bytes
+                                        .iter()
+                                        .map(|byte| self.select(*enabled, *byte, zero))
+                                        .collect(),
+                                ),
+                                _ => panic!("list element does not match its basic type"),
+                            })
+                            .collect::<Vec<_>>();
+                        self.ssz_element_chunks(element, &masked)
+                    }
+                    _ => {
+                        let zero = self.constant::<Bytes32Variable>(H256::zero());
+                        let roots = self.ssz_element_chunks(element, elements);
+                        roots
+                            .into_iter()
+                            .zip(is_enabled.iter())
+                            .map(|(root, enabled)| self.select(*enabled, root, zero))
+                            .collect()
+                    }
+                };
+                let root = self.ssz_merkleize_chunks(&chunks, element.chunk_count(*limit));
+                self.ssz_mix_in_length(root, *length)
+            }
+            (SSZType::Container(fields), SSZValueVariable::Composite(values)) => {
+                assert_eq!(values.len(), fields.len(), "container of the wrong size");
+                let roots = fields
+                    .iter()
+                    .zip(values.iter())
+                    .map(|(field, value)| self.ssz_merkleize(field, value))
+                    .collect::<Vec<_>>();
+                self.ssz_merkleize_chunks(&roots, fields.len())
+            }
+            _ => panic!("value does not match the SSZ type {:?}", ty),
+        }
+    }
+
+    /// Merkleizes `chunks` in a tree of `limit` leaves padded to a power of two, whose leaves
+    /// after the chunks are zero.
+    pub fn ssz_merkleize_chunks(
+        &mut self,
+        chunks: &[Bytes32Variable],
+        limit: usize,
+    ) -> Bytes32Variable {
+        assert!(chunks.len() <= limit, "more chunks than the limit");
+        let depth = log2_ceil_usize(limit);
+        let zero_hashes = zero_hashes(depth);
+        if chunks.is_empty() {
+            return self.constant::<Bytes32Variable>(zero_hashes[depth]);
+        }
+
+        let mut nodes = chunks.to_vec();
+        for zero_hash in zero_hashes[..depth].iter() {
+            if nodes.len() % 2 == 1 {
+                nodes.push(self.constant::<Bytes32Variable>(*zero_hash));
+            }
+            nodes = nodes
+                .chunks(2)
+                .map(|pair| self.sha256_pair(pair[0], pair[1]))
+                .collect();
+        }
+        nodes[0]
+    }
+
+    /// Mixes the length of a list into the root of its elements.
+    pub fn ssz_mix_in_length(
+        &mut self,
+        root: Bytes32Variable,
+        length: U64Variable,
+    ) -> Bytes32Variable {
+        let zero = self.constant::<ByteVariable>(0);
+        let mut bytes = length.encode(self);
+        bytes.reverse();
+        bytes.resize(BYTES_PER_CHUNK, zero);
+        let length = Bytes32Variable::from(bytes.as_slice());
+        self.sha256_pair(root, length)
+    }
+
+    /// Packs the serialization of basic values into chunks, padding the last chunk with zeros.
+    fn ssz_pack(&mut self, bytes: &[ByteVariable]) -> Vec<Bytes32Variable> {
+        let zero = self.constant::<ByteVariable>(0);
+        let mut bytes = bytes.to_vec();
+        let nb_chunks = (bytes.len() + BYTES_PER_CHUNK - 1) / BYTES_PER_CHUNK;
+        bytes.resize(nb_chunks.max(1) * BYTES_PER_CHUNK, zero);
+        bytes
+            .chunks(BYTES_PER_CHUNK)
+            .map(Bytes32Variable::from)
+            .collect()
+    }
+
+    /// Returns the chunks of the elements of a vector or a list, which are the packed elements if
+    /// they are basic and their roots otherwise.
+    fn ssz_element_chunks(
+        &mut self,
+        element: &SSZType,
+        elements: &[SSZValueVariable],
+    ) -> Vec<Bytes32Variable> {
+        match element {
+            SSZType::Basic(size) => {
+                let bytes = elements
+                    .iter()
+                    .flat_map(|element| match element {
+                        SSZValueVariable::Basic(bytes) => {
+                            assert_eq!(bytes.len(), *size, "basic value of the wrong size");
+                            bytes.clone()
+                        }
+                        _ => panic!("element does not match its basic type"),
+                    })
+                    .collect::<Vec<_>>();
+                if bytes.is_empty() {
+                    return Vec::new();
+                }
+                self.ssz_pack(&bytes)
+            }
+            _ => elements
+                .iter()
+                .map(|value| self.ssz_merkleize(element, value))
+                .collect(),
+        }
+    }
+
+    /// Returns whether each of `capacity` elements is before `length`, asserting that `length` is
+    /// at most `capacity`.
+    fn ssz_enabled_elements(&mut self, capacity: usize, length: U64Variable) -> Vec<BoolVariable> {
+        let mut is_enabled = self._true();
+        let mut is_within_capacity = self._false();
+        let mut enabled = Vec::with_capacity(capacity);
+        for i in 0..=capacity {
+            let index = self.constant::<U64Variable>(i as u64);
+            let is_length = self.is_equal(index, length);
+            is_within_capacity = self.or(is_within_capacity, is_length);
+            if i < capacity {
+                let not_length = self.not(is_length);
+                is_enabled = self.and(is_enabled, not_length);
+                enabled.push(is_enabled);
+            }
+        }
+        let true_v = self._true();
+        self.assert_is_equal(is_within_capacity, true_v);
+        enabled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::DefaultBuilder;
+
+    /// Merkleizes native chunks as in the SSZ specification.
+    fn merkleize(chunks: &[[u8; 32]], limit: usize) -> [u8; 32] {
+        let size = limit.next_power_of_two();
+        let mut nodes = chunks.to_vec();
+        nodes.resize(size, [0u8; 32]);
+        while nodes.len() > 1 {
+            nodes = nodes
+                .chunks(2)
+                .map(|pair| sha256(&[pair[0], pair[1]].concat()))
+                .collect();
+        }
+        nodes[0]
+    }
+
+    fn mix_in_length(root: [u8; 32], length: u64) -> [u8; 32] {
+        let mut chunk = [0u8; 32];
+        chunk[..8].copy_from_slice(&length.to_le_bytes());
+        sha256(&[root, chunk].concat())
+    }
+
+    fn uint64(value: u64) -> [u8; 32] {
+        let mut chunk = [0u8; 32];
+        chunk[..8].copy_from_slice(&value.to_le_bytes());
+        chunk
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_ssz_merkleize() {
+        // A container of a `uint64`, a `boolean`, a `Bytes32`, a `List[uint64, 100]` and a
+        // `List[Container(uint64, Bytes32), 1000]`.
+        let item = SSZType::Container(vec![SSZType::uint64(), SSZType::bytes32()]);
+        let ty = SSZType::Container(vec![
+            SSZType::uint64(),
+            SSZType::bool(),
+            SSZType::bytes32(),
+            SSZType::list(SSZType::uint64(), 100),
+            SSZType::list(item, 1000),
+        ]);
+
+        let mut builder = DefaultBuilder::new();
+        let slot = builder.read::<U64Variable>();
+        let flag = builder.read::<BoolVariable>();
+        let root = builder.read::<Bytes32Variable>();
+        let numbers = (0..6)
+            .map(|_| builder.read::<U64Variable>())
+            .collect::<Vec<_>>();
+        let numbers_len = builder.read::<U64Variable>();
+        let items = (0..2)
+            .map(|_| {
+                (
+                    builder.read::<U64Variable>(),
+                    builder.read::<Bytes32Variable>(),
+                )
+            })
+            .collect::<Vec<_>>();
+        let items_len = builder.read::<U64Variable>();
+
+        let numbers = numbers
+            .into_iter()
+            .map(|number| SSZValueVariable::from_u64(&mut builder, number))
+            .collect();
+        let items = items
+            .into_iter()
+            .map(|(index, root)| {
+                SSZValueVariable::Composite(vec![
+                    SSZValueVariable::from_u64(&mut builder, index),
+                    SSZValueVariable::from_bytes32(root),
+                ])
+            })
+            .collect();
+        let value = SSZValueVariable::Composite(vec![
+            SSZValueVariable::from_u64(&mut builder, slot),
+            SSZValueVariable::from_bool(&mut builder, flag),
+            SSZValueVariable::from_bytes32(root),
+            SSZValueVariable::List(numbers, numbers_len),
+            SSZValueVariable::List(items, items_len),
+        ]);
+        let hash_tree_root = builder.ssz_merkleize(&ty, &value);
+        builder.write(hash_tree_root);
+        let circuit = builder.mock_build();
+
+        // The lists have 5 of 6 numbers and 1 of 2 items, whose ignored entries are not zero.
+        let numbers = [1u64, 2, 3, 4, 5, 6];
+        let items = [(7u64, H256::repeat_byte(7)), (8u64, H256::repeat_byte(8))];
+        let mut flag_chunk = [0u8; 32];
+        flag_chunk[0] = 1;
+        let mut packed = [[0u8; 32]; 2];
+        for (i, number) in numbers[..5].iter().enumerate() {
+            packed[i / 4][8 * (i % 4)..8 * (i % 4 + 1)].copy_from_slice(&number.to_le_bytes());
+        }
+        let item_root = merkleize(&[uint64(items[0].0), items[0].1 .0], 2);
+        let expected = merkleize(
+            &[
+                uint64(42),
+                flag_chunk,
+                H256::repeat_byte(9).0,
+                mix_in_length(merkleize(&packed, 25), 5),
+                mix_in_length(merkleize(&[item_root], 1000), 1),
+            ],
+            5,
+        );
+
+        let mut input = circuit.input();
+        input.write::<U64Variable>(42);
+        input.write::<BoolVariable>(true);
+        input.write::<Bytes32Variable>(H256::repeat_byte(9));
+        for number in numbers {
+            input.write::<U64Variable>(number);
+        }
+        input.write::<U64Variable>(5);
+        for (index, root) in items {
+            input.write::<U64Variable>(index);
+            input.write::<Bytes32Variable>(root);
+        }
+        input.write::<U64Variable>(1);
+        let (_witness, mut output) = circuit.mock_prove(&input);
+        assert_eq!(output.read::<Bytes32Variable>(), H256(expected));
+    }
+}
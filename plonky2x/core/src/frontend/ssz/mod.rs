@@ -0,0 +1,2 @@
+pub mod merkleize;
+pub mod types;
@@ -0,0 +1,102 @@
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::builder::CircuitBuilder;
+use crate::frontend::uint::uint64::U64Variable;
+use crate::frontend::vars::{BoolVariable, ByteVariable, Bytes32Variable, EvmVariable};
+
+/// The number of bytes of a chunk of the merkleization.
+pub const BYTES_PER_CHUNK: usize = 32;
+
+/// The type of an SSZ value, which determines how it is merkleized.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SSZType {
+    /// A basic value of the given number of bytes, such as an unsigned integer or a boolean.
+    Basic(usize),
+    /// A vector of the given number of elements.
+    Vector(Box<SSZType>, usize),
+    /// A list of at most the given number of elements.
+    List(Box<SSZType>, usize),
+    /// A container of fields of the given types.
+    Container(Vec<SSZType>),
+}
+
+impl SSZType {
+    /// The type `boolean`.
+    pub fn bool() -> Self {
+        Self::Basic(1)
+    }
+
+    /// The type `uint64`.
+    pub fn uint64() -> Self {
+        Self::Basic(8)
+    }
+
+    /// The type `Bytes32`, which is a vector of 32 bytes.
+    pub fn bytes32() -> Self {
+        Self::Vector(Box::new(Self::Basic(1)), 32)
+    }
+
+    /// The type `Vector[element, len]`.
+    pub fn vector(element: SSZType, len: usize) -> Self {
+        Self::Vector(Box::new(element), len)
+    }
+
+    /// The type `List[element, limit]`.
+    pub fn list(element: SSZType, limit: usize) -> Self {
+        Self::List(Box::new(element), limit)
+    }
+
+    /// Returns the number of chunks of the merkleization of `len` elements of the type, which are
+    /// packed together if the type is basic.
+    pub(crate) fn chunk_count(&self, len: usize) -> usize {
+        match self {
+            Self::Basic(size) => (len * size + BYTES_PER_CHUNK - 1) / BYTES_PER_CHUNK,
+            _ => len,
+        }
+    }
+}
+
+/// An SSZ value in the circuit, whose shape must match its `SSZType`.
+#[derive(Debug, Clone)]
+pub enum SSZValueVariable {
+    /// The little-endian serialization of a basic value.
+    Basic(Vec<ByteVariable>),
+    /// The elements of a vector or the fields of a container.
+    Composite(Vec<SSZValueVariable>),
+    /// The elements of a list, up to the capacity of the circuit, and the length of the list. The
+    /// elements at and after the length are ignored.
+    List(Vec<SSZValueVariable>, U64Variable),
+}
+
+impl SSZValueVariable {
+    /// A `boolean` value.
+    pub fn from_bool<L: PlonkParameters<D>, const D: usize>(
+        builder: &mut CircuitBuilder<L, D>,
+        value: BoolVariable,
+    ) -> Self {
+        let false_v = builder._false();
+        let mut bits = [false_v; 8];
+        bits[7] = value;
+        Self::Basic(vec![ByteVariable::from_be_bits(bits)])
+    }
+
+    /// A `uint64` value.
+    pub fn from_u64<L: PlonkParameters<D>, const D: usize>(
+        builder: &mut CircuitBuilder<L, D>,
+        value: U64Variable,
+    ) -> Self {
+        let mut bytes = value.encode(builder);
+        bytes.reverse();
+        Self::Basic(bytes)
+    }
+
+    /// A `Bytes32` value.
+    pub fn from_bytes32(value: Bytes32Variable) -> Self {
+        Self::Composite(
+            value
+                .as_bytes()
+                .iter()
+                .map(|byte| Self::Basic(vec![*byte]))
+                .collect(),
+        )
+    }
+}
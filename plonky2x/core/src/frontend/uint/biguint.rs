@@ -0,0 +1,437 @@
+use core::array;
+
+use num::BigUint;
+use plonky2::hash::hash_types::RichField;
+
+use crate::frontend::uint::num::biguint::{BigUintTarget, CircuitBuilderBiguint};
+use crate::frontend::uint::num::u32::gadgets::arithmetic_u32::{CircuitBuilderU32, U32Target};
+use crate::frontend::vars::{EvmVariable, U32Variable};
+use crate::prelude::{
+    Add, BoolVariable, ByteVariable, CircuitBuilder, CircuitVariable, Div, LessThanOrEqual, Mul,
+    One, PlonkParameters, Rem, Sub, Variable, Zero,
+};
+
+/// An unsigned integer encoded as `LIMBS` little-endian u32 limbs.
+///
+/// The fixed-width integer variables (`U64Variable`, `U128Variable`, `U256Variable`, ...) convert
+/// to and from `BigUintVariable` with the same number of limbs and share its arithmetic.
+#[derive(Debug, Clone, Copy)]
+pub struct BigUintVariable<const LIMBS: usize> {
+    pub limbs: [U32Variable; LIMBS],
+}
+
+impl<const LIMBS: usize> CircuitVariable for BigUintVariable<LIMBS> {
+    type ValueType<F: RichField> = BigUint;
+
+    fn init_unsafe<L: PlonkParameters<D>, const D: usize>(
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> Self {
+        Self {
+            limbs: array::from_fn(|_| U32Variable::init_unsafe(builder)),
+        }
+    }
+
+    fn variables(&self) -> Vec<Variable> {
+        self.limbs.iter().map(|x| x.variable).collect()
+    }
+
+    fn from_variables_unsafe(variables: &[Variable]) -> Self {
+        assert_eq!(variables.len(), LIMBS);
+        Self {
+            limbs: array::from_fn(|i| U32Variable::from_variables_unsafe(&[variables[i]])),
+        }
+    }
+
+    fn assert_is_valid<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        builder: &mut CircuitBuilder<L, D>,
+    ) {
+        for limb in self.limbs.iter() {
+            limb.assert_is_valid(builder);
+        }
+    }
+
+    fn nb_elements() -> usize {
+        U32Variable::nb_elements() * LIMBS
+    }
+
+    fn elements<F: RichField>(value: BigUint) -> Vec<F> {
+        let mut digits = value.to_u32_digits();
+        assert!(
+            digits.len() <= LIMBS,
+            "value does not fit in {} u32 limbs",
+            LIMBS
+        );
+        digits.resize(LIMBS, 0);
+        digits
+            .into_iter()
+            .flat_map(U32Variable::elements::<F>)
+            .collect()
+    }
+
+    fn from_elements<F: RichField>(elements: &[F]) -> BigUint {
+        assert_eq!(elements.len(), LIMBS);
+        let digits = elements
+            .iter()
+            .map(|x| U32Variable::from_elements(&[*x]))
+            .collect::<Vec<_>>();
+        BigUint::from_slice(&digits)
+    }
+}
+
+impl<const LIMBS: usize> EvmVariable for BigUintVariable<LIMBS> {
+    fn encode<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> Vec<ByteVariable> {
+        self.limbs
+            .iter()
+            .rev()
+            .flat_map(|x| x.encode(builder))
+            .collect::<Vec<_>>()
+    }
+
+    fn decode<L: PlonkParameters<D>, const D: usize>(
+        builder: &mut CircuitBuilder<L, D>,
+        bytes: &[ByteVariable],
+    ) -> Self {
+        assert_eq!(bytes.len(), LIMBS * 4);
+        let mut limbs: [U32Variable; LIMBS] =
+            array::from_fn(|i| U32Variable::decode(builder, &bytes[i * 4..(i + 1) * 4]));
+        limbs.reverse();
+        Self { limbs }
+    }
+
+    fn encode_value<F: RichField>(value: BigUint) -> Vec<u8> {
+        let bytes = value.to_bytes_be();
+        assert!(
+            bytes.len() <= LIMBS * 4,
+            "value does not fit in {} bytes",
+            LIMBS * 4
+        );
+        let mut encoded = vec![0u8; LIMBS * 4 - bytes.len()];
+        encoded.extend(bytes);
+        encoded
+    }
+
+    fn decode_value<F: RichField>(bytes: &[u8]) -> BigUint {
+        assert_eq!(bytes.len(), LIMBS * 4);
+        BigUint::from_bytes_be(bytes)
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize, const LIMBS: usize> Zero<L, D>
+    for BigUintVariable<LIMBS>
+{
+    fn zero(builder: &mut CircuitBuilder<L, D>) -> Self {
+        let zero = U32Variable::zero(builder);
+        Self {
+            limbs: [zero; LIMBS],
+        }
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize, const LIMBS: usize> One<L, D>
+    for BigUintVariable<LIMBS>
+{
+    fn one(builder: &mut CircuitBuilder<L, D>) -> Self {
+        let zero = U32Variable::zero(builder);
+        let one = U32Variable::one(builder);
+        let mut limbs = [zero; LIMBS];
+        limbs[0] = one;
+        Self { limbs }
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize, const LIMBS: usize> Add<L, D>
+    for BigUintVariable<LIMBS>
+{
+    type Output = Self;
+
+    fn add(self, rhs: Self, builder: &mut CircuitBuilder<L, D>) -> Self::Output {
+        let sum_biguint = builder
+            .api
+            .add_biguint(&self.to_biguint(), &rhs.to_biguint());
+        Self::from_biguint_low(&sum_biguint)
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize, const LIMBS: usize> Sub<L, D>
+    for BigUintVariable<LIMBS>
+{
+    type Output = Self;
+
+    fn sub(self, rhs: Self, builder: &mut CircuitBuilder<L, D>) -> Self::Output {
+        let diff_biguint = builder
+            .api
+            .sub_biguint(&self.to_biguint(), &rhs.to_biguint());
+        Self::from_biguint_low(&diff_biguint)
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize, const LIMBS: usize> Mul<L, D>
+    for BigUintVariable<LIMBS>
+{
+    type Output = Self;
+
+    fn mul(self, rhs: Self, builder: &mut CircuitBuilder<L, D>) -> Self::Output {
+        let product_biguint = builder
+            .api
+            .mul_biguint(&self.to_biguint(), &rhs.to_biguint());
+        Self::from_biguint_low(&product_biguint)
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize, const LIMBS: usize> Div<L, D>
+    for BigUintVariable<LIMBS>
+{
+    type Output = Self;
+
+    fn div(self, rhs: Self, builder: &mut CircuitBuilder<L, D>) -> Self::Output {
+        let quotient_biguint = builder
+            .api
+            .div_biguint(&self.to_biguint(), &rhs.to_biguint());
+        Self::from_biguint_low(&quotient_biguint)
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize, const LIMBS: usize> Rem<L, D>
+    for BigUintVariable<LIMBS>
+{
+    type Output = Self;
+
+    fn rem(self, rhs: Self, builder: &mut CircuitBuilder<L, D>) -> Self::Output {
+        let rem_biguint = builder
+            .api
+            .rem_biguint(&self.to_biguint(), &rhs.to_biguint());
+        Self::from_biguint_low(&rem_biguint)
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize, const LIMBS: usize> LessThanOrEqual<L, D>
+    for BigUintVariable<LIMBS>
+{
+    #[must_use]
+    fn lte(self, rhs: Self, builder: &mut CircuitBuilder<L, D>) -> BoolVariable {
+        // `self <= rhs` iff computing `rhs - self` limb-wise does not leave a final borrow.
+        let (_, borrow) = rhs.overflowing_sub(self, builder);
+        builder.not(borrow)
+    }
+}
+
+impl<const LIMBS: usize> BigUintVariable<LIMBS> {
+    /// Views the limbs as a BigUintTarget for use with the biguint gadgets.
+    pub(crate) fn to_biguint(&self) -> BigUintTarget {
+        BigUintTarget {
+            limbs: self.limbs.iter().map(|x| U32Target::from(*x)).collect(),
+        }
+    }
+
+    /// Takes the least significant limbs of a BigUintTarget, discarding any higher limbs.
+    pub(crate) fn from_biguint_low(value: &BigUintTarget) -> Self {
+        assert!(value.limbs.len() >= LIMBS);
+        Self {
+            limbs: array::from_fn(|i| value.limbs[i].into()),
+        }
+    }
+
+    /// Divides two integers, returning both the quotient and the remainder.
+    ///
+    /// The quotient and remainder are supplied by a witness generator and constrained so that
+    /// `quotient * rhs + remainder == self` and `remainder < rhs`.
+    pub fn div_rem<L: PlonkParameters<D>, const D: usize>(
+        self,
+        rhs: Self,
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> (Self, Self) {
+        let (quotient_biguint, rem_biguint) = builder
+            .api
+            .div_rem_biguint(&self.to_biguint(), &rhs.to_biguint());
+
+        (
+            Self::from_biguint_low(&quotient_biguint),
+            Self::from_biguint_low(&rem_biguint),
+        )
+    }
+
+    /// Narrows the integer to a U32Variable, returning the least significant limb and whether any
+    /// of the discarded high limbs were non-zero.
+    pub fn try_to_u32<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> (U32Variable, BoolVariable) {
+        // Each limb is less than 2^32, so their sum cannot wrap around the field.
+        let mut high_sum = builder.zero::<Variable>();
+        for limb in self.limbs[1..].iter() {
+            high_sum = builder.add(high_sum, limb.variable);
+        }
+        let fits = builder.is_zero(high_sum);
+        let overflow = builder.not(fits);
+        (self.limbs[0], overflow)
+    }
+
+    /// Narrows the integer to a U32Variable, asserting that it fits in 32 bits.
+    pub fn to_u32<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> U32Variable {
+        let (result, overflow) = self.try_to_u32(builder);
+        let false_v = builder._false();
+        builder.assert_is_equal(overflow, false_v);
+        result
+    }
+
+    /// Adds two integers, returning the wrapped sum and whether the addition overflowed.
+    pub fn overflowing_add<L: PlonkParameters<D>, const D: usize>(
+        self,
+        rhs: Self,
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> (Self, BoolVariable) {
+        let sum_biguint = builder
+            .api
+            .add_biguint(&self.to_biguint(), &rhs.to_biguint());
+
+        // The final carry of adding two N-limb integers is either 0 or 1.
+        let overflow =
+            BoolVariable::from_variables_unsafe(&[Variable(sum_biguint.limbs[LIMBS].target)]);
+        (Self::from_biguint_low(&sum_biguint), overflow)
+    }
+
+    /// Subtracts two integers, returning the wrapped difference and whether the subtraction
+    /// underflowed.
+    pub fn overflowing_sub<L: PlonkParameters<D>, const D: usize>(
+        self,
+        rhs: Self,
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> (Self, BoolVariable) {
+        let mut borrow = builder.api.zero_u32();
+        let mut limbs = Self::zero(builder).limbs;
+        for i in 0..LIMBS {
+            let (diff, new_borrow) = builder.api.sub_u32(
+                U32Target::from(self.limbs[i]),
+                U32Target::from(rhs.limbs[i]),
+                borrow,
+            );
+            limbs[i] = diff.into();
+            borrow = new_borrow;
+        }
+
+        // The final borrow is either 0 or 1.
+        let underflow = BoolVariable::from_variables_unsafe(&[Variable(borrow.target)]);
+
+        (Self { limbs }, underflow)
+    }
+
+    /// Multiplies two integers, returning the wrapped product and whether the multiplication
+    /// overflowed.
+    pub fn overflowing_mul<L: PlonkParameters<D>, const D: usize>(
+        self,
+        rhs: Self,
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> (Self, BoolVariable) {
+        let product_biguint = builder
+            .api
+            .mul_biguint(&self.to_biguint(), &rhs.to_biguint());
+
+        // The product overflows iff any of the high limbs is non-zero. Each limb is less than
+        // 2^32, so their sum cannot wrap around the field.
+        let mut high_sum = builder.zero::<Variable>();
+        for limb in product_biguint.limbs[LIMBS..].iter() {
+            high_sum = builder.add(high_sum, Variable(limb.target));
+        }
+        let no_overflow = builder.is_zero(high_sum);
+        let overflow = builder.not(no_overflow);
+
+        (Self::from_biguint_low(&product_biguint), overflow)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::BigUint;
+    use rand::rngs::OsRng;
+    use rand::Rng;
+
+    use super::*;
+    use crate::prelude::*;
+
+    type L = DefaultParameters;
+    const D: usize = 2;
+
+    /// A width that has no fixed-width alias.
+    const LIMBS: usize = 3;
+
+    fn random_value() -> BigUint {
+        BigUint::from_slice(&[OsRng.gen(), OsRng.gen(), OsRng.gen()])
+    }
+
+    #[test]
+    fn test_biguint_variable_arithmetic() {
+        let modulus = BigUint::from(1u8) << (32 * LIMBS);
+        let a = random_value();
+        let b = random_value();
+
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let a_var = BigUintVariable::<LIMBS>::constant(&mut builder, a.clone());
+        let b_var = BigUintVariable::<LIMBS>::constant(&mut builder, b.clone());
+
+        let sum = builder.add(a_var, b_var);
+        let expected = builder.constant::<BigUintVariable<LIMBS>>((&a + &b) % &modulus);
+        builder.assert_is_equal(sum, expected);
+
+        let diff = builder.sub(a_var, b_var);
+        let expected = builder.constant::<BigUintVariable<LIMBS>>((&a + &modulus - &b) % &modulus);
+        builder.assert_is_equal(diff, expected);
+
+        let product = builder.mul(a_var, b_var);
+        let expected = builder.constant::<BigUintVariable<LIMBS>>((&a * &b) % &modulus);
+        builder.assert_is_equal(product, expected);
+
+        let (quotient, rem) = a_var.div_rem(b_var, &mut builder);
+        let expected = builder.constant::<BigUintVariable<LIMBS>>(&a / &b);
+        builder.assert_is_equal(quotient, expected);
+        let expected = builder.constant::<BigUintVariable<LIMBS>>(&a % &b);
+        builder.assert_is_equal(rem, expected);
+
+        let lte = builder.lte(a_var, b_var);
+        let expected = builder.constant::<BoolVariable>(a <= b);
+        builder.assert_is_equal(lte, expected);
+
+        let (_, overflow) = a_var.overflowing_add(b_var, &mut builder);
+        let expected = builder.constant::<BoolVariable>(&a + &b >= modulus);
+        builder.assert_is_equal(overflow, expected);
+
+        let circuit = builder.build();
+        let pw = PartialWitness::new();
+        let proof = circuit.data.prove(pw).unwrap();
+        circuit.data.verify(proof).unwrap();
+    }
+
+    #[test]
+    fn test_biguint_variable_evm() {
+        type F = GoldilocksField;
+
+        let value = random_value();
+        let encoded = BigUintVariable::<LIMBS>::encode_value::<F>(value.clone());
+        assert_eq!(encoded.len(), LIMBS * 4);
+        assert_eq!(BigUintVariable::<LIMBS>::decode_value::<F>(&encoded), value);
+
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let bytes = encoded
+            .iter()
+            .map(|byte| builder.constant::<ByteVariable>(*byte))
+            .collect::<Vec<_>>();
+        let decoded = BigUintVariable::<LIMBS>::decode(&mut builder, &bytes);
+        let expected = builder.constant::<BigUintVariable<LIMBS>>(value);
+        builder.assert_is_equal(decoded, expected);
+        let reencoded = decoded.encode(&mut builder);
+        for (byte, expected) in reencoded.into_iter().zip(bytes) {
+            builder.assert_is_equal(byte, expected);
+        }
+
+        let circuit = builder.build();
+        let pw = PartialWitness::new();
+        let proof = circuit.data.prove(pw).unwrap();
+        circuit.data.verify(proof).unwrap();
+    }
+}
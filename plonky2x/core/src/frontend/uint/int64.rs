@@ -0,0 +1,259 @@
+use plonky2::hash::hash_types::RichField;
+
+use crate::frontend::vars::{EvmVariable, U32Variable};
+use crate::prelude::{
+    Add, BoolVariable, ByteVariable, CircuitBuilder, CircuitVariable, LessThanOrEqual, Mul, Neg,
+    One, PlonkParameters, Sub, U64Variable, Variable, Zero,
+};
+
+/// The bit pattern of the sign bit within the most significant u32 limb.
+const SIGN_BIT: u32 = 1 << 31;
+
+/// A signed 64-bit integer in two's-complement representation.
+///
+/// Under the hood, the bit pattern is stored as a `U64Variable`, so addition, subtraction and
+/// multiplication are the same wrapping operations as for unsigned integers.
+#[derive(Debug, Clone, Copy)]
+pub struct I64Variable {
+    pub value: U64Variable,
+}
+
+impl CircuitVariable for I64Variable {
+    type ValueType<F: RichField> = i64;
+
+    fn init_unsafe<L: PlonkParameters<D>, const D: usize>(
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> Self {
+        Self {
+            value: U64Variable::init_unsafe(builder),
+        }
+    }
+
+    fn variables(&self) -> Vec<Variable> {
+        self.value.variables()
+    }
+
+    fn from_variables_unsafe(variables: &[Variable]) -> Self {
+        Self {
+            value: U64Variable::from_variables_unsafe(variables),
+        }
+    }
+
+    fn assert_is_valid<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        builder: &mut CircuitBuilder<L, D>,
+    ) {
+        self.value.assert_is_valid(builder);
+    }
+
+    fn nb_elements() -> usize {
+        U64Variable::nb_elements()
+    }
+
+    fn elements<F: RichField>(value: Self::ValueType<F>) -> Vec<F> {
+        U64Variable::elements::<F>(value as u64)
+    }
+
+    fn from_elements<F: RichField>(elements: &[F]) -> Self::ValueType<F> {
+        U64Variable::from_elements::<F>(elements) as i64
+    }
+}
+
+impl EvmVariable for I64Variable {
+    fn encode<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> Vec<ByteVariable> {
+        self.value.encode(builder)
+    }
+
+    fn decode<L: PlonkParameters<D>, const D: usize>(
+        builder: &mut CircuitBuilder<L, D>,
+        bytes: &[ByteVariable],
+    ) -> Self {
+        Self {
+            value: U64Variable::decode(builder, bytes),
+        }
+    }
+
+    fn encode_value<F: RichField>(value: Self::ValueType<F>) -> Vec<u8> {
+        value.to_be_bytes().to_vec()
+    }
+
+    fn decode_value<F: RichField>(bytes: &[u8]) -> Self::ValueType<F> {
+        assert_eq!(bytes.len(), 8);
+        i64::from_be_bytes(bytes.try_into().unwrap())
+    }
+}
+
+impl I64Variable {
+    /// Returns true if the value is strictly negative, i.e. the sign bit is set.
+    pub fn is_negative<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> BoolVariable {
+        let sign_bit = builder.constant::<U32Variable>(SIGN_BIT);
+        builder.lte(sign_bit, self.value.limbs[1])
+    }
+
+    /// Maps the two's-complement bit pattern onto an unsigned value with the same ordering by
+    /// flipping the sign bit (i.e. adding 2^63 modulo 2^64).
+    fn to_biased<L: PlonkParameters<D>, const D: usize>(
+        self,
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> U64Variable {
+        let sign_bit = builder.constant::<U32Variable>(SIGN_BIT);
+        let high = builder.add(self.value.limbs[1], sign_bit);
+        U64Variable {
+            limbs: [self.value.limbs[0], high],
+        }
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize> Zero<L, D> for I64Variable {
+    fn zero(builder: &mut CircuitBuilder<L, D>) -> Self {
+        Self {
+            value: U64Variable::zero(builder),
+        }
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize> One<L, D> for I64Variable {
+    fn one(builder: &mut CircuitBuilder<L, D>) -> Self {
+        Self {
+            value: U64Variable::one(builder),
+        }
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize> Add<L, D> for I64Variable {
+    type Output = Self;
+
+    fn add(self, rhs: Self, builder: &mut CircuitBuilder<L, D>) -> Self::Output {
+        Self {
+            value: builder.add(self.value, rhs.value),
+        }
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize> Sub<L, D> for I64Variable {
+    type Output = Self;
+
+    fn sub(self, rhs: Self, builder: &mut CircuitBuilder<L, D>) -> Self::Output {
+        Self {
+            value: builder.sub(self.value, rhs.value),
+        }
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize> Mul<L, D> for I64Variable {
+    type Output = Self;
+
+    fn mul(self, rhs: Self, builder: &mut CircuitBuilder<L, D>) -> Self::Output {
+        Self {
+            value: builder.mul(self.value, rhs.value),
+        }
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize> Neg<L, D> for I64Variable {
+    type Output = Self;
+
+    fn neg(self, builder: &mut CircuitBuilder<L, D>) -> Self::Output {
+        let zero = builder.zero::<Self>();
+        builder.sub(zero, self)
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize> LessThanOrEqual<L, D> for I64Variable {
+    #[must_use]
+    fn lte(self, rhs: Self, builder: &mut CircuitBuilder<L, D>) -> BoolVariable {
+        let lhs = self.to_biased(builder);
+        let rhs = rhs.to_biased(builder);
+        builder.lte(lhs, rhs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::I64Variable;
+    use crate::frontend::vars::EvmVariable;
+    use crate::prelude::*;
+
+    type L = DefaultParameters;
+    const D: usize = 2;
+
+    #[test]
+    fn test_i64_evm_value() {
+        for value in [0i64, 1, -1, i64::MIN, i64::MAX, -123456789] {
+            let encoded = I64Variable::encode_value::<GoldilocksField>(value);
+            assert_eq!(encoded, value.to_be_bytes().to_vec());
+            let decoded = I64Variable::decode_value::<GoldilocksField>(&encoded);
+            assert_eq!(decoded, value);
+        }
+    }
+
+    #[test]
+    fn test_i64_arithmetic() {
+        let mut builder = CircuitBuilder::<L, D>::new();
+
+        let a_value = -1234567i64;
+        let b_value = 987654i64;
+
+        let a = builder.constant::<I64Variable>(a_value);
+        let b = builder.constant::<I64Variable>(b_value);
+
+        let sum = builder.add(a, b);
+        let expected_sum = builder.constant::<I64Variable>(a_value.wrapping_add(b_value));
+        builder.assert_is_equal(sum, expected_sum);
+
+        let diff = builder.sub(a, b);
+        let expected_diff = builder.constant::<I64Variable>(a_value.wrapping_sub(b_value));
+        builder.assert_is_equal(diff, expected_diff);
+
+        let product = builder.mul(a, b);
+        let expected_product = builder.constant::<I64Variable>(a_value.wrapping_mul(b_value));
+        builder.assert_is_equal(product, expected_product);
+
+        let neg = builder.neg(a);
+        let expected_neg = builder.constant::<I64Variable>(-a_value);
+        builder.assert_is_equal(neg, expected_neg);
+
+        let circuit = builder.build();
+        let pw = PartialWitness::new();
+        let proof = circuit.data.prove(pw).unwrap();
+        circuit.data.verify(proof).unwrap();
+    }
+
+    #[test]
+    fn test_i64_comparison() {
+        let mut builder = CircuitBuilder::<L, D>::new();
+
+        let true_v = builder._true();
+        let false_v = builder._false();
+
+        let neg = builder.constant::<I64Variable>(-5);
+        let pos = builder.constant::<I64Variable>(3);
+        let min = builder.constant::<I64Variable>(i64::MIN);
+        let max = builder.constant::<I64Variable>(i64::MAX);
+
+        let neg_lte_pos = builder.lte(neg, pos);
+        builder.assert_is_equal(neg_lte_pos, true_v);
+        let pos_lte_neg = builder.lte(pos, neg);
+        builder.assert_is_equal(pos_lte_neg, false_v);
+        let min_lt_max = builder.lt(min, max);
+        builder.assert_is_equal(min_lt_max, true_v);
+        let neg_lte_neg = builder.lte(neg, neg);
+        builder.assert_is_equal(neg_lte_neg, true_v);
+
+        let neg_is_negative = neg.is_negative(&mut builder);
+        builder.assert_is_equal(neg_is_negative, true_v);
+        let pos_is_negative = pos.is_negative(&mut builder);
+        builder.assert_is_equal(pos_is_negative, false_v);
+
+        let circuit = builder.build();
+        let pw = PartialWitness::new();
+        let proof = circuit.data.prove(pw).unwrap();
+        circuit.data.verify(proof).unwrap();
+    }
+}
@@ -1,13 +1,17 @@
 use core::fmt::Debug;
 
+pub mod biguint;
+pub mod int64;
 pub mod uint128;
 pub mod uint256;
 pub mod uint32;
 pub mod uint512;
 pub mod uint64;
+pub mod uint8;
 
 pub(crate) mod num;
 
+mod shift;
 mod uint32_n;
 
 pub trait Uint<const N: usize>: Debug + Clone + Copy + Sync + Send + 'static {
@@ -0,0 +1,244 @@
+use itertools::Itertools;
+
+use super::uint128::U128Variable;
+use super::uint256::U256Variable;
+use super::uint32::U32Variable;
+use super::uint64::U64Variable;
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::builder::bits::Endianness;
+use crate::frontend::builder::CircuitBuilder;
+use crate::frontend::ops::{RotateLeft, RotateRight, Shl, Shr};
+use crate::frontend::vars::{BoolVariable, CircuitVariable, Variable};
+
+#[derive(Debug, Clone, Copy)]
+enum ShiftKind {
+    Shl,
+    Shr,
+    RotateLeft,
+    RotateRight,
+}
+
+impl ShiftKind {
+    /// Returns the index of the source bit for the little-endian bit `i` of a `width`-bit value
+    /// shifted by `amount`, or `None` if the bit is shifted in as zero.
+    fn source(self, i: usize, amount: usize, width: usize) -> Option<usize> {
+        match self {
+            ShiftKind::Shl => i.checked_sub(amount),
+            ShiftKind::Shr => Some(i + amount).filter(|j| *j < width),
+            ShiftKind::RotateLeft => Some((i + width - amount % width) % width),
+            ShiftKind::RotateRight => Some((i + amount) % width),
+        }
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Decomposes little-endian u32 limbs into little-endian bits.
+    fn limbs_to_le_bits(&mut self, limbs: &[Variable]) -> Vec<BoolVariable> {
+        limbs
+            .iter()
+            .flat_map(|limb| self.to_bits(limb, 32, Endianness::Little))
+            .collect()
+    }
+
+    /// Recomposes little-endian bits into little-endian u32 limbs.
+    fn le_bits_to_limbs(&mut self, bits: &[BoolVariable]) -> Vec<Variable> {
+        bits.chunks(32)
+            .map(|chunk| self.from_bits::<Variable>(chunk, Endianness::Little))
+            .collect()
+    }
+
+    fn shift_le_bits(
+        &mut self,
+        bits: &[BoolVariable],
+        kind: ShiftKind,
+        amount: usize,
+    ) -> Vec<BoolVariable> {
+        let width = bits.len();
+        let zero = self._false();
+        (0..width)
+            .map(|i| kind.source(i, amount, width).map_or(zero, |j| bits[j]))
+            .collect()
+    }
+
+    /// Shifts or rotates the value held in `limbs` by a constant `amount`.
+    fn shift_limbs(&mut self, limbs: &[Variable], kind: ShiftKind, amount: usize) -> Vec<Variable> {
+        let width = 32 * limbs.len();
+        if matches!(kind, ShiftKind::Shl | ShiftKind::Shr) {
+            assert!(
+                amount < width,
+                "shift amount is too large, must be less than {}",
+                width
+            );
+        }
+        let bits = self.limbs_to_le_bits(limbs);
+        let shifted = self.shift_le_bits(&bits, kind, amount);
+        self.le_bits_to_limbs(&shifted)
+    }
+
+    /// Shifts or rotates the value held in `limbs` by a dynamic `amount`, which is constrained to
+    /// be less than the bit width, using a barrel shifter over the bits of `amount`.
+    fn shift_limbs_variable(
+        &mut self,
+        limbs: &[Variable],
+        kind: ShiftKind,
+        amount: U32Variable,
+    ) -> Vec<Variable> {
+        let width = 32 * limbs.len();
+        let amount_bits = self.to_bits(
+            &amount.variable,
+            width.trailing_zeros() as usize,
+            Endianness::Little,
+        );
+
+        let mut bits = self.limbs_to_le_bits(limbs);
+        for (k, amount_bit) in amount_bits.into_iter().enumerate() {
+            let shifted = self.shift_le_bits(&bits, kind, 1 << k);
+            bits = shifted
+                .into_iter()
+                .zip_eq(bits)
+                .map(|(shifted, current)| self.select(amount_bit, shifted, current))
+                .collect();
+        }
+        self.le_bits_to_limbs(&bits)
+    }
+}
+
+/// Implements the shift and rotate operations with both constant (`usize`) and dynamic
+/// (`U32Variable`) amounts for a type made of little-endian u32 limbs.
+macro_rules! impl_shift_ops {
+    ($type:ty) => {
+        impl_shift_ops!($type, Shl, shl, ShiftKind::Shl);
+        impl_shift_ops!($type, Shr, shr, ShiftKind::Shr);
+        impl_shift_ops!($type, RotateLeft, rotate_left, ShiftKind::RotateLeft);
+        impl_shift_ops!($type, RotateRight, rotate_right, ShiftKind::RotateRight);
+    };
+    ($type:ty, $trait:ident, $method:ident, $kind:expr) => {
+        impl<L: PlonkParameters<D>, const D: usize> $trait<L, D, usize> for $type {
+            type Output = Self;
+
+            fn $method(self, rhs: usize, builder: &mut CircuitBuilder<L, D>) -> Self::Output {
+                let limbs = builder.shift_limbs(&self.variables(), $kind, rhs);
+                // Every limb is recomposed from 32 bits, so it is a valid u32.
+                Self::from_variables_unsafe(&limbs)
+            }
+        }
+
+        impl<L: PlonkParameters<D>, const D: usize> $trait<L, D, U32Variable> for $type {
+            type Output = Self;
+
+            fn $method(self, rhs: U32Variable, builder: &mut CircuitBuilder<L, D>) -> Self::Output {
+                let limbs = builder.shift_limbs_variable(&self.variables(), $kind, rhs);
+                // Every limb is recomposed from 32 bits, so it is a valid u32.
+                Self::from_variables_unsafe(&limbs)
+            }
+        }
+    };
+}
+
+impl_shift_ops!(U32Variable);
+impl_shift_ops!(U64Variable);
+impl_shift_ops!(U128Variable);
+impl_shift_ops!(U256Variable);
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn test_u32_shifts() {
+        let mut builder = DefaultBuilder::new();
+
+        let x = builder.read::<U32Variable>();
+        let amount = builder.read::<U32Variable>();
+
+        let shl = builder.shl(x, 7usize);
+        let shr = builder.shr(x, 7usize);
+        let rotl = builder.rotate_left(x, 7usize);
+        let rotr = builder.rotate_right(x, 7usize);
+        let shl_var = builder.shl(x, amount);
+        let shr_var = builder.shr(x, amount);
+        let rotl_var = builder.rotate_left(x, amount);
+        let rotr_var = builder.rotate_right(x, amount);
+        for result in [shl, shr, rotl, rotr, shl_var, shr_var, rotl_var, rotr_var] {
+            builder.write(result);
+        }
+
+        let circuit = builder.build();
+        let x_value = 0x8badf00du32;
+        for amount_value in [0u32, 7, 31] {
+            let mut input = circuit.input();
+            input.write::<U32Variable>(x_value);
+            input.write::<U32Variable>(amount_value);
+
+            let (proof, mut output) = circuit.prove(&input);
+            circuit.verify(&proof, &input, &output);
+
+            assert_eq!(output.read::<U32Variable>(), x_value << 7);
+            assert_eq!(output.read::<U32Variable>(), x_value >> 7);
+            assert_eq!(output.read::<U32Variable>(), x_value.rotate_left(7));
+            assert_eq!(output.read::<U32Variable>(), x_value.rotate_right(7));
+            assert_eq!(output.read::<U32Variable>(), x_value << amount_value);
+            assert_eq!(output.read::<U32Variable>(), x_value >> amount_value);
+            assert_eq!(
+                output.read::<U32Variable>(),
+                x_value.rotate_left(amount_value)
+            );
+            assert_eq!(
+                output.read::<U32Variable>(),
+                x_value.rotate_right(amount_value)
+            );
+        }
+    }
+
+    #[test]
+    fn test_u64_shifts() {
+        let mut builder = DefaultBuilder::new();
+
+        let x = builder.read::<U64Variable>();
+        let amount = builder.read::<U32Variable>();
+
+        let shl = builder.shl(x, 40usize);
+        let rotr = builder.rotate_right(x, 13usize);
+        let shr_var = builder.shr(x, amount);
+        let rotl_var = builder.rotate_left(x, amount);
+        for result in [shl, rotr, shr_var, rotl_var] {
+            builder.write(result);
+        }
+
+        let circuit = builder.build();
+        let x_value = 0x0123_4567_89ab_cdefu64;
+        let amount_value = 37u32;
+        let mut input = circuit.input();
+        input.write::<U64Variable>(x_value);
+        input.write::<U32Variable>(amount_value);
+
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        assert_eq!(output.read::<U64Variable>(), x_value << 40);
+        assert_eq!(output.read::<U64Variable>(), x_value.rotate_right(13));
+        assert_eq!(output.read::<U64Variable>(), x_value >> amount_value);
+        assert_eq!(
+            output.read::<U64Variable>(),
+            x_value.rotate_left(amount_value)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "was set twice with different values")]
+    fn test_variable_shift_amount_too_large() {
+        let mut builder = DefaultBuilder::new();
+        let x = builder.read::<U32Variable>();
+        let amount = builder.read::<U32Variable>();
+        let shl = builder.shl(x, amount);
+        builder.write(shl);
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        input.write::<U32Variable>(1);
+        input.write::<U32Variable>(32);
+
+        let (proof, output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+    }
+}
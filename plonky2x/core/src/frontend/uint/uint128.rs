@@ -3,8 +3,7 @@ use ethers::types::U128;
 use plonky2::hash::hash_types::RichField;
 
 use super::Uint;
-use crate::frontend::uint::num::biguint::{BigUintTarget, CircuitBuilderBiguint};
-use crate::frontend::uint::num::u32::gadgets::arithmetic_u32::U32Target;
+use crate::frontend::uint::num::biguint::BigUintTarget;
 use crate::frontend::vars::{EvmVariable, SSZVariable, U256Variable, U32Variable};
 use crate::prelude::{
     Add, BoolVariable, ByteVariable, Bytes32Variable, BytesVariable, CircuitBuilder,
@@ -2,9 +2,9 @@ use array_macro::array;
 use ethers::types::U256;
 use plonky2::hash::hash_types::RichField;
 
+use super::uint512::U512Variable;
 use super::Uint;
 use crate::frontend::uint::num::biguint::{BigUintTarget, CircuitBuilderBiguint};
-use crate::frontend::uint::num::u32::gadgets::arithmetic_u32::U32Target;
 use crate::frontend::vars::{EvmVariable, SSZVariable, U32Variable};
 use crate::prelude::{
     Add, BoolVariable, ByteVariable, Bytes32Variable, BytesVariable, CircuitBuilder,
@@ -44,3 +44,19 @@ impl Uint<8> for U256 {
 
 make_uint32_n!(U256Variable, U256, 8);
 make_uint32_n_tests!(U256Variable, U256, 8);
+
+impl U256Variable {
+    /// Multiplies two U256Variables, returning the full 512-bit product.
+    pub fn widening_mul<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        rhs: U256Variable,
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> U512Variable {
+        let product_biguint = builder
+            .api
+            .mul_biguint(&self.to_biguint(), &rhs.to_biguint());
+
+        // The product of two u256 values always fits in 512 bits, so the top carry limb is zero.
+        U512Variable::from_biguint_low(&product_biguint)
+    }
+}
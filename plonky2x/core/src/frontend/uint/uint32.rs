@@ -3,6 +3,7 @@ use std::fmt::Debug;
 use itertools::Itertools;
 use plonky2::iop::target::BoolTarget;
 
+use crate::frontend::builder::bits::Endianness;
 use crate::frontend::uint::num::biguint::{BigUintTarget, CircuitBuilderBiguint};
 use crate::frontend::uint::num::u32::gadgets::arithmetic_u32::{CircuitBuilderU32, U32Target};
 use crate::frontend::uint::num::u32::gadgets::multiple_comparison::list_lte_circuit;
@@ -71,13 +72,9 @@ impl EvmVariable for U32Variable {
         &self,
         builder: &mut CircuitBuilder<L, D>,
     ) -> Vec<ByteVariable> {
-        let mut bits = builder.api.split_le(self.variable.0, 32);
-        bits.reverse();
+        let bits = builder.to_bits(self, 32, Endianness::Big);
         bits.chunks(8)
-            .map(|chunk| {
-                let targets = chunk.iter().map(|b| b.target).collect_vec();
-                ByteVariable::from_targets(&targets)
-            })
+            .map(|chunk| ByteVariable(chunk.try_into().unwrap()))
             .collect()
     }
 
@@ -187,6 +184,22 @@ impl<L: PlonkParameters<D>, const D: usize> Div<L, D> for U32Variable {
     }
 }
 
+impl<L: PlonkParameters<D>, const D: usize> Rem<L, D> for U32Variable {
+    type Output = Self;
+
+    fn rem(self, rhs: U32Variable, builder: &mut CircuitBuilder<L, D>) -> Self::Output {
+        let self_biguint = BigUintTarget {
+            limbs: vec![self.into()],
+        };
+        let rhs_biguint = BigUintTarget {
+            limbs: vec![rhs.into()],
+        };
+
+        let rem_biguint = builder.api.rem_biguint(&self_biguint, &rhs_biguint);
+        rem_biguint.limbs[0].into()
+    }
+}
+
 impl<L: PlonkParameters<D>, const D: usize> Add<L, D> for U32Variable {
     type Output = Self;
 
@@ -238,6 +251,75 @@ impl U32Variable {
         result
     }
 
+    /// Computes `self + rhs + carry`, returning the low 32 bits and the carry out.
+    pub fn add_carrying<L: PlonkParameters<D>, const D: usize>(
+        self,
+        rhs: U32Variable,
+        carry: BoolVariable,
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> (U32Variable, BoolVariable) {
+        // A boolean is always within u32 range.
+        let carry = U32Target::from_target_unsafe(carry.variable.0);
+        let (sum, carry_out) = builder.api.add_many_u32(&[self.into(), rhs.into(), carry]);
+        // The sum is at most 2 * (2^32 - 1) + 1, so the carry out is either 0 or 1.
+        let carry_out = BoolVariable::from_variables_unsafe(&[Variable(carry_out.target)]);
+        (sum.into(), carry_out)
+    }
+
+    /// Computes `self - rhs - borrow`, returning the wrapped difference and the borrow out.
+    pub fn sub_borrowing<L: PlonkParameters<D>, const D: usize>(
+        self,
+        rhs: U32Variable,
+        borrow: BoolVariable,
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> (U32Variable, BoolVariable) {
+        // A boolean is always within u32 range.
+        let borrow = U32Target::from_target_unsafe(borrow.variable.0);
+        let (diff, borrow_out) = builder.api.sub_u32(self.into(), rhs.into(), borrow);
+        let borrow_out = BoolVariable::from_variables_unsafe(&[Variable(borrow_out.target)]);
+        (diff.into(), borrow_out)
+    }
+
+    /// Computes the full 64-bit product of two u32 values, returned as `(low, high)` limbs.
+    pub fn widening_mul<L: PlonkParameters<D>, const D: usize>(
+        self,
+        rhs: U32Variable,
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> (U32Variable, U32Variable) {
+        let (low, high) = builder.api.mul_u32(self.into(), rhs.into());
+        (low.into(), high.into())
+    }
+
+    /// Computes the quotient and remainder of `self / rhs`.
+    ///
+    /// Both values are supplied by a witness generator, range-checked to 32 bits and constrained
+    /// so that `quotient * rhs + remainder == self` and `remainder < rhs`.
+    pub fn div_rem<L: PlonkParameters<D>, const D: usize>(
+        self,
+        rhs: U32Variable,
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> (U32Variable, U32Variable) {
+        let self_biguint = BigUintTarget {
+            limbs: vec![self.into()],
+        };
+        let rhs_biguint = BigUintTarget {
+            limbs: vec![rhs.into()],
+        };
+        let (quotient, rem) = builder.api.div_rem_biguint(&self_biguint, &rhs_biguint);
+        (quotient.limbs[0].into(), rem.limbs[0].into())
+    }
+
+    /// Widens the integer to a U256Variable, with the higher limbs set to zero.
+    pub fn to_u256<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> U256Variable {
+        let zero = builder.zero::<U32Variable>();
+        let mut limbs = [zero; 8];
+        limbs[0] = *self;
+        U256Variable { limbs }
+    }
+
     pub fn from_be_bits<L: PlonkParameters<D>, const D: usize>(
         bools: &[BoolVariable],
         builder: &mut CircuitBuilder<L, D>,
@@ -260,11 +342,8 @@ impl U32Variable {
         &self,
         builder: &mut CircuitBuilder<L, D>,
     ) -> [BoolVariable; 32] {
-        let mut bits = builder.api.split_le(self.variable.0, 32);
-        bits.reverse();
-        bits.iter()
-            .map(|b| (*b).into())
-            .collect_vec()
+        builder
+            .to_bits(self, 32, Endianness::Big)
             .try_into()
             .unwrap()
     }
@@ -272,6 +351,7 @@ impl U32Variable {
 
 #[cfg(test)]
 mod tests {
+    use ethers::types::U256;
     use rand::Rng;
 
     use crate::frontend::vars::EvmVariable;
@@ -294,6 +374,19 @@ mod tests {
         circuit.data.verify(proof).unwrap();
     }
 
+    #[test]
+    fn test_to_u256() {
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let var = U32Variable::constant(&mut builder, 0x12345678);
+        let var_u256 = var.to_u256(&mut builder);
+        let expected = U256Variable::constant(&mut builder, U256::from(0x12345678));
+        builder.assert_is_equal(var_u256, expected);
+        let circuit = builder.build();
+        let pw = PartialWitness::new();
+        let proof = circuit.data.prove(pw).unwrap();
+        circuit.data.verify(proof).unwrap();
+    }
+
     #[test]
     fn test_u32_evm() {
         let mut builder = CircuitBuilder::<L, D>::new();
@@ -401,4 +494,70 @@ mod tests {
         let proof = circuit.data.prove(pw).unwrap();
         circuit.data.verify(proof).unwrap();
     }
+
+    #[test]
+    fn test_u32_carrying_ops() {
+        let mut builder = CircuitBuilder::<L, D>::new();
+
+        let mut rng = rand::thread_rng();
+        let operand_a: u32 = rng.gen();
+        let operand_b: u32 = rng.gen();
+
+        let a = U32Variable::constant(&mut builder, operand_a);
+        let b = U32Variable::constant(&mut builder, operand_b);
+        let carry_in = builder._true();
+
+        let (sum, carry_out) = a.add_carrying(b, carry_in, &mut builder);
+        let expected = operand_a as u64 + operand_b as u64 + 1;
+        let expected_sum = U32Variable::constant(&mut builder, expected as u32);
+        let expected_carry = BoolVariable::constant(&mut builder, expected >> 32 == 1);
+        builder.assert_is_equal(sum, expected_sum);
+        builder.assert_is_equal(carry_out, expected_carry);
+
+        let (diff, borrow_out) = a.sub_borrowing(b, carry_in, &mut builder);
+        let (expected_diff, borrow_1) = operand_a.overflowing_sub(operand_b);
+        let (expected_diff, borrow_2) = expected_diff.overflowing_sub(1);
+        let expected_diff = U32Variable::constant(&mut builder, expected_diff);
+        let expected_borrow = BoolVariable::constant(&mut builder, borrow_1 || borrow_2);
+        builder.assert_is_equal(diff, expected_diff);
+        builder.assert_is_equal(borrow_out, expected_borrow);
+
+        let (low, high) = a.widening_mul(b, &mut builder);
+        let expected = operand_a as u64 * operand_b as u64;
+        let expected_low = U32Variable::constant(&mut builder, expected as u32);
+        let expected_high = U32Variable::constant(&mut builder, (expected >> 32) as u32);
+        builder.assert_is_equal(low, expected_low);
+        builder.assert_is_equal(high, expected_high);
+
+        let circuit = builder.build();
+        let pw = PartialWitness::new();
+        let proof = circuit.data.prove(pw).unwrap();
+        circuit.data.verify(proof).unwrap();
+    }
+
+    #[test]
+    fn test_u32_div_rem() {
+        let mut builder = CircuitBuilder::<L, D>::new();
+
+        let mut rng = rand::thread_rng();
+        let operand_a: u32 = rng.gen();
+        let operand_b: u32 = rng.gen_range(1..u16::MAX as u32);
+
+        let a = U32Variable::constant(&mut builder, operand_a);
+        let b = U32Variable::constant(&mut builder, operand_b);
+
+        let (quotient, rem) = a.div_rem(b, &mut builder);
+        let expected_quotient = U32Variable::constant(&mut builder, operand_a / operand_b);
+        let expected_rem = U32Variable::constant(&mut builder, operand_a % operand_b);
+        builder.assert_is_equal(quotient, expected_quotient);
+        builder.assert_is_equal(rem, expected_rem);
+
+        let rem = builder.rem(a, b);
+        builder.assert_is_equal(rem, expected_rem);
+
+        let circuit = builder.build();
+        let pw = PartialWitness::new();
+        let proof = circuit.data.prove(pw).unwrap();
+        circuit.data.verify(proof).unwrap();
+    }
 }
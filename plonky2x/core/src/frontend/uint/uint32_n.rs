@@ -63,26 +63,16 @@ macro_rules! make_uint32_n {
                 &self,
                 builder: &mut CircuitBuilder<L, D>,
             ) -> Vec<ByteVariable> {
-                self.limbs
-                    .iter()
-                    .rev()
-                    .flat_map(|x| x.encode(builder))
-                    .collect::<Vec<_>>()
+                self.to_biguint_variable().encode(builder)
             }
 
             fn decode<L: PlonkParameters<D>, const D: usize>(
                 builder: &mut CircuitBuilder<L, D>,
                 bytes: &[ByteVariable],
             ) -> Self {
-                assert_eq!(bytes.len(), $c * 4);
-                let mut limbs = [U32Variable::init_unsafe(builder); $c];
-                for i in 0..$c {
-                    limbs[i] = U32Variable::decode(builder, &bytes[i * 4..(i + 1) * 4]);
-                }
-                limbs.reverse();
-                Self {
-                    limbs
-                }
+                let value =
+                    $crate::frontend::uint::biguint::BigUintVariable::<$c>::decode(builder, bytes);
+                value.into()
             }
 
             fn encode_value<F: RichField>(value: Self::ValueType<F>) -> Vec<u8> {
@@ -111,24 +101,27 @@ macro_rules! make_uint32_n {
             }
         }
 
+        impl From<$a> for $crate::frontend::uint::biguint::BigUintVariable<$c> {
+            fn from(value: $a) -> Self {
+                Self { limbs: value.limbs }
+            }
+        }
+
+        impl From<$crate::frontend::uint::biguint::BigUintVariable<$c>> for $a {
+            fn from(value: $crate::frontend::uint::biguint::BigUintVariable<$c>) -> Self {
+                Self { limbs: value.limbs }
+            }
+        }
+
         impl<L: PlonkParameters<D>, const D: usize> Zero<L, D> for $a {
             fn zero(builder: &mut CircuitBuilder<L, D>) -> Self {
-                let zero = U32Variable::zero(builder);
-                Self {
-                    limbs: [zero; $c],
-                }
+                $a::from_biguint_variable(Zero::zero(builder))
             }
         }
 
         impl<L: PlonkParameters<D>, const D: usize> One<L, D> for $a {
             fn one(builder: &mut CircuitBuilder<L, D>) -> Self {
-                let zero = U32Variable::zero(builder);
-                let one = U32Variable::one(builder);
-                let mut limbs = [zero; $c];
-                limbs[0] = one;
-                Self {
-                    limbs
-                }
+                $a::from_biguint_variable(One::one(builder))
             }
         }
 
@@ -136,33 +129,7 @@ macro_rules! make_uint32_n {
             type Output = Self;
 
             fn add(self, rhs: $a, builder: &mut CircuitBuilder<L, D>) -> Self::Output {
-                let self_targets = self
-                    .limbs
-                    .iter()
-                    .map(|x| U32Target::from(*x))
-                    .collect::<Vec<_>>();
-                let rhs_targets = rhs
-                    .limbs
-                    .iter()
-                    .map(|x| U32Target::from(*x))
-                    .collect::<Vec<_>>();
-                assert_eq!(self_targets.len(), rhs_targets.len());
-                assert_eq!(self_targets.len(), $c);
-
-                let self_biguint = BigUintTarget {
-                    limbs: self_targets,
-                };
-                let rhs_biguint = BigUintTarget { limbs: rhs_targets };
-                let sum_biguint = builder.api.add_biguint(&self_biguint, &rhs_biguint);
-
-                let mut limbs: [U32Variable; $c] = Self::zero(builder).limbs;
-                for i in 0..$c {
-                    limbs[i] = sum_biguint.limbs[i].into();
-                }
-
-                Self {
-                    limbs
-                }
+                builder.add(self.to_biguint_variable(), rhs.to_biguint_variable()).into()
             }
         }
 
@@ -170,33 +137,7 @@ macro_rules! make_uint32_n {
             type Output = Self;
 
             fn sub(self, rhs: $a, builder: &mut CircuitBuilder<L, D>) -> Self::Output {
-                let self_targets = self
-                    .limbs
-                    .iter()
-                    .map(|x| U32Target::from(*x))
-                    .collect::<Vec<_>>();
-                let rhs_targets = rhs
-                    .limbs
-                    .iter()
-                    .map(|x| U32Target::from(*x))
-                    .collect::<Vec<_>>();
-                assert_eq!(self_targets.len(), rhs_targets.len());
-                assert_eq!(self_targets.len(), $c);
-
-                let self_biguint = BigUintTarget {
-                    limbs: self_targets,
-                };
-                let rhs_biguint = BigUintTarget { limbs: rhs_targets };
-                let diff_biguint = builder.api.sub_biguint(&self_biguint, &rhs_biguint);
-
-                let mut limbs: [U32Variable; $c] = Self::zero(builder).limbs;
-                for i in 0..$c {
-                    limbs[i] = diff_biguint.limbs[i].into();
-                }
-
-                Self {
-                    limbs
-                }
+                builder.sub(self.to_biguint_variable(), rhs.to_biguint_variable()).into()
             }
         }
 
@@ -204,33 +145,7 @@ macro_rules! make_uint32_n {
             type Output = Self;
 
             fn mul(self, rhs: $a, builder: &mut CircuitBuilder<L, D>) -> Self::Output {
-                let self_targets = self
-                    .limbs
-                    .iter()
-                    .map(|x| U32Target::from(*x))
-                    .collect::<Vec<_>>();
-                let rhs_targets = rhs
-                    .limbs
-                    .iter()
-                    .map(|x| U32Target::from(*x))
-                    .collect::<Vec<_>>();
-                assert_eq!(self_targets.len(), rhs_targets.len());
-                assert_eq!(self_targets.len(), $c);
-
-                let self_biguint = BigUintTarget {
-                    limbs: self_targets,
-                };
-                let rhs_biguint = BigUintTarget { limbs: rhs_targets };
-                let product_biguint = builder.api.mul_biguint(&self_biguint, &rhs_biguint);
-
-                let mut limbs: [U32Variable; $c] = Self::zero(builder).limbs;
-                for i in 0..$c {
-                    limbs[i] = product_biguint.limbs[i].into();
-                }
-
-                Self {
-                    limbs
-                }
+                builder.mul(self.to_biguint_variable(), rhs.to_biguint_variable()).into()
             }
         }
 
@@ -238,33 +153,7 @@ macro_rules! make_uint32_n {
             type Output = Self;
 
             fn div(self, rhs: $a, builder: &mut CircuitBuilder<L, D>) -> Self::Output {
-                let self_targets = self
-                    .limbs
-                    .iter()
-                    .map(|x| U32Target::from(*x))
-                    .collect::<Vec<_>>();
-                let rhs_targets = rhs
-                    .limbs
-                    .iter()
-                    .map(|x| U32Target::from(*x))
-                    .collect::<Vec<_>>();
-                assert_eq!(self_targets.len(), rhs_targets.len());
-                assert_eq!(self_targets.len(), $c);
-
-                let self_biguint = BigUintTarget {
-                    limbs: self_targets,
-                };
-                let rhs_biguint = BigUintTarget { limbs: rhs_targets };
-                let quotient_biguint = builder.api.div_biguint(&self_biguint, &rhs_biguint);
-
-                let mut limbs: [U32Variable; $c] = Self::zero(builder).limbs;
-                for i in 0..$c {
-                    limbs[i] = quotient_biguint.limbs[i].into();
-                }
-
-                Self {
-                    limbs
-                }
+                builder.div(self.to_biguint_variable(), rhs.to_biguint_variable()).into()
             }
         }
 
@@ -272,50 +161,112 @@ macro_rules! make_uint32_n {
             type Output = Self;
 
             fn rem(self, rhs: $a, builder: &mut CircuitBuilder<L, D>) -> Self::Output {
-                let self_targets = self
-                    .limbs
-                    .iter()
-                    .map(|x| U32Target::from(*x))
-                    .collect::<Vec<_>>();
-                let rhs_targets = rhs
-                    .limbs
-                    .iter()
-                    .map(|x| U32Target::from(*x))
-                    .collect::<Vec<_>>();
-                assert_eq!(self_targets.len(), rhs_targets.len());
-                assert_eq!(self_targets.len(), $c);
-
-                let self_biguint = BigUintTarget {
-                    limbs: self_targets,
-                };
-                let rhs_biguint = BigUintTarget { limbs: rhs_targets };
-                let rem_biguint = builder.api.rem_biguint(&self_biguint, &rhs_biguint);
-
-                let mut limbs: [U32Variable; $c] = Self::zero(builder).limbs;
-                for i in 0..$c {
-                    limbs[i] = rem_biguint.limbs[i].into();
-                }
-
-                Self {
-                    limbs
-                }
+                builder.rem(self.to_biguint_variable(), rhs.to_biguint_variable()).into()
             }
         }
 
         impl<L: PlonkParameters<D>, const D: usize> LessThanOrEqual<L, D> for $a {
             #[must_use]
             fn lte(self, rhs: Self, builder: &mut CircuitBuilder<L, D>) -> BoolVariable {
-                let mut lte_acc = builder.constant::<BoolVariable>(false);
-                let mut equal_so_far = builder.constant::<BoolVariable>(true);
-                for i in 0..$c {
-                    let lhs = self.limbs[$c - i - 1];
-                    let rhs = rhs.limbs[$c - i - 1];
-                    let lte = builder.lte(lhs, rhs);
-                    lte_acc = builder.select(equal_so_far, lte, lte_acc);
-                    let equal = builder.is_equal(lhs, rhs);
-                    equal_so_far = builder.and(equal_so_far, equal);
-                }
-                builder.or(lte_acc, equal_so_far)
+                builder.lte(self.to_biguint_variable(), rhs.to_biguint_variable())
+            }
+        }
+
+        impl $a {
+            /// Views the integer as a BigUintVariable with the same limbs.
+            pub fn to_biguint_variable(
+                self,
+            ) -> $crate::frontend::uint::biguint::BigUintVariable<$c> {
+                self.into()
+            }
+
+            /// Views a BigUintVariable with the same number of limbs as this integer type.
+            pub fn from_biguint_variable(
+                value: $crate::frontend::uint::biguint::BigUintVariable<$c>,
+            ) -> Self {
+                value.into()
+            }
+
+            /// Views the limbs as a BigUintTarget for use with the biguint gadgets.
+            pub(crate) fn to_biguint(&self) -> BigUintTarget {
+                self.to_biguint_variable().to_biguint()
+            }
+
+            /// Takes the least significant limbs of a BigUintTarget, discarding any higher limbs.
+            pub(crate) fn from_biguint_low(value: &BigUintTarget) -> Self {
+                let value =
+                    $crate::frontend::uint::biguint::BigUintVariable::<$c>::from_biguint_low(value);
+                value.into()
+            }
+
+            /// Divides two integers, returning both the quotient and the remainder.
+            ///
+            /// The quotient and remainder are supplied by a witness generator and constrained so
+            /// that `quotient * rhs + remainder == self` and `remainder < rhs`.
+            pub fn div_rem<L: PlonkParameters<D>, const D: usize>(
+                self,
+                rhs: $a,
+                builder: &mut CircuitBuilder<L, D>,
+            ) -> (Self, Self) {
+                let (quotient, rem) = self
+                    .to_biguint_variable()
+                    .div_rem(rhs.to_biguint_variable(), builder);
+                (quotient.into(), rem.into())
+            }
+
+            /// Narrows the integer to a U32Variable, returning the least significant limb and
+            /// whether any of the discarded high limbs were non-zero.
+            pub fn try_to_u32<L: PlonkParameters<D>, const D: usize>(
+                &self,
+                builder: &mut CircuitBuilder<L, D>,
+            ) -> (U32Variable, BoolVariable) {
+                self.to_biguint_variable().try_to_u32(builder)
+            }
+
+            /// Narrows the integer to a U32Variable, asserting that it fits in 32 bits.
+            pub fn to_u32<L: PlonkParameters<D>, const D: usize>(
+                &self,
+                builder: &mut CircuitBuilder<L, D>,
+            ) -> U32Variable {
+                self.to_biguint_variable().to_u32(builder)
+            }
+
+            /// Adds two integers, returning the wrapped sum and whether the addition overflowed.
+            pub fn overflowing_add<L: PlonkParameters<D>, const D: usize>(
+                self,
+                rhs: $a,
+                builder: &mut CircuitBuilder<L, D>,
+            ) -> (Self, BoolVariable) {
+                let (sum, overflow) = self
+                    .to_biguint_variable()
+                    .overflowing_add(rhs.to_biguint_variable(), builder);
+                (sum.into(), overflow)
+            }
+
+            /// Subtracts two integers, returning the wrapped difference and whether the
+            /// subtraction underflowed.
+            pub fn overflowing_sub<L: PlonkParameters<D>, const D: usize>(
+                self,
+                rhs: $a,
+                builder: &mut CircuitBuilder<L, D>,
+            ) -> (Self, BoolVariable) {
+                let (diff, underflow) = self
+                    .to_biguint_variable()
+                    .overflowing_sub(rhs.to_biguint_variable(), builder);
+                (diff.into(), underflow)
+            }
+
+            /// Multiplies two integers, returning the wrapped product and whether the
+            /// multiplication overflowed.
+            pub fn overflowing_mul<L: PlonkParameters<D>, const D: usize>(
+                self,
+                rhs: $a,
+                builder: &mut CircuitBuilder<L, D>,
+            ) -> (Self, BoolVariable) {
+                let (product, overflow) = self
+                    .to_biguint_variable()
+                    .overflowing_mul(rhs.to_biguint_variable(), builder);
+                (product.into(), overflow)
             }
         }
     };
@@ -428,6 +379,178 @@ macro_rules! make_uint32_n_tests {
                 circuit.data.verify(proof).unwrap();
             }
 
+            #[test]
+            fn test_u32n_try_to_u32() {
+                let mut rng = OsRng;
+
+                let small_value: u32 = rng.gen();
+                let mut small_limbs = [0u32; $c];
+                small_limbs[0] = small_value;
+                let mut large_limbs = small_limbs;
+                large_limbs[$c - 1] = rng.gen_range(1..u32::MAX);
+                let small = <$b as Uint<$c>>::from_u32_limbs(small_limbs);
+                let large = <$b as Uint<$c>>::from_u32_limbs(large_limbs);
+
+                let mut builder = CircuitBuilder::<L, D>::new();
+                let true_v = builder._true();
+                let false_v = builder._false();
+                let expected = builder.constant::<U32Variable>(small_value);
+
+                let small = $a::constant(&mut builder, small);
+                let (narrowed, overflow) = small.try_to_u32(&mut builder);
+                builder.assert_is_equal(narrowed, expected);
+                builder.assert_is_equal(overflow, false_v);
+                let narrowed = small.to_u32(&mut builder);
+                builder.assert_is_equal(narrowed, expected);
+
+                let large = $a::constant(&mut builder, large);
+                let (_, overflow) = large.try_to_u32(&mut builder);
+                builder.assert_is_equal(overflow, true_v);
+
+                let circuit = builder.build();
+                let pw = PartialWitness::new();
+
+                let proof = circuit.data.prove(pw).unwrap();
+                circuit.data.verify(proof).unwrap();
+            }
+
+            #[test]
+            fn test_u32n_comparisons() {
+                let mut rng = OsRng;
+
+                let mut small_limbs: [u32; $c] = [rng.gen(); $c];
+                small_limbs[$c - 1] = rng.gen_range(0..u32::MAX / 2);
+                let mut large_limbs = small_limbs;
+                large_limbs[$c - 1] += 1;
+                large_limbs[0] = 0;
+                let small = <$b as Uint<$c>>::from_u32_limbs(small_limbs);
+                let large = <$b as Uint<$c>>::from_u32_limbs(large_limbs);
+
+                let mut builder = CircuitBuilder::<L, D>::new();
+                let true_v = builder._true();
+                let false_v = builder._false();
+
+                let small = $a::constant(&mut builder, small);
+                let large = $a::constant(&mut builder, large);
+
+                let result = builder.lte(small, large);
+                builder.assert_is_equal(result, true_v);
+                let result = builder.lte(large, small);
+                builder.assert_is_equal(result, false_v);
+                let result = builder.lte(small, small);
+                builder.assert_is_equal(result, true_v);
+
+                let result = builder.lt(small, large);
+                builder.assert_is_equal(result, true_v);
+                let result = builder.lt(small, small);
+                builder.assert_is_equal(result, false_v);
+
+                let result = builder.gt(large, small);
+                builder.assert_is_equal(result, true_v);
+                let result = builder.gt(small, large);
+                builder.assert_is_equal(result, false_v);
+
+                let result = builder.gte(large, large);
+                builder.assert_is_equal(result, true_v);
+                let result = builder.gte(small, large);
+                builder.assert_is_equal(result, false_v);
+
+                let result = builder.is_equal(small, small);
+                builder.assert_is_equal(result, true_v);
+                let result = builder.is_equal(small, large);
+                builder.assert_is_equal(result, false_v);
+
+                let circuit = builder.build();
+                let pw = PartialWitness::new();
+
+                let proof = circuit.data.prove(pw).unwrap();
+                circuit.data.verify(proof).unwrap();
+            }
+
+            #[test]
+            fn test_u32n_div_rem() {
+                let mut rng = OsRng;
+
+                let a = <$b as Uint<$c>>::from_u32_limbs([rng.gen(); $c]);
+                let mut b_limbs = [0u32; $c];
+                b_limbs[0] = rng.gen_range(1..u32::MAX);
+                b_limbs[$c / 2] = rng.gen_range(1..u32::MAX);
+                let b = <$b as Uint<$c>>::from_u32_limbs(b_limbs);
+
+                let mut builder = CircuitBuilder::<L, D>::new();
+
+                let a_var = $a::constant(&mut builder, a);
+                let b_var = $a::constant(&mut builder, b);
+
+                let (quotient, rem) = a_var.div_rem(b_var, &mut builder);
+                let div = builder.div(a_var, b_var);
+                let rem_op = builder.rem(a_var, b_var);
+                builder.assert_is_equal(quotient, div);
+                builder.assert_is_equal(rem, rem_op);
+
+                // quotient * b + rem == a
+                let product = builder.mul(quotient, b_var);
+                let reconstructed = builder.add(product, rem);
+                builder.assert_is_equal(reconstructed, a_var);
+
+                let rem_lt_b = builder.lt(rem, b_var);
+                let true_v = builder._true();
+                builder.assert_is_equal(rem_lt_b, true_v);
+
+                let circuit = builder.build();
+                let pw = PartialWitness::new();
+
+                let proof = circuit.data.prove(pw).unwrap();
+                circuit.data.verify(proof).unwrap();
+            }
+
+            #[test]
+            fn test_u32n_overflowing_ops() {
+                let max = <$b as Uint<$c>>::from_u32_limbs([u32::MAX; $c]);
+                let one = <$b as Uint<$c>>::from_u32_limbs(array_macro::array![i => (i == 0) as u32; $c]);
+                let two = <$b as Uint<$c>>::from_u32_limbs(array_macro::array![i => 2 * (i == 0) as u32; $c]);
+
+                let mut builder = CircuitBuilder::<L, D>::new();
+                let true_v = builder._true();
+                let false_v = builder._false();
+
+                let max_var = $a::constant(&mut builder, max);
+                let one_var = $a::constant(&mut builder, one);
+                let two_var = $a::constant(&mut builder, two);
+
+                let (sum, overflow) = max_var.overflowing_add(one_var, &mut builder);
+                let expected = $a::constant(&mut builder, max.overflowing_add(one).0);
+                builder.assert_is_equal(sum, expected);
+                builder.assert_is_equal(overflow, true_v);
+
+                let (sum, overflow) = one_var.overflowing_add(one_var, &mut builder);
+                builder.assert_is_equal(sum, two_var);
+                builder.assert_is_equal(overflow, false_v);
+
+                let (diff, underflow) = one_var.overflowing_sub(two_var, &mut builder);
+                builder.assert_is_equal(diff, max_var);
+                builder.assert_is_equal(underflow, true_v);
+
+                let (diff, underflow) = two_var.overflowing_sub(one_var, &mut builder);
+                builder.assert_is_equal(diff, one_var);
+                builder.assert_is_equal(underflow, false_v);
+
+                let (product, overflow) = max_var.overflowing_mul(two_var, &mut builder);
+                let expected = $a::constant(&mut builder, max.overflowing_mul(two).0);
+                builder.assert_is_equal(product, expected);
+                builder.assert_is_equal(overflow, true_v);
+
+                let (product, overflow) = max_var.overflowing_mul(one_var, &mut builder);
+                builder.assert_is_equal(product, max_var);
+                builder.assert_is_equal(overflow, false_v);
+
+                let circuit = builder.build();
+                let pw = PartialWitness::new();
+
+                let proof = circuit.data.prove(pw).unwrap();
+                circuit.data.verify(proof).unwrap();
+            }
+
             #[test]
             fn test_u256_mul() {
                 const D: usize = 2;
@@ -2,9 +2,9 @@ use array_macro::array;
 use ethers::types::U512;
 use plonky2::hash::hash_types::RichField;
 
+use super::uint256::U256Variable;
 use super::Uint;
 use crate::frontend::uint::num::biguint::{BigUintTarget, CircuitBuilderBiguint};
-use crate::frontend::uint::num::u32::gadgets::arithmetic_u32::U32Target;
 use crate::frontend::vars::{EvmVariable, SSZVariable, U32Variable};
 use crate::prelude::{
     Add, BoolVariable, ByteVariable, Bytes32Variable, BytesVariable, CircuitBuilder,
@@ -44,3 +44,65 @@ impl Uint<16> for U512 {
 
 make_uint32_n!(U512Variable, U512, 16);
 make_uint32_n_tests!(U512Variable, U512, 16);
+
+impl U512Variable {
+    /// Reduces the integer modulo a 256-bit modulus.
+    ///
+    /// The quotient and remainder are supplied by a witness generator and constrained so that
+    /// `quotient * modulus + remainder == self` and `remainder < modulus`.
+    pub fn reduce<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        modulus: U256Variable,
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> U256Variable {
+        let rem_biguint = builder
+            .api
+            .rem_biguint(&self.to_biguint(), &modulus.to_biguint());
+        U256Variable::from_biguint_low(&rem_biguint)
+    }
+}
+
+#[cfg(test)]
+mod reduction_tests {
+    use ethers::types::{U256, U512};
+
+    use crate::frontend::uint::uint512::U512Variable;
+    use crate::prelude::*;
+
+    type L = DefaultParameters;
+    const D: usize = 2;
+
+    #[test]
+    fn test_u256_mulmod() {
+        let mut builder = CircuitBuilder::<L, D>::new();
+
+        // The secp256k1 base field modulus.
+        let modulus_value = U256::from_str_radix(
+            "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f",
+            16,
+        )
+        .unwrap();
+        let a_value = modulus_value - U256::from(12345);
+        let b_value = U256::MAX - U256::from(67890);
+
+        let product_value = a_value.full_mul(b_value);
+        let expected_value = U256::try_from(product_value % U512::from(modulus_value)).unwrap();
+
+        let a = builder.constant::<U256Variable>(a_value);
+        let b = builder.constant::<U256Variable>(b_value);
+        let modulus = builder.constant::<U256Variable>(modulus_value);
+
+        let product = a.widening_mul(b, &mut builder);
+        let expected_product = builder.constant::<U512Variable>(product_value);
+        builder.assert_is_equal(product, expected_product);
+
+        let reduced = product.reduce(modulus, &mut builder);
+        let expected = builder.constant::<U256Variable>(expected_value);
+        builder.assert_is_equal(reduced, expected);
+
+        let circuit = builder.build();
+        let pw = PartialWitness::new();
+        let proof = circuit.data.prove(pw).unwrap();
+        circuit.data.verify(proof).unwrap();
+    }
+}
@@ -3,10 +3,10 @@ use ethers::types::U64;
 use plonky2::field::types::Field;
 use plonky2::hash::hash_types::RichField;
 
+use super::uint128::U128Variable;
 use super::uint256::U256Variable;
 use super::Uint;
 use crate::frontend::uint::num::biguint::{BigUintTarget, CircuitBuilderBiguint};
-use crate::frontend::uint::num::u32::gadgets::arithmetic_u32::U32Target;
 use crate::frontend::vars::{EvmVariable, SSZVariable, U32Variable};
 use crate::prelude::{
     Add, BoolVariable, ByteVariable, Bytes32Variable, BytesVariable, CircuitBuilder,
@@ -51,6 +51,32 @@ make_uint32_n!(U64Variable, u64, 2);
 make_uint32_n_tests!(U64Variable, u64, 2);
 
 impl U64Variable {
+    /// Decomposes a Variable into a U64Variable, range-checking both u32 limbs.
+    ///
+    /// Every Goldilocks field element fits in 64 bits, so this never fails for honest inputs. The
+    /// decomposition is constrained to be the canonical one, i.e. the limbs never encode a value
+    /// greater than or equal to the field order.
+    pub fn from_variable<L: PlonkParameters<D>, const D: usize>(
+        builder: &mut CircuitBuilder<L, D>,
+        variable: Variable,
+    ) -> Self {
+        let (low, high) = builder.api.split_low_high(variable.0, 32, 64);
+
+        // The field order is 2^64 - 2^32 + 1, so the only non-canonical decompositions have a high
+        // limb of 2^32 - 1 and a nonzero low limb.
+        let max_high = builder.api.constant(L::Field::from_canonical_u32(u32::MAX));
+        let high_is_max = builder.api.is_equal(high, max_high);
+        let low_if_high_is_max = builder.api.mul(high_is_max.target, low);
+        builder.api.assert_zero(low_if_high_is_max);
+
+        Self {
+            limbs: [
+                U32Variable::from_variables_unsafe(&[Variable(low)]),
+                U32Variable::from_variables_unsafe(&[Variable(high)]),
+            ],
+        }
+    }
+
     /// Converts a U64Variable to Variable with overflow.
     ///
     /// Note: This function assumes that the U64 is in the range [0, 2^64-2^32+1). Otherwise, it
@@ -80,4 +106,48 @@ impl U64Variable {
         }
         result
     }
+
+    /// Multiplies two U64Variables, returning the full 128-bit product.
+    pub fn widening_mul<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        rhs: U64Variable,
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> U128Variable {
+        let product_biguint = builder
+            .api
+            .mul_biguint(&self.to_biguint(), &rhs.to_biguint());
+
+        // The product of two u64 values always fits in 128 bits, so the top carry limb is zero.
+        U128Variable::from_biguint_low(&product_biguint)
+    }
+}
+
+#[cfg(test)]
+mod widening_tests {
+    use ethers::types::U128;
+
+    use crate::prelude::*;
+
+    type L = DefaultParameters;
+    const D: usize = 2;
+
+    #[test]
+    fn test_u64_widening_mul() {
+        let mut builder = CircuitBuilder::<L, D>::new();
+
+        let a_value = u64::MAX - 7;
+        let b_value = 0xdeadbeef_12345678u64;
+        let expected_value = U128::from(a_value) * U128::from(b_value);
+
+        let a = builder.constant::<U64Variable>(a_value);
+        let b = builder.constant::<U64Variable>(b_value);
+        let product = a.widening_mul(b, &mut builder);
+        let expected = builder.constant::<U128Variable>(expected_value);
+        builder.assert_is_equal(product, expected);
+
+        let circuit = builder.build();
+        let pw = PartialWitness::new();
+        let proof = circuit.data.prove(pw).unwrap();
+        circuit.data.verify(proof).unwrap();
+    }
 }
@@ -0,0 +1,210 @@
+use std::fmt::Debug;
+
+use plonky2::field::types::PrimeField64;
+
+use crate::frontend::builder::bits::Endianness;
+use crate::frontend::vars::EvmVariable;
+use crate::prelude::*;
+
+/// A variable in the circuit representing a u8 value.
+///
+/// Unlike `ByteVariable`, which stores eight boolean variables, the value is stored as a single
+/// field element that is range-checked with a lookup into a byte table. Bits are only computed when
+/// they are explicitly requested, which makes this representation much cheaper for circuits that
+/// move bytes around without doing bitwise operations on them.
+///
+/// `BytesVariable` and `Bytes32Variable` are still arrays of `ByteVariable`, since the hashing and
+/// bitwise gadgets built on them need every bit. Convert at the boundary with
+/// `U8Variable::from_byte_variable` and `U8Variable::to_byte_variable`.
+#[derive(Debug, Clone, Copy)]
+#[allow(clippy::manual_non_exhaustive)]
+pub struct U8Variable {
+    pub variable: Variable,
+    /// This private field is here to force all instantiations to go the methods below.
+    _private: (),
+}
+
+impl CircuitVariable for U8Variable {
+    type ValueType<F: RichField> = u8;
+
+    fn init_unsafe<L: PlonkParameters<D>, const D: usize>(
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> Self {
+        Self {
+            variable: Variable::init_unsafe(builder),
+            _private: (),
+        }
+    }
+
+    fn variables(&self) -> Vec<Variable> {
+        vec![self.variable]
+    }
+
+    fn from_variables_unsafe(variables: &[Variable]) -> Self {
+        assert_eq!(variables.len(), 1);
+        Self {
+            variable: variables[0],
+            _private: (),
+        }
+    }
+
+    fn assert_is_valid<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        builder: &mut CircuitBuilder<L, D>,
+    ) {
+        builder.range_check_u8(self.variable);
+    }
+
+    fn nb_elements() -> usize {
+        1
+    }
+
+    fn elements<F: RichField>(value: Self::ValueType<F>) -> Vec<F> {
+        vec![F::from_canonical_u8(value)]
+    }
+
+    fn from_elements<F: RichField>(elements: &[F]) -> Self::ValueType<F> {
+        assert_eq!(elements.len(), 1);
+        elements[0].to_canonical_u64() as u8
+    }
+}
+
+impl EvmVariable for U8Variable {
+    fn encode<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> Vec<ByteVariable> {
+        vec![self.to_byte_variable(builder)]
+    }
+
+    fn decode<L: PlonkParameters<D>, const D: usize>(
+        builder: &mut CircuitBuilder<L, D>,
+        bytes: &[ByteVariable],
+    ) -> Self {
+        assert_eq!(bytes.len(), 1);
+        Self::from_byte_variable(bytes[0], builder)
+    }
+
+    fn encode_value<F: RichField>(value: Self::ValueType<F>) -> Vec<u8> {
+        vec![value]
+    }
+
+    fn decode_value<F: RichField>(bytes: &[u8]) -> Self::ValueType<F> {
+        assert_eq!(bytes.len(), 1);
+        bytes[0]
+    }
+}
+
+impl U8Variable {
+    /// Decomposes the value into its big-endian bits.
+    pub fn to_byte_variable<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> ByteVariable {
+        let bits = builder.to_bits(self, 8, Endianness::Big);
+        ByteVariable::from_be_bits(bits.try_into().unwrap())
+    }
+
+    /// Packs the bits of a `ByteVariable` into a single field element.
+    pub fn from_byte_variable<L: PlonkParameters<D>, const D: usize>(
+        byte: ByteVariable,
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> Self {
+        let variable = byte.to_variable(builder);
+
+        // The sum of 8 bits is always within u8 range.
+        Self::from_variables_unsafe(&[variable])
+    }
+
+    /// Widens the value to a `U32Variable`.
+    pub fn to_u32(&self) -> U32Variable {
+        // Every u8 is within u32 range.
+        U32Variable::from_variables_unsafe(&[self.variable])
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize> Zero<L, D> for U8Variable {
+    fn zero(builder: &mut CircuitBuilder<L, D>) -> Self {
+        let zero = Variable::zero(builder);
+
+        // "zero" is within u8.
+        Self::from_variables_unsafe(&[zero])
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize> One<L, D> for U8Variable {
+    fn one(builder: &mut CircuitBuilder<L, D>) -> Self {
+        let one = Variable::one(builder);
+
+        // "one" is within u8.
+        Self::from_variables_unsafe(&[one])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::U8Variable;
+    use crate::frontend::vars::EvmVariable;
+    use crate::prelude::*;
+
+    type L = DefaultParameters;
+    const D: usize = 2;
+
+    #[test]
+    fn test_u8_byte_roundtrip() {
+        let mut builder = CircuitBuilder::<L, D>::new();
+
+        let a = builder.read::<U8Variable>();
+        let byte = a.to_byte_variable(&mut builder);
+        let expected_byte = builder.constant::<ByteVariable>(0xa5);
+        builder.assert_is_equal(byte, expected_byte);
+
+        let b = U8Variable::from_byte_variable(byte, &mut builder);
+        builder.assert_is_equal(a, b);
+        builder.write(b);
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        input.write::<U8Variable>(0xa5);
+
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        assert_eq!(output.read::<U8Variable>(), 0xa5);
+    }
+
+    #[test]
+    fn test_u8_evm() {
+        let mut builder = CircuitBuilder::<L, D>::new();
+
+        let a = builder.evm_read::<U8Variable>();
+        builder.evm_write(a);
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        input.evm_write::<U8Variable>(0x7f);
+
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        assert_eq!(output.evm_read::<U8Variable>(), 0x7f);
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds")]
+    fn test_u8_rejects_out_of_range() {
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let a = builder.read::<U32Variable>();
+        let b = U8Variable::from_variables(&mut builder, &a.variables());
+        builder.write(b);
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        // The lookup generator indexes the byte table by the input, so a value past the end of the
+        // table fails the witness generation.
+        input.write::<U32Variable>(300);
+
+        let (proof, output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+    }
+}
@@ -6,14 +6,21 @@ use plonky2::field::types::{Field, PrimeField64};
 use plonky2::hash::hash_types::RichField;
 use plonky2::hash::poseidon::PoseidonHash;
 use plonky2::iop::challenger::RecursiveChallenger;
-use plonky2::iop::target::Target;
+use plonky2::iop::target::{BoolTarget, Target};
 use serde::{Deserialize, Serialize};
 
-use super::{ByteVariable, CircuitVariable, ValueStream, Variable, VariableStream};
+use super::{
+    ByteVariable, CircuitVariable, EvmVariable, U32Variable, ValueStream, Variable, VariableStream,
+};
 use crate::backend::circuit::PlonkParameters;
+use crate::frontend::builder::bits::Endianness;
 use crate::frontend::builder::CircuitBuilder;
 use crate::frontend::hint::simple::hint::Hint;
 
+/// The largest array that can be read with a single random access gate under the standard
+/// recursion config.
+const MAX_RANDOM_ACCESS_SIZE: usize = 64;
+
 /// A variable in the circuit representing a fixed length array of variables.
 /// We use this to avoid stack overflow arrays associated with fixed-length arrays.
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -125,6 +132,51 @@ impl<V: CircuitVariable, const N: usize> CircuitVariable for ArrayVariable<V, N>
     }
 }
 
+impl<V: EvmVariable, const N: usize> EvmVariable for ArrayVariable<V, N> {
+    fn encode<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> Vec<ByteVariable> {
+        self.data.iter().flat_map(|x| x.encode(builder)).collect()
+    }
+
+    fn decode<L: PlonkParameters<D>, const D: usize>(
+        builder: &mut CircuitBuilder<L, D>,
+        bytes: &[ByteVariable],
+    ) -> Self {
+        if N == 0 {
+            assert!(bytes.is_empty());
+            return Self { data: Vec::new() };
+        }
+        assert_eq!(bytes.len() % N, 0);
+        let elem_size = bytes.len() / N;
+        Self {
+            data: bytes
+                .chunks_exact(elem_size)
+                .map(|chunk| V::decode(builder, chunk))
+                .collect(),
+        }
+    }
+
+    fn encode_value<F: RichField>(value: Self::ValueType<F>) -> Vec<u8> {
+        assert_eq!(value.len(), N);
+        value.into_iter().flat_map(|x| V::encode_value(x)).collect()
+    }
+
+    fn decode_value<F: RichField>(bytes: &[u8]) -> Self::ValueType<F> {
+        if N == 0 {
+            assert!(bytes.is_empty());
+            return Vec::new();
+        }
+        assert_eq!(bytes.len() % N, 0);
+        let elem_size = bytes.len() / N;
+        bytes
+            .chunks_exact(elem_size)
+            .map(|chunk| V::decode_value::<F>(chunk))
+            .collect()
+    }
+}
+
 impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
     /// Given `array` of variables and dynamic `selector`, returns `array[selector]` as a variable.
     pub fn select_array<V: CircuitVariable>(&mut self, array: &[V], selector: Variable) -> V {
@@ -177,6 +229,200 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         V::from_variables_unsafe(&selected_vars)
     }
 
+    /// Given an `array` of variables and a dynamic `index`, returns `array[index]`, asserting that
+    /// `index < array.len()`.
+    ///
+    /// Each underlying variable is read with a single random access gate, padding the array to the
+    /// next power of two. Arrays too large to fit in one gate fall back to `select_array`.
+    pub fn select_index<V: CircuitVariable>(&mut self, array: &[V], index: Variable) -> V {
+        assert!(!array.is_empty(), "cannot select from an empty array");
+
+        let padded_len = array.len().next_power_of_two();
+        let use_gate = padded_len <= MAX_RANDOM_ACCESS_SIZE;
+
+        // When the random access gate is used, it range checks `index` to `log2(padded_len)` bits,
+        // so an explicit bounds check is only needed if the array was padded.
+        if !use_gate || padded_len != array.len() {
+            let index_u32 = if use_gate {
+                U32Variable::from_variables_unsafe(&[index])
+            } else {
+                U32Variable::from_variables(self, &[index])
+            };
+            let len = self.constant::<U32Variable>(array.len() as u32);
+            let in_bounds = self.lt(index_u32, len);
+            let true_v = self._true();
+            self.assert_is_equal(in_bounds, true_v);
+        }
+
+        if !use_gate {
+            return self.select_array(array, index);
+        }
+
+        let selected_vars = (0..V::nb_elements())
+            .map(|i| {
+                let mut targets = array.iter().map(|v| v.variables()[i].0).collect_vec();
+                targets.resize(padded_len, array[0].variables()[i].0);
+                Variable(self.api.random_access(index.0, targets))
+            })
+            .collect_vec();
+
+        // Every selected variable comes from the same element of `array`, so the result is as
+        // valid as the elements of `array`.
+        V::from_variables_unsafe(&selected_vars)
+    }
+
+    /// Given `bytes` and a dynamic `offset`, returns `bytes[offset..offset + N]`, asserting that
+    /// `offset + N <= bytes.len()`.
+    ///
+    /// Each byte is read with random access gates on the byte's packed value. Arrays longer than a
+    /// single gate are split into chunks, which are indexed by the low bits of the index, and the
+    /// chunk results are then indexed by the remaining bits.
+    pub fn select_subarray<const N: usize>(
+        &mut self,
+        bytes: &[ByteVariable],
+        offset: Variable,
+    ) -> [ByteVariable; N] {
+        assert!(
+            N <= bytes.len(),
+            "subarray of length {} does not fit in {} bytes",
+            N,
+            bytes.len()
+        );
+
+        let offset_u32 = U32Variable::from_variables(self, &[offset]);
+        let max_offset = self.constant::<U32Variable>((bytes.len() - N) as u32);
+        let in_bounds = self.lte(offset_u32, max_offset);
+        let true_v = self._true();
+        self.assert_is_equal(in_bounds, true_v);
+
+        let values = bytes
+            .iter()
+            .map(|byte| byte.to_variable(self).0)
+            .collect_vec();
+        let nb_bits = values.len().next_power_of_two().trailing_zeros() as usize;
+
+        let result = (0..N)
+            .map(|i| {
+                let i = self.constant::<Variable>(L::Field::from_canonical_usize(i));
+                let index = self.add(offset, i);
+                // The bounds check above guarantees `index < bytes.len()`, so it fits in `nb_bits`.
+                let index_bits: Vec<BoolTarget> = self
+                    .to_bits(&index, nb_bits, Endianness::Little)
+                    .into_iter()
+                    .map(|bit| bit.into())
+                    .collect();
+                let selected = self.random_access_bits(&index_bits, values.clone());
+                ByteVariable::from_target(self, selected)
+            })
+            .collect_vec();
+        result.try_into().unwrap()
+    }
+
+    /// Returns `values[index]` where `index` is given by its little-endian bits, splitting the
+    /// lookup into as many levels of random access gates as needed.
+    fn random_access_bits(&mut self, index_bits: &[BoolTarget], mut values: Vec<Target>) -> Target {
+        let gate_bits = MAX_RANDOM_ACCESS_SIZE.trailing_zeros() as usize;
+        values.resize(1 << index_bits.len(), values[0]);
+
+        if index_bits.len() <= gate_bits {
+            let index = self.api.le_sum(index_bits.iter());
+            return self.api.random_access(index, values);
+        }
+
+        let (low_bits, high_bits) = index_bits.split_at(gate_bits);
+        let low_index = self.api.le_sum(low_bits.iter());
+        let chunk_values = values
+            .chunks(MAX_RANDOM_ACCESS_SIZE)
+            .map(|chunk| self.api.random_access(low_index, chunk.to_vec()))
+            .collect_vec();
+        self.random_access_bits(high_bits, chunk_values)
+    }
+
+    /// Rotates `array` to the left by a dynamic `shift`, i.e. returns `out` with
+    /// `out[i] = array[(i + shift) % N]`. `shift` must fit in the number of bits of `N`.
+    pub fn rotate_left_array<V: CircuitVariable, const N: usize>(
+        &mut self,
+        array: &ArrayVariable<V, N>,
+        shift: Variable,
+    ) -> ArrayVariable<V, N> {
+        self.barrel_shift(array, shift, |elements, amount| {
+            (0..N)
+                .map(|i| Some(elements[(i + amount) % N].clone()))
+                .collect()
+        })
+    }
+
+    /// Rotates `array` to the right by a dynamic `shift`, i.e. returns `out` with
+    /// `out[(i + shift) % N] = array[i]`. `shift` must fit in the number of bits of `N`.
+    pub fn rotate_right_array<V: CircuitVariable, const N: usize>(
+        &mut self,
+        array: &ArrayVariable<V, N>,
+        shift: Variable,
+    ) -> ArrayVariable<V, N> {
+        self.barrel_shift(array, shift, |elements, amount| {
+            (0..N)
+                .map(|i| Some(elements[(i + N - amount % N) % N].clone()))
+                .collect()
+        })
+    }
+
+    /// Shifts `array` to the left by a dynamic `shift`, i.e. returns `out` with
+    /// `out[i] = array[i + shift]`, filling the vacated positions at the end with zero. Shifting
+    /// by `N` or more returns all zeros. `shift` must fit in the number of bits of `N`.
+    pub fn shift_left_array<V: CircuitVariable, const N: usize>(
+        &mut self,
+        array: &ArrayVariable<V, N>,
+        shift: Variable,
+    ) -> ArrayVariable<V, N> {
+        self.barrel_shift(array, shift, |elements, amount| {
+            (0..N).map(|i| elements.get(i + amount).cloned()).collect()
+        })
+    }
+
+    /// Shifts `array` to the right by a dynamic `shift`, i.e. returns `out` with
+    /// `out[i + shift] = array[i]`, filling the vacated positions at the start with zero. Shifting
+    /// by `N` or more returns all zeros. `shift` must fit in the number of bits of `N`.
+    pub fn shift_right_array<V: CircuitVariable, const N: usize>(
+        &mut self,
+        array: &ArrayVariable<V, N>,
+        shift: Variable,
+    ) -> ArrayVariable<V, N> {
+        self.barrel_shift(array, shift, |elements, amount| {
+            (0..N)
+                .map(|i| i.checked_sub(amount).map(|j| elements[j].clone()))
+                .collect()
+        })
+    }
+
+    /// A barrel shifter: for every bit `k` of `shift`, conditionally applies `permute` with an
+    /// amount of `2^k`. `permute` returns `None` for positions that are filled with zero.
+    fn barrel_shift<V: CircuitVariable, const N: usize>(
+        &mut self,
+        array: &ArrayVariable<V, N>,
+        shift: Variable,
+        permute: impl Fn(&[V], usize) -> Vec<Option<V>>,
+    ) -> ArrayVariable<V, N> {
+        let nb_bits = (usize::BITS - N.leading_zeros()) as usize;
+        let shift_bits = self.to_bits(&shift, nb_bits, Endianness::Little);
+
+        let zero = self.zero::<Variable>();
+        let zero_element = V::from_variables_unsafe(&vec![zero; V::nb_elements()]);
+
+        let mut elements = array.as_vec();
+        for (k, bit) in shift_bits.into_iter().enumerate() {
+            let permuted = permute(&elements, 1 << k);
+            elements = permuted
+                .into_iter()
+                .zip(elements.iter())
+                .map(|(shifted, current)| {
+                    let shifted = shifted.unwrap_or_else(|| zero_element.clone());
+                    self.select(bit, shifted, current.clone())
+                })
+                .collect();
+        }
+        ArrayVariable::new(elements)
+    }
+
     /// Given an `array` of ByteVariable's, a dynamic `index` start_idx, and a commitment to the
     /// `array`, 'seed', return `array[start_idx..start_idx+sub_array_size]` as an `array`.
     /// `seed` is used to generate randomness for the proof, and must contain a valid commitment to
@@ -378,6 +624,47 @@ mod tests {
     type L = DefaultParameters;
     const D: usize = 2;
 
+    #[test]
+    fn test_array_evm() {
+        let mut builder = CircuitBuilder::<L, D>::new();
+
+        let array = builder.evm_read::<ArrayVariable<U32Variable, 3>>();
+        let sum = builder.add_many(array.as_slice());
+        builder.evm_write(sum);
+        builder.evm_write(array);
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        input.evm_write::<ArrayVariable<U32Variable, 3>>(vec![1, 2, 0x01020304]);
+
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        assert_eq!(output.evm_read::<U32Variable>(), 0x01020307);
+        assert_eq!(
+            output.evm_read::<ArrayVariable<U32Variable, 3>>(),
+            vec![1, 2, 0x01020304]
+        );
+
+        let encoded = ArrayVariable::<U32Variable, 3>::encode_value::<GoldilocksField>(vec![
+            1, 2, 0x01020304,
+        ]);
+        assert_eq!(encoded, vec![0, 0, 0, 1, 0, 0, 0, 2, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_array_evm_empty() {
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let array = ArrayVariable::<U32Variable, 0>::decode(&mut builder, &[]);
+        assert!(array.as_slice().is_empty());
+        assert!(array.encode(&mut builder).is_empty());
+
+        let encoded = ArrayVariable::<U32Variable, 0>::encode_value::<GoldilocksField>(vec![]);
+        assert!(encoded.is_empty());
+        let decoded = ArrayVariable::<U32Variable, 0>::decode_value::<GoldilocksField>(&encoded);
+        assert!(decoded.is_empty());
+    }
+
     #[test]
     fn test_array_variable() {
         let mut builder = CircuitBuilder::<L, D>::new();
@@ -486,6 +773,49 @@ mod tests {
         assert_eq!(output.read::<U256Variable>(), input_u256[1]);
     }
 
+    #[test]
+    fn test_select_index_gate() {
+        let mut builder = DefaultBuilder::new();
+        let array = builder.read::<ArrayVariable<U256Variable, 10>>();
+        let index = builder.read::<Variable>();
+        let result = builder.select_index(array.as_slice(), index);
+        builder.write(result);
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        let values = (0..10u64)
+            .map(|i| U256::from(i * 1000 + 7))
+            .collect::<Vec<_>>();
+        input.write::<ArrayVariable<U256Variable, 10>>(values.clone());
+        input.write::<Variable>(GoldilocksField::from_canonical_u16(9));
+
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        assert_eq!(output.read::<U256Variable>(), values[9]);
+    }
+
+    #[test]
+    #[should_panic(expected = "was set twice with different values")]
+    fn test_select_index_out_of_bounds() {
+        let mut builder = DefaultBuilder::new();
+        let array = builder.read::<ArrayVariable<Variable, 10>>();
+        let index = builder.read::<Variable>();
+        let result = builder.select_index(array.as_slice(), index);
+        builder.write(result);
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        let values = (0..10u64)
+            .map(GoldilocksField::from_canonical_u64)
+            .collect::<Vec<_>>();
+        input.write::<ArrayVariable<Variable, 10>>(values);
+        input.write::<Variable>(GoldilocksField::from_canonical_u16(12));
+
+        let (proof, output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+    }
+
     #[test]
     fn test_get_fixed_subarray() {
         utils::setup_logger();
@@ -580,4 +910,92 @@ mod tests {
         let (proof, output) = circuit.prove(&input);
         circuit.verify(&proof, &input, &output);
     }
+
+    #[test]
+    fn test_select_subarray() {
+        let mut builder = DefaultBuilder::new();
+
+        // 100 bytes do not fit in a single random access gate.
+        let bytes = builder.read::<ArrayVariable<ByteVariable, 100>>();
+        let offset = builder.read::<Variable>();
+        let sub_array = builder.select_subarray::<8>(bytes.as_slice(), offset);
+        builder.write(ArrayVariable::<ByteVariable, 8>::new(sub_array.to_vec()));
+
+        let circuit = builder.build();
+        let values = (0..100).map(|i| (i * 3) as u8).collect_vec();
+        for offset in [0usize, 37, 63, 92] {
+            let mut input = circuit.input();
+            input.write::<ArrayVariable<ByteVariable, 100>>(values.clone());
+            input.write::<Variable>(GoldilocksField::from_canonical_usize(offset));
+
+            let (proof, mut output) = circuit.prove(&input);
+            circuit.verify(&proof, &input, &output);
+
+            let result = output.read::<ArrayVariable<ByteVariable, 8>>();
+            assert_eq!(result, values[offset..offset + 8].to_vec());
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "was set twice with different values")]
+    fn test_select_subarray_out_of_bounds() {
+        let mut builder = DefaultBuilder::new();
+        let bytes = builder.read::<ArrayVariable<ByteVariable, 16>>();
+        let offset = builder.read::<Variable>();
+        let sub_array = builder.select_subarray::<4>(bytes.as_slice(), offset);
+        builder.write(ArrayVariable::<ByteVariable, 4>::new(sub_array.to_vec()));
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        input.write::<ArrayVariable<ByteVariable, 16>>(vec![0u8; 16]);
+        input.write::<Variable>(GoldilocksField::from_canonical_usize(13));
+
+        let (proof, output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+    }
+
+    #[test]
+    fn test_array_rotate_and_shift() {
+        let mut builder = DefaultBuilder::new();
+
+        let array = builder.read::<ArrayVariable<ByteVariable, 6>>();
+        let shift = builder.read::<Variable>();
+
+        let rotl = builder.rotate_left_array(&array, shift);
+        let rotr = builder.rotate_right_array(&array, shift);
+        let shl = builder.shift_left_array(&array, shift);
+        let shr = builder.shift_right_array(&array, shift);
+        builder.write(rotl);
+        builder.write(rotr);
+        builder.write(shl);
+        builder.write(shr);
+
+        let circuit = builder.build();
+        let values = vec![1u8, 2, 3, 4, 5, 6];
+        for shift in 0..8usize {
+            let mut input = circuit.input();
+            input.write::<ArrayVariable<ByteVariable, 6>>(values.clone());
+            input.write::<Variable>(GoldilocksField::from_canonical_usize(shift));
+
+            let (proof, mut output) = circuit.prove(&input);
+            circuit.verify(&proof, &input, &output);
+
+            let mut expected_rotl = values.clone();
+            expected_rotl.rotate_left(shift % 6);
+            let mut expected_rotr = values.clone();
+            expected_rotr.rotate_right(shift % 6);
+            let expected_shl = (0..6)
+                .map(|i| values.get(i + shift).copied().unwrap_or(0))
+                .collect_vec();
+            let expected_shr = (0..6)
+                .map(|i| i.checked_sub(shift).map_or(0, |j| values[j]))
+                .collect_vec();
+
+            type Bytes = ArrayVariable<ByteVariable, 6>;
+            assert_eq!(output.read::<Bytes>(), expected_rotl);
+            assert_eq!(output.read::<Bytes>(), expected_rotr);
+            assert_eq!(output.read::<Bytes>(), expected_shl);
+            assert_eq!(output.read::<Bytes>(), expected_shr);
+        }
+    }
 }
@@ -4,7 +4,7 @@ use plonky2::hash::hash_types::RichField;
 use plonky2::iop::target::BoolTarget;
 use serde::{Deserialize, Serialize};
 
-use super::{CircuitVariable, Variable};
+use super::{ByteVariable, CircuitVariable, EvmVariable, Variable};
 use crate::backend::circuit::PlonkParameters;
 use crate::frontend::builder::CircuitBuilder;
 use crate::frontend::ops::{BitAnd, BitOr, BitXor, Not};
@@ -65,6 +65,42 @@ impl CircuitVariable for BoolVariable {
     }
 }
 
+impl EvmVariable for BoolVariable {
+    /// Encodes the boolean as a single byte that is either 0 or 1, matching `abi.encodePacked`.
+    fn encode<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> Vec<ByteVariable> {
+        let false_v = builder._false();
+        let mut bits = [false_v; 8];
+        bits[7] = *self;
+        vec![ByteVariable(bits)]
+    }
+
+    fn decode<L: PlonkParameters<D>, const D: usize>(
+        builder: &mut CircuitBuilder<L, D>,
+        bytes: &[ByteVariable],
+    ) -> Self {
+        assert_eq!(bytes.len(), 1);
+        let bits = bytes[0].as_be_bits();
+        let false_v = builder._false();
+        for bit in bits[..7].iter() {
+            builder.assert_is_equal(*bit, false_v);
+        }
+        bits[7]
+    }
+
+    fn encode_value<F: RichField>(value: Self::ValueType<F>) -> Vec<u8> {
+        vec![value as u8]
+    }
+
+    fn decode_value<F: RichField>(bytes: &[u8]) -> Self::ValueType<F> {
+        assert_eq!(bytes.len(), 1);
+        assert!(bytes[0] <= 1, "invalid boolean byte {}", bytes[0]);
+        bytes[0] == 1
+    }
+}
+
 impl From<BoolTarget> for BoolVariable {
     fn from(v: BoolTarget) -> Self {
         // BoolTarget's range is the same as BoolVariable's.
@@ -130,6 +166,7 @@ impl<L: PlonkParameters<D>, const D: usize> Not<L, D> for BoolVariable {
 
 #[cfg(test)]
 mod tests {
+    use crate::frontend::vars::EvmVariable;
     use crate::prelude::*;
 
     type L = DefaultParameters;
@@ -173,4 +210,35 @@ mod tests {
         let proof = circuit.data.prove(pw).unwrap();
         circuit.data.verify(proof).unwrap();
     }
+
+    #[test]
+    fn test_bool_evm() {
+        assert_eq!(
+            BoolVariable::encode_value::<GoldilocksField>(true),
+            vec![1u8]
+        );
+        assert_eq!(
+            BoolVariable::encode_value::<GoldilocksField>(false),
+            vec![0u8]
+        );
+
+        let mut builder = CircuitBuilder::<L, D>::new();
+
+        let x = builder.evm_read::<BoolVariable>();
+        let y = builder.evm_read::<BoolVariable>();
+        let x_and_y = builder.and(x, y);
+        builder.evm_write(x_and_y);
+        builder.evm_write(x);
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        input.evm_write::<BoolVariable>(true);
+        input.evm_write::<BoolVariable>(false);
+
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        assert!(!output.evm_read::<BoolVariable>());
+        assert!(output.evm_read::<BoolVariable>());
+    }
 }
@@ -278,7 +278,7 @@ impl<L: PlonkParameters<D>, const D: usize> RotateRight<L, D, usize> for ByteVar
 
     fn rotate_right(self, rhs: usize, _builder: &mut CircuitBuilder<L, D>) -> Self::Output {
         let self_bits = self.as_be_bits();
-        let rot_bit = |i| self_bits[(i + 8 - rhs) % 8];
+        let rot_bit = |i| self_bits[(i + 8 - rhs % 8) % 8];
         ByteVariable(array![i => rot_bit(i); 8])
     }
 }
@@ -379,6 +379,28 @@ mod tests {
         circuit.data.verify(proof).unwrap();
     }
 
+    #[test]
+    fn test_byte_rotate_wraps_amount() {
+        let mut builder = CircuitBuilder::<L, D>::new();
+
+        let value = 0b1011_0010u8;
+        let x = builder.constant::<ByteVariable>(value);
+        for i in [0, 3, 8, 11, 16] {
+            let rot_left = builder.rotate_left(x, i);
+            let expected = builder.constant::<ByteVariable>(value.rotate_left(i as u32));
+            builder.assert_is_equal(rot_left, expected);
+
+            let rot_right = builder.rotate_right(x, i);
+            let expected = builder.constant::<ByteVariable>(value.rotate_right(i as u32));
+            builder.assert_is_equal(rot_right, expected);
+        }
+
+        let circuit = builder.build();
+        let pw = PartialWitness::new();
+        let proof = circuit.data.prove(pw).unwrap();
+        circuit.data.verify(proof).unwrap();
+    }
+
     #[test]
     fn test_to_nibbles() {
         let mut builder = CircuitBuilder::<L, D>::new();
@@ -111,6 +111,17 @@ impl SSZVariable for Bytes32Variable {
 }
 
 impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Interprets the bytes as a big-endian u256.
+    pub fn bytes32_to_u256(&mut self, bytes: Bytes32Variable) -> U256Variable {
+        bytes.as_u256(self)
+    }
+
+    /// Encodes the u256 as 32 big-endian bytes.
+    pub fn u256_to_bytes32(&mut self, value: U256Variable) -> Bytes32Variable {
+        let bytes = value.encode(self);
+        Bytes32Variable::from(bytes.as_slice())
+    }
+
     // Returns a Bytes32Variable with the first `num_bits` set to 0.
     pub fn mask_be_bits(&mut self, original: Bytes32Variable, num_bits: usize) -> Bytes32Variable {
         let variables = original.variables();
@@ -126,12 +137,13 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
 
 #[cfg(test)]
 mod test {
-    use ethers::types::U256;
+    use ethers::types::{H256, U256};
     use plonky2::iop::witness::PartialWitness;
 
     use super::Bytes32Variable;
     use crate::frontend::uint::uint256::U256Variable;
-    use crate::prelude::{CircuitVariable, DefaultBuilder};
+    use crate::frontend::vars::EvmVariable;
+    use crate::prelude::{CircuitVariable, DefaultBuilder, GoldilocksField};
     use crate::utils::bytes32;
 
     #[test]
@@ -158,4 +170,35 @@ mod test {
         let proof = circuit.data.prove(pw).unwrap();
         circuit.data.verify(proof).unwrap();
     }
+
+    #[test]
+    fn test_bytes32_u256_roundtrip() {
+        type F = GoldilocksField;
+
+        let mut builder = DefaultBuilder::new();
+
+        let value = U256::from_dec_str(
+            "108959270400061671294053818573968651411470832267186275529291850190552309358907",
+        )
+        .unwrap();
+        let bytes_value = H256::from_slice(&U256Variable::encode_value::<F>(value));
+        assert_eq!(
+            U256Variable::decode_value::<F>(bytes_value.as_bytes()),
+            value
+        );
+
+        let u256 = U256Variable::constant(&mut builder, value);
+        let b32 = Bytes32Variable::constant(&mut builder, bytes_value);
+
+        let converted_b32 = builder.u256_to_bytes32(u256);
+        builder.assert_is_equal(converted_b32, b32);
+
+        let converted_u256 = builder.bytes32_to_u256(b32);
+        builder.assert_is_equal(converted_u256, u256);
+
+        let circuit = builder.build();
+        let pw = PartialWitness::new();
+        let proof = circuit.data.prove(pw).unwrap();
+        circuit.data.verify(proof).unwrap();
+    }
 }
@@ -2,8 +2,8 @@ use array_macro::array;
 use log::debug;
 use plonky2::hash::hash_types::RichField;
 
-use super::{CircuitVariable, Variable};
-use crate::backend::circuit::PlonkParameters;
+use super::{ByteVariable, CircuitVariable, EvmVariable, Variable};
+use crate::backend::circuit::{DefaultParameters, PlonkParameters};
 use crate::frontend::builder::CircuitBuilder;
 
 impl<const N: usize, V: CircuitVariable> CircuitVariable for [V; N] {
@@ -644,3 +644,167 @@ impl<
         )
     }
 }
+
+impl<const N: usize, V: EvmVariable> EvmVariable for [V; N] {
+    fn encode<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> Vec<ByteVariable> {
+        self.iter().flat_map(|v| v.encode(builder)).collect()
+    }
+
+    fn decode<L: PlonkParameters<D>, const D: usize>(
+        builder: &mut CircuitBuilder<L, D>,
+        bytes: &[ByteVariable],
+    ) -> Self {
+        assert_eq!(bytes.len() % N, 0);
+        let elem_size = bytes.len() / N;
+        core::array::from_fn(|i| V::decode(builder, &bytes[i * elem_size..(i + 1) * elem_size]))
+    }
+
+    fn encode_value<F: RichField>(value: Self::ValueType<F>) -> Vec<u8> {
+        value.into_iter().flat_map(|v| V::encode_value(v)).collect()
+    }
+
+    fn decode_value<F: RichField>(bytes: &[u8]) -> Self::ValueType<F> {
+        assert_eq!(bytes.len() % N, 0);
+        let elem_size = bytes.len() / N;
+        bytes
+            .chunks_exact(elem_size)
+            .map(V::decode_value::<F>)
+            .collect::<Vec<_>>()
+            .try_into()
+            .unwrap()
+    }
+}
+
+/// The byte length of an `EvmVariable` when decoding values outside of a circuit context. The
+/// encoded length does not depend on the plonky2 parameters, so the default ones are used.
+fn nb_bytes_of<V: EvmVariable>() -> usize {
+    V::nb_bytes::<DefaultParameters, 2>()
+}
+
+impl<V1: EvmVariable, V2: EvmVariable> EvmVariable for (V1, V2) {
+    fn encode<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> Vec<ByteVariable> {
+        let mut bytes = self.0.encode(builder);
+        bytes.extend(self.1.encode(builder));
+        bytes
+    }
+
+    fn decode<L: PlonkParameters<D>, const D: usize>(
+        builder: &mut CircuitBuilder<L, D>,
+        bytes: &[ByteVariable],
+    ) -> Self {
+        let split = V1::nb_bytes::<L, D>();
+        (
+            V1::decode(builder, &bytes[..split]),
+            V2::decode(builder, &bytes[split..]),
+        )
+    }
+
+    fn encode_value<F: RichField>(value: Self::ValueType<F>) -> Vec<u8> {
+        let mut bytes = V1::encode_value(value.0);
+        bytes.extend(V2::encode_value(value.1));
+        bytes
+    }
+
+    fn decode_value<F: RichField>(bytes: &[u8]) -> Self::ValueType<F> {
+        let split = nb_bytes_of::<V1>();
+        (
+            V1::decode_value(&bytes[..split]),
+            V2::decode_value(&bytes[split..]),
+        )
+    }
+}
+
+impl<V1: EvmVariable, V2: EvmVariable, V3: EvmVariable> EvmVariable for (V1, V2, V3) {
+    fn encode<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> Vec<ByteVariable> {
+        let mut bytes = self.0.encode(builder);
+        bytes.extend(self.1.encode(builder));
+        bytes.extend(self.2.encode(builder));
+        bytes
+    }
+
+    fn decode<L: PlonkParameters<D>, const D: usize>(
+        builder: &mut CircuitBuilder<L, D>,
+        bytes: &[ByteVariable],
+    ) -> Self {
+        let split_1 = V1::nb_bytes::<L, D>();
+        let split_2 = split_1 + V2::nb_bytes::<L, D>();
+        (
+            V1::decode(builder, &bytes[..split_1]),
+            V2::decode(builder, &bytes[split_1..split_2]),
+            V3::decode(builder, &bytes[split_2..]),
+        )
+    }
+
+    fn encode_value<F: RichField>(value: Self::ValueType<F>) -> Vec<u8> {
+        let mut bytes = V1::encode_value(value.0);
+        bytes.extend(V2::encode_value(value.1));
+        bytes.extend(V3::encode_value(value.2));
+        bytes
+    }
+
+    fn decode_value<F: RichField>(bytes: &[u8]) -> Self::ValueType<F> {
+        let split_1 = nb_bytes_of::<V1>();
+        let split_2 = split_1 + nb_bytes_of::<V2>();
+        (
+            V1::decode_value(&bytes[..split_1]),
+            V2::decode_value(&bytes[split_1..split_2]),
+            V3::decode_value(&bytes[split_2..]),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::frontend::vars::EvmVariable;
+    use crate::prelude::*;
+
+    type L = DefaultParameters;
+    const D: usize = 2;
+
+    #[test]
+    fn test_collections_evm() {
+        type F = GoldilocksField;
+
+        let array_value = [0x01020304u32, 0x05060708];
+        let encoded = <[U32Variable; 2]>::encode_value::<F>(array_value);
+        assert_eq!(encoded, vec![1, 2, 3, 4, 5, 6, 7, 8]);
+        assert_eq!(<[U32Variable; 2]>::decode_value::<F>(&encoded), array_value);
+
+        let tuple_value = (true, 0xabu8, 0x01020304u32);
+        let encoded = <(BoolVariable, ByteVariable, U32Variable)>::encode_value::<F>(tuple_value);
+        assert_eq!(encoded, vec![1, 0xab, 1, 2, 3, 4]);
+        assert_eq!(
+            <(BoolVariable, ByteVariable, U32Variable)>::decode_value::<F>(&encoded),
+            tuple_value
+        );
+
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let array = builder.evm_read::<[U32Variable; 2]>();
+        let tuple = builder.evm_read::<(BoolVariable, U32Variable)>();
+        builder.evm_write(tuple);
+        builder.evm_write(array);
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        input.evm_write::<[U32Variable; 2]>(array_value);
+        input.evm_write::<(BoolVariable, U32Variable)>((false, 42));
+
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        assert_eq!(
+            output.evm_read::<(BoolVariable, U32Variable)>(),
+            (false, 42)
+        );
+        assert_eq!(output.evm_read::<[U32Variable; 2]>(), array_value);
+    }
+}
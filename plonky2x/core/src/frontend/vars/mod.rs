@@ -6,7 +6,9 @@ mod bytes32;
 mod collections;
 
 mod stream;
+mod string;
 mod variable;
+mod variable_bytes;
 use std::fmt::Debug;
 
 pub use array::*;
@@ -19,7 +21,9 @@ use plonky2::hash::hash_types::RichField;
 use plonky2::iop::target::Target;
 use plonky2::iop::witness::{Witness, WitnessWrite};
 pub use stream::*;
+pub use string::*;
 pub use variable::*;
+pub use variable_bytes::*;
 
 pub use super::uint::uint256::*;
 pub use super::uint::uint32::*;
@@ -229,6 +233,42 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_derive_struct_roundtrip() {
+        use ethers::types::{H160, H256};
+
+        use crate::frontend::eth::vars::AddressVariable;
+
+        #[derive(Debug, Clone, CircuitVariable)]
+        #[value_name(StorageProofInputValue)]
+        #[value_derive(PartialEq, Eq)]
+        struct StorageProofInput {
+            address: AddressVariable,
+            slot: Bytes32Variable,
+            block: U64Variable,
+        }
+
+        let value = StorageProofInputValue {
+            address: H160::from_low_u64_be(0xdeadbeef),
+            slot: H256::from_low_u64_be(7),
+            block: 18_000_000,
+        };
+
+        let mut builder = CircuitBuilder::<DefaultParameters, 2>::new();
+        let input_var = builder.read::<StorageProofInput>();
+        let constant_var = builder.constant::<StorageProofInput>(value.clone());
+        builder.assert_is_equal(input_var.clone(), constant_var);
+        builder.write::<StorageProofInput>(input_var);
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        input.write::<StorageProofInput>(value.clone());
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        assert_eq!(output.read::<StorageProofInput>(), value);
+    }
+
     #[test]
     fn test_value_derive_struct() {
         #[derive(Debug, Clone, CircuitVariable)]
@@ -0,0 +1,445 @@
+use std::fmt::Debug;
+
+use plonky2::field::types::Field;
+use plonky2::hash::hash_types::RichField;
+
+use super::{
+    BoolVariable, ByteVariable, BytesVariable, CircuitVariable, EvmVariable, U32Variable, Variable,
+};
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::builder::CircuitBuilder;
+
+/// The smallest printable ASCII character (space).
+const MIN_PRINTABLE: u32 = 0x20;
+
+/// The largest printable ASCII character (tilde).
+const MAX_PRINTABLE: u32 = 0x7e;
+
+/// The bounds `[lo, hi)` of a UTF-8 continuation byte.
+const CONTINUATION_LO: u32 = 0x80;
+const CONTINUATION_HI: u32 = 0xc0;
+
+/// A variable in the circuit representing a fixed-length string of printable ASCII characters.
+#[derive(Debug, Clone, Copy)]
+pub struct StringVariable<const N: usize>(pub BytesVariable<N>);
+
+impl<const N: usize> CircuitVariable for StringVariable<N> {
+    type ValueType<F: RichField> = String;
+
+    fn init_unsafe<L: PlonkParameters<D>, const D: usize>(
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> Self {
+        Self(BytesVariable::init_unsafe(builder))
+    }
+
+    fn variables(&self) -> Vec<Variable> {
+        self.0.variables()
+    }
+
+    fn from_variables_unsafe(variables: &[Variable]) -> Self {
+        Self(BytesVariable::from_variables_unsafe(variables))
+    }
+
+    /// Checks that every byte is a printable ASCII character, i.e. in the range `[0x20, 0x7e]`.
+    fn assert_is_valid<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        builder: &mut CircuitBuilder<L, D>,
+    ) {
+        self.0.assert_is_valid(builder);
+
+        let true_v = builder._true();
+        let min = builder.constant::<U32Variable>(MIN_PRINTABLE);
+        let upper_bound = builder.constant::<U32Variable>(MAX_PRINTABLE + 1);
+        for byte in self.0 .0.iter() {
+            // A byte is always within u32 range.
+            let value = U32Variable::from_variables_unsafe(&[byte.to_variable(builder)]);
+            let is_printable = builder.within_range(value, min, upper_bound);
+            builder.assert_is_equal(is_printable, true_v);
+        }
+    }
+
+    fn nb_elements() -> usize {
+        BytesVariable::<N>::nb_elements()
+    }
+
+    fn elements<F: RichField>(value: Self::ValueType<F>) -> Vec<F> {
+        assert_eq!(
+            value.len(),
+            N,
+            "string {:?} does not have length {}",
+            value,
+            N
+        );
+        assert!(
+            value
+                .bytes()
+                .all(|b| (MIN_PRINTABLE..=MAX_PRINTABLE).contains(&(b as u32))),
+            "string {:?} is not printable ascii",
+            value
+        );
+        BytesVariable::<N>::elements(value.as_bytes().try_into().unwrap())
+    }
+
+    fn from_elements<F: RichField>(elements: &[F]) -> Self::ValueType<F> {
+        let bytes = BytesVariable::<N>::from_elements(elements);
+        String::from_utf8(bytes.to_vec()).expect("string is not valid ascii")
+    }
+}
+
+impl<const N: usize> EvmVariable for StringVariable<N> {
+    fn encode<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> Vec<ByteVariable> {
+        self.0.encode(builder)
+    }
+
+    fn decode<L: PlonkParameters<D>, const D: usize>(
+        builder: &mut CircuitBuilder<L, D>,
+        bytes: &[ByteVariable],
+    ) -> Self {
+        let string = Self(BytesVariable::decode(builder, bytes));
+        string.assert_is_valid(builder);
+        string
+    }
+
+    fn encode_value<F: RichField>(value: Self::ValueType<F>) -> Vec<u8> {
+        assert_eq!(value.len(), N);
+        value.into_bytes()
+    }
+
+    fn decode_value<F: RichField>(bytes: &[u8]) -> Self::ValueType<F> {
+        assert_eq!(bytes.len(), N);
+        String::from_utf8(bytes.to_vec()).expect("string is not valid ascii")
+    }
+}
+
+impl<const N: usize> StringVariable<N> {
+    pub fn as_bytes(&self) -> [ByteVariable; N] {
+        self.0 .0
+    }
+
+    /// Converts the string to a UTF-8 string. Printable ASCII is always valid UTF-8, so this adds
+    /// no constraints.
+    pub fn to_utf8(self) -> Utf8StringVariable<N> {
+        Utf8StringVariable(self.0)
+    }
+
+    /// Returns whether the string starts with `prefix`.
+    pub fn starts_with<L: PlonkParameters<D>, const D: usize, const M: usize>(
+        &self,
+        prefix: &StringVariable<M>,
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> BoolVariable {
+        assert!(M <= N, "prefix is longer than the string");
+        let mut result = builder._true();
+        for i in 0..M {
+            let eq = builder.is_equal(self.0 .0[i], prefix.0 .0[i]);
+            result = builder.and(result, eq);
+        }
+        result
+    }
+}
+
+/// A variable in the circuit representing a fixed-length string of `N` bytes of valid UTF-8.
+///
+/// This is the permissive counterpart of `StringVariable`, for names that are not restricted to
+/// printable ASCII. Validity follows RFC 3629: overlong encodings, surrogates, and code points
+/// above `U+10FFFF` are rejected, and the string may not end in the middle of a character.
+#[derive(Debug, Clone, Copy)]
+pub struct Utf8StringVariable<const N: usize>(pub BytesVariable<N>);
+
+impl<const N: usize> CircuitVariable for Utf8StringVariable<N> {
+    type ValueType<F: RichField> = String;
+
+    fn init_unsafe<L: PlonkParameters<D>, const D: usize>(
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> Self {
+        Self(BytesVariable::init_unsafe(builder))
+    }
+
+    fn variables(&self) -> Vec<Variable> {
+        self.0.variables()
+    }
+
+    fn from_variables_unsafe(variables: &[Variable]) -> Self {
+        Self(BytesVariable::from_variables_unsafe(variables))
+    }
+
+    /// Checks that the bytes are valid UTF-8 by running the UTF-8 decoder over them. The state of
+    /// the decoder is the number of continuation bytes still expected and the range `[lo, hi)`
+    /// allowed for the next one.
+    fn assert_is_valid<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        builder: &mut CircuitBuilder<L, D>,
+    ) {
+        self.0.assert_is_valid(builder);
+
+        let true_v = builder._true();
+        let zero = builder.zero::<Variable>();
+        let two = builder.constant::<Variable>(L::Field::from_canonical_u64(2));
+        let three = builder.constant::<Variable>(L::Field::from_canonical_u64(3));
+        let c =
+            |builder: &mut CircuitBuilder<L, D>, value: u32| builder.constant::<U32Variable>(value);
+        let ascii_lo = c(builder, 0x00);
+        let lead2_lo = c(builder, 0xc2);
+        let lead3_lo = c(builder, 0xe0);
+        let lead4_lo = c(builder, 0xf0);
+        let lead4_hi = c(builder, 0xf5);
+        let continuation_lo = c(builder, CONTINUATION_LO);
+        let continuation_hi = c(builder, CONTINUATION_HI);
+        // Leading bytes that restrict the range of the following byte.
+        let e0 = c(builder, 0xe0);
+        let ed = c(builder, 0xed);
+        let f0 = c(builder, 0xf0);
+        let f4 = c(builder, 0xf4);
+        let e0_lo = c(builder, 0xa0);
+        let ed_hi = c(builder, 0xa0);
+        let f0_lo = c(builder, 0x90);
+        let f4_hi = c(builder, 0x90);
+
+        let mut remaining = zero;
+        let mut lo = continuation_lo;
+        let mut hi = continuation_hi;
+        for byte in self.0 .0.iter() {
+            // A byte is always within u32 range.
+            let value = U32Variable::from_variables_unsafe(&[byte.to_variable(builder)]);
+
+            let is_ascii = builder.within_range(value, ascii_lo, continuation_lo);
+            let is_lead2 = builder.within_range(value, lead2_lo, lead3_lo);
+            let is_lead3 = builder.within_range(value, lead3_lo, lead4_lo);
+            let is_lead4 = builder.within_range(value, lead4_lo, lead4_hi);
+            let is_lead = builder.or(is_ascii, is_lead2);
+            let is_lead = builder.or(is_lead, is_lead3);
+            let is_lead = builder.or(is_lead, is_lead4);
+            let is_continuation = builder.within_range(value, lo, hi);
+
+            let remaining_is_zero = builder.is_zero(remaining);
+            let in_sequence = builder.not(remaining_is_zero);
+            let is_valid = builder.select(in_sequence, is_continuation, is_lead);
+            builder.assert_is_equal(is_valid, true_v);
+
+            // The ranges are disjoint, so at most one of the terms is nonzero.
+            let lead3_remaining = builder.mul(is_lead3.variable, two);
+            let lead4_remaining = builder.mul(is_lead4.variable, three);
+            let lead_remaining = builder.add(is_lead2.variable, lead3_remaining);
+            let lead_remaining = builder.add(lead_remaining, lead4_remaining);
+            let one = builder.one::<Variable>();
+            let continuation_remaining = builder.sub(remaining, one);
+            remaining = builder.select(in_sequence, continuation_remaining, lead_remaining);
+
+            // A continuation byte never equals one of these leading bytes, so the ranges are only
+            // narrowed after the corresponding leading byte.
+            let is_e0 = builder.is_equal(value, e0);
+            let is_f0 = builder.is_equal(value, f0);
+            let is_ed = builder.is_equal(value, ed);
+            let is_f4 = builder.is_equal(value, f4);
+            lo = builder.select(is_f0, f0_lo, continuation_lo);
+            lo = builder.select(is_e0, e0_lo, lo);
+            hi = builder.select(is_f4, f4_hi, continuation_hi);
+            hi = builder.select(is_ed, ed_hi, hi);
+        }
+        builder.assert_is_equal(remaining, zero);
+    }
+
+    fn nb_elements() -> usize {
+        BytesVariable::<N>::nb_elements()
+    }
+
+    fn elements<F: RichField>(value: Self::ValueType<F>) -> Vec<F> {
+        assert_eq!(
+            value.len(),
+            N,
+            "string {:?} does not have length {}",
+            value,
+            N
+        );
+        BytesVariable::<N>::elements(value.as_bytes().try_into().unwrap())
+    }
+
+    fn from_elements<F: RichField>(elements: &[F]) -> Self::ValueType<F> {
+        let bytes = BytesVariable::<N>::from_elements(elements);
+        String::from_utf8(bytes.to_vec()).expect("string is not valid utf-8")
+    }
+}
+
+impl<const N: usize> EvmVariable for Utf8StringVariable<N> {
+    fn encode<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> Vec<ByteVariable> {
+        self.0.encode(builder)
+    }
+
+    fn decode<L: PlonkParameters<D>, const D: usize>(
+        builder: &mut CircuitBuilder<L, D>,
+        bytes: &[ByteVariable],
+    ) -> Self {
+        let string = Self(BytesVariable::decode(builder, bytes));
+        string.assert_is_valid(builder);
+        string
+    }
+
+    fn encode_value<F: RichField>(value: Self::ValueType<F>) -> Vec<u8> {
+        assert_eq!(value.len(), N);
+        value.into_bytes()
+    }
+
+    fn decode_value<F: RichField>(bytes: &[u8]) -> Self::ValueType<F> {
+        assert_eq!(bytes.len(), N);
+        String::from_utf8(bytes.to_vec()).expect("string is not valid utf-8")
+    }
+}
+
+impl<const N: usize> Utf8StringVariable<N> {
+    pub fn as_bytes(&self) -> [ByteVariable; N] {
+        self.0 .0
+    }
+
+    /// Returns whether the string starts with `prefix`.
+    pub fn starts_with<L: PlonkParameters<D>, const D: usize, const M: usize>(
+        &self,
+        prefix: &Utf8StringVariable<M>,
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> BoolVariable {
+        assert!(M <= N, "prefix is longer than the string");
+        let mut result = builder._true();
+        for i in 0..M {
+            let eq = builder.is_equal(self.0 .0[i], prefix.0 .0[i]);
+            result = builder.and(result, eq);
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{StringVariable, Utf8StringVariable};
+    use crate::frontend::vars::EvmVariable;
+    use crate::prelude::*;
+
+    type L = DefaultParameters;
+    const D: usize = 2;
+
+    #[test]
+    fn test_string_value() {
+        let value = "USDC".to_string();
+        let encoded = StringVariable::<4>::encode_value::<GoldilocksField>(value.clone());
+        assert_eq!(encoded, b"USDC".to_vec());
+        assert_eq!(
+            StringVariable::<4>::decode_value::<GoldilocksField>(&encoded),
+            value
+        );
+    }
+
+    #[test]
+    fn test_string_operations() {
+        let mut builder = CircuitBuilder::<L, D>::new();
+
+        let symbol = builder.read::<StringVariable<4>>();
+        let usdc = builder.constant::<StringVariable<4>>("USDC".to_string());
+        let usd = builder.constant::<StringVariable<3>>("USD".to_string());
+        let eur = builder.constant::<StringVariable<3>>("EUR".to_string());
+
+        let is_usdc = builder.is_equal(symbol, usdc);
+        let starts_with_usd = symbol.starts_with(&usd, &mut builder);
+        let starts_with_eur = symbol.starts_with(&eur, &mut builder);
+        builder.write(is_usdc);
+        builder.write(starts_with_usd);
+        builder.write(starts_with_eur);
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        input.write::<StringVariable<4>>("USDT".to_string());
+
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        assert!(!output.read::<BoolVariable>());
+        assert!(output.read::<BoolVariable>());
+        assert!(!output.read::<BoolVariable>());
+    }
+
+    #[test]
+    #[should_panic(expected = "was set twice with different values")]
+    fn test_string_rejects_non_printable() {
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let bytes = builder.read::<BytesVariable<2>>();
+        let string = StringVariable::<2>::from_variables(&mut builder, &bytes.variables());
+        builder.write(string);
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        input.write::<BytesVariable<2>>([b'a', 0x07]);
+
+        let (proof, output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+    }
+
+    #[test]
+    fn test_utf8_string() {
+        let mut builder = CircuitBuilder::<L, D>::new();
+
+        let name = builder.read::<Utf8StringVariable<10>>();
+        let prefix = builder.constant::<Utf8StringVariable<3>>("né".to_string());
+        let starts_with_prefix = name.starts_with(&prefix, &mut builder);
+        builder.write(name);
+        builder.write(starts_with_prefix);
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        // One, two, three and four byte characters.
+        input.write::<Utf8StringVariable<10>>("né€😀".to_string());
+
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        assert_eq!(output.read::<Utf8StringVariable<10>>(), "né€😀");
+        assert!(output.read::<BoolVariable>());
+    }
+
+    fn assert_utf8_is_valid(bytes: [u8; 4]) {
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let bytes_v = builder.read::<BytesVariable<4>>();
+        let string = Utf8StringVariable::<4>::from_variables(&mut builder, &bytes_v.variables());
+        builder.write(string.0);
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        input.write::<BytesVariable<4>>(bytes);
+
+        let (proof, output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+    }
+
+    #[test]
+    fn test_utf8_string_accepts_boundaries() {
+        // U+10FFFF, the largest code point.
+        assert_utf8_is_valid([0xf4, 0x8f, 0xbf, 0xbf]);
+        // U+FFFF followed by an ASCII character.
+        assert_utf8_is_valid([0xef, 0xbf, 0xbf, b'a']);
+        // U+0800 followed by an ASCII character.
+        assert_utf8_is_valid([0xe0, 0xa0, 0x80, b'a']);
+    }
+
+    #[test]
+    #[should_panic(expected = "was set twice with different values")]
+    fn test_utf8_string_rejects_overlong() {
+        // An overlong encoding of '/'.
+        assert_utf8_is_valid([b'a', 0xc0, 0xaf, b'a']);
+    }
+
+    #[test]
+    #[should_panic(expected = "was set twice with different values")]
+    fn test_utf8_string_rejects_surrogate() {
+        // U+D800, a surrogate.
+        assert_utf8_is_valid([0xed, 0xa0, 0x80, b'a']);
+    }
+
+    #[test]
+    #[should_panic(expected = "was set twice with different values")]
+    fn test_utf8_string_rejects_truncated() {
+        // A three byte character cut off at the end of the string.
+        assert_utf8_is_valid([b'a', b'b', 0xe2, 0x82]);
+    }
+}
@@ -4,10 +4,11 @@ use plonky2::hash::hash_types::RichField;
 use plonky2::iop::target::Target;
 use serde::{Deserialize, Serialize};
 
-use super::CircuitVariable;
+use super::{BoolVariable, CircuitVariable};
 use crate::backend::circuit::PlonkParameters;
 use crate::frontend::builder::CircuitBuilder;
-use crate::frontend::ops::{Add, Div, Mul, Neg, One, Sub, Zero};
+use crate::frontend::ops::{Add, Div, LessThanOrEqual, Mul, Neg, One, Sub, Zero};
+use crate::prelude::U64Variable;
 
 /// A variable in the circuit. It represents a value between `[0, 2**64 - 2**32 + 1)`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -105,6 +106,16 @@ impl<L: PlonkParameters<D>, const D: usize> Div<L, D> for Variable {
     }
 }
 
+impl<L: PlonkParameters<D>, const D: usize> LessThanOrEqual<L, D> for Variable {
+    /// Compares the canonical representations of the two field elements as integers.
+    #[must_use]
+    fn lte(self, rhs: Self, builder: &mut CircuitBuilder<L, D>) -> BoolVariable {
+        let lhs = U64Variable::from_variable(builder, self);
+        let rhs = U64Variable::from_variable(builder, rhs);
+        builder.lte(lhs, rhs)
+    }
+}
+
 impl<L: PlonkParameters<D>, const D: usize> Zero<L, D> for Variable {
     fn zero(builder: &mut CircuitBuilder<L, D>) -> Self {
         Variable(builder.api.zero())
@@ -0,0 +1,227 @@
+use core::fmt::Debug;
+
+use plonky2::field::types::Field;
+use plonky2::hash::hash_types::RichField;
+
+use super::{
+    ArrayVariable, BoolVariable, ByteVariable, Bytes32Variable, CircuitVariable, EvmVariable,
+    U32Variable, Variable,
+};
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::builder::CircuitBuilder;
+
+/// A variable in the circuit representing a byte string of dynamic length, up to `MAX` bytes.
+///
+/// The bytes are stored in a fixed-size array of `MAX` bytes, and only the first `length` bytes
+/// are considered part of the value. The remaining bytes are padding and are ignored by equality
+/// and hashing.
+#[derive(Debug, Clone)]
+pub struct VariableBytesVariable<const MAX: usize> {
+    pub data: ArrayVariable<ByteVariable, MAX>,
+    pub length: U32Variable,
+}
+
+impl<const MAX: usize> CircuitVariable for VariableBytesVariable<MAX> {
+    type ValueType<F: RichField> = Vec<u8>;
+
+    fn init_unsafe<L: PlonkParameters<D>, const D: usize>(
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> Self {
+        Self {
+            data: ArrayVariable::init_unsafe(builder),
+            length: U32Variable::init_unsafe(builder),
+        }
+    }
+
+    fn variables(&self) -> Vec<Variable> {
+        let mut variables = self.data.variables();
+        variables.extend(self.length.variables());
+        variables
+    }
+
+    fn from_variables_unsafe(variables: &[Variable]) -> Self {
+        assert_eq!(variables.len(), Self::nb_elements());
+        let split = ArrayVariable::<ByteVariable, MAX>::nb_elements();
+        Self {
+            data: ArrayVariable::from_variables_unsafe(&variables[..split]),
+            length: U32Variable::from_variables_unsafe(&variables[split..]),
+        }
+    }
+
+    fn assert_is_valid<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        builder: &mut CircuitBuilder<L, D>,
+    ) {
+        self.data.assert_is_valid(builder);
+        self.length.assert_is_valid(builder);
+
+        let max = builder.constant::<U32Variable>(MAX as u32);
+        let is_length_valid = builder.lte(self.length, max);
+        let true_v = builder._true();
+        builder.assert_is_equal(is_length_valid, true_v);
+    }
+
+    fn nb_elements() -> usize {
+        ArrayVariable::<ByteVariable, MAX>::nb_elements() + U32Variable::nb_elements()
+    }
+
+    fn elements<F: RichField>(value: Self::ValueType<F>) -> Vec<F> {
+        assert!(
+            value.len() <= MAX,
+            "value has length {} which exceeds the maximum length {}",
+            value.len(),
+            MAX
+        );
+        let length = value.len() as u32;
+        let mut padded = value;
+        padded.resize(MAX, 0);
+        let mut elements = ArrayVariable::<ByteVariable, MAX>::elements::<F>(padded);
+        elements.extend(U32Variable::elements::<F>(length));
+        elements
+    }
+
+    fn from_elements<F: RichField>(elements: &[F]) -> Self::ValueType<F> {
+        assert_eq!(elements.len(), Self::nb_elements());
+        let split = ArrayVariable::<ByteVariable, MAX>::nb_elements();
+        let mut data = ArrayVariable::<ByteVariable, MAX>::from_elements::<F>(&elements[..split]);
+        let length = U32Variable::from_elements::<F>(&elements[split..]);
+        data.truncate(length as usize);
+        data
+    }
+}
+
+impl<const MAX: usize> EvmVariable for VariableBytesVariable<MAX> {
+    /// Encodes the variable as the big-endian u32 length followed by the `MAX` padded bytes.
+    fn encode<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> Vec<ByteVariable> {
+        let mut bytes = self.length.encode(builder);
+        bytes.extend(self.data.as_slice());
+        bytes
+    }
+
+    fn decode<L: PlonkParameters<D>, const D: usize>(
+        builder: &mut CircuitBuilder<L, D>,
+        bytes: &[ByteVariable],
+    ) -> Self {
+        assert_eq!(bytes.len(), 4 + MAX);
+        Self {
+            length: U32Variable::decode(builder, &bytes[..4]),
+            data: ArrayVariable::new(bytes[4..].to_vec()),
+        }
+    }
+
+    fn encode_value<F: RichField>(value: Self::ValueType<F>) -> Vec<u8> {
+        assert!(value.len() <= MAX);
+        let mut bytes = (value.len() as u32).to_be_bytes().to_vec();
+        bytes.extend(value);
+        bytes.resize(4 + MAX, 0);
+        bytes
+    }
+
+    fn decode_value<F: RichField>(bytes: &[u8]) -> Self::ValueType<F> {
+        assert_eq!(bytes.len(), 4 + MAX);
+        let length = u32::from_be_bytes(bytes[..4].try_into().unwrap()) as usize;
+        assert!(length <= MAX);
+        bytes[4..4 + length].to_vec()
+    }
+}
+
+impl<const MAX: usize> VariableBytesVariable<MAX> {
+    /// Returns, for every index `i < MAX`, whether `i < length`.
+    pub fn mask<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> Vec<BoolVariable> {
+        let mut in_range = builder._true();
+        let mut mask = Vec::with_capacity(MAX);
+        for i in 0..MAX {
+            let index = builder.constant::<Variable>(L::Field::from_canonical_usize(i));
+            let at_length = builder.is_equal(index, self.length.variable);
+            let not_at_length = builder.not(at_length);
+            in_range = builder.and(in_range, not_at_length);
+            mask.push(in_range);
+        }
+        mask
+    }
+
+    /// Returns whether two variable-length byte strings have the same length and agree on every
+    /// byte up to that length. Padding bytes beyond the length are ignored.
+    pub fn is_equal<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        other: &Self,
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> BoolVariable {
+        let mut result = builder.is_equal(self.length, other.length);
+        let mask = self.mask(builder);
+        for i in 0..MAX {
+            let byte_eq = builder.is_equal(self.data[i], other.data[i]);
+            let out_of_range = builder.not(mask[i]);
+            let byte_ok = builder.or(byte_eq, out_of_range);
+            result = builder.and(result, byte_ok);
+        }
+        result
+    }
+
+    /// Computes the SHA-256 digest of the first `length` bytes.
+    pub fn sha256<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> Bytes32Variable {
+        builder.curta_sha256_variable(self.data.as_slice(), self.length)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::VariableBytesVariable;
+    use crate::frontend::vars::EvmVariable;
+    use crate::prelude::*;
+
+    type L = DefaultParameters;
+    const D: usize = 2;
+
+    #[test]
+    fn test_variable_bytes_evm_value() {
+        let value = vec![1u8, 2, 3, 4, 5];
+        let encoded = VariableBytesVariable::<8>::encode_value::<GoldilocksField>(value.clone());
+        assert_eq!(encoded, vec![0, 0, 0, 5, 1, 2, 3, 4, 5, 0, 0, 0]);
+        let decoded = VariableBytesVariable::<8>::decode_value::<GoldilocksField>(&encoded);
+        assert_eq!(decoded, value);
+    }
+
+    #[test]
+    fn test_variable_bytes_is_equal() {
+        let mut builder = CircuitBuilder::<L, D>::new();
+
+        let a = builder.read::<VariableBytesVariable<8>>();
+        let b = builder.read::<VariableBytesVariable<8>>();
+        let c = builder.read::<VariableBytesVariable<8>>();
+
+        // `a` and `b` hold the same prefix but different padding bytes.
+        let mut b_data = b.data.as_vec();
+        b_data[7] = builder.constant::<ByteVariable>(0xff);
+        let b = VariableBytesVariable::<8> {
+            data: ArrayVariable::new(b_data),
+            length: b.length,
+        };
+
+        let a_eq_b = a.is_equal(&b, &mut builder);
+        let a_eq_c = a.is_equal(&c, &mut builder);
+        builder.write(a_eq_b);
+        builder.write(a_eq_c);
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        input.write::<VariableBytesVariable<8>>(vec![1, 2, 3]);
+        input.write::<VariableBytesVariable<8>>(vec![1, 2, 3]);
+        input.write::<VariableBytesVariable<8>>(vec![1, 2, 3, 0]);
+
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        assert!(output.read::<BoolVariable>());
+        assert!(!output.read::<BoolVariable>());
+    }
+}
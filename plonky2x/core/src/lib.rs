@@ -28,9 +28,11 @@ pub mod prelude {
     pub use crate::backend::circuit::{GateRegistry, HintRegistry};
     pub use crate::frontend::builder::{CircuitBuilder, DefaultBuilder};
     pub use crate::frontend::ops::*;
+    pub use crate::frontend::uint::int64::I64Variable;
     pub use crate::frontend::uint::uint128::U128Variable;
     pub use crate::frontend::uint::uint256::U256Variable;
     pub use crate::frontend::uint::uint64::U64Variable;
+    pub use crate::frontend::uint::uint8::U8Variable;
     pub use crate::frontend::vars::{
         ArrayVariable, BoolVariable, ByteVariable, Bytes32Variable, BytesVariable, CircuitVariable,
         OutputVariableStream, U32Variable, ValueStream, Variable, VariableStream,
@@ -527,6 +527,40 @@ impl BeaconClient {
         Ok(response.result)
     }
 
+    /// An asynchronous version of `get_validator`.
+    pub async fn get_validator_async(
+        &self,
+        beacon_id: String,
+        validator_idx: u64,
+    ) -> Result<GetBeaconValidator> {
+        let endpoint = format!(
+            "{}/api/beacon/proof/validator/{}/{}",
+            self.rpc_url, beacon_id, validator_idx
+        );
+        debug!("{}", endpoint);
+        let response = self.client.fetch_async(&endpoint).await?;
+        let response: CustomResponse<GetBeaconValidator> = response.json().await?;
+        assert!(response.success);
+        Ok(response.result)
+    }
+
+    /// An asynchronous version of `get_validator_by_pubkey`.
+    pub async fn get_validator_by_pubkey_async(
+        &self,
+        beacon_id: String,
+        pubkey: String,
+    ) -> Result<GetBeaconValidator> {
+        let endpoint = format!(
+            "{}/api/beacon/proof/validator/{}/{}",
+            self.rpc_url, beacon_id, pubkey
+        );
+        info!("{}", endpoint);
+        let response = self.client.fetch_async(&endpoint).await?;
+        let response: CustomResponse<GetBeaconValidator> = response.json().await?;
+        assert!(response.success);
+        Ok(response.result)
+    }
+
     /// Gets the balances root based on a beacon_id.
     pub fn get_balances_root(&self, beacon_id: String) -> Result<GetBeaconBalancesRoot> {
         let endpoint = format!("{}/api/beacon/proof/balance/{}", self.rpc_url, beacon_id);
@@ -617,6 +651,38 @@ impl BeaconClient {
         Ok(response.result)
     }
 
+    /// An asynchronous version of `get_validator_balance_v2`.
+    pub async fn get_validator_balance_v2_async(
+        &self,
+        beacon_id: String,
+        validator_idx: u64,
+    ) -> Result<GetBeaconBalance> {
+        let endpoint = format!(
+            "{}/api/beacon/proof/balance/{}/{}",
+            self.rpc_url, beacon_id, validator_idx
+        );
+        let response = self.client.fetch_async(&endpoint).await?;
+        let response: CustomResponse<GetBeaconBalance> = response.json().await?;
+        assert!(response.success);
+        Ok(response.result)
+    }
+
+    /// An asynchronous version of `get_validator_balance_by_pubkey_v2`.
+    pub async fn get_validator_balance_by_pubkey_v2_async(
+        &self,
+        beacon_id: String,
+        pubkey: String,
+    ) -> Result<GetBeaconBalance> {
+        let endpoint = format!(
+            "{}/api/beacon/proof/balance/{}/{}",
+            self.rpc_url, beacon_id, pubkey
+        );
+        let response = self.client.fetch_async(&endpoint).await?;
+        let response: CustomResponse<GetBeaconBalance> = response.json().await?;
+        assert!(response.success);
+        Ok(response.result)
+    }
+
     /// Gets the balance of a validator based on a beacon_id and validator index.
     #[allow(unused)]
     fn get_validator_balance_deprecated(
@@ -1,10 +1,8 @@
 use itertools::Itertools;
 use num::BigInt;
-use plonky2::field::extension::Extendable;
 use plonky2::hash::hash_types::RichField;
 use plonky2::plonk::circuit_data::VerifierCircuitTarget;
-use plonky2::plonk::config::GenericConfig;
-use plonky2::plonk::proof::{ProofWithPublicInputs, ProofWithPublicInputsTarget};
+use plonky2::plonk::proof::ProofWithPublicInputsTarget;
 use plonky2::util::serialization::{Buffer, IoResult, Read, Write};
 use serde::ser::SerializeSeq;
 use serde::Deserialize;
@@ -79,82 +77,6 @@ where
         .collect_vec())
 }
 
-pub fn serialize_proof_with_pis<
-    F: RichField + Extendable<E>,
-    C: GenericConfig<E, F = F>,
-    S,
-    const E: usize,
->(
-    proof_with_pis: &ProofWithPublicInputs<F, C, E>,
-    serializer: S,
-) -> Result<S::Ok, S::Error>
-where
-    S: serde::Serializer,
-{
-    let bytes = format!(
-        "0x{}",
-        hex::encode(bincode::serialize(proof_with_pis).unwrap())
-    );
-    serializer.serialize_str(&bytes)
-}
-
-pub fn deserialize_proof_with_pis<
-    'de,
-    F: RichField + Extendable<E>,
-    C: GenericConfig<E, F = F>,
-    D,
-    const E: usize,
->(
-    deserializer: D,
-) -> Result<ProofWithPublicInputs<F, C, E>, D::Error>
-where
-    D: serde::Deserializer<'de>,
-{
-    let s: String = Deserialize::deserialize(deserializer)?;
-    Ok(bincode::deserialize(&hex::decode(&s[2..]).unwrap()).unwrap())
-}
-
-pub fn serialize_proof_with_pis_vec<
-    F: RichField + Extendable<E>,
-    C: GenericConfig<E, F = F>,
-    S,
-    const E: usize,
->(
-    proof_with_pis_vec: &[ProofWithPublicInputs<F, C, E>],
-    serializer: S,
-) -> Result<S::Ok, S::Error>
-where
-    S: serde::Serializer,
-{
-    let serialized_proofs: Vec<String> = proof_with_pis_vec
-        .iter()
-        .map(|proof| format!("0x{}", hex::encode(bincode::serialize(proof).unwrap())))
-        .collect();
-    let mut seq = serializer.serialize_seq(Some(serialized_proofs.len()))?;
-    for proof in serialized_proofs {
-        seq.serialize_element(&proof)?;
-    }
-    seq.end()
-}
-
-pub fn deserialize_proof_with_pis_vec<
-    'de,
-    F: RichField + Extendable<E>,
-    C: GenericConfig<E, F = F>,
-    D,
-    const E: usize,
->(
-    deserializer: D,
-) -> Result<Vec<ProofWithPublicInputs<F, C, E>>, D::Error>
-where
-    D: serde::Deserializer<'de>,
-{
-    let s: Vec<String> = Vec::deserialize(deserializer)?;
-    Ok(s.into_iter()
-        .map(|q| bincode::deserialize(&hex::decode(&q[2..]).unwrap()).unwrap())
-        .collect_vec())
-}
-
 pub fn serialize_proof_with_pis_target<S, const D: usize>(
     proof_with_pis: &ProofWithPublicInputsTarget<D>,
     serializer: S,
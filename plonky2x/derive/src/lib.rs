@@ -72,7 +72,7 @@ pub fn derive_circuit_variable(input: proc_macro::TokenStream) -> proc_macro::To
     let (_, value_ty_generics, _) = value_generics.split_for_impl();
 
     let init_unsafe_expanded = init_unsafe(&data);
-    let _constant_expanded = constant(&data);
+    let constant_expanded = constant(&data);
     let variables_expanded = variables(&data);
     let from_variables_unsafe_expanded = from_variables_unsafe(&data);
     let assert_is_valid_expanded = assert_is_valid(&data);
@@ -94,6 +94,13 @@ pub fn derive_circuit_variable(input: proc_macro::TokenStream) -> proc_macro::To
                 #init_unsafe_expanded
             }
 
+            fn constant<L: PlonkParameters<D>, const D: usize>(
+                builder: &mut CircuitBuilder<L, D>,
+                value: Self::ValueType<L::Field>,
+            ) -> Self {
+                #constant_expanded
+            }
+
             fn variables(&self) -> Vec<Variable> {
                 #variables_expanded
             }